@@ -0,0 +1,162 @@
+use loom_core::value::{Object, Value};
+use loom_signal::{Signal, Type};
+use serde::Serialize;
+use storage::entity::{Action, Target, Trace, TraceAction};
+
+use crate::RequestContext;
+
+/// Snapshot fields left out of audit diffs: embeddings are large, derived
+/// from the memory text and would leak it into the audit trail
+const UNAUDITED: &[&str] = &["embedding"];
+
+impl RequestContext {
+    /// Record a write operation as a trace action row and an `audit` signal.
+    ///
+    /// `before` is `None` for creates and `after` is `None` for deletes; the
+    /// stored diff lists the changed paths between the two snapshots.
+    pub async fn audit<T: Serialize>(
+        &self,
+        target: Target,
+        target_id: uuid::Uuid,
+        action: Action,
+        before: Option<&T>,
+        after: Option<&T>,
+    ) -> Result<TraceAction, sqlx::Error> {
+        let diff = diff_of(before, after)?;
+        let trace = self.trace().await?;
+        let mut builder = TraceAction::builder(trace.id, target_id, target, action).diff(diff);
+
+        if let Some(actor) = self.actor() {
+            builder = builder.actor(actor);
+        }
+
+        let trace_action = self
+            .storage()
+            .trace_actions
+            .create(&builder.build())
+            .await?;
+        let changes = trace_action
+            .diff
+            .as_ref()
+            .and_then(|d| d.as_array())
+            .map(|d| d.len())
+            .unwrap_or_default();
+
+        self.emit(
+            Signal::new()
+                .otype(Type::Event)
+                .name(format!("audit.{}.{}", target, action))
+                .attr("request_id", self.request_id())
+                .attr("trace_id", trace.id.to_string())
+                .attr("actor", self.actor().unwrap_or_default())
+                .attr("tenant", self.tenant())
+                .attr("target", target.as_str())
+                .attr("target_id", target_id.to_string())
+                .attr("action", action.as_str())
+                .attr("changes", changes)
+                .attr(
+                    "diff",
+                    Value::from(trace_action.diff.clone().unwrap_or_default()),
+                )
+                .build(),
+        );
+
+        Ok(trace_action)
+    }
+
+    /// Get the root trace for this request, creating it on first use
+    async fn trace(&self) -> Result<Trace, sqlx::Error> {
+        let storage = self.storage();
+        let existing = storage
+            .traces
+            .get_by_request_id(self.request_id())
+            .await?
+            .into_iter()
+            .find(|t| t.parent_id.is_none());
+
+        match existing {
+            Some(trace) => Ok(trace),
+            None => {
+                let trace = Trace::builder().request_id(self.request_id()).build();
+                storage.traces.create(&trace).await
+            }
+        }
+    }
+}
+
+/// Diff two snapshots into a JSON array of `{ path, kind, before, after }` entries.
+/// A missing snapshot is treated as an empty object, and [`UNAUDITED`] fields
+/// are ignored.
+fn diff_of<T: Serialize>(
+    before: Option<&T>,
+    after: Option<&T>,
+) -> Result<serde_json::Value, sqlx::Error> {
+    let before = to_value(before)?;
+    let after = to_value(after)?;
+    let changes = before
+        .diff(&after)
+        .into_iter()
+        .map(|change| {
+            serde_json::json!({
+                "path": change.path,
+                "kind": change.kind(),
+                "before": change.before.map(serde_json::Value::from),
+                "after": change.after.map(serde_json::Value::from),
+            })
+        })
+        .collect();
+
+    Ok(serde_json::Value::Array(changes))
+}
+
+fn to_value<T: Serialize>(snapshot: Option<&T>) -> Result<Value, sqlx::Error> {
+    match snapshot {
+        None => Ok(Value::Object(Object::new())),
+        Some(v) => {
+            let mut value =
+                serde_json::to_value(v).map_err(|e| sqlx::Error::Encode(Box::new(e)))?;
+
+            if let Some(fields) = value.as_object_mut() {
+                for field in UNAUDITED {
+                    fields.remove(*field);
+                }
+            }
+
+            Ok(Value::from(value))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use storage::entity::Memory;
+
+    use super::*;
+
+    #[test]
+    fn diff_leaves_out_embeddings() {
+        let before = Memory::builder(uuid::Uuid::new_v4()).build();
+        let mut after = before.clone();
+        after.embedding = Some(vec![0.1, 0.2, 0.3]);
+        after.score = 0.9;
+
+        for diff in [
+            diff_of(None, Some(&after)).unwrap(),
+            diff_of(Some(&before), Some(&after)).unwrap(),
+            diff_of(Some(&after), None).unwrap(),
+        ] {
+            let paths: Vec<&str> = diff
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|change| change["path"].as_str().unwrap())
+                .collect();
+
+            assert!(paths.contains(&"score"), "{paths:?}");
+            assert!(
+                !paths.iter().any(|p| p.starts_with("embedding")),
+                "{paths:?}"
+            );
+        }
+    }
+}
@@ -0,0 +1,110 @@
+use std::collections::HashMap;
+
+use actix_web::http::header::{AUTHORIZATION, HeaderMap};
+
+/// Who a request is made by, as vouched for by its API key
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Identity {
+    pub tenant: String,
+    pub actor: String,
+}
+
+/// Bearer tokens accepted by the api, each bound to one [`Identity`]
+#[derive(Debug, Clone, Default)]
+pub struct ApiKeys {
+    keys: HashMap<String, Identity>,
+}
+
+impl ApiKeys {
+    /// Parse a comma separated list of `<tenant>:<actor>:<token>` entries
+    pub fn parse(value: &str) -> Result<Self, String> {
+        let mut keys = HashMap::new();
+
+        for entry in value.split(',').map(str::trim).filter(|v| !v.is_empty()) {
+            let mut parts = entry.splitn(3, ':').map(str::trim);
+            let (Some(tenant), Some(actor), Some(token)) =
+                (parts.next(), parts.next(), parts.next())
+            else {
+                return Err(format!(
+                    "expected `<tenant>:<actor>:<token>`, found '{}'",
+                    entry
+                ));
+            };
+
+            if tenant.is_empty() || actor.is_empty() || token.is_empty() {
+                return Err(format!("empty tenant, actor or token in '{}'", entry));
+            }
+
+            let identity = Identity {
+                tenant: tenant.to_string(),
+                actor: actor.to_string(),
+            };
+
+            if keys.insert(token.to_string(), identity).is_some() {
+                return Err(format!("duplicate token for tenant '{}'", tenant));
+            }
+        }
+
+        if keys.is_empty() {
+            return Err("no api keys configured".to_string());
+        }
+
+        Ok(Self { keys })
+    }
+
+    /// Identity of the `Authorization: Bearer <token>` header, if the token is known
+    pub fn identify(&self, headers: &HeaderMap) -> Option<&Identity> {
+        let token = headers
+            .get(AUTHORIZATION)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.strip_prefix("Bearer "))
+            .map(str::trim)?;
+
+        self.keys.get(token)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::http::header::HeaderValue;
+
+    use super::*;
+
+    fn bearer(token: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            AUTHORIZATION,
+            HeaderValue::from_str(&format!("Bearer {}", token)).unwrap(),
+        );
+        headers
+    }
+
+    #[test]
+    fn identifies_known_tokens() {
+        let keys = ApiKeys::parse("acme:alice:s3cr3t, globex:bob:a:b").unwrap();
+
+        assert_eq!(
+            keys.identify(&bearer("s3cr3t")),
+            Some(&Identity {
+                tenant: "acme".to_string(),
+                actor: "alice".to_string(),
+            })
+        );
+        assert_eq!(keys.identify(&bearer("a:b")).unwrap().tenant, "globex");
+        assert_eq!(keys.identify(&bearer("unknown")), None);
+        assert_eq!(keys.identify(&HeaderMap::new()), None);
+    }
+
+    #[test]
+    fn rejects_invalid_entries() {
+        for value in [
+            "",
+            "acme:alice",
+            "acme::token",
+            ":alice:token",
+            "a:b:t,c:d:t",
+        ] {
+            assert!(ApiKeys::parse(value).is_err(), "{value}");
+        }
+    }
+}
@@ -1,38 +1,118 @@
+use std::sync::{Arc, Mutex};
+
 use chrono::{DateTime, Utc};
 use sqlx::PgPool;
 
 use events::Socket;
-use storage::Storage;
+use loom_runtime::retrieve::RetrieveLayer;
+use loom_signal::{Emitter, Signal};
+use storage::{Cipher, Storage};
+
+use crate::auth::ApiKeys;
 
 #[derive(Clone)]
 pub struct Context {
     pool: PgPool,
-    amqp: Socket,
+    amqp: Option<Socket>,
+    signals: Arc<dyn Emitter + Send + Sync>,
+    retriever: Option<Arc<Mutex<RetrieveLayer>>>,
+    cipher: Option<Arc<Cipher>>,
+    api_keys: Option<Arc<ApiKeys>>,
     start_time: DateTime<Utc>,
 }
 
 impl Context {
-    pub fn new(pool: PgPool, amqp: Socket) -> Self {
+    pub fn new(pool: PgPool, signals: Arc<dyn Emitter + Send + Sync>) -> Self {
         Self {
             pool,
-            amqp,
+            amqp: None,
+            signals,
+            retriever: None,
+            cipher: None,
+            api_keys: None,
             start_time: Utc::now(),
         }
     }
 
+    pub fn with_amqp(mut self, amqp: Socket) -> Self {
+        self.amqp = Some(amqp);
+        self
+    }
+
+    /// Embed `/memories/relevant` queries with `retriever`
+    pub fn with_retriever(mut self, retriever: RetrieveLayer) -> Self {
+        self.retriever = Some(Arc::new(Mutex::new(retriever)));
+        self
+    }
+
+    /// Encrypt memory text at rest with `cipher`
+    pub fn with_cipher(mut self, cipher: Option<Cipher>) -> Self {
+        self.cipher = cipher.map(Arc::new);
+        self
+    }
+
+    /// Authenticate requests with `api_keys` instead of trusting their headers
+    pub fn with_api_keys(mut self, api_keys: Option<ApiKeys>) -> Self {
+        self.api_keys = api_keys.map(Arc::new);
+        self
+    }
+
     pub fn start_time(&self) -> DateTime<Utc> {
         self.start_time
     }
 
-    pub fn storage(&self) -> Storage<'_> {
-        Storage::new(&self.pool)
+    /// Storage scoped to `tenant`
+    pub fn storage<'a>(&'a self, tenant: &'a str) -> Storage<'a> {
+        Storage::new(&self.pool, tenant).with_cipher(self.cipher.as_deref())
     }
 
     pub fn pool(&self) -> &PgPool {
         &self.pool
     }
 
-    pub fn amqp(&self) -> &Socket {
-        &self.amqp
+    pub fn api_keys(&self) -> Option<&ApiKeys> {
+        self.api_keys.as_deref()
+    }
+
+    pub fn amqp(&self) -> Option<&Socket> {
+        self.amqp.as_ref()
+    }
+
+    /// Shared handle to the query embedding layer, if one is loaded
+    pub fn retriever(&self) -> Option<Arc<Mutex<RetrieveLayer>>> {
+        self.retriever.clone()
+    }
+
+    /// Emit a signal through the api's emitter
+    pub fn emit(&self, signal: Signal) {
+        self.signals.emit(signal);
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod testing {
+    use std::sync::Arc;
+
+    use loom_signal::consumers::MemoryEmitter;
+    use sqlx::postgres::PgPoolOptions;
+
+    use super::Context;
+
+    /// Context on the migrated database at `DATABASE_URL`, for route tests
+    /// run with `cargo test -p api -- --ignored`
+    pub(crate) async fn context() -> Context {
+        let url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        let pool = PgPoolOptions::new()
+            .max_connections(2)
+            .connect(&url)
+            .await
+            .expect("Failed to create pool");
+
+        sqlx::migrate!("../../crates/storage/migrations")
+            .run(&pool)
+            .await
+            .expect("Failed to run migrations");
+
+        Context::new(pool, Arc::new(MemoryEmitter::new()))
     }
 }
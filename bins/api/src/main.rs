@@ -1,7 +1,15 @@
+use std::sync::Arc;
+
 use actix_web::{App, HttpServer, web};
 use events::{Key, MemoryAction};
+use futures::FutureExt;
+use loom_runtime::Runtime;
+use loom_runtime::retrieve::RetrieveConfig;
+use loom_signal::consumers::StdoutEmitter;
 use sqlx::postgres::PgPoolOptions;
 
+mod audit;
+mod auth;
 mod config;
 mod context;
 mod request_context;
@@ -33,7 +41,44 @@ async fn main() -> std::io::Result<()> {
         .await
         .expect("error while connecting to rabbitmq");
 
-    let ctx = Context::new(pool, amqp);
+    let retriever = RetrieveConfig::default()
+        .build()
+        .expect("Failed to load retrieval model");
+
+    let runtime = Runtime::new()
+        .on_shutdown("amqp", {
+            let amqp = amqp.clone();
+            move |_| async move { amqp.close().await }.boxed()
+        })
+        .on_shutdown("pool", {
+            let pool = pool.clone();
+            move |_| {
+                async move {
+                    pool.close().await;
+                    Ok(())
+                }
+                .boxed()
+            }
+        });
+
+    #[cfg(feature = "arrow")]
+    let runtime = runtime.codec(loom_runtime::ArrowCodec::new());
+
+    #[cfg(feature = "parquet")]
+    let runtime = runtime.codec(loom_runtime::ParquetCodec::new());
+
+    let runtime = runtime.build();
+    runtime
+        .health()
+        .await
+        .expect("DataSource health check failed");
+
+    let signals = Arc::new(StdoutEmitter::new().json());
+    let ctx = Context::new(pool, signals)
+        .with_amqp(amqp)
+        .with_retriever(retriever)
+        .with_cipher(config.cipher())
+        .with_api_keys(config.api_keys());
     println!("Starting server at http://0.0.0.0:{}", config.port);
 
     HttpServer::new(move || {
@@ -41,9 +86,24 @@ async fn main() -> std::io::Result<()> {
             .app_data(web::Data::new(ctx.clone()))
             .wrap(RequestContextMiddleware)
             .service(routes::index)
+            .service(routes::audit)
             .service(routes::ingest)
+            .service(routes::relevant)
+            .service(routes::timeline)
+            .service(routes::create_memory)
+            .service(routes::update_memory)
+            .service(routes::delete_memory)
+            .service(routes::create_facet)
+            .service(routes::update_facet)
+            .service(routes::delete_facet)
     })
     .bind(("0.0.0.0", config.port))?
     .run()
-    .await
+    .await?;
+
+    if let Err(e) = runtime.shutdown().await {
+        eprintln!("Error shutting down runtime: {}", e);
+    }
+
+    Ok(())
 }
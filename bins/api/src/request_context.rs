@@ -2,26 +2,41 @@ use std::future::{Ready, ready};
 use std::sync::Arc;
 
 use actix_web::dev::{Service, ServiceRequest, ServiceResponse, Transform, forward_ready};
+use actix_web::error::{ErrorBadRequest, ErrorUnauthorized};
 use actix_web::http::header::HeaderMap;
 use actix_web::{Error, FromRequest, HttpMessage, HttpRequest, web};
 
+use storage::Storage;
+
 use crate::Context;
 
 const REQUEST_ID_HEADER: &str = "X-Request-ID";
+const ACTOR_HEADER: &str = "X-Actor-ID";
+const TENANT_HEADER: &str = "X-Tenant-ID";
 
 #[derive(Clone)]
 pub struct RequestContext {
     ctx: Arc<Context>,
     headers: HeaderMap,
     request_id: String,
+    actor: Option<String>,
+    tenant: String,
 }
 
 impl RequestContext {
-    pub fn new(ctx: Arc<Context>, headers: HeaderMap, request_id: String) -> Self {
+    pub fn new(
+        ctx: Arc<Context>,
+        headers: HeaderMap,
+        request_id: String,
+        actor: Option<String>,
+        tenant: String,
+    ) -> Self {
         Self {
             ctx,
             headers,
             request_id,
+            actor,
+            tenant,
         }
     }
 
@@ -36,6 +51,23 @@ impl RequestContext {
     pub fn request_id(&self) -> &str {
         &self.request_id
     }
+
+    /// Who made the request: the actor of its API key, or the `X-Actor-ID`
+    /// header when no keys are configured
+    pub fn actor(&self) -> Option<&str> {
+        self.actor.as_deref()
+    }
+
+    /// Tenant the request is scoped to: the tenant of its API key, or the
+    /// `X-Tenant-ID` header when no keys are configured
+    pub fn tenant(&self) -> &str {
+        &self.tenant
+    }
+
+    /// Storage scoped to the request's tenant
+    pub fn storage(&self) -> Storage<'_> {
+        self.ctx.storage(&self.tenant)
+    }
 }
 
 impl FromRequest for RequestContext {
@@ -43,8 +75,13 @@ impl FromRequest for RequestContext {
     type Future = Ready<Result<Self, Self::Error>>;
 
     fn from_request(req: &HttpRequest, _payload: &mut actix_web::dev::Payload) -> Self::Future {
-        let ctx = req
-            .extensions()
+        let extensions = req.extensions();
+
+        if let Some(rejection) = extensions.get::<Rejection>() {
+            return ready(Err(rejection.error()));
+        }
+
+        let ctx = extensions
             .get::<RequestContext>()
             .cloned()
             .expect("RequestContext not found in request extensions");
@@ -110,9 +147,139 @@ where
             .map(String::from)
             .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
 
-        let ctx = RequestContext::new(ctx, headers, request_id);
+        match identify(&ctx, &headers) {
+            Ok((tenant, actor)) => {
+                let ctx = RequestContext::new(ctx, headers, request_id, actor, tenant);
+                req.extensions_mut().insert(ctx);
+            }
+            Err(rejection) => {
+                req.extensions_mut().insert(rejection);
+            }
+        }
 
-        req.extensions_mut().insert(ctx);
         self.service.call(req)
     }
 }
+
+/// Why a request can't be given a [`RequestContext`], reported by routes that
+/// extract one
+#[derive(Debug, Clone, Copy)]
+enum Rejection {
+    MissingTenant,
+    Unauthorized,
+}
+
+impl Rejection {
+    fn error(self) -> Error {
+        match self {
+            Self::MissingTenant => ErrorBadRequest("missing `X-Tenant-ID` header"),
+            Self::Unauthorized => ErrorUnauthorized("missing or unknown bearer token"),
+        }
+    }
+}
+
+/// Tenant and actor of a request, from its API key when keys are configured
+/// and from its headers otherwise
+fn identify(ctx: &Context, headers: &HeaderMap) -> Result<(String, Option<String>), Rejection> {
+    let Some(keys) = ctx.api_keys() else {
+        let tenant = header(headers, TENANT_HEADER).ok_or(Rejection::MissingTenant)?;
+        return Ok((tenant, header(headers, ACTOR_HEADER)));
+    };
+
+    let identity = keys.identify(headers).ok_or(Rejection::Unauthorized)?;
+    Ok((identity.tenant.clone(), Some(identity.actor.clone())))
+}
+
+/// Non-blank value of the header `name`
+fn header(headers: &HeaderMap, name: &str) -> Option<String> {
+    headers
+        .get(name)
+        .and_then(|v| v.to_str().ok())
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(String::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::{App, HttpResponse, get, http::StatusCode, test};
+    use loom_signal::consumers::MemoryEmitter;
+    use sqlx::postgres::PgPoolOptions;
+
+    use super::*;
+    use crate::auth::ApiKeys;
+
+    #[get("/whoami")]
+    async fn whoami(ctx: RequestContext) -> HttpResponse {
+        HttpResponse::Ok().body(format!("{}/{}", ctx.tenant(), ctx.actor().unwrap_or("-")))
+    }
+
+    fn context(api_keys: Option<&str>) -> Context {
+        let pool = PgPoolOptions::new()
+            .connect_lazy("postgres://localhost/unused")
+            .unwrap();
+
+        Context::new(pool, Arc::new(MemoryEmitter::new()))
+            .with_api_keys(api_keys.map(|keys| ApiKeys::parse(keys).unwrap()))
+    }
+
+    async fn call(ctx: Context, headers: &[(&str, &str)]) -> (StatusCode, String) {
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(ctx))
+                .wrap(RequestContextMiddleware)
+                .service(whoami),
+        )
+        .await;
+
+        let mut req = test::TestRequest::get().uri("/whoami");
+        for header in headers {
+            req = req.insert_header(*header);
+        }
+
+        let res = test::call_service(&app, req.to_request()).await;
+        let status = res.status();
+        let body = test::read_body(res).await;
+        (status, String::from_utf8(body.to_vec()).unwrap())
+    }
+
+    #[actix_web::test]
+    async fn rejects_missing_tenant_header() {
+        let (status, _) = call(context(None), &[]).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let (status, _) = call(context(None), &[(TENANT_HEADER, " ")]).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let (status, body) = call(
+            context(None),
+            &[(TENANT_HEADER, "acme"), (ACTOR_HEADER, "alice")],
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, "acme/alice");
+    }
+
+    #[actix_web::test]
+    async fn api_keys_decide_tenant_and_actor() {
+        let keys = Some("acme:alice:s3cr3t");
+
+        let (status, _) = call(context(keys), &[(TENANT_HEADER, "acme")]).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+        let (status, _) = call(context(keys), &[("Authorization", "Bearer wrong")]).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+        let (status, body) = call(
+            context(keys),
+            &[
+                ("Authorization", "Bearer s3cr3t"),
+                (TENANT_HEADER, "globex"),
+                (ACTOR_HEADER, "mallory"),
+            ],
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, "acme/alice");
+    }
+}
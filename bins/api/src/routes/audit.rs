@@ -0,0 +1,40 @@
+use actix_web::{HttpResponse, get, web};
+use serde::Deserialize;
+use storage::entity::Target;
+
+use crate::RequestContext;
+
+#[derive(Deserialize)]
+struct AuditQuery {
+    pub actor: Option<String>,
+    pub target: Option<Target>,
+    pub target_id: Option<uuid::Uuid>,
+    #[serde(default = "AuditQuery::limit")]
+    pub limit: i64,
+}
+
+impl AuditQuery {
+    fn limit() -> i64 {
+        100
+    }
+}
+
+#[get("/audit")]
+pub async fn audit(ctx: RequestContext, query: web::Query<AuditQuery>) -> HttpResponse {
+    let query = query.into_inner();
+    let result = ctx
+        .storage()
+        .trace_actions
+        .get_audit(
+            query.actor.as_deref(),
+            query.target,
+            query.target_id,
+            query.limit.clamp(1, 1000),
+        )
+        .await;
+
+    match result {
+        Ok(actions) => HttpResponse::Ok().json(actions),
+        Err(err) => HttpResponse::InternalServerError().body(err.to_string()),
+    }
+}
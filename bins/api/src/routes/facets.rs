@@ -0,0 +1,203 @@
+use actix_web::{HttpResponse, delete, post, put, web};
+use serde::Deserialize;
+use storage::entity::{Action, Facet, FacetType, Target};
+
+use crate::RequestContext;
+
+#[derive(Deserialize)]
+struct CreateFacetPayload {
+    pub memory_id: uuid::Uuid,
+    #[serde(rename = "type")]
+    pub ty: FacetType,
+    pub confidence: Option<f32>,
+    #[serde(default)]
+    pub data: serde_json::Value,
+}
+
+/// Fields of a facet to change; unset fields keep their value
+#[derive(Deserialize)]
+struct UpdateFacetPayload {
+    pub confidence: Option<f32>,
+    pub data: Option<serde_json::Value>,
+}
+
+#[post("/facets")]
+pub async fn create_facet(
+    ctx: RequestContext,
+    payload: web::Json<CreateFacetPayload>,
+) -> HttpResponse {
+    let payload = payload.into_inner();
+    let storage = ctx.storage();
+
+    match storage.memories.get(payload.memory_id).await {
+        Ok(Some(_)) => {}
+        Ok(None) => return HttpResponse::NotFound().body("memory not found"),
+        Err(err) => return HttpResponse::InternalServerError().body(err.to_string()),
+    }
+
+    let mut builder =
+        Facet::builder(payload.memory_id, payload.ty).data(payload.data.to_string().into_bytes());
+
+    if let Some(confidence) = payload.confidence {
+        builder = builder.confidence(confidence);
+    }
+
+    let facet = match storage.facets.create(&builder.build()).await {
+        Ok(facet) => facet,
+        Err(err) => return HttpResponse::InternalServerError().body(err.to_string()),
+    };
+
+    match ctx
+        .audit(Target::Facet, facet.id, Action::Create, None, Some(&facet))
+        .await
+    {
+        Ok(_) => HttpResponse::Created().json(facet),
+        Err(err) => HttpResponse::InternalServerError().body(err.to_string()),
+    }
+}
+
+#[put("/facets/{id}")]
+pub async fn update_facet(
+    ctx: RequestContext,
+    id: web::Path<uuid::Uuid>,
+    payload: web::Json<UpdateFacetPayload>,
+) -> HttpResponse {
+    let id = id.into_inner();
+    let payload = payload.into_inner();
+    let storage = ctx.storage();
+
+    let before = match storage.facets.get(id).await {
+        Ok(Some(facet)) => facet,
+        Ok(None) => return HttpResponse::NotFound().finish(),
+        Err(err) => return HttpResponse::InternalServerError().body(err.to_string()),
+    };
+
+    let mut facet = before.clone();
+    facet.confidence = payload.confidence.unwrap_or(facet.confidence);
+
+    if let Some(data) = payload.data {
+        facet.data = data.to_string().into_bytes();
+    }
+
+    let after = match storage.facets.update(&facet).await {
+        Ok(Some(facet)) => facet,
+        Ok(None) => return HttpResponse::NotFound().finish(),
+        Err(err) => return HttpResponse::InternalServerError().body(err.to_string()),
+    };
+
+    match ctx
+        .audit(
+            Target::Facet,
+            id,
+            Action::Update,
+            Some(&before),
+            Some(&after),
+        )
+        .await
+    {
+        Ok(_) => HttpResponse::Ok().json(after),
+        Err(err) => HttpResponse::InternalServerError().body(err.to_string()),
+    }
+}
+
+#[delete("/facets/{id}")]
+pub async fn delete_facet(ctx: RequestContext, id: web::Path<uuid::Uuid>) -> HttpResponse {
+    let id = id.into_inner();
+    let storage = ctx.storage();
+
+    let before = match storage.facets.get(id).await {
+        Ok(Some(facet)) => facet,
+        Ok(None) => return HttpResponse::NotFound().finish(),
+        Err(err) => return HttpResponse::InternalServerError().body(err.to_string()),
+    };
+
+    if let Err(err) = storage.facets.delete(id).await {
+        return HttpResponse::InternalServerError().body(err.to_string());
+    }
+
+    match ctx
+        .audit(Target::Facet, id, Action::Delete, Some(&before), None)
+        .await
+    {
+        Ok(_) => HttpResponse::NoContent().finish(),
+        Err(err) => HttpResponse::InternalServerError().body(err.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::{App, http::StatusCode, test};
+    use storage::entity::Memory;
+
+    use super::*;
+    use crate::RequestContextMiddleware;
+    use crate::context::testing;
+
+    #[actix_web::test]
+    #[ignore = "needs a Postgres database at DATABASE_URL"]
+    async fn facet_writes_are_audited() {
+        let ctx = testing::context().await;
+        let tenant = uuid::Uuid::new_v4().to_string();
+        let memory = ctx
+            .storage(&tenant)
+            .memories
+            .create(&Memory::builder(uuid::Uuid::new_v4()).build())
+            .await
+            .unwrap();
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(ctx.clone()))
+                .wrap(RequestContextMiddleware)
+                .service(create_facet)
+                .service(update_facet)
+                .service(delete_facet),
+        )
+        .await;
+
+        let request = |req: test::TestRequest| {
+            req.insert_header(("X-Tenant-ID", tenant.as_str()))
+                .insert_header(("X-Actor-ID", "alice"))
+                .to_request()
+        };
+
+        let facet: Facet = test::call_and_read_body_json(
+            &app,
+            request(
+                test::TestRequest::post()
+                    .uri("/facets")
+                    .set_json(serde_json::json!({
+                        "memory_id": memory.id,
+                        "type": "Fact",
+                        "data": { "label": "deadline" },
+                    })),
+            ),
+        )
+        .await;
+
+        let res = test::call_service(
+            &app,
+            request(
+                test::TestRequest::put()
+                    .uri(&format!("/facets/{}", facet.id))
+                    .set_json(serde_json::json!({ "confidence": 0.4 })),
+            ),
+        )
+        .await;
+        assert_eq!(res.status(), StatusCode::OK);
+
+        let res = test::call_service(
+            &app,
+            request(test::TestRequest::delete().uri(&format!("/facets/{}", facet.id))),
+        )
+        .await;
+        assert_eq!(res.status(), StatusCode::NO_CONTENT);
+
+        let actions = ctx
+            .storage(&tenant)
+            .trace_actions
+            .get_audit(Some("alice"), Some(Target::Facet), Some(facet.id), 10)
+            .await
+            .unwrap();
+        assert_eq!(actions.len(), 3);
+    }
+}
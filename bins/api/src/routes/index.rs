@@ -1,7 +1,7 @@
-use actix_web::{HttpResponse, get};
+use actix_web::{HttpResponse, get, web};
 use serde::Serialize;
 
-use crate::RequestContext;
+use crate::Context;
 
 #[derive(Serialize)]
 struct IndexResponse {
@@ -9,7 +9,7 @@ struct IndexResponse {
 }
 
 #[get("/")]
-pub async fn index(ctx: RequestContext) -> HttpResponse {
+pub async fn index(ctx: web::Data<Context>) -> HttpResponse {
     HttpResponse::Ok().json(IndexResponse {
         start_time: ctx.start_time().to_rfc3339(),
     })
@@ -0,0 +1,381 @@
+use actix_web::{HttpResponse, delete, get, post, put, web};
+use serde::{Deserialize, Serialize};
+use storage::entity::{Action, FacetBucket, FacetType, Memory, RankedMemory, Sensitivity, Target};
+
+use crate::RequestContext;
+
+#[derive(Deserialize)]
+struct RelevantQuery {
+    pub q: String,
+    pub k: Option<usize>,
+    pub scope_id: uuid::Uuid,
+}
+
+#[derive(Serialize)]
+struct RelevantResponse {
+    pub query: String,
+    pub k: usize,
+    pub memories: Vec<RankedMemory>,
+}
+
+#[get("/memories/relevant")]
+pub async fn relevant(ctx: RequestContext, query: web::Query<RelevantQuery>) -> HttpResponse {
+    let query = query.into_inner();
+
+    if query.q.trim().is_empty() {
+        return HttpResponse::BadRequest().body("query parameter `q` must not be empty");
+    }
+
+    let Some(retriever) = ctx.retriever() else {
+        return HttpResponse::ServiceUnavailable().body("no retrieval model is loaded");
+    };
+    let text = query.q.clone();
+    let trace_id = ctx.request_id().to_string();
+    let embedded = web::block(move || {
+        let layer = retriever.lock().expect("retriever lock poisoned");
+        let config = layer.config().clone();
+        layer
+            .invoke(loom_runtime::Context::new(&text, ()).with_trace_id(&trace_id))
+            .map(|result| (result.output, config))
+    })
+    .await;
+
+    let (embedding, config) = match embedded {
+        Ok(Ok(v)) => v,
+        Ok(Err(err)) => return HttpResponse::InternalServerError().body(err.to_string()),
+        Err(err) => return HttpResponse::InternalServerError().body(err.to_string()),
+    };
+
+    let k = config.k_or_default(query.k);
+    let result = ctx
+        .storage()
+        .memories
+        .search(
+            query.scope_id,
+            &query.q,
+            &embedding,
+            config.vector_weight,
+            config.keyword_weight,
+            k as i64,
+        )
+        .await;
+
+    match result {
+        Ok(mut memories) => {
+            for memory in &mut memories {
+                memory.score = config.score(memory.similarity, memory.keyword_score);
+            }
+
+            HttpResponse::Ok().json(RelevantResponse {
+                query: query.q,
+                k,
+                memories,
+            })
+        }
+        Err(err) => HttpResponse::InternalServerError().body(err.to_string()),
+    }
+}
+
+#[derive(Deserialize)]
+struct CreateMemoryPayload {
+    pub scope_id: uuid::Uuid,
+    pub score: Option<f32>,
+    pub confidence: Option<f32>,
+    pub importance: Option<f32>,
+    pub sensitivity: Option<Sensitivity>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Fields of a memory to change; unset fields keep their value
+#[derive(Deserialize)]
+struct UpdateMemoryPayload {
+    pub score: Option<f32>,
+    pub confidence: Option<f32>,
+    pub importance: Option<f32>,
+    pub sensitivity: Option<Sensitivity>,
+    pub tags: Option<Vec<String>>,
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[post("/memories")]
+pub async fn create_memory(
+    ctx: RequestContext,
+    payload: web::Json<CreateMemoryPayload>,
+) -> HttpResponse {
+    let payload = payload.into_inner();
+    let mut builder = Memory::builder(payload.scope_id).tags(payload.tags);
+
+    if let Some(score) = payload.score {
+        builder = builder.score(score);
+    }
+
+    if let Some(confidence) = payload.confidence {
+        builder = builder.confidence(confidence);
+    }
+
+    if let Some(importance) = payload.importance {
+        builder = builder.importance(importance);
+    }
+
+    if let Some(sensitivity) = payload.sensitivity {
+        builder = builder.sensitivity(sensitivity);
+    }
+
+    if let Some(expires_at) = payload.expires_at {
+        builder = builder.expires_at(expires_at);
+    }
+
+    let memory = match ctx.storage().memories.create(&builder.build()).await {
+        Ok(memory) => memory,
+        Err(err) => return HttpResponse::InternalServerError().body(err.to_string()),
+    };
+
+    match ctx
+        .audit(
+            Target::Memory,
+            memory.id,
+            Action::Create,
+            None,
+            Some(&memory),
+        )
+        .await
+    {
+        Ok(_) => HttpResponse::Created().json(memory),
+        Err(err) => HttpResponse::InternalServerError().body(err.to_string()),
+    }
+}
+
+#[put("/memories/{id}")]
+pub async fn update_memory(
+    ctx: RequestContext,
+    id: web::Path<uuid::Uuid>,
+    payload: web::Json<UpdateMemoryPayload>,
+) -> HttpResponse {
+    let id = id.into_inner();
+    let payload = payload.into_inner();
+    let storage = ctx.storage();
+
+    let before = match storage.memories.get(id).await {
+        Ok(Some(memory)) => memory,
+        Ok(None) => return HttpResponse::NotFound().finish(),
+        Err(err) => return HttpResponse::InternalServerError().body(err.to_string()),
+    };
+
+    let mut memory = before.clone();
+    memory.score = payload.score.unwrap_or(memory.score);
+    memory.confidence = payload.confidence.unwrap_or(memory.confidence);
+    memory.importance = payload.importance.unwrap_or(memory.importance);
+    memory.sensitivity = payload.sensitivity.unwrap_or(memory.sensitivity);
+    memory.tags = payload.tags.unwrap_or(memory.tags);
+    memory.expires_at = payload.expires_at.or(memory.expires_at);
+
+    let after = match storage.memories.update(&memory).await {
+        Ok(Some(memory)) => memory,
+        Ok(None) => return HttpResponse::NotFound().finish(),
+        Err(err) => return HttpResponse::InternalServerError().body(err.to_string()),
+    };
+
+    match ctx
+        .audit(
+            Target::Memory,
+            id,
+            Action::Update,
+            Some(&before),
+            Some(&after),
+        )
+        .await
+    {
+        Ok(_) => HttpResponse::Ok().json(after),
+        Err(err) => HttpResponse::InternalServerError().body(err.to_string()),
+    }
+}
+
+#[delete("/memories/{id}")]
+pub async fn delete_memory(ctx: RequestContext, id: web::Path<uuid::Uuid>) -> HttpResponse {
+    let id = id.into_inner();
+    let storage = ctx.storage();
+
+    let before = match storage.memories.get(id).await {
+        Ok(Some(memory)) => memory,
+        Ok(None) => return HttpResponse::NotFound().finish(),
+        Err(err) => return HttpResponse::InternalServerError().body(err.to_string()),
+    };
+
+    if let Err(err) = storage.memories.delete(id).await {
+        return HttpResponse::InternalServerError().body(err.to_string());
+    }
+
+    match ctx
+        .audit(Target::Memory, id, Action::Delete, Some(&before), None)
+        .await
+    {
+        Ok(_) => HttpResponse::NoContent().finish(),
+        Err(err) => HttpResponse::InternalServerError().body(err.to_string()),
+    }
+}
+
+#[derive(Deserialize)]
+struct TimelineQuery {
+    pub label: String,
+    #[serde(default = "TimelineQuery::window")]
+    pub window: String,
+    pub kind: Option<FacetType>,
+    pub scope_id: Option<uuid::Uuid>,
+    pub since: Option<chrono::DateTime<chrono::Utc>>,
+    pub until: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl TimelineQuery {
+    fn window() -> String {
+        "1d".to_string()
+    }
+}
+
+#[derive(Serialize)]
+struct TimelineResponse {
+    pub label: String,
+    pub window: String,
+    pub buckets: Vec<FacetBucket>,
+}
+
+#[get("/memories/timeline")]
+pub async fn timeline(ctx: RequestContext, query: web::Query<TimelineQuery>) -> HttpResponse {
+    let query = query.into_inner();
+
+    if query.label.trim().is_empty() {
+        return HttpResponse::BadRequest().body("query parameter `label` must not be empty");
+    }
+
+    let Some(window) = parse_window(&query.window) else {
+        return HttpResponse::BadRequest()
+            .body("query parameter `window` must be a positive `<n>m`, `<n>h`, `<n>d` or `<n>w`");
+    };
+
+    let result = ctx
+        .storage()
+        .facets
+        .timeline(
+            &query.label,
+            window,
+            query.kind,
+            query.scope_id,
+            query.since,
+            query.until,
+        )
+        .await;
+
+    match result {
+        Ok(buckets) => HttpResponse::Ok().json(TimelineResponse {
+            label: query.label,
+            window: query.window,
+            buckets,
+        }),
+        Err(err) => HttpResponse::InternalServerError().body(err.to_string()),
+    }
+}
+
+/// Parse a bucket width such as `15m`, `6h`, `1d` or `2w`
+fn parse_window(window: &str) -> Option<chrono::TimeDelta> {
+    let unit = window.chars().last()?;
+    let n = window[..window.len() - unit.len_utf8()]
+        .parse::<i64>()
+        .ok()
+        .filter(|n| *n > 0)?;
+
+    match unit {
+        'm' => chrono::TimeDelta::try_minutes(n),
+        'h' => chrono::TimeDelta::try_hours(n),
+        'd' => chrono::TimeDelta::try_days(n),
+        'w' => chrono::TimeDelta::try_weeks(n),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::{App, http::StatusCode, test};
+    use storage::entity::TraceAction;
+
+    use super::*;
+    use crate::RequestContextMiddleware;
+    use crate::context::testing;
+
+    #[test]
+    fn parse_window_units() {
+        assert_eq!(parse_window("15m"), chrono::TimeDelta::try_minutes(15));
+        assert_eq!(parse_window("6h"), chrono::TimeDelta::try_hours(6));
+        assert_eq!(parse_window("1d"), chrono::TimeDelta::try_days(1));
+        assert_eq!(parse_window("2w"), chrono::TimeDelta::try_weeks(2));
+    }
+
+    #[test]
+    fn parse_window_rejects_invalid() {
+        for window in ["", "d", "0d", "-1d", "1s", "1.5h", "d1", "1dd", "1é"] {
+            assert_eq!(parse_window(window), None, "{window}");
+        }
+    }
+
+    #[actix_web::test]
+    #[ignore = "needs a Postgres database at DATABASE_URL"]
+    async fn memory_writes_are_audited() {
+        let ctx = testing::context().await;
+        let tenant = uuid::Uuid::new_v4().to_string();
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(ctx.clone()))
+                .wrap(RequestContextMiddleware)
+                .service(create_memory)
+                .service(update_memory)
+                .service(delete_memory),
+        )
+        .await;
+
+        let request = |req: test::TestRequest| {
+            req.insert_header(("X-Tenant-ID", tenant.as_str()))
+                .insert_header(("X-Actor-ID", "alice"))
+                .to_request()
+        };
+
+        let memory: Memory = test::call_and_read_body_json(
+            &app,
+            request(test::TestRequest::post().uri("/memories").set_json(
+                serde_json::json!({ "scope_id": uuid::Uuid::new_v4(), "tags": ["task"] }),
+            )),
+        )
+        .await;
+
+        let res = test::call_service(
+            &app,
+            request(
+                test::TestRequest::put()
+                    .uri(&format!("/memories/{}", memory.id))
+                    .set_json(serde_json::json!({ "score": 0.9 })),
+            ),
+        )
+        .await;
+        assert_eq!(res.status(), StatusCode::OK);
+
+        let res = test::call_service(
+            &app,
+            request(test::TestRequest::delete().uri(&format!("/memories/{}", memory.id))),
+        )
+        .await;
+        assert_eq!(res.status(), StatusCode::NO_CONTENT);
+
+        let actions: Vec<TraceAction> = ctx
+            .storage(&tenant)
+            .trace_actions
+            .get_audit(Some("alice"), Some(Target::Memory), Some(memory.id), 10)
+            .await
+            .unwrap();
+        let mut kinds: Vec<Action> = actions.iter().map(|a| a.action).collect();
+        kinds.sort_by_key(|a| a.as_str());
+        assert_eq!(kinds, [Action::Create, Action::Delete, Action::Update]);
+
+        let update = actions.iter().find(|a| a.action == Action::Update).unwrap();
+        let diff = update.diff.as_ref().unwrap().as_array().unwrap();
+        assert!(diff.iter().any(|change| change["path"] == "score"));
+    }
+}
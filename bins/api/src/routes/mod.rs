@@ -1,5 +1,11 @@
+mod audit;
+mod facets;
 mod index;
 mod ingest;
+mod memories;
 
+pub use audit::*;
+pub use facets::*;
 pub use index::*;
 pub use ingest::*;
+pub use memories::*;
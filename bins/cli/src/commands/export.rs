@@ -0,0 +1,107 @@
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::PathBuf;
+
+use clap::Args;
+use futures::{Stream, StreamExt};
+use storage::{Cipher, Storage};
+
+use super::{Format, connect};
+use crate::record::Record;
+
+/// Export all records
+#[derive(Debug, Args)]
+pub struct ExportCommand {
+    /// Output format
+    #[arg(short, long, value_enum, default_value_t = Format::Ndjson)]
+    pub format: Format,
+
+    /// Output file (defaults to stdout)
+    #[arg(short, long)]
+    pub output: Option<PathBuf>,
+}
+
+impl ExportCommand {
+    pub async fn exec(self, database_url: &str, tenant: &str, cipher: Option<&Cipher>) {
+        let pool = connect(database_url).await;
+        let storage = Storage::new(&pool, tenant).with_cipher(cipher);
+        let writer: Box<dyn Write> = match &self.output {
+            None => Box::new(std::io::stdout().lock()),
+            Some(path) => match File::create(path) {
+                Ok(f) => Box::new(f),
+                Err(e) => {
+                    eprintln!("Error creating {:?}: {}", path, e);
+                    std::process::exit(1);
+                }
+            },
+        };
+
+        let mut exporter = Exporter {
+            format: self.format,
+            writer: BufWriter::new(writer),
+            counts: BTreeMap::new(),
+        };
+
+        // parents are written before the rows that reference them
+        let result = async {
+            exporter
+                .write_all(storage.sources.stream(), Record::Source)
+                .await?;
+            exporter
+                .write_all(storage.memories.stream(), Record::Memory)
+                .await?;
+            exporter
+                .write_all(storage.memory_sources.stream(), Record::MemorySource)
+                .await?;
+            exporter
+                .write_all(storage.facets.stream(), Record::Facet)
+                .await?;
+            exporter
+                .write_all(storage.traces.stream(), Record::Trace)
+                .await?;
+            exporter
+                .write_all(storage.trace_actions.stream(), Record::TraceAction)
+                .await?;
+            exporter.writer.flush().map_err(|e| e.to_string())
+        }
+        .await;
+
+        if let Err(e) = result {
+            eprintln!("Error exporting records: {}", e);
+            std::process::exit(1);
+        }
+
+        for (kind, count) in &exporter.counts {
+            eprintln!("exported {} {} record(s)", count, kind);
+        }
+    }
+}
+
+struct Exporter<W: Write> {
+    format: Format,
+    writer: W,
+    counts: BTreeMap<&'static str, usize>,
+}
+
+impl<W: Write> Exporter<W> {
+    async fn write_all<T>(
+        &mut self,
+        mut rows: impl Stream<Item = Result<T, sqlx::Error>> + Unpin,
+        record: impl Fn(T) -> Record,
+    ) -> Result<(), String> {
+        while let Some(row) = rows.next().await {
+            let record = record(row.map_err(|e| e.to_string())?);
+            *self.counts.entry(record.kind()).or_default() += 1;
+
+            match self.format {
+                Format::Ndjson => {
+                    serde_json::to_writer(&mut self.writer, &record).map_err(|e| e.to_string())?;
+                    self.writer.write_all(b"\n").map_err(|e| e.to_string())?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,110 @@
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read};
+use std::path::PathBuf;
+
+use clap::Args;
+use storage::{Cipher, Storage};
+
+use super::{Format, connect};
+use crate::record::Record;
+
+/// Import records from an export
+#[derive(Debug, Args)]
+pub struct ImportCommand {
+    /// Input file (defaults to stdin)
+    pub path: Option<PathBuf>,
+
+    /// Input format
+    #[arg(short, long, value_enum, default_value_t = Format::Ndjson)]
+    pub format: Format,
+}
+
+impl ImportCommand {
+    pub async fn exec(self, database_url: &str, tenant: &str, cipher: Option<&Cipher>) {
+        let pool = connect(database_url).await;
+
+        if let Err(e) = sqlx::migrate!("../../crates/storage/migrations")
+            .run(&pool)
+            .await
+        {
+            eprintln!("Error running migrations: {}", e);
+            std::process::exit(1);
+        }
+
+        let storage = Storage::new(&pool, tenant).with_cipher(cipher);
+        let reader: Box<dyn Read> = match &self.path {
+            None => Box::new(std::io::stdin().lock()),
+            Some(path) => match File::open(path) {
+                Ok(f) => Box::new(f),
+                Err(e) => {
+                    eprintln!("Error opening {:?}: {}", path, e);
+                    std::process::exit(1);
+                }
+            },
+        };
+
+        // (inserted, skipped) per record kind
+        let mut counts: BTreeMap<&'static str, (usize, usize)> = BTreeMap::new();
+
+        for (i, line) in BufReader::new(reader).lines().enumerate() {
+            let line = match line {
+                Ok(l) => l,
+                Err(e) => {
+                    eprintln!("Error reading line {}: {}", i + 1, e);
+                    std::process::exit(1);
+                }
+            };
+
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let record: Record = match self.format {
+                Format::Ndjson => match serde_json::from_str(&line) {
+                    Ok(r) => r,
+                    Err(e) => {
+                        eprintln!("Error parsing line {}: {}", i + 1, e);
+                        std::process::exit(1);
+                    }
+                },
+            };
+
+            let kind = record.kind();
+            let inserted = match restore(&storage, &record).await {
+                Ok(v) => v,
+                Err(e) => {
+                    eprintln!("Error importing {} on line {}: {}", kind, i + 1, e);
+                    std::process::exit(1);
+                }
+            };
+
+            let entry = counts.entry(kind).or_default();
+
+            if inserted {
+                entry.0 += 1;
+            } else {
+                entry.1 += 1;
+            }
+        }
+
+        for (kind, (inserted, skipped)) in &counts {
+            eprintln!(
+                "imported {} {} record(s), {} already present",
+                inserted, kind, skipped
+            );
+        }
+    }
+}
+
+/// Insert a record, relying on the export order for referential integrity
+async fn restore(storage: &Storage<'_>, record: &Record) -> Result<bool, sqlx::Error> {
+    match record {
+        Record::Source(v) => storage.sources.restore(v).await,
+        Record::Memory(v) => storage.memories.restore(v).await,
+        Record::MemorySource(v) => storage.memory_sources.restore(v).await,
+        Record::Facet(v) => storage.facets.restore(v).await,
+        Record::Trace(v) => storage.traces.restore(v).await,
+        Record::TraceAction(v) => storage.trace_actions.restore(v).await,
+    }
+}
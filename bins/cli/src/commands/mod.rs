@@ -0,0 +1,35 @@
+use sqlx::PgPool;
+use sqlx::postgres::PgPoolOptions;
+
+pub mod export;
+pub mod import;
+pub mod replay;
+pub mod rotate_keys;
+
+pub use export::ExportCommand;
+pub use import::ImportCommand;
+pub use replay::ReplayCommand;
+pub use rotate_keys::RotateKeysCommand;
+
+/// Export/import file formats
+#[derive(Debug, Clone, Copy, Default, clap::ValueEnum)]
+pub enum Format {
+    /// One JSON record per line
+    #[default]
+    Ndjson,
+}
+
+/// Connect to the database or exit with an error
+pub async fn connect(database_url: &str) -> PgPool {
+    match PgPoolOptions::new()
+        .max_connections(1)
+        .connect(database_url)
+        .await
+    {
+        Ok(pool) => pool,
+        Err(e) => {
+            eprintln!("Error connecting to database: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,442 @@
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+
+use clap::Args;
+use loom::config::FileProvider;
+use loom::core::ident_path;
+use loom::error::Result;
+use loom::runtime::eval::score::ScoreResult;
+use loom::runtime::facet::{Facet, FacetInput, NamedEntity};
+use loom::runtime::pipeline::PipelineConfig;
+use loom::runtime::{Context, RConfig, Runtime};
+use serde::Serialize;
+use storage::entity::{Action, Status, Trace, TraceAction};
+use storage::{Cipher, Storage};
+
+use super::connect;
+
+/// Request id of the traces the worker writes per memory
+const REQUEST_ID: &str = "memory.create";
+
+/// Worker stages that write to the database or the queue, never replayed
+const NATIVE: &[&str] = &["persist", "publish"];
+
+/// Re-run stored traces through the current pipeline without writing anything
+///
+/// Each trace's input is rebuilt from the stored source text of its memory
+/// and run through the enabled layer stages of `--pipeline-config` (`persist`
+/// and `publish` are skipped). Prints one JSON line per trace comparing the
+/// original outcome with the replayed one, then a summary.
+///
+/// A memory that was never stored (e.g. dropped by the pre-filter) replays
+/// the input the worker kept on its trace. Traces with neither stored text
+/// nor a kept input (written by older workers) can't be replayed and are only
+/// counted. A memory that merged several inputs replays their combined text.
+#[derive(Debug, Args)]
+pub struct ReplayCommand {
+    /// Trace id, or `<first id>..<last id>` for every memory trace started
+    /// between the two (inclusive); may be repeated
+    #[arg(long, required = true, value_parser = parse_range)]
+    pub trace: Vec<(uuid::Uuid, uuid::Uuid)>,
+
+    /// Loom config file declaring the pipeline and its layers
+    #[arg(long, env = "PIPELINE_CONFIG")]
+    pub pipeline_config: Option<PathBuf>,
+
+    /// Most traces replayed per range
+    #[arg(long, default_value_t = 1000)]
+    pub limit: i64,
+
+    /// Only print traces whose outcome changed
+    #[arg(long)]
+    pub changed: bool,
+}
+
+impl ReplayCommand {
+    pub async fn exec(self, database_url: &str, tenant: &str, cipher: Option<&Cipher>) {
+        let pipeline = match Pipeline::new(self.pipeline_config.as_deref()) {
+            Ok(v) => v,
+            Err(e) => {
+                eprintln!("Error building pipeline: {}", e);
+                std::process::exit(1);
+            }
+        };
+
+        let pool = connect(database_url).await;
+        let storage = Storage::new(&pool, tenant).with_cipher(cipher);
+        let mut summary = Summary::default();
+
+        for (first, last) in &self.trace {
+            let traces = match storage
+                .traces
+                .get_range(*first, *last, Some(REQUEST_ID), self.limit)
+                .await
+            {
+                Ok(v) => v,
+                Err(e) => {
+                    eprintln!("Error reading traces: {}", e);
+                    std::process::exit(1);
+                }
+            };
+
+            for trace in traces {
+                let replay = match replay(&storage, cipher, &pipeline, &trace).await {
+                    Ok(v) => v,
+                    Err(e) => {
+                        eprintln!("Error replaying trace {}: {}", trace.id, e);
+                        std::process::exit(1);
+                    }
+                };
+
+                let Some(replay) = replay else {
+                    summary.unavailable += 1;
+                    continue;
+                };
+
+                if replay.changed {
+                    summary.changed += 1;
+                } else {
+                    summary.unchanged += 1;
+                }
+
+                if replay.changed || !self.changed {
+                    match serde_json::to_string(&replay) {
+                        Ok(line) => println!("{}", line),
+                        Err(e) => eprintln!("Error serializing trace {}: {}", trace.id, e),
+                    }
+                }
+            }
+        }
+
+        eprintln!(
+            "replayed {} trace(s) through {}: {} changed, {} unchanged, {} without stored input",
+            summary.changed + summary.unchanged,
+            pipeline.stages.join(" -> "),
+            summary.changed,
+            summary.unchanged,
+            summary.unavailable
+        );
+
+        if let Err(e) = pipeline.runtime.shutdown().await {
+            eprintln!("Error shutting down runtime: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+#[derive(Default)]
+struct Summary {
+    changed: usize,
+    unchanged: usize,
+    unavailable: usize,
+}
+
+/// How a memory left the pipeline
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "decision", rename_all = "snake_case")]
+enum Decision {
+    /// A stage dropped the memory
+    Skipped {
+        stage: String,
+        reason: String,
+    },
+    /// A stage failed
+    Failed {
+        reason: String,
+    },
+    Processed,
+}
+
+#[derive(Debug, Serialize)]
+struct Outcome {
+    #[serde(flatten)]
+    decision: Decision,
+    score: Option<f32>,
+    /// `<kind>:<label>` of each facet, e.g. `emotion:stress`
+    facets: BTreeSet<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct Replay {
+    trace_id: uuid::Uuid,
+    memory_id: uuid::Uuid,
+    changed: bool,
+    original: Outcome,
+    replayed: Outcome,
+}
+
+/// Replay one trace, or `None` if its input can't be rebuilt
+async fn replay(
+    storage: &Storage<'_>,
+    cipher: Option<&Cipher>,
+    pipeline: &Pipeline,
+    trace: &Trace,
+) -> std::result::Result<Option<Replay>, sqlx::Error> {
+    let actions = storage.trace_actions.get_by_trace(trace.id).await?;
+    let Some(memory_id) = actions.first().map(|a| a.target_id) else {
+        return Ok(None);
+    };
+
+    let memory = storage.memories.get(memory_id).await?;
+    let mut sources = storage.memory_sources.get_by_memory(memory_id).await?;
+    sources.sort_by_key(|s| s.start_offset);
+
+    let stored = sources
+        .into_iter()
+        .filter_map(|s| s.text)
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let text = match stored.trim().is_empty() {
+        false => stored,
+        true => match input_text(&actions, cipher)? {
+            Some(text) => text,
+            None => return Ok(None),
+        },
+    };
+
+    if text.trim().is_empty() {
+        return Ok(None);
+    }
+
+    let skipped = actions
+        .iter()
+        .filter(|a| a.action == Action::Signal)
+        .filter_map(|a| a.diff.as_ref())
+        .find_map(|diff| {
+            let stage = diff["name"].as_str()?.strip_prefix("pipeline.")?;
+            let reason = diff["attributes"]["skipped"].as_str()?;
+
+            Some(Decision::Skipped {
+                stage: stage.to_string(),
+                reason: reason.to_string(),
+            })
+        });
+
+    let decision = match (skipped, trace.status) {
+        (Some(skipped), _) => skipped,
+        (None, Status::Error) => Decision::Failed {
+            reason: trace.status_message.clone().unwrap_or_default(),
+        },
+        (None, _) => Decision::Processed,
+    };
+
+    let facets = storage
+        .facets
+        .get_by_memory(memory_id)
+        .await?
+        .iter()
+        .filter_map(|f| serde_json::from_slice::<serde_json::Value>(&f.data).ok())
+        .filter_map(|data| facet_key(&data))
+        .collect();
+
+    let original = Outcome {
+        decision,
+        score: memory.map(|m| m.score),
+        facets,
+    };
+
+    let replayed = pipeline.run(&text);
+    let changed = original.decision != replayed.decision || original.facets != replayed.facets;
+
+    Ok(Some(Replay {
+        trace_id: trace.id,
+        memory_id,
+        changed,
+        original,
+        replayed,
+    }))
+}
+
+/// The input the worker kept on a trace whose run stored no memory
+fn input_text(
+    actions: &[TraceAction],
+    cipher: Option<&Cipher>,
+) -> std::result::Result<Option<String>, sqlx::Error> {
+    let Some(text) = actions
+        .iter()
+        .filter(|a| a.action == Action::Input)
+        .find_map(|a| a.diff.as_ref()?["text"].as_str())
+    else {
+        return Ok(None);
+    };
+
+    match cipher {
+        Some(cipher) => cipher
+            .decrypt(text)
+            .map(Some)
+            .map_err(|e| sqlx::Error::Decode(Box::new(e))),
+        None if Cipher::is_encrypted(text) => Ok(None),
+        None => Ok(Some(text.to_string())),
+    }
+}
+
+/// `<kind>:<label>` of a facet's serialized data, e.g. `person:Alice`
+fn facet_key(data: &serde_json::Value) -> Option<String> {
+    let kind = data["kind"].as_str()?;
+    let label = ["label", "name", "text"]
+        .iter()
+        .find_map(|key| data[*key].as_str())?;
+
+    Some(format!("{}:{}", kind, label))
+}
+
+/// The enabled layer stages of a pipeline config, built like the worker builds them
+/// (see [`loom::runtime::Builder::pipeline`])
+struct Pipeline {
+    runtime: Runtime,
+    stages: Vec<String>,
+}
+
+impl Pipeline {
+    fn new(path: Option<&std::path::Path>) -> Result<Self> {
+        let rconfig = match path {
+            None => RConfig::new().build()?,
+            Some(path) => RConfig::new()
+                .with_provider(FileProvider::builder(path).build())
+                .build()?,
+        };
+
+        let pipeline = match rconfig.get(&ident_path!("pipeline")) {
+            None => PipelineConfig::new(["persist"]),
+            Some(_) => rconfig.bind_section(&ident_path!("pipeline"))?,
+        };
+
+        let runtime = Runtime::new();
+
+        #[cfg(feature = "arrow")]
+        let runtime = runtime.codec(loom::runtime::ArrowCodec::new());
+
+        #[cfg(feature = "parquet")]
+        let runtime = runtime.codec(loom::runtime::ParquetCodec::new());
+
+        let runtime = runtime.config(rconfig).pipeline(&pipeline)?.try_build()?;
+        let stages = pipeline
+            .resolve(runtime.layers(), NATIVE)?
+            .into_iter()
+            .filter(|stage| !NATIVE.contains(&stage.as_str()))
+            .collect();
+
+        Ok(Self { runtime, stages })
+    }
+
+    /// Run the stages on `text` the way the worker does, stopping at the first
+    /// stage that drops or fails it
+    fn run(&self, text: &str) -> Outcome {
+        let mut score: Option<ScoreResult> = None;
+        let mut entities: Vec<NamedEntity> = Vec::new();
+        let mut facets: Vec<Facet> = Vec::new();
+        let mut decision = Decision::Processed;
+
+        for stage in &self.stages {
+            let res = match stage.as_str() {
+                "prefilter" => self
+                    .runtime
+                    .eval::<Context<()>, ()>("prefilter", Context::new(text, ())),
+                "score" => self
+                    .runtime
+                    .eval::<Context<()>, ScoreResult>("score", Context::new(text, ()))
+                    .map(|result| score = Some(result)),
+                "ner" => self
+                    .runtime
+                    .eval::<Context<()>, Vec<NamedEntity>>("ner", Context::new(text, ()))
+                    .map(|v| entities = v),
+                "facet" => self
+                    .runtime
+                    .eval::<Context<FacetInput>, Vec<Facet>>(
+                        "facet",
+                        Context::new(
+                            text,
+                            FacetInput::new(score.clone().unwrap_or_default(), entities.clone()),
+                        ),
+                    )
+                    .map(|v| facets.extend(v)),
+                _ => Ok(()),
+            };
+
+            match res {
+                Ok(()) => {}
+                Err(err) if err.code().is_cancel() => {
+                    decision = Decision::Skipped {
+                        stage: stage.clone(),
+                        reason: err.to_string(),
+                    };
+                    break;
+                }
+                Err(err) => {
+                    decision = Decision::Failed {
+                        reason: err.to_string(),
+                    };
+                    break;
+                }
+            }
+        }
+
+        Outcome {
+            decision,
+            score: score.map(|s| s.score),
+            facets: facets
+                .iter()
+                .filter_map(|f| serde_json::to_value(&f.data).ok())
+                .filter_map(|data| facet_key(&data))
+                .collect(),
+        }
+    }
+}
+
+/// Parse `<id>` or `<first id>..<last id>`
+fn parse_range(value: &str) -> std::result::Result<(uuid::Uuid, uuid::Uuid), String> {
+    let (first, last) = value.split_once("..").unwrap_or((value, value));
+    let parse = |id: &str| {
+        uuid::Uuid::parse_str(id.trim()).map_err(|e| format!("invalid trace id '{}': {}", id, e))
+    };
+
+    Ok((parse(first)?, parse(last)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIRST: &str = "0190f3a1-0000-7000-8000-000000000001";
+    const LAST: &str = "0190f3a1-0000-7000-8000-000000000002";
+
+    #[test]
+    fn parse_range_single_id() {
+        let id = uuid::Uuid::parse_str(FIRST).unwrap();
+
+        assert_eq!(parse_range(FIRST).unwrap(), (id, id));
+    }
+
+    #[test]
+    fn parse_range_bounds() {
+        let (first, last) = parse_range(&format!("{} .. {}", FIRST, LAST)).unwrap();
+
+        assert_eq!(first.to_string(), FIRST);
+        assert_eq!(last.to_string(), LAST);
+    }
+
+    #[test]
+    fn parse_range_rejects_invalid_ids() {
+        let err = parse_range(&format!("{}..nope", FIRST)).unwrap_err();
+
+        assert!(err.contains("invalid trace id 'nope'"));
+        assert!(parse_range("").is_err());
+    }
+
+    #[test]
+    fn facet_key_uses_first_label_field() {
+        let person = serde_json::json!({ "kind": "person", "name": "Alice", "text": "alice" });
+        let emotion = serde_json::json!({ "kind": "emotion", "label": "stress" });
+
+        assert_eq!(facet_key(&person).as_deref(), Some("person:Alice"));
+        assert_eq!(facet_key(&emotion).as_deref(), Some("emotion:stress"));
+    }
+
+    #[test]
+    fn facet_key_needs_kind_and_label() {
+        assert_eq!(facet_key(&serde_json::json!({ "label": "stress" })), None);
+        assert_eq!(facet_key(&serde_json::json!({ "kind": "date" })), None);
+        assert_eq!(facet_key(&serde_json::json!("person:Alice")), None);
+    }
+}
@@ -0,0 +1,64 @@
+use clap::Args;
+use storage::{Cipher, Storage};
+
+use super::connect;
+
+/// Re-encrypt memory text with the current encryption key
+///
+/// Rows that are still plaintext or were written with an older key are rewritten
+/// with the first key of `--encryption-keys`, across every tenant since the keys
+/// are shared. Older keys must stay configured until the rotation completes.
+#[derive(Debug, Args)]
+pub struct RotateKeysCommand {
+    /// Number of rows rewritten per batch
+    #[arg(long, default_value_t = 500)]
+    pub batch_size: i64,
+}
+
+impl RotateKeysCommand {
+    pub async fn exec(self, database_url: &str, cipher: Option<&Cipher>) {
+        let Some(cipher) = cipher else {
+            eprintln!("Error rotating keys: no encryption keys configured");
+            std::process::exit(1);
+        };
+
+        let pool = connect(database_url).await;
+        let tenants = match storage::tenants(&pool).await {
+            Ok(v) => v,
+            Err(e) => {
+                eprintln!("Error listing tenants: {}", e);
+                std::process::exit(1);
+            }
+        };
+
+        let mut total = 0;
+
+        for tenant in &tenants {
+            let storage = Storage::new(&pool, tenant).with_cipher(Some(cipher));
+
+            loop {
+                let rotation = match storage.memory_sources.rotate(self.batch_size).await {
+                    Ok(v) => v,
+                    Err(e) => {
+                        eprintln!("Error rotating keys for tenant '{}': {}", tenant, e);
+                        std::process::exit(1);
+                    }
+                };
+
+                // rows skipped because they changed mid-batch are found again
+                if rotation.found == 0 {
+                    break;
+                }
+
+                total += rotation.rotated;
+            }
+        }
+
+        eprintln!(
+            "re-encrypted {} memory source(s) across {} tenant(s) with key '{}'",
+            total,
+            tenants.len(),
+            cipher.key_id()
+        );
+    }
+}
@@ -0,0 +1,26 @@
+use storage::entity::{Facet, Memory, MemorySource, Source, Trace, TraceAction};
+
+/// A single exported row, tagged with its table
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "type", content = "data", rename_all = "snake_case")]
+pub enum Record {
+    Source(Source),
+    Memory(Memory),
+    MemorySource(MemorySource),
+    Facet(Facet),
+    Trace(Trace),
+    TraceAction(TraceAction),
+}
+
+impl Record {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Source(_) => "source",
+            Self::Memory(_) => "memory",
+            Self::MemorySource(_) => "memory_source",
+            Self::Facet(_) => "facet",
+            Self::Trace(_) => "trace",
+            Self::TraceAction(_) => "trace_action",
+        }
+    }
+}
@@ -0,0 +1,159 @@
+use loom::error::Result;
+use loom::runtime::consolidate::{ConsolidateConfig, ConsolidateItem, ConsolidateLayer};
+use sqlx::PgPool;
+use storage::entity::{
+    Action, Memory, MemorySource, Sensitivity, SourcedMemory, Target, Trace, TraceAction,
+};
+use storage::{Cipher, Storage};
+
+const ACTOR: &str = "worker";
+
+/// Maximum number of memory rows fetched per consolidation pass
+const BATCH_SIZE: i64 = 1000;
+
+/// Periodic job that groups related memories (same source, same time window),
+/// summarizes each group into a consolidated memory and links the constituents to it.
+pub struct Consolidate {
+    layer: ConsolidateLayer,
+}
+
+impl Consolidate {
+    pub fn new(config: ConsolidateConfig) -> Result<Self> {
+        Ok(Self {
+            layer: config.build()?,
+        })
+    }
+
+    /// Run a consolidation pass for every tenant, returning the consolidated memories created.
+    /// Source text is decrypted and the summaries encrypted with `cipher`, if any.
+    pub async fn run(&self, pool: &PgPool, cipher: Option<&Cipher>) -> Result<Vec<Memory>> {
+        let mut consolidated = Vec::new();
+
+        for tenant in storage::tenants(pool).await? {
+            let storage = Storage::new(pool, &tenant).with_cipher(cipher);
+            consolidated.extend(self.run_tenant(&storage).await?);
+        }
+
+        Ok(consolidated)
+    }
+
+    /// Run a single consolidation pass within one tenant.
+    /// Only memories older than one window are considered so open windows are not split.
+    async fn run_tenant(&self, storage: &Storage<'_>) -> Result<Vec<Memory>> {
+        let before = chrono::Utc::now() - self.layer.config().window();
+        let rows = storage
+            .memories
+            .get_unconsolidated(before, BATCH_SIZE)
+            .await?;
+
+        let groups = self.layer.group(rows.iter().map(|row| {
+            ConsolidateItem::new(
+                row.memory.id.to_string(),
+                row.source_id.to_string(),
+                &row.source_text,
+                row.memory.created_at,
+            )
+        }));
+
+        if groups.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut trace = storage
+            .traces
+            .create(&Trace::builder().request_id("consolidate").build())
+            .await?;
+
+        let mut consolidated = Vec::new();
+
+        for group in groups {
+            let members: Vec<&SourcedMemory> = group
+                .iter()
+                .filter_map(|item| rows.iter().find(|r| r.memory.id.to_string() == item.id))
+                .collect();
+
+            let summary = self.layer.summarize(&group)?;
+            let memory = self.write(storage, &trace, &members, &summary).await?;
+            consolidated.push(memory);
+        }
+
+        trace.ended_at = Some(chrono::Utc::now());
+        storage.traces.update(&trace).await?;
+        Ok(consolidated)
+    }
+
+    async fn write(
+        &self,
+        storage: &Storage<'_>,
+        trace: &Trace,
+        members: &[&SourcedMemory],
+        summary: &str,
+    ) -> Result<Memory> {
+        let mut memory = members[0].memory.clone();
+        memory.id = uuid::Uuid::new_v4();
+        memory.embedding = None;
+        memory.consolidated_id = None;
+
+        for member in &members[1..] {
+            memory.merge(&member.memory);
+        }
+
+        let memory = storage.memories.create(&memory).await?;
+        let ids: Vec<uuid::Uuid> = members.iter().map(|m| m.memory.id).collect();
+        let hash = blake3::hash(summary.as_bytes())
+            .as_bytes()
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect::<String>();
+
+        let mut source_ids: Vec<uuid::Uuid> = members.iter().map(|m| m.source_id).collect();
+        source_ids.sort();
+        source_ids.dedup();
+
+        for source_id in source_ids {
+            let mut source = MemorySource::builder(memory.id, source_id, &hash);
+
+            // text is not stored for high sensitivity memories
+            if memory.sensitivity < Sensitivity::Sensitive {
+                source = source.text(summary);
+            }
+
+            storage.memory_sources.create(&source.build()).await?;
+        }
+
+        storage.memories.consolidate(&ids, memory.id).await?;
+        storage
+            .trace_actions
+            .create(
+                &TraceAction::builder(trace.id, memory.id, Target::Memory, Action::Create)
+                    .actor(ACTOR)
+                    .diff(serde_json::json!([{
+                        "path": "consolidated_from",
+                        "kind": "added",
+                        "before": null,
+                        "after": ids,
+                    }]))
+                    .build(),
+            )
+            .await?;
+
+        for id in &ids {
+            storage
+                .trace_actions
+                .create(
+                    &TraceAction::builder(trace.id, *id, Target::Memory, Action::Update)
+                        .actor(ACTOR)
+                        .diff(serde_json::json!([{
+                            "path": "consolidated_id",
+                            "kind": "added",
+                            "before": null,
+                            "after": memory.id,
+                        }]))
+                        .build(),
+                )
+                .await?;
+        }
+
+        Ok(memory)
+    }
+}
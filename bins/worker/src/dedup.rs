@@ -0,0 +1,142 @@
+use std::sync::Arc;
+
+use loom::error::Result;
+use loom::runtime::dedup::{DedupConfig, DedupDecision, DedupLayer, Duplicate};
+use loom::signal::{Emitter, NoopEmitter, Span};
+use sqlx::PgPool;
+use storage::entity::{Memory, MemorySource};
+use storage::{Cipher, DEFAULT_TENANT, Storage};
+
+/// Body of a `memory.create` event
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+pub struct CreateMemory {
+    #[serde(default = "CreateMemory::tenant")]
+    pub tenant: String,
+    pub memory: Memory,
+    pub text: String,
+    #[serde(default)]
+    pub sources: Vec<MemorySource>,
+}
+
+impl CreateMemory {
+    fn tenant() -> String {
+        DEFAULT_TENANT.to_string()
+    }
+}
+
+/// Worker step that embeds incoming memories and merges near-duplicates
+/// into the closest stored memory instead of inserting a new row.
+pub struct Dedup {
+    layer: DedupLayer,
+    signals: Arc<dyn Emitter + Send + Sync>,
+}
+
+impl Dedup {
+    pub fn new(config: DedupConfig) -> Result<Self> {
+        Ok(Self {
+            layer: config.build()?,
+            signals: Arc::new(NoopEmitter),
+        })
+    }
+
+    /// Emit `dedup.embed` and `dedup.decide` spans to `emitter`
+    pub fn with_emitter<E: Emitter + Send + Sync + 'static>(mut self, emitter: E) -> Self {
+        self.signals = Arc::new(emitter);
+        self
+    }
+
+    /// Insert or merge the memory within its tenant, linking its sources to
+    /// whichever memory was kept. Source text is encrypted with `cipher`, if any.
+    pub async fn run(
+        &self,
+        pool: &PgPool,
+        cipher: Option<&Cipher>,
+        body: CreateMemory,
+    ) -> Result<(DedupDecision, Memory)> {
+        let CreateMemory {
+            tenant,
+            mut memory,
+            text,
+            sources,
+        } = body;
+
+        let storage = Storage::new(pool, &tenant).with_cipher(cipher);
+        let config = self.layer.config();
+        let span = Span::new("dedup.embed").with_attr("text_len", text.len() as i64);
+        let embedding = match self.layer.embed(&text) {
+            Ok(v) => {
+                self.signals.emit(span.finish());
+                v
+            }
+            Err(err) => {
+                self.signals.emit(span.finish_with_error(err.to_string()));
+                return Err(err);
+            }
+        };
+
+        let span = Span::new("dedup.decide");
+        let similar = storage
+            .memories
+            .get_similar(
+                memory.scope_id,
+                &embedding,
+                config.threshold,
+                config.limit as i64,
+            )
+            .await?;
+
+        let decision = self.layer.decide(
+            similar
+                .iter()
+                .map(|m| Duplicate::new(m.memory.id.to_string(), m.similarity)),
+        );
+
+        let span = span
+            .with_attr("candidates", similar.len() as i64)
+            .with_attr("threshold", config.threshold);
+
+        self.signals.emit(match &decision {
+            DedupDecision::Insert => span.with_attr("decision", "insert").finish(),
+            DedupDecision::Merge(duplicate) => span
+                .with_attr("decision", "merge")
+                .with_attr("merged_into", duplicate.id.as_str())
+                .with_attr("similarity", duplicate.similarity)
+                .finish(),
+        });
+
+        memory.embedding = Some(embedding);
+
+        let memory = match &decision {
+            DedupDecision::Insert => storage.memories.create(&memory).await?,
+            DedupDecision::Merge(duplicate) => {
+                let mut existing = similar
+                    .into_iter()
+                    .map(|m| m.memory)
+                    .find(|m| m.id.to_string() == duplicate.id)
+                    .expect("merge target must be a candidate");
+
+                existing.merge(&memory);
+                storage
+                    .memories
+                    .update(&existing)
+                    .await?
+                    .unwrap_or(existing)
+            }
+        };
+
+        for mut source in sources {
+            source.memory_id = memory.id;
+
+            if storage
+                .memory_sources
+                .get(source.memory_id, source.source_id)
+                .await?
+                .is_none()
+            {
+                storage.memory_sources.create(&source).await?;
+            }
+        }
+
+        Ok((decision, memory))
+    }
+}
@@ -1,28 +1,216 @@
 mod config;
+mod consolidate;
+mod dedup;
+mod pipeline;
+mod scaling;
+mod signals;
 
-use events::{Key, MemoryAction};
+use std::time::{Duration, Instant};
+
+use events::{FacetAction, Key, MemoryAction};
+use loom::error::{Error, ErrorCode};
+use loom::runtime::consolidate::ConsolidateConfig;
+use loom::runtime::dedup::{DedupConfig, DedupDecision};
+use loom::signal::{Emitter, Level, Signal, SignalBroadcaster, Type};
+use sqlx::postgres::PgPoolOptions;
 
 use config::Config;
+use consolidate::Consolidate;
+use dedup::{CreateMemory, Dedup};
+use pipeline::{Ingest, Outcome};
+use scaling::Scaling;
+use signals::{SignalStore, SignalTrace};
 
 #[tokio::main]
-async fn main() -> Result<(), loom::error::Error> {
+async fn main() -> Result<(), Error> {
     let config = Config::from_env();
+    let pool = PgPoolOptions::new()
+        .max_connections(5)
+        .connect(&config.database_url)
+        .await?;
+
+    let cipher = config.cipher();
+    let signals = SignalTrace::new();
+    let store = SignalStore::new(config.signal_batch_size);
+    let dedup = Dedup::new(DedupConfig {
+        threshold: config.dedup_threshold,
+        ..Default::default()
+    })?
+    .with_emitter(
+        SignalBroadcaster::new()
+            .add(signals.clone())
+            .add(store.clone()),
+    );
+
     let socket = events::new(&config.rabbitmq_url)
         .with_app_id("loom[worker]")
         .with_queue(Key::memory(MemoryAction::Create))
+        .with_queue(Key::facet(FacetAction::Create))
+        .with_prefetch(config.prefetch)
         .connect()
         .await?;
 
-    let mut consumer = socket.consume(Key::memory(MemoryAction::Create)).await?;
+    let ingest = Ingest::new(&config, &pool, dedup, signals.clone(), store.clone())?;
+
+    let consolidate = Consolidate::new(ConsolidateConfig::default())?;
+    let mut interval = tokio::time::interval(Duration::from_secs(config.consolidate_interval));
+
+    let mut scaling = Scaling::new(
+        Key::memory(MemoryAction::Create),
+        Duration::from_secs(config.scaling_target_drain),
+    );
+    let mut scale = tokio::time::interval(Duration::from_secs(config.scaling_interval));
+
+    println!(
+        "waiting for messages on memory.create (pipeline: {})...",
+        ingest.stages().join(" -> ")
+    );
+
+    let consumed = async {
+        let mut consumer = socket.consume(Key::memory(MemoryAction::Create)).await?;
+        let producer = socket.produce();
+
+        loop {
+            tokio::select! {
+                res = consumer.dequeue::<CreateMemory>() => {
+                    let (delivery, event) = match res {
+                        None => break,
+                        // Already rejected by `dequeue`
+                        Some(Err(err)) if err.code().is_bad_arguments() => {
+                            eprintln!("Error decoding event: {}", err);
+                            continue;
+                        }
+                        Some(Err(err)) => return Err(err),
+                        Some(Ok(v)) => v,
+                    };
+
+                    let tenant = event.body.tenant.clone();
+                    let memory_id = event.body.memory.id;
+                    let text = event.body.text.clone();
+
+                    store.begin(&tenant);
+                    let started = Instant::now();
+                    let outcome = ingest
+                        .run(&pool, cipher.as_ref(), &producer, event.id, event.body)
+                        .await;
+
+                    scaling.record(started.elapsed());
+                    store.begin(storage::DEFAULT_TENANT);
+                    log_write("signals", store.commit(&pool).await);
+
+                    // Keep the input of runs that stored no memory, for `merc replay`
+                    match outcome {
+                        Err(err) => {
+                            // Retry once what may pass on redelivery, drop the rest
+                            let requeue = is_transient(&err) && !delivery.redelivered;
 
-    println!("waiting for messages on memory.create...");
+                            signals.emit(
+                                Signal::new()
+                                    .otype(Type::Event)
+                                    .level(Level::Error)
+                                    .name("worker.failed")
+                                    .attr("error", err.to_string())
+                                    .attr("requeue", requeue)
+                                    .build(),
+                            );
+                            log_write(
+                                "trace",
+                                signals
+                                    .flush(&pool, cipher.as_ref(), &tenant, memory_id, Some(&text))
+                                    .await,
+                            );
+                            eprintln!("Error processing memory {}: {}", memory_id, err);
+                            log_ack(consumer.nack(&delivery, requeue).await);
+                        }
+                        Ok(Outcome::Skipped(reason)) => {
+                            log_write(
+                                "trace",
+                                signals
+                                    .flush(&pool, cipher.as_ref(), &tenant, memory_id, Some(&text))
+                                    .await,
+                            );
+                            println!("skipped memory {}: {}", memory_id, reason);
+                            log_ack(consumer.ack(&delivery).await);
+                        }
+                        Ok(Outcome::Processed { decision, memory, facets }) => {
+                            let id = memory.as_ref().map(|m| m.id).unwrap_or(memory_id);
+                            let input = memory.is_none().then_some(text.as_str());
+                            log_write(
+                                "trace",
+                                signals
+                                    .flush(&pool, cipher.as_ref(), &tenant, id, input)
+                                    .await,
+                            );
 
-    while let Some(res) = consumer.dequeue::<String>().await {
-        let _ = match res {
-            Err(err) => return Err(err),
-            Ok(v) => v,
-        };
+                            match decision {
+                                None => println!("processed memory {} without persisting", id),
+                                Some(DedupDecision::Insert) => {
+                                    println!("inserted memory {} with {} facets", id, facets)
+                                }
+                                Some(DedupDecision::Merge(duplicate)) => println!(
+                                    "merged into memory {} (similarity {:.3}) with {} facets",
+                                    id, duplicate.similarity, facets
+                                ),
+                            }
+
+                            log_ack(consumer.ack(&delivery).await);
+                        }
+                    }
+                }
+                _ = scale.tick() => {
+                    match scaling.sample(&socket).await {
+                        Ok(sample) => store.emit(sample.signal()),
+                        Err(err) => eprintln!("Error sampling queue depth: {}", err),
+                    }
+
+                    log_write("signals", store.flush(&pool).await);
+                }
+                _ = interval.tick() => {
+                    log_write("signals", store.flush(&pool).await);
+
+                    match consolidate.run(&pool, cipher.as_ref()).await {
+                        Ok(consolidated) if !consolidated.is_empty() => {
+                            println!("consolidated {} memory groups", consolidated.len())
+                        }
+                        Ok(_) => {}
+                        Err(err) => eprintln!("Error consolidating memories: {}", err),
+                    }
+                }
+            }
+        }
+
+        Ok::<(), Error>(())
     }
+    .await;
 
-    Ok(())
+    // Flush signals and close connections however consuming stopped
+    let shutdown = ingest.shutdown().await;
+    consumed.and(shutdown)
+}
+
+/// Whether a failed message may succeed when redelivered: timeouts,
+/// cancellations and failures raised by a dependency (e.g. a dropped
+/// database connection), as opposed to messages rejected as invalid
+fn is_transient(err: &Error) -> bool {
+    match err.code() {
+        ErrorCode::Timeout | ErrorCode::Cancel => true,
+        ErrorCode::Unknown => err.inner().is_some(),
+        _ => false,
+    }
+}
+
+/// Log a failed ack or nack; the broker redelivers the message once the
+/// channel closes
+fn log_ack(res: Result<(), Error>) {
+    if let Err(err) = res {
+        eprintln!("Error acknowledging message: {}", err);
+    }
+}
+
+/// Log a failed write of analytics data, which shouldn't stop the worker
+/// from consuming
+fn log_write(what: &str, res: Result<usize, Error>) {
+    if let Err(err) = res {
+        eprintln!("Error writing {}: {}", what, err);
+    }
 }
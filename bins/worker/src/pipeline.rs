@@ -0,0 +1,264 @@
+use events::{Event, FacetAction, Key, SocketProducer};
+use futures::FutureExt;
+use loom::config::FileProvider;
+use loom::core::ident_path;
+use loom::error::Result;
+use loom::runtime::dedup::DedupDecision;
+use loom::runtime::eval::score::ScoreResult;
+use loom::runtime::facet::{Facet, FacetInput, FacetType, NamedEntity};
+use loom::runtime::pipeline::PipelineConfig;
+use loom::runtime::{Context, RConfig, Runtime, Span};
+use sqlx::PgPool;
+use storage::entity::{self, Memory};
+use storage::{Cipher, Storage};
+
+use crate::config::Config;
+use crate::dedup::{CreateMemory, Dedup};
+use crate::signals::{SignalStore, SignalTrace};
+
+/// Stages run by the worker itself rather than by a runtime layer
+const NATIVE: &[&str] = &["persist", "publish"];
+
+/// Outcome of running one memory through the pipeline
+pub enum Outcome {
+    /// A stage dropped the memory, with its reason
+    Skipped(String),
+    /// Every stage ran. `decision` and `memory` are only set if `persist` is enabled
+    Processed {
+        decision: Option<DedupDecision>,
+        memory: Option<Memory>,
+        facets: usize,
+    },
+}
+
+/// Ingestion pipeline run for every `memory.create` event.
+///
+/// Stages are declared in order under `pipeline.stages` of the config file:
+/// `prefilter`, `score`, `ner` and `facet` are runtime layers (configured under
+/// `layers.<name>`), `persist` deduplicates and stores the memory and its facets,
+/// `publish` emits a `facet.create` event per stored facet. Without a config file
+/// only `persist` runs.
+pub struct Ingest {
+    runtime: Runtime,
+    dedup: Dedup,
+    stages: Vec<String>,
+}
+
+impl Ingest {
+    pub fn new(
+        config: &Config,
+        pool: &PgPool,
+        dedup: Dedup,
+        signals: SignalTrace,
+        store: SignalStore,
+    ) -> Result<Self> {
+        let rconfig = match config.pipeline_config.as_deref() {
+            None => RConfig::new().build()?,
+            Some(path) => RConfig::new()
+                .with_provider(FileProvider::builder(path).build())
+                .build()?,
+        };
+
+        let pipeline = match rconfig.get(&ident_path!("pipeline")) {
+            None => PipelineConfig::new(["persist"]),
+            Some(_) => rconfig.bind_section(&ident_path!("pipeline"))?,
+        };
+
+        let pool = pool.clone();
+        let runtime = Runtime::new()
+            .emitter(signals)
+            .emitter(store.clone())
+            .on_shutdown("signals", move |_| {
+                async move { store.flush(&pool).await.map(|_| ()) }.boxed()
+            });
+
+        #[cfg(feature = "arrow")]
+        let runtime = runtime.codec(loom::runtime::ArrowCodec::new());
+
+        #[cfg(feature = "parquet")]
+        let runtime = runtime.codec(loom::runtime::ParquetCodec::new());
+
+        #[cfg(feature = "mock")]
+        let runtime = if config.mock_scorer {
+            runtime.scorer(
+                loom_testkit::MockScorer::new(loom_testkit::score_config())
+                    .default_scores(&[("task", 0.9)]),
+            )
+        } else {
+            runtime
+        };
+
+        let runtime = runtime.config(rconfig).pipeline(&pipeline)?.build();
+        let stages = pipeline.resolve(runtime.layers(), NATIVE)?;
+
+        Ok(Self {
+            runtime,
+            dedup,
+            stages,
+        })
+    }
+
+    /// Names of the enabled stages, in order
+    pub fn stages(&self) -> &[String] {
+        &self.stages
+    }
+
+    /// Run the runtime's shutdown hooks, writing the batched signals, and
+    /// flush its emitters
+    pub async fn shutdown(&self) -> Result<()> {
+        self.runtime.shutdown().await
+    }
+
+    /// Run every enabled stage on the memory, emitting a `pipeline.<stage>` span per stage.
+    /// A stage rejecting the memory (e.g. a score below threshold) stops the pipeline.
+    /// Layer stages join the trace `trace_id`, e.g. the id of the event being handled.
+    pub async fn run(
+        &self,
+        pool: &PgPool,
+        cipher: Option<&Cipher>,
+        producer: &SocketProducer<'_>,
+        trace_id: uuid::Uuid,
+        mut body: CreateMemory,
+    ) -> Result<Outcome> {
+        let storage = Storage::new(pool, &body.tenant).with_cipher(cipher);
+        let text = body.text.clone();
+        let trace_id = trace_id.to_string();
+        let mut score: Option<ScoreResult> = None;
+        let mut entities: Vec<NamedEntity> = Vec::new();
+        let mut pending: Vec<Facet> = Vec::new();
+        let mut stored: Vec<entity::Facet> = Vec::new();
+        let mut persisted: Option<(DedupDecision, Memory)> = None;
+
+        for stage in &self.stages {
+            let span =
+                Span::new(format!("pipeline.{}", stage)).with_attr("trace_id", trace_id.as_str());
+            let res = match stage.as_str() {
+                "prefilter" => self.runtime.eval::<Context<()>, ()>(
+                    "prefilter",
+                    Context::new(&text, ()).with_trace_id(&trace_id),
+                ),
+                "score" => self
+                    .runtime
+                    .eval::<Context<()>, ScoreResult>(
+                        "score",
+                        Context::new(&text, ()).with_trace_id(&trace_id),
+                    )
+                    .map(|result| {
+                        body.memory.score = result.score;
+                        score = Some(result);
+                    }),
+                "ner" => self
+                    .runtime
+                    .eval::<Context<()>, Vec<NamedEntity>>(
+                        "ner",
+                        Context::new(&text, ()).with_trace_id(&trace_id),
+                    )
+                    .map(|v| entities = v),
+                "facet" => self
+                    .runtime
+                    .eval::<Context<FacetInput>, Vec<Facet>>(
+                        "facet",
+                        Context::new(
+                            &text,
+                            FacetInput::new(score.clone().unwrap_or_default(), entities.clone()),
+                        )
+                        .with_trace_id(&trace_id),
+                    )
+                    .map(|v| pending.extend(v)),
+                "persist" => match self.dedup.run(pool, cipher, body.clone()).await {
+                    Err(err) => Err(err),
+                    Ok(v) => {
+                        persisted = Some(v);
+                        Ok(())
+                    }
+                },
+                "publish" => {
+                    async {
+                        for facet in &stored {
+                            producer
+                                .enqueue(Event::new(Key::facet(FacetAction::Create), facet))
+                                .await?;
+                        }
+
+                        Ok(())
+                    }
+                    .await
+                }
+                _ => unreachable!("stages are resolved on creation"),
+            };
+
+            match res {
+                Err(err) if err.code().is_cancel() => {
+                    let reason = err.to_string();
+                    self.runtime
+                        .emit(span.with_attr("skipped", reason.as_str()).finish());
+                    return Ok(Outcome::Skipped(reason));
+                }
+                Err(err) => {
+                    self.runtime.emit(span.finish_with_error(err.to_string()));
+                    return Err(err);
+                }
+                Ok(()) => self.runtime.emit(span.finish()),
+            }
+
+            // facets are stored as soon as both the memory and the facets exist,
+            // whichever of `persist` and `facet` runs last
+            if let Some((_, memory)) = &persisted {
+                for facet in pending.drain(..) {
+                    stored.push(
+                        storage
+                            .facets
+                            .create(&to_entity(memory.id, &facet)?)
+                            .await?,
+                    );
+                }
+            }
+        }
+
+        let (decision, memory) = match persisted {
+            None => (None, None),
+            Some((decision, memory)) => (Some(decision), Some(memory)),
+        };
+
+        Ok(Outcome::Processed {
+            decision,
+            memory,
+            facets: stored.len(),
+        })
+    }
+}
+
+/// Convert a runtime facet into a storage row of `memory_id`
+fn to_entity(memory_id: uuid::Uuid, facet: &Facet) -> Result<entity::Facet> {
+    let data = serde_json::to_vec(&facet.data)?;
+    let mut builder = entity::Facet::builder(memory_id, facet_type(facet.ty))
+        .confidence(facet.confidence)
+        .data(data);
+
+    if !facet.provenance.config_hash.is_empty() {
+        builder = builder.provenance(
+            &facet.provenance.config_hash,
+            &facet.provenance.model,
+            &facet.provenance.version,
+        );
+    }
+
+    Ok(builder.build())
+}
+
+fn facet_type(ty: FacetType) -> entity::FacetType {
+    match ty {
+        FacetType::Preference => entity::FacetType::Preference,
+        FacetType::Profile => entity::FacetType::Profile,
+        FacetType::Relationship => entity::FacetType::Relationship,
+        FacetType::Fact => entity::FacetType::Fact,
+        FacetType::Decision => entity::FacetType::Decision,
+        FacetType::Policy => entity::FacetType::Policy,
+        FacetType::Plan => entity::FacetType::Plan,
+        FacetType::Episode => entity::FacetType::Episode,
+        FacetType::ProjectContext => entity::FacetType::ProjectContext,
+        FacetType::Insight => entity::FacetType::Insight,
+        FacetType::Status => entity::FacetType::Status,
+        FacetType::Feedback => entity::FacetType::Feedback,
+    }
+}
@@ -0,0 +1,223 @@
+use std::time::{Duration, Instant};
+
+use events::{Key, Socket};
+use loom::error::Result;
+use loom::signal::{Level, Signal, Type};
+
+/// Samples a queue's depth and this worker's processing rate, the inputs
+/// HPA/KEDA-style autoscalers need to size the worker pool.
+///
+/// Each sample is published as a `worker.scaling` metric signal, so it lands
+/// in the `signals` table where e.g. KEDA's PostgreSQL scaler can query it.
+pub struct Scaling {
+    key: Key,
+    target_drain: Duration,
+    processed: u64,
+    busy: Duration,
+    since: Instant,
+}
+
+impl Scaling {
+    /// Sample `key`, sizing the pool so its backlog drains within `target_drain`
+    pub fn new(key: Key, target_drain: Duration) -> Self {
+        Self {
+            key,
+            target_drain,
+            processed: 0,
+            busy: Duration::ZERO,
+            since: Instant::now(),
+        }
+    }
+
+    /// Count a message as processed, after `busy` spent on it
+    pub fn record(&mut self, busy: Duration) {
+        self.processed += 1;
+        self.busy += busy;
+    }
+
+    /// Query the queue depth and compute the rate since the last sample
+    pub async fn sample(&mut self, socket: &Socket) -> Result<ScalingSample> {
+        let depth = socket.depth(self.key).await?;
+        let elapsed = self.since.elapsed().as_secs_f64();
+        let (rate, utilization) = if elapsed > 0.0 {
+            (
+                self.processed as f64 / elapsed,
+                (self.busy.as_secs_f64() / elapsed).min(1.0),
+            )
+        } else {
+            (0.0, 0.0)
+        };
+
+        self.processed = 0;
+        self.busy = Duration::ZERO;
+        self.since = Instant::now();
+
+        let backlog = backlog(
+            depth.messages,
+            depth.consumers,
+            socket.prefetch().unwrap_or(1),
+            utilization,
+        );
+
+        Ok(ScalingSample {
+            queue: self.key.queue().to_string(),
+            depth: backlog,
+            ready: depth.messages,
+            consumers: depth.consumers,
+            rate,
+            utilization,
+            desired: desired(
+                backlog,
+                depth.consumers,
+                rate,
+                self.target_drain.as_secs_f64(),
+            ),
+        })
+    }
+}
+
+/// One observation of a queue and the worker consuming it
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ScalingSample {
+    pub queue: String,
+    /// Messages not yet processed: those ready in the queue plus an estimate
+    /// of those delivered to consumers but not yet acked
+    pub depth: u32,
+    /// Messages ready in the queue, as the broker reports them
+    pub ready: u32,
+    /// Workers consuming the queue
+    pub consumers: u32,
+    /// Messages per second processed by this worker since the previous sample
+    pub rate: f64,
+    /// Share of the time since the previous sample this worker spent processing
+    pub utilization: f64,
+    /// Workers needed to drain the backlog within the target, assuming each
+    /// processes at this worker's rate
+    pub desired: u32,
+}
+
+impl ScalingSample {
+    /// Seconds to drain the backlog at the pool's current rate, if it is processing
+    pub fn lag(&self) -> Option<f64> {
+        let rate = self.rate * self.consumers.max(1) as f64;
+
+        (rate > 0.0).then(|| self.depth as f64 / rate)
+    }
+
+    pub fn signal(&self) -> Signal {
+        let mut signal = Signal::new()
+            .otype(Type::Metric)
+            .level(Level::Info)
+            .name("worker.scaling")
+            .attr("queue", self.queue.clone())
+            .attr("depth", self.depth)
+            .attr("ready", self.ready)
+            .attr("consumers", self.consumers)
+            .attr("rate", self.rate)
+            .attr("utilization", self.utilization)
+            .attr("desired", self.desired);
+
+        if let Some(lag) = self.lag() {
+            signal = signal.attr("lag", lag);
+        }
+
+        signal.build()
+    }
+}
+
+/// Messages not yet processed, given `ready` ones in the queue and
+/// `consumers` that each take up to `prefetch` unacked messages.
+///
+/// The broker only counts ready messages, so those already delivered are
+/// estimated: while messages wait in the queue every consumer's prefetch
+/// window is full; otherwise windows are assumed as full as this worker is
+/// busy.
+fn backlog(ready: u32, consumers: u32, prefetch: u16, utilization: f64) -> u32 {
+    let windows = consumers as f64 * prefetch as f64;
+    let in_flight = if ready > 0 {
+        windows
+    } else {
+        (windows * utilization).round()
+    };
+
+    ready.saturating_add(in_flight as u32)
+}
+
+/// Workers needed to drain `depth` messages within `target_drain` seconds.
+/// An empty queue needs a single worker, so an idle pool scales down; without
+/// a measured rate the pool grows by one while messages wait.
+fn desired(depth: u32, consumers: u32, rate: f64, target_drain: f64) -> u32 {
+    if depth == 0 {
+        return 1;
+    }
+
+    if rate <= 0.0 || target_drain <= 0.0 {
+        return consumers + 1;
+    }
+
+    ((depth as f64 / (rate * target_drain)).ceil() as u32).max(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(depth: u32, consumers: u32, rate: f64) -> ScalingSample {
+        ScalingSample {
+            queue: "memory.create".to_string(),
+            depth,
+            ready: depth,
+            consumers,
+            rate,
+            utilization: 1.0,
+            desired: desired(depth, consumers, rate, 60.0),
+        }
+    }
+
+    #[test]
+    fn backlog_counts_in_flight_messages() {
+        // Nothing ready, but both consumers are busy with full windows
+        assert_eq!(backlog(0, 2, 10, 1.0), 20);
+        assert_eq!(backlog(0, 2, 10, 0.5), 10);
+        assert_eq!(backlog(0, 2, 10, 0.0), 0);
+        // Messages only wait in the queue once every window is full
+        assert_eq!(backlog(5, 2, 10, 0.1), 25);
+    }
+
+    #[test]
+    fn desired_scales_up_with_busy_consumers() {
+        // Every message sits in a prefetch window, but takes 10s to process
+        let depth = backlog(0, 2, 10, 1.0);
+
+        assert!(depth > 0);
+        assert_eq!(desired(depth, 2, 0.1, 60.0), 4);
+    }
+
+    #[test]
+    fn desired_scales_down_when_idle() {
+        assert_eq!(desired(0, 5, 2.0, 60.0), 1);
+        assert_eq!(desired(0, 0, 0.0, 60.0), 1);
+    }
+
+    #[test]
+    fn desired_drains_backlog_within_target() {
+        // 600 messages at 2/s per worker drain in 60s with 5 workers
+        assert_eq!(desired(600, 1, 2.0, 60.0), 5);
+        assert_eq!(desired(601, 1, 2.0, 60.0), 6);
+        assert_eq!(desired(10, 4, 2.0, 60.0), 1);
+    }
+
+    #[test]
+    fn desired_grows_without_a_rate() {
+        assert_eq!(desired(10, 3, 0.0, 60.0), 4);
+        assert_eq!(desired(10, 3, 2.0, 0.0), 4);
+    }
+
+    #[test]
+    fn lag_uses_pool_rate() {
+        assert_eq!(sample(100, 4, 5.0).lag(), Some(5.0));
+        assert_eq!(sample(100, 0, 5.0).lag(), Some(20.0));
+        assert_eq!(sample(100, 4, 0.0).lag(), None);
+        assert_eq!(sample(0, 4, 5.0).lag(), Some(0.0));
+    }
+}
@@ -0,0 +1,325 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use loom::error::Result;
+use loom::signal::{Emitter, Level, Signal};
+use sqlx::PgPool;
+use storage::entity::{self, Action, Target, Trace, TraceAction};
+use storage::{Cipher, DEFAULT_TENANT, Storage};
+
+const ACTOR: &str = "worker";
+
+/// Bridges loom signals into storage traces.
+///
+/// Signals emitted while a memory is processed (spans, errors) are buffered,
+/// then [`SignalTrace::flush`] persists them as `signal` trace actions on that
+/// memory under a single trace, so they can be inspected per memory.
+#[derive(Clone, Default)]
+pub struct SignalTrace {
+    signals: Arc<Mutex<Vec<Signal>>>,
+}
+
+impl SignalTrace {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Take every buffered signal, leaving the buffer empty
+    pub fn drain(&self) -> Vec<Signal> {
+        self.signals
+            .lock()
+            .map(|mut s| std::mem::take(&mut *s))
+            .unwrap_or_default()
+    }
+
+    /// Persist the buffered signals as trace actions on `memory_id` within `tenant`.
+    /// The trace is marked as errored if any signal was emitted at error level.
+    /// With `input`, the memory's text is kept on the trace as an `input`
+    /// action, encrypted with `cipher` if any, so a run that stored nothing
+    /// (e.g. dropped by the pre-filter) can still be replayed.
+    /// Returns the number of signals written.
+    pub async fn flush(
+        &self,
+        pool: &PgPool,
+        cipher: Option<&Cipher>,
+        tenant: &str,
+        memory_id: uuid::Uuid,
+        input: Option<&str>,
+    ) -> Result<usize> {
+        let signals = self.drain();
+
+        if signals.is_empty() && input.is_none() {
+            return Ok(0);
+        }
+
+        let storage = Storage::new(pool, tenant);
+        let mut trace = Trace::builder().request_id("memory.create");
+
+        if let Some(signal) = signals.iter().find(|s| s.level() == Level::Error) {
+            trace = trace.error(signal.name());
+        }
+
+        let mut trace = storage.traces.create(&trace.build()).await?;
+
+        for signal in &signals {
+            storage
+                .trace_actions
+                .create(
+                    &TraceAction::builder(trace.id, memory_id, Target::Memory, Action::Signal)
+                        .actor(ACTOR)
+                        .diff(serde_json::json!({
+                            "type": signal.otype().as_str(),
+                            "level": signal.level().as_str(),
+                            "name": signal.name(),
+                            "attributes": signal.attributes(),
+                            "emitted_at": emitted_at(signal),
+                        }))
+                        .build(),
+                )
+                .await?;
+        }
+
+        if let Some(text) = input {
+            let text = match cipher {
+                Some(cipher) => cipher.encrypt(text)?,
+                None => text.to_string(),
+            };
+
+            storage
+                .trace_actions
+                .create(
+                    &TraceAction::builder(trace.id, memory_id, Target::Source, Action::Input)
+                        .actor(ACTOR)
+                        .diff(serde_json::json!({ "text": text }))
+                        .build(),
+                )
+                .await?;
+        }
+
+        trace.ended_at = Some(chrono::Utc::now());
+        storage.traces.update(&trace).await?;
+        Ok(signals.len())
+    }
+}
+
+impl Emitter for SignalTrace {
+    fn emit(&self, signal: Signal) {
+        if let Ok(mut signals) = self.signals.lock() {
+            signals.push(signal);
+        }
+    }
+}
+
+/// Persists loom signals to the `signals` table in batches, for SQL analytics
+/// over historical ingestion and eval telemetry.
+///
+/// Signals are buffered per tenant: those emitted after
+/// [`SignalStore::begin`] belong to that tenant until the next `begin`. A
+/// tenant's batch is written by [`SignalStore::commit`] once it holds
+/// `batch_size` signals, or by [`SignalStore::flush`].
+#[derive(Clone)]
+pub struct SignalStore {
+    batches: Arc<Mutex<Batches>>,
+}
+
+impl SignalStore {
+    pub fn new(batch_size: usize) -> Self {
+        Self {
+            batches: Arc::new(Mutex::new(Batches::new(batch_size))),
+        }
+    }
+
+    /// Number of signals not yet written
+    pub fn len(&self) -> usize {
+        self.batches.lock().map(|b| b.len()).unwrap_or_default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Attribute signals emitted from now on to `tenant`
+    pub fn begin(&self, tenant: &str) {
+        if let Ok(mut batches) = self.batches.lock() {
+            batches.begin(tenant);
+        }
+    }
+
+    /// Write every batch that is full.
+    /// Returns the number of signals written.
+    pub async fn commit(&self, pool: &PgPool) -> Result<usize> {
+        let full = self
+            .batches
+            .lock()
+            .map(|mut b| b.take_full())
+            .unwrap_or_default();
+
+        self.write(pool, full).await
+    }
+
+    /// Write every batch regardless of size.
+    /// Returns the number of signals written.
+    pub async fn flush(&self, pool: &PgPool) -> Result<usize> {
+        let batches = self
+            .batches
+            .lock()
+            .map(|mut b| b.take_all())
+            .unwrap_or_default();
+
+        self.write(pool, batches).await
+    }
+
+    async fn write(&self, pool: &PgPool, batches: Vec<(String, Vec<Signal>)>) -> Result<usize> {
+        let mut written = 0;
+
+        for (tenant, signals) in batches {
+            let rows = signals
+                .iter()
+                .map(|signal| {
+                    entity::Signal::builder(
+                        signal.otype().as_str(),
+                        signal.level().as_str(),
+                        signal.name(),
+                    )
+                    .attributes(serde_json::json!(signal.attributes()))
+                    .emitted_at(emitted_at(signal))
+                    .build()
+                })
+                .collect::<Vec<_>>();
+
+            written += Storage::new(pool, &tenant)
+                .signals
+                .create_many(&rows)
+                .await? as usize;
+        }
+
+        Ok(written)
+    }
+}
+
+impl Emitter for SignalStore {
+    fn emit(&self, signal: Signal) {
+        if let Ok(mut batches) = self.batches.lock() {
+            batches.push(signal);
+        }
+    }
+}
+
+/// Signals waiting to be written, keyed by the tenant they belong to
+struct Batches {
+    batch_size: usize,
+    tenant: String,
+    by_tenant: HashMap<String, Vec<Signal>>,
+}
+
+impl Batches {
+    fn new(batch_size: usize) -> Self {
+        Self {
+            batch_size: batch_size.max(1),
+            tenant: DEFAULT_TENANT.to_string(),
+            by_tenant: HashMap::new(),
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.by_tenant.values().map(Vec::len).sum()
+    }
+
+    fn begin(&mut self, tenant: &str) {
+        self.tenant = tenant.to_string();
+    }
+
+    fn push(&mut self, signal: Signal) {
+        self.by_tenant
+            .entry(self.tenant.clone())
+            .or_default()
+            .push(signal);
+    }
+
+    /// Remove and return the batches holding at least `batch_size` signals
+    fn take_full(&mut self) -> Vec<(String, Vec<Signal>)> {
+        let tenants = self
+            .by_tenant
+            .iter()
+            .filter(|(_, signals)| signals.len() >= self.batch_size)
+            .map(|(tenant, _)| tenant.clone())
+            .collect::<Vec<_>>();
+
+        tenants
+            .into_iter()
+            .filter_map(|tenant| self.by_tenant.remove_entry(&tenant))
+            .collect()
+    }
+
+    /// Remove and return every non-empty batch
+    fn take_all(&mut self) -> Vec<(String, Vec<Signal>)> {
+        self.by_tenant
+            .drain()
+            .filter(|(_, signals)| !signals.is_empty())
+            .collect()
+    }
+}
+
+fn emitted_at(signal: &Signal) -> chrono::DateTime<chrono::Utc> {
+    chrono::DateTime::from(signal.created_at())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn signal(name: &str) -> Signal {
+        Signal::new().name(name).build()
+    }
+
+    fn names(batch: &[Signal]) -> Vec<&str> {
+        batch.iter().map(|s| s.name()).collect()
+    }
+
+    #[test]
+    fn signals_are_batched_by_tenant() {
+        let mut batches = Batches::new(2);
+        batches.push(signal("worker.scaling"));
+        batches.begin("acme");
+        batches.push(signal("pipeline.score"));
+        batches.begin("globex");
+        batches.push(signal("pipeline.persist"));
+        batches.begin("acme");
+        batches.push(signal("pipeline.publish"));
+        assert_eq!(batches.len(), 4);
+
+        let full = batches.take_full();
+        assert_eq!(full.len(), 1);
+        assert_eq!(full[0].0, "acme");
+        assert_eq!(names(&full[0].1), ["pipeline.score", "pipeline.publish"]);
+        assert_eq!(batches.len(), 2);
+
+        let mut rest = batches.take_all();
+        rest.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(rest.len(), 2);
+        assert_eq!(rest[0].0, DEFAULT_TENANT);
+        assert_eq!(names(&rest[0].1), ["worker.scaling"]);
+        assert_eq!(rest[1].0, "globex");
+        assert_eq!(batches.len(), 0);
+    }
+
+    #[test]
+    fn take_full_keeps_partial_batches() {
+        let mut batches = Batches::new(3);
+        batches.begin("acme");
+        batches.push(signal("pipeline.score"));
+        batches.push(signal("pipeline.persist"));
+
+        assert!(batches.take_full().is_empty());
+        assert_eq!(batches.len(), 2);
+    }
+
+    #[test]
+    fn batch_size_is_at_least_one() {
+        let mut batches = Batches::new(0);
+        batches.push(signal("worker.scaling"));
+
+        assert_eq!(batches.take_full().len(), 1);
+        assert!(batches.take_all().is_empty());
+    }
+}
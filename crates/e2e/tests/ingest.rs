@@ -0,0 +1,117 @@
+use e2e::Harness;
+use events::{Event, FacetAction, Key, MemoryAction};
+use loom::error::Result;
+use storage::entity::{Action, Facet, FacetType, Memory, Target};
+
+const PIPELINE: &str = r#"
+pipeline:
+  stages:
+    - prefilter
+    - score
+    - facet
+    - persist
+    - publish
+
+layers:
+  prefilter:
+    deny: ["unsubscribe"]
+"#;
+
+fn create(memory: &Memory, text: &str) -> Event<serde_json::Value> {
+    Event::new(
+        Key::memory(MemoryAction::Create),
+        serde_json::json!({ "memory": memory, "text": text }),
+    )
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn ingests_memories_end_to_end() -> Result<()> {
+    let harness = Harness::start(PIPELINE).await?;
+    let socket = harness
+        .socket(&[
+            Key::memory(MemoryAction::Create),
+            Key::facet(FacetAction::Create),
+        ])
+        .await?;
+
+    let h = &harness;
+    let scope_id = uuid::Uuid::new_v4();
+    let accepted = Memory::builder(scope_id).build();
+    let rejected = Memory::builder(scope_id).build();
+    let producer = socket.produce();
+
+    producer
+        .enqueue(create(&accepted, "let's deploy the new release on friday"))
+        .await?;
+    producer
+        .enqueue(create(&rejected, "click here to unsubscribe"))
+        .await?;
+
+    // accepted memory is scored, stored with its facets, and its facets are published
+    let memory = harness
+        .wait_for("accepted memory", move || async move {
+            Ok(h.storage().memories.get(accepted.id).await?)
+        })
+        .await?;
+
+    assert!(memory.score > 0.5);
+    assert!(memory.embedding.is_some());
+
+    let facets = harness.storage().facets.get_by_memory(memory.id).await?;
+    assert_eq!(facets.len(), 1);
+    assert_eq!(facets[0].ty, FacetType::Plan);
+
+    let mut consumer = socket.consume(Key::facet(FacetAction::Create)).await?;
+    let (_, event) = tokio::time::timeout(e2e::TIMEOUT, consumer.dequeue::<Facet>())
+        .await?
+        .expect("facet queue closed")?;
+
+    assert_eq!(event.body.id, facets[0].id);
+    assert_eq!(event.body.memory_id, memory.id);
+
+    // every stage leaves a signal on the memory
+    let actions = harness
+        .wait_for("pipeline signals", move || async move {
+            let actions = h
+                .storage()
+                .trace_actions
+                .get_by_target(memory.id, Target::Memory)
+                .await?;
+
+            Ok((actions.len() >= 5).then_some(actions))
+        })
+        .await?;
+
+    assert!(actions.iter().all(|a| a.action == Action::Signal));
+
+    // rejected memory is dropped by the pre-filter but its signals are kept
+    let actions = harness
+        .wait_for("pre-filter signal", move || async move {
+            let actions = h
+                .storage()
+                .trace_actions
+                .get_by_target(rejected.id, Target::Memory)
+                .await?;
+
+            Ok((!actions.is_empty()).then_some(actions))
+        })
+        .await?;
+
+    assert_eq!(
+        actions[0].diff.as_ref().unwrap()["name"],
+        "pipeline.prefilter"
+    );
+    assert!(harness.storage().memories.get(rejected.id).await?.is_none());
+
+    // the api serves what the worker stored
+    let res: serde_json::Value = reqwest::get(format!(
+        "{}/memories/relevant?q=deploy&scope_id={}",
+        harness.api_url, scope_id
+    ))
+    .await?
+    .json()
+    .await?;
+
+    assert_eq!(res["memories"][0]["id"], memory.id.to_string());
+    Ok(())
+}
@@ -1,5 +1,7 @@
 use futures_lite::StreamExt;
-use loom_error::Result;
+use lapin::message::Delivery;
+use lapin::options;
+use loom_error::{Error, ErrorCode, Result};
 
 use crate::{Event, Socket};
 
@@ -14,19 +16,50 @@ impl<'a> SocketConsumer<'a> {
         &self.socket
     }
 
+    /// Wait for the next event. Deliveries must be [`ack`](Self::ack)ed or
+    /// [`nack`](Self::nack)ed once handled. Ones that don't decode are
+    /// rejected without requeueing and returned as a `BadArguments` error,
+    /// after which consuming can go on.
     pub async fn dequeue<T: for<'b> serde::Deserialize<'b>>(
         &mut self,
-    ) -> Option<Result<(lapin::message::Delivery, Event<T>)>> {
+    ) -> Option<Result<(Delivery, Event<T>)>> {
         let delivery = match self.consumer.next().await? {
             Err(err) => return Some(Err(err.into())),
             Ok(v) => v,
         };
 
         let data: Event<T> = match serde_json::from_slice(&delivery.data) {
-            Err(err) => return Some(Err(err.into())),
+            Err(err) => {
+                if let Err(err) = self.nack(&delivery, false).await {
+                    return Some(Err(err));
+                }
+
+                return Some(Err(Error::builder()
+                    .code(ErrorCode::BadArguments)
+                    .message(format!("invalid event: {}", err))
+                    .build()));
+            }
             Ok(v) => v,
         };
 
         Some(Ok((delivery, data)))
     }
+
+    /// Acknowledge `delivery`, so the broker drops it
+    pub async fn ack(&self, delivery: &Delivery) -> Result<()> {
+        delivery.ack(options::BasicAckOptions::default()).await?;
+        Ok(())
+    }
+
+    /// Reject `delivery`, putting it back in the queue if `requeue`, else
+    /// dropping it
+    pub async fn nack(&self, delivery: &Delivery, requeue: bool) -> Result<()> {
+        delivery
+            .nack(options::BasicNackOptions {
+                requeue,
+                ..Default::default()
+            })
+            .await?;
+        Ok(())
+    }
 }
@@ -21,10 +21,13 @@ impl Key {
         }
     }
 
+    /// Queue name, unique across exchanges (e.g. `memory.create`)
     pub fn queue(&self) -> &str {
         match self {
-            Self::Memory(v) => v.name(),
-            Self::Facet(v) => v.name(),
+            Self::Memory(MemoryAction::Create) => "memory.create",
+            Self::Memory(MemoryAction::Update) => "memory.update",
+            Self::Facet(FacetAction::Create) => "facet.create",
+            Self::Facet(FacetAction::Update) => "facet.update",
         }
     }
 }
@@ -11,6 +11,7 @@ pub struct Socket {
     conn: Arc<Connection>,
     channel: Arc<Channel>,
     queues: HashMap<Key, lapin::Queue>,
+    prefetch: Option<u16>,
 }
 
 impl Socket {
@@ -30,6 +31,12 @@ impl Socket {
         self.queues.get(&key)
     }
 
+    /// Messages the broker delivers to each consumer ahead of their acks,
+    /// if limited
+    pub fn prefetch(&self) -> Option<u16> {
+        self.prefetch
+    }
+
     pub async fn consume(&self, key: Key) -> Result<SocketConsumer<'_>> {
         if !self.queues.contains_key(&key) {
             return Err(Error::builder().message("queue not found").build());
@@ -54,12 +61,53 @@ impl Socket {
     pub fn produce(&self) -> SocketProducer<'_> {
         SocketProducer { socket: self }
     }
+
+    /// Fetch the current depth of a declared queue from the broker
+    pub async fn depth(&self, key: Key) -> Result<QueueDepth> {
+        if !self.queues.contains_key(&key) {
+            return Err(Error::builder().message("queue not found").build());
+        }
+
+        let queue = self
+            .channel()
+            .queue_declare(
+                key.queue(),
+                options::QueueDeclareOptions {
+                    passive: true,
+                    ..Default::default()
+                },
+                types::FieldTable::default(),
+            )
+            .await?;
+
+        Ok(QueueDepth {
+            messages: queue.message_count(),
+            consumers: queue.consumer_count(),
+        })
+    }
+
+    /// Close the channel, then the connection
+    pub async fn close(&self) -> Result<()> {
+        self.channel().close(200, "shutdown").await?;
+        self.conn().close(200, "shutdown").await?;
+        Ok(())
+    }
+}
+
+/// Snapshot of a queue's backlog
+#[derive(Debug, Copy, Clone, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub struct QueueDepth {
+    /// Messages ready for delivery, excluding those delivered but not yet
+    /// acked, which the broker doesn't report here
+    pub messages: u32,
+    pub consumers: u32,
 }
 
 pub struct SocketOptions {
     app_id: String,
     uri: String,
     queues: Vec<Key>,
+    prefetch: Option<u16>,
 }
 
 impl SocketOptions {
@@ -68,6 +116,7 @@ impl SocketOptions {
             app_id: String::new(),
             uri: uri.to_string(),
             queues: vec![],
+            prefetch: None,
         }
     }
 
@@ -81,11 +130,24 @@ impl SocketOptions {
         self
     }
 
+    /// Deliver at most `count` unacked messages to each consumer, leaving the
+    /// rest ready in the queue for other consumers
+    pub fn with_prefetch(mut self, count: u16) -> Self {
+        self.prefetch = Some(count);
+        self
+    }
+
     pub async fn connect(self) -> Result<Socket> {
         let conn = Connection::connect(&self.uri, ConnectionProperties::default()).await?;
         let channel = conn.create_channel().await?;
         let mut queues = HashMap::new();
 
+        if let Some(count) = self.prefetch {
+            channel
+                .basic_qos(count, options::BasicQosOptions::default())
+                .await?;
+        }
+
         for key in self.queues {
             channel
                 .exchange_declare(
@@ -122,6 +184,7 @@ impl SocketOptions {
             conn: Arc::new(conn),
             channel: Arc::new(channel),
             queues,
+            prefetch: self.prefetch,
         })
     }
 }
@@ -0,0 +1,7 @@
+-- Add score provenance to facets
+ALTER TABLE facets ADD COLUMN config_hash TEXT;
+ALTER TABLE facets ADD COLUMN model TEXT;
+ALTER TABLE facets ADD COLUMN version TEXT;
+
+-- Indexes
+CREATE INDEX idx_facets_config_hash ON facets(config_hash);
@@ -0,0 +1,6 @@
+-- Add audit details to trace_actions
+ALTER TABLE trace_actions ADD COLUMN actor TEXT;
+ALTER TABLE trace_actions ADD COLUMN diff JSONB;
+
+-- Indexes
+CREATE INDEX idx_trace_actions_actor ON trace_actions(actor);
@@ -0,0 +1,2 @@
+-- Enable pgvector for embedding similarity search
+CREATE EXTENSION IF NOT EXISTS vector;
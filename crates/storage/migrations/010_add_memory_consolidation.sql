@@ -0,0 +1,5 @@
+-- Link consolidated memories to the memory that replaced them
+ALTER TABLE memories ADD COLUMN consolidated_id UUID REFERENCES memories(id) ON DELETE SET NULL;
+
+-- Indexes
+CREATE INDEX idx_memories_consolidated_id ON memories(consolidated_id);
@@ -0,0 +1,23 @@
+-- Scope every row to a tenant, existing rows belong to the default tenant
+ALTER TABLE memories ADD COLUMN tenant_id TEXT NOT NULL DEFAULT 'default';
+ALTER TABLE facets ADD COLUMN tenant_id TEXT NOT NULL DEFAULT 'default';
+ALTER TABLE sources ADD COLUMN tenant_id TEXT NOT NULL DEFAULT 'default';
+ALTER TABLE memory_sources ADD COLUMN tenant_id TEXT NOT NULL DEFAULT 'default';
+ALTER TABLE traces ADD COLUMN tenant_id TEXT NOT NULL DEFAULT 'default';
+ALTER TABLE trace_actions ADD COLUMN tenant_id TEXT NOT NULL DEFAULT 'default';
+
+-- New rows must name their tenant explicitly
+ALTER TABLE memories ALTER COLUMN tenant_id DROP DEFAULT;
+ALTER TABLE facets ALTER COLUMN tenant_id DROP DEFAULT;
+ALTER TABLE sources ALTER COLUMN tenant_id DROP DEFAULT;
+ALTER TABLE memory_sources ALTER COLUMN tenant_id DROP DEFAULT;
+ALTER TABLE traces ALTER COLUMN tenant_id DROP DEFAULT;
+ALTER TABLE trace_actions ALTER COLUMN tenant_id DROP DEFAULT;
+
+-- Indexes
+CREATE INDEX idx_memories_tenant_id ON memories(tenant_id);
+CREATE INDEX idx_facets_tenant_id ON facets(tenant_id);
+CREATE INDEX idx_sources_tenant_id ON sources(tenant_id);
+CREATE INDEX idx_memory_sources_tenant_id ON memory_sources(tenant_id);
+CREATE INDEX idx_traces_tenant_id ON traces(tenant_id);
+CREATE INDEX idx_trace_actions_tenant_id ON trace_actions(tenant_id);
@@ -0,0 +1,17 @@
+-- Create signals table, persisted loom signals for analytics
+CREATE TABLE signals (
+    id UUID PRIMARY KEY NOT NULL,
+    tenant_id TEXT NOT NULL,
+    otype TEXT NOT NULL,
+    level TEXT NOT NULL,
+    name TEXT NOT NULL,
+    attributes JSONB NOT NULL DEFAULT '{}',
+    emitted_at TIMESTAMPTZ NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Indexes
+CREATE INDEX idx_signals_tenant_id ON signals(tenant_id);
+CREATE INDEX idx_signals_name ON signals(name);
+CREATE INDEX idx_signals_level ON signals(level);
+CREATE INDEX idx_signals_emitted_at ON signals(emitted_at);
@@ -6,6 +6,9 @@ pub struct FacetBuilder {
     ty: FacetType,
     confidence: f32,
     data: Vec<u8>,
+    config_hash: Option<String>,
+    model: Option<String>,
+    version: Option<String>,
 }
 
 impl FacetBuilder {
@@ -15,6 +18,9 @@ impl FacetBuilder {
             ty,
             confidence: 1.0,
             data: Vec::new(),
+            config_hash: None,
+            model: None,
+            version: None,
         }
     }
 
@@ -28,6 +34,13 @@ impl FacetBuilder {
         self
     }
 
+    pub fn provenance(mut self, config_hash: &str, model: &str, version: &str) -> Self {
+        self.config_hash = Some(config_hash.to_string());
+        self.model = Some(model.to_string());
+        self.version = Some(version.to_string());
+        self
+    }
+
     pub fn build(self) -> Facet {
         let now = chrono::Utc::now();
         Facet {
@@ -36,6 +49,9 @@ impl FacetBuilder {
             ty: self.ty,
             confidence: self.confidence,
             data: self.data,
+            config_hash: self.config_hash,
+            model: self.model,
+            version: self.version,
             created_at: now,
             updated_at: now,
         }
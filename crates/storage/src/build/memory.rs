@@ -9,6 +9,7 @@ pub struct MemoryBuilder {
     sensitivity: Sensitivity,
     tags: Vec<String>,
     embedding: Option<Vec<f32>>,
+    consolidated_id: Option<uuid::Uuid>,
     expires_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
@@ -22,6 +23,7 @@ impl MemoryBuilder {
             sensitivity: Sensitivity::Low,
             tags: Vec::new(),
             embedding: None,
+            consolidated_id: None,
             expires_at: None,
         }
     }
@@ -61,6 +63,11 @@ impl MemoryBuilder {
         self
     }
 
+    pub fn consolidated_id(mut self, consolidated_id: uuid::Uuid) -> Self {
+        self.consolidated_id = Some(consolidated_id);
+        self
+    }
+
     pub fn expires_at(mut self, expires_at: chrono::DateTime<chrono::Utc>) -> Self {
         self.expires_at = Some(expires_at);
         self
@@ -77,6 +84,7 @@ impl MemoryBuilder {
             sensitivity: self.sensitivity,
             tags: self.tags,
             embedding: self.embedding,
+            consolidated_id: self.consolidated_id,
             expires_at: self.expires_at,
             created_at: now,
             updated_at: now,
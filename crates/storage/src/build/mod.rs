@@ -1,6 +1,7 @@
 mod facet;
 mod memory;
 mod memory_source;
+mod signal;
 mod source;
 mod trace;
 mod trace_action;
@@ -8,6 +9,7 @@ mod trace_action;
 pub use facet::*;
 pub use memory::*;
 pub use memory_source::*;
+pub use signal::*;
 pub use source::*;
 pub use trace::*;
 pub use trace_action::*;
@@ -0,0 +1,50 @@
+use crate::entity::Signal;
+
+#[derive(Debug, Clone)]
+pub struct SignalBuilder {
+    otype: String,
+    level: String,
+    name: String,
+    attributes: sqlx::types::JsonValue,
+    emitted_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl SignalBuilder {
+    pub fn new(
+        otype: impl Into<String>,
+        level: impl Into<String>,
+        name: impl Into<String>,
+    ) -> Self {
+        Self {
+            otype: otype.into(),
+            level: level.into(),
+            name: name.into(),
+            attributes: sqlx::types::JsonValue::Object(Default::default()),
+            emitted_at: None,
+        }
+    }
+
+    pub fn attributes(mut self, attributes: sqlx::types::JsonValue) -> Self {
+        self.attributes = attributes;
+        self
+    }
+
+    pub fn emitted_at(mut self, emitted_at: chrono::DateTime<chrono::Utc>) -> Self {
+        self.emitted_at = Some(emitted_at);
+        self
+    }
+
+    pub fn build(self) -> Signal {
+        let now = chrono::Utc::now();
+
+        Signal {
+            id: uuid::Uuid::new_v4(),
+            otype: self.otype,
+            level: self.level,
+            name: self.name,
+            attributes: self.attributes,
+            emitted_at: self.emitted_at.unwrap_or(now),
+            created_at: now,
+        }
+    }
+}
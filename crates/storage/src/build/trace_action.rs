@@ -6,6 +6,8 @@ pub struct TraceActionBuilder {
     target_id: uuid::Uuid,
     target: Target,
     action: Action,
+    actor: Option<String>,
+    diff: Option<sqlx::types::JsonValue>,
 }
 
 impl TraceActionBuilder {
@@ -20,15 +22,29 @@ impl TraceActionBuilder {
             target_id,
             target,
             action,
+            actor: None,
+            diff: None,
         }
     }
 
+    pub fn actor(mut self, actor: impl Into<String>) -> Self {
+        self.actor = Some(actor.into());
+        self
+    }
+
+    pub fn diff(mut self, diff: sqlx::types::JsonValue) -> Self {
+        self.diff = Some(diff);
+        self
+    }
+
     pub fn build(self) -> TraceAction {
         TraceAction {
             trace_id: self.trace_id,
             target_id: self.target_id,
             target: self.target,
             action: self.action,
+            actor: self.actor,
+            diff: self.diff,
             created_at: chrono::Utc::now(),
         }
     }
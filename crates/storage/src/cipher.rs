@@ -0,0 +1,305 @@
+use std::collections::HashMap;
+
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+
+const PREFIX: &str = "enc";
+const NONCE_LEN: usize = 12;
+
+/// SQL `LIKE` pattern matching encrypted values, which full text search must skip
+pub(crate) const ENCRYPTED_PATTERN: &str = "enc:%";
+
+/// Errors raised while parsing keys or encrypting/decrypting text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CipherError {
+    /// A key spec is malformed or a key is not 32 bytes of base64
+    InvalidKey(String),
+    /// The ciphertext was written with a key that is not configured
+    UnknownKey(String),
+    /// The ciphertext is malformed or fails authentication
+    Decrypt,
+    /// The plaintext could not be encrypted
+    Encrypt,
+}
+
+impl std::fmt::Display for CipherError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidKey(v) => write!(f, "invalid encryption key: {}", v),
+            Self::UnknownKey(id) => write!(f, "unknown encryption key id '{}'", id),
+            Self::Decrypt => write!(f, "failed to decrypt text"),
+            Self::Encrypt => write!(f, "failed to encrypt text"),
+        }
+    }
+}
+
+impl std::error::Error for CipherError {}
+
+/// AES-256-GCM encryption for memory text at rest.
+///
+/// Values are stored as `enc:<key id>:<base64(nonce || ciphertext)>`. New values
+/// are always written with the current key; older keys are kept for decryption
+/// only so rows can be re-encrypted (see [`MemorySourceStorage::rotate`](crate::MemorySourceStorage::rotate)).
+/// Values without the `enc:` prefix are treated as plaintext, so encryption can be
+/// enabled on an existing database.
+#[derive(Clone)]
+pub struct Cipher {
+    current: String,
+    keys: HashMap<String, Aes256Gcm>,
+}
+
+impl Cipher {
+    /// Create a cipher whose current key is `key` (32 bytes)
+    pub fn new(key_id: impl Into<String>, key: &[u8]) -> Result<Self, CipherError> {
+        let key_id = key_id.into();
+        let mut keys = HashMap::new();
+        keys.insert(key_id.clone(), Self::aes(&key_id, key)?);
+
+        Ok(Self {
+            current: key_id,
+            keys,
+        })
+    }
+
+    /// Parse a comma separated list of `<key id>:<base64 key>` entries.
+    /// The first entry is the current key, the rest are only used to decrypt.
+    pub fn parse(spec: &str) -> Result<Self, CipherError> {
+        let mut entries = spec.split(',').map(str::trim).filter(|e| !e.is_empty());
+        let (id, key) = Self::parse_entry(entries.next().unwrap_or_default())?;
+        let mut cipher = Self::new(id, &key)?;
+
+        for entry in entries {
+            let (id, key) = Self::parse_entry(entry)?;
+            cipher = cipher.with_key(id, &key)?;
+        }
+
+        Ok(cipher)
+    }
+
+    /// Add a key that is only used to decrypt values written before a rotation
+    pub fn with_key(mut self, key_id: impl Into<String>, key: &[u8]) -> Result<Self, CipherError> {
+        let key_id = key_id.into();
+        let aes = Self::aes(&key_id, key)?;
+        self.keys.entry(key_id).or_insert(aes);
+        Ok(self)
+    }
+
+    /// Id of the key new values are encrypted with
+    pub fn key_id(&self) -> &str {
+        &self.current
+    }
+
+    /// Prefix of every value encrypted with the current key
+    pub fn current_prefix(&self) -> String {
+        format!("{}:{}:", PREFIX, self.current)
+    }
+
+    /// Whether `value` was encrypted with any key
+    pub fn is_encrypted(value: &str) -> bool {
+        value.starts_with(&format!("{}:", PREFIX))
+    }
+
+    pub fn encrypt(&self, plaintext: &str) -> Result<String, CipherError> {
+        let aes = &self.keys[&self.current];
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let ciphertext = aes
+            .encrypt(&nonce, plaintext.as_bytes())
+            .map_err(|_| CipherError::Encrypt)?;
+
+        let mut bytes = nonce.to_vec();
+        bytes.extend(ciphertext);
+        Ok(format!(
+            "{}{}",
+            self.current_prefix(),
+            STANDARD.encode(bytes)
+        ))
+    }
+
+    /// Decrypt `value` with the key it was written with, returning plaintext values as-is
+    pub fn decrypt(&self, value: &str) -> Result<String, CipherError> {
+        if !Self::is_encrypted(value) {
+            return Ok(value.to_string());
+        }
+
+        let mut parts = value.splitn(3, ':').skip(1);
+        let (Some(key_id), Some(payload)) = (parts.next(), parts.next()) else {
+            return Err(CipherError::Decrypt);
+        };
+
+        let aes = self
+            .keys
+            .get(key_id)
+            .ok_or_else(|| CipherError::UnknownKey(key_id.to_string()))?;
+        let bytes = STANDARD.decode(payload).map_err(|_| CipherError::Decrypt)?;
+
+        if bytes.len() < NONCE_LEN {
+            return Err(CipherError::Decrypt);
+        }
+
+        let (nonce, ciphertext) = bytes.split_at(NONCE_LEN);
+        let plaintext = aes
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| CipherError::Decrypt)?;
+
+        String::from_utf8(plaintext).map_err(|_| CipherError::Decrypt)
+    }
+
+    fn aes(key_id: &str, key: &[u8]) -> Result<Aes256Gcm, CipherError> {
+        if key_id.is_empty() || key_id.contains(':') {
+            return Err(CipherError::InvalidKey(format!(
+                "key id '{}' must be non-empty and not contain ':'",
+                key_id
+            )));
+        }
+
+        if key.len() != 32 {
+            return Err(CipherError::InvalidKey(format!(
+                "key '{}' must be 32 bytes, found {}",
+                key_id,
+                key.len()
+            )));
+        }
+
+        Ok(Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key)))
+    }
+
+    fn parse_entry(entry: &str) -> Result<(&str, Vec<u8>), CipherError> {
+        let (id, key) = entry.split_once(':').ok_or_else(|| {
+            CipherError::InvalidKey(format!(
+                "expected '<key id>:<base64 key>', found '{}'",
+                entry
+            ))
+        })?;
+        let key = STANDARD
+            .decode(key.trim())
+            .map_err(|e| CipherError::InvalidKey(format!("key '{}': {}", id, e)))?;
+
+        Ok((id.trim(), key))
+    }
+}
+
+impl std::fmt::Debug for Cipher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut key_ids: Vec<_> = self.keys.keys().collect();
+        key_ids.sort();
+
+        f.debug_struct("Cipher")
+            .field("current", &self.current)
+            .field("key_ids", &key_ids)
+            .finish()
+    }
+}
+
+/// Encrypt optional text for a write, leaving it untouched when no cipher is configured
+pub(crate) fn seal(
+    cipher: Option<&Cipher>,
+    text: &Option<String>,
+) -> Result<Option<String>, sqlx::Error> {
+    match (cipher, text) {
+        (Some(cipher), Some(text)) => cipher
+            .encrypt(text)
+            .map(Some)
+            .map_err(|e| sqlx::Error::Encode(Box::new(e))),
+        _ => Ok(text.clone()),
+    }
+}
+
+/// Decrypt text after a read, leaving it untouched when no cipher is configured
+pub(crate) fn open(cipher: Option<&Cipher>, text: String) -> Result<String, sqlx::Error> {
+    match cipher {
+        Some(cipher) => cipher
+            .decrypt(&text)
+            .map_err(|e| sqlx::Error::Decode(Box::new(e))),
+        None => Ok(text),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: [u8; 32] = [7; 32];
+    const OLD_KEY: [u8; 32] = [9; 32];
+
+    #[test]
+    fn round_trips_text() {
+        let cipher = Cipher::new("k1", &KEY).unwrap();
+        let sealed = cipher.encrypt("call the dentist").unwrap();
+
+        assert!(sealed.starts_with("enc:k1:"));
+        assert!(!sealed.contains("dentist"));
+        assert_ne!(sealed, cipher.encrypt("call the dentist").unwrap());
+        assert_eq!(cipher.decrypt(&sealed).unwrap(), "call the dentist");
+        assert_eq!(cipher.decrypt("plain text").unwrap(), "plain text");
+    }
+
+    #[test]
+    fn decrypts_with_rotated_keys() {
+        let old = Cipher::new("k0", &OLD_KEY).unwrap();
+        let sealed = old.encrypt("ship it friday").unwrap();
+        let spec = format!(
+            "k1:{}, k0:{}",
+            STANDARD.encode(KEY),
+            STANDARD.encode(OLD_KEY)
+        );
+        let cipher = Cipher::parse(&spec).unwrap();
+
+        assert_eq!(cipher.key_id(), "k1");
+        assert_eq!(cipher.decrypt(&sealed).unwrap(), "ship it friday");
+    }
+
+    #[test]
+    fn rejects_wrong_and_unknown_keys() {
+        let sealed = Cipher::new("k1", &KEY).unwrap().encrypt("secret").unwrap();
+
+        let wrong = Cipher::new("k1", &OLD_KEY).unwrap();
+        assert_eq!(wrong.decrypt(&sealed), Err(CipherError::Decrypt));
+
+        let other = Cipher::new("k2", &KEY).unwrap();
+        assert_eq!(
+            other.decrypt(&sealed),
+            Err(CipherError::UnknownKey("k1".to_string()))
+        );
+    }
+
+    #[test]
+    fn rejects_tampered_ciphertext() {
+        let cipher = Cipher::new("k1", &KEY).unwrap();
+        let sealed = cipher.encrypt("secret").unwrap();
+        let payload = sealed.strip_prefix("enc:k1:").unwrap();
+        let mut bytes = STANDARD.decode(payload).unwrap();
+        let last = bytes.len() - 1;
+        bytes[last] ^= 1;
+
+        let tampered = format!("enc:k1:{}", STANDARD.encode(&bytes));
+        assert_eq!(cipher.decrypt(&tampered), Err(CipherError::Decrypt));
+        assert_eq!(cipher.decrypt("enc:k1:AAAA"), Err(CipherError::Decrypt));
+        assert_eq!(cipher.decrypt("enc:k1"), Err(CipherError::Decrypt));
+    }
+
+    #[test]
+    fn rejects_invalid_keys() {
+        assert!(matches!(
+            Cipher::new("k1", &[0; 16]),
+            Err(CipherError::InvalidKey(_))
+        ));
+        assert!(matches!(
+            Cipher::new("k:1", &KEY),
+            Err(CipherError::InvalidKey(_))
+        ));
+        assert!(matches!(
+            Cipher::parse("no-separator"),
+            Err(CipherError::InvalidKey(_))
+        ));
+    }
+
+    #[test]
+    fn encrypted_pattern_matches_encrypted_values() {
+        let sealed = Cipher::new("k1", &KEY).unwrap().encrypt("secret").unwrap();
+
+        assert!(sealed.starts_with(ENCRYPTED_PATTERN.trim_end_matches('%')));
+        assert!(Cipher::is_encrypted(&sealed));
+    }
+}
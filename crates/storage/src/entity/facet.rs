@@ -8,6 +8,12 @@ pub struct Facet {
     pub ty: FacetType,
     pub confidence: f32,
     pub data: Vec<u8>,
+    /// Hash of the score config that produced this facet
+    pub config_hash: Option<String>,
+    /// Model identifier that produced this facet
+    pub model: Option<String>,
+    /// Library version that produced this facet
+    pub version: Option<String>,
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub updated_at: chrono::DateTime<chrono::Utc>,
 }
@@ -18,6 +24,16 @@ impl Facet {
     }
 }
 
+/// Facets of one label aggregated over a time bucket of memories
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, sqlx::FromRow)]
+pub struct FacetBucket {
+    /// Start of the bucket
+    pub bucket: chrono::DateTime<chrono::Utc>,
+    pub count: i64,
+    pub avg_confidence: f32,
+    pub max_confidence: f32,
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize, sqlx::Type)]
 #[sqlx(type_name = "TEXT", rename_all = "snake_case")]
 pub enum FacetType {
@@ -11,6 +11,7 @@ pub struct Memory {
     pub sensitivity: Sensitivity,
     pub tags: Vec<String>,
     pub embedding: Option<Vec<f32>>,
+    pub consolidated_id: Option<uuid::Uuid>,
     pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub updated_at: chrono::DateTime<chrono::Utc>,
@@ -20,4 +21,62 @@ impl Memory {
     pub fn builder(scope_id: uuid::Uuid) -> MemoryBuilder {
         MemoryBuilder::new(scope_id)
     }
+
+    /// Returns true if this memory was folded into a consolidated memory
+    pub fn is_consolidated(&self) -> bool {
+        self.consolidated_id.is_some()
+    }
+
+    /// Fold a near-duplicate into this memory: tags are unioned, scores and
+    /// sensitivity keep the higher value and the later expiry wins (`None` never expires).
+    pub fn merge(&mut self, other: &Memory) {
+        self.score = self.score.max(other.score);
+        self.sensitivity = self.sensitivity.max(other.sensitivity);
+        self.confidence = self.confidence.max(other.confidence);
+        self.importance = self.importance.max(other.importance);
+
+        for tag in &other.tags {
+            if !self.tags.contains(tag) {
+                self.tags.push(tag.clone());
+            }
+        }
+
+        self.expires_at = match (self.expires_at, other.expires_at) {
+            (Some(a), Some(b)) => Some(a.max(b)),
+            _ => None,
+        };
+    }
+}
+
+/// A memory returned by a similarity search, with its cosine similarity to the query embedding
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, sqlx::FromRow)]
+pub struct SimilarMemory {
+    #[sqlx(flatten)]
+    #[serde(flatten)]
+    pub memory: Memory,
+    pub similarity: f32,
+}
+
+/// A memory joined with the text of one of its sources
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, sqlx::FromRow)]
+pub struct SourcedMemory {
+    #[sqlx(flatten)]
+    #[serde(flatten)]
+    pub memory: Memory,
+    pub source_id: uuid::Uuid,
+    pub source_text: String,
+}
+
+/// A memory ranked by hybrid search: embedding similarity plus keyword rank
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, sqlx::FromRow)]
+pub struct RankedMemory {
+    #[sqlx(flatten)]
+    #[serde(flatten)]
+    pub memory: Memory,
+    pub similarity: f32,
+    pub keyword_score: f32,
+    /// Combined score, not read from the database: set by the caller from
+    /// `similarity` and `keyword_score` (e.g. with `RetrieveConfig::score`)
+    #[sqlx(default)]
+    pub score: f32,
 }
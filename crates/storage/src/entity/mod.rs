@@ -2,6 +2,7 @@ mod facet;
 mod memory;
 mod memory_source;
 mod sensitivity;
+mod signal;
 mod source;
 mod status;
 mod trace;
@@ -11,6 +12,7 @@ pub use facet::*;
 pub use memory::*;
 pub use memory_source::*;
 pub use sensitivity::*;
+pub use signal::*;
 pub use source::*;
 pub use status::*;
 pub use trace::*;
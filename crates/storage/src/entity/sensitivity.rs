@@ -1,4 +1,16 @@
-#[derive(Debug, Copy, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize, sqlx::Type)]
+/// Sensitivity levels, ordered from least to most sensitive
+#[derive(
+    Debug,
+    Copy,
+    Clone,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    serde::Serialize,
+    serde::Deserialize,
+    sqlx::Type,
+)]
 #[sqlx(type_name = "TEXT", rename_all = "snake_case")]
 pub enum Sensitivity {
     Low,
@@ -0,0 +1,23 @@
+use crate::build::SignalBuilder;
+
+/// A loom signal persisted for analytics
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, sqlx::FromRow)]
+pub struct Signal {
+    pub id: uuid::Uuid,
+    pub otype: String,
+    pub level: String,
+    pub name: String,
+    pub attributes: sqlx::types::JsonValue,
+    pub emitted_at: chrono::DateTime<chrono::Utc>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl Signal {
+    pub fn builder(
+        otype: impl Into<String>,
+        level: impl Into<String>,
+        name: impl Into<String>,
+    ) -> SignalBuilder {
+        SignalBuilder::new(otype, level, name)
+    }
+}
@@ -1,7 +1,7 @@
 use crate::build::TraceBuilder;
 use crate::entity::Status;
 
-#[derive(Debug, Clone, sqlx::FromRow)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, sqlx::FromRow)]
 pub struct Trace {
     pub id: uuid::Uuid,
     pub parent_id: Option<uuid::Uuid>,
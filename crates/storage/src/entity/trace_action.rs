@@ -1,11 +1,13 @@
 use crate::build::TraceActionBuilder;
 
-#[derive(Debug, Clone, sqlx::FromRow)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, sqlx::FromRow)]
 pub struct TraceAction {
     pub trace_id: uuid::Uuid,
     pub target_id: uuid::Uuid,
     pub target: Target,
     pub action: Action,
+    pub actor: Option<String>,
+    pub diff: Option<sqlx::types::JsonValue>,
     pub created_at: chrono::DateTime<chrono::Utc>,
 }
 
@@ -21,6 +23,7 @@ impl TraceAction {
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
 #[sqlx(type_name = "TEXT", rename_all = "snake_case")]
 pub enum Target {
     Memory,
@@ -29,6 +32,7 @@ pub enum Target {
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
 #[sqlx(type_name = "TEXT", rename_all = "snake_case")]
 pub enum Action {
     Create,
@@ -36,4 +40,50 @@ pub enum Action {
     Delete,
     Read,
     Cite,
+    /// A loom signal (span, event, error) emitted while processing the target
+    Signal,
+    /// The input a pipeline run started from, kept when the run stored
+    /// nothing to rebuild it from (e.g. a memory dropped by the pre-filter)
+    Input,
+}
+
+impl Target {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Memory => "memory",
+            Self::Facet => "facet",
+            Self::Source => "source",
+        }
+    }
+}
+
+impl std::fmt::Display for Target {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl Action {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Create => "create",
+            Self::Update => "update",
+            Self::Delete => "delete",
+            Self::Read => "read",
+            Self::Cite => "cite",
+            Self::Signal => "signal",
+            Self::Input => "input",
+        }
+    }
+
+    /// Returns true for actions that modify the target
+    pub fn is_write(&self) -> bool {
+        matches!(self, Self::Create | Self::Update | Self::Delete)
+    }
+}
+
+impl std::fmt::Display for Action {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
 }
@@ -1,35 +1,143 @@
+use futures::stream::BoxStream;
 use sqlx::PgPool;
 
-use crate::entity::Facet;
+use crate::entity::{Facet, FacetBucket, FacetType};
 
 pub struct FacetStorage<'a> {
     pool: &'a PgPool,
+    tenant: &'a str,
 }
 
 impl<'a> FacetStorage<'a> {
-    pub fn new(pool: &'a PgPool) -> Self {
-        Self { pool }
+    pub fn new(pool: &'a PgPool, tenant: &'a str) -> Self {
+        Self { pool, tenant }
     }
 
     pub async fn get(&self, id: uuid::Uuid) -> Result<Option<Facet>, sqlx::Error> {
-        sqlx::query_as::<_, Facet>("SELECT * FROM facets WHERE id = $1")
+        sqlx::query_as::<_, Facet>("SELECT * FROM facets WHERE id = $1 AND tenant_id = $2")
             .bind(id)
+            .bind(self.tenant)
             .fetch_optional(self.pool)
             .await
     }
 
     pub async fn get_by_memory(&self, memory_id: uuid::Uuid) -> Result<Vec<Facet>, sqlx::Error> {
-        sqlx::query_as::<_, Facet>("SELECT * FROM facets WHERE memory_id = $1")
+        sqlx::query_as::<_, Facet>("SELECT * FROM facets WHERE memory_id = $1 AND tenant_id = $2")
             .bind(memory_id)
+            .bind(self.tenant)
             .fetch_all(self.pool)
             .await
     }
 
+    /// Get facets that were not produced by the given config hash (including facets
+    /// without provenance), oldest first, so re-scoring jobs can target them.
+    pub async fn get_outdated(
+        &self,
+        config_hash: &str,
+        limit: i64,
+    ) -> Result<Vec<Facet>, sqlx::Error> {
+        sqlx::query_as::<_, Facet>(
+            r#"
+            SELECT * FROM facets
+            WHERE config_hash IS DISTINCT FROM $1 AND tenant_id = $3
+            ORDER BY updated_at ASC
+            LIMIT $2
+            "#,
+        )
+        .bind(config_hash)
+        .bind(limit)
+        .bind(self.tenant)
+        .fetch_all(self.pool)
+        .await
+    }
+
+    /// Aggregate the confidence of facets labelled `label` (e.g. an emotion
+    /// facet's `stress`) into buckets of `window` by memory creation time,
+    /// oldest first. Buckets without facets are omitted, as are facets of
+    /// another `kind` and facets whose data isn't a JSON object.
+    pub async fn timeline(
+        &self,
+        label: &str,
+        kind: Option<FacetType>,
+        window: chrono::TimeDelta,
+        scope_id: Option<uuid::Uuid>,
+        since: Option<chrono::DateTime<chrono::Utc>>,
+        until: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> Result<Vec<FacetBucket>, sqlx::Error> {
+        sqlx::query_as::<_, FacetBucket>(
+            r#"
+            SELECT
+                date_bin(make_interval(secs => $2), m.created_at, TIMESTAMPTZ 'epoch') AS bucket,
+                COUNT(*) AS count,
+                AVG(f.confidence)::REAL AS avg_confidence,
+                MAX(f.confidence) AS max_confidence
+            FROM facets f
+            JOIN memories m ON m.id = f.memory_id AND m.tenant_id = f.tenant_id
+            WHERE f.tenant_id = $6
+              AND ($7::TEXT IS NULL OR f.type = $7)
+              AND CASE
+                  WHEN convert_from(f.data, 'UTF8') IS JSON OBJECT
+                  THEN convert_from(f.data, 'UTF8')::JSONB ->> 'label' = $1
+                  ELSE FALSE
+              END
+              AND ($3::UUID IS NULL OR m.scope_id = $3)
+              AND ($4::TIMESTAMPTZ IS NULL OR m.created_at >= $4)
+              AND ($5::TIMESTAMPTZ IS NULL OR m.created_at < $5)
+            GROUP BY bucket
+            ORDER BY bucket
+            "#,
+        )
+        .bind(label)
+        .bind(window.num_seconds() as f64)
+        .bind(scope_id)
+        .bind(since)
+        .bind(until)
+        .bind(self.tenant)
+        .bind(kind)
+        .fetch_all(self.pool)
+        .await
+    }
+
+    /// Stream every row of `facets`, for exports.
+    pub fn stream(&self) -> BoxStream<'a, Result<Facet, sqlx::Error>> {
+        sqlx::query_as::<_, Facet>(
+            "SELECT * FROM facets WHERE tenant_id = $1 ORDER BY created_at, id",
+        )
+        .bind(self.tenant)
+        .fetch(self.pool)
+    }
+
+    /// Insert a previously exported row as-is, keeping its timestamps.
+    /// Returns false if the row already exists.
+    pub async fn restore(&self, facet: &Facet) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO facets (id, memory_id, type, confidence, data, config_hash, model, version, created_at, updated_at, tenant_id)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+            ON CONFLICT (id) DO NOTHING
+            "#,
+        )
+        .bind(facet.id)
+        .bind(facet.memory_id)
+        .bind(&facet.ty)
+        .bind(facet.confidence)
+        .bind(&facet.data)
+        .bind(&facet.config_hash)
+        .bind(&facet.model)
+        .bind(&facet.version)
+        .bind(facet.created_at)
+        .bind(facet.updated_at)
+        .bind(self.tenant)
+        .execute(self.pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
     pub async fn create(&self, facet: &Facet) -> Result<Facet, sqlx::Error> {
         sqlx::query_as::<_, Facet>(
             r#"
-            INSERT INTO facets (id, memory_id, type, confidence, data, created_at, updated_at)
-            VALUES ($1, $2, $3, $4, $5, NOW(), NOW())
+            INSERT INTO facets (id, memory_id, type, confidence, data, config_hash, model, version, created_at, updated_at, tenant_id)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, NOW(), NOW(), $9)
             RETURNING *
             "#,
         )
@@ -38,6 +146,10 @@ impl<'a> FacetStorage<'a> {
         .bind(&facet.ty)
         .bind(facet.confidence)
         .bind(&facet.data)
+        .bind(&facet.config_hash)
+        .bind(&facet.model)
+        .bind(&facet.version)
+        .bind(self.tenant)
         .fetch_one(self.pool)
         .await
     }
@@ -46,8 +158,8 @@ impl<'a> FacetStorage<'a> {
         sqlx::query_as::<_, Facet>(
             r#"
             UPDATE facets
-            SET type = $2, confidence = $3, data = $4, updated_at = NOW()
-            WHERE id = $1
+            SET type = $2, confidence = $3, data = $4, config_hash = $5, model = $6, version = $7, updated_at = NOW()
+            WHERE id = $1 AND tenant_id = $8
             RETURNING *
             "#,
         )
@@ -55,13 +167,18 @@ impl<'a> FacetStorage<'a> {
         .bind(&facet.ty)
         .bind(facet.confidence)
         .bind(&facet.data)
+        .bind(&facet.config_hash)
+        .bind(&facet.model)
+        .bind(&facet.version)
+        .bind(self.tenant)
         .fetch_optional(self.pool)
         .await
     }
 
     pub async fn delete(&self, id: uuid::Uuid) -> Result<bool, sqlx::Error> {
-        let result = sqlx::query("DELETE FROM facets WHERE id = $1")
+        let result = sqlx::query("DELETE FROM facets WHERE id = $1 AND tenant_id = $2")
             .bind(id)
+            .bind(self.tenant)
             .execute(self.pool)
             .await?;
         Ok(result.rows_affected() > 0)
@@ -3,16 +3,20 @@ use sqlx::PgPool;
 pub mod build;
 pub mod entity;
 
+mod cipher;
 mod facet_storage;
 mod memory_source_storage;
 mod memory_storage;
+mod signal_storage;
 mod source_storage;
 mod trace_action_storage;
 mod trace_storage;
 
+pub use cipher::{Cipher, CipherError};
 pub use facet_storage::*;
 pub use memory_source_storage::*;
 pub use memory_storage::*;
+pub use signal_storage::*;
 pub use source_storage::*;
 pub use trace_action_storage::*;
 pub use trace_storage::*;
@@ -24,17 +28,37 @@ pub struct Storage<'a> {
     pub memory_sources: MemorySourceStorage<'a>,
     pub traces: TraceStorage<'a>,
     pub trace_actions: TraceActionStorage<'a>,
+    pub signals: SignalStorage<'a>,
 }
 
 impl<'a> Storage<'a> {
-    pub fn new(pool: &'a PgPool) -> Self {
+    /// Create a storage handle whose queries only see and write rows of `tenant`
+    pub fn new(pool: &'a PgPool, tenant: &'a str) -> Self {
         Self {
-            memories: MemoryStorage::new(pool),
-            facets: FacetStorage::new(pool),
-            sources: SourceStorage::new(pool),
-            memory_sources: MemorySourceStorage::new(pool),
-            traces: TraceStorage::new(pool),
-            trace_actions: TraceActionStorage::new(pool),
+            memories: MemoryStorage::new(pool, tenant),
+            facets: FacetStorage::new(pool, tenant),
+            sources: SourceStorage::new(pool, tenant),
+            memory_sources: MemorySourceStorage::new(pool, tenant),
+            traces: TraceStorage::new(pool, tenant),
+            trace_actions: TraceActionStorage::new(pool, tenant),
+            signals: SignalStorage::new(pool, tenant),
         }
     }
+
+    /// Encrypt memory source text at rest with `cipher`, if any
+    pub fn with_cipher(mut self, cipher: Option<&'a Cipher>) -> Self {
+        self.memories = self.memories.with_cipher(cipher);
+        self.memory_sources = self.memory_sources.with_cipher(cipher);
+        self
+    }
+}
+
+/// Tenant used when none is provided
+pub const DEFAULT_TENANT: &str = "default";
+
+/// List every tenant that owns at least one memory, for jobs that run across tenants
+pub async fn tenants(pool: &PgPool) -> Result<Vec<String>, sqlx::Error> {
+    sqlx::query_scalar::<_, String>("SELECT DISTINCT tenant_id FROM memories ORDER BY tenant_id")
+        .fetch_all(pool)
+        .await
 }
@@ -1,14 +1,39 @@
+use futures::StreamExt;
+use futures::stream::BoxStream;
 use sqlx::PgPool;
 
+use crate::cipher::{self, Cipher};
 use crate::entity::MemorySource;
 
+/// Outcome of one [`MemorySourceStorage::rotate`] batch
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Rotation {
+    /// Rows found with plaintext or an older key
+    pub found: u64,
+    /// Rows rewritten with the current key; rows changed by someone else since
+    /// they were read are skipped and picked up again by the next batch
+    pub rotated: u64,
+}
+
 pub struct MemorySourceStorage<'a> {
     pool: &'a PgPool,
+    tenant: &'a str,
+    cipher: Option<&'a Cipher>,
 }
 
 impl<'a> MemorySourceStorage<'a> {
-    pub fn new(pool: &'a PgPool) -> Self {
-        Self { pool }
+    pub fn new(pool: &'a PgPool, tenant: &'a str) -> Self {
+        Self {
+            pool,
+            tenant,
+            cipher: None,
+        }
+    }
+
+    /// Encrypt `text` on writes and decrypt it on reads
+    pub fn with_cipher(mut self, cipher: Option<&'a Cipher>) -> Self {
+        self.cipher = cipher;
+        self
     }
 
     pub async fn get(
@@ -17,51 +42,109 @@ impl<'a> MemorySourceStorage<'a> {
         source_id: uuid::Uuid,
     ) -> Result<Option<MemorySource>, sqlx::Error> {
         sqlx::query_as::<_, MemorySource>(
-            "SELECT * FROM memory_sources WHERE memory_id = $1 AND source_id = $2",
+            "SELECT * FROM memory_sources WHERE memory_id = $1 AND source_id = $2 AND tenant_id = $3",
         )
         .bind(memory_id)
         .bind(source_id)
+        .bind(self.tenant)
         .fetch_optional(self.pool)
-        .await
+        .await?
+        .map(|v| self.open(v))
+        .transpose()
     }
 
     pub async fn get_by_memory(
         &self,
         memory_id: uuid::Uuid,
     ) -> Result<Vec<MemorySource>, sqlx::Error> {
-        sqlx::query_as::<_, MemorySource>("SELECT * FROM memory_sources WHERE memory_id = $1")
-            .bind(memory_id)
-            .fetch_all(self.pool)
-            .await
+        sqlx::query_as::<_, MemorySource>(
+            "SELECT * FROM memory_sources WHERE memory_id = $1 AND tenant_id = $2",
+        )
+        .bind(memory_id)
+        .bind(self.tenant)
+        .fetch_all(self.pool)
+        .await?
+        .into_iter()
+        .map(|v| self.open(v))
+        .collect()
     }
 
     pub async fn get_by_source(
         &self,
         source_id: uuid::Uuid,
     ) -> Result<Vec<MemorySource>, sqlx::Error> {
-        sqlx::query_as::<_, MemorySource>("SELECT * FROM memory_sources WHERE source_id = $1")
-            .bind(source_id)
-            .fetch_all(self.pool)
-            .await
+        sqlx::query_as::<_, MemorySource>(
+            "SELECT * FROM memory_sources WHERE source_id = $1 AND tenant_id = $2",
+        )
+        .bind(source_id)
+        .bind(self.tenant)
+        .fetch_all(self.pool)
+        .await?
+        .into_iter()
+        .map(|v| self.open(v))
+        .collect()
+    }
+
+    /// Stream every row of `memory_sources`, for exports.
+    /// Text is decrypted, so exports are portable across keys.
+    pub fn stream(&self) -> BoxStream<'a, Result<MemorySource, sqlx::Error>> {
+        let cipher = self.cipher;
+
+        sqlx::query_as::<_, MemorySource>(
+            "SELECT * FROM memory_sources WHERE tenant_id = $1 ORDER BY memory_id, source_id",
+        )
+        .bind(self.tenant)
+        .fetch(self.pool)
+        .map(move |row| {
+            let mut row = row?;
+            row.text = row.text.map(|t| cipher::open(cipher, t)).transpose()?;
+            Ok(row)
+        })
+        .boxed()
+    }
+
+    /// Insert a previously exported row as-is.
+    /// Returns false if the row already exists.
+    pub async fn restore(&self, memory_source: &MemorySource) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO memory_sources (memory_id, source_id, confidence, text, hash, start_offset, end_offset, tenant_id)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            ON CONFLICT (memory_id, source_id) DO NOTHING
+            "#,
+        )
+        .bind(memory_source.memory_id)
+        .bind(memory_source.source_id)
+        .bind(memory_source.confidence)
+        .bind(self.seal(memory_source)?)
+        .bind(&memory_source.hash)
+        .bind(memory_source.start_offset)
+        .bind(memory_source.end_offset)
+        .bind(self.tenant)
+        .execute(self.pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
     }
 
     pub async fn create(&self, memory_source: &MemorySource) -> Result<MemorySource, sqlx::Error> {
         sqlx::query_as::<_, MemorySource>(
             r#"
-            INSERT INTO memory_sources (memory_id, source_id, confidence, text, hash, start_offset, end_offset)
-            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            INSERT INTO memory_sources (memory_id, source_id, confidence, text, hash, start_offset, end_offset, tenant_id)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
             RETURNING *
             "#,
         )
         .bind(memory_source.memory_id)
         .bind(memory_source.source_id)
         .bind(memory_source.confidence)
-        .bind(&memory_source.text)
+        .bind(self.seal(memory_source)?)
         .bind(&memory_source.hash)
         .bind(memory_source.start_offset)
         .bind(memory_source.end_offset)
+        .bind(self.tenant)
         .fetch_one(self.pool)
         .await
+        .and_then(|v| self.open(v))
     }
 
     pub async fn update(
@@ -72,19 +155,22 @@ impl<'a> MemorySourceStorage<'a> {
             r#"
             UPDATE memory_sources
             SET confidence = $3, text = $4, hash = $5, start_offset = $6, end_offset = $7
-            WHERE memory_id = $1 AND source_id = $2
+            WHERE memory_id = $1 AND source_id = $2 AND tenant_id = $8
             RETURNING *
             "#,
         )
         .bind(memory_source.memory_id)
         .bind(memory_source.source_id)
         .bind(memory_source.confidence)
-        .bind(&memory_source.text)
+        .bind(self.seal(memory_source)?)
         .bind(&memory_source.hash)
         .bind(memory_source.start_offset)
         .bind(memory_source.end_offset)
+        .bind(self.tenant)
         .fetch_optional(self.pool)
-        .await
+        .await?
+        .map(|v| self.open(v))
+        .transpose()
     }
 
     pub async fn delete(
@@ -92,12 +178,82 @@ impl<'a> MemorySourceStorage<'a> {
         memory_id: uuid::Uuid,
         source_id: uuid::Uuid,
     ) -> Result<bool, sqlx::Error> {
-        let result =
-            sqlx::query("DELETE FROM memory_sources WHERE memory_id = $1 AND source_id = $2")
-                .bind(memory_id)
-                .bind(source_id)
-                .execute(self.pool)
-                .await?;
+        let result = sqlx::query(
+            "DELETE FROM memory_sources WHERE memory_id = $1 AND source_id = $2 AND tenant_id = $3",
+        )
+        .bind(memory_id)
+        .bind(source_id)
+        .bind(self.tenant)
+        .execute(self.pool)
+        .await?;
         Ok(result.rows_affected() > 0)
     }
+
+    /// Re-encrypt up to `limit` rows whose text is plaintext or was written with an
+    /// older key, using the current key. Call repeatedly until no rows are
+    /// [`found`](Rotation::found) to rotate the whole tenant.
+    /// Does nothing without a cipher.
+    pub async fn rotate(&self, limit: i64) -> Result<Rotation, sqlx::Error> {
+        let Some(cipher) = self.cipher else {
+            return Ok(Rotation::default());
+        };
+
+        let rows = sqlx::query_as::<_, MemorySource>(
+            r#"
+            SELECT * FROM memory_sources
+            WHERE tenant_id = $1 AND text IS NOT NULL AND NOT starts_with(text, $2)
+            LIMIT $3
+            "#,
+        )
+        .bind(self.tenant)
+        .bind(cipher.current_prefix())
+        .bind(limit)
+        .fetch_all(self.pool)
+        .await?;
+
+        let mut rotation = Rotation {
+            found: rows.len() as u64,
+            rotated: 0,
+        };
+
+        for row in rows {
+            let stored = row.text.clone();
+            let text = row
+                .text
+                .map(|t| cipher::open(Some(cipher), t))
+                .transpose()?;
+
+            // only rewrite the row if no one changed it since it was read
+            let result = sqlx::query(
+                r#"
+                UPDATE memory_sources
+                SET text = $3
+                WHERE memory_id = $1 AND source_id = $2 AND tenant_id = $4 AND text = $5
+                "#,
+            )
+            .bind(row.memory_id)
+            .bind(row.source_id)
+            .bind(cipher::seal(Some(cipher), &text)?)
+            .bind(self.tenant)
+            .bind(stored)
+            .execute(self.pool)
+            .await?;
+
+            rotation.rotated += result.rows_affected();
+        }
+
+        Ok(rotation)
+    }
+
+    fn seal(&self, memory_source: &MemorySource) -> Result<Option<String>, sqlx::Error> {
+        cipher::seal(self.cipher, &memory_source.text)
+    }
+
+    fn open(&self, mut memory_source: MemorySource) -> Result<MemorySource, sqlx::Error> {
+        memory_source.text = memory_source
+            .text
+            .map(|t| cipher::open(self.cipher, t))
+            .transpose()?;
+        Ok(memory_source)
+    }
 }
@@ -1,35 +1,230 @@
+use futures::stream::BoxStream;
 use sqlx::PgPool;
 
-use crate::entity::Memory;
+use crate::cipher::{self, Cipher};
+use crate::entity::{Memory, RankedMemory, SimilarMemory, SourcedMemory};
 
 pub struct MemoryStorage<'a> {
     pool: &'a PgPool,
+    tenant: &'a str,
+    cipher: Option<&'a Cipher>,
 }
 
 impl<'a> MemoryStorage<'a> {
-    pub fn new(pool: &'a PgPool) -> Self {
-        Self { pool }
+    pub fn new(pool: &'a PgPool, tenant: &'a str) -> Self {
+        Self {
+            pool,
+            tenant,
+            cipher: None,
+        }
+    }
+
+    /// Decrypt source text joined into reads
+    pub fn with_cipher(mut self, cipher: Option<&'a Cipher>) -> Self {
+        self.cipher = cipher;
+        self
     }
 
     pub async fn get(&self, id: uuid::Uuid) -> Result<Option<Memory>, sqlx::Error> {
-        sqlx::query_as::<_, Memory>("SELECT * FROM memories WHERE id = $1")
+        sqlx::query_as::<_, Memory>("SELECT * FROM memories WHERE id = $1 AND tenant_id = $2")
             .bind(id)
+            .bind(self.tenant)
             .fetch_optional(self.pool)
             .await
     }
 
     pub async fn get_by_scope(&self, scope_id: uuid::Uuid) -> Result<Vec<Memory>, sqlx::Error> {
-        sqlx::query_as::<_, Memory>("SELECT * FROM memories WHERE scope_id = $1")
+        sqlx::query_as::<_, Memory>("SELECT * FROM memories WHERE scope_id = $1 AND tenant_id = $2")
             .bind(scope_id)
+            .bind(self.tenant)
             .fetch_all(self.pool)
             .await
     }
 
+    /// Find memories in a scope whose embedding has a cosine similarity of at least
+    /// `threshold` to `embedding`, most similar first. Requires the pgvector extension.
+    pub async fn get_similar(
+        &self,
+        scope_id: uuid::Uuid,
+        embedding: &[f32],
+        threshold: f32,
+        limit: i64,
+    ) -> Result<Vec<SimilarMemory>, sqlx::Error> {
+        sqlx::query_as::<_, SimilarMemory>(
+            r#"
+            SELECT *, (1 - (embedding::vector <=> $2::real[]::vector))::real AS similarity
+            FROM memories
+            WHERE scope_id = $1
+              AND tenant_id = $5
+              AND embedding IS NOT NULL
+              AND cardinality(embedding) = cardinality($2::real[])
+              AND 1 - (embedding::vector <=> $2::real[]::vector) >= $3
+            ORDER BY embedding::vector <=> $2::real[]::vector
+            LIMIT $4
+            "#,
+        )
+        .bind(scope_id)
+        .bind(embedding)
+        .bind(threshold)
+        .bind(limit)
+        .bind(self.tenant)
+        .fetch_all(self.pool)
+        .await
+    }
+
+    /// Hybrid search over the active memories (not consolidated, not expired) of a scope.
+    ///
+    /// Memories are ordered by `vector_weight * cosine similarity + keyword_weight * keyword rank`,
+    /// where the keyword rank is the best normalized full text rank of its source texts.
+    /// Memories without a matching embedding only score on keywords. The combined
+    /// [`RankedMemory::score`] is left at 0 for the caller to compute.
+    /// Encrypted source text is skipped rather than ranked on its ciphertext, so
+    /// with encryption enabled ranking is effectively vector only.
+    pub async fn search(
+        &self,
+        scope_id: uuid::Uuid,
+        query: &str,
+        embedding: &[f32],
+        vector_weight: f32,
+        keyword_weight: f32,
+        limit: i64,
+    ) -> Result<Vec<RankedMemory>, sqlx::Error> {
+        sqlx::query_as::<_, RankedMemory>(
+            r#"
+            WITH candidates AS (
+                SELECT m.*,
+                    COALESCE(CASE WHEN cardinality(m.embedding) = cardinality($3::real[])
+                        THEN (1 - (m.embedding::vector <=> $3::real[]::vector))::real
+                    END, 0)::real AS similarity,
+                    COALESCE((
+                        SELECT MAX(ts_rank(to_tsvector('english', ms.text), plainto_tsquery('english', $2), 32))
+                        FROM memory_sources ms
+                        WHERE ms.memory_id = m.id AND ms.tenant_id = m.tenant_id AND ms.text IS NOT NULL
+                          AND ms.text NOT LIKE $8
+                    ), 0)::real AS keyword_score
+                FROM memories m
+                WHERE m.tenant_id = $7
+                  AND m.consolidated_id IS NULL
+                  AND (m.expires_at IS NULL OR m.expires_at > NOW())
+                  AND m.scope_id = $1
+            )
+            SELECT *
+            FROM candidates
+            ORDER BY $4 * similarity + $5 * keyword_score DESC
+            LIMIT $6
+            "#,
+        )
+        .bind(scope_id)
+        .bind(query)
+        .bind(embedding)
+        .bind(vector_weight)
+        .bind(keyword_weight)
+        .bind(limit)
+        .bind(self.tenant)
+        .bind(cipher::ENCRYPTED_PATTERN)
+        .fetch_all(self.pool)
+        .await
+    }
+
+    /// Get memories created before `before` that are neither consolidated nor the
+    /// result of a consolidation, one row per source with stored text, ordered by
+    /// source and creation time.
+    pub async fn get_unconsolidated(
+        &self,
+        before: chrono::DateTime<chrono::Utc>,
+        limit: i64,
+    ) -> Result<Vec<SourcedMemory>, sqlx::Error> {
+        sqlx::query_as::<_, SourcedMemory>(
+            r#"
+            SELECT m.*, ms.source_id, ms.text AS source_text
+            FROM memories m
+            JOIN memory_sources ms ON ms.memory_id = m.id AND ms.tenant_id = m.tenant_id
+            WHERE m.tenant_id = $3
+              AND m.consolidated_id IS NULL
+              AND m.created_at < $1
+              AND ms.text IS NOT NULL
+              AND NOT EXISTS (SELECT 1 FROM memories c WHERE c.consolidated_id = m.id)
+            ORDER BY ms.source_id, m.created_at
+            LIMIT $2
+            "#,
+        )
+        .bind(before)
+        .bind(limit)
+        .bind(self.tenant)
+        .fetch_all(self.pool)
+        .await?
+        .into_iter()
+        .map(|mut v| {
+            v.source_text = cipher::open(self.cipher, v.source_text)?;
+            Ok(v)
+        })
+        .collect()
+    }
+
+    /// Mark memories as consolidated into `consolidated_id`
+    pub async fn consolidate(
+        &self,
+        ids: &[uuid::Uuid],
+        consolidated_id: uuid::Uuid,
+    ) -> Result<Vec<Memory>, sqlx::Error> {
+        sqlx::query_as::<_, Memory>(
+            r#"
+            UPDATE memories
+            SET consolidated_id = $2, updated_at = NOW()
+            WHERE id = ANY($1) AND tenant_id = $3
+            RETURNING *
+            "#,
+        )
+        .bind(ids)
+        .bind(consolidated_id)
+        .bind(self.tenant)
+        .fetch_all(self.pool)
+        .await
+    }
+
+    /// Stream every row of `memories`, for exports.
+    /// Constituents of a consolidation are streamed after the memory they point to.
+    pub fn stream(&self) -> BoxStream<'a, Result<Memory, sqlx::Error>> {
+        sqlx::query_as::<_, Memory>(
+            "SELECT * FROM memories WHERE tenant_id = $1 ORDER BY consolidated_id IS NOT NULL, created_at, id",
+        )
+        .bind(self.tenant)
+        .fetch(self.pool)
+    }
+
+    /// Insert a previously exported row as-is, keeping its timestamps.
+    /// Returns false if the row already exists.
+    pub async fn restore(&self, memory: &Memory) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO memories (id, scope_id, score, confidence, importance, sensitivity, tags, embedding, consolidated_id, expires_at, created_at, updated_at, tenant_id)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+            ON CONFLICT (id) DO NOTHING
+            "#,
+        )
+        .bind(memory.id)
+        .bind(memory.scope_id)
+        .bind(memory.score)
+        .bind(memory.confidence)
+        .bind(memory.importance)
+        .bind(&memory.sensitivity)
+        .bind(&memory.tags)
+        .bind(&memory.embedding)
+        .bind(memory.consolidated_id)
+        .bind(memory.expires_at)
+        .bind(memory.created_at)
+        .bind(memory.updated_at)
+        .bind(self.tenant)
+        .execute(self.pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
     pub async fn create(&self, memory: &Memory) -> Result<Memory, sqlx::Error> {
         sqlx::query_as::<_, Memory>(
             r#"
-            INSERT INTO memories (id, scope_id, score, confidence, importance, sensitivity, tags, embedding, expires_at, created_at, updated_at)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, NOW(), NOW())
+            INSERT INTO memories (id, scope_id, score, confidence, importance, sensitivity, tags, embedding, consolidated_id, expires_at, created_at, updated_at, tenant_id)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, NOW(), NOW(), $11)
             RETURNING *
             "#,
         )
@@ -41,7 +236,9 @@ impl<'a> MemoryStorage<'a> {
         .bind(&memory.sensitivity)
         .bind(&memory.tags)
         .bind(&memory.embedding)
+        .bind(memory.consolidated_id)
         .bind(memory.expires_at)
+        .bind(self.tenant)
         .fetch_one(self.pool)
         .await
     }
@@ -50,8 +247,8 @@ impl<'a> MemoryStorage<'a> {
         sqlx::query_as::<_, Memory>(
             r#"
             UPDATE memories
-            SET score = $2, confidence = $3, importance = $4, sensitivity = $5, tags = $6, embedding = $7, expires_at = $8, updated_at = NOW()
-            WHERE id = $1
+            SET score = $2, confidence = $3, importance = $4, sensitivity = $5, tags = $6, embedding = $7, consolidated_id = $8, expires_at = $9, updated_at = NOW()
+            WHERE id = $1 AND tenant_id = $10
             RETURNING *
             "#,
         )
@@ -62,14 +259,17 @@ impl<'a> MemoryStorage<'a> {
         .bind(&memory.sensitivity)
         .bind(&memory.tags)
         .bind(&memory.embedding)
+        .bind(memory.consolidated_id)
         .bind(memory.expires_at)
+        .bind(self.tenant)
         .fetch_optional(self.pool)
         .await
     }
 
     pub async fn delete(&self, id: uuid::Uuid) -> Result<bool, sqlx::Error> {
-        let result = sqlx::query("DELETE FROM memories WHERE id = $1")
+        let result = sqlx::query("DELETE FROM memories WHERE id = $1 AND tenant_id = $2")
             .bind(id)
+            .bind(self.tenant)
             .execute(self.pool)
             .await?;
         Ok(result.rows_affected() > 0)
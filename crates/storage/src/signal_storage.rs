@@ -0,0 +1,82 @@
+use sqlx::{PgPool, Postgres, QueryBuilder};
+
+use crate::entity::Signal;
+
+/// Rows written per `INSERT`, keeping each statement well under
+/// Postgres' bind parameter limit
+const CHUNK_SIZE: usize = 1000;
+
+pub struct SignalStorage<'a> {
+    pool: &'a PgPool,
+    tenant: &'a str,
+}
+
+impl<'a> SignalStorage<'a> {
+    pub fn new(pool: &'a PgPool, tenant: &'a str) -> Self {
+        Self { pool, tenant }
+    }
+
+    /// Get signals named `name` newest first, optionally only those emitted since `since`
+    pub async fn get_by_name(
+        &self,
+        name: &str,
+        since: Option<chrono::DateTime<chrono::Utc>>,
+        limit: i64,
+    ) -> Result<Vec<Signal>, sqlx::Error> {
+        sqlx::query_as::<_, Signal>(
+            r#"
+            SELECT * FROM signals
+            WHERE name = $1
+              AND tenant_id = $4
+              AND ($2::TIMESTAMPTZ IS NULL OR emitted_at >= $2)
+            ORDER BY emitted_at DESC
+            LIMIT $3
+            "#,
+        )
+        .bind(name)
+        .bind(since)
+        .bind(limit)
+        .bind(self.tenant)
+        .fetch_all(self.pool)
+        .await
+    }
+
+    /// Insert a batch of signals in as few statements as possible.
+    /// Returns the number of rows written.
+    pub async fn create_many(&self, signals: &[Signal]) -> Result<u64, sqlx::Error> {
+        let mut written = 0;
+
+        for chunk in signals.chunks(CHUNK_SIZE) {
+            let mut query = QueryBuilder::<Postgres>::new(
+                "INSERT INTO signals (id, otype, level, name, attributes, emitted_at, tenant_id) ",
+            );
+
+            query.push_values(chunk, |mut row, signal| {
+                row.push_bind(signal.id)
+                    .push_bind(&signal.otype)
+                    .push_bind(&signal.level)
+                    .push_bind(&signal.name)
+                    .push_bind(&signal.attributes)
+                    .push_bind(signal.emitted_at)
+                    .push_bind(self.tenant);
+            });
+
+            written += query.build().execute(self.pool).await?.rows_affected();
+        }
+
+        Ok(written)
+    }
+
+    /// Delete signals emitted before `before`, returning how many were removed
+    pub async fn delete_before(
+        &self,
+        before: chrono::DateTime<chrono::Utc>,
+    ) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query("DELETE FROM signals WHERE emitted_at < $1 AND tenant_id = $2")
+            .bind(before)
+            .bind(self.tenant)
+            .execute(self.pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
@@ -1,26 +1,30 @@
+use futures::stream::BoxStream;
 use sqlx::PgPool;
 
 use crate::entity::Source;
 
 pub struct SourceStorage<'a> {
     pool: &'a PgPool,
+    tenant: &'a str,
 }
 
 impl<'a> SourceStorage<'a> {
-    pub fn new(pool: &'a PgPool) -> Self {
-        Self { pool }
+    pub fn new(pool: &'a PgPool, tenant: &'a str) -> Self {
+        Self { pool, tenant }
     }
 
     pub async fn get(&self, id: uuid::Uuid) -> Result<Option<Source>, sqlx::Error> {
-        sqlx::query_as::<_, Source>("SELECT * FROM sources WHERE id = $1")
+        sqlx::query_as::<_, Source>("SELECT * FROM sources WHERE id = $1 AND tenant_id = $2")
             .bind(id)
+            .bind(self.tenant)
             .fetch_optional(self.pool)
             .await
     }
 
     pub async fn get_by_scope(&self, scope_id: uuid::Uuid) -> Result<Vec<Source>, sqlx::Error> {
-        sqlx::query_as::<_, Source>("SELECT * FROM sources WHERE scope_id = $1")
+        sqlx::query_as::<_, Source>("SELECT * FROM sources WHERE scope_id = $1 AND tenant_id = $2")
             .bind(scope_id)
+            .bind(self.tenant)
             .fetch_all(self.pool)
             .await
     }
@@ -29,17 +33,51 @@ impl<'a> SourceStorage<'a> {
         &self,
         external_id: &str,
     ) -> Result<Option<Source>, sqlx::Error> {
-        sqlx::query_as::<_, Source>("SELECT * FROM sources WHERE external_id = $1")
-            .bind(external_id)
-            .fetch_optional(self.pool)
-            .await
+        sqlx::query_as::<_, Source>(
+            "SELECT * FROM sources WHERE external_id = $1 AND tenant_id = $2",
+        )
+        .bind(external_id)
+        .bind(self.tenant)
+        .fetch_optional(self.pool)
+        .await
+    }
+
+    /// Stream every row of `sources`, for exports.
+    pub fn stream(&self) -> BoxStream<'a, Result<Source, sqlx::Error>> {
+        sqlx::query_as::<_, Source>(
+            "SELECT * FROM sources WHERE tenant_id = $1 ORDER BY created_at, id",
+        )
+        .bind(self.tenant)
+        .fetch(self.pool)
+    }
+
+    /// Insert a previously exported row as-is, keeping its timestamps.
+    /// Returns false if the row already exists.
+    pub async fn restore(&self, source: &Source) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO sources (id, scope_id, external_id, type, uri, created_at, tenant_id)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            ON CONFLICT (id) DO NOTHING
+            "#,
+        )
+        .bind(source.id)
+        .bind(source.scope_id)
+        .bind(&source.external_id)
+        .bind(&source.ty)
+        .bind(&source.uri)
+        .bind(source.created_at)
+        .bind(self.tenant)
+        .execute(self.pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
     }
 
     pub async fn create(&self, source: &Source) -> Result<Source, sqlx::Error> {
         sqlx::query_as::<_, Source>(
             r#"
-            INSERT INTO sources (id, scope_id, external_id, type, uri, created_at)
-            VALUES ($1, $2, $3, $4, $5, NOW())
+            INSERT INTO sources (id, scope_id, external_id, type, uri, created_at, tenant_id)
+            VALUES ($1, $2, $3, $4, $5, NOW(), $6)
             RETURNING *
             "#,
         )
@@ -48,13 +86,15 @@ impl<'a> SourceStorage<'a> {
         .bind(&source.external_id)
         .bind(&source.ty)
         .bind(&source.uri)
+        .bind(self.tenant)
         .fetch_one(self.pool)
         .await
     }
 
     pub async fn delete(&self, id: uuid::Uuid) -> Result<bool, sqlx::Error> {
-        let result = sqlx::query("DELETE FROM sources WHERE id = $1")
+        let result = sqlx::query("DELETE FROM sources WHERE id = $1 AND tenant_id = $2")
             .bind(id)
+            .bind(self.tenant)
             .execute(self.pool)
             .await?;
         Ok(result.rows_affected() > 0)
@@ -1,24 +1,29 @@
+use futures::stream::BoxStream;
 use sqlx::PgPool;
 
 use crate::entity::{Target, TraceAction};
 
 pub struct TraceActionStorage<'a> {
     pool: &'a PgPool,
+    tenant: &'a str,
 }
 
 impl<'a> TraceActionStorage<'a> {
-    pub fn new(pool: &'a PgPool) -> Self {
-        Self { pool }
+    pub fn new(pool: &'a PgPool, tenant: &'a str) -> Self {
+        Self { pool, tenant }
     }
 
     pub async fn get_by_trace(
         &self,
         trace_id: uuid::Uuid,
     ) -> Result<Vec<TraceAction>, sqlx::Error> {
-        sqlx::query_as::<_, TraceAction>("SELECT * FROM trace_actions WHERE trace_id = $1")
-            .bind(trace_id)
-            .fetch_all(self.pool)
-            .await
+        sqlx::query_as::<_, TraceAction>(
+            "SELECT * FROM trace_actions WHERE trace_id = $1 AND tenant_id = $2",
+        )
+        .bind(trace_id)
+        .bind(self.tenant)
+        .fetch_all(self.pool)
+        .await
     }
 
     pub async fn get_by_target(
@@ -27,19 +32,82 @@ impl<'a> TraceActionStorage<'a> {
         target: Target,
     ) -> Result<Vec<TraceAction>, sqlx::Error> {
         sqlx::query_as::<_, TraceAction>(
-            "SELECT * FROM trace_actions WHERE target_id = $1 AND target = $2",
+            "SELECT * FROM trace_actions WHERE target_id = $1 AND target = $2 AND tenant_id = $3",
         )
         .bind(target_id)
         .bind(target)
+        .bind(self.tenant)
         .fetch_all(self.pool)
         .await
     }
 
+    /// Get write actions (create, update, delete) newest first, optionally
+    /// filtered by actor, target kind, and target id
+    pub async fn get_audit(
+        &self,
+        actor: Option<&str>,
+        target: Option<Target>,
+        target_id: Option<uuid::Uuid>,
+        limit: i64,
+    ) -> Result<Vec<TraceAction>, sqlx::Error> {
+        sqlx::query_as::<_, TraceAction>(
+            r#"
+            SELECT * FROM trace_actions
+            WHERE action IN ('create', 'update', 'delete')
+              AND tenant_id = $5
+              AND ($1::TEXT IS NULL OR actor = $1)
+              AND ($2::TEXT IS NULL OR target = $2)
+              AND ($3::UUID IS NULL OR target_id = $3)
+            ORDER BY created_at DESC
+            LIMIT $4
+            "#,
+        )
+        .bind(actor)
+        .bind(target)
+        .bind(target_id)
+        .bind(limit)
+        .bind(self.tenant)
+        .fetch_all(self.pool)
+        .await
+    }
+
+    /// Stream every row of `trace_actions`, for exports.
+    pub fn stream(&self) -> BoxStream<'a, Result<TraceAction, sqlx::Error>> {
+        sqlx::query_as::<_, TraceAction>(
+            "SELECT * FROM trace_actions WHERE tenant_id = $1 ORDER BY created_at, trace_id",
+        )
+        .bind(self.tenant)
+        .fetch(self.pool)
+    }
+
+    /// Insert a previously exported row as-is, keeping its timestamps.
+    /// Returns false if the row already exists.
+    pub async fn restore(&self, trace_action: &TraceAction) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO trace_actions (trace_id, target_id, target, action, actor, diff, created_at, tenant_id)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            ON CONFLICT (trace_id, target_id, action, created_at) DO NOTHING
+            "#,
+        )
+        .bind(trace_action.trace_id)
+        .bind(trace_action.target_id)
+        .bind(&trace_action.target)
+        .bind(&trace_action.action)
+        .bind(&trace_action.actor)
+        .bind(&trace_action.diff)
+        .bind(trace_action.created_at)
+        .bind(self.tenant)
+        .execute(self.pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
     pub async fn create(&self, trace_action: &TraceAction) -> Result<TraceAction, sqlx::Error> {
         sqlx::query_as::<_, TraceAction>(
             r#"
-            INSERT INTO trace_actions (trace_id, target_id, target, action, created_at)
-            VALUES ($1, $2, $3, $4, NOW())
+            INSERT INTO trace_actions (trace_id, target_id, target, action, actor, diff, created_at, tenant_id)
+            VALUES ($1, $2, $3, $4, $5, $6, NOW(), $7)
             RETURNING *
             "#,
         )
@@ -47,15 +115,20 @@ impl<'a> TraceActionStorage<'a> {
         .bind(trace_action.target_id)
         .bind(&trace_action.target)
         .bind(&trace_action.action)
+        .bind(&trace_action.actor)
+        .bind(&trace_action.diff)
+        .bind(self.tenant)
         .fetch_one(self.pool)
         .await
     }
 
     pub async fn delete_by_trace(&self, trace_id: uuid::Uuid) -> Result<u64, sqlx::Error> {
-        let result = sqlx::query("DELETE FROM trace_actions WHERE trace_id = $1")
-            .bind(trace_id)
-            .execute(self.pool)
-            .await?;
+        let result =
+            sqlx::query("DELETE FROM trace_actions WHERE trace_id = $1 AND tenant_id = $2")
+                .bind(trace_id)
+                .bind(self.tenant)
+                .execute(self.pool)
+                .await?;
         Ok(result.rows_affected())
     }
 }
@@ -1,42 +1,110 @@
+use futures::stream::BoxStream;
 use sqlx::PgPool;
 
 use crate::entity::Trace;
 
 pub struct TraceStorage<'a> {
     pool: &'a PgPool,
+    tenant: &'a str,
 }
 
 impl<'a> TraceStorage<'a> {
-    pub fn new(pool: &'a PgPool) -> Self {
-        Self { pool }
+    pub fn new(pool: &'a PgPool, tenant: &'a str) -> Self {
+        Self { pool, tenant }
     }
 
     pub async fn get(&self, id: uuid::Uuid) -> Result<Option<Trace>, sqlx::Error> {
-        sqlx::query_as::<_, Trace>("SELECT * FROM traces WHERE id = $1")
+        sqlx::query_as::<_, Trace>("SELECT * FROM traces WHERE id = $1 AND tenant_id = $2")
             .bind(id)
+            .bind(self.tenant)
             .fetch_optional(self.pool)
             .await
     }
 
     pub async fn get_by_request_id(&self, request_id: &str) -> Result<Vec<Trace>, sqlx::Error> {
-        sqlx::query_as::<_, Trace>("SELECT * FROM traces WHERE request_id = $1")
+        sqlx::query_as::<_, Trace>("SELECT * FROM traces WHERE request_id = $1 AND tenant_id = $2")
             .bind(request_id)
+            .bind(self.tenant)
             .fetch_all(self.pool)
             .await
     }
 
     pub async fn get_children(&self, parent_id: uuid::Uuid) -> Result<Vec<Trace>, sqlx::Error> {
-        sqlx::query_as::<_, Trace>("SELECT * FROM traces WHERE parent_id = $1")
+        sqlx::query_as::<_, Trace>("SELECT * FROM traces WHERE parent_id = $1 AND tenant_id = $2")
             .bind(parent_id)
+            .bind(self.tenant)
             .fetch_all(self.pool)
             .await
     }
 
+    /// Get the traces started from trace `first` through trace `last`, both
+    /// included, in start order, optionally only those of `request_id`
+    pub async fn get_range(
+        &self,
+        first: uuid::Uuid,
+        last: uuid::Uuid,
+        request_id: Option<&str>,
+        limit: i64,
+    ) -> Result<Vec<Trace>, sqlx::Error> {
+        sqlx::query_as::<_, Trace>(
+            r#"
+            SELECT t.* FROM traces t
+            JOIN traces a ON a.id = $1 AND a.tenant_id = $5
+            JOIN traces b ON b.id = $2 AND b.tenant_id = $5
+            WHERE t.tenant_id = $5
+              AND t.started_at BETWEEN LEAST(a.started_at, b.started_at) AND GREATEST(a.started_at, b.started_at)
+              AND ($3::TEXT IS NULL OR t.request_id = $3)
+            ORDER BY t.started_at, t.id
+            LIMIT $4
+            "#,
+        )
+        .bind(first)
+        .bind(last)
+        .bind(request_id)
+        .bind(limit)
+        .bind(self.tenant)
+        .fetch_all(self.pool)
+        .await
+    }
+
+    /// Stream every row of `traces`, for exports.
+    /// Parents are streamed before their children.
+    pub fn stream(&self) -> BoxStream<'a, Result<Trace, sqlx::Error>> {
+        sqlx::query_as::<_, Trace>(
+            "SELECT * FROM traces WHERE tenant_id = $1 ORDER BY started_at, id",
+        )
+        .bind(self.tenant)
+        .fetch(self.pool)
+    }
+
+    /// Insert a previously exported row as-is, keeping its timestamps.
+    /// Returns false if the row already exists.
+    pub async fn restore(&self, trace: &Trace) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO traces (id, parent_id, request_id, status, status_message, started_at, ended_at, tenant_id)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            ON CONFLICT (id) DO NOTHING
+            "#,
+        )
+        .bind(trace.id)
+        .bind(trace.parent_id)
+        .bind(&trace.request_id)
+        .bind(&trace.status)
+        .bind(&trace.status_message)
+        .bind(trace.started_at)
+        .bind(trace.ended_at)
+        .bind(self.tenant)
+        .execute(self.pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
     pub async fn create(&self, trace: &Trace) -> Result<Trace, sqlx::Error> {
         sqlx::query_as::<_, Trace>(
             r#"
-            INSERT INTO traces (id, parent_id, request_id, status, status_message, started_at)
-            VALUES ($1, $2, $3, $4, $5, NOW())
+            INSERT INTO traces (id, parent_id, request_id, status, status_message, started_at, tenant_id)
+            VALUES ($1, $2, $3, $4, $5, NOW(), $6)
             RETURNING *
             "#,
         )
@@ -45,6 +113,7 @@ impl<'a> TraceStorage<'a> {
         .bind(&trace.request_id)
         .bind(&trace.status)
         .bind(&trace.status_message)
+        .bind(self.tenant)
         .fetch_one(self.pool)
         .await
     }
@@ -54,7 +123,7 @@ impl<'a> TraceStorage<'a> {
             r#"
             UPDATE traces
             SET status = $2, status_message = $3, ended_at = $4
-            WHERE id = $1
+            WHERE id = $1 AND tenant_id = $5
             RETURNING *
             "#,
         )
@@ -62,13 +131,15 @@ impl<'a> TraceStorage<'a> {
         .bind(&trace.status)
         .bind(&trace.status_message)
         .bind(trace.ended_at)
+        .bind(self.tenant)
         .fetch_optional(self.pool)
         .await
     }
 
     pub async fn delete(&self, id: uuid::Uuid) -> Result<bool, sqlx::Error> {
-        let result = sqlx::query("DELETE FROM traces WHERE id = $1")
+        let result = sqlx::query("DELETE FROM traces WHERE id = $1 AND tenant_id = $2")
             .bind(id)
+            .bind(self.tenant)
             .execute(self.pool)
             .await?;
         Ok(result.rows_affected() > 0)
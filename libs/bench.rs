@@ -0,0 +1,15 @@
+//! Criterion settings shared by the workspace benchmarks, included into each
+//! bench target with `#[path = "../../bench.rs"] mod bench;`.
+
+use criterion::Criterion;
+
+/// Criterion with `LOOM_BENCH_SAMPLE_SIZE` samples per benchmark, or
+/// `default_sample_size` when it is unset or not a number.
+pub fn config(default_sample_size: usize) -> Criterion {
+    let sample_size = std::env::var("LOOM_BENCH_SAMPLE_SIZE")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(default_sample_size);
+
+    Criterion::default().sample_size(sample_size)
+}
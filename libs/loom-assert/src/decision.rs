@@ -0,0 +1,70 @@
+use crate::Report;
+
+/// Something that decides to accept or reject, such as a scorer's output or
+/// a sample result, checked with [`assert_decision!`](crate::assert_decision).
+pub trait DecisionSubject {
+    /// Name of the decision made (e.g. `Accept`)
+    fn decision_name(&self) -> String;
+
+    /// `(name, value)` pairs explaining the decision, listed when an
+    /// assertion fails (e.g. label scores)
+    fn decision_context(&self) -> Vec<(String, String)> {
+        Vec::new()
+    }
+}
+
+/// Failure report for [`assert_decision!`](crate::assert_decision).
+pub fn decision_report<S: DecisionSubject + ?Sized>(subject: &S, expected: &str) -> Report {
+    Report::new(format!("decision == {}", expected))
+        .actual(subject.decision_name())
+        .rows("context", subject.decision_context())
+}
+
+/// Assert the decision of a [`DecisionSubject`].
+///
+/// ```ignore
+/// assert_decision!(output, Accept);
+/// ```
+///
+/// On failure, panics with the actual decision and the subject's context.
+#[macro_export]
+macro_rules! assert_decision {
+    ($subject:expr, $decision:ident) => {{
+        let subject = &$subject;
+
+        if $crate::DecisionSubject::decision_name(subject) != stringify!($decision) {
+            panic!(
+                "{}",
+                $crate::decision_report(subject, stringify!($decision))
+            );
+        }
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Verdict(&'static str);
+
+    impl DecisionSubject for Verdict {
+        fn decision_name(&self) -> String {
+            self.0.to_string()
+        }
+
+        fn decision_context(&self) -> Vec<(String, String)> {
+            vec![("score".to_string(), "0.120".to_string())]
+        }
+    }
+
+    #[test]
+    fn passes_matching_decision() {
+        assert_decision!(Verdict("Accept"), Accept);
+    }
+
+    #[test]
+    #[should_panic(expected = "decision == Accept\n  actual: Reject\n  context:\n    score  0.120")]
+    fn reports_other_decision() {
+        assert_decision!(Verdict("Reject"), Accept);
+    }
+}
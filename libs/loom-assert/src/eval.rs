@@ -0,0 +1,112 @@
+use crate::{Report, format_score};
+
+/// Something with named evaluation metrics, such as `EvalMetrics`, checked
+/// with [`assert_eval!`](crate::assert_eval).
+pub trait EvalSubject {
+    /// `(name, value)` pairs, in the order reports list them. Overall metrics
+    /// use plain names (`accuracy`, `f1`); breakdowns use dotted names
+    /// (`label.stress.f1`).
+    fn eval_metrics(&self) -> Vec<(String, f32)>;
+
+    /// Value of the metric `name`, or `None` if the subject has no such metric
+    fn eval_metric(&self, name: &str) -> Option<f32> {
+        self.eval_metrics()
+            .into_iter()
+            .find(|(metric, _)| metric == name)
+            .map(|(_, value)| value)
+    }
+}
+
+/// Failure report for [`assert_eval!`](crate::assert_eval), listing every
+/// metric and marking `metric`.
+pub fn eval_report<S: EvalSubject + ?Sized>(
+    subject: &S,
+    expectation: &str,
+    metric: &str,
+    actual: Option<f32>,
+) -> Report {
+    let rows = subject
+        .eval_metrics()
+        .into_iter()
+        .map(|(name, value)| (name, format_score(value)));
+
+    Report::new(expectation)
+        .actual(
+            actual
+                .map(format_score)
+                .unwrap_or_else(|| "missing".to_string()),
+        )
+        .rows("metrics", rows)
+        .mark(metric)
+}
+
+/// Assert on a metric of an [`EvalSubject`], by name or dotted path.
+///
+/// ```ignore
+/// assert_eval!(metrics, accuracy >= 0.9);
+/// assert_eval!(metrics, "label.stress.f1" >= 0.8);
+/// ```
+///
+/// On failure, panics with every metric and the checked one marked.
+#[macro_export]
+macro_rules! assert_eval {
+    (@check $subject:expr, $metric:expr, $op:tt, $expected:expr) => {{
+        let subject = &$subject;
+        let metric: &str = $metric;
+        let expected = ($expected) as f32;
+        let actual = $crate::EvalSubject::eval_metric(subject, metric);
+
+        if !actual.is_some_and(|actual| actual $op expected) {
+            let expectation = format!("{} {} {}", metric, stringify!($op), expected);
+            panic!("{}", $crate::eval_report(subject, &expectation, metric, actual));
+        }
+    }};
+    ($subject:expr, $metric:ident $op:tt $expected:expr) => {
+        $crate::assert_eval!(@check $subject, stringify!($metric), $op, $expected)
+    };
+    ($subject:expr, $metric:literal $op:tt $expected:expr) => {
+        $crate::assert_eval!(@check $subject, $metric, $op, $expected)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Metrics;
+
+    impl EvalSubject for Metrics {
+        fn eval_metrics(&self) -> Vec<(String, f32)> {
+            vec![
+                ("accuracy".to_string(), 0.92),
+                ("f1".to_string(), 0.85),
+                ("label.stress.f1".to_string(), 0.61),
+            ]
+        }
+    }
+
+    #[test]
+    fn passes_matching_metrics() {
+        assert_eval!(Metrics, accuracy >= 0.9);
+        assert_eval!(Metrics, f1 > 0.8);
+        assert_eval!(Metrics, "label.stress.f1" < 0.7);
+    }
+
+    #[test]
+    #[should_panic(expected = "label.stress.f1 >= 0.8\n  actual: 0.610")]
+    fn reports_failing_metric() {
+        assert_eval!(Metrics, "label.stress.f1" >= 0.8);
+    }
+
+    #[test]
+    #[should_panic(expected = "> accuracy")]
+    fn marks_failing_metric() {
+        assert_eval!(Metrics, accuracy >= 0.95);
+    }
+
+    #[test]
+    #[should_panic(expected = "actual: missing")]
+    fn reports_missing_metric() {
+        assert_eval!(Metrics, recall >= 0.5);
+    }
+}
@@ -1 +1,19 @@
+//! Domain assertions for Loom test suites.
+//!
+//! The macros check anything implementing the subject traits ([`ScoreSubject`],
+//! [`DecisionSubject`], [`EvalSubject`]) and panic with a [`Report`] listing
+//! every score or metric the subject had, so a failing golden test shows more
+//! than the single value that missed. [`assert_value_eq!`] compares whole
+//! serialized results, within a [`ValueMatcher`]'s tolerances.
 
+mod decision;
+mod eval;
+mod report;
+mod score;
+mod value;
+
+pub use decision::*;
+pub use eval::*;
+pub use report::*;
+pub use score::*;
+pub use value::*;
@@ -0,0 +1,128 @@
+use std::fmt;
+
+/// Failure message of a domain assertion: the failed expectation, the actual
+/// value, and every value the subject had, with the offending row marked.
+///
+/// ```text
+/// assertion failed: label "stress" >= 0.7
+///   actual: 0.420
+///   labels:
+///   > stress   0.420
+///     anxiety  0.310
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Report {
+    expectation: String,
+    actual: Option<String>,
+    section: Option<String>,
+    rows: Vec<(String, String)>,
+    marked: Option<String>,
+}
+
+impl Report {
+    pub fn new(expectation: impl Into<String>) -> Self {
+        Self {
+            expectation: expectation.into(),
+            ..Default::default()
+        }
+    }
+
+    pub fn actual(mut self, actual: impl fmt::Display) -> Self {
+        self.actual = Some(actual.to_string());
+        self
+    }
+
+    /// Rows listed under `section`, in order.
+    pub fn rows(
+        mut self,
+        section: impl Into<String>,
+        rows: impl IntoIterator<Item = (String, String)>,
+    ) -> Self {
+        self.section = Some(section.into());
+        self.rows = rows.into_iter().collect();
+        self
+    }
+
+    /// Mark the row named `name` as the one the assertion failed on.
+    pub fn mark(mut self, name: impl Into<String>) -> Self {
+        self.marked = Some(name.into());
+        self
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "assertion failed: {}", self.expectation)?;
+
+        if let Some(actual) = &self.actual {
+            write!(f, "\n  actual: {}", actual)?;
+        }
+
+        if let Some(section) = &self.section {
+            if self.rows.is_empty() {
+                return write!(f, "\n  {}: (none)", section);
+            }
+
+            write!(f, "\n  {}:", section)?;
+            let width = self.rows.iter().map(|(name, _)| name.len()).max();
+
+            for (name, value) in &self.rows {
+                let marker = if self.marked.as_deref() == Some(name.as_str()) {
+                    '>'
+                } else {
+                    ' '
+                };
+
+                write!(
+                    f,
+                    "\n  {} {:width$}  {}",
+                    marker,
+                    name,
+                    value,
+                    width = width.unwrap_or_default()
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Format a score or metric the way reports show it.
+pub fn format_score(value: f32) -> String {
+    format!("{:.3}", value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn marks_the_failing_row() {
+        let report = Report::new("label \"stress\" >= 0.7")
+            .actual(format_score(0.42))
+            .rows(
+                "labels",
+                [
+                    ("stress".to_string(), format_score(0.42)),
+                    ("anxiety".to_string(), format_score(0.31)),
+                ],
+            )
+            .mark("stress");
+
+        assert_eq!(
+            report.to_string(),
+            "assertion failed: label \"stress\" >= 0.7\n  actual: 0.420\n  labels:\n  > stress   0.420\n    anxiety  0.310"
+        );
+    }
+
+    #[test]
+    fn reports_empty_sections() {
+        let report = Report::new("accuracy >= 0.9").rows("metrics", []);
+
+        assert_eq!(
+            report.to_string(),
+            "assertion failed: accuracy >= 0.9\n  metrics: (none)"
+        );
+    }
+}
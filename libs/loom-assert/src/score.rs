@@ -0,0 +1,132 @@
+use crate::{Report, format_score};
+
+/// Something with an overall score and per-label scores, such as a
+/// `ScoreResult`, checked with [`assert_score!`](crate::assert_score).
+pub trait ScoreSubject {
+    /// Overall score
+    fn overall_score(&self) -> f32;
+
+    /// `(label, score)` pairs, in the order reports list them
+    fn label_scores(&self) -> Vec<(String, f32)>;
+
+    /// Score of `label`, or `None` if the subject has no such label
+    fn label_score(&self, label: &str) -> Option<f32> {
+        self.label_scores()
+            .into_iter()
+            .find(|(name, _)| name == label)
+            .map(|(_, score)| score)
+    }
+}
+
+/// Failure report for [`assert_score!`](crate::assert_score), listing every
+/// label score and marking `label` when there is one.
+pub fn score_report<S: ScoreSubject + ?Sized>(
+    subject: &S,
+    expectation: &str,
+    label: Option<&str>,
+    actual: Option<f32>,
+) -> Report {
+    let rows = subject
+        .label_scores()
+        .into_iter()
+        .map(|(name, score)| (name, format_score(score)));
+
+    let report = Report::new(expectation)
+        .actual(
+            actual
+                .map(format_score)
+                .unwrap_or_else(|| "missing".to_string()),
+        )
+        .rows("labels", rows);
+
+    match label {
+        Some(label) => report.mark(label),
+        None => report,
+    }
+}
+
+/// Assert on the overall score or a label's score of a [`ScoreSubject`].
+///
+/// ```ignore
+/// assert_score!(result, score >= 0.5);
+/// assert_score!(result, label "stress" >= 0.7);
+/// ```
+///
+/// On failure, panics with every label's score and the checked one marked.
+#[macro_export]
+macro_rules! assert_score {
+    ($subject:expr, label $label:literal $op:tt $expected:expr) => {{
+        let subject = &$subject;
+        let expected = ($expected) as f32;
+        let actual = $crate::ScoreSubject::label_score(subject, $label);
+
+        if !actual.is_some_and(|actual| actual $op expected) {
+            let expectation = format!("label {:?} {} {}", $label, stringify!($op), expected);
+            panic!(
+                "{}",
+                $crate::score_report(subject, &expectation, Some($label), actual)
+            );
+        }
+    }};
+    ($subject:expr, score $op:tt $expected:expr) => {{
+        let subject = &$subject;
+        let expected = ($expected) as f32;
+        let actual = $crate::ScoreSubject::overall_score(subject);
+
+        if !(actual $op expected) {
+            let expectation = format!("score {} {}", stringify!($op), expected);
+            panic!(
+                "{}",
+                $crate::score_report(subject, &expectation, None, Some(actual))
+            );
+        }
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Scores(Vec<(&'static str, f32)>);
+
+    impl ScoreSubject for Scores {
+        fn overall_score(&self) -> f32 {
+            self.0.iter().map(|(_, s)| *s).fold(0.0, f32::max)
+        }
+
+        fn label_scores(&self) -> Vec<(String, f32)> {
+            self.0.iter().map(|(l, s)| (l.to_string(), *s)).collect()
+        }
+    }
+
+    fn scores() -> Scores {
+        Scores(vec![("stress", 0.42), ("anxiety", 0.31)])
+    }
+
+    #[test]
+    fn passes_matching_scores() {
+        let result = scores();
+
+        assert_score!(result, label "stress" >= 0.4);
+        assert_score!(result, label "anxiety" < 0.5);
+        assert_score!(result, score > 0.4);
+    }
+
+    #[test]
+    #[should_panic(expected = "label \"stress\" >= 0.7\n  actual: 0.420\n  labels:\n  > stress")]
+    fn reports_failing_label() {
+        assert_score!(scores(), label "stress" >= 0.7);
+    }
+
+    #[test]
+    #[should_panic(expected = "actual: missing")]
+    fn reports_missing_label() {
+        assert_score!(scores(), label "joy" >= 0.1);
+    }
+
+    #[test]
+    #[should_panic(expected = "score >= 0.9")]
+    fn reports_failing_overall_score() {
+        assert_score!(scores(), score >= 0.9);
+    }
+}
@@ -0,0 +1,387 @@
+use std::any::Any;
+
+use loom_core::value::{Change, ChangeKind, Number, Value};
+use serde::Serialize;
+
+use crate::Report;
+
+/// Fields that differ between otherwise identical runs: timings, throughput,
+/// timestamps and the host environment.
+const VOLATILE: &[&str] = &[
+    "**.elapsed_ms",
+    "**.throughput",
+    "**.timestamp",
+    "**.created_at",
+    "**.environment",
+];
+
+/// How [`assert_value_eq!`](crate::assert_value_eq) compares two values:
+/// numeric tolerances and paths to skip.
+///
+/// Paths use the same form as [`Value::diff`] (`sample_results[0].score`),
+/// where `*` matches any single key or index (`labels.*.score`,
+/// `sample_results[*]`) and `**` matches any number of segments
+/// (`**.elapsed_ms`). Ignoring a path skips everything beneath it.
+///
+/// ```ignore
+/// let matcher = ValueMatcher::new()
+///     .tolerance(1e-6)
+///     .tolerance_at("**.score", 1e-3)
+///     .ignore_volatile();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ValueMatcher {
+    tolerance: f64,
+    tolerances: Vec<(Pattern, f64)>,
+    ignored: Vec<Pattern>,
+}
+
+impl ValueMatcher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Absolute tolerance for numbers at any path without a more specific one
+    /// (default `0`, exact).
+    pub fn tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Absolute tolerance for numbers matching `path`. When several paths
+    /// match, the one added last wins.
+    pub fn tolerance_at(mut self, path: &str, tolerance: f64) -> Self {
+        self.tolerances.push((Pattern::parse(path), tolerance));
+        self
+    }
+
+    /// Skip `path` and everything beneath it.
+    pub fn ignore(mut self, path: &str) -> Self {
+        self.ignored.push(Pattern::parse(path));
+        self
+    }
+
+    /// Skip timings (`elapsed_ms`, `throughput`), timestamps (`timestamp`,
+    /// `created_at`) and the eval `environment` at any depth.
+    pub fn ignore_volatile(self) -> Self {
+        VOLATILE
+            .iter()
+            .fold(self, |matcher, path| matcher.ignore(path))
+    }
+
+    /// Differences between `actual` and `expected`, as [`Change`]s from the
+    /// expected value (`before`) to the actual one (`after`). Numbers compare
+    /// by value, so `1` matches `1.0`.
+    pub fn mismatches(&self, actual: &Value, expected: &Value) -> Vec<Change> {
+        let mut mismatches = Vec::new();
+        self.compare(
+            &mut Vec::new(),
+            Some(expected),
+            Some(actual),
+            &mut mismatches,
+        );
+        mismatches
+    }
+
+    fn compare(
+        &self,
+        path: &mut Vec<Segment>,
+        expected: Option<&Value>,
+        actual: Option<&Value>,
+        out: &mut Vec<Change>,
+    ) {
+        if self.ignored.iter().any(|pattern| pattern.matches(path)) {
+            return;
+        }
+
+        match (expected, actual) {
+            (Some(Value::Object(a)), Some(Value::Object(b))) => {
+                let keys: std::collections::BTreeSet<&String> = a.keys().chain(b.keys()).collect();
+
+                for key in keys {
+                    path.push(Segment::Key(key.clone()));
+                    self.compare(path, a.get(key), b.get(key), out);
+                    path.pop();
+                }
+            }
+            (Some(Value::Array(a)), Some(Value::Array(b))) => {
+                for i in 0..a.len().max(b.len()) {
+                    path.push(Segment::Index(i));
+                    self.compare(path, a.get(i), b.get(i), out);
+                    path.pop();
+                }
+            }
+            (Some(Value::Number(a)), Some(Value::Number(b)))
+                if numbers_match(a, b, self.tolerance_for(path)) => {}
+            (a, b) if a == b => {}
+            (a, b) => out.push(Change {
+                path: render(path),
+                before: a.cloned(),
+                after: b.cloned(),
+            }),
+        }
+    }
+
+    fn tolerance_for(&self, path: &[Segment]) -> f64 {
+        self.tolerances
+            .iter()
+            .rev()
+            .find(|(pattern, _)| pattern.matches(path))
+            .map_or(self.tolerance, |(_, tolerance)| *tolerance)
+    }
+}
+
+/// Failure report for [`assert_value_eq!`](crate::assert_value_eq), listing
+/// each mismatched path with the actual and expected value.
+pub fn value_report(mismatches: &[Change]) -> Report {
+    let rows = mismatches.iter().map(|change| {
+        let path = if change.path.is_empty() {
+            "(root)".to_string()
+        } else {
+            change.path.clone()
+        };
+
+        let detail = match (change.kind(), &change.before, &change.after) {
+            (ChangeKind::Added, _, Some(actual)) => format!("unexpected {}", actual),
+            (ChangeKind::Removed, Some(expected), _) => format!("missing, expected {}", expected),
+            (_, Some(expected), Some(actual)) => format!("{}, expected {}", actual, expected),
+            _ => String::new(),
+        };
+
+        (path, detail)
+    });
+
+    Report::new("value == expected")
+        .actual(format!("{} mismatched path(s)", mismatches.len()))
+        .rows("mismatches", rows)
+}
+
+/// Convert `value` for comparison: a [`Value`] is used as-is, anything else
+/// goes through its `Serialize` impl (as JSON).
+pub fn to_value<T: Serialize + Any>(value: &T) -> Value {
+    if let Some(value) = (value as &dyn Any).downcast_ref::<Value>() {
+        return value.clone();
+    }
+
+    serde_json::to_value(value)
+        .map(Value::from)
+        .unwrap_or_else(|e| panic!("failed to serialize value for comparison: {}", e))
+}
+
+/// Assert two values are equal up to a [`ValueMatcher`]'s tolerances and
+/// ignored paths. Either side may be a [`Value`] or any `Serialize` type,
+/// such as a `ScoreResult` and its golden copy read back from disk.
+///
+/// ```ignore
+/// assert_value_eq!(result, golden);
+/// assert_value_eq!(result, golden, ValueMatcher::new().tolerance(1e-6).ignore_volatile());
+/// ```
+///
+/// On failure, panics with every mismatched path.
+#[macro_export]
+macro_rules! assert_value_eq {
+    ($actual:expr, $expected:expr $(,)?) => {
+        $crate::assert_value_eq!($actual, $expected, $crate::ValueMatcher::new())
+    };
+    ($actual:expr, $expected:expr, $matcher:expr $(,)?) => {{
+        let actual = $crate::to_value(&$actual);
+        let expected = $crate::to_value(&$expected);
+        let mismatches = $crate::ValueMatcher::mismatches(&$matcher, &actual, &expected);
+
+        if !mismatches.is_empty() {
+            panic!("{}", $crate::value_report(&mismatches));
+        }
+    }};
+}
+
+fn numbers_match(a: &Number, b: &Number, tolerance: f64) -> bool {
+    match (a, b) {
+        (Number::Int(a), Number::Int(b)) if a == b => true,
+        _ => (as_f64(a) - as_f64(b)).abs() <= tolerance,
+    }
+}
+
+fn as_f64(number: &Number) -> f64 {
+    match number {
+        Number::Int(v) => *v as f64,
+        Number::Float(v) => *v,
+    }
+}
+
+fn render(path: &[Segment]) -> String {
+    let mut out = String::new();
+
+    for segment in path {
+        match segment {
+            Segment::Key(key) if out.is_empty() => out.push_str(key),
+            Segment::Key(key) => {
+                out.push('.');
+                out.push_str(key);
+            }
+            Segment::Index(i) => out.push_str(&format!("[{}]", i)),
+        }
+    }
+
+    out
+}
+
+/// One step of a value path.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Key(String),
+    Index(usize),
+}
+
+/// One step of a path pattern.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Step {
+    Key(String),
+    Index(usize),
+    /// `*` / `[*]`: any single key or index
+    Any,
+    /// `**`: any number of segments, including none
+    Rest,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Pattern(Vec<Step>);
+
+impl Pattern {
+    fn parse(path: &str) -> Self {
+        let mut steps = Vec::new();
+
+        for part in path.split('.').filter(|part| !part.is_empty()) {
+            let (name, indices) = part.split_at(part.find('[').unwrap_or(part.len()));
+
+            match name {
+                "" => {}
+                "**" => steps.push(Step::Rest),
+                "*" => steps.push(Step::Any),
+                name => steps.push(Step::Key(name.to_string())),
+            }
+
+            for index in indices.split('[').filter_map(|i| i.strip_suffix(']')) {
+                steps.push(match index.parse() {
+                    Ok(i) => Step::Index(i),
+                    Err(_) => Step::Any,
+                });
+            }
+        }
+
+        Self(steps)
+    }
+
+    fn matches(&self, path: &[Segment]) -> bool {
+        matches_from(&self.0, path)
+    }
+}
+
+fn matches_from(steps: &[Step], path: &[Segment]) -> bool {
+    match (steps.split_first(), path.split_first()) {
+        (None, None) => true,
+        (Some((Step::Rest, rest)), _) => {
+            matches_from(rest, path) || (!path.is_empty() && matches_from(steps, &path[1..]))
+        }
+        (Some((step, rest)), Some((segment, path))) => {
+            let matched = match (step, segment) {
+                (Step::Any, _) => true,
+                (Step::Key(a), Segment::Key(b)) => a == b,
+                (Step::Index(a), Segment::Index(b)) => a == b,
+                _ => false,
+            };
+
+            matched && matches_from(rest, path)
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Serialize)]
+    struct Run {
+        score: f32,
+        labels: Vec<&'static str>,
+        elapsed_ms: i64,
+    }
+
+    fn object(json: &str) -> Value {
+        Value::from(serde_json::from_str::<serde_json::Value>(json).unwrap())
+    }
+
+    #[test]
+    fn passes_within_tolerance() {
+        let actual = object(r#"{"score": 0.8200001, "count": 3}"#);
+        let expected = object(r#"{"score": 0.82, "count": 3.0}"#);
+
+        assert_value_eq!(actual, expected, ValueMatcher::new().tolerance(1e-6));
+    }
+
+    #[test]
+    fn per_path_tolerance_wins() {
+        let matcher = ValueMatcher::new()
+            .tolerance(1e-6)
+            .tolerance_at("labels.*.score", 0.01);
+        let actual = object(r#"{"score": 0.5, "labels": {"stress": {"score": 0.705}}}"#);
+        let expected = object(r#"{"score": 0.5001, "labels": {"stress": {"score": 0.7}}}"#);
+        let mismatches = matcher.mismatches(&actual, &expected);
+
+        assert_eq!(mismatches.len(), 1);
+        assert_eq!(mismatches[0].path, "score");
+    }
+
+    #[test]
+    fn ignores_volatile_fields() {
+        let actual = object(
+            r#"{"elapsed_ms": 120, "samples": [{"id": "a", "elapsed_ms": 3}], "environment": {"hostname": "ci"}}"#,
+        );
+        let expected = object(r#"{"elapsed_ms": 95, "samples": [{"id": "a", "elapsed_ms": 7}]}"#);
+
+        assert_value_eq!(actual, expected, ValueMatcher::new().ignore_volatile());
+    }
+
+    #[test]
+    fn compares_serialized_structs() {
+        let run = Run {
+            score: 0.82,
+            labels: vec!["stress"],
+            elapsed_ms: 10,
+        };
+        let golden = object(r#"{"score": 0.82, "labels": ["stress"], "elapsed_ms": 12}"#);
+
+        assert_value_eq!(
+            run,
+            golden,
+            ValueMatcher::new().tolerance(1e-6).ignore("elapsed_ms")
+        );
+    }
+
+    #[test]
+    fn matches_patterns() {
+        let path = [
+            Segment::Key("sample_results".to_string()),
+            Segment::Index(2),
+            Segment::Key("elapsed_ms".to_string()),
+        ];
+
+        assert!(Pattern::parse("sample_results[2].elapsed_ms").matches(&path));
+        assert!(Pattern::parse("sample_results[*].elapsed_ms").matches(&path));
+        assert!(Pattern::parse("**.elapsed_ms").matches(&path));
+        assert!(Pattern::parse("**").matches(&path));
+        assert!(!Pattern::parse("sample_results[1].elapsed_ms").matches(&path));
+        assert!(!Pattern::parse("*.elapsed_ms").matches(&path));
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "value == expected\n  actual: 2 mismatched path(s)\n  mismatches:\n    labels[1]  missing, expected joy\n    score      0.9, expected 0.82"
+    )]
+    fn reports_mismatched_paths() {
+        let actual = object(r#"{"score": 0.9, "labels": ["stress"]}"#);
+        let expected = object(r#"{"score": 0.82, "labels": ["stress", "joy"]}"#);
+
+        assert_value_eq!(actual, expected, ValueMatcher::new().tolerance(1e-6));
+    }
+}
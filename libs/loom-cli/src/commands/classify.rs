@@ -4,6 +4,7 @@ use clap::Args;
 use loom::runtime::{FileSystemSource, JsonCodec, Runtime, TomlCodec, YamlCodec};
 
 use super::load_config;
+use crate::widgets::ScoreTree;
 
 /// Classify a single text
 #[derive(Debug, Args)]
@@ -37,23 +38,18 @@ impl ClassifyCommand {
             .config(config)
             .build();
 
-        // Use runtime.score() which internally uses runtime.eval()
-        match runtime.score(&self.text) {
-            Ok(result) => {
-                println!("Decision: Accept");
-                println!("Score: {:.3}", result.score);
-                println!("\nDetected labels:");
-                for (label, score) in result.raw_scores() {
-                    if score > 0.0 {
-                        println!("  {}: {:.3}", label, score);
-                    }
-                }
-            }
+        // Score without applying the decision, so rejected results can still be shown
+        let result = match runtime.score_batch(&[self.text.as_str()]) {
+            Ok(mut outputs) => outputs.remove(0).inner().clone(),
             Err(e) => {
-                // Score below threshold means rejection
-                println!("Decision: Reject");
-                println!("Reason: {}", e);
+                eprintln!("Error scoring text: {}", e);
+                std::process::exit(1);
             }
-        }
+        };
+
+        let scorer = runtime.scorer().lock().expect("scorer lock poisoned");
+        let tree = ScoreTree::new(&result, scorer.config()).text_len(self.text.len());
+
+        print!("{}", tree);
     }
 }
@@ -0,0 +1,89 @@
+use std::io::stdout;
+use std::path::PathBuf;
+
+use clap::Args;
+use crossterm::ExecutableCommand;
+use crossterm::style::{Color, ResetColor, SetForegroundColor};
+use loom::io::path::{FilePath, Path};
+use loom::runtime::{Runtime, eval};
+
+use super::build_runtime;
+
+/// Compare two eval runs on the same dataset
+#[derive(Debug, Args)]
+pub struct CompareCommand {
+    /// Path to the baseline results JSON (from run command)
+    pub baseline: PathBuf,
+
+    /// Path to the candidate results JSON (from run command)
+    pub candidate: PathBuf,
+
+    /// Significance level for the McNemar test
+    #[arg(long, default_value_t = 0.05)]
+    pub alpha: f64,
+
+    /// Exit with error code if the candidate is significantly worse
+    #[arg(long)]
+    pub fail_on_regression: bool,
+}
+
+impl CompareCommand {
+    pub async fn exec(self) {
+        let runtime = build_runtime();
+        let baseline = load(&runtime, &self.baseline).await;
+        let candidate = load(&runtime, &self.candidate).await;
+        let cmp = baseline.compare(&candidate, self.alpha);
+        let mut stdout = stdout();
+
+        println!("Paired samples: {}", cmp.paired);
+        if cmp.unpaired > 0 {
+            eprintln!(
+                "Warning: Ignoring {} samples not present in both runs",
+                cmp.unpaired
+            );
+        }
+
+        println!();
+        println!("Baseline accuracy:  {:.1}%", cmp.baseline_accuracy * 100.0);
+        println!("Candidate accuracy: {:.1}%", cmp.candidate_accuracy * 100.0);
+        println!("Delta:              {:+.1}%", cmp.delta() * 100.0);
+        println!();
+        println!("Only baseline correct:  {}", cmp.baseline_only);
+        println!("Only candidate correct: {}", cmp.candidate_only);
+        println!("McNemar p-value:        {:.4}", cmp.p_value);
+        println!();
+
+        if cmp.is_regression() {
+            let _ = stdout.execute(SetForegroundColor(Color::Red));
+            print!("✗ ");
+            let _ = stdout.execute(ResetColor);
+            println!("Significant regression (p < {})", cmp.alpha);
+        } else if cmp.is_improvement() {
+            let _ = stdout.execute(SetForegroundColor(Color::Green));
+            print!("✓ ");
+            let _ = stdout.execute(ResetColor);
+            println!("Significant improvement (p < {})", cmp.alpha);
+        } else {
+            let _ = stdout.execute(SetForegroundColor(Color::Yellow));
+            print!("○ ");
+            let _ = stdout.execute(ResetColor);
+            println!("No significant difference (p >= {})", cmp.alpha);
+        }
+
+        if self.fail_on_regression && cmp.is_regression() {
+            std::process::exit(1);
+        }
+    }
+}
+
+async fn load(runtime: &Runtime, path: &std::path::Path) -> eval::EvalResult {
+    let file_path = Path::File(FilePath::from(path.to_path_buf()));
+
+    match runtime.fetch(&file_path).await {
+        Ok(r) => r,
+        Err(e) => {
+            eprintln!("Error loading results from {:?}: {}", path, e);
+            std::process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use std::path::PathBuf;
+
+use clap::{Args, Subcommand};
+use loom::runtime::LoomConfig;
+
+use super::write_atomic;
+
+/// Inspect config files
+#[derive(Debug, Args)]
+pub struct ConfigCommand {
+    #[command(subcommand)]
+    pub command: ConfigSubcommand,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ConfigSubcommand {
+    /// Print the JSON Schema of config files, for editors to validate against
+    Schema(SchemaCommand),
+}
+
+impl ConfigCommand {
+    pub fn exec(self) {
+        match self.command {
+            ConfigSubcommand::Schema(cmd) => cmd.exec(),
+        }
+    }
+}
+
+/// Print the config file JSON Schema
+#[derive(Debug, Args)]
+pub struct SchemaCommand {
+    /// Write the schema to this file instead of stdout
+    #[arg(short, long)]
+    pub output: Option<PathBuf>,
+}
+
+impl SchemaCommand {
+    pub fn exec(self) {
+        let schema = serde_json::to_string_pretty(&LoomConfig::schema())
+            .expect("JSON Schema is serializable");
+
+        match &self.output {
+            Some(path) => {
+                if let Err(e) = write_atomic(path, schema) {
+                    eprintln!("Error writing schema to {:?}: {}", path, e);
+                    std::process::exit(1);
+                }
+            }
+            None => println!("{}", schema),
+        }
+    }
+}
@@ -0,0 +1,107 @@
+use std::path::PathBuf;
+
+use clap::{Args, ValueEnum};
+use loom::core::Format;
+use loom::io::path::{FilePath, Path};
+use loom::runtime::eval;
+
+use super::{build_runtime, resolve_output_path, write_atomic};
+use crate::widgets;
+
+/// Export format of the confusion matrix
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum ConfusionFormat {
+    Json,
+    Csv,
+}
+
+/// Build a label confusion matrix from eval results
+#[derive(Debug, Args)]
+pub struct ConfusionCommand {
+    /// Path to the results JSON (from run command)
+    pub path: PathBuf,
+
+    /// Output directory for the matrix (default: input file's directory)
+    #[arg(short, long)]
+    pub output: Option<PathBuf>,
+
+    /// Export format
+    #[arg(short, long, value_enum, default_value_t = ConfusionFormat::Json)]
+    pub format: ConfusionFormat,
+
+    /// Number of most confused label pairs to show
+    #[arg(long, default_value_t = 10)]
+    pub top: usize,
+}
+
+impl ConfusionCommand {
+    pub async fn exec(self) {
+        let path = &self.path;
+        let runtime = build_runtime();
+        let file_path = Path::File(FilePath::from(path.clone()));
+
+        let result: eval::EvalResult = match runtime.fetch(&file_path).await {
+            Ok(r) => r,
+            Err(e) => {
+                eprintln!("Error loading results: {}", e);
+                std::process::exit(1);
+            }
+        };
+
+        let confusion = result.confusion();
+        let pairs = confusion.pairs();
+
+        println!("=== Most Confused Labels ===\n");
+
+        if pairs.is_empty() {
+            println!("No confused labels");
+        } else {
+            let mut table = widgets::Table::new().headers(vec!["Expected", "Detected", "Count"]);
+
+            for pair in pairs.iter().take(self.top) {
+                table = table.row(vec![
+                    pair.expected.clone(),
+                    pair.detected.clone(),
+                    pair.count.to_string(),
+                ]);
+            }
+
+            print!("{}", table);
+        }
+
+        let filename = match self.format {
+            ConfusionFormat::Json => "confusion.json",
+            ConfusionFormat::Csv => "confusion.csv",
+        };
+        let output_path = resolve_output_path(path, self.output.as_deref(), filename);
+
+        // Ensure output directory exists
+        if let Some(parent) = output_path.parent() {
+            if let Err(e) = std::fs::create_dir_all(parent) {
+                eprintln!("Error creating output directory: {}", e);
+                std::process::exit(1);
+            }
+        }
+
+        let written = match self.format {
+            ConfusionFormat::Json => runtime
+                .store(
+                    &Path::File(FilePath::from(output_path.clone())),
+                    &confusion,
+                    Format::Json,
+                )
+                .await
+                .map_err(|e| e.to_string()),
+            ConfusionFormat::Csv => {
+                write_atomic(&output_path, confusion.to_csv()).map_err(|e| e.to_string())
+            }
+        };
+
+        if let Err(e) = written {
+            eprintln!("Error writing output file: {}", e);
+            std::process::exit(1);
+        }
+
+        println!("\nConfusion matrix written to {:?}", output_path);
+    }
+}
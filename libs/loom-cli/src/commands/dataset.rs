@@ -0,0 +1,211 @@
+use std::path::PathBuf;
+
+use clap::{Args, Subcommand};
+use loom::core::ident_path;
+use loom::io::path::{FilePath, Path};
+use loom::runtime::{
+    FileSystemSource, JsonCodec, Runtime, ScoreConfig, TomlCodec, YamlCodec, eval,
+};
+
+use super::{build_runtime, load_config};
+use crate::widgets;
+
+/// Width of the longest text length histogram bar
+const BAR_WIDTH: usize = 40;
+
+/// Inspect a dataset
+#[derive(Debug, Args)]
+pub struct DatasetCommand {
+    #[command(subcommand)]
+    pub command: DatasetSubcommand,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum DatasetSubcommand {
+    /// Report sample counts, text lengths, duplicates and class imbalance
+    Stats(StatsCommand),
+}
+
+impl DatasetCommand {
+    pub async fn exec(self) {
+        match self.command {
+            DatasetSubcommand::Stats(cmd) => cmd.exec().await,
+        }
+    }
+}
+
+/// Report dataset statistics
+#[derive(Debug, Args)]
+pub struct StatsCommand {
+    /// Path to the dataset JSON file
+    pub path: PathBuf,
+
+    /// Print statistics as JSON
+    #[arg(long)]
+    pub json: bool,
+
+    /// Config whose model tokenizer is used to also report text lengths in tokens,
+    /// and whose categories and labels are counted even if no sample expects them
+    #[arg(short, long)]
+    pub config: Option<PathBuf>,
+}
+
+impl StatsCommand {
+    pub async fn exec(self) {
+        let file_path = Path::File(FilePath::from(self.path.clone()));
+        let dataset: eval::SampleDataset = match build_runtime().fetch(&file_path).await {
+            Ok(d) => d,
+            Err(e) => {
+                eprintln!("Error loading dataset: {}", e);
+                std::process::exit(1);
+            }
+        };
+
+        let stats = match &self.config {
+            Some(config_path) => {
+                let config = match load_config(config_path.to_str().unwrap_or_default()) {
+                    Ok(c) => c,
+                    Err(e) => {
+                        eprintln!("Error loading config: {}", e);
+                        std::process::exit(1);
+                    }
+                };
+
+                // Count the config's categories and labels even if no sample expects them
+                let score_config: ScoreConfig =
+                    match config.get_section(&ident_path!("layers.score")).bind() {
+                        Ok(c) => c,
+                        Err(e) => {
+                            eprintln!("Error parsing score config: {}", e);
+                            std::process::exit(1);
+                        }
+                    };
+
+                // Build runtime with config in blocking task (scorer building uses rust-bert which conflicts with tokio)
+                let runtime = match tokio::task::spawn_blocking(move || {
+                    Runtime::new()
+                        .source(FileSystemSource::builder().build())
+                        .codec(JsonCodec::new())
+                        .codec(YamlCodec::new())
+                        .codec(TomlCodec::new())
+                        .config(config)
+                        .build()
+                })
+                .await
+                {
+                    Ok(r) => r,
+                    Err(e) => {
+                        eprintln!("Error building runtime: {}", e);
+                        std::process::exit(1);
+                    }
+                };
+
+                let stats = dataset
+                    .stats_with_tokens(|text| runtime.tokenize_len(text))
+                    .with_score_config(&score_config);
+
+                if stats.token_length.is_none() {
+                    eprintln!("Warning: the configured model does not expose its tokenizer");
+                }
+
+                stats
+            }
+            None => dataset.stats(),
+        };
+
+        if self.json {
+            match serde_json::to_string_pretty(&stats) {
+                Ok(json) => println!("{}", json),
+                Err(e) => {
+                    eprintln!("Error serializing stats: {}", e);
+                    std::process::exit(1);
+                }
+            }
+
+            return;
+        }
+
+        println!("=== Dataset Statistics ===\n");
+        println!("Total samples: {}", stats.total);
+        println!(
+            "Decisions:     {} accept / {} reject",
+            stats.accept, stats.reject
+        );
+        println!(
+            "Difficulty:    {} easy / {} medium / {} hard",
+            stats.per_difficulty.easy, stats.per_difficulty.medium, stats.per_difficulty.hard
+        );
+        println!(
+            "Duplicates:    {} texts ({:.1}%), {} IDs",
+            stats.duplicate_texts,
+            stats.duplicate_rate() * 100.0,
+            stats.duplicate_ids
+        );
+
+        println!("\n=== Imbalance (largest / smallest) ===\n");
+        println!("Decision: {:.2}", stats.decision_imbalance());
+        println!("Category: {:.2}", stats.category_imbalance());
+        println!("Label:    {:.2}", stats.label_imbalance());
+
+        println!("\n=== Per-Category ===\n");
+        print!(
+            "{}",
+            counts_table("Category", &stats.per_category, stats.total)
+        );
+
+        println!("\n=== Per-Label ===\n");
+        print!("{}", counts_table("Label", &stats.per_label, stats.total));
+
+        println!("\n=== Text Length (chars) ===\n");
+        print_length(&stats.text_length);
+
+        if let Some(tokens) = &stats.token_length {
+            println!("\n=== Text Length (tokens) ===\n");
+            print_length(tokens);
+        }
+    }
+}
+
+fn print_length(length: &eval::LengthStats) {
+    println!(
+        "min {} / median {} / mean {:.1} / max {}\n",
+        length.min, length.median, length.mean, length.max
+    );
+
+    let most = length.histogram.iter().map(|b| b.count).max().unwrap_or(0);
+
+    for bucket in &length.histogram {
+        let range = match bucket.max {
+            Some(max) => format!("{}-{}", bucket.min, max - 1),
+            None => format!("{}+", bucket.min),
+        };
+        let bar = if most > 0 {
+            bucket.count * BAR_WIDTH / most
+        } else {
+            0
+        };
+
+        println!("{:>8} │{} {}", range, "█".repeat(bar), bucket.count);
+    }
+}
+
+fn counts_table(
+    name: &str,
+    counts: &std::collections::BTreeMap<String, usize>,
+    total: usize,
+) -> widgets::Table {
+    let mut rows: Vec<_> = counts.iter().collect();
+    rows.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+
+    let mut table = widgets::Table::new().headers(vec![name, "Count", "Share"]);
+
+    for (key, count) in rows {
+        table = table.row(vec![
+            key.clone(),
+            count.to_string(),
+            format!("{:.1}%", *count as f32 / total.max(1) as f32 * 100.0),
+        ]);
+    }
+
+    table
+}
@@ -1,17 +1,32 @@
 use std::path::{Path, PathBuf};
 
 use loom::config::{Config, ConfigError, EnvProvider, FileProvider};
-use loom::runtime::{FileSystemSource, JsonCodec, Runtime, TomlCodec, YamlCodec};
+use loom::io::AtomicFile;
+#[cfg(feature = "arrow")]
+use loom::runtime::ArrowCodec;
+#[cfg(feature = "parquet")]
+use loom::runtime::ParquetCodec;
+use loom::runtime::{FileSystemSource, JsonCodec, NdjsonCodec, Runtime, TomlCodec, YamlCodec};
 
 pub mod classify;
+pub mod compare;
+pub mod config;
+pub mod confusion;
+pub mod dataset;
 pub mod run;
 pub mod score;
+pub mod select;
 pub mod train;
 pub mod validate;
 
 pub use classify::ClassifyCommand;
+pub use compare::CompareCommand;
+pub use config::ConfigCommand;
+pub use confusion::ConfusionCommand;
+pub use dataset::DatasetCommand;
 pub use run::RunCommand;
 pub use score::ScoreCommand;
+pub use select::SelectCommand;
 pub use train::TrainCommand;
 pub use validate::ValidateCommand;
 
@@ -33,14 +48,28 @@ pub fn resolve_output_path(
     base_dir.join(filename)
 }
 
+/// Write `contents` to `path` through a temp file renamed into place, so an
+/// interrupted run never leaves a truncated export behind.
+pub fn write_atomic(path: &Path, contents: impl AsRef<[u8]>) -> std::io::Result<()> {
+    AtomicFile::write(path, contents.as_ref())
+}
+
 /// Build a Runtime configured with standard sources and codecs.
 pub fn build_runtime() -> Runtime {
-    Runtime::new()
+    let builder = Runtime::new()
         .source(FileSystemSource::builder().build())
         .codec(JsonCodec::new())
+        .codec(NdjsonCodec::new())
         .codec(YamlCodec::new())
-        .codec(TomlCodec::new())
-        .build()
+        .codec(TomlCodec::new());
+
+    #[cfg(feature = "arrow")]
+    let builder = builder.codec(ArrowCodec::new());
+
+    #[cfg(feature = "parquet")]
+    let builder = builder.codec(ParquetCodec::new());
+
+    builder.build()
 }
 
 /// Load configuration from file with environment variable overrides.
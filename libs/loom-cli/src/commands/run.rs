@@ -1,14 +1,16 @@
 use std::collections::HashSet;
 use std::path::PathBuf;
 
-use clap::Args;
+use clap::{Args, ValueEnum};
 use loom::core::{Format, ident_path};
 use loom::io::path::{FilePath, Path};
+use loom::io::sources::{STD_STREAM_PATH, StdStreamSource};
 use loom::runtime::{
-    Emitter, FileSystemSource, JsonCodec, Runtime, ScoreConfig, Signal, TomlCodec, YamlCodec, eval,
+    Emitter, FileSystemSource, FilterEmitter, JsonCodec, Level, MemoryEmitter, ProgressUpdate,
+    Runtime, ScoreConfig, Signal, TomlCodec, YamlCodec, eval,
 };
 
-use super::{load_config, resolve_output_path};
+use super::{load_config, resolve_output_path, write_atomic};
 use crate::widgets::{self, Widget};
 
 /// Signal emitter that displays progress on stdout.
@@ -16,24 +18,10 @@ struct ProgressEmitter;
 
 impl Emitter for ProgressEmitter {
     fn emit(&self, signal: Signal) {
-        if signal.name() == "eval.progress" {
-            let attrs = signal.attributes();
-            let current = attrs.get("current").and_then(|v| v.as_int()).unwrap_or(0);
-            let total = attrs.get("total").and_then(|v| v.as_int()).unwrap_or(0);
-            let sample_id = attrs
-                .get("sample_id")
-                .and_then(|v| v.as_str())
-                .unwrap_or("");
-            let correct = attrs
-                .get("correct")
-                .and_then(|v| v.as_bool())
-                .unwrap_or(false);
-            let status = if correct { '✓' } else { '✗' };
-
-            widgets::ProgressBar::new()
-                .total(total as usize)
-                .current(current as usize)
-                .message(sample_id)
+        if let Some(update) = ProgressUpdate::from_signal(&signal) {
+            let status = if update.ok { '✓' } else { '✗' };
+
+            widgets::ProgressBar::from_update(&update)
                 .status(status)
                 .render()
                 .write();
@@ -41,17 +29,27 @@ impl Emitter for ProgressEmitter {
     }
 }
 
+/// Export format of per-sample predictions
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum PredictionFormat {
+    /// JSON Lines, one object per sample
+    Jsonl,
+    /// CSV with id, y_true, y_pred and score columns
+    Csv,
+}
+
 /// Run evaluation against a dataset
 #[derive(Debug, Args)]
 pub struct RunCommand {
-    /// Path to the dataset JSON file
+    /// Path to the dataset JSON file, or `-` to read it from stdin
     pub path: PathBuf,
 
     /// Path to config file (YAML/JSON/TOML)
     #[arg(short, long)]
     pub config: PathBuf,
 
-    /// Output directory for results (default: input file's directory)
+    /// Output directory for results (default: input file's directory), or `-`
+    /// to write them to stdout (the default when reading from stdin)
     #[arg(short, long)]
     pub output: Option<PathBuf>,
 
@@ -70,6 +68,22 @@ pub struct RunCommand {
     /// Fail if samples have categories/labels not in config (overrides config)
     #[arg(long)]
     pub strict: Option<bool>,
+
+    /// Bootstrap resamples for 95% confidence intervals (0 to disable)
+    #[arg(long, default_value_t = 1000)]
+    pub bootstrap: usize,
+
+    /// Shuffle the dataset with this seed before batching (default: dataset order)
+    #[arg(long)]
+    pub seed: Option<u64>,
+
+    /// Resume from a partial results JSON: skip samples it already covers and merge
+    #[arg(long)]
+    pub resume: Option<PathBuf>,
+
+    /// Also write per-sample predictions (y_true/y_pred/score) in this format
+    #[arg(long, value_enum)]
+    pub predictions: Option<PredictionFormat>,
 }
 
 impl RunCommand {
@@ -81,8 +95,29 @@ impl RunCommand {
         let concurrency = self.concurrency;
         let batch_size = self.batch_size;
         let strict = self.strict;
+        let bootstrap = self.bootstrap;
+        let seed = self.seed;
+        let predictions = self.predictions;
+        let resume = self.resume.clone();
+
+        // Results go to stdout, so everything else goes to stderr
+        let from_stdin = path.as_os_str() == STD_STREAM_PATH;
+        let to_stdout = match output {
+            Some(output) => output.as_os_str() == STD_STREAM_PATH,
+            None => from_stdin,
+        };
 
-        println!("Loading config from {:?}...", config_path);
+        macro_rules! say {
+            ($($arg:tt)*) => {
+                if to_stdout {
+                    eprintln!($($arg)*)
+                } else {
+                    println!($($arg)*)
+                }
+            };
+        }
+
+        say!("Loading config from {:?}...", config_path);
 
         let config = match load_config(config_path.to_str().unwrap_or_default()) {
             Ok(c) => c,
@@ -92,18 +127,33 @@ impl RunCommand {
             }
         };
 
-        println!("Building runtime (this may download model files on first run)...");
+        say!("Building runtime (this may download model files on first run)...");
+
+        // Warnings and errors of the run, for the verbose report
+        let signals = MemoryEmitter::new();
+        let recorded = signals.clone();
 
         // Build runtime with config in blocking task (scorer building uses rust-bert which conflicts with tokio)
         let runtime = match tokio::task::spawn_blocking(move || {
-            Runtime::new()
+            let builder = Runtime::new()
                 .source(FileSystemSource::builder().build())
+                .source(StdStreamSource::default())
                 .codec(JsonCodec::new())
                 .codec(YamlCodec::new())
                 .codec(TomlCodec::new())
-                .config(config)
-                .emitter(ProgressEmitter)
-                .build()
+                .config(config);
+
+            let builder = if verbose {
+                builder.emitter(FilterEmitter::new(recorded).with_level(Level::Warn))
+            } else {
+                builder
+            };
+
+            if to_stdout {
+                builder.build()
+            } else {
+                builder.emitter(ProgressEmitter).build()
+            }
         })
         .await
         {
@@ -114,11 +164,18 @@ impl RunCommand {
             }
         };
 
+        if let Err(e) = runtime.health().await {
+            eprintln!("Error checking data sources: {}", e);
+            std::process::exit(1);
+        }
+
         // Get runtime settings
         let loom_config = runtime.config();
 
         // Merge CLI args with config values (CLI overrides config)
-        let output_dir = output.or(loom_config.output.as_ref());
+        let output_dir = output
+            .filter(|o| o.as_os_str() != STD_STREAM_PATH)
+            .or(loom_config.output.as_ref());
         let output_path =
             resolve_output_path(path, output_dir.map(|p| p.as_path()), "results.json");
         let batch_size = batch_size.unwrap_or(loom_config.batch_size);
@@ -143,10 +200,17 @@ impl RunCommand {
             .flat_map(|c| c.labels.keys().cloned())
             .collect();
 
-        println!("Loading dataset from {:?}...", path);
+        say!("Loading dataset from {:?}...", path);
 
-        let file_path = Path::File(FilePath::from(path.clone()));
-        let mut dataset: eval::SampleDataset = match runtime.load("file_system", &file_path).await {
+        let loaded = if from_stdin {
+            runtime.load("std", &StdStreamSource::path()).await
+        } else {
+            runtime
+                .fetch(&Path::File(FilePath::from(path.clone())))
+                .await
+        };
+
+        let mut dataset: eval::SampleDataset = match loaded {
             Ok(d) => d,
             Err(e) => {
                 eprintln!("Error loading dataset: {}", e);
@@ -154,7 +218,7 @@ impl RunCommand {
             }
         };
 
-        println!("Loaded {} samples", dataset.samples.len());
+        say!("Loaded {} samples", dataset.samples.len());
 
         // Validate dataset against config
         let errors = dataset.validate_with_config(Some(&valid_categories), Some(&valid_labels));
@@ -199,10 +263,55 @@ impl RunCommand {
             std::process::exit(1);
         }
 
+        if let Some(seed) = seed {
+            dataset.shuffle(seed);
+        }
+
+        let partial: Option<eval::EvalResult> = match &resume {
+            Some(resume_path) => {
+                let file_path = Path::File(FilePath::from(resume_path.clone()));
+                match runtime.fetch(&file_path).await {
+                    Ok(r) => Some(r),
+                    Err(e) => {
+                        eprintln!("Error loading partial results: {}", e);
+                        std::process::exit(1);
+                    }
+                }
+            }
+            None => None,
+        };
+
         let total = dataset.samples.len();
-        println!("\nRunning benchmark with batch size {}...\n", batch_size);
 
-        let result = match runtime.eval_scoring(&dataset, batch_size).await {
+        if let (Some(partial), Some(resume_path)) = (&partial, &resume) {
+            let pending = dataset.pending(partial).samples.len();
+            say!(
+                "Resuming from {:?}: {} of {} samples already evaluated",
+                resume_path,
+                total - pending,
+                total
+            );
+        }
+
+        match seed {
+            Some(seed) => say!(
+                "\nRunning benchmark with batch size {} (seed {})...\n",
+                batch_size,
+                seed
+            ),
+            None => say!("\nRunning benchmark with batch size {}...\n", batch_size),
+        }
+
+        let evaluated = match partial {
+            Some(partial) => {
+                runtime
+                    .eval_scoring_resume(&dataset, batch_size, partial)
+                    .await
+            }
+            None => runtime.eval_scoring(&dataset, batch_size).await,
+        };
+
+        let mut result = match evaluated {
             Ok(r) => r,
             Err(e) => {
                 eprintln!("Error running evaluation: {}", e);
@@ -211,43 +320,86 @@ impl RunCommand {
         };
 
         // Clear the progress line
-        widgets::ProgressBar::clear();
-        println!("Completed {} samples\n", total);
+        if !to_stdout {
+            widgets::ProgressBar::clear();
+        }
+        say!("Completed {} samples\n", total);
+
+        result.seed = seed;
 
         // Compute metrics from raw counts
         let metrics = result.metrics();
 
+        if bootstrap > 0 {
+            result.confidence = Some(eval::Bootstrap::new().iterations(bootstrap).run(&result));
+        }
+
         // Display prominent score summary
         let score_out_of_100 = (metrics.accuracy * 100.0).round() as u32;
-        println!("========================================");
-        println!(
+        say!("========================================");
+        say!(
             "  SCORE: {}/100 ({:.1}%)",
             score_out_of_100,
             metrics.accuracy * 100.0
         );
-        println!("========================================\n");
+        say!("========================================\n");
 
-        println!("=== Benchmark Results ===\n");
-        println!("Total samples: {}", result.total);
-        println!(
+        say!("=== Benchmark Results ===\n");
+        say!("Total samples: {}", result.total);
+        say!(
             "Correct:       {} ({:.1}%)",
             result.correct,
             metrics.accuracy * 100.0
         );
-        println!();
-        println!("Precision: {:.3}", metrics.precision);
-        println!("Recall:    {:.3}", metrics.recall);
-        println!("F1 Score:  {:.3}", metrics.f1);
+        say!();
+        say!("Precision: {:.3}", metrics.precision);
+        say!("Recall:    {:.3}", metrics.recall);
+        say!("F1 Score:  {:.3}", metrics.f1);
+
+        if result.is_weighted() {
+            let weighted = result.weighted_metrics();
+            say!();
+            say!("Weighted accuracy: {:.1}%", weighted.accuracy * 100.0);
+            say!("Weighted F1 Score: {:.3}", weighted.f1);
+        }
+
+        if let Some(ci) = &result.confidence {
+            // Intervals resample the sample results with their weights, so
+            // print them beside the point estimates computed the same way
+            let point = result.weighted_metrics();
+            let weighted = if result.is_weighted() {
+                "Weighted "
+            } else {
+                ""
+            };
+
+            say!();
+            say!(
+                "{}Accuracy: {:.3}, {:.0}% CI {} ({} resamples)",
+                weighted,
+                point.accuracy,
+                ci.confidence * 100.0,
+                ci.accuracy,
+                ci.iterations
+            );
+            say!(
+                "{}F1:       {:.3}, {:.0}% CI {}",
+                weighted,
+                point.f1,
+                ci.confidence * 100.0,
+                ci.f1
+            );
+        }
 
         if verbose {
-            println!("\n=== Per-Category Results ===\n");
+            say!("\n=== Per-Category Results ===\n");
             let mut categories: Vec<_> = result.per_category.iter().collect();
             categories.sort_by_key(|(cat, _)| cat.as_str());
 
             for (category, cat_result) in categories {
                 let cat_metrics = metrics.per_category.get(category);
                 let accuracy = cat_metrics.map(|m| m.accuracy).unwrap_or(0.0);
-                println!(
+                say!(
                     "{:20} {:3}/{:3} ({:.1}%)",
                     category,
                     cat_result.correct,
@@ -256,13 +408,34 @@ impl RunCommand {
                 );
             }
 
-            println!("\n=== Per-Label Results ===\n");
+            if !result.per_source.is_empty() {
+                say!("\n=== Per-Source Results ===\n");
+                let mut sources: Vec<_> = result.per_source.iter().collect();
+                sources.sort_by_key(|(source, _)| source.as_str());
+
+                for (source, source_result) in sources {
+                    let accuracy = metrics
+                        .per_source
+                        .get(source)
+                        .map(|m| m.accuracy)
+                        .unwrap_or(0.0);
+                    say!(
+                        "{:30} {:3}/{:3} ({:.1}%)",
+                        source,
+                        source_result.correct,
+                        source_result.total,
+                        accuracy * 100.0
+                    );
+                }
+            }
+
+            say!("\n=== Per-Label Results ===\n");
 
             let mut labels: Vec<_> = result.per_label.iter().collect();
             labels.sort_by_key(|(label, _)| label.as_str());
 
             let mut table = widgets::Table::new().headers(vec![
-                "Label", "Expect", "Detect", "TP", "Prec", "Recall", "F1",
+                "Label", "Expect", "Detect", "TP", "Prec", "Recall", "F1", "F1 CI",
             ]);
 
             for (label, label_result) in labels {
@@ -279,11 +452,21 @@ impl RunCommand {
                         format!("{:.3}", precision),
                         format!("{:.3}", recall),
                         format!("{:.3}", f1),
+                        result
+                            .confidence
+                            .as_ref()
+                            .and_then(|ci| ci.per_label.get(label))
+                            .map(|ci| ci.to_string())
+                            .unwrap_or_else(|| "-".to_string()),
                     ]);
                 }
             }
 
-            print!("{}", table);
+            if to_stdout {
+                eprint!("{}", table);
+            } else {
+                print!("{}", table);
+            }
 
             // Show misclassified samples
             let incorrect: Vec<_> = result
@@ -293,42 +476,94 @@ impl RunCommand {
                 .collect();
 
             if !incorrect.is_empty() {
-                println!("\n=== Misclassified Samples ({}) ===\n", incorrect.len());
+                say!("\n=== Misclassified Samples ({}) ===\n", incorrect.len());
                 for sample in incorrect.iter().take(10) {
-                    println!("ID: {}", sample.id);
-                    println!(
+                    say!("ID: {}", sample.id);
+                    say!(
                         "  Expected: {:?}, Actual: {:?}",
-                        sample.expected_decision, sample.actual_decision
+                        sample.expected_decision,
+                        sample.actual_decision
                     );
-                    println!("  Score: {:.3}", sample.score);
-                    println!("  Expected labels: {:?}", sample.expected_labels);
-                    println!("  Detected labels: {:?}", sample.detected_labels);
-                    println!();
+                    say!("  Score: {:.3}", sample.score);
+                    say!("  Expected labels: {:?}", sample.expected_labels);
+                    say!("  Detected labels: {:?}", sample.detected_labels);
+                    say!();
                 }
                 if incorrect.len() > 10 {
-                    println!("... and {} more", incorrect.len() - 10);
+                    say!("... and {} more", incorrect.len() - 10);
+                }
+            }
+
+            // Show warnings and errors signalled during the run
+            let problems = signals.by_level(Level::Warn);
+
+            if !problems.is_empty() {
+                say!("\n=== Warnings and Errors ({}) ===\n", problems.len());
+                for signal in problems.iter().take(10) {
+                    say!(
+                        "[{}] {} {:?}",
+                        signal.level(),
+                        signal.name(),
+                        signal.attributes()
+                    );
+                }
+                if problems.len() > 10 {
+                    say!("... and {} more", problems.len() - 10);
                 }
             }
         }
 
-        // Ensure output directory exists
-        if let Some(parent) = output_path.parent() {
-            if let Err(e) = std::fs::create_dir_all(parent) {
-                eprintln!("Error creating output directory: {}", e);
+        if to_stdout {
+            if let Err(e) = runtime
+                .save("std", &StdStreamSource::path(), &result, Format::Json)
+                .await
+            {
+                eprintln!("Error writing results to stdout: {}", e);
+                std::process::exit(1);
+            }
+        } else {
+            // Ensure output directory exists
+            if let Some(parent) = output_path.parent() {
+                if let Err(e) = std::fs::create_dir_all(parent) {
+                    eprintln!("Error creating output directory: {}", e);
+                    std::process::exit(1);
+                }
+            }
+
+            // Write results to output file
+            let file_path = Path::File(FilePath::from(output_path.clone()));
+            if let Err(e) = runtime.store(&file_path, &result, Format::Json).await {
+                eprintln!("Error writing output file: {}", e);
                 std::process::exit(1);
             }
+
+            println!("\nResults written to {:?}", output_path);
         }
 
-        // Write results to output file
-        let file_path = Path::File(FilePath::from(output_path.clone()));
-        if let Err(e) = runtime
-            .save("file_system", &file_path, &result, Format::Json)
-            .await
-        {
-            eprintln!("Error writing output file: {}", e);
-            std::process::exit(1);
+        if let Some(format) = predictions {
+            let (filename, written) = match format {
+                PredictionFormat::Jsonl => (
+                    "predictions.jsonl",
+                    result.predictions_jsonl().map_err(|e| e.to_string()),
+                ),
+                PredictionFormat::Csv => ("predictions.csv", Ok(result.predictions_csv())),
+            };
+            let predictions_path =
+                resolve_output_path(path, output_dir.map(|p| p.as_path()), filename);
+
+            if let Err(e) = written.and_then(|contents| {
+                write_atomic(&predictions_path, contents).map_err(|e| e.to_string())
+            }) {
+                eprintln!("Error writing predictions file: {}", e);
+                std::process::exit(1);
+            }
+
+            say!("Predictions written to {:?}", predictions_path);
         }
 
-        println!("\nResults written to {:?}", output_path);
+        if let Err(e) = runtime.shutdown().await {
+            eprintln!("Error shutting down runtime: {}", e);
+            std::process::exit(1);
+        }
     }
 }
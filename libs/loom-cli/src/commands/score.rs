@@ -5,7 +5,8 @@ use clap::Args;
 use loom::core::{Format, ident_path};
 use loom::io::path::{FilePath, Path};
 use loom::runtime::{
-    Emitter, FileSystemSource, JsonCodec, Runtime, ScoreConfig, Signal, TomlCodec, YamlCodec, eval,
+    Emitter, FileSystemSource, JsonCodec, ProgressUpdate, Runtime, ScoreConfig, Signal, TomlCodec,
+    YamlCodec, eval,
 };
 
 use super::{load_config, resolve_output_path};
@@ -24,18 +25,9 @@ impl ScoreProgressEmitter {
 
 impl Emitter for ScoreProgressEmitter {
     fn emit(&self, signal: Signal) {
-        if signal.name() == "eval.progress" {
-            let attrs = signal.attributes();
-            let current = attrs.get("current").and_then(|v| v.as_int()).unwrap_or(0);
-            let sample_id = attrs
-                .get("sample_id")
-                .and_then(|v| v.as_str())
-                .unwrap_or("");
-
-            widgets::ProgressBar::new()
+        if let Some(update) = ProgressUpdate::from_signal(&signal) {
+            widgets::ProgressBar::from_update(&update)
                 .total(self.total)
-                .current(current as usize)
-                .message(sample_id)
                 .status('•')
                 .render()
                 .write();
@@ -117,6 +109,17 @@ impl ScoreCommand {
         // Merge CLI args with config values (CLI overrides config)
         let output_dir = output.or(loom_config.output.as_ref());
         let output_path = resolve_output_path(path, output_dir.map(|p| p.as_path()), "scores.json");
+        let file_path = Path::File(FilePath::from(output_path.clone()));
+
+        // Remember what the export looked like before scoring, so a concurrent
+        // run that writes it first isn't silently overwritten
+        let expected = match runtime.etag(&file_path).await {
+            Ok(etag) => etag,
+            Err(e) => {
+                eprintln!("Error reading output file: {}", e);
+                std::process::exit(1);
+            }
+        };
         let batch_size = batch_size.unwrap_or(loom_config.batch_size);
         let strict = strict.unwrap_or(loom_config.strict);
         let _ = concurrency; // Reserved for future multi-model parallelism
@@ -142,7 +145,7 @@ impl ScoreCommand {
         println!("Loading dataset from {:?}...", path);
 
         let file_path = Path::File(FilePath::from(path.clone()));
-        let mut dataset: eval::SampleDataset = match runtime.load("file_system", &file_path).await {
+        let mut dataset: eval::SampleDataset = match runtime.fetch(&file_path).await {
             Ok(d) => d,
             Err(e) => {
                 eprintln!("Error loading dataset: {}", e);
@@ -264,10 +267,9 @@ impl ScoreCommand {
             }
         }
 
-        // Write to output file using runtime
-        let file_path = Path::File(FilePath::from(output_path.clone()));
+        // Write to output file using runtime, unless another run changed it meanwhile
         if let Err(e) = runtime
-            .save("file_system", &file_path, &export, Format::Json)
+            .store_if(&file_path, &export, Format::Json, expected)
             .await
         {
             eprintln!("Error writing output file: {}", e);
@@ -0,0 +1,164 @@
+use std::path::PathBuf;
+
+use clap::Args;
+use loom::core::{Format, ident_path};
+use loom::io::path::{FilePath, Path};
+use loom::runtime::{
+    FileSystemSource, JsonCodec, Runtime, ScoreConfig, TomlCodec, YamlCodec, eval,
+};
+
+use super::{build_runtime, load_config, resolve_output_path};
+use crate::widgets;
+
+/// Select the most uncertain samples for human labeling
+#[derive(Debug, Args)]
+pub struct SelectCommand {
+    /// Path to the dataset JSON file (samples may be unlabeled)
+    pub path: PathBuf,
+
+    /// Path to config file (YAML/JSON/TOML). Repeat to score with an ensemble;
+    /// the first config's threshold is used
+    #[arg(short, long, required = true)]
+    pub config: Vec<PathBuf>,
+
+    /// Number of samples to select
+    #[arg(short = 'n', long, default_value_t = 50)]
+    pub top: usize,
+
+    /// Output directory for the selection (default: input file's directory)
+    #[arg(short, long)]
+    pub output: Option<PathBuf>,
+
+    /// Batch size for ML inference (overrides config)
+    #[arg(long)]
+    pub batch_size: Option<usize>,
+}
+
+impl SelectCommand {
+    pub async fn exec(self) {
+        let path = &self.path;
+
+        println!("Loading dataset from {:?}...", path);
+
+        let file_path = Path::File(FilePath::from(path.clone()));
+        let dataset: eval::SampleDataset = match build_runtime().fetch(&file_path).await {
+            Ok(d) => d,
+            Err(e) => {
+                eprintln!("Error loading dataset: {}", e);
+                std::process::exit(1);
+            }
+        };
+
+        println!("Loaded {} samples", dataset.samples.len());
+
+        let mut runs = Vec::with_capacity(self.config.len());
+        let mut threshold_config: Option<ScoreConfig> = None;
+        let mut output_dir = self.output.clone();
+
+        for config_path in &self.config {
+            println!("\nScoring with {:?}...", config_path);
+
+            let config = match load_config(config_path.to_str().unwrap_or_default()) {
+                Ok(c) => c,
+                Err(e) => {
+                    eprintln!("Error loading config: {}", e);
+                    std::process::exit(1);
+                }
+            };
+
+            // Build runtime with config in blocking task (scorer building uses rust-bert which conflicts with tokio)
+            let runtime = match tokio::task::spawn_blocking(move || {
+                Runtime::new()
+                    .source(FileSystemSource::builder().build())
+                    .codec(JsonCodec::new())
+                    .codec(YamlCodec::new())
+                    .codec(TomlCodec::new())
+                    .config(config)
+                    .build()
+            })
+            .await
+            {
+                Ok(r) => r,
+                Err(e) => {
+                    eprintln!("Error building runtime: {}", e);
+                    std::process::exit(1);
+                }
+            };
+
+            let loom_config = runtime.config();
+            let batch_size = self.batch_size.unwrap_or(loom_config.batch_size);
+
+            if threshold_config.is_none() {
+                let score_path = ident_path!("layers.score");
+                threshold_config = match runtime.rconfig().get_section(&score_path).bind() {
+                    Ok(c) => Some(c),
+                    Err(e) => {
+                        eprintln!("Error parsing score config: {}", e);
+                        std::process::exit(1);
+                    }
+                };
+                output_dir = output_dir.or(loom_config.output.clone());
+            }
+
+            match runtime.eval_scoring(&dataset, batch_size).await {
+                Ok(r) => runs.push(r),
+                Err(e) => {
+                    eprintln!("Error scoring dataset: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+
+        let score_config = threshold_config.expect("at least one config is required");
+        let selected = dataset.select(&runs, &score_config, self.top);
+
+        println!("\n=== Selected Samples ({}) ===\n", selected.len());
+
+        let mut table = widgets::Table::new().headers(vec![
+            "ID",
+            "Score",
+            "Threshold",
+            "Margin",
+            "Disagree",
+            "Uncertainty",
+        ]);
+
+        for s in &selected {
+            table = table.row(vec![
+                s.sample.id.clone(),
+                format!("{:.3}", s.score),
+                format!("{:.3}", s.threshold),
+                format!("{:.3}", s.margin),
+                format!("{:.3}", s.disagreement),
+                format!("{:.3}", s.uncertainty),
+            ]);
+        }
+
+        print!("{}", table);
+
+        let output_path = resolve_output_path(path, output_dir.as_deref(), "selection.json");
+
+        // Ensure output directory exists
+        if let Some(parent) = output_path.parent() {
+            if let Err(e) = std::fs::create_dir_all(parent) {
+                eprintln!("Error creating output directory: {}", e);
+                std::process::exit(1);
+            }
+        }
+
+        // Export as a dataset so the selection can be labeled and merged back
+        let mut export = eval::SampleDataset::new();
+        export.samples = selected.into_iter().map(|s| s.into_sample()).collect();
+
+        let file_path = Path::File(FilePath::from(output_path.clone()));
+        if let Err(e) = build_runtime()
+            .store(&file_path, &export, Format::Json)
+            .await
+        {
+            eprintln!("Error writing output file: {}", e);
+            std::process::exit(1);
+        }
+
+        println!("\nSelection written to {:?}", output_path);
+    }
+}
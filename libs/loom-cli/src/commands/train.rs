@@ -40,7 +40,7 @@ impl TrainCommand {
         let runtime = build_runtime();
         let file_path = Path::File(FilePath::from(path.clone()));
 
-        let export: RawScoreExport = match runtime.load("file_system", &file_path).await {
+        let export: RawScoreExport = match runtime.fetch(&file_path).await {
             Ok(e) => e,
             Err(e) => {
                 widgets::Spinner::clear();
@@ -97,10 +97,7 @@ impl TrainCommand {
 
         // Write parameters to output file using runtime
         let output_path = Path::File(FilePath::from(output.clone()));
-        if let Err(e) = runtime
-            .save("file_system", &output_path, &result, Format::Json)
-            .await
-        {
+        if let Err(e) = runtime.store(&output_path, &result, Format::Json).await {
             eprintln!("\nError writing output file: {}", e);
             std::process::exit(1);
         }
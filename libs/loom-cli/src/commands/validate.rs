@@ -1,4 +1,4 @@
-use std::io::stdout;
+use std::io::{stderr, stdout};
 use std::path::PathBuf;
 
 use clap::Args;
@@ -7,7 +7,7 @@ use crossterm::style::{Color, ResetColor, SetForegroundColor};
 use loom::core::ident_path;
 use loom::io::path::{FilePath, Path};
 use loom::runtime::{
-    FileSystemSource, JsonCodec, Runtime, ScoreConfig, TomlCodec, YamlCodec, eval,
+    CodecError, FileSystemSource, JsonCodec, Runtime, ScoreConfig, TomlCodec, YamlCodec, eval,
 };
 
 use super::{build_runtime, load_config};
@@ -27,6 +27,10 @@ pub struct ValidateCommand {
     #[arg(long)]
     pub strict: bool,
 
+    /// Print findings as JSON, grouped by severity
+    #[arg(long)]
+    pub json: bool,
+
     /// Test N samples by running them through the scorer (requires --config)
     #[arg(long)]
     pub test_samples: Option<usize>,
@@ -46,17 +50,25 @@ impl ValidateCommand {
 
         let runtime = build_runtime();
         let file_path = Path::File(FilePath::from(path.clone()));
-        let dataset: eval::SampleDataset = match runtime.load("file_system", &file_path).await {
+        let dataset: eval::SampleDataset = match runtime.fetch(&file_path).await {
             Ok(d) => d,
             Err(e) => {
                 widgets::Spinner::clear();
-                eprintln!("Error loading dataset: {}", e);
+
+                // Re-read leniently so every parse error is reported, not just the first
+                match runtime.decode_lenient("file_system", &file_path).await {
+                    Ok(partial) if !partial.is_complete() => {
+                        print_decode_errors(&partial.errors, self.json)
+                    }
+                    _ => eprintln!("Error loading dataset: {}", e),
+                }
+
                 std::process::exit(1);
             }
         };
 
         // Load config if provided for category/label validation
-        let (valid_categories, valid_labels) = if let Some(cfg_path) = config_path {
+        let score_config = if let Some(cfg_path) = config_path {
             let config = match load_config(cfg_path.to_str().unwrap_or_default()) {
                 Ok(c) => c,
                 Err(e) => {
@@ -78,42 +90,64 @@ impl ValidateCommand {
                 }
             };
 
-            let categories: Vec<String> = score_config.categories.keys().cloned().collect();
-            let labels: Vec<String> = score_config
-                .categories
-                .values()
-                .flat_map(|c| c.labels.keys().cloned())
-                .collect();
-
-            (Some(categories), Some(labels))
+            Some(score_config)
         } else {
-            (None, None)
+            None
         };
 
         widgets::Spinner::clear();
 
-        let errors =
-            dataset.validate_with_config(valid_categories.as_deref(), valid_labels.as_deref());
+        let report = dataset.report(score_config.as_ref());
         let mut stdout = stdout();
 
-        if errors.is_empty() {
+        if self.json {
+            match serde_json::to_string_pretty(&report) {
+                Ok(json) => println!("{}", json),
+                Err(e) => {
+                    eprintln!("Error serializing report: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        } else if report.is_empty() {
             let _ = stdout.execute(SetForegroundColor(Color::Green));
             print!("✓ ");
             let _ = stdout.execute(ResetColor);
             println!("Dataset is valid ({} samples)", dataset.samples.len());
         } else {
-            let _ = stdout.execute(SetForegroundColor(Color::Red));
-            print!("✗ ");
-            let _ = stdout.execute(ResetColor);
-            println!("Found {} validation error(s):\n", errors.len());
-            for error in &errors {
-                println!("  - {}", error);
+            if report.is_valid() {
+                let _ = stdout.execute(SetForegroundColor(Color::Yellow));
+                print!("○ ");
+            } else {
+                let _ = stdout.execute(SetForegroundColor(Color::Red));
+                print!("✗ ");
             }
-            if strict {
-                std::process::exit(1);
+            let _ = stdout.execute(ResetColor);
+            println!(
+                "Found {} error(s) and {} warning(s):",
+                report.errors.len(),
+                report.warnings.len()
+            );
+
+            for (findings, color) in [
+                (&report.errors, Color::Red),
+                (&report.warnings, Color::Yellow),
+            ] {
+                if let Some(first) = findings.first() {
+                    let _ = stdout.execute(SetForegroundColor(color));
+                    println!("\n{}s:", first.severity);
+                    let _ = stdout.execute(ResetColor);
+                }
+
+                for finding in findings {
+                    println!("  - {}", finding);
+                }
             }
         }
 
+        if strict && !report.is_valid() {
+            std::process::exit(1);
+        }
+
         // Test samples if requested
         if let Some(n) = test_samples {
             if config_path.is_none() {
@@ -177,3 +211,33 @@ impl ValidateCommand {
         }
     }
 }
+
+/// Print the parse errors found in a dataset file, one per line with its
+/// position, or as a JSON array with `--json`.
+fn print_decode_errors(errors: &[CodecError], json: bool) {
+    if json {
+        let errors: Vec<_> = errors
+            .iter()
+            .map(|e| {
+                serde_json::json!({
+                    "line": e.position().map(|p| p.line),
+                    "column": e.position().map(|p| p.column),
+                    "message": e.to_string(),
+                })
+            })
+            .collect();
+
+        println!("{}", serde_json::json!({ "decode_errors": errors }));
+        return;
+    }
+
+    let mut stderr = stderr();
+    let _ = stderr.execute(SetForegroundColor(Color::Red));
+    eprint!("✗ ");
+    let _ = stderr.execute(ResetColor);
+    eprintln!("Dataset has {} parse error(s):", errors.len());
+
+    for error in errors {
+        eprintln!("  - {}", error);
+    }
+}
@@ -3,7 +3,10 @@ use clap::{Parser, Subcommand};
 mod commands;
 pub mod widgets;
 
-use commands::{ClassifyCommand, RunCommand, ScoreCommand, TrainCommand, ValidateCommand};
+use commands::{
+    ClassifyCommand, CompareCommand, ConfigCommand, ConfusionCommand, DatasetCommand, RunCommand,
+    ScoreCommand, SelectCommand, TrainCommand, ValidateCommand,
+};
 
 /// Loom scoring engine CLI
 ///
@@ -33,6 +36,21 @@ enum Commands {
 
     /// Train Platt calibration parameters from raw scores
     Train(TrainCommand),
+
+    /// Compare two eval runs on the same dataset
+    Compare(CompareCommand),
+
+    /// Select the most uncertain samples for human labeling
+    Select(SelectCommand),
+
+    /// Build a label confusion matrix from eval results
+    Confusion(ConfusionCommand),
+
+    /// Inspect a dataset
+    Dataset(DatasetCommand),
+
+    /// Inspect config files
+    Config(ConfigCommand),
 }
 
 #[tokio::main]
@@ -45,5 +63,10 @@ async fn main() {
         Commands::Validate(cmd) => cmd.exec().await,
         Commands::Score(cmd) => cmd.exec().await,
         Commands::Train(cmd) => cmd.exec().await,
+        Commands::Compare(cmd) => cmd.exec().await,
+        Commands::Select(cmd) => cmd.exec().await,
+        Commands::Confusion(cmd) => cmd.exec().await,
+        Commands::Dataset(cmd) => cmd.exec().await,
+        Commands::Config(cmd) => cmd.exec(),
     }
 }
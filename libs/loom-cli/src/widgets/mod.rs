@@ -1,4 +1,5 @@
 mod progress;
+mod score_tree;
 mod spinner;
 mod table;
 
@@ -8,6 +9,7 @@ use std::ops::Deref;
 use crossterm::{ExecutableCommand, cursor, terminal};
 
 pub use progress::ProgressBar;
+pub use score_tree::ScoreTree;
 pub use spinner::Spinner;
 pub use table::Table;
 
@@ -1,5 +1,7 @@
 use std::fmt;
 
+use loom::runtime::ProgressUpdate;
+
 use super::{Widget, WidgetResult};
 
 pub struct ProgressBar {
@@ -21,6 +23,14 @@ impl ProgressBar {
         }
     }
 
+    /// Bar for a progress signal: position and total, labelled with the item
+    pub fn from_update(update: &ProgressUpdate) -> Self {
+        Self::new()
+            .total(update.total)
+            .current(update.current)
+            .message(update.label.as_str())
+    }
+
     pub fn current(mut self, current: usize) -> Self {
         self.current = current;
         self
@@ -0,0 +1,134 @@
+use std::fmt;
+
+use crossterm::style::Stylize;
+use loom::runtime::eval::score::{ScoreConfig, ScoreResult};
+
+use super::{Widget, WidgetResult};
+
+/// Renders a `ScoreResult` as a colored category → label tree.
+///
+/// Each label shows its raw score, calibrated score, and threshold, marked
+/// pass (✓) when the calibrated score clears the threshold. The root line
+/// shows the overall decision, including guards and text length modifiers.
+///
+/// # Example
+/// ```text
+/// ✓ accept 0.812 (threshold 0.750)
+/// ├── sentiment 0.406
+/// │   ├── ✓ positive  raw 0.812  calibrated 0.812  threshold 0.700
+/// │   └── ✗ negative  raw 0.051  calibrated 0.051  threshold 0.700
+/// └── task 0.000
+///     └── ✗ task      raw 0.102  calibrated 0.102  threshold 0.700
+/// ```
+pub struct ScoreTree<'a> {
+    result: &'a ScoreResult,
+    config: &'a ScoreConfig,
+    text_len: usize,
+}
+
+impl<'a> ScoreTree<'a> {
+    pub fn new(result: &'a ScoreResult, config: &'a ScoreConfig) -> Self {
+        Self {
+            result,
+            config,
+            text_len: 0,
+        }
+    }
+
+    /// Length of the scored text, used to apply the config's threshold modifiers
+    pub fn text_len(mut self, text_len: usize) -> Self {
+        self.text_len = text_len;
+        self
+    }
+
+    fn marker(pass: bool) -> String {
+        if pass {
+            "✓".green().to_string()
+        } else {
+            "✗".red().to_string()
+        }
+    }
+}
+
+impl Widget for ScoreTree<'_> {
+    fn render(&self) -> WidgetResult {
+        let mut output = String::new();
+        let threshold = self.config.threshold_of(self.text_len);
+        let decision = match self.config.check(self.text_len, self.result) {
+            Ok(None) => (true, "accept".to_string()),
+            Ok(Some(guard)) => (true, format!("accept by guard '{}'", guard)),
+            Err(e) => (false, format!("reject: {}", e)),
+        };
+
+        output.push_str(&format!(
+            "{} {} {:.3} {}\n",
+            Self::marker(decision.0),
+            decision.1.as_str().bold(),
+            self.result.score,
+            format!("(threshold {:.3})", threshold).dark_grey(),
+        ));
+
+        let width = self
+            .result
+            .categories
+            .values()
+            .flat_map(|c| c.labels.keys().map(|l| l.chars().count()))
+            .max()
+            .unwrap_or(0);
+
+        let categories = self.result.categories.len();
+
+        for (i, (name, category)) in self.result.categories.iter().enumerate() {
+            let last = i + 1 == categories;
+            let (branch, indent) = if last {
+                ("└── ", "    ")
+            } else {
+                ("├── ", "│   ")
+            };
+
+            output.push_str(&format!(
+                "{}{} {:.3}\n",
+                branch,
+                name.as_str().cyan().bold(),
+                category.score
+            ));
+
+            let labels = category.labels.len();
+
+            for (j, (label, score)) in category.labels.iter().enumerate() {
+                let branch = if j + 1 == labels {
+                    "└── "
+                } else {
+                    "├── "
+                };
+                let (calibrated, threshold) = match self.config.label(label) {
+                    Some(config) => (config.calibrate(score.raw_score), config.threshold),
+                    None => (score.raw_score, 0.0),
+                };
+
+                output.push_str(&format!(
+                    "{}{}{} {:<width$}  {} {:.3}  {} {:.3}  {} {:.3}\n",
+                    indent,
+                    branch,
+                    Self::marker(calibrated >= threshold),
+                    label,
+                    "raw".dark_grey(),
+                    score.raw_score,
+                    "calibrated".dark_grey(),
+                    calibrated,
+                    "threshold".dark_grey(),
+                    threshold,
+                    width = width,
+                ));
+            }
+        }
+
+        WidgetResult::new(output)
+    }
+}
+
+impl fmt::Display for ScoreTree<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", &*self.render())
+    }
+}
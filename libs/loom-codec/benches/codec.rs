@@ -0,0 +1,60 @@
+//! Benchmarks for codec encode/decode of documents.
+//!
+//! Run with `cargo bench -p loom-codec --bench codec`.
+//! Set `LOOM_BENCH_SAMPLE_SIZE` to trade accuracy for speed (default: 100).
+
+use std::hint::black_box;
+
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use loom_codec::path::{FilePath, Path};
+use loom_codec::{Codec, JsonCodec, MediaType, Record};
+
+#[path = "../../bench.rs"]
+mod bench;
+
+/// Build a JSON sample dataset with `n` samples.
+fn dataset(n: usize) -> String {
+    let samples = (0..n)
+        .map(|i| {
+            format!(
+                r#"{{"id":"sample-{i}","text":"I need to finish the report by friday {i}","expected_decision":"accept","expected_labels":["task","stress"],"primary_category":"context","difficulty":"easy"}}"#
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(",");
+
+    format!(
+        r#"{{"version":"1.0.0","created":"2025-01-01","samples":[{}]}}"#,
+        samples
+    )
+}
+
+fn bench_json(c: &mut Criterion) {
+    let codec = JsonCodec::new();
+    let path = Path::File(FilePath::parse("/bench.json"));
+    let mut group = c.benchmark_group("codec/json");
+
+    for n in [10, 100, 1000] {
+        let record = Record::from_str(path.clone(), MediaType::TextJson, &dataset(n));
+        let document = codec.decode(record.clone()).expect("valid json");
+        group.throughput(Throughput::Bytes(record.content.len() as u64));
+
+        group.bench_with_input(BenchmarkId::new("decode", n), &record, |b, record| {
+            b.iter(|| codec.decode(black_box(record.clone())).expect("decode"))
+        });
+
+        group.bench_with_input(BenchmarkId::new("encode", n), &document, |b, document| {
+            b.iter(|| codec.encode(black_box(document.clone())).expect("encode"))
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group! {
+    name = benches;
+    config = bench::config(100);
+    targets = bench_json
+}
+
+criterion_main!(benches);
@@ -0,0 +1,475 @@
+use std::io::Cursor;
+use std::sync::Arc;
+
+use arrow::array::{
+    Array as ArrowArray, ArrayRef, AsArray, BooleanArray, Float64Array, Int64Array, StringArray,
+};
+use arrow::datatypes::{
+    ArrowPrimitiveType, DataType, Field, Float16Type, Float32Type, Float64Type, Int8Type,
+    Int16Type, Int32Type, Int64Type, Schema, UInt8Type, UInt16Type, UInt32Type, UInt64Type,
+};
+use arrow::ipc::reader::{FileReader, StreamReader};
+use arrow::ipc::writer::FileWriter;
+use arrow::record_batch::RecordBatch;
+use arrow::util::display::{ArrayFormatter, FormatOptions};
+
+use crate::columns::{Column, infer_columns};
+use crate::path::IdentPath;
+use crate::value::{Array, Number, Object, Value};
+use crate::{Document, Entity, Format, Record};
+
+use super::{Codec, CodecError};
+
+/// Leading magic bytes of the Arrow IPC file format; anything else is read
+/// as the IPC stream format.
+const FILE_MAGIC: &[u8] = b"ARROW1";
+
+/// Codec for Apache Arrow IPC data, as exported by HuggingFace `datasets`
+/// and pandas/pyarrow (`.arrow`, `.feather`).
+///
+/// Both the IPC file and stream formats decode; each row becomes a
+/// `Value::Object` entity at `root[i]`. Lists decode to arrays and structs to
+/// objects; dates, timestamps, decimals and other logical types keep their
+/// display form. Encoding writes an IPC file with one nullable column per
+/// object key, storing nested arrays/objects as JSON text.
+#[derive(Debug, Clone, Default)]
+pub struct ArrowCodec;
+
+impl ArrowCodec {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Decode an Arrow IPC file or stream into its rows, in order.
+    pub fn decode_rows(&self, bytes: Vec<u8>) -> Result<Vec<Object>, CodecError> {
+        Ok(self.decode_batches(bytes)?.into_iter().flatten().collect())
+    }
+
+    /// Decode an Arrow IPC file or stream into the rows of each record batch.
+    pub fn decode_batches(&self, bytes: Vec<u8>) -> Result<Vec<Vec<Object>>, CodecError> {
+        let batches: Result<Vec<RecordBatch>, _> = if bytes.starts_with(FILE_MAGIC) {
+            FileReader::try_new(Cursor::new(bytes), None)
+                .map_err(CodecError::decode)?
+                .collect()
+        } else {
+            StreamReader::try_new(Cursor::new(bytes), None)
+                .map_err(CodecError::decode)?
+                .collect()
+        };
+
+        batches
+            .map_err(CodecError::decode)?
+            .iter()
+            .map(batch_rows)
+            .collect()
+    }
+
+    /// Encode rows into an Arrow IPC file with a single record batch.
+    pub fn encode_rows(&self, rows: &[Object]) -> Result<Vec<u8>, CodecError> {
+        let columns = infer_columns(rows);
+
+        if columns.is_empty() {
+            return Err(CodecError::Encode("rows have no columns".to_string()));
+        }
+
+        let fields: Vec<Field> = columns
+            .iter()
+            .map(|(name, column)| {
+                let data_type = match column {
+                    Column::Bool => DataType::Boolean,
+                    Column::Int => DataType::Int64,
+                    Column::Float => DataType::Float64,
+                    Column::Text => DataType::Utf8,
+                };
+
+                Field::new(name, data_type, true)
+            })
+            .collect();
+
+        let arrays: Vec<ArrayRef> = columns
+            .iter()
+            .map(|(name, column)| -> ArrayRef {
+                let values = rows
+                    .iter()
+                    .map(|row| row.get(name).filter(|v| !v.is_null()));
+
+                match column {
+                    Column::Bool => Arc::new(
+                        values
+                            .map(|v| v.and_then(|v| v.as_bool()))
+                            .collect::<BooleanArray>(),
+                    ),
+                    Column::Int => Arc::new(
+                        values
+                            .map(|v| v.and_then(|v| v.as_int()))
+                            .collect::<Int64Array>(),
+                    ),
+                    Column::Float => Arc::new(
+                        values
+                            .map(|v| v.and_then(|v| v.as_float()))
+                            .collect::<Float64Array>(),
+                    ),
+                    Column::Text => Arc::new(
+                        values
+                            .map(|v| {
+                                v.map(|v| match v {
+                                    Value::String(s) => s.clone(),
+                                    v => serde_json::Value::from(v).to_string(),
+                                })
+                            })
+                            .collect::<StringArray>(),
+                    ),
+                }
+            })
+            .collect();
+
+        let schema = Arc::new(Schema::new(fields));
+        let batch = RecordBatch::try_new(schema.clone(), arrays).map_err(CodecError::encode)?;
+        let mut writer = FileWriter::try_new(Vec::new(), &schema).map_err(CodecError::encode)?;
+
+        writer.write(&batch).map_err(CodecError::encode)?;
+        writer.finish().map_err(CodecError::encode)?;
+        writer.into_inner().map_err(CodecError::encode)
+    }
+}
+
+impl Codec for ArrowCodec {
+    fn format(&self) -> Format {
+        Format::Arrow
+    }
+
+    fn decode(&self, record: Record) -> Result<Document, CodecError> {
+        if record.media_type.format() != Format::Arrow {
+            return Err(CodecError::UnsupportedMediaType(record.media_type));
+        }
+
+        let entities = self
+            .decode_rows(record.content)?
+            .into_iter()
+            .enumerate()
+            .map(|(i, row)| {
+                Entity::new(
+                    IdentPath::parse(&format!("root[{}]", i)).expect("valid field path"),
+                    record.media_type.as_mime_str(),
+                    Value::Object(row),
+                )
+            })
+            .collect();
+
+        Ok(Document::new(record.path, record.media_type, entities)
+            .with_attributes(record.attributes))
+    }
+
+    /// Encode a document of row entities. An entity holding an array of
+    /// objects (e.g. a serialized `Vec<Object>`) contributes each element as a row.
+    fn encode(&self, document: Document) -> Result<Record, CodecError> {
+        if document.media_type.format() != Format::Arrow {
+            return Err(CodecError::UnsupportedMediaType(document.media_type));
+        }
+
+        let mut rows = Vec::new();
+
+        for entity in &document.content {
+            match &entity.content {
+                Value::Object(row) => rows.push(row.clone()),
+                Value::Array(items) => {
+                    for item in items.iter() {
+                        let row = item.as_object().ok_or_else(|| {
+                            CodecError::Encode(format!(
+                                "expected object row, found {}",
+                                item.kind()
+                            ))
+                        })?;
+                        rows.push(row.clone());
+                    }
+                }
+                other => {
+                    return Err(CodecError::Encode(format!(
+                        "expected object row, found {}",
+                        other.kind()
+                    )));
+                }
+            }
+        }
+
+        let bytes = self.encode_rows(&rows)?;
+        Ok(Record::new(document.path, document.media_type, bytes)
+            .with_attributes(document.attributes))
+    }
+}
+
+fn batch_rows(batch: &RecordBatch) -> Result<Vec<Object>, CodecError> {
+    let schema = batch.schema();
+    let columns = schema
+        .fields()
+        .iter()
+        .zip(batch.columns())
+        .map(|(field, column)| Ok((field.name().clone(), column_values(column.as_ref())?)))
+        .collect::<Result<Vec<_>, CodecError>>()?;
+
+    Ok((0..batch.num_rows())
+        .map(|i| {
+            let mut object = Object::new();
+
+            for (name, values) in &columns {
+                object.insert(name.clone(), values[i].clone());
+            }
+
+            object
+        })
+        .collect())
+}
+
+/// Convert a whole column at once, so nested and formatted columns are only
+/// set up once per batch.
+fn column_values(array: &dyn ArrowArray) -> Result<Vec<Value>, CodecError> {
+    let int = |v: i64| Value::Number(Number::Int(v));
+    let float = |v: f64| Value::Number(Number::Float(v));
+
+    let values = match array.data_type() {
+        DataType::Null => vec![Value::Null; array.len()],
+        DataType::Boolean => array
+            .as_boolean()
+            .iter()
+            .map(|v| v.map_or(Value::Null, Value::Bool))
+            .collect(),
+        DataType::Int8 => primitive::<Int8Type>(array, |v| int(v as i64)),
+        DataType::Int16 => primitive::<Int16Type>(array, |v| int(v as i64)),
+        DataType::Int32 => primitive::<Int32Type>(array, |v| int(v as i64)),
+        DataType::Int64 => primitive::<Int64Type>(array, int),
+        DataType::UInt8 => primitive::<UInt8Type>(array, |v| int(v as i64)),
+        DataType::UInt16 => primitive::<UInt16Type>(array, |v| int(v as i64)),
+        DataType::UInt32 => primitive::<UInt32Type>(array, |v| int(v as i64)),
+        DataType::UInt64 => primitive::<UInt64Type>(array, |v| match i64::try_from(v) {
+            Ok(v) => int(v),
+            Err(_) => float(v as f64),
+        }),
+        DataType::Float16 => primitive::<Float16Type>(array, |v| float(v.to_f64())),
+        DataType::Float32 => primitive::<Float32Type>(array, |v| float(v as f64)),
+        DataType::Float64 => primitive::<Float64Type>(array, float),
+        DataType::Utf8 => strings(array.as_string::<i32>().iter()),
+        DataType::LargeUtf8 => strings(array.as_string::<i64>().iter()),
+        DataType::Utf8View => strings(array.as_string_view().iter()),
+        DataType::Binary => array.as_binary::<i32>().iter().map(bytes_value).collect(),
+        DataType::LargeBinary => array.as_binary::<i64>().iter().map(bytes_value).collect(),
+        DataType::List(_) => lists(array.as_list::<i32>().iter())?,
+        DataType::LargeList(_) => lists(array.as_list::<i64>().iter())?,
+        DataType::FixedSizeList(_, _) => lists(array.as_fixed_size_list().iter())?,
+        DataType::Struct(_) => {
+            let array = array.as_struct();
+            let columns = array
+                .column_names()
+                .into_iter()
+                .zip(array.columns())
+                .map(|(name, column)| Ok((name.to_string(), column_values(column.as_ref())?)))
+                .collect::<Result<Vec<_>, CodecError>>()?;
+
+            (0..array.len())
+                .map(|i| {
+                    if array.is_null(i) {
+                        return Value::Null;
+                    }
+
+                    let mut object = Object::new();
+
+                    for (name, values) in &columns {
+                        object.insert(name.clone(), values[i].clone());
+                    }
+
+                    Value::Object(object)
+                })
+                .collect()
+        }
+        // Dates, timestamps, decimals, dictionaries and maps keep their display form
+        _ => {
+            let formatter = ArrayFormatter::try_new(array, &FormatOptions::default())
+                .map_err(CodecError::decode)?;
+
+            (0..array.len())
+                .map(|i| {
+                    if array.is_null(i) {
+                        Value::Null
+                    } else {
+                        Value::String(formatter.value(i).to_string())
+                    }
+                })
+                .collect()
+        }
+    };
+
+    Ok(values)
+}
+
+fn primitive<T: ArrowPrimitiveType>(
+    array: &dyn ArrowArray,
+    value: impl Fn(T::Native) -> Value,
+) -> Vec<Value> {
+    array
+        .as_primitive::<T>()
+        .iter()
+        .map(|v| v.map_or(Value::Null, &value))
+        .collect()
+}
+
+fn strings<'a>(values: impl Iterator<Item = Option<&'a str>>) -> Vec<Value> {
+    values
+        .map(|v| v.map_or(Value::Null, |s| Value::String(s.to_string())))
+        .collect()
+}
+
+fn bytes_value(bytes: Option<&[u8]>) -> Value {
+    match bytes {
+        None => Value::Null,
+        Some(bytes) => match std::str::from_utf8(bytes) {
+            Ok(s) => Value::String(s.to_string()),
+            Err(_) => Value::Array(Array::from(bytes.to_vec())),
+        },
+    }
+}
+
+fn lists(values: impl Iterator<Item = Option<ArrayRef>>) -> Result<Vec<Value>, CodecError> {
+    values
+        .map(|v| match v {
+            Some(items) => Ok(Value::Array(Array::from(column_values(items.as_ref())?))),
+            None => Ok(Value::Null),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow::array::{Int32Array, ListArray, StructArray};
+    use arrow::ipc::writer::StreamWriter;
+
+    use super::*;
+    use crate::MediaType;
+    use crate::path::{FilePath, Path};
+
+    fn row(id: i64, text: &str, weight: Option<f64>) -> Object {
+        let mut row = Object::new();
+        row.insert("id".to_string(), Value::Number(Number::Int(id)));
+        row.insert("text".to_string(), Value::String(text.to_string()));
+        row.insert(
+            "weight".to_string(),
+            weight.map_or(Value::Null, |w| Value::Number(Number::Float(w))),
+        );
+        row.insert(
+            "labels".to_string(),
+            Value::Array(Array::from(vec!["task", "time"])),
+        );
+        row
+    }
+
+    /// An IPC stream with list and struct columns, split over two batches,
+    /// the way pyarrow writes a HuggingFace dataset.
+    fn dataset_stream() -> Vec<u8> {
+        let batch = |ids: Vec<i32>, labels: Vec<Option<Vec<Option<i64>>>>| {
+            let meta = StructArray::from(vec![(
+                Arc::new(Field::new("source", DataType::Utf8, true)),
+                Arc::new(StringArray::from(vec!["hf"; ids.len()])) as ArrayRef,
+            )]);
+
+            RecordBatch::try_from_iter(vec![
+                ("id", Arc::new(Int32Array::from(ids)) as ArrayRef),
+                (
+                    "labels",
+                    Arc::new(ListArray::from_iter_primitive::<Int64Type, _, _>(labels)) as ArrayRef,
+                ),
+                ("meta", Arc::new(meta) as ArrayRef),
+            ])
+            .unwrap()
+        };
+
+        let first = batch(vec![1, 2], vec![Some(vec![Some(3), Some(4)]), None]);
+        let second = batch(vec![3], vec![Some(vec![])]);
+
+        let mut writer = StreamWriter::try_new(Vec::new(), &first.schema()).unwrap();
+        writer.write(&first).unwrap();
+        writer.write(&second).unwrap();
+        writer.finish().unwrap();
+        writer.into_inner().unwrap()
+    }
+
+    #[test]
+    fn test_roundtrip_rows() {
+        let codec = ArrowCodec::new();
+        let rows = vec![row(1, "a", Some(0.5)), row(2, "b", None)];
+
+        let bytes = codec.encode_rows(&rows).unwrap();
+        let decoded = codec.decode_rows(bytes).unwrap();
+
+        assert_eq!(decoded.len(), 2);
+        assert_eq!(decoded[0]["id"].as_int(), Some(1));
+        assert_eq!(decoded[0]["text"].as_str(), Some("a"));
+        assert_eq!(decoded[0]["weight"].as_float(), Some(0.5));
+        assert!(decoded[1]["weight"].is_null());
+        assert_eq!(decoded[1]["labels"].as_str(), Some("[\"task\",\"time\"]"));
+    }
+
+    #[test]
+    fn test_decode_stream_batches() {
+        let codec = ArrowCodec::new();
+        let batches = codec.decode_batches(dataset_stream()).unwrap();
+
+        assert_eq!(batches.len(), 2);
+        assert_eq!(batches[0].len(), 2);
+        assert_eq!(batches[1][0]["id"].as_int(), Some(3));
+    }
+
+    #[test]
+    fn test_decode_nested_columns() {
+        let codec = ArrowCodec::new();
+        let rows = codec.decode_rows(dataset_stream()).unwrap();
+        let labels = rows[0]["labels"].as_array().unwrap();
+
+        assert_eq!(rows.len(), 3);
+        assert_eq!(labels.len(), 2);
+        assert_eq!(labels[1].as_int(), Some(4));
+        assert!(rows[1]["labels"].is_null());
+        assert_eq!(rows[2]["labels"].as_array().map(|a| a.len()), Some(0));
+        assert_eq!(rows[0]["meta"]["source"].as_str(), Some("hf"));
+    }
+
+    #[test]
+    fn test_codec_roundtrip() {
+        let codec = ArrowCodec::new();
+        let path = Path::File(FilePath::parse("/test.arrow"));
+        let entity = Entity::new(
+            IdentPath::parse("root").unwrap(),
+            MediaType::Arrow.as_mime_str(),
+            Value::Array(Array::from(vec![
+                Value::Object(row(1, "a", Some(1.0))),
+                Value::Object(row(2, "b", Some(2.0))),
+            ])),
+        );
+        let document = Document::new(path, MediaType::Arrow, vec![entity]);
+
+        let record = codec.encode(document).unwrap();
+        let decoded = codec.decode(record).unwrap();
+
+        assert_eq!(decoded.content.len(), 2);
+        assert_eq!(decoded.content[1].path.to_string(), "root[1]");
+        assert_eq!(decoded.content[1].content["text"].as_str(), Some("b"));
+    }
+
+    #[test]
+    fn test_decode_invalid_bytes() {
+        let codec = ArrowCodec::new();
+
+        assert!(
+            codec
+                .decode_rows(b"not arrow".to_vec())
+                .unwrap_err()
+                .is_decode()
+        );
+    }
+
+    #[test]
+    fn test_unsupported_media_type() {
+        let codec = ArrowCodec::new();
+        let path = Path::File(FilePath::parse("/test.json"));
+        let record = Record::from_str(path, MediaType::TextJson, "{}");
+
+        assert!(codec.decode(record).unwrap_err().is_unsupported());
+    }
+}
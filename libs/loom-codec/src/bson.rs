@@ -0,0 +1,218 @@
+use std::io::{Cursor, Write};
+
+use crate::path::IdentPath;
+use crate::value::Value;
+use crate::{Document, Entity, Format, Record};
+
+use super::{Codec, CodecError};
+
+/// Codec for BSON, MongoDB's binary document format.
+///
+/// A record may hold several concatenated documents (as written by
+/// `mongodump`); each decodes to its own entity at `root[i]`. Encoding writes
+/// every entity back as one document, so entities must be objects.
+#[derive(Debug, Clone, Default)]
+pub struct BsonCodec;
+
+impl BsonCodec {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Codec for BsonCodec {
+    fn format(&self) -> Format {
+        Format::Bson
+    }
+
+    fn decode(&self, record: Record) -> Result<Document, CodecError> {
+        if record.media_type.format() != Format::Bson {
+            return Err(CodecError::UnsupportedMediaType(record.media_type));
+        }
+
+        let len = record.content.len() as u64;
+        let mut reader = Cursor::new(record.content.as_slice());
+        let mut entities = Vec::new();
+
+        while reader.position() < len {
+            let doc = bson::Document::from_reader(&mut reader).map_err(CodecError::decode)?;
+            let path =
+                IdentPath::parse(&format!("root[{}]", entities.len())).expect("valid field path");
+
+            entities.push(Entity::new(
+                path,
+                record.media_type.as_mime_str(),
+                Value::from(doc),
+            ));
+        }
+
+        Ok(Document::new(record.path, record.media_type, entities)
+            .with_attributes(record.attributes))
+    }
+
+    fn encode(&self, document: Document) -> Result<Record, CodecError> {
+        if document.media_type.format() != Format::Bson {
+            return Err(CodecError::UnsupportedMediaType(document.media_type));
+        }
+
+        let mut bytes = Vec::new();
+
+        for entity in &document.content {
+            let bson::Bson::Document(doc) = bson::Bson::from(&entity.content) else {
+                return Err(CodecError::Encode(format!(
+                    "{} is not an object; BSON can only encode documents",
+                    entity.path
+                )));
+            };
+
+            doc.to_writer(&mut bytes).map_err(CodecError::encode)?;
+        }
+
+        Ok(Record::new(document.path, document.media_type, bytes)
+            .with_attributes(document.attributes))
+    }
+
+    /// BSON documents concatenate, so records are written back to back.
+    fn encode_stream(
+        &self,
+        documents: &mut dyn Iterator<Item = Document>,
+        out: &mut dyn Write,
+    ) -> Result<usize, CodecError> {
+        let mut count = 0;
+
+        for document in documents {
+            let record = self.encode(document)?;
+            out.write_all(&record.content).map_err(CodecError::encode)?;
+            count += 1;
+        }
+
+        Ok(count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MediaType;
+    use crate::path::FilePath;
+    use crate::path::Path;
+    use crate::value::{Array, Number, Object};
+
+    fn path() -> Path {
+        Path::File(FilePath::parse("/test.bson"))
+    }
+
+    fn document(values: Vec<Value>) -> Document {
+        let entities = values
+            .into_iter()
+            .enumerate()
+            .map(|(i, value)| {
+                Entity::new(
+                    IdentPath::parse(&format!("root[{}]", i)).unwrap(),
+                    "application/bson",
+                    value,
+                )
+            })
+            .collect();
+
+        Document::new(path(), MediaType::Bson, entities)
+    }
+
+    #[test]
+    fn test_roundtrip_multiple_documents() {
+        let codec = BsonCodec::new();
+
+        let mut first = Object::new();
+        first.insert("id".to_string(), Value::Number(Number::Int(-42)));
+        first.insert("score".to_string(), Value::Number(Number::Float(0.875)));
+        first.insert("notes".to_string(), Value::Null);
+        first.insert(
+            "labels".to_string(),
+            Value::Array(Array::from(vec![Value::String("task".to_string())])),
+        );
+
+        let mut second = Object::new();
+        second.insert("accepted".to_string(), Value::Bool(true));
+
+        let original = vec![Value::Object(first), Value::Object(second)];
+        let record = codec.encode(document(original.clone())).unwrap();
+
+        assert_eq!(record.media_type, MediaType::Bson);
+
+        let decoded = codec.decode(record).unwrap();
+
+        assert_eq!(decoded.content.len(), 2);
+        assert_eq!(decoded.content[1].path.to_string(), "root[1]");
+        assert_eq!(decoded.content[0].content, original[0]);
+        assert_eq!(decoded.content[1].content, original[1]);
+    }
+
+    #[test]
+    fn test_encode_stream_concatenates_documents() {
+        let codec = BsonCodec::new();
+        let mut documents = (0..3).map(|i| {
+            let mut object = Object::new();
+            object.insert("i".to_string(), Value::Number(Number::Int(i)));
+            document(vec![Value::Object(object)])
+        });
+        let mut out = Vec::new();
+
+        assert_eq!(codec.encode_stream(&mut documents, &mut out).unwrap(), 3);
+
+        let decoded = codec
+            .decode(Record::new(path(), MediaType::Bson, out))
+            .unwrap();
+
+        assert_eq!(decoded.content.len(), 3);
+        assert_eq!(decoded.content[2].content["i"].as_int(), Some(2));
+    }
+
+    #[test]
+    fn test_decode_mongo_types() {
+        let codec = BsonCodec::new();
+        let id = bson::oid::ObjectId::new();
+        let doc = bson::doc! {
+            "_id": id,
+            "count": 7_i32,
+            "created": bson::DateTime::from_millis(0),
+        };
+
+        let mut bytes = Vec::new();
+        doc.to_writer(&mut bytes).unwrap();
+
+        let decoded = codec
+            .decode(Record::new(path(), MediaType::Bson, bytes))
+            .unwrap();
+        let value = &decoded.content[0].content;
+
+        assert_eq!(value["_id"].as_str(), Some(id.to_hex().as_str()));
+        assert_eq!(value["count"].as_int(), Some(7));
+        assert_eq!(value["created"].as_str(), Some("1970-01-01T00:00:00Z"));
+    }
+
+    #[test]
+    fn test_encode_rejects_non_objects() {
+        let codec = BsonCodec::new();
+        let err = codec
+            .encode(document(vec![Value::Number(Number::Int(1))]))
+            .unwrap_err();
+
+        assert!(err.is_encode());
+    }
+
+    #[test]
+    fn test_decode_invalid() {
+        let codec = BsonCodec::new();
+        let record = Record::new(path(), MediaType::Bson, vec![0x05, 0x00]);
+
+        assert!(codec.decode(record).unwrap_err().is_decode());
+    }
+
+    #[test]
+    fn test_unsupported_media_type() {
+        let codec = BsonCodec::new();
+        let record = Record::from_str(path(), MediaType::TextJson, "{}");
+
+        assert!(codec.decode(record).unwrap_err().is_unsupported());
+    }
+}
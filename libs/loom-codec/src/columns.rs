@@ -0,0 +1,87 @@
+use std::collections::BTreeMap;
+
+use crate::value::{Number, Object, Value};
+
+/// Column type of a columnar (Arrow, Parquet) encoding, inferred from row values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Column {
+    Bool,
+    Int,
+    Float,
+    Text,
+}
+
+impl Column {
+    pub(crate) fn of(value: &Value) -> Option<Self> {
+        match value {
+            Value::Null => None,
+            Value::Bool(_) => Some(Self::Bool),
+            Value::Number(Number::Int(_)) => Some(Self::Int),
+            Value::Number(Number::Float(_)) => Some(Self::Float),
+            Value::String(_) | Value::Array(_) | Value::Object(_) => Some(Self::Text),
+        }
+    }
+
+    /// Widen two column types: integers widen to floats, anything else mixed becomes text.
+    pub(crate) fn merge(self, other: Self) -> Self {
+        match (self, other) {
+            (a, b) if a == b => a,
+            (Self::Int, Self::Float) | (Self::Float, Self::Int) => Self::Float,
+            _ => Self::Text,
+        }
+    }
+}
+
+/// Columns keyed by name; all-null columns are written as text.
+pub(crate) fn infer_columns(rows: &[Object]) -> BTreeMap<String, Column> {
+    let mut columns: BTreeMap<String, Option<Column>> = BTreeMap::new();
+
+    for row in rows {
+        for (name, value) in row.iter() {
+            let column = columns.entry(name.clone()).or_default();
+            *column = match (*column, Column::of(value)) {
+                (Some(a), Some(b)) => Some(a.merge(b)),
+                (a, b) => a.or(b),
+            };
+        }
+    }
+
+    columns
+        .into_iter()
+        .map(|(name, column)| (name, column.unwrap_or(Column::Text)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(name: &str, value: Value) -> Object {
+        let mut row = Object::new();
+        row.insert(name.to_string(), value);
+        row
+    }
+
+    #[test]
+    fn test_mixed_numbers_widen_to_float() {
+        let rows = [
+            row("x", Value::Number(Number::Int(1))),
+            row("x", Value::Number(Number::Float(1.5))),
+        ];
+
+        assert_eq!(infer_columns(&rows)["x"], Column::Float);
+    }
+
+    #[test]
+    fn test_mixed_types_become_text() {
+        let rows = [
+            row("x", Value::Bool(true)),
+            row("x", Value::Number(Number::Int(1))),
+            row("y", Value::Null),
+        ];
+        let columns = infer_columns(&rows);
+
+        assert_eq!(columns["x"], Column::Text);
+        assert_eq!(columns["y"], Column::Text);
+    }
+}
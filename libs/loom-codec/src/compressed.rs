@@ -0,0 +1,285 @@
+use std::io::{Read, Write};
+
+use crate::{Document, Format, MediaType, Record};
+
+use super::{Codec, CodecError, EncodeOptions, PartialDocument};
+
+/// Compression applied by a [`CompressedCodec`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Compression {
+    /// gzip (`.gz`)
+    #[cfg(feature = "gzip")]
+    Gzip,
+    /// Zstandard (`.zst`)
+    #[cfg(feature = "zstd")]
+    Zstd,
+}
+
+impl Compression {
+    /// File extension (without the leading dot).
+    pub fn extension(self) -> &'static str {
+        match self {
+            #[cfg(feature = "gzip")]
+            Self::Gzip => "gz",
+            #[cfg(feature = "zstd")]
+            Self::Zstd => "zst",
+        }
+    }
+
+    /// Media type of compressed records.
+    pub fn media_type(self) -> MediaType {
+        match self {
+            #[cfg(feature = "gzip")]
+            Self::Gzip => MediaType::ArchiveGzip,
+            #[cfg(feature = "zstd")]
+            Self::Zstd => MediaType::ArchiveZstd,
+        }
+    }
+
+    pub fn compress(self, content: &[u8]) -> Result<Vec<u8>, CodecError> {
+        match self {
+            #[cfg(feature = "gzip")]
+            Self::Gzip => {
+                let mut encoder =
+                    flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+                encoder.write_all(content).map_err(CodecError::encode)?;
+                encoder.finish().map_err(CodecError::encode)
+            }
+            #[cfg(feature = "zstd")]
+            Self::Zstd => zstd::stream::encode_all(content, 0).map_err(CodecError::encode),
+        }
+    }
+
+    /// Decompress `content`, failing once the output grows past `limit`
+    /// bytes rather than inflating a hostile or corrupt record without end.
+    pub fn decompress(self, content: &[u8], limit: usize) -> Result<Vec<u8>, CodecError> {
+        match self {
+            #[cfg(feature = "gzip")]
+            Self::Gzip => read_limited(flate2::read::GzDecoder::new(content), limit),
+            #[cfg(feature = "zstd")]
+            Self::Zstd => read_limited(
+                zstd::stream::read::Decoder::new(content).map_err(CodecError::decode)?,
+                limit,
+            ),
+        }
+    }
+}
+
+/// All of `reader`, unless it yields more than `limit` bytes
+fn read_limited(reader: impl Read, limit: usize) -> Result<Vec<u8>, CodecError> {
+    let mut out = Vec::new();
+    reader
+        .take(limit as u64 + 1)
+        .read_to_end(&mut out)
+        .map_err(CodecError::decode)?;
+
+    if out.len() > limit {
+        return Err(CodecError::Decode(format!(
+            "decompressed content exceeds the limit of {} bytes",
+            limit
+        )));
+    }
+
+    Ok(out)
+}
+
+/// Codec wrapper that decompresses records before decoding them with the
+/// inner codec, and compresses what the inner codec encodes.
+///
+/// Decompressed content is capped at [`CompressedCodec::DEFAULT_MAX_SIZE`]
+/// bytes unless set otherwise with
+/// [`with_max_size`](CompressedCodec::with_max_size).
+///
+/// Compressed records carry the compression's media type (e.g.
+/// `application/gzip`), so register the wrapper under a compound extension
+/// with [`CodecRegistryBuilder::compound`](crate::CodecRegistryBuilder::compound):
+///
+/// ```ignore
+/// let codec = CompressedCodec::gzip(JsonCodec::new());
+/// let registry = CodecRegistry::new()
+///     .codec(JsonCodec::new())
+///     .compound(&codec.extension(), codec) // "json.gz"
+///     .build();
+/// ```
+#[derive(Debug, Clone)]
+pub struct CompressedCodec<C> {
+    inner: C,
+    compression: Compression,
+    max_size: usize,
+}
+
+impl<C: Codec> CompressedCodec<C> {
+    /// Largest decompressed record accepted by default: 256 MiB
+    pub const DEFAULT_MAX_SIZE: usize = 256 * 1024 * 1024;
+
+    pub fn new(inner: C, compression: Compression) -> Self {
+        Self {
+            inner,
+            compression,
+            max_size: Self::DEFAULT_MAX_SIZE,
+        }
+    }
+
+    #[cfg(feature = "gzip")]
+    pub fn gzip(inner: C) -> Self {
+        Self::new(inner, Compression::Gzip)
+    }
+
+    #[cfg(feature = "zstd")]
+    pub fn zstd(inner: C) -> Self {
+        Self::new(inner, Compression::Zstd)
+    }
+
+    pub fn inner(&self) -> &C {
+        &self.inner
+    }
+
+    pub fn compression(&self) -> Compression {
+        self.compression
+    }
+
+    /// Fail to decode records that decompress to more than `bytes`.
+    pub fn with_max_size(mut self, bytes: usize) -> Self {
+        self.max_size = bytes;
+        self
+    }
+
+    pub fn max_size(&self) -> usize {
+        self.max_size
+    }
+
+    fn compress_record(&self, record: Record) -> Result<Record, CodecError> {
+        let content = self.compression.compress(&record.content)?;
+
+        Ok(
+            Record::new(record.path, self.compression.media_type(), content)
+                .with_attributes(record.attributes),
+        )
+    }
+
+    /// Compound extension of the records this codec handles, e.g. `json.gz`.
+    pub fn extension(&self) -> String {
+        format!(
+            "{}.{}",
+            self.inner.format().extension(),
+            self.compression.extension()
+        )
+    }
+}
+
+impl<C: Codec> Codec for CompressedCodec<C> {
+    fn format(&self) -> Format {
+        self.inner.format()
+    }
+
+    fn decode(&self, record: Record) -> Result<Document, CodecError> {
+        if record.media_type != self.compression.media_type() {
+            return Err(CodecError::UnsupportedMediaType(record.media_type));
+        }
+
+        let content = self
+            .compression
+            .decompress(&record.content, self.max_size)?;
+        let media_type = self.inner.format().media_type();
+
+        self.inner.decode(
+            Record::new(record.path, media_type, content).with_attributes(record.attributes),
+        )
+    }
+
+    fn decode_lenient(&self, record: Record) -> Result<PartialDocument, CodecError> {
+        if record.media_type != self.compression.media_type() {
+            return Err(CodecError::UnsupportedMediaType(record.media_type));
+        }
+
+        let content = match self.compression.decompress(&record.content, self.max_size) {
+            Ok(content) => content,
+            Err(e) => return Ok(PartialDocument::failed(record.path, record.media_type, e)),
+        };
+        let media_type = self.inner.format().media_type();
+
+        self.inner.decode_lenient(
+            Record::new(record.path, media_type, content).with_attributes(record.attributes),
+        )
+    }
+
+    fn encode(&self, document: Document) -> Result<Record, CodecError> {
+        let record = self.inner.encode(document)?;
+        self.compress_record(record)
+    }
+
+    fn encode_with(
+        &self,
+        document: Document,
+        options: &EncodeOptions,
+    ) -> Result<Record, CodecError> {
+        let record = self.inner.encode_with(document, options)?;
+        self.compress_record(record)
+    }
+}
+
+#[cfg(all(test, feature = "json"))]
+mod tests {
+    use super::*;
+    use crate::JsonCodec;
+    use crate::path::{FilePath, Path};
+
+    fn path() -> Path {
+        Path::File(FilePath::parse("/test.json.gz"))
+    }
+
+    fn roundtrip(codec: CompressedCodec<JsonCodec>) {
+        let json = Record::from_str(path(), MediaType::TextJson, r#"{"name":"test"}"#);
+        let document = JsonCodec::new().decode(json).unwrap();
+
+        let record = codec.encode(document).unwrap();
+        assert_eq!(record.media_type, codec.compression().media_type());
+        assert_ne!(record.content_str().ok(), Some(r#"{"name":"test"}"#));
+
+        let decoded = codec.decode(record).unwrap();
+        assert_eq!(decoded.media_type, MediaType::TextJson);
+        assert_eq!(decoded.content[0].content["name"].as_str(), Some("test"));
+    }
+
+    #[cfg(feature = "gzip")]
+    #[test]
+    fn test_gzip_roundtrip() {
+        let codec = CompressedCodec::gzip(JsonCodec::new());
+        assert_eq!(codec.extension(), "json.gz");
+        roundtrip(codec);
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn test_zstd_roundtrip() {
+        let codec = CompressedCodec::zstd(JsonCodec::new());
+        assert_eq!(codec.extension(), "json.zst");
+        roundtrip(codec);
+    }
+
+    #[cfg(feature = "gzip")]
+    #[test]
+    fn test_rejects_oversized_content() {
+        let codec = CompressedCodec::gzip(JsonCodec::new()).with_max_size(1024);
+        let json = format!("[{}0]", "0,".repeat(1024));
+        let content = Compression::Gzip.compress(json.as_bytes()).unwrap();
+        let record = Record::new(path(), MediaType::ArchiveGzip, content);
+
+        assert_eq!(codec.max_size(), 1024);
+        assert!(codec.decode(record.clone()).unwrap_err().is_decode());
+        assert!(
+            CompressedCodec::gzip(JsonCodec::new())
+                .decode(record)
+                .is_ok()
+        );
+    }
+
+    #[cfg(feature = "gzip")]
+    #[test]
+    fn test_rejects_uncompressed_records() {
+        let codec = CompressedCodec::gzip(JsonCodec::new());
+        let record = Record::from_str(path(), MediaType::TextJson, "{}");
+
+        assert!(codec.decode(record).unwrap_err().is_unsupported());
+    }
+}
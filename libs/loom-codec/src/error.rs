@@ -1,11 +1,57 @@
-use crate::MediaType;
+use crate::{MediaType, SchemaViolation};
 use std::fmt;
 
+/// 1-based line and column of a problem in a record
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Position {
+    pub fn new(line: usize, column: usize) -> Self {
+        Self { line, column }
+    }
+
+    /// Position of byte `offset` in `text`
+    pub fn of_offset(text: &str, offset: usize) -> Self {
+        let mut end = offset.min(text.len());
+
+        while !text.is_char_boundary(end) {
+            end -= 1;
+        }
+
+        let before = &text[..end];
+        let line = before.matches('\n').count() + 1;
+        let column = before
+            .rfind('\n')
+            .map_or(before, |i| &before[i + 1..])
+            .chars()
+            .count()
+            + 1;
+
+        Self { line, column }
+    }
+}
+
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}", self.line, self.column)
+    }
+}
+
 #[derive(Debug)]
 pub enum CodecError {
     UnsupportedMediaType(MediaType),
     Decode(String),
     Encode(String),
+    /// Decode failure at a known position in the record
+    Syntax {
+        position: Position,
+        message: String,
+    },
+    /// Decoded content does not match its schema
+    SchemaViolation(Vec<SchemaViolation>),
 }
 
 impl CodecError {
@@ -17,17 +63,36 @@ impl CodecError {
         Self::Encode(e.to_string())
     }
 
+    pub fn syntax(position: Position, message: impl Into<String>) -> Self {
+        Self::Syntax {
+            position,
+            message: message.into(),
+        }
+    }
+
+    /// Where in the record the error occurred, if known
+    pub fn position(&self) -> Option<Position> {
+        match self {
+            Self::Syntax { position, .. } => Some(*position),
+            _ => None,
+        }
+    }
+
     pub fn is_unsupported(&self) -> bool {
         matches!(self, Self::UnsupportedMediaType(_))
     }
 
     pub fn is_decode(&self) -> bool {
-        matches!(self, Self::Decode(_))
+        matches!(self, Self::Decode(_) | Self::Syntax { .. })
     }
 
     pub fn is_encode(&self) -> bool {
         matches!(self, Self::Encode(_))
     }
+
+    pub fn is_schema_violation(&self) -> bool {
+        matches!(self, Self::SchemaViolation(_))
+    }
 }
 
 impl fmt::Display for CodecError {
@@ -36,6 +101,22 @@ impl fmt::Display for CodecError {
             Self::UnsupportedMediaType(mt) => write!(f, "unsupported media type: {}", mt),
             Self::Decode(msg) => write!(f, "decode error: {}", msg),
             Self::Encode(msg) => write!(f, "encode error: {}", msg),
+            Self::Syntax { position, message } => {
+                write!(f, "decode error at {}: {}", position, message)
+            }
+            Self::SchemaViolation(violations) => {
+                write!(f, "schema violation: ")?;
+
+                for (i, violation) in violations.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, "; ")?;
+                    }
+
+                    write!(f, "{}", violation)?;
+                }
+
+                Ok(())
+            }
         }
     }
 }
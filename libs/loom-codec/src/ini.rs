@@ -0,0 +1,364 @@
+use crate::path::IdentPath;
+use crate::value::{Number, Object, Value};
+use crate::{Document, Entity, Format, Record};
+
+use super::{Codec, CodecError};
+
+/// Codec for INI and Java `.properties` files.
+///
+/// `[section]` headers and dotted keys (`server.port = 8080`) both map to
+/// nested objects, so `[server]` + `port = 8080` and `server.port = 8080`
+/// decode to the same value. Keys may be separated from values by `=` or
+/// `:`, lines starting with `;` or `#` are comments, and a trailing `\`
+/// continues a value on the next line.
+///
+/// Unquoted `true` / `false` and numbers decode to bools and numbers; wrap a
+/// value in quotes to keep it a string.
+#[derive(Debug, Clone, Default)]
+pub struct IniCodec;
+
+impl IniCodec {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Codec for IniCodec {
+    fn format(&self) -> Format {
+        Format::Ini
+    }
+
+    fn decode(&self, record: Record) -> Result<Document, CodecError> {
+        if record.media_type.format() != Format::Ini {
+            return Err(CodecError::UnsupportedMediaType(record.media_type));
+        }
+
+        let text = String::from_utf8(record.content)?;
+        let value = Value::Object(parse(&text)?);
+
+        let entity = Entity::new(
+            IdentPath::parse("root").expect("valid field path"),
+            record.media_type.as_mime_str(),
+            value,
+        );
+
+        Ok(Document::new(record.path, record.media_type, vec![entity])
+            .with_attributes(record.attributes))
+    }
+
+    fn encode(&self, document: Document) -> Result<Record, CodecError> {
+        if document.media_type.format() != Format::Ini {
+            return Err(CodecError::UnsupportedMediaType(document.media_type));
+        }
+
+        let content = document
+            .content
+            .first()
+            .ok_or_else(|| CodecError::Encode("document has no content".to_string()))?;
+
+        let Value::Object(object) = &content.content else {
+            return Err(CodecError::Encode(format!(
+                "{} is not an object; INI can only encode sections of keys",
+                content.path
+            )));
+        };
+
+        let mut text = String::new();
+        write_section(&mut text, &[], object)?;
+
+        Ok(Record::from_str(document.path, document.media_type, &text)
+            .with_attributes(document.attributes))
+    }
+}
+
+fn parse(text: &str) -> Result<Object, CodecError> {
+    let mut root = Object::new();
+    let mut section: Vec<String> = Vec::new();
+    let mut lines = text.lines().enumerate();
+
+    while let Some((i, line)) = lines.next() {
+        let line_no = i + 1;
+        let mut line = line.trim().to_string();
+
+        if line.is_empty() || line.starts_with([';', '#']) {
+            continue;
+        }
+
+        while line.ends_with('\\') {
+            line.pop();
+
+            match lines.next() {
+                Some((_, next)) => line.push_str(next.trim()),
+                None => break,
+            }
+        }
+
+        if let Some(header) = line.strip_prefix('[') {
+            let name = header.strip_suffix(']').ok_or_else(|| {
+                CodecError::Decode(format!("line {}: unclosed section header", line_no))
+            })?;
+
+            section = split_key(name, line_no)?;
+            table(&mut root, &section, line_no)?;
+            continue;
+        }
+
+        let sep = line.find(['=', ':']).ok_or_else(|| {
+            CodecError::Decode(format!("line {}: expected `key = value`", line_no))
+        })?;
+
+        let mut path = section.clone();
+        path.extend(split_key(&line[..sep], line_no)?);
+
+        let name = path.pop().expect("key has at least one segment");
+        let table = table(&mut root, &path, line_no)?;
+
+        if table.get(&name).is_some_and(Value::is_object) {
+            return Err(CodecError::Decode(format!(
+                "line {}: '{}' is a section, not a value",
+                line_no, name
+            )));
+        }
+
+        table.insert(name, parse_value(line[sep + 1..].trim()));
+    }
+
+    Ok(root)
+}
+
+/// Split a dotted key or section name into its segments.
+fn split_key(key: &str, line_no: usize) -> Result<Vec<String>, CodecError> {
+    key.split('.')
+        .map(|segment| {
+            let segment = segment.trim();
+
+            if segment.is_empty() {
+                return Err(CodecError::Decode(format!(
+                    "line {}: empty key in '{}'",
+                    line_no,
+                    key.trim()
+                )));
+            }
+
+            Ok(segment.to_string())
+        })
+        .collect()
+}
+
+/// The object at `path`, creating empty sections along the way.
+fn table<'a>(
+    root: &'a mut Object,
+    path: &[String],
+    line_no: usize,
+) -> Result<&'a mut Object, CodecError> {
+    let mut table = root;
+
+    for name in path {
+        table = table
+            .entry(name.clone())
+            .or_insert_with(|| Value::Object(Object::new()))
+            .as_object_mut()
+            .ok_or_else(|| {
+                CodecError::Decode(format!(
+                    "line {}: '{}' is a value, not a section",
+                    line_no, name
+                ))
+            })?;
+    }
+
+    Ok(table)
+}
+
+fn parse_value(raw: &str) -> Value {
+    let quoted = raw.len() >= 2
+        && ((raw.starts_with('"') && raw.ends_with('"'))
+            || (raw.starts_with('\'') && raw.ends_with('\'')));
+
+    if quoted {
+        return Value::String(raw[1..raw.len() - 1].to_string());
+    }
+
+    match raw {
+        "true" => return Value::Bool(true),
+        "false" => return Value::Bool(false),
+        _ => {}
+    }
+
+    if let Ok(i) = raw.parse::<i64>() {
+        return Value::Number(Number::Int(i));
+    }
+
+    // Only plain decimal notation, so words like `inf` or `NaN` stay strings
+    let numeric = raw
+        .bytes()
+        .all(|b| b.is_ascii_digit() || matches!(b, b'+' | b'-' | b'.' | b'e' | b'E'));
+
+    if numeric && let Ok(f) = raw.parse::<f64>() {
+        return Value::Number(Number::Float(f));
+    }
+
+    Value::String(raw.to_string())
+}
+
+fn write_section(text: &mut String, path: &[&str], object: &Object) -> Result<(), CodecError> {
+    let (sections, values): (Vec<_>, Vec<_>) = object.iter().partition(|(_, v)| v.is_object());
+
+    // Empty sections keep their header so they survive a roundtrip
+    if !path.is_empty() && (!values.is_empty() || sections.is_empty()) {
+        if !text.is_empty() {
+            text.push('\n');
+        }
+
+        text.push_str(&format!("[{}]\n", path.join(".")));
+    }
+
+    for (key, value) in values {
+        text.push_str(&format!("{} = {}\n", key, format_value(key, value)?));
+    }
+
+    for (key, value) in sections {
+        let mut path = path.to_vec();
+        path.push(key.as_str());
+
+        if let Value::Object(object) = value {
+            write_section(text, &path, object)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn format_value(key: &str, value: &Value) -> Result<String, CodecError> {
+    match value {
+        Value::Null => Ok(String::new()),
+        Value::Bool(b) => Ok(b.to_string()),
+        Value::Number(Number::Int(i)) => Ok(i.to_string()),
+        Value::Number(Number::Float(f)) => Ok(format!("{:?}", f)),
+        Value::String(s) if s.contains(['\n', '\r']) => Err(CodecError::Encode(format!(
+            "'{}' spans multiple lines, which INI cannot encode",
+            key
+        ))),
+        // Quote strings that would otherwise decode as something else
+        Value::String(s) => match parse_value(s) {
+            Value::String(ref parsed) if parsed == s && s.trim() == s && !s.ends_with('\\') => {
+                Ok(s.clone())
+            }
+            _ => Ok(format!("\"{}\"", s)),
+        },
+        Value::Array(_) | Value::Object(_) => Err(CodecError::Encode(format!(
+            "INI cannot encode {} values ('{}')",
+            value.kind(),
+            key
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MediaType;
+    use crate::path::FilePath;
+    use crate::path::Path;
+    use crate::value::Array;
+
+    fn path() -> Path {
+        Path::File(FilePath::parse("/test.ini"))
+    }
+
+    fn decode(text: &str) -> Result<Value, CodecError> {
+        let record = Record::from_str(path(), MediaType::TextIni, text);
+        let document = IniCodec::new().decode(record)?;
+
+        Ok(document.content[0].content.clone())
+    }
+
+    fn document(value: Value) -> Document {
+        let entity = Entity::new(IdentPath::parse("root").unwrap(), "text/x-ini", value);
+
+        Document::new(path(), MediaType::TextIni, vec![entity])
+    }
+
+    #[test]
+    fn test_decode_sections() {
+        let value = decode(
+            "; global\nname = loom\n\n[server]\nhost = localhost\nport = 8080\n\n[server.tls]\nenabled = true\n",
+        )
+        .unwrap();
+
+        assert_eq!(value["name"].as_str(), Some("loom"));
+        assert_eq!(value["server"]["host"].as_str(), Some("localhost"));
+        assert_eq!(value["server"]["port"].as_int(), Some(8080));
+        assert_eq!(value["server"]["tls"]["enabled"].as_bool(), Some(true));
+    }
+
+    #[test]
+    fn test_decode_properties() {
+        let value = decode(
+            "# generated\ndb.url: jdbc:postgresql://localhost/loom\ndb.pool.size=10\nmessage = hello \\\n  world\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            value["db"]["url"].as_str(),
+            Some("jdbc:postgresql://localhost/loom")
+        );
+        assert_eq!(value["db"]["pool"]["size"].as_int(), Some(10));
+        assert_eq!(value["message"].as_str(), Some("hello world"));
+    }
+
+    #[test]
+    fn test_decode_values() {
+        let value = decode("a = 0.5\nb = \"42\"\nc = NaN\nd =\ne = 'true'\n").unwrap();
+
+        assert_eq!(value["a"].as_float(), Some(0.5));
+        assert_eq!(value["b"].as_str(), Some("42"));
+        assert_eq!(value["c"].as_str(), Some("NaN"));
+        assert_eq!(value["d"].as_str(), Some(""));
+        assert_eq!(value["e"].as_str(), Some("true"));
+    }
+
+    #[test]
+    fn test_decode_conflicting_keys() {
+        assert!(decode("a = 1\na.b = 2\n").unwrap_err().is_decode());
+        assert!(decode("a.b = 1\na = 2\n").unwrap_err().is_decode());
+        assert!(decode("[a\nb = 1\n").unwrap_err().is_decode());
+        assert!(decode("no separator\n").unwrap_err().is_decode());
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let original = decode(
+            "name = loom\nversion = \"1\"\nratio = 1.0\n\n[server]\nport = 8080\n\n[server.tls]\nenabled = false\n",
+        )
+        .unwrap();
+
+        let record = IniCodec::new().encode(document(original.clone())).unwrap();
+        let text = String::from_utf8(record.content.clone()).unwrap();
+
+        assert!(text.contains("[server.tls]"));
+        assert!(text.contains("version = \"1\""));
+        assert_eq!(decode(&text).unwrap(), original);
+    }
+
+    #[test]
+    fn test_encode_rejects_arrays() {
+        let mut object = Object::new();
+        object.insert(
+            "tags".to_string(),
+            Value::Array(Array::from(vec![Value::Null])),
+        );
+
+        let err = IniCodec::new()
+            .encode(document(Value::Object(object)))
+            .unwrap_err();
+
+        assert!(err.is_encode());
+    }
+
+    #[test]
+    fn test_unsupported_media_type() {
+        let record = Record::from_str(path(), MediaType::TextJson, "{}");
+
+        assert!(IniCodec::new().decode(record).unwrap_err().is_unsupported());
+    }
+}
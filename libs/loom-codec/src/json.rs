@@ -1,12 +1,14 @@
+use std::io::Write;
+
 use crate::path::IdentPath;
 use crate::value::Value;
 use crate::{Document, Entity, Format, Record};
 
-use super::{Codec, CodecError};
+use super::{Codec, CodecError, EncodeOptions, Position};
 
 #[derive(Debug, Clone)]
 pub struct JsonCodec {
-    pub pretty_print: bool,
+    pub options: EncodeOptions,
 }
 
 impl Default for JsonCodec {
@@ -18,16 +20,23 @@ impl Default for JsonCodec {
 impl JsonCodec {
     pub fn new() -> Self {
         Self {
-            pretty_print: false,
+            options: EncodeOptions::compact(),
         }
     }
 
     pub fn pretty() -> Self {
-        Self { pretty_print: true }
+        Self {
+            options: EncodeOptions::pretty(),
+        }
     }
 
     pub fn with_pretty_print(mut self, pretty: bool) -> Self {
-        self.pretty_print = pretty;
+        self.options.indent = pretty.then_some(2);
+        self
+    }
+
+    pub fn with_options(mut self, options: EncodeOptions) -> Self {
+        self.options = options;
         self
     }
 }
@@ -43,7 +52,7 @@ impl Codec for JsonCodec {
         }
 
         let text = String::from_utf8(record.content)?;
-        let json: serde_json::Value = serde_json::from_str(&text).map_err(CodecError::decode)?;
+        let json: serde_json::Value = serde_json::from_str(&text).map_err(syntax_error)?;
         let value: Value = json.into();
 
         let entity = Entity::new(
@@ -52,10 +61,19 @@ impl Codec for JsonCodec {
             value,
         );
 
-        Ok(Document::new(record.path, record.media_type, vec![entity]))
+        Ok(Document::new(record.path, record.media_type, vec![entity])
+            .with_attributes(record.attributes))
     }
 
     fn encode(&self, document: Document) -> Result<Record, CodecError> {
+        self.encode_with(document, &self.options)
+    }
+
+    fn encode_with(
+        &self,
+        document: Document,
+        options: &EncodeOptions,
+    ) -> Result<Record, CodecError> {
         if document.media_type.format() != Format::Json {
             return Err(CodecError::UnsupportedMediaType(document.media_type));
         }
@@ -65,16 +83,52 @@ impl Codec for JsonCodec {
             .first()
             .ok_or_else(|| CodecError::Encode("document has no content".to_string()))?;
 
-        let json: serde_json::Value = (&content.content).into();
-        let text = if self.pretty_print {
-            serde_json::to_string_pretty(&json)
-        } else {
-            serde_json::to_string(&json)
+        let value: serde_json::Value = (&content.content).into();
+        let text = options.encode(&value, Format::Json)?;
+
+        Ok(Record::from_str(document.path, document.media_type, &text)
+            .with_attributes(document.attributes))
+    }
+
+    /// Writes the documents as the elements of one array.
+    fn encode_stream(
+        &self,
+        documents: &mut dyn Iterator<Item = Document>,
+        out: &mut dyn Write,
+    ) -> Result<usize, CodecError> {
+        let mut count = 0;
+
+        out.write_all(b"[").map_err(CodecError::encode)?;
+
+        for document in documents {
+            let record = self.encode(document)?;
+
+            if count > 0 {
+                out.write_all(b",").map_err(CodecError::encode)?;
+            }
+
+            out.write_all(record.content.trim_ascii_end())
+                .map_err(CodecError::encode)?;
+            count += 1;
         }
-        .map_err(CodecError::encode)?;
 
-        Ok(Record::from_str(document.path, document.media_type, &text))
+        out.write_all(self.options.finish("]".to_string()).as_bytes())
+            .map_err(CodecError::encode)?;
+        Ok(count)
+    }
+}
+
+/// A decode error at the position `serde_json` reports, if any.
+pub(crate) fn syntax_error(e: serde_json::Error) -> CodecError {
+    if e.line() == 0 {
+        return CodecError::decode(e);
     }
+
+    let position = Position::new(e.line(), e.column());
+    let message = e.to_string();
+    let suffix = format!(" at line {} column {}", e.line(), e.column());
+
+    CodecError::syntax(position, message.strip_suffix(&suffix).unwrap_or(&message))
 }
 
 #[cfg(test)]
@@ -103,6 +157,31 @@ mod tests {
         assert_eq!(document.content[0].content["value"].as_int(), Some(42));
     }
 
+    #[test]
+    fn test_encode_stream_writes_array() {
+        let codec = JsonCodec::new();
+        let path = Path::File(FilePath::parse("/test.json"));
+        let mut documents = (0_i64..3).map(|i| {
+            let entity = Entity::new(
+                IdentPath::parse("root").unwrap(),
+                "application/json",
+                Value::from(i),
+            );
+            Document::new(path.clone(), MediaType::TextJson, vec![entity])
+        });
+        let mut out = Vec::new();
+
+        assert_eq!(codec.encode_stream(&mut documents, &mut out).unwrap(), 3);
+
+        let decoded = codec
+            .decode(Record::new(path.clone(), MediaType::TextJson, out))
+            .unwrap();
+        let items = decoded.content[0].content.as_array().unwrap();
+
+        assert_eq!(items.len(), 3);
+        assert_eq!(items[2].as_int(), Some(2));
+    }
+
     #[test]
     fn test_encode_json() {
         let codec = JsonCodec::new();
@@ -139,6 +218,20 @@ mod tests {
         assert_eq!(orig_json, round_json);
     }
 
+    #[test]
+    fn test_roundtrip_preserves_attributes() {
+        let codec = JsonCodec::new();
+        let path = Path::File(FilePath::parse("/test.json"));
+        let original = Record::from_str(path, MediaType::TextJson, r#"{"test":123}"#)
+            .with_attribute("mtime", 1_700_000_000_000u64);
+
+        let document = codec.decode(original.clone()).unwrap();
+        assert_eq!(document.attributes, original.attributes);
+
+        let record = codec.encode(document).unwrap();
+        assert_eq!(record.attributes, original.attributes);
+    }
+
     #[test]
     fn test_pretty_print() {
         let codec = JsonCodec::pretty();
@@ -160,6 +253,32 @@ mod tests {
         assert!(text.contains('\n'));
     }
 
+    #[test]
+    fn test_decode_error_position() {
+        let codec = JsonCodec::new();
+        let path = Path::File(FilePath::parse("/test.json"));
+        let record = Record::from_str(path, MediaType::TextJson, "{\n  \"a\": 1,\n  \"b\": }");
+
+        let err = codec.decode(record).unwrap_err();
+
+        assert!(err.is_decode());
+        assert_eq!(err.position().map(|p| p.line), Some(3));
+        assert!(err.to_string().starts_with("decode error at line 3"));
+    }
+
+    #[test]
+    fn test_decode_lenient_reports_error() {
+        let codec = JsonCodec::new();
+        let path = Path::File(FilePath::parse("/test.json"));
+        let record = Record::from_str(path, MediaType::TextJson, "[1, 2,");
+
+        let partial = codec.decode_lenient(record).unwrap();
+
+        assert!(partial.document.content.is_empty());
+        assert_eq!(partial.errors.len(), 1);
+        assert!(partial.errors[0].position().is_some());
+    }
+
     #[test]
     fn test_unsupported_media_type() {
         let codec = JsonCodec::new();
@@ -1,31 +1,90 @@
 mod error;
+mod options;
+mod partial;
 mod registry;
+mod schema;
 
 #[cfg(feature = "json")]
 mod json;
 
+#[cfg(feature = "json")]
+mod ndjson;
+
 #[cfg(feature = "yaml")]
 mod yaml;
 
 #[cfg(feature = "toml")]
 mod toml;
 
+#[cfg(feature = "ini")]
+mod ini;
+
+#[cfg(feature = "msgpack")]
+mod msgpack;
+
+#[cfg(feature = "bson")]
+mod bson;
+
+#[cfg(feature = "protobuf")]
+mod protobuf;
+
+#[cfg(any(feature = "parquet", feature = "arrow"))]
+mod columns;
+
+#[cfg(feature = "parquet")]
+mod parquet;
+
+#[cfg(feature = "arrow")]
+mod arrow;
+
+#[cfg(any(feature = "gzip", feature = "zstd"))]
+mod compressed;
+
 mod text;
 
 pub use error::*;
+pub use options::*;
+pub use partial::*;
 pub use registry::*;
+pub use schema::*;
 
 #[cfg(feature = "json")]
 pub use json::*;
 
+#[cfg(feature = "json")]
+pub use ndjson::*;
+
 #[cfg(feature = "yaml")]
 pub use yaml::*;
 
 #[cfg(feature = "toml")]
 pub use toml::*;
 
+#[cfg(feature = "ini")]
+pub use ini::*;
+
+#[cfg(feature = "msgpack")]
+pub use msgpack::*;
+
+#[cfg(feature = "bson")]
+pub use bson::*;
+
+#[cfg(feature = "protobuf")]
+pub use protobuf::*;
+
+#[cfg(feature = "parquet")]
+pub use parquet::*;
+
+#[cfg(feature = "arrow")]
+pub use arrow::*;
+
+#[cfg(any(feature = "gzip", feature = "zstd"))]
+pub use compressed::*;
+
 pub use text::*;
 
+use std::io::Write;
+
 // Re-export types from dependencies
 pub use loom_core::{Format, MediaType, path, value};
 pub use loom_io::{Document, Entity, Record};
@@ -34,6 +93,70 @@ pub trait Codec: Send + Sync {
     fn format(&self) -> Format;
     fn decode(&self, record: Record) -> Result<Document, CodecError>;
     fn encode(&self, document: Document) -> Result<Record, CodecError>;
+
+    /// Decode as much of `record` as possible, collecting an error (with its
+    /// position where known) for each part that fails instead of stopping at
+    /// the first. Codecs that can't recover, the default, return their
+    /// [`decode`](Codec::decode) error as the only one. Only an unsupported
+    /// media type fails outright.
+    fn decode_lenient(&self, record: Record) -> Result<PartialDocument, CodecError> {
+        let path = record.path.clone();
+        let media_type = record.media_type;
+
+        match self.decode(record) {
+            Ok(document) => Ok(document.into()),
+            Err(error) if error.is_unsupported() => Err(error),
+            Err(error) => Ok(PartialDocument::failed(path, media_type, error)),
+        }
+    }
+
+    /// Encode with `options` instead of the codec's own formatting.
+    /// Codecs without formatting choices ignore them.
+    fn encode_with(
+        &self,
+        document: Document,
+        options: &EncodeOptions,
+    ) -> Result<Record, CodecError> {
+        let _ = options;
+        self.encode(document)
+    }
+
+    /// Encode `document` as a rewrite of `original`, the record it was
+    /// decoded from. Codecs that support [`EncodeOptions::lossless`] keep the
+    /// original's layout when their options enable it; the rest encode as usual.
+    fn rewrite(&self, original: &Record, document: Document) -> Result<Record, CodecError> {
+        let _ = original;
+        self.encode(document)
+    }
+
+    /// [`rewrite`](Codec::rewrite) with `options` instead of the codec's own.
+    fn rewrite_with(
+        &self,
+        original: &Record,
+        document: Document,
+        options: &EncodeOptions,
+    ) -> Result<Record, CodecError> {
+        let _ = original;
+        self.encode_with(document, options)
+    }
+
+    /// Encode `documents` one at a time straight into `out`, returning how
+    /// many were written, so large exports never hold the whole output in
+    /// memory. Only codecs that can frame records as they go support this:
+    /// NDJSON, BSON and delimited Protobuf write records back to back, JSON
+    /// writes one array and YAML a `---` separated stream. The default fails,
+    /// since concatenating whole documents of other formats is invalid output.
+    fn encode_stream(
+        &self,
+        documents: &mut dyn Iterator<Item = Document>,
+        out: &mut dyn Write,
+    ) -> Result<usize, CodecError> {
+        let _ = (documents, out);
+        Err(CodecError::Encode(format!(
+            "{} records can't be encoded as a stream",
+            self.format()
+        )))
+    }
 }
 
 #[macro_export]
@@ -0,0 +1,199 @@
+use crate::path::IdentPath;
+use crate::value::Value;
+use crate::{Document, Entity, Format, Record};
+
+use super::{Codec, CodecError};
+
+/// Codec for MessagePack, a compact binary alternative to JSON.
+///
+/// Decoding fails rather than lose data on values [`Value`] can't hold:
+/// integers above `i64::MAX`, binary and extension values, strings that
+/// aren't UTF-8 and map keys that aren't strings, integers or booleans.
+#[derive(Debug, Clone, Default)]
+pub struct MsgPackCodec;
+
+impl MsgPackCodec {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Codec for MsgPackCodec {
+    fn format(&self) -> Format {
+        Format::MsgPack
+    }
+
+    fn decode(&self, record: Record) -> Result<Document, CodecError> {
+        if record.media_type.format() != Format::MsgPack {
+            return Err(CodecError::UnsupportedMediaType(record.media_type));
+        }
+
+        let msgpack =
+            rmpv::decode::read_value(&mut record.content.as_slice()).map_err(CodecError::decode)?;
+        let value = Value::try_from(msgpack).map_err(CodecError::decode)?;
+
+        let entity = Entity::new(
+            IdentPath::parse("root").expect("valid field path"),
+            record.media_type.as_mime_str(),
+            value,
+        );
+
+        Ok(Document::new(record.path, record.media_type, vec![entity])
+            .with_attributes(record.attributes))
+    }
+
+    fn encode(&self, document: Document) -> Result<Record, CodecError> {
+        if document.media_type.format() != Format::MsgPack {
+            return Err(CodecError::UnsupportedMediaType(document.media_type));
+        }
+
+        let content = document
+            .content
+            .first()
+            .ok_or_else(|| CodecError::Encode("document has no content".to_string()))?;
+
+        let msgpack: rmpv::Value = (&content.content).into();
+        let mut bytes = Vec::new();
+        rmpv::encode::write_value(&mut bytes, &msgpack).map_err(CodecError::encode)?;
+
+        Ok(Record::new(document.path, document.media_type, bytes)
+            .with_attributes(document.attributes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MediaType;
+    use crate::path::FilePath;
+    use crate::path::Path;
+    use crate::value::{Array, Number, Object};
+
+    fn document(value: Value) -> Document {
+        let path = Path::File(FilePath::parse("/test.msgpack"));
+        let entity = Entity::new(
+            IdentPath::parse("root").unwrap(),
+            "application/msgpack",
+            value,
+        );
+
+        Document::new(path, MediaType::MsgPack, vec![entity])
+    }
+
+    #[test]
+    fn test_roundtrip_nested() {
+        let codec = MsgPackCodec::new();
+
+        let mut label = Object::new();
+        label.insert("name".to_string(), Value::String("task".to_string()));
+        label.insert("score".to_string(), Value::Number(Number::Float(0.875)));
+
+        let mut obj = Object::new();
+        obj.insert("id".to_string(), Value::Number(Number::Int(-42)));
+        obj.insert("accepted".to_string(), Value::Bool(true));
+        obj.insert("notes".to_string(), Value::Null);
+        obj.insert(
+            "labels".to_string(),
+            Value::Array(Array::from(vec![
+                Value::Object(label),
+                Value::Array(Array::from(vec![1, 2, 3])),
+            ])),
+        );
+
+        let original = Value::Object(obj);
+        let record = codec.encode(document(original.clone())).unwrap();
+
+        assert_eq!(record.media_type, MediaType::MsgPack);
+
+        let decoded = codec.decode(record).unwrap();
+
+        assert_eq!(decoded.content[0].content, original);
+        assert_eq!(
+            decoded.content[0].content["labels"][0]["name"].as_str(),
+            Some("task")
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    fn test_encode_is_smaller_than_json() {
+        let codec = MsgPackCodec::new();
+
+        let mut obj = Object::new();
+        obj.insert("threshold".to_string(), Value::Number(Number::Float(0.75)));
+        obj.insert("top_k".to_string(), Value::Number(Number::Int(2)));
+
+        let value = Value::Object(obj);
+        let json = serde_json::to_vec(&serde_json::Value::from(&value)).unwrap();
+        let record = codec.encode(document(value)).unwrap();
+
+        assert!(record.content.len() < json.len());
+    }
+
+    fn decode_raw(msgpack: rmpv::Value) -> Result<Document, CodecError> {
+        let mut bytes = Vec::new();
+        rmpv::encode::write_value(&mut bytes, &msgpack).unwrap();
+
+        decode_bytes(bytes)
+    }
+
+    fn decode_bytes(bytes: Vec<u8>) -> Result<Document, CodecError> {
+        let path = Path::File(FilePath::parse("/test.msgpack"));
+        MsgPackCodec::new().decode(Record::new(path, MediaType::MsgPack, bytes))
+    }
+
+    #[test]
+    fn test_decode_rejects_lossy_values() {
+        let lossy = [
+            rmpv::Value::from(u64::MAX),
+            rmpv::Value::Binary(vec![1, 2]),
+            rmpv::Value::Ext(1, vec![0]),
+            rmpv::Value::Map(vec![(
+                rmpv::Value::Array(vec![rmpv::Value::from(1)]),
+                rmpv::Value::Nil,
+            )]),
+            rmpv::Value::Array(vec![rmpv::Value::Binary(vec![1])]),
+        ];
+
+        for value in lossy {
+            assert!(decode_raw(value).unwrap_err().is_decode());
+        }
+
+        // A two byte fixstr that isn't UTF-8
+        assert!(
+            decode_bytes(vec![0xa2, 0xff, 0xfe])
+                .unwrap_err()
+                .is_decode()
+        );
+    }
+
+    #[test]
+    fn test_decode_scalar_keys() {
+        let map = rmpv::Value::Map(vec![
+            (rmpv::Value::from(1), rmpv::Value::from(i64::MAX as u64)),
+            (rmpv::Value::from(true), rmpv::Value::from("yes")),
+        ]);
+        let document = decode_raw(map).unwrap();
+
+        assert_eq!(document.content[0].content["1"].as_int(), Some(i64::MAX));
+        assert_eq!(document.content[0].content["true"].as_str(), Some("yes"));
+    }
+
+    #[test]
+    fn test_decode_invalid() {
+        let codec = MsgPackCodec::new();
+        let path = Path::File(FilePath::parse("/test.msgpack"));
+        let record = Record::new(path, MediaType::MsgPack, vec![0xc1]);
+
+        assert!(codec.decode(record).unwrap_err().is_decode());
+    }
+
+    #[test]
+    fn test_unsupported_media_type() {
+        let codec = MsgPackCodec::new();
+        let path = Path::File(FilePath::parse("/test.json"));
+        let record = Record::from_str(path, MediaType::TextJson, "{}");
+
+        assert!(codec.decode(record).unwrap_err().is_unsupported());
+    }
+}
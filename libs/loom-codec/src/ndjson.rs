@@ -0,0 +1,314 @@
+use std::io::{BufRead, Write};
+
+use crate::path::{IdentPath, Path};
+use crate::value::Value;
+use crate::{Document, Entity, Format, MediaType, Record};
+
+use super::{Codec, CodecError, PartialDocument, Position};
+
+/// Codec for newline-delimited JSON (JSON Lines).
+///
+/// `decode` parses the whole record into a document with one entity per line.
+/// For files too large to hold in memory, [`NdjsonCodec::stream`] yields one
+/// record per line lazily instead.
+#[derive(Debug, Clone, Default)]
+pub struct NdjsonCodec;
+
+impl NdjsonCodec {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Lazily split `reader` into one JSON record per non-empty line.
+    pub fn stream<R: BufRead>(&self, path: Path, reader: R) -> NdjsonStream<R> {
+        NdjsonStream {
+            path,
+            lines: reader.lines(),
+        }
+    }
+
+    /// Lazily decode `reader` into one value per non-empty line.
+    pub fn decode_stream<R: BufRead>(
+        &self,
+        path: Path,
+        reader: R,
+    ) -> impl Iterator<Item = Result<Value, CodecError>> {
+        self.stream(path, reader)
+            .map(|record| record.and_then(|r| decode_line(&r.content)))
+    }
+}
+
+impl Codec for NdjsonCodec {
+    fn format(&self) -> Format {
+        Format::Ndjson
+    }
+
+    fn decode(&self, record: Record) -> Result<Document, CodecError> {
+        if record.media_type.format() != Format::Ndjson {
+            return Err(CodecError::UnsupportedMediaType(record.media_type));
+        }
+
+        let text = String::from_utf8(record.content)?;
+        let (entities, mut errors) = decode_lines(&text);
+
+        if !errors.is_empty() {
+            return Err(errors.remove(0));
+        }
+
+        Ok(Document::new(record.path, record.media_type, entities)
+            .with_attributes(record.attributes))
+    }
+
+    /// Skips lines that fail to parse, reporting each with its line number.
+    fn decode_lenient(&self, record: Record) -> Result<PartialDocument, CodecError> {
+        if record.media_type.format() != Format::Ndjson {
+            return Err(CodecError::UnsupportedMediaType(record.media_type));
+        }
+
+        let text = match String::from_utf8(record.content) {
+            Ok(text) => text,
+            Err(e) => {
+                return Ok(PartialDocument::failed(
+                    record.path,
+                    record.media_type,
+                    e.into(),
+                ));
+            }
+        };
+
+        let (entities, errors) = decode_lines(&text);
+        let document = Document::new(record.path, record.media_type, entities)
+            .with_attributes(record.attributes);
+
+        Ok(PartialDocument::new(document, errors))
+    }
+
+    fn encode(&self, document: Document) -> Result<Record, CodecError> {
+        if document.media_type.format() != Format::Ndjson {
+            return Err(CodecError::UnsupportedMediaType(document.media_type));
+        }
+
+        let mut text = String::new();
+
+        for entity in &document.content {
+            let json: serde_json::Value = (&entity.content).into();
+            text.push_str(&serde_json::to_string(&json).map_err(CodecError::encode)?);
+            text.push('\n');
+        }
+
+        Ok(Record::from_str(document.path, document.media_type, &text)
+            .with_attributes(document.attributes))
+    }
+
+    fn encode_stream(
+        &self,
+        documents: &mut dyn Iterator<Item = Document>,
+        out: &mut dyn Write,
+    ) -> Result<usize, CodecError> {
+        let mut count = 0;
+
+        for document in documents {
+            if document.media_type.format() != Format::Ndjson {
+                return Err(CodecError::UnsupportedMediaType(document.media_type));
+            }
+
+            for entity in &document.content {
+                let json: serde_json::Value = (&entity.content).into();
+                serde_json::to_writer(&mut *out, &json).map_err(CodecError::encode)?;
+                out.write_all(b"\n").map_err(CodecError::encode)?;
+            }
+
+            count += 1;
+        }
+
+        Ok(count)
+    }
+}
+
+/// Iterator over the lines of a JSON Lines reader, one [`Record`] per line.
+pub struct NdjsonStream<R> {
+    path: Path,
+    lines: std::io::Lines<R>,
+}
+
+impl<R: BufRead> Iterator for NdjsonStream<R> {
+    type Item = Result<Record, CodecError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let line = match self.lines.next()? {
+                Ok(line) => line,
+                Err(e) => return Some(Err(CodecError::decode(e))),
+            };
+
+            if !line.trim().is_empty() {
+                return Some(Ok(Record::from_str(
+                    self.path.clone(),
+                    MediaType::TextJson,
+                    &line,
+                )));
+            }
+        }
+    }
+}
+
+/// One entity per non-empty line that parses, and an error per line that
+/// doesn't.
+fn decode_lines(text: &str) -> (Vec<Entity>, Vec<CodecError>) {
+    let mut entities = Vec::new();
+    let mut errors = Vec::new();
+
+    for (i, line) in text.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        match decode_line(line.as_bytes()) {
+            Ok(value) => {
+                let path = IdentPath::parse(&format!("root[{}]", entities.len()))
+                    .expect("valid field path");
+
+                entities.push(Entity::new(path, MediaType::TextJson.as_mime_str(), value));
+            }
+            Err(CodecError::Syntax { position, message }) => {
+                errors.push(CodecError::syntax(
+                    Position::new(i + 1, position.column),
+                    message,
+                ));
+            }
+            Err(e) => errors.push(e),
+        }
+    }
+
+    (entities, errors)
+}
+
+fn decode_line(line: &[u8]) -> Result<Value, CodecError> {
+    let json: serde_json::Value =
+        serde_json::from_slice(line).map_err(crate::json::syntax_error)?;
+    Ok(json.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::path::FilePath;
+
+    const LINES: &str = "{\"id\":1,\"text\":\"a\"}\n\n{\"id\":2,\"text\":\"b\"}\n";
+
+    fn path() -> Path {
+        Path::File(FilePath::parse("/test.jsonl"))
+    }
+
+    #[test]
+    fn test_decode_ndjson() {
+        let codec = NdjsonCodec::new();
+        let record = Record::from_str(path(), MediaType::TextNdjson, LINES);
+
+        let document = codec.decode(record).unwrap();
+
+        assert_eq!(document.content.len(), 2);
+        assert_eq!(document.content[0].path.to_string(), "root[0]");
+        assert_eq!(document.content[1].content["id"].as_int(), Some(2));
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let codec = NdjsonCodec::new();
+        let record = Record::from_str(path(), MediaType::TextNdjson, LINES);
+
+        let document = codec.decode(record).unwrap();
+        let encoded = codec.encode(document).unwrap();
+        let text = encoded.content_str().unwrap();
+
+        assert_eq!(text.lines().count(), 2);
+        assert!(text.ends_with('\n'));
+    }
+
+    #[test]
+    fn test_encode_stream() {
+        let codec = NdjsonCodec::new();
+        let mut documents = LINES.lines().filter(|line| !line.is_empty()).map(|line| {
+            let record = Record::from_str(path(), MediaType::TextNdjson, line);
+            codec.decode(record).unwrap()
+        });
+        let mut out = Vec::new();
+
+        let count = codec.encode_stream(&mut documents, &mut out).unwrap();
+        let text = String::from_utf8(out).unwrap();
+
+        assert_eq!(count, 2);
+        assert_eq!(
+            text,
+            "{\"id\":1,\"text\":\"a\"}\n{\"id\":2,\"text\":\"b\"}\n"
+        );
+    }
+
+    #[test]
+    fn test_stream_skips_blank_lines() {
+        let codec = NdjsonCodec::new();
+        let records: Vec<Record> = codec
+            .stream(path(), LINES.as_bytes())
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[1].media_type, MediaType::TextJson);
+        assert_eq!(
+            records[1].content_str().unwrap(),
+            "{\"id\":2,\"text\":\"b\"}"
+        );
+    }
+
+    #[test]
+    fn test_decode_stream_reports_invalid_lines() {
+        let codec = NdjsonCodec::new();
+        let values: Vec<_> = codec
+            .decode_stream(path(), "{\"id\":1}\nnot json\n".as_bytes())
+            .collect();
+
+        assert_eq!(values.len(), 2);
+        assert!(values[0].is_ok());
+        assert!(values[1].as_ref().unwrap_err().is_decode());
+    }
+
+    #[test]
+    fn test_decode_reports_line() {
+        let codec = NdjsonCodec::new();
+        let record = Record::from_str(path(), MediaType::TextNdjson, "{\"id\":1}\n\n{\"id\":}\n");
+
+        let err = codec.decode(record).unwrap_err();
+
+        assert_eq!(err.position().map(|p| p.line), Some(3));
+    }
+
+    #[test]
+    fn test_decode_lenient_skips_invalid_lines() {
+        let codec = NdjsonCodec::new();
+        let record = Record::from_str(
+            path(),
+            MediaType::TextNdjson,
+            "{\"id\":1}\nnot json\n{\"id\":3}\n{\"id\":\n",
+        );
+
+        let partial = codec.decode_lenient(record).unwrap();
+        let lines: Vec<_> = partial
+            .errors
+            .iter()
+            .map(|e| e.position().map(|p| p.line))
+            .collect();
+
+        assert_eq!(partial.document.content.len(), 2);
+        assert_eq!(partial.document.content[1].path.to_string(), "root[1]");
+        assert_eq!(partial.document.content[1].content["id"].as_int(), Some(3));
+        assert_eq!(lines, vec![Some(2), Some(4)]);
+    }
+
+    #[test]
+    fn test_unsupported_media_type() {
+        let codec = NdjsonCodec::new();
+        let record = Record::from_str(path(), MediaType::TextJson, LINES);
+
+        assert!(codec.decode(record).unwrap_err().is_unsupported());
+    }
+}
@@ -0,0 +1,183 @@
+use serde::Serialize;
+
+use crate::Format;
+
+use super::CodecError;
+
+/// Output formatting used when encoding.
+///
+/// The default is compact output, written as the encoder produces it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EncodeOptions {
+    /// Spaces per indentation level, `None` for compact output.
+    /// TOML only distinguishes compact from pretty output, and YAML is
+    /// always written in block style.
+    pub indent: Option<usize>,
+
+    /// Sort object keys (JSON and TOML) instead of keeping the order of the
+    /// encoded value. `Value` objects are always key-ordered.
+    pub sort_keys: bool,
+
+    /// Ensure the output ends with exactly one newline
+    pub trailing_newline: bool,
+
+    /// When rewriting a record (see [`Codec::rewrite`](crate::Codec::rewrite)),
+    /// keep its key order and formatting and only change the values that
+    /// differ. TOML also keeps comments; YAML keeps key order only.
+    pub lossless: bool,
+}
+
+impl EncodeOptions {
+    pub fn compact() -> Self {
+        Self::default()
+    }
+
+    /// Two-space indentation.
+    pub fn pretty() -> Self {
+        Self::default().with_indent(2)
+    }
+
+    pub fn with_indent(mut self, indent: usize) -> Self {
+        self.indent = Some(indent);
+        self
+    }
+
+    pub fn with_sort_keys(mut self, sort_keys: bool) -> Self {
+        self.sort_keys = sort_keys;
+        self
+    }
+
+    pub fn with_trailing_newline(mut self, trailing_newline: bool) -> Self {
+        self.trailing_newline = trailing_newline;
+        self
+    }
+
+    pub fn with_lossless(mut self, lossless: bool) -> Self {
+        self.lossless = lossless;
+        self
+    }
+
+    pub fn is_pretty(&self) -> bool {
+        self.indent.is_some()
+    }
+
+    /// Serialize `value` as `format` with these options.
+    pub fn encode<T: Serialize>(&self, value: &T, format: Format) -> Result<String, CodecError> {
+        let text = match format {
+            #[cfg(feature = "json")]
+            Format::Json => self.json(value)?,
+            #[cfg(feature = "yaml")]
+            Format::Yaml => serde_saphyr::to_string(value).map_err(CodecError::encode)?,
+            #[cfg(feature = "toml")]
+            Format::Toml => self.toml(value)?,
+            other => return Err(CodecError::UnsupportedMediaType(other.media_type())),
+        };
+
+        Ok(self.finish(text))
+    }
+
+    /// Apply the trailing newline option to encoded output.
+    pub fn finish(&self, mut text: String) -> String {
+        if self.trailing_newline {
+            text.truncate(text.trim_end_matches('\n').len());
+            text.push('\n');
+        }
+
+        text
+    }
+
+    #[cfg(feature = "json")]
+    fn json<T: Serialize>(&self, value: &T) -> Result<String, CodecError> {
+        if self.sort_keys {
+            // serde_json maps are key-ordered
+            let value = serde_json::to_value(value).map_err(CodecError::encode)?;
+            return self.json_unsorted(&value);
+        }
+
+        self.json_unsorted(value)
+    }
+
+    #[cfg(feature = "json")]
+    fn json_unsorted<T: Serialize>(&self, value: &T) -> Result<String, CodecError> {
+        let Some(indent) = self.indent else {
+            return serde_json::to_string(value).map_err(CodecError::encode);
+        };
+
+        let indent = " ".repeat(indent);
+        let formatter = serde_json::ser::PrettyFormatter::with_indent(indent.as_bytes());
+        let mut out = Vec::new();
+        let mut serializer = serde_json::Serializer::with_formatter(&mut out, formatter);
+        value
+            .serialize(&mut serializer)
+            .map_err(CodecError::encode)?;
+
+        Ok(String::from_utf8(out)?)
+    }
+
+    #[cfg(feature = "toml")]
+    fn toml<T: Serialize>(&self, value: &T) -> Result<String, CodecError> {
+        if self.sort_keys {
+            // toml tables are key-ordered
+            let value = toml::Value::try_from(value).map_err(CodecError::encode)?;
+            return self.toml_unsorted(&value);
+        }
+
+        self.toml_unsorted(value)
+    }
+
+    #[cfg(feature = "toml")]
+    fn toml_unsorted<T: Serialize>(&self, value: &T) -> Result<String, CodecError> {
+        if self.is_pretty() {
+            toml::to_string_pretty(value)
+        } else {
+            toml::to_string(value)
+        }
+        .map_err(CodecError::encode)
+    }
+}
+
+#[cfg(all(test, feature = "json"))]
+mod tests {
+    use super::*;
+
+    #[derive(Serialize)]
+    struct Export {
+        name: &'static str,
+        count: usize,
+    }
+
+    const EXPORT: Export = Export {
+        name: "test",
+        count: 2,
+    };
+
+    #[test]
+    fn test_compact_by_default() {
+        let text = EncodeOptions::default()
+            .encode(&EXPORT, Format::Json)
+            .unwrap();
+
+        assert_eq!(text, r#"{"name":"test","count":2}"#);
+    }
+
+    #[test]
+    fn test_indent_sort_and_newline() {
+        let options = EncodeOptions::default()
+            .with_indent(4)
+            .with_sort_keys(true)
+            .with_trailing_newline(true);
+
+        let text = options.encode(&EXPORT, Format::Json).unwrap();
+
+        assert_eq!(text, "{\n    \"count\": 2,\n    \"name\": \"test\"\n}\n");
+    }
+
+    #[test]
+    fn test_unsupported_format() {
+        let err = EncodeOptions::default()
+            .encode(&EXPORT, Format::Csv)
+            .unwrap_err();
+
+        assert!(err.is_unsupported());
+    }
+}
@@ -0,0 +1,380 @@
+use std::sync::Arc;
+
+use bytes::Bytes;
+use parquet::basic::{ConvertedType, Repetition, Type as PhysicalType};
+use parquet::data_type::{BoolType, ByteArray, ByteArrayType, DoubleType, Int64Type};
+use parquet::file::properties::WriterProperties;
+use parquet::file::reader::{FileReader, SerializedFileReader};
+use parquet::file::writer::SerializedFileWriter;
+use parquet::record::{Field, Row};
+use parquet::schema::types::Type;
+
+use crate::columns::{Column, infer_columns};
+use crate::path::IdentPath;
+use crate::value::{Array, Number, Object, Value};
+use crate::{Document, Entity, Format, Record};
+
+use super::{Codec, CodecError};
+
+/// Codec for Apache Parquet files.
+///
+/// Each row decodes to a `Value::Object` entity at `root[i]`. Encoding writes
+/// one optional column per object key: booleans, integers, floats and strings
+/// map to their Parquet types. Nested arrays/objects have no column type and
+/// are rejected; flatten them or serialize them to strings first.
+#[derive(Debug, Clone, Default)]
+pub struct ParquetCodec;
+
+impl ParquetCodec {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Decode a Parquet file into its rows.
+    pub fn decode_rows(&self, bytes: Vec<u8>) -> Result<Vec<Object>, CodecError> {
+        let reader = SerializedFileReader::new(Bytes::from(bytes)).map_err(CodecError::decode)?;
+        let rows = reader.get_row_iter(None).map_err(CodecError::decode)?;
+
+        rows.map(|row| row.map(|row| row_object(&row)).map_err(CodecError::decode))
+            .collect()
+    }
+
+    /// Encode rows into a Parquet file with a single row group, or an empty
+    /// file with no columns when there are no rows.
+    pub fn encode_rows(&self, rows: &[Object]) -> Result<Vec<u8>, CodecError> {
+        let nested = rows
+            .iter()
+            .flat_map(|row| row.iter())
+            .find(|(_, value)| matches!(value, Value::Array(_) | Value::Object(_)));
+
+        if let Some((name, value)) = nested {
+            return Err(CodecError::Encode(format!(
+                "column '{}' holds a nested {}, only scalar columns can be written",
+                name,
+                value.kind()
+            )));
+        }
+
+        let columns = infer_columns(rows);
+
+        if columns.is_empty() && !rows.is_empty() {
+            return Err(CodecError::Encode("rows have no columns".to_string()));
+        }
+
+        let fields = columns
+            .iter()
+            .map(|(name, column)| {
+                let (physical, converted) = match column {
+                    Column::Bool => (PhysicalType::BOOLEAN, ConvertedType::NONE),
+                    Column::Int => (PhysicalType::INT64, ConvertedType::NONE),
+                    Column::Float => (PhysicalType::DOUBLE, ConvertedType::NONE),
+                    Column::Text => (PhysicalType::BYTE_ARRAY, ConvertedType::UTF8),
+                };
+
+                Type::primitive_type_builder(name, physical)
+                    .with_repetition(Repetition::OPTIONAL)
+                    .with_converted_type(converted)
+                    .build()
+                    .map(Arc::new)
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(CodecError::encode)?;
+
+        let schema = Type::group_type_builder("schema")
+            .with_fields(fields)
+            .build()
+            .map_err(CodecError::encode)?;
+
+        let mut bytes = Vec::new();
+        let properties = Arc::new(WriterProperties::builder().build());
+        let mut writer = SerializedFileWriter::new(&mut bytes, Arc::new(schema), properties)
+            .map_err(CodecError::encode)?;
+
+        if rows.is_empty() {
+            writer.close().map_err(CodecError::encode)?;
+            return Ok(bytes);
+        }
+
+        let mut row_group = writer.next_row_group().map_err(CodecError::encode)?;
+
+        for (name, column) in &columns {
+            let values: Vec<&Value> = rows
+                .iter()
+                .filter_map(|row| row.get(name).filter(|v| !v.is_null()))
+                .collect();
+            let def_levels: Vec<i16> = rows
+                .iter()
+                .map(|row| row.get(name).is_some_and(|v| !v.is_null()) as i16)
+                .collect();
+
+            let mut column_writer = row_group
+                .next_column()
+                .map_err(CodecError::encode)?
+                .ok_or_else(|| CodecError::Encode(format!("no writer for column '{}'", name)))?;
+
+            match column {
+                Column::Bool => {
+                    let values: Vec<bool> = values.iter().filter_map(|v| v.as_bool()).collect();
+                    column_writer
+                        .typed::<BoolType>()
+                        .write_batch(&values, Some(&def_levels), None)
+                }
+                Column::Int => {
+                    let values: Vec<i64> = values.iter().filter_map(|v| v.as_int()).collect();
+                    column_writer
+                        .typed::<Int64Type>()
+                        .write_batch(&values, Some(&def_levels), None)
+                }
+                Column::Float => {
+                    let values: Vec<f64> = values.iter().filter_map(|v| v.as_float()).collect();
+                    column_writer.typed::<DoubleType>().write_batch(
+                        &values,
+                        Some(&def_levels),
+                        None,
+                    )
+                }
+                Column::Text => {
+                    let values: Vec<ByteArray> = values
+                        .iter()
+                        .map(|v| match v {
+                            Value::String(s) => ByteArray::from(s.as_str()),
+                            // Scalars of a mixed-type column
+                            v => ByteArray::from(serde_json::Value::from(*v).to_string().as_str()),
+                        })
+                        .collect();
+                    column_writer.typed::<ByteArrayType>().write_batch(
+                        &values,
+                        Some(&def_levels),
+                        None,
+                    )
+                }
+            }
+            .map_err(CodecError::encode)?;
+
+            column_writer.close().map_err(CodecError::encode)?;
+        }
+
+        row_group.close().map_err(CodecError::encode)?;
+        writer.close().map_err(CodecError::encode)?;
+        Ok(bytes)
+    }
+}
+
+impl Codec for ParquetCodec {
+    fn format(&self) -> Format {
+        Format::Parquet
+    }
+
+    fn decode(&self, record: Record) -> Result<Document, CodecError> {
+        if record.media_type.format() != Format::Parquet {
+            return Err(CodecError::UnsupportedMediaType(record.media_type));
+        }
+
+        let entities = self
+            .decode_rows(record.content)?
+            .into_iter()
+            .enumerate()
+            .map(|(i, row)| {
+                Entity::new(
+                    IdentPath::parse(&format!("root[{}]", i)).expect("valid field path"),
+                    record.media_type.as_mime_str(),
+                    Value::Object(row),
+                )
+            })
+            .collect();
+
+        Ok(Document::new(record.path, record.media_type, entities)
+            .with_attributes(record.attributes))
+    }
+
+    /// Encode a document of row entities. An entity holding an array of
+    /// objects (e.g. a serialized `Vec<Object>`) contributes each element as a row.
+    fn encode(&self, document: Document) -> Result<Record, CodecError> {
+        if document.media_type.format() != Format::Parquet {
+            return Err(CodecError::UnsupportedMediaType(document.media_type));
+        }
+
+        let mut rows = Vec::new();
+
+        for entity in &document.content {
+            match &entity.content {
+                Value::Object(row) => rows.push(row.clone()),
+                Value::Array(items) => {
+                    for item in items.iter() {
+                        let row = item.as_object().ok_or_else(|| {
+                            CodecError::Encode(format!(
+                                "expected object row, found {}",
+                                item.kind()
+                            ))
+                        })?;
+                        rows.push(row.clone());
+                    }
+                }
+                other => {
+                    return Err(CodecError::Encode(format!(
+                        "expected object row, found {}",
+                        other.kind()
+                    )));
+                }
+            }
+        }
+
+        let bytes = self.encode_rows(&rows)?;
+        Ok(Record::new(document.path, document.media_type, bytes)
+            .with_attributes(document.attributes))
+    }
+}
+
+fn row_object(row: &Row) -> Object {
+    let mut object = Object::new();
+
+    for (name, field) in row.get_column_iter() {
+        object.insert(name.clone(), field_value(field));
+    }
+
+    object
+}
+
+fn field_value(field: &Field) -> Value {
+    match field {
+        Field::Null => Value::Null,
+        Field::Bool(b) => Value::Bool(*b),
+        Field::Byte(v) => Value::Number(Number::Int(*v as i64)),
+        Field::Short(v) => Value::Number(Number::Int(*v as i64)),
+        Field::Int(v) => Value::Number(Number::Int(*v as i64)),
+        Field::Long(v) => Value::Number(Number::Int(*v)),
+        Field::UByte(v) => Value::Number(Number::Int(*v as i64)),
+        Field::UShort(v) => Value::Number(Number::Int(*v as i64)),
+        Field::UInt(v) => Value::Number(Number::Int(*v as i64)),
+        Field::ULong(v) => match i64::try_from(*v) {
+            Ok(v) => Value::Number(Number::Int(v)),
+            Err(_) => Value::Number(Number::Float(*v as f64)),
+        },
+        Field::Float(v) => Value::Number(Number::Float(*v as f64)),
+        Field::Double(v) => Value::Number(Number::Float(*v)),
+        Field::Str(s) => Value::String(s.clone()),
+        Field::Bytes(bytes) => match bytes.as_utf8() {
+            Ok(s) => Value::String(s.to_string()),
+            Err(_) => Value::Array(Array::from(bytes.data().to_vec())),
+        },
+        Field::Group(row) => Value::Object(row_object(row)),
+        Field::ListInternal(list) => Value::Array(Array::from(
+            list.elements().iter().map(field_value).collect::<Vec<_>>(),
+        )),
+        Field::MapInternal(map) => {
+            let mut object = Object::new();
+
+            for (key, value) in map.entries() {
+                let key = match key {
+                    Field::Str(s) => s.clone(),
+                    key => key.to_string(),
+                };
+                object.insert(key, field_value(value));
+            }
+
+            Value::Object(object)
+        }
+        // Dates, timestamps, decimals and half floats keep their display form
+        field => Value::String(field.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MediaType;
+    use crate::path::{FilePath, Path};
+
+    fn row(id: i64, text: &str, weight: Option<f64>) -> Object {
+        let mut row = Object::new();
+        row.insert("id".to_string(), Value::Number(Number::Int(id)));
+        row.insert("text".to_string(), Value::String(text.to_string()));
+        row.insert(
+            "weight".to_string(),
+            weight.map_or(Value::Null, |w| Value::Number(Number::Float(w))),
+        );
+        row
+    }
+
+    #[test]
+    fn test_roundtrip_rows() {
+        let codec = ParquetCodec::new();
+        let rows = vec![row(1, "a", Some(0.5)), row(2, "b", None)];
+
+        let bytes = codec.encode_rows(&rows).unwrap();
+        let decoded = codec.decode_rows(bytes).unwrap();
+
+        assert_eq!(decoded.len(), 2);
+        assert_eq!(decoded[0]["id"].as_int(), Some(1));
+        assert_eq!(decoded[0]["text"].as_str(), Some("a"));
+        assert_eq!(decoded[0]["weight"].as_float(), Some(0.5));
+        assert!(decoded[1]["weight"].is_null());
+    }
+
+    #[test]
+    fn test_roundtrip_no_rows() {
+        let codec = ParquetCodec::new();
+        let bytes = codec.encode_rows(&[]).unwrap();
+
+        assert!(codec.decode_rows(bytes).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_mixed_column_as_text() {
+        let codec = ParquetCodec::new();
+        let mut flag = Object::new();
+        flag.insert("x".to_string(), Value::Bool(true));
+        let mut count = Object::new();
+        count.insert("x".to_string(), Value::Number(Number::Int(2)));
+
+        let bytes = codec.encode_rows(&[flag, count]).unwrap();
+        let decoded = codec.decode_rows(bytes).unwrap();
+
+        assert_eq!(decoded[0]["x"].as_str(), Some("true"));
+        assert_eq!(decoded[1]["x"].as_str(), Some("2"));
+    }
+
+    #[test]
+    fn test_nested_values_rejected() {
+        let codec = ParquetCodec::new();
+        let mut labels = row(1, "a", None);
+        labels.insert(
+            "labels".to_string(),
+            Value::Array(Array::from(vec!["task", "time"])),
+        );
+
+        let err = codec.encode_rows(&[labels]).unwrap_err();
+        assert!(err.to_string().contains("'labels'"));
+    }
+
+    #[test]
+    fn test_codec_roundtrip() {
+        let codec = ParquetCodec::new();
+        let path = Path::File(FilePath::parse("/test.parquet"));
+        let entity = Entity::new(
+            IdentPath::parse("root").unwrap(),
+            MediaType::Parquet.as_mime_str(),
+            Value::Array(Array::from(vec![
+                Value::Object(row(1, "a", Some(1.0))),
+                Value::Object(row(2, "b", Some(2.0))),
+            ])),
+        );
+        let document = Document::new(path, MediaType::Parquet, vec![entity]);
+
+        let record = codec.encode(document).unwrap();
+        let decoded = codec.decode(record).unwrap();
+
+        assert_eq!(decoded.content.len(), 2);
+        assert_eq!(decoded.content[1].path.to_string(), "root[1]");
+        assert_eq!(decoded.content[1].content["text"].as_str(), Some("b"));
+    }
+
+    #[test]
+    fn test_unsupported_media_type() {
+        let codec = ParquetCodec::new();
+        let path = Path::File(FilePath::parse("/test.json"));
+        let record = Record::from_str(path, MediaType::TextJson, "{}");
+
+        assert!(codec.decode(record).unwrap_err().is_unsupported());
+    }
+}
@@ -0,0 +1,44 @@
+use crate::{Document, MediaType, path::Path};
+
+use super::CodecError;
+
+/// Result of [`Codec::decode_lenient`](crate::Codec::decode_lenient): the
+/// entities that decoded, plus an error for each part of the record that
+/// didn't.
+#[derive(Debug)]
+pub struct PartialDocument {
+    pub document: Document,
+    pub errors: Vec<CodecError>,
+}
+
+impl PartialDocument {
+    pub fn new(document: Document, errors: Vec<CodecError>) -> Self {
+        Self { document, errors }
+    }
+
+    /// Nothing decoded: an empty document and the error that stopped it
+    pub fn failed(path: Path, media_type: MediaType, error: CodecError) -> Self {
+        Self {
+            document: Document::new(path, media_type, vec![]),
+            errors: vec![error],
+        }
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// The document if everything decoded, otherwise the first error
+    pub fn into_result(self) -> Result<Document, CodecError> {
+        match self.errors.into_iter().next() {
+            Some(error) => Err(error),
+            None => Ok(self.document),
+        }
+    }
+}
+
+impl From<Document> for PartialDocument {
+    fn from(document: Document) -> Self {
+        Self::new(document, vec![])
+    }
+}
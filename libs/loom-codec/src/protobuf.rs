@@ -0,0 +1,534 @@
+use std::collections::HashMap;
+use std::io::Write;
+
+use prost::Message;
+use prost_reflect::{
+    DescriptorPool, DynamicMessage, FieldDescriptor, Kind, MapKey, MessageDescriptor,
+};
+
+use crate::path::IdentPath;
+use crate::value::{Array, Number, Object, Value};
+use crate::{Document, Entity, Format, Record};
+
+use super::{Codec, CodecError};
+
+/// Codec for Protocol Buffers, decoded dynamically against a message
+/// descriptor instead of generated types.
+///
+/// Fields map to object keys by name; enums decode to their value names and
+/// `bytes` to arrays of integers. Fields without presence are always present,
+/// holding their default value when unset. A record holds a single message,
+/// or with [`ProtobufCodec::with_delimited`] a stream of length-delimited
+/// messages, each decoding to its own entity at `root[i]`.
+#[derive(Debug, Clone)]
+pub struct ProtobufCodec {
+    message: MessageDescriptor,
+    delimited: bool,
+}
+
+impl ProtobufCodec {
+    /// Codec for `message` (fully qualified, e.g. `events.ScoreEvent`) from
+    /// an encoded `FileDescriptorSet`, as written by `protoc --descriptor_set_out`.
+    pub fn new(descriptor_set: &[u8], message: &str) -> Result<Self, CodecError> {
+        let pool = DescriptorPool::decode(descriptor_set).map_err(CodecError::decode)?;
+        let message = pool.get_message_by_name(message).ok_or_else(|| {
+            CodecError::Decode(format!("message '{}' not found in descriptor set", message))
+        })?;
+
+        Ok(Self::from_descriptor(message))
+    }
+
+    pub fn from_descriptor(message: MessageDescriptor) -> Self {
+        Self {
+            message,
+            delimited: false,
+        }
+    }
+
+    /// Read and write records as streams of length-delimited messages
+    pub fn with_delimited(mut self, delimited: bool) -> Self {
+        self.delimited = delimited;
+        self
+    }
+
+    /// Descriptor of the message this codec decodes
+    pub fn descriptor(&self) -> &MessageDescriptor {
+        &self.message
+    }
+
+    fn entity(&self, record: &Record, index: Option<usize>, message: &DynamicMessage) -> Entity {
+        let path = match index {
+            Some(i) => format!("root[{}]", i),
+            None => "root".to_string(),
+        };
+
+        Entity::new(
+            IdentPath::parse(&path).expect("valid field path"),
+            record.media_type.as_mime_str(),
+            message_to_value(message),
+        )
+    }
+}
+
+impl Codec for ProtobufCodec {
+    fn format(&self) -> Format {
+        Format::Protobuf
+    }
+
+    fn decode(&self, record: Record) -> Result<Document, CodecError> {
+        if record.media_type.format() != Format::Protobuf {
+            return Err(CodecError::UnsupportedMediaType(record.media_type));
+        }
+
+        let mut entities = Vec::new();
+
+        if self.delimited {
+            let mut buf = record.content.as_slice();
+
+            while !buf.is_empty() {
+                let mut message = DynamicMessage::new(self.message.clone());
+                message
+                    .merge_length_delimited(&mut buf)
+                    .map_err(CodecError::decode)?;
+                entities.push(self.entity(&record, Some(entities.len()), &message));
+            }
+        } else {
+            let message = DynamicMessage::decode(self.message.clone(), record.content.as_slice())
+                .map_err(CodecError::decode)?;
+            entities.push(self.entity(&record, None, &message));
+        }
+
+        Ok(Document::new(record.path, record.media_type, entities)
+            .with_attributes(record.attributes))
+    }
+
+    fn encode(&self, document: Document) -> Result<Record, CodecError> {
+        if document.media_type.format() != Format::Protobuf {
+            return Err(CodecError::UnsupportedMediaType(document.media_type));
+        }
+
+        if !self.delimited && document.content.len() != 1 {
+            return Err(CodecError::Encode(format!(
+                "expected one message, found {}; use a delimited codec for streams",
+                document.content.len()
+            )));
+        }
+
+        let mut bytes = Vec::new();
+
+        for entity in &document.content {
+            let message = value_to_message(&self.message, &entity.content)
+                .map_err(|e| CodecError::Encode(format!("{}: {}", entity.path, e)))?;
+
+            if self.delimited {
+                message
+                    .encode_length_delimited(&mut bytes)
+                    .map_err(CodecError::encode)?;
+            } else {
+                message.encode(&mut bytes).map_err(CodecError::encode)?;
+            }
+        }
+
+        Ok(Record::new(document.path, document.media_type, bytes)
+            .with_attributes(document.attributes))
+    }
+
+    /// Length-delimited messages concatenate, so a delimited codec writes
+    /// records back to back; plain messages have no framing and can't be streamed.
+    fn encode_stream(
+        &self,
+        documents: &mut dyn Iterator<Item = Document>,
+        out: &mut dyn Write,
+    ) -> Result<usize, CodecError> {
+        if !self.delimited {
+            return Err(CodecError::Encode(
+                "plain messages can't be streamed; use a delimited codec".to_string(),
+            ));
+        }
+
+        let mut count = 0;
+
+        for document in documents {
+            let record = self.encode(document)?;
+            out.write_all(&record.content).map_err(CodecError::encode)?;
+            count += 1;
+        }
+
+        Ok(count)
+    }
+}
+
+fn message_to_value(message: &DynamicMessage) -> Value {
+    let mut obj = Object::new();
+
+    for field in message.descriptor().fields() {
+        if field.supports_presence() && !message.has_field(&field) {
+            continue;
+        }
+
+        let value = message.get_field(&field);
+        obj.insert(field.name().to_string(), to_value(&field.kind(), &value));
+    }
+
+    Value::Object(obj)
+}
+
+fn to_value(kind: &Kind, value: &prost_reflect::Value) -> Value {
+    use prost_reflect::Value as Proto;
+
+    match value {
+        Proto::Bool(b) => Value::Bool(*b),
+        Proto::I32(i) => Value::Number(Number::Int(*i as i64)),
+        Proto::I64(i) => Value::Number(Number::Int(*i)),
+        Proto::U32(i) => Value::Number(Number::Int(*i as i64)),
+        Proto::U64(i) => match i64::try_from(*i) {
+            Ok(i) => Value::Number(Number::Int(i)),
+            Err(_) => Value::Number(Number::Float(*i as f64)),
+        },
+        Proto::F32(f) => Value::Number(Number::Float(*f as f64)),
+        Proto::F64(f) => Value::Number(Number::Float(*f)),
+        Proto::String(s) => Value::String(s.clone()),
+        Proto::Bytes(bytes) => Value::Array(Array::from(
+            bytes.iter().map(|b| *b as i64).collect::<Vec<_>>(),
+        )),
+        Proto::EnumNumber(n) => match kind.as_enum().and_then(|e| e.get_value(*n)) {
+            Some(v) => Value::String(v.name().to_string()),
+            None => Value::Number(Number::Int(*n as i64)),
+        },
+        Proto::Message(message) => message_to_value(message),
+        Proto::List(items) => Value::Array(Array::from(
+            items.iter().map(|v| to_value(kind, v)).collect::<Vec<_>>(),
+        )),
+        Proto::Map(entries) => {
+            let value_kind = kind
+                .as_message()
+                .map(|entry| entry.map_entry_value_field().kind())
+                .unwrap_or_else(|| kind.clone());
+            let mut obj = Object::new();
+
+            for (key, value) in entries {
+                let key = match key {
+                    MapKey::Bool(b) => b.to_string(),
+                    MapKey::I32(i) => i.to_string(),
+                    MapKey::I64(i) => i.to_string(),
+                    MapKey::U32(i) => i.to_string(),
+                    MapKey::U64(i) => i.to_string(),
+                    MapKey::String(s) => s.clone(),
+                };
+
+                obj.insert(key, to_value(&value_kind, value));
+            }
+
+            Value::Object(obj)
+        }
+    }
+}
+
+fn value_to_message(
+    descriptor: &MessageDescriptor,
+    value: &Value,
+) -> Result<DynamicMessage, String> {
+    let obj = value
+        .as_object()
+        .ok_or_else(|| format!("{} is not an object", descriptor.full_name()))?;
+    let mut message = DynamicMessage::new(descriptor.clone());
+
+    for (key, value) in obj.iter() {
+        if value.is_null() {
+            continue;
+        }
+
+        let field = descriptor
+            .get_field_by_name(key)
+            .ok_or_else(|| format!("unknown field '{}' in {}", key, descriptor.full_name()))?;
+        let proto = field_to_proto(&field, value).map_err(|e| format!("{}: {}", key, e))?;
+
+        message.set_field(&field, proto);
+    }
+
+    Ok(message)
+}
+
+fn field_to_proto(field: &FieldDescriptor, value: &Value) -> Result<prost_reflect::Value, String> {
+    let kind = field.kind();
+
+    if field.is_map() {
+        let entry = kind.as_message().expect("map fields have an entry message");
+        let key_kind = entry.map_entry_key_field().kind();
+        let value_kind = entry.map_entry_value_field().kind();
+        let obj = value.as_object().ok_or("expected an object")?;
+        let mut map = HashMap::new();
+
+        for (key, value) in obj.iter() {
+            map.insert(map_key(&key_kind, key)?, to_proto(&value_kind, value)?);
+        }
+
+        return Ok(prost_reflect::Value::Map(map));
+    }
+
+    if field.is_list() {
+        let items = value.as_array().ok_or("expected an array")?;
+        let list = items
+            .iter()
+            .map(|v| to_proto(&kind, v))
+            .collect::<Result<_, _>>()?;
+
+        return Ok(prost_reflect::Value::List(list));
+    }
+
+    to_proto(&kind, value)
+}
+
+fn to_proto(kind: &Kind, value: &Value) -> Result<prost_reflect::Value, String> {
+    use prost_reflect::Value as Proto;
+
+    let int = || {
+        value
+            .as_int()
+            .ok_or_else(|| "expected an integer".to_string())
+    };
+    let float = || {
+        value
+            .as_float()
+            .ok_or_else(|| "expected a number".to_string())
+    };
+    let out_of_range = |_| "integer out of range".to_string();
+
+    Ok(match kind {
+        Kind::Bool => Proto::Bool(value.as_bool().ok_or("expected a bool")?),
+        Kind::Int32 | Kind::Sint32 | Kind::Sfixed32 => {
+            Proto::I32(int()?.try_into().map_err(out_of_range)?)
+        }
+        Kind::Int64 | Kind::Sint64 | Kind::Sfixed64 => Proto::I64(int()?),
+        Kind::Uint32 | Kind::Fixed32 => Proto::U32(int()?.try_into().map_err(out_of_range)?),
+        Kind::Uint64 | Kind::Fixed64 => Proto::U64(int()?.try_into().map_err(out_of_range)?),
+        Kind::Float => Proto::F32(float()? as f32),
+        Kind::Double => Proto::F64(float()?),
+        Kind::String => Proto::String(value.as_str().ok_or("expected a string")?.to_string()),
+        Kind::Bytes => {
+            let items = value.as_array().ok_or("expected an array of bytes")?;
+            let bytes = items
+                .iter()
+                .map(|b| {
+                    b.as_int()
+                        .and_then(|b| u8::try_from(b).ok())
+                        .ok_or_else(|| "expected a byte".to_string())
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+
+            Proto::Bytes(bytes.into())
+        }
+        Kind::Enum(e) => match value {
+            Value::String(name) => Proto::EnumNumber(
+                e.get_value_by_name(name)
+                    .ok_or_else(|| format!("unknown {} value '{}'", e.full_name(), name))?
+                    .number(),
+            ),
+            _ => Proto::EnumNumber(int()?.try_into().map_err(out_of_range)?),
+        },
+        Kind::Message(descriptor) => Proto::Message(value_to_message(descriptor, value)?),
+    })
+}
+
+fn map_key(kind: &Kind, key: &str) -> Result<MapKey, String> {
+    let invalid = || format!("invalid map key '{}'", key);
+
+    Ok(match kind {
+        Kind::Bool => MapKey::Bool(key.parse().map_err(|_| invalid())?),
+        Kind::Int32 | Kind::Sint32 | Kind::Sfixed32 => {
+            MapKey::I32(key.parse().map_err(|_| invalid())?)
+        }
+        Kind::Int64 | Kind::Sint64 | Kind::Sfixed64 => {
+            MapKey::I64(key.parse().map_err(|_| invalid())?)
+        }
+        Kind::Uint32 | Kind::Fixed32 => MapKey::U32(key.parse().map_err(|_| invalid())?),
+        Kind::Uint64 | Kind::Fixed64 => MapKey::U64(key.parse().map_err(|_| invalid())?),
+        _ => MapKey::String(key.to_string()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MediaType;
+    use crate::path::{FilePath, Path};
+    use prost_types::field_descriptor_proto::{Label, Type};
+    use prost_types::{
+        DescriptorProto, EnumDescriptorProto, EnumValueDescriptorProto, FieldDescriptorProto,
+        FileDescriptorProto, FileDescriptorSet,
+    };
+
+    fn field(name: &str, number: i32, ty: Type, label: Label) -> FieldDescriptorProto {
+        FieldDescriptorProto {
+            name: Some(name.to_string()),
+            number: Some(number),
+            r#type: Some(ty as i32),
+            label: Some(label as i32),
+            ..Default::default()
+        }
+    }
+
+    /// `events.ScoreEvent { string text = 1; double score = 2; repeated string labels = 3; Decision decision = 4; }`
+    fn descriptor_set() -> Vec<u8> {
+        let decision = FieldDescriptorProto {
+            type_name: Some(".events.Decision".to_string()),
+            ..field("decision", 4, Type::Enum, Label::Optional)
+        };
+
+        let set = FileDescriptorSet {
+            file: vec![FileDescriptorProto {
+                name: Some("events.proto".to_string()),
+                package: Some("events".to_string()),
+                syntax: Some("proto3".to_string()),
+                message_type: vec![DescriptorProto {
+                    name: Some("ScoreEvent".to_string()),
+                    field: vec![
+                        field("text", 1, Type::String, Label::Optional),
+                        field("score", 2, Type::Double, Label::Optional),
+                        field("labels", 3, Type::String, Label::Repeated),
+                        decision,
+                    ],
+                    ..Default::default()
+                }],
+                enum_type: vec![EnumDescriptorProto {
+                    name: Some("Decision".to_string()),
+                    value: ["UNKNOWN", "ACCEPT", "REJECT"]
+                        .iter()
+                        .enumerate()
+                        .map(|(i, name)| EnumValueDescriptorProto {
+                            name: Some(name.to_string()),
+                            number: Some(i as i32),
+                            ..Default::default()
+                        })
+                        .collect(),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+        };
+
+        set.encode_to_vec()
+    }
+
+    fn codec() -> ProtobufCodec {
+        ProtobufCodec::new(&descriptor_set(), "events.ScoreEvent").unwrap()
+    }
+
+    fn path() -> Path {
+        Path::File(FilePath::parse("/events.pb"))
+    }
+
+    fn event(text: &str, score: f64) -> DynamicMessage {
+        let mut message = DynamicMessage::new(codec().descriptor().clone());
+        message.set_field_by_name("text", prost_reflect::Value::String(text.to_string()));
+        message.set_field_by_name("score", prost_reflect::Value::F64(score));
+        message.set_field_by_name(
+            "labels",
+            prost_reflect::Value::List(vec![prost_reflect::Value::String("task".to_string())]),
+        );
+        message.set_field_by_name("decision", prost_reflect::Value::EnumNumber(1));
+        message
+    }
+
+    #[test]
+    fn test_decode_message() {
+        let record = Record::new(
+            path(),
+            MediaType::Protobuf,
+            event("ship it", 0.9).encode_to_vec(),
+        );
+        let document = codec().decode(record).unwrap();
+        let value = &document.content[0].content;
+
+        assert_eq!(document.content[0].path.to_string(), "root");
+        assert_eq!(value["text"].as_str(), Some("ship it"));
+        assert_eq!(value["score"].as_float(), Some(0.9));
+        assert_eq!(value["labels"][0].as_str(), Some("task"));
+        assert_eq!(value["decision"].as_str(), Some("ACCEPT"));
+    }
+
+    #[test]
+    fn test_decode_fills_defaults() {
+        let record = Record::new(path(), MediaType::Protobuf, Vec::new());
+        let document = codec().decode(record).unwrap();
+        let value = &document.content[0].content;
+
+        assert_eq!(value["text"].as_str(), Some(""));
+        assert_eq!(value["decision"].as_str(), Some("UNKNOWN"));
+    }
+
+    #[test]
+    fn test_roundtrip_delimited() {
+        let codec = codec().with_delimited(true);
+        let mut bytes = Vec::new();
+        event("a", 0.1).encode_length_delimited(&mut bytes).unwrap();
+        event("b", 0.2).encode_length_delimited(&mut bytes).unwrap();
+
+        let document = codec
+            .decode(Record::new(path(), MediaType::Protobuf, bytes.clone()))
+            .unwrap();
+
+        assert_eq!(document.content.len(), 2);
+        assert_eq!(document.content[1].path.to_string(), "root[1]");
+        assert_eq!(document.content[1].content["text"].as_str(), Some("b"));
+
+        let record = codec.encode(document).unwrap();
+
+        assert_eq!(record.content, bytes);
+    }
+
+    #[test]
+    fn test_encode_stream_delimited() {
+        let record = Record::new(path(), MediaType::Protobuf, event("a", 0.1).encode_to_vec());
+        let document = codec().decode(record).unwrap();
+        let mut out = Vec::new();
+
+        assert!(
+            codec()
+                .encode_stream(&mut std::iter::once(document.clone()), &mut out)
+                .unwrap_err()
+                .is_encode()
+        );
+
+        let codec = codec().with_delimited(true);
+        let mut documents = vec![document.clone(), document].into_iter();
+
+        assert_eq!(codec.encode_stream(&mut documents, &mut out).unwrap(), 2);
+
+        let decoded = codec
+            .decode(Record::new(path(), MediaType::Protobuf, out))
+            .unwrap();
+        assert_eq!(decoded.content.len(), 2);
+    }
+
+    #[test]
+    fn test_encode_rejects_unknown_fields() {
+        let mut obj = Object::new();
+        obj.insert("missing".to_string(), Value::Bool(true));
+
+        let entity = Entity::new(
+            IdentPath::parse("root").unwrap(),
+            "application/x-protobuf",
+            Value::Object(obj),
+        );
+        let document = Document::new(path(), MediaType::Protobuf, vec![entity]);
+
+        assert!(codec().encode(document).unwrap_err().is_encode());
+    }
+
+    #[test]
+    fn test_unknown_message() {
+        assert!(
+            ProtobufCodec::new(&descriptor_set(), "events.Missing")
+                .unwrap_err()
+                .is_decode()
+        );
+    }
+
+    #[test]
+    fn test_unsupported_media_type() {
+        let record = Record::from_str(path(), MediaType::TextJson, "{}");
+
+        assert!(codec().decode(record).unwrap_err().is_unsupported());
+    }
+}
@@ -1,11 +1,14 @@
 use std::collections::HashMap;
 
-use loom_core::Format;
+use loom_core::{Format, MediaType};
+use loom_io::{Document, Record};
 
-use super::Codec;
+use super::{Codec, CodecError, EncodeOptions};
 
 pub struct CodecRegistry {
     codecs: HashMap<Format, Box<dyn Codec>>,
+    compound: HashMap<String, Box<dyn Codec>>,
+    options: HashMap<Format, EncodeOptions>,
 }
 
 impl CodecRegistry {
@@ -14,11 +17,11 @@ impl CodecRegistry {
     }
 
     pub fn len(&self) -> usize {
-        self.codecs.len()
+        self.codecs.len() + self.compound.len()
     }
 
     pub fn is_empty(&self) -> bool {
-        self.codecs.is_empty()
+        self.codecs.is_empty() && self.compound.is_empty()
     }
 
     pub fn exists(&self, format: Format) -> bool {
@@ -28,11 +31,77 @@ impl CodecRegistry {
     pub fn get(&self, format: Format) -> Option<&dyn Codec> {
         self.codecs.get(&format).map(|c| c.as_ref())
     }
+
+    /// Encode options registered for `format`, overriding its codec's own.
+    pub fn options(&self, format: Format) -> Option<&EncodeOptions> {
+        self.options.get(&format)
+    }
+
+    /// Encode `document` with the codec for its format, applying the
+    /// registered encode options when there are any.
+    pub fn encode(&self, document: Document) -> Result<Record, CodecError> {
+        let format = document.media_type.format();
+        let codec = self
+            .get(format)
+            .ok_or(CodecError::UnsupportedMediaType(document.media_type))?;
+
+        match self.options(format) {
+            Some(options) => codec.encode_with(document, options),
+            None => codec.encode(document),
+        }
+    }
+
+    /// Rewrite `original` with `document`'s content using the codec for its
+    /// format, applying the registered encode options when there are any.
+    pub fn rewrite(&self, original: &Record, document: Document) -> Result<Record, CodecError> {
+        let format = document.media_type.format();
+        let codec = self
+            .get(format)
+            .ok_or(CodecError::UnsupportedMediaType(document.media_type))?;
+
+        match self.options(format) {
+            Some(options) => codec.rewrite_with(original, document, options),
+            None => codec.rewrite(original, document),
+        }
+    }
+
+    /// Codec registered for the compound extension `path` ends with
+    /// (e.g. `data.json.gz`), preferring the longest match.
+    pub fn compound(&self, path: &str) -> Option<&dyn Codec> {
+        let path = path.to_ascii_lowercase();
+
+        self.compound
+            .iter()
+            .filter(|(ext, _)| {
+                path.strip_suffix(ext.as_str())
+                    .is_some_and(|rest| rest.ends_with('.'))
+            })
+            .max_by_key(|(ext, _)| ext.len())
+            .map(|(_, c)| c.as_ref())
+    }
+
+    /// Codec for `path`: a compound extension match, or else the codec for
+    /// the format inferred from its extension.
+    pub fn for_path(&self, path: &str) -> Option<&dyn Codec> {
+        self.compound(path)
+            .or_else(|| self.get(MediaType::from_path(path).format()))
+    }
+}
+
+impl std::fmt::Debug for CodecRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CodecRegistry")
+            .field("formats", &self.codecs.keys().collect::<Vec<_>>())
+            .field("compound", &self.compound.keys().collect::<Vec<_>>())
+            .finish()
+    }
 }
 
 #[derive(Default)]
 pub struct CodecRegistryBuilder {
     codecs: HashMap<Format, Box<dyn Codec>>,
+    compound: HashMap<String, Box<dyn Codec>>,
+    options: HashMap<Format, EncodeOptions>,
 }
 
 impl CodecRegistryBuilder {
@@ -45,9 +114,100 @@ impl CodecRegistryBuilder {
         self
     }
 
+    /// Register `codec` for paths ending in a compound extension such as
+    /// `json.gz`, in addition to (not instead of) the codec for its format.
+    pub fn compound<T: Codec + 'static>(mut self, extension: &str, codec: T) -> Self {
+        let extension = extension.trim_start_matches('.').to_ascii_lowercase();
+        self.compound.insert(extension, Box::new(codec));
+        self
+    }
+
+    /// Encode `format` with `options`, whichever codec is registered for it.
+    pub fn encode_options(mut self, format: Format, options: EncodeOptions) -> Self {
+        self.options.insert(format, options);
+        self
+    }
+
     pub fn build(self) -> CodecRegistry {
         CodecRegistry {
             codecs: self.codecs,
+            compound: self.compound,
+            options: self.options,
         }
     }
 }
+
+#[cfg(all(test, feature = "json"))]
+mod tests {
+    use super::*;
+    use crate::{JsonCodec, TextCodec};
+
+    #[test]
+    fn test_compound_prefers_longest_extension() {
+        let registry = CodecRegistry::new()
+            .codec(TextCodec::new())
+            .compound("gz", TextCodec::new())
+            .compound(".JSON.gz", JsonCodec::new())
+            .build();
+
+        assert_eq!(registry.len(), 3);
+        assert_eq!(
+            registry.compound("/data/train.json.gz").map(|c| c.format()),
+            Some(Format::Json)
+        );
+        assert_eq!(
+            registry.compound("/data/notes.txt.gz").map(|c| c.format()),
+            Some(Format::Text)
+        );
+        assert_eq!(
+            registry.compound("/data/json.gz").map(|c| c.format()),
+            Some(Format::Text)
+        );
+        assert!(registry.compound("/data/train.json").is_none());
+    }
+
+    #[test]
+    fn test_for_path_falls_back_to_format() {
+        let registry = CodecRegistry::new().codec(JsonCodec::new()).build();
+
+        assert_eq!(
+            registry.for_path("/data/train.json").map(|c| c.format()),
+            Some(Format::Json)
+        );
+        assert!(registry.for_path("/data/train.json.gz").is_none());
+    }
+
+    #[test]
+    fn test_encode_applies_registered_options() {
+        use crate::Entity;
+        use crate::path::{FilePath, IdentPath, Path};
+        use crate::value::Value;
+
+        let document = || {
+            Document::new(
+                Path::File(FilePath::parse("/export.json")),
+                MediaType::TextJson,
+                vec![Entity::new(
+                    IdentPath::parse("root").unwrap(),
+                    "application/json",
+                    Value::from(serde_json::json!({ "a": 1 })),
+                )],
+            )
+        };
+
+        let compact = CodecRegistry::new().codec(JsonCodec::new()).build();
+        let pretty = CodecRegistry::new()
+            .codec(JsonCodec::new())
+            .encode_options(
+                Format::Json,
+                EncodeOptions::pretty().with_trailing_newline(true),
+            )
+            .build();
+
+        let record = compact.encode(document()).unwrap();
+        assert_eq!(record.content_str().unwrap(), r#"{"a":1}"#);
+
+        let record = pretty.encode(document()).unwrap();
+        assert_eq!(record.content_str().unwrap(), "{\n  \"a\": 1\n}\n");
+    }
+}
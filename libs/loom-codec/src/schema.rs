@@ -0,0 +1,387 @@
+use crate::value::{Number, Value};
+use crate::{Document, Format, Record};
+
+use super::{Codec, CodecError, EncodeOptions, PartialDocument};
+
+/// A JSON-Schema-like schema, defined as a [`Value`].
+///
+/// Supported keywords:
+///
+/// - `type` - `"null"`, `"boolean"`, `"integer"`, `"number"`, `"string"`,
+///   `"array"` or `"object"`, or an array of these
+/// - `enum` - allowed values
+/// - `minimum` / `maximum` - bounds for numbers
+/// - `minLength` / `maxLength` - bounds for string length (in characters)
+/// - `minItems` / `maxItems` - bounds for array length
+/// - `items` - schema every array element must match
+/// - `properties` - schemas for object fields, keyed by name
+/// - `required` - object fields that must be present
+/// - `additionalProperties` - `false` rejects fields not listed in `properties`
+///
+/// Unknown keywords are ignored.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Schema(Value);
+
+impl Schema {
+    pub fn new(schema: impl Into<Value>) -> Self {
+        Self(schema.into())
+    }
+
+    /// Validate `value`, reporting violations relative to `path`
+    /// (e.g. the path of the entity being validated).
+    pub fn validate(&self, path: &str, value: &Value) -> Vec<SchemaViolation> {
+        let mut violations = Vec::new();
+        validate_into(path, &self.0, value, &mut violations);
+        violations
+    }
+}
+
+impl From<Value> for Schema {
+    fn from(schema: Value) -> Self {
+        Self(schema)
+    }
+}
+
+/// A value that does not match its schema.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaViolation {
+    /// Path to the offending value (e.g. `root[3].text`)
+    pub path: String,
+    pub message: String,
+}
+
+impl std::fmt::Display for SchemaViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.path, self.message)
+    }
+}
+
+/// Codec wrapper that validates every decoded entity against a [`Schema`],
+/// so malformed files fail at load time with the offending paths instead of
+/// deep inside whatever consumes them.
+///
+/// ```ignore
+/// let codec = ValidatingCodec::new(JsonCodec::new(), Schema::new(schema));
+/// let document = codec.decode(record)?; // Err(CodecError::SchemaViolation(..))
+/// ```
+#[derive(Debug, Clone)]
+pub struct ValidatingCodec<C> {
+    inner: C,
+    schema: Schema,
+}
+
+impl<C: Codec> ValidatingCodec<C> {
+    pub fn new(inner: C, schema: impl Into<Schema>) -> Self {
+        Self {
+            inner,
+            schema: schema.into(),
+        }
+    }
+
+    pub fn inner(&self) -> &C {
+        &self.inner
+    }
+
+    pub fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    fn violations(&self, document: &Document) -> Vec<SchemaViolation> {
+        document
+            .content
+            .iter()
+            .flat_map(|entity| {
+                self.schema
+                    .validate(&entity.path.to_string(), &entity.content)
+            })
+            .collect()
+    }
+}
+
+impl<C: Codec> Codec for ValidatingCodec<C> {
+    fn format(&self) -> Format {
+        self.inner.format()
+    }
+
+    fn decode(&self, record: Record) -> Result<Document, CodecError> {
+        let document = self.inner.decode(record)?;
+        let violations = self.violations(&document);
+
+        if !violations.is_empty() {
+            return Err(CodecError::SchemaViolation(violations));
+        }
+
+        Ok(document)
+    }
+
+    /// Keeps the entities that decoded, adding any schema violations among
+    /// them to the inner codec's errors.
+    fn decode_lenient(&self, record: Record) -> Result<PartialDocument, CodecError> {
+        let mut partial = self.inner.decode_lenient(record)?;
+        let violations = self.violations(&partial.document);
+
+        if !violations.is_empty() {
+            partial.errors.push(CodecError::SchemaViolation(violations));
+        }
+
+        Ok(partial)
+    }
+
+    fn encode(&self, document: Document) -> Result<Record, CodecError> {
+        self.inner.encode(document)
+    }
+
+    fn encode_with(
+        &self,
+        document: Document,
+        options: &EncodeOptions,
+    ) -> Result<Record, CodecError> {
+        self.inner.encode_with(document, options)
+    }
+
+    fn rewrite(&self, original: &Record, document: Document) -> Result<Record, CodecError> {
+        self.inner.rewrite(original, document)
+    }
+
+    fn rewrite_with(
+        &self,
+        original: &Record,
+        document: Document,
+        options: &EncodeOptions,
+    ) -> Result<Record, CodecError> {
+        self.inner.rewrite_with(original, document, options)
+    }
+}
+
+fn validate_into(path: &str, schema: &Value, value: &Value, out: &mut Vec<SchemaViolation>) {
+    let Some(schema) = schema.as_object() else {
+        return;
+    };
+
+    let mut violation = |message: String| {
+        out.push(SchemaViolation {
+            path: path.to_string(),
+            message,
+        })
+    };
+
+    if let Some(expected) = schema.get("type") {
+        let types: Vec<&str> = match expected {
+            Value::Array(types) => types.iter().filter_map(|t| t.as_str()).collect(),
+            other => other.as_str().into_iter().collect(),
+        };
+
+        if !types.is_empty() && !types.iter().any(|t| is_type(value, t)) {
+            violation(format!(
+                "expected {}, found {}",
+                types.join(" or "),
+                value.kind()
+            ));
+            return;
+        }
+    }
+
+    if let Some(allowed) = schema.get("enum").and_then(|v| v.as_array())
+        && !allowed.contains(value)
+    {
+        violation("value is not one of the allowed values".to_string());
+    }
+
+    if let Some(n) = value.as_float() {
+        if let Some(min) = schema.get("minimum").and_then(|v| v.as_float())
+            && n < min
+        {
+            violation(format!("{} is less than the minimum of {}", n, min));
+        }
+
+        if let Some(max) = schema.get("maximum").and_then(|v| v.as_float())
+            && n > max
+        {
+            violation(format!("{} is greater than the maximum of {}", n, max));
+        }
+    }
+
+    let length = match value {
+        Value::String(s) => Some((s.chars().count(), "minLength", "maxLength", "characters")),
+        Value::Array(a) => Some((a.len(), "minItems", "maxItems", "items")),
+        _ => None,
+    };
+
+    if let Some((len, min_key, max_key, unit)) = length {
+        if let Some(min) = limit(schema.get(min_key))
+            && len < min
+        {
+            violation(format!("expected at least {} {}, found {}", min, unit, len));
+        }
+
+        if let Some(max) = limit(schema.get(max_key))
+            && len > max
+        {
+            violation(format!("expected at most {} {}, found {}", max, unit, len));
+        }
+    }
+
+    match value {
+        Value::Array(items) => {
+            if let Some(item_schema) = schema.get("items") {
+                for (i, item) in items.iter().enumerate() {
+                    validate_into(&format!("{}[{}]", path, i), item_schema, item, out);
+                }
+            }
+        }
+        Value::Object(fields) => {
+            let properties = schema.get("properties").and_then(|v| v.as_object());
+
+            if let Some(required) = schema.get("required").and_then(|v| v.as_array()) {
+                for name in required.iter().filter_map(|v| v.as_str()) {
+                    if !fields.contains_key(name) {
+                        out.push(SchemaViolation {
+                            path: field_path(path, name),
+                            message: "required field is missing".to_string(),
+                        });
+                    }
+                }
+            }
+
+            let additional = schema
+                .get("additionalProperties")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(true);
+
+            for (name, field) in fields.iter() {
+                match properties.and_then(|p| p.get(name)) {
+                    Some(field_schema) => {
+                        validate_into(&field_path(path, name), field_schema, field, out)
+                    }
+                    None if !additional => out.push(SchemaViolation {
+                        path: field_path(path, name),
+                        message: "field is not allowed".to_string(),
+                    }),
+                    None => {}
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn is_type(value: &Value, name: &str) -> bool {
+    match name {
+        "null" => value.is_null(),
+        "boolean" => value.is_bool(),
+        "integer" => match value {
+            Value::Number(Number::Int(_)) => true,
+            Value::Number(Number::Float(f)) => f.fract() == 0.0,
+            _ => false,
+        },
+        "number" => value.is_number(),
+        "string" => value.is_string(),
+        "array" => value.is_array(),
+        "object" => value.is_object(),
+        _ => true,
+    }
+}
+
+fn limit(value: Option<&Value>) -> Option<usize> {
+    value
+        .and_then(|v| v.as_int())
+        .and_then(|v| usize::try_from(v).ok())
+}
+
+fn field_path(path: &str, name: &str) -> String {
+    if path.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}.{}", path, name)
+    }
+}
+
+#[cfg(all(test, feature = "json"))]
+mod tests {
+    use super::*;
+    use crate::path::{FilePath, Path};
+    use crate::{JsonCodec, MediaType};
+
+    fn schema() -> Schema {
+        Schema::new(Value::from(serde_json::json!({
+            "type": "array",
+            "items": {
+                "type": "object",
+                "required": ["id", "text"],
+                "additionalProperties": false,
+                "properties": {
+                    "id": { "type": "string", "minLength": 1 },
+                    "text": { "type": "string" },
+                    "decision": { "enum": ["accept", "reject"] },
+                    "weight": { "type": "number", "minimum": 0 }
+                }
+            }
+        })))
+    }
+
+    fn record(json: &str) -> Record {
+        Record::from_str(
+            Path::File(FilePath::parse("/samples.json")),
+            MediaType::TextJson,
+            json,
+        )
+    }
+
+    #[test]
+    fn test_valid_document_decodes() {
+        let codec = ValidatingCodec::new(JsonCodec::new(), schema());
+        let document = codec
+            .decode(record(
+                r#"[{"id":"a","text":"hi","decision":"accept","weight":1}]"#,
+            ))
+            .unwrap();
+
+        assert_eq!(document.content.len(), 1);
+    }
+
+    #[test]
+    fn test_violations_report_paths() {
+        let codec = ValidatingCodec::new(JsonCodec::new(), schema());
+        let err = codec
+            .decode(record(
+                r#"[{"id":"a","text":"hi"},{"id":"","decision":"maybe","weight":-1,"extra":true}]"#,
+            ))
+            .unwrap_err();
+
+        let CodecError::SchemaViolation(violations) = err else {
+            panic!("expected schema violation");
+        };
+
+        let paths: Vec<&str> = violations.iter().map(|v| v.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                "root[1].text",
+                "root[1].decision",
+                "root[1].extra",
+                "root[1].id",
+                "root[1].weight",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lenient_decode_keeps_document_with_violations() {
+        let codec = ValidatingCodec::new(JsonCodec::new(), schema());
+        let partial = codec
+            .decode_lenient(record(r#"[{"id":"a","text":"hi"},{"id":"b"}]"#))
+            .unwrap();
+
+        assert_eq!(partial.document.content.len(), 1);
+        assert_eq!(partial.errors.len(), 1);
+        assert!(partial.errors[0].is_schema_violation());
+    }
+
+    #[test]
+    fn test_type_mismatch_stops_at_value() {
+        let violations = schema().validate("root", &Value::from("not an array"));
+
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].message, "expected array, found string");
+    }
+}
@@ -30,7 +30,8 @@ impl Codec for TextCodec {
             Value::String(text),
         );
 
-        Ok(Document::new(record.path, record.media_type, vec![entity]))
+        Ok(Document::new(record.path, record.media_type, vec![entity])
+            .with_attributes(record.attributes))
     }
 
     fn encode(&self, document: Document) -> Result<Record, CodecError> {
@@ -48,7 +49,8 @@ impl Codec for TextCodec {
             .as_str()
             .ok_or_else(|| CodecError::Encode("content is not a string".to_string()))?;
 
-        Ok(Record::from_str(document.path, document.media_type, text))
+        Ok(Record::from_str(document.path, document.media_type, text)
+            .with_attributes(document.attributes))
     }
 }
 
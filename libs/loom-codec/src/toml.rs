@@ -1,12 +1,14 @@
+use toml_edit::{ArrayOfTables, DocumentMut, InlineTable, Item, Table, TableLike};
+
 use crate::path::IdentPath;
-use crate::value::Value;
+use crate::value::{Array, Number, Object, Value};
 use crate::{Document, Entity, Format, Record};
 
-use super::{Codec, CodecError};
+use super::{Codec, CodecError, EncodeOptions, Position};
 
 #[derive(Debug, Clone)]
 pub struct TomlCodec {
-    pub pretty_print: bool,
+    pub options: EncodeOptions,
 }
 
 impl Default for TomlCodec {
@@ -18,16 +20,23 @@ impl Default for TomlCodec {
 impl TomlCodec {
     pub fn new() -> Self {
         Self {
-            pretty_print: false,
+            options: EncodeOptions::compact(),
         }
     }
 
     pub fn pretty() -> Self {
-        Self { pretty_print: true }
+        Self {
+            options: EncodeOptions::pretty(),
+        }
     }
 
     pub fn with_pretty_print(mut self, pretty: bool) -> Self {
-        self.pretty_print = pretty;
+        self.options.indent = pretty.then_some(2);
+        self
+    }
+
+    pub fn with_options(mut self, options: EncodeOptions) -> Self {
+        self.options = options;
         self
     }
 }
@@ -43,7 +52,10 @@ impl Codec for TomlCodec {
         }
 
         let text = String::from_utf8(record.content)?;
-        let toml_val: toml::Value = toml::from_str(&text).map_err(CodecError::decode)?;
+        let toml_val: toml::Value = toml::from_str(&text).map_err(|e| match e.span() {
+            Some(span) => CodecError::syntax(Position::of_offset(&text, span.start), e.message()),
+            None => CodecError::decode(e),
+        })?;
         let value: Value = toml_val.into();
 
         let entity = Entity::new(
@@ -52,10 +64,19 @@ impl Codec for TomlCodec {
             value,
         );
 
-        Ok(Document::new(record.path, record.media_type, vec![entity]))
+        Ok(Document::new(record.path, record.media_type, vec![entity])
+            .with_attributes(record.attributes))
     }
 
     fn encode(&self, document: Document) -> Result<Record, CodecError> {
+        self.encode_with(document, &self.options)
+    }
+
+    fn encode_with(
+        &self,
+        document: Document,
+        options: &EncodeOptions,
+    ) -> Result<Record, CodecError> {
         if document.media_type.format() != Format::Toml {
             return Err(CodecError::UnsupportedMediaType(document.media_type));
         }
@@ -65,15 +86,188 @@ impl Codec for TomlCodec {
             .first()
             .ok_or_else(|| CodecError::Encode("document has no content".to_string()))?;
 
-        let toml_val: toml::Value = (&content.content).into();
-        let text = if self.pretty_print {
-            toml::to_string_pretty(&toml_val)
-        } else {
-            toml::to_string(&toml_val)
+        let value: toml::Value = (&content.content).into();
+        let text = options.encode(&value, Format::Toml)?;
+
+        Ok(Record::from_str(document.path, document.media_type, &text)
+            .with_attributes(document.attributes))
+    }
+
+    fn rewrite(&self, original: &Record, document: Document) -> Result<Record, CodecError> {
+        self.rewrite_with(original, document, &self.options)
+    }
+
+    /// With `options.lossless`, edits `original` in place: unchanged values
+    /// keep their formatting and comments, changed values keep the comments
+    /// around them, removed keys are dropped and new keys are appended to
+    /// their table.
+    fn rewrite_with(
+        &self,
+        original: &Record,
+        document: Document,
+        options: &EncodeOptions,
+    ) -> Result<Record, CodecError> {
+        if !options.lossless {
+            return self.encode_with(document, options);
+        }
+
+        if document.media_type.format() != Format::Toml {
+            return Err(CodecError::UnsupportedMediaType(document.media_type));
+        }
+
+        let content = document
+            .content
+            .first()
+            .ok_or_else(|| CodecError::Encode("document has no content".to_string()))?;
+
+        let object = content.content.as_object().ok_or_else(|| {
+            CodecError::Encode(format!(
+                "expected object document, found {}",
+                content.content.kind()
+            ))
+        })?;
+
+        let mut edit: DocumentMut = original
+            .content_str()?
+            .parse()
+            .map_err(CodecError::decode)?;
+
+        merge_table(edit.as_table_mut(), object, false);
+
+        Ok(Record::from_str(
+            document.path,
+            document.media_type,
+            &options.finish(edit.to_string()),
+        )
+        .with_attributes(document.attributes))
+    }
+}
+
+/// Update `table` in place to hold `object`. Nulls count as missing, since
+/// TOML has no null.
+fn merge_table(table: &mut dyn TableLike, object: &Object, inline: bool) {
+    let stale: Vec<String> = table
+        .iter()
+        .map(|(key, _)| key.to_string())
+        .filter(|key| object.get(key).is_none_or(Value::is_null))
+        .collect();
+
+    for key in stale {
+        table.remove(&key);
+    }
+
+    for (key, value) in object.iter().filter(|(_, value)| !value.is_null()) {
+        match table.get_mut(key) {
+            Some(item) => merge_item(item, value),
+            None => {
+                table.insert(key, new_item(value, inline));
+            }
         }
-        .map_err(CodecError::encode)?;
+    }
+}
 
-        Ok(Record::from_str(document.path, document.media_type, &text))
+fn merge_item(item: &mut Item, value: &Value) {
+    let inline = item.is_inline_table();
+
+    match (item, value) {
+        (item, Value::Object(object)) if item.is_table_like() => {
+            if let Some(table) = item.as_table_like_mut() {
+                merge_table(table, object, inline);
+            }
+        }
+        (Item::ArrayOfTables(tables), Value::Array(items))
+            if items.iter().all(Value::is_object) =>
+        {
+            merge_tables(tables, items);
+        }
+        (Item::Value(existing), value) if same(existing, value) => {}
+        (Item::Value(existing), value) => {
+            if let Some(mut replacement) = edit_value(value) {
+                *replacement.decor_mut() = existing.decor().clone();
+                *existing = replacement;
+            }
+        }
+        (item, value) => *item = new_item(value, false),
+    }
+}
+
+/// Merge `[[array]]` tables by index.
+fn merge_tables(tables: &mut ArrayOfTables, items: &Array) {
+    while tables.len() > items.len() {
+        tables.remove(tables.len() - 1);
+    }
+
+    for (i, object) in items.iter().filter_map(Value::as_object).enumerate() {
+        match tables.get_mut(i) {
+            Some(table) => merge_table(table, object, false),
+            None => tables.push(new_table(object)),
+        }
+    }
+}
+
+/// Objects become `[table]`s and arrays of objects `[[tables]]`, unless
+/// nested in an inline table.
+fn new_item(value: &Value, inline: bool) -> Item {
+    match value {
+        Value::Object(object) if !inline => Item::Table(new_table(object)),
+        Value::Array(items)
+            if !inline && !items.is_empty() && items.iter().all(Value::is_object) =>
+        {
+            let mut tables = ArrayOfTables::new();
+
+            for object in items.iter().filter_map(Value::as_object) {
+                tables.push(new_table(object));
+            }
+
+            Item::ArrayOfTables(tables)
+        }
+        value => edit_value(value).map_or(Item::None, Item::Value),
+    }
+}
+
+fn new_table(object: &Object) -> Table {
+    let mut table = Table::new();
+    merge_table(&mut table, object, false);
+    table
+}
+
+fn edit_value(value: &Value) -> Option<toml_edit::Value> {
+    let value: toml_edit::Value = match value {
+        Value::Null => return None,
+        Value::Bool(b) => (*b).into(),
+        Value::Number(Number::Int(i)) => (*i).into(),
+        Value::Number(Number::Float(f)) => (*f).into(),
+        Value::String(s) => s.as_str().into(),
+        Value::Array(items) => {
+            toml_edit::Value::Array(items.iter().filter_map(edit_value).collect())
+        }
+        Value::Object(object) => {
+            let mut table = InlineTable::new();
+            merge_table(&mut table, object, true);
+            toml_edit::Value::InlineTable(table)
+        }
+    };
+
+    Some(value)
+}
+
+/// Whether `existing` already holds `value`, so it can stay as written.
+fn same(existing: &toml_edit::Value, value: &Value) -> bool {
+    match (existing, value) {
+        (toml_edit::Value::String(s), Value::String(v)) => s.value() == v,
+        (toml_edit::Value::Integer(i), Value::Number(Number::Int(v))) => i.value() == v,
+        (toml_edit::Value::Float(f), value) => value.as_float() == Some(*f.value()),
+        (toml_edit::Value::Boolean(b), Value::Bool(v)) => b.value() == v,
+        (toml_edit::Value::Datetime(d), Value::String(v)) => d.value().to_string() == *v,
+        (toml_edit::Value::Array(a), Value::Array(items)) => {
+            a.len() == items.len() && a.iter().zip(items.iter()).all(|(a, b)| same(a, b))
+        }
+        (toml_edit::Value::InlineTable(t), Value::Object(object)) => {
+            t.len() == object.values().filter(|v| !v.is_null()).count()
+                && t.iter()
+                    .all(|(key, a)| object.get(key).is_some_and(|b| same(a, b)))
+        }
+        _ => false,
     }
 }
 
@@ -142,6 +336,79 @@ mod tests {
         assert_eq!(doc2.content[0].content["test"].as_int(), Some(123));
     }
 
+    const CONFIG: &str = "# model settings\n[model]\nname = \"stress\" # default model\nthreshold = 0.5\n\n[labels]\nzeta = 1\nalpha = 2\n";
+
+    fn edited_config(codec: &TomlCodec) -> (Record, Document) {
+        let path = Path::File(FilePath::parse("/config.toml"));
+        let original = Record::from_str(path, MediaType::TextToml, CONFIG);
+        let mut document = codec.decode(original.clone()).unwrap();
+        let root = document.content[0].content.as_object_mut().unwrap();
+
+        let model = root.get_mut("model").unwrap().as_object_mut().unwrap();
+        model.insert("threshold".to_string(), Value::Number(Number::Float(0.7)));
+
+        let labels = root.get_mut("labels").unwrap().as_object_mut().unwrap();
+        labels.remove("alpha");
+        labels.insert("beta".to_string(), Value::Number(Number::Int(3)));
+
+        (original, document)
+    }
+
+    #[test]
+    fn test_lossless_rewrite_keeps_layout() {
+        let codec = TomlCodec::new().with_options(EncodeOptions::default().with_lossless(true));
+        let (original, document) = edited_config(&codec);
+
+        let record = codec.rewrite(&original, document).unwrap();
+        let text = record.content_str().unwrap();
+
+        assert!(text.starts_with("# model settings\n[model]\n"));
+        assert!(text.contains("name = \"stress\" # default model\n"));
+        assert!(text.contains("threshold = 0.7\n"));
+        assert!(!text.contains("alpha"));
+        assert!(text.find("zeta").unwrap() < text.find("beta").unwrap());
+    }
+
+    #[test]
+    fn test_rewrite_without_lossless_encodes() {
+        let codec = TomlCodec::new();
+        let (original, document) = edited_config(&codec);
+
+        let record = codec.rewrite(&original, document).unwrap();
+        let text = record.content_str().unwrap();
+
+        assert!(!text.contains('#'));
+        assert!(text.find("beta").unwrap() < text.find("zeta").unwrap());
+    }
+
+    #[test]
+    fn test_decode_error_position() {
+        let codec = TomlCodec::new();
+        let path = Path::File(FilePath::parse("/test.toml"));
+        let record = Record::from_str(path, MediaType::TextToml, "name = \"a\"\nvalue = \n");
+
+        let err = codec.decode(record).unwrap_err();
+
+        assert_eq!(err.position().map(|p| p.line), Some(2));
+    }
+
+    #[test]
+    fn test_encode_stream_unsupported() {
+        let codec = TomlCodec::new();
+        let path = Path::File(FilePath::parse("/test.toml"));
+        let entity = Entity::new(
+            IdentPath::parse("root").unwrap(),
+            "application/toml",
+            Value::Object(Object::new()),
+        );
+        let mut documents = std::iter::once(Document::new(path, MediaType::TextToml, vec![entity]));
+        let mut out = Vec::new();
+
+        let err = codec.encode_stream(&mut documents, &mut out).unwrap_err();
+        assert!(err.is_encode());
+        assert!(out.is_empty());
+    }
+
     #[test]
     fn test_unsupported_media_type() {
         let codec = TomlCodec::new();
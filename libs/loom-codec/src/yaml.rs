@@ -1,17 +1,81 @@
+use std::io::Write;
+
 use saphyr::{Yaml, YamlEmitter};
 
 use crate::path::IdentPath;
 use crate::value::Value;
 use crate::{Document, Entity, Format, Record};
 
-use super::{Codec, CodecError};
+use super::{Codec, CodecError, EncodeOptions, Position};
 
+/// Codec for YAML.
+///
+/// A stream of several `---` separated documents decodes to an array with one
+/// value per document. With `multi_document` set, every stream decodes to
+/// such an array and arrays encode back to one document per element, as in
+/// k8s-style config bundles.
 #[derive(Debug, Clone, Default)]
-pub struct YamlCodec;
+pub struct YamlCodec {
+    /// Output is always block style, so only `trailing_newline` and
+    /// `lossless` apply
+    pub options: EncodeOptions,
+    pub multi_document: bool,
+}
 
 impl YamlCodec {
     pub fn new() -> Self {
-        Self
+        Self::default()
+    }
+
+    pub fn with_options(mut self, options: EncodeOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    pub fn with_multi_document(mut self, multi_document: bool) -> Self {
+        self.multi_document = multi_document;
+        self
+    }
+
+    fn load(&self, text: &str) -> Result<Yaml, CodecError> {
+        let mut docs = Yaml::load_from_str(text).map_err(|e| {
+            let marker = e.marker();
+            CodecError::syntax(Position::new(marker.line(), marker.col() + 1), e.info())
+        })?;
+
+        if self.multi_document || docs.len() > 1 {
+            return Ok(Yaml::Array(docs));
+        }
+
+        Ok(docs.pop().unwrap_or(Yaml::Null))
+    }
+
+    fn emit(
+        &self,
+        document: Document,
+        yaml: &Yaml,
+        options: &EncodeOptions,
+    ) -> Result<Record, CodecError> {
+        let out_str = match yaml {
+            Yaml::Array(docs) if self.multi_document => {
+                let mut out_str = String::new();
+
+                for doc in docs {
+                    let text = dump(doc)?;
+                    out_str.push_str("---\n");
+                    out_str.push_str(text.strip_prefix("---").unwrap_or(&text).trim());
+                    out_str.push('\n');
+                }
+
+                out_str.trim_end().to_string()
+            }
+            yaml => dump(yaml)?,
+        };
+
+        Ok(
+            Record::from_str(document.path, document.media_type, &options.finish(out_str))
+                .with_attributes(document.attributes),
+        )
     }
 }
 
@@ -26,19 +90,26 @@ impl Codec for YamlCodec {
         }
 
         let text = String::from_utf8(record.content)?;
-        let docs = Yaml::load_from_str(&text).map_err(|e| CodecError::Decode(e.to_string()))?;
-        let yaml = docs.into_iter().next().unwrap_or(Yaml::Null);
-        let value = Value::from(yaml);
+        let value = Value::from(self.load(&text)?);
         let entity = Entity::new(
             IdentPath::parse("root").expect("valid field path"),
             record.media_type.as_mime_str(),
             value,
         );
 
-        Ok(Document::new(record.path, record.media_type, vec![entity]))
+        Ok(Document::new(record.path, record.media_type, vec![entity])
+            .with_attributes(record.attributes))
     }
 
     fn encode(&self, document: Document) -> Result<Record, CodecError> {
+        self.encode_with(document, &self.options)
+    }
+
+    fn encode_with(
+        &self,
+        document: Document,
+        options: &EncodeOptions,
+    ) -> Result<Record, CodecError> {
         if document.media_type.format() != Format::Yaml {
             return Err(CodecError::UnsupportedMediaType(document.media_type));
         }
@@ -49,17 +120,143 @@ impl Codec for YamlCodec {
             .ok_or_else(|| CodecError::Encode("document has no content".to_string()))?;
 
         let yaml = Yaml::from(&content.content);
-        let mut out_str = String::new();
-        let mut emitter = YamlEmitter::new(&mut out_str);
-        emitter
-            .dump(&yaml)
-            .map_err(|e| CodecError::Encode(e.to_string()))?;
-
-        Ok(Record::from_str(
-            document.path,
-            document.media_type,
-            &out_str,
-        ))
+        self.emit(document, &yaml, options)
+    }
+
+    fn rewrite(&self, original: &Record, document: Document) -> Result<Record, CodecError> {
+        self.rewrite_with(original, document, &self.options)
+    }
+
+    /// Writes one `---` separated document per input document, which decodes
+    /// back to an array (a stream of one decodes to that value alone, unless
+    /// `multi_document` is set).
+    fn encode_stream(
+        &self,
+        documents: &mut dyn Iterator<Item = Document>,
+        out: &mut dyn Write,
+    ) -> Result<usize, CodecError> {
+        let mut count = 0;
+
+        for document in documents {
+            if document.media_type.format() != Format::Yaml {
+                return Err(CodecError::UnsupportedMediaType(document.media_type));
+            }
+
+            let content = document
+                .content
+                .first()
+                .ok_or_else(|| CodecError::Encode("document has no content".to_string()))?;
+            let text = dump(&Yaml::from(&content.content))?;
+
+            writeln!(
+                out,
+                "---\n{}",
+                text.strip_prefix("---").unwrap_or(&text).trim()
+            )
+            .map_err(CodecError::encode)?;
+            count += 1;
+        }
+
+        Ok(count)
+    }
+
+    /// With `options.lossless`, keeps the key order of `original`: existing
+    /// keys stay where they were and new keys are appended to their mapping.
+    /// Comments are not preserved.
+    fn rewrite_with(
+        &self,
+        original: &Record,
+        document: Document,
+        options: &EncodeOptions,
+    ) -> Result<Record, CodecError> {
+        if !options.lossless {
+            return self.encode_with(document, options);
+        }
+
+        if document.media_type.format() != Format::Yaml {
+            return Err(CodecError::UnsupportedMediaType(document.media_type));
+        }
+
+        let content = document
+            .content
+            .first()
+            .ok_or_else(|| CodecError::Encode("document has no content".to_string()))?;
+
+        let mut yaml = self.load(original.content_str()?)?;
+
+        merge(&mut yaml, &content.content);
+        self.emit(document, &yaml, options)
+    }
+}
+
+fn dump(yaml: &Yaml) -> Result<String, CodecError> {
+    let mut out_str = String::new();
+    let mut emitter = YamlEmitter::new(&mut out_str);
+    emitter
+        .dump(yaml)
+        .map_err(|e| CodecError::Encode(e.to_string()))?;
+
+    Ok(out_str)
+}
+
+/// Update `yaml` in place to hold `value`, keeping mapping order and the
+/// original form of unchanged scalars.
+fn merge(yaml: &mut Yaml, value: &Value) {
+    match (yaml, value) {
+        (Yaml::Hash(hash), Value::Object(object)) => {
+            let stale: Vec<Yaml> = hash
+                .keys()
+                .filter(|key| key_name(key).is_some_and(|name| !object.contains_key(&name)))
+                .cloned()
+                .collect();
+
+            for key in stale {
+                hash.remove(&key);
+            }
+
+            for (name, value) in object.iter() {
+                let key = hash
+                    .keys()
+                    .find(|key| key_name(key).as_ref() == Some(name))
+                    .cloned();
+
+                match key {
+                    Some(key) => {
+                        if let Some(existing) = hash.get_mut(&key) {
+                            merge(existing, value);
+                        }
+                    }
+                    None => {
+                        hash.insert(Yaml::String(name.clone()), Yaml::from(value));
+                    }
+                }
+            }
+        }
+        (Yaml::Array(items), Value::Array(values)) => {
+            items.truncate(values.len());
+
+            for (i, value) in values.iter().enumerate() {
+                match items.get_mut(i) {
+                    Some(existing) => merge(existing, value),
+                    None => items.push(Yaml::from(value)),
+                }
+            }
+        }
+        (yaml, value) => {
+            if Value::from(yaml.clone()) != *value {
+                *yaml = Yaml::from(value);
+            }
+        }
+    }
+}
+
+/// Object key a mapping key decodes to, matching `Value::from(Yaml)`.
+fn key_name(key: &Yaml) -> Option<String> {
+    match key {
+        Yaml::String(s) | Yaml::Real(s) => Some(s.clone()),
+        Yaml::Integer(i) => Some(i.to_string()),
+        Yaml::Boolean(b) => Some(b.to_string()),
+        _ => None,
     }
 }
 
@@ -85,6 +282,33 @@ mod tests {
         assert_eq!(document.content[0].content["value"].as_int(), Some(42));
     }
 
+    #[test]
+    fn test_encode_stream_writes_documents() {
+        let codec = YamlCodec::new();
+        let path = Path::File(FilePath::parse("/test.yaml"));
+        let mut documents = ["a", "b"].into_iter().map(|name| {
+            let mut obj = Object::new();
+            obj.insert("name".to_string(), Value::from(name));
+            let entity = Entity::new(
+                IdentPath::parse("root").unwrap(),
+                "application/yaml",
+                Value::Object(obj),
+            );
+            Document::new(path.clone(), MediaType::TextYaml, vec![entity])
+        });
+        let mut out = Vec::new();
+
+        assert_eq!(codec.encode_stream(&mut documents, &mut out).unwrap(), 2);
+
+        let decoded = codec
+            .decode(Record::new(path.clone(), MediaType::TextYaml, out))
+            .unwrap();
+        let items = decoded.content[0].content.as_array().unwrap();
+
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[1]["name"].as_str(), Some("b"));
+    }
+
     #[test]
     fn test_encode_yaml() {
         let codec = YamlCodec::new();
@@ -124,6 +348,86 @@ mod tests {
         assert_eq!(doc2.content[0].content["test"].as_int(), Some(123));
     }
 
+    #[test]
+    fn test_lossless_rewrite_keeps_key_order() {
+        let codec = YamlCodec::new().with_options(EncodeOptions::default().with_lossless(true));
+        let path = Path::File(FilePath::parse("/config.yaml"));
+        let original = Record::from_str(
+            path,
+            MediaType::TextYaml,
+            "zeta: 1\nalpha: 2\nmodel:\n  name: stress\n  threshold: 0.50\n",
+        );
+        let mut document = codec.decode(original.clone()).unwrap();
+        let root = document.content[0].content.as_object_mut().unwrap();
+        root.insert("alpha".to_string(), Value::from(3));
+        root.insert("beta".to_string(), Value::from(4));
+
+        let record = codec.rewrite(&original, document).unwrap();
+        let text = record.content_str().unwrap();
+
+        assert!(text.contains("threshold: 0.50"));
+        assert!(text.contains("alpha: 3"));
+        assert!(text.find("zeta").unwrap() < text.find("alpha").unwrap());
+        assert!(text.find("model").unwrap() < text.find("beta").unwrap());
+    }
+
+    #[test]
+    fn test_decode_multi_document() {
+        let codec = YamlCodec::new();
+        let path = Path::File(FilePath::parse("/bundle.yaml"));
+        let record = Record::from_str(
+            path,
+            MediaType::TextYaml,
+            "kind: Service\nname: api\n---\nkind: Deployment\nreplicas: 2\n",
+        );
+
+        let document = codec.decode(record).unwrap();
+        let docs = document.content[0].content.as_array().unwrap();
+
+        assert_eq!(docs.len(), 2);
+        assert_eq!(docs[0]["kind"].as_str(), Some("Service"));
+        assert_eq!(docs[1]["replicas"].as_int(), Some(2));
+    }
+
+    #[test]
+    fn test_multi_document_roundtrip() {
+        let codec = YamlCodec::new().with_multi_document(true);
+        let path = Path::File(FilePath::parse("/bundle.yaml"));
+        let original = Record::from_str(
+            path.clone(),
+            MediaType::TextYaml,
+            "kind: Service\n---\nkind: Deployment\n---\n- a\n- b\n",
+        );
+
+        let document = codec.decode(original).unwrap();
+        let record = codec.encode(document).unwrap();
+        let text = record.content_str().unwrap();
+
+        assert_eq!(text.matches("---").count(), 3);
+
+        let doc2 = codec
+            .decode(Record::from_str(path, MediaType::TextYaml, text))
+            .unwrap();
+        let docs = doc2.content[0].content.as_array().unwrap();
+
+        assert_eq!(docs.len(), 3);
+        assert_eq!(docs[1]["kind"].as_str(), Some("Deployment"));
+        assert_eq!(docs[2].as_array().map(|items| items.len()), Some(2));
+    }
+
+    #[test]
+    fn test_multi_document_single_stream() {
+        let codec = YamlCodec::new().with_multi_document(true);
+        let path = Path::File(FilePath::parse("/config.yaml"));
+        let record = Record::from_str(path, MediaType::TextYaml, "- a\n- b\n");
+
+        let document = codec.decode(record).unwrap();
+        let docs = document.content[0].content.as_array().unwrap();
+
+        assert_eq!(docs.len(), 1);
+        assert!(docs[0].is_array());
+    }
+
     #[test]
     fn test_unsupported_media_type() {
         let codec = YamlCodec::new();
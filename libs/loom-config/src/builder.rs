@@ -1,16 +1,23 @@
+use std::collections::BTreeSet;
+
 use loom_core::Format;
-use loom_core::path::Path;
+use loom_core::path::{IdentPath, Path};
 use loom_core::value::{Object, Value};
 
-use super::providers::Provider;
+use super::encryption::{KeyProvider, Keys};
+use super::merge::{MergeStrategies, MergeStrategy};
+use super::providers::{Provider, Secrets, SecretsProvider};
 use super::{Config, ConfigError, Env};
 
 #[derive(Default)]
 pub struct ConfigBuilder {
     providers: Vec<Box<dyn Provider>>,
     env: Option<Env>,
+    profile: Option<String>,
     path: Option<Path>,
     format: Option<Format>,
+    strategies: MergeStrategies,
+    keys: Keys,
 }
 
 impl ConfigBuilder {
@@ -23,11 +30,45 @@ impl ConfigBuilder {
         self
     }
 
+    /// Add a provider of secret values; they bind like any other value but
+    /// are redacted from the config's `Debug` output
+    pub fn with_secrets<P: SecretsProvider + 'static>(mut self, provider: P) -> Self {
+        self.providers.push(Box::new(Secrets(provider)));
+        self
+    }
+
+    /// Decrypt `enc:` values with `provider` when the config is bound. The
+    /// config itself keeps the ciphertext, so it is safe to print or save.
+    pub fn with_key_provider<K: KeyProvider + 'static>(mut self, provider: K) -> Self {
+        self.keys = Keys::new(provider);
+        self
+    }
+
     pub fn with_env(mut self, env: Env) -> Self {
         self.env = Some(env);
         self
     }
 
+    /// Load the overrides of a profile such as `dev`, `staging` or `prod` on
+    /// top of each provider's values. Providers still merge in the order
+    /// added, each immediately followed by its profile's overrides, so with
+    /// a file then an env provider, precedence (lowest first) is:
+    /// `config.yaml`, `config.prod.yaml`, `LOOM_*`, `LOOM_PROD_*`.
+    ///
+    /// The config's [`Env`] defaults to the profile's.
+    pub fn with_profile(mut self, profile: impl Into<String>) -> Self {
+        self.profile = Some(profile.into());
+        self
+    }
+
+    /// Combine the arrays providers set at `path` with `strategy` instead of
+    /// letting the highest-priority one replace the rest, e.g. to add score
+    /// categories in `config.prod.yaml` without repeating the base ones
+    pub fn with_merge_strategy(mut self, path: IdentPath, strategy: MergeStrategy) -> Self {
+        self.strategies.insert(path.to_string(), strategy);
+        self
+    }
+
     pub fn with_path(mut self, path: Path) -> Self {
         self.path = Some(path);
         self
@@ -41,14 +82,36 @@ impl ConfigBuilder {
     pub fn build(self) -> Result<Config, ConfigError> {
         use super::ConfigSource;
 
-        let env = self.env.unwrap_or_else(Env::from_env);
+        let env = self
+            .env
+            .or_else(|| self.profile.as_deref().map(Env::from_str))
+            .unwrap_or_else(Env::from_env);
         let mut merged = Value::Object(Object::new());
         let mut sources = Vec::new();
+        let mut secrets = BTreeSet::new();
+        let mut layers = Vec::new();
+
+        let mut providers = Vec::new();
 
-        for provider in &self.providers {
+        for mut provider in self.providers {
+            let overrides = self
+                .profile
+                .as_deref()
+                .and_then(|profile| provider.profile(profile));
+
+            providers.push(provider);
+            providers.extend(overrides);
+        }
+
+        for provider in &providers {
             match provider.load() {
                 Ok(Some(value)) => {
-                    merged.merge(value);
+                    if provider.secret() {
+                        leaf_paths(&value, "", &mut secrets);
+                    }
+
+                    layers.push(value.clone());
+                    self.strategies.merge(&mut merged, value);
                     sources.push(ConfigSource {
                         name: provider.name().to_string(),
                         path: provider.path().clone(),
@@ -74,10 +137,35 @@ impl ConfigBuilder {
             format: self.format,
             data: merged,
             sources,
+            secrets,
+            layers,
+            set_paths: BTreeSet::new(),
+            keys: self.keys,
         })
     }
 }
 
+/// Dotted paths of the non-object values under `value` (at `path`)
+fn leaf_paths(value: &Value, path: &str, paths: &mut BTreeSet<String>) {
+    match value {
+        Value::Object(object) => {
+            for (key, child) in object.iter() {
+                let child_path = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", path, key)
+                };
+
+                leaf_paths(child, &child_path, paths);
+            }
+        }
+        _ if !path.is_empty() => {
+            paths.insert(path.to_string());
+        }
+        _ => {}
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::super::providers::MemoryProvider;
@@ -159,4 +247,82 @@ mod tests {
         assert!(config.path().is_some());
         assert_eq!(config.format(), Some(Format::Json));
     }
+
+    #[test]
+    fn test_builder_with_profile() {
+        use super::super::providers::{EnvProvider, FileProvider};
+
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("config.json"),
+            r#"{"database": {"host": "localhost", "port": 5432}, "workers": 2}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("config.prod.json"),
+            r#"{"database": {"host": "db.internal"}, "workers": 8}"#,
+        )
+        .unwrap();
+
+        let config = Config::new()
+            .with_provider(FileProvider::builder(dir.path().join("config.json")).build())
+            .with_provider(EnvProvider::new(Some("LOOM_TEST_PROFILE_")))
+            .with_profile("prod")
+            .build()
+            .unwrap();
+
+        let host = IdentPath::parse("database.host").unwrap();
+        let port = IdentPath::parse("database.port").unwrap();
+        let workers = IdentPath::parse("workers").unwrap();
+        assert_eq!(config.get_str(&host), Some("db.internal"));
+        assert_eq!(config.get_int(&port), Some(5432));
+        assert_eq!(config.get_int(&workers), Some(8));
+        assert!(config.env().is_prod());
+        assert_eq!(config.sources().len(), 2);
+
+        // A missing profile file is skipped
+        let config = Config::new()
+            .with_provider(FileProvider::builder(dir.path().join("config.json")).build())
+            .with_profile("dev")
+            .build()
+            .unwrap();
+
+        assert_eq!(config.get_str(&host), Some("localhost"));
+        assert!(config.env().is_dev());
+    }
+
+    #[test]
+    fn test_builder_with_merge_strategy() {
+        use loom_core::value::Array;
+
+        let tags = |items: &[&str]| {
+            MemoryProvider::from_value({
+                let mut root = Object::new();
+                root.insert(
+                    "tags".to_string(),
+                    Value::Array(Array::from(items.to_vec())),
+                );
+                Value::Object(root)
+            })
+        };
+        let path = IdentPath::parse("tags").unwrap();
+
+        let config = Config::new()
+            .with_provider(tags(&["a", "b"]))
+            .with_provider(tags(&["c"]))
+            .build()
+            .unwrap();
+        assert_eq!(config.get(&path), Some(&Value::Array(Array::from(["c"]))));
+
+        let config = Config::new()
+            .with_provider(tags(&["a", "b"]))
+            .with_provider(tags(&["c"]))
+            .with_merge_strategy(path.clone(), MergeStrategy::Append)
+            .build()
+            .unwrap();
+        assert_eq!(
+            config.get(&path),
+            Some(&Value::Array(Array::from(["a", "b", "c"])))
+        );
+    }
 }
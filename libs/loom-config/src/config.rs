@@ -1,10 +1,16 @@
+use std::collections::BTreeSet;
+
 use serde::de::DeserializeOwned;
+use serde::ser::SerializeStruct;
 
 use loom_core::Format;
+pub use loom_core::REDACTED;
 use loom_core::path::{IdentPath, Path};
-use loom_core::value::Value;
+use loom_core::value::{Change, Object, Value};
 
-use super::{ConfigBuilder, ConfigError, ConfigSection, Env};
+use super::encryption::Keys;
+use super::providers::{FileProvider, MemoryProvider, Provider};
+use super::{ConfigBuilder, ConfigError, ConfigSection, Env, Explanation, Override};
 
 #[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
 pub struct ConfigSource {
@@ -13,13 +19,30 @@ pub struct ConfigSource {
     pub format: Format,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[derive(Clone, PartialEq, Eq, serde::Deserialize)]
 pub struct Config {
     pub(crate) env: Env,
     pub(crate) data: Value,
     pub(crate) path: Option<Path>,
     pub(crate) format: Option<Format>,
     pub(crate) sources: Vec<ConfigSource>,
+
+    /// Dotted paths of the values loaded by secret providers
+    #[serde(default)]
+    pub(crate) secrets: BTreeSet<String>,
+
+    /// Values loaded by each of `sources`, to explain where a value came from
+    #[serde(skip)]
+    pub(crate) layers: Vec<Value>,
+
+    /// Dotted paths [`set`](Config::set) after loading, whose values no
+    /// source supplied
+    #[serde(skip)]
+    pub(crate) set_paths: BTreeSet<String>,
+
+    /// Decrypts `enc:` values when binding
+    #[serde(skip)]
+    pub(crate) keys: Keys,
 }
 
 impl Config {
@@ -69,11 +92,63 @@ impl Config {
 
     pub fn get_section(&self, path: &IdentPath) -> ConfigSection {
         let value = self.get(path).cloned().unwrap_or(Value::Null);
-        ConfigSection::new(value, path.clone())
+        ConfigSection::new(value, path.clone()).with_keys(self.keys.clone())
     }
 
     pub fn root_section(&self) -> ConfigSection {
-        ConfigSection::root(self.data.clone())
+        ConfigSection::root(self.data.clone()).with_keys(self.keys.clone())
+    }
+
+    /// Set the value at `path`, adding any missing sections on the way, e.g.
+    /// to update a threshold before [`save`](Config::save)-ing it
+    pub fn set(&mut self, path: &IdentPath, value: impl Into<Value>) {
+        MemoryProvider::set_by_path(&mut self.data, path, value.into());
+        self.set_paths.insert(path.to_string());
+    }
+
+    /// Whether the value at `path`, or a section holding it, was
+    /// [`set`](Config::set) after loading
+    fn is_set(&self, path: &IdentPath) -> bool {
+        let path = path.to_string();
+
+        self.set_paths.iter().any(|set| {
+            path.starts_with(set.as_str())
+                && matches!(path.as_bytes().get(set.len()), None | Some(b'.' | b'['))
+        })
+    }
+
+    /// Whether the value at `path` was loaded by a secrets provider, or is a
+    /// section holding such a value
+    pub fn is_secret(&self, path: &IdentPath) -> bool {
+        let path = path.to_string();
+
+        self.secrets.iter().any(|secret| {
+            secret.starts_with(&path)
+                && matches!(secret.as_bytes().get(path.len()), None | Some(b'.'))
+        }) || self.secrets.iter().any(|secret| {
+            path.starts_with(secret.as_str())
+                && matches!(path.as_bytes().get(secret.len()), Some(b'.' | b'['))
+        })
+    }
+
+    /// The text of every secret value and decrypted `enc:` value, for
+    /// redacting it wherever it is printed, e.g. by a signal emitter
+    pub fn secret_values(&self) -> Vec<String> {
+        self.secrets
+            .iter()
+            .filter_map(|secret| IdentPath::parse(secret).ok())
+            .filter_map(|path| self.get(&path))
+            .map(|value| value.to_string())
+            .chain(self.keys.plaintexts(&self.data))
+            .filter(|value| !value.is_empty())
+            .collect()
+    }
+
+    /// The config's data with every secret value replaced by [`REDACTED`]
+    pub fn redacted(&self) -> Value {
+        let mut data = self.data.clone();
+        redact(&mut data, "", &self.secrets);
+        data
     }
 
     pub fn merge(self, other: Self) -> Self {
@@ -83,15 +158,152 @@ impl Config {
         let mut sources = self.sources;
         sources.extend(other.sources);
 
+        let mut secrets = self.secrets;
+        secrets.extend(other.secrets);
+
+        let mut layers = self.layers;
+        layers.extend(other.layers);
+
+        let mut set_paths = self.set_paths;
+        set_paths.extend(other.set_paths);
+
+        let keys = self.keys.or(other.keys);
+
         Self {
             env: self.env,
             data,
             path: self.path.or(other.path),
             format: self.format.or(other.format),
             sources,
+            secrets,
+            layers,
+            set_paths,
+            keys,
+        }
+    }
+
+    /// Which source supplied the value at `path` and which lower-priority
+    /// sources' values it overrode. A value [`set`](Config::set) after
+    /// loading has no source and overrides every source's value. Secret
+    /// values are shown as [`REDACTED`].
+    pub fn explain(&self, path: &IdentPath) -> Explanation {
+        let secret = self.is_secret(path);
+        let shown = |value: &Value| {
+            if secret {
+                Value::from(REDACTED)
+            } else {
+                value.clone()
+            }
+        };
+
+        let set = self.is_set(path);
+        let mut layers = self
+            .sources
+            .iter()
+            .zip(&self.layers)
+            .rev()
+            .filter_map(|(source, layer)| Some((source, layer.get_by_path(path)?)));
+
+        let source = if set {
+            None
+        } else {
+            layers.next().map(|(source, _)| source.clone())
+        };
+        let overridden = layers
+            .map(|(source, value)| Override {
+                source: source.clone(),
+                value: shown(value),
+            })
+            .collect();
+
+        Explanation {
+            path: path.clone(),
+            value: self.get(path).map(shown),
+            source,
+            set,
+            overridden,
+        }
+    }
+
+    /// The changes that turn this config's values into `other`'s, with
+    /// secret values of either shown as [`REDACTED`]
+    pub fn diff(&self, other: &Config) -> Vec<Change> {
+        self.redacted().diff(&other.redacted())
+    }
+
+    /// Write the effective config back through the file source named
+    /// `provider` (a `FileProvider`'s name is its path), in its format.
+    /// Values of secret providers are left out.
+    pub fn save(&self, provider: &str) -> Result<(), ConfigError> {
+        let source = self.writable_source(provider)?;
+        write_value(&self.without_secrets(), &source.path, source.format)
+    }
+
+    /// Replace the section at `path` in the file source named `provider`
+    /// with its effective value, keeping the rest of the file as it is, e.g.
+    /// to persist tuned `layers.score` parameters without also writing out
+    /// values from environment overrides
+    pub fn save_section(&self, provider: &str, path: &IdentPath) -> Result<(), ConfigError> {
+        let source = self.writable_source(provider)?;
+        let section = self
+            .without_secrets()
+            .get_by_path(path)
+            .cloned()
+            .ok_or_else(|| ConfigError::not_found(path.to_string()))?;
+
+        // The file as written, without its `$include`s resolved
+        let Path::File(file) = &source.path else {
+            unreachable!("writable sources are files");
+        };
+        let mut data = FileProvider::builder(file.to_path_buf())
+            .format(source.format)
+            .with_includes(false)
+            .build()
+            .load()?
+            .unwrap_or_else(|| Value::Object(Object::new()));
+
+        MemoryProvider::set_by_path(&mut data, path, section);
+        write_value(&data, &source.path, source.format)
+    }
+
+    fn writable_source(&self, provider: &str) -> Result<&ConfigSource, ConfigError> {
+        let source = self
+            .sources
+            .iter()
+            .find(|source| source.name == provider)
+            .ok_or_else(|| ConfigError::not_found(provider))?;
+
+        match source.path {
+            Path::File(_) => Ok(source),
+            _ => Err(ConfigError::provider(format!(
+                "config source '{}' is not writable",
+                provider
+            ))),
         }
     }
 
+    /// The config's data without the values of secret providers
+    fn without_secrets(&self) -> Value {
+        let mut data = self.data.clone();
+
+        for secret in &self.secrets {
+            let (parent, key) = match secret.rsplit_once('.') {
+                Some((parent, key)) => (IdentPath::parse(parent).ok(), key),
+                None => (None, secret.as_str()),
+            };
+            let parent = match &parent {
+                Some(parent) => data.get_by_path_mut(parent),
+                None => Some(&mut data),
+            };
+
+            if let Some(Value::Object(object)) = parent {
+                object.remove(key);
+            }
+        }
+
+        data
+    }
+
     pub fn write(&self) -> Result<(), ConfigError> {
         let path = self
             .path
@@ -105,54 +317,113 @@ impl Config {
     }
 
     pub fn write_to(&self, path: Path, format: Format) -> Result<(), ConfigError> {
-        let file_path: &std::path::Path = match &path {
-            Path::File(fp) => fp,
-            _ => return Err(ConfigError::provider("Can only write to file paths")),
-        };
-
-        #[cfg(feature = "json")]
-        if format == Format::Json {
-            let json: serde_json::Value = (&self.data).into();
-            let content = serde_json::to_string_pretty(&json).map_err(ConfigError::parse)?;
-            std::fs::write(file_path, content)?;
-            return Ok(());
-        }
-
-        #[cfg(feature = "yaml")]
-        if format == Format::Yaml {
-            let yaml: saphyr::Yaml = (&self.data).into();
-            let mut out = String::new();
-            let mut emitter = saphyr::YamlEmitter::new(&mut out);
-            emitter.dump(&yaml).map_err(ConfigError::parse)?;
-            std::fs::write(file_path, out)?;
-            return Ok(());
-        }
-
-        #[cfg(feature = "toml")]
-        if format == Format::Toml {
-            let toml_value: toml::Value = (&self.data).into();
-            let content = toml::to_string_pretty(&toml_value).map_err(ConfigError::parse)?;
-            std::fs::write(file_path, content)?;
-            return Ok(());
-        }
-
-        Err(ConfigError::provider(format!(
-            "Unsupported format: {:?}",
-            format
-        )))
+        write_value(&self.data, &path, format)
     }
 
+    /// Deserialize the config into `T`, decrypting the `enc:` values it
+    /// reads on the way; values `T` has no field for aren't decrypted
     pub fn bind<T: DeserializeOwned>(&self) -> Result<T, ConfigError> {
-        let json: serde_json::Value = (&self.data).into();
-        serde_json::from_value(json).map_err(ConfigError::deserialize)
+        self.keys.bind(self.data.clone(), "")
     }
 
     pub fn bind_section<T: DeserializeOwned>(&self, path: &IdentPath) -> Result<T, ConfigError> {
         let value = self
             .get(path)
             .ok_or_else(|| ConfigError::not_found(path.to_string()))?;
-        let json: serde_json::Value = value.into();
-        serde_json::from_value(json).map_err(ConfigError::deserialize)
+        self.keys.bind(value.clone(), &path.to_string())
+    }
+
+    /// Check the config against the type it binds to, reporting every
+    /// unknown key, missing field and mismatched value by its full path
+    /// rather than the first error [`bind`](Config::bind) runs into
+    pub fn validate_against<T: DeserializeOwned>(&self) -> Result<(), ConfigError> {
+        self.root_section().validate::<T>()
+    }
+}
+
+impl std::fmt::Debug for Config {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Config")
+            .field("env", &self.env)
+            .field("data", &self.redacted())
+            .field("path", &self.path)
+            .field("format", &self.format)
+            .field("sources", &self.sources)
+            .field("secrets", &self.secrets)
+            .finish()
+    }
+}
+
+/// Serialized like `Debug`, with every secret value replaced by [`REDACTED`]
+impl serde::Serialize for Config {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("Config", 6)?;
+        state.serialize_field("env", &self.env)?;
+        state.serialize_field("data", &self.redacted())?;
+        state.serialize_field("path", &self.path)?;
+        state.serialize_field("format", &self.format)?;
+        state.serialize_field("sources", &self.sources)?;
+        state.serialize_field("secrets", &self.secrets)?;
+        state.end()
+    }
+}
+
+/// Write `data` to the file at `path` in `format`
+fn write_value(data: &Value, path: &Path, format: Format) -> Result<(), ConfigError> {
+    let file_path: &std::path::Path = match path {
+        Path::File(fp) => fp,
+        _ => return Err(ConfigError::provider("Can only write to file paths")),
+    };
+
+    #[cfg(feature = "json")]
+    if format == Format::Json {
+        let json: serde_json::Value = data.into();
+        let content = serde_json::to_string_pretty(&json).map_err(ConfigError::parse)?;
+        std::fs::write(file_path, content)?;
+        return Ok(());
+    }
+
+    #[cfg(feature = "yaml")]
+    if format == Format::Yaml {
+        let yaml: saphyr::Yaml = data.into();
+        let mut out = String::new();
+        let mut emitter = saphyr::YamlEmitter::new(&mut out);
+        emitter.dump(&yaml).map_err(ConfigError::parse)?;
+        std::fs::write(file_path, out)?;
+        return Ok(());
+    }
+
+    #[cfg(feature = "toml")]
+    if format == Format::Toml {
+        let toml_value: toml::Value = data.into();
+        let content = toml::to_string_pretty(&toml_value).map_err(ConfigError::parse)?;
+        std::fs::write(file_path, content)?;
+        return Ok(());
+    }
+
+    Err(ConfigError::provider(format!(
+        "Unsupported format: {:?}",
+        format
+    )))
+}
+
+/// Replace the values at `secrets` under `value` (at `path`) with [`REDACTED`]
+fn redact(value: &mut Value, path: &str, secrets: &BTreeSet<String>) {
+    if !path.is_empty() && secrets.contains(path) {
+        *value = Value::String(REDACTED.to_string());
+        return;
+    }
+
+    if let Value::Object(object) = value {
+        for (key, child) in object.iter_mut() {
+            let child_path = if path.is_empty() {
+                key.clone()
+            } else {
+                format!("{}.{}", path, key)
+            };
+
+            redact(child, &child_path, secrets);
+        }
     }
 }
 
@@ -295,4 +566,275 @@ mod tests {
             }
         );
     }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_bind_decrypts_values() {
+        use crate::KeyProvider;
+
+        struct Reverse;
+
+        impl KeyProvider for Reverse {
+            fn name(&self) -> &str {
+                "reverse"
+            }
+
+            fn decrypt(&self, ciphertext: &str) -> Result<String, ConfigError> {
+                Ok(ciphertext.chars().rev().collect())
+            }
+        }
+
+        // Plaintexts bind as strings, even when they look like numbers
+        #[derive(Debug, serde::Deserialize, PartialEq)]
+        struct Database {
+            url: String,
+            port: String,
+        }
+
+        let provider = || {
+            MemoryProvider::from_pairs([
+                ("database.url", "enc:ppa/bd@2retnuh:ppa//:sergtsop"),
+                ("database.port", "enc:2345"),
+            ])
+        };
+        let path = IdentPath::parse("database").unwrap();
+        let config = Config::new()
+            .with_provider(provider())
+            .with_key_provider(Reverse)
+            .build()
+            .unwrap();
+
+        let expected = Database {
+            url: "postgres://app:hunter2@db/app".to_string(),
+            port: "5432".to_string(),
+        };
+        assert_eq!(config.bind_section::<Database>(&path).unwrap(), expected);
+        assert_eq!(
+            config.get_section(&path).bind::<Database>().unwrap(),
+            expected
+        );
+        assert!(
+            config
+                .root_section()
+                .get_section("database")
+                .validate::<Database>()
+                .is_ok()
+        );
+        assert!(config.secret_values().contains(&expected.url));
+
+        // The config keeps the ciphertext
+        let url = IdentPath::parse("database.url").unwrap();
+        assert!(
+            config
+                .get_str(&url)
+                .unwrap()
+                .starts_with(crate::ENCRYPTED_PREFIX)
+        );
+
+        let config = Config::new().with_provider(provider()).build().unwrap();
+        let err = config.bind_section::<Database>(&path).unwrap_err();
+        assert!(err.is_decrypt());
+
+        // Values the bound type doesn't read aren't decrypted
+        #[derive(Debug, serde::Deserialize)]
+        struct Root {
+            name: String,
+        }
+
+        let config = Config::new()
+            .with_provider(provider())
+            .with_provider(MemoryProvider::from_pairs([("name", "app")]))
+            .build()
+            .unwrap();
+        assert_eq!(config.bind::<Root>().unwrap().name, "app");
+    }
+
+    #[test]
+    fn test_secrets_are_redacted() {
+        use crate::providers::SecretsProvider;
+        use std::collections::BTreeMap;
+
+        struct Vault;
+
+        impl SecretsProvider for Vault {
+            fn name(&self) -> &str {
+                "vault"
+            }
+
+            fn load(&self) -> Result<BTreeMap<String, String>, ConfigError> {
+                Ok(BTreeMap::from([(
+                    "database.password".to_string(),
+                    "hunter2".to_string(),
+                )]))
+            }
+        }
+
+        let config = Config::new()
+            .with_provider(MemoryProvider::from_pairs([("database.host", "localhost")]))
+            .with_secrets(Vault)
+            .build()
+            .unwrap();
+
+        let password = IdentPath::parse("database.password").unwrap();
+        assert_eq!(config.get_str(&password), Some("hunter2"));
+        assert!(config.is_secret(&password));
+        assert!(config.is_secret(&IdentPath::parse("database").unwrap()));
+        assert!(!config.is_secret(&IdentPath::parse("database.host").unwrap()));
+        assert_eq!(config.secret_values(), ["hunter2"]);
+        assert!(config.without_secrets().get_by_path(&password).is_none());
+
+        let debug = format!("{:?}", config);
+        assert!(!debug.contains("hunter2"));
+        assert!(debug.contains(REDACTED));
+        assert!(debug.contains("localhost"));
+
+        #[cfg(feature = "json")]
+        {
+            let json = serde_json::to_string(&config).unwrap();
+            assert!(!json.contains("hunter2"));
+            assert!(json.contains(REDACTED));
+
+            #[derive(serde::Deserialize)]
+            struct Database {
+                password: String,
+            }
+
+            let database: Database = config
+                .bind_section(&IdentPath::parse("database").unwrap())
+                .unwrap();
+            assert_eq!(database.password, "hunter2");
+        }
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_save() {
+        use crate::FileProvider;
+
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("config.json");
+        std::fs::write(&file, r#"{"layers": {"score": {"threshold": 0.5}}}"#).unwrap();
+        let name = file.to_str().unwrap();
+
+        let mut config = Config::new()
+            .with_provider(FileProvider::builder(&file).build())
+            .with_provider(MemoryProvider::from_pairs([("workers", "8")]))
+            .build()
+            .unwrap();
+        let threshold = IdentPath::parse("layers.score.threshold").unwrap();
+        config.set(&threshold, 0.75);
+
+        // Only the section, so the memory override stays out of the file
+        config
+            .save_section(name, &IdentPath::parse("layers.score").unwrap())
+            .unwrap();
+        let saved = Config::new()
+            .with_provider(FileProvider::builder(&file).build())
+            .build()
+            .unwrap();
+        assert_eq!(saved.get_float(&threshold), Some(0.75));
+        assert!(saved.get(&IdentPath::parse("workers").unwrap()).is_none());
+
+        // The effective config
+        config.save(name).unwrap();
+        let saved = Config::new()
+            .with_provider(FileProvider::builder(&file).build())
+            .build()
+            .unwrap();
+        assert_eq!(
+            saved.get_str(&IdentPath::parse("workers").unwrap()),
+            Some("8")
+        );
+
+        assert!(config.save("memory").unwrap_err().is_provider());
+        assert!(config.save("missing.json").unwrap_err().is_not_found());
+    }
+
+    #[test]
+    fn test_explain() {
+        let config = Config::new()
+            .with_provider(MemoryProvider::from_pairs([
+                ("batch_size", "16"),
+                ("database.host", "localhost"),
+            ]))
+            .with_provider(MemoryProvider::from_pairs([("batch_size", "8")]))
+            .with_provider(MemoryProvider::from_pairs([("batch_size", "4")]))
+            .build()
+            .unwrap();
+
+        let explanation = config.explain(&IdentPath::parse("batch_size").unwrap());
+        assert_eq!(explanation.value, Some(Value::from("4")));
+        assert!(explanation.source.is_some());
+        assert_eq!(
+            explanation
+                .overridden
+                .iter()
+                .map(|over| over.value.clone())
+                .collect::<Vec<_>>(),
+            [Value::from("8"), Value::from("16")]
+        );
+        assert_eq!(
+            explanation.to_string(),
+            "batch_size = 4 (from memory)\n  overrides 8 from memory\n  overrides 16 from memory"
+        );
+
+        let explanation = config.explain(&IdentPath::parse("database.port").unwrap());
+        assert!(explanation.value.is_none() && explanation.source.is_none());
+    }
+
+    #[test]
+    fn test_explain_after_set() {
+        let mut config = Config::new()
+            .with_provider(MemoryProvider::from_pairs([
+                ("threshold", "0.5"),
+                ("database.host", "localhost"),
+                ("database.port", "5432"),
+            ]))
+            .build()
+            .unwrap();
+
+        config.set(&IdentPath::parse("threshold").unwrap(), 0.75);
+        config.set(
+            &IdentPath::parse("database").unwrap(),
+            Value::Object(Object::new()),
+        );
+
+        let explanation = config.explain(&IdentPath::parse("threshold").unwrap());
+        assert_eq!(explanation.value, Some(Value::from(0.75)));
+        assert!(explanation.set && explanation.source.is_none());
+        assert_eq!(
+            explanation.to_string(),
+            "threshold = 0.75 (set programmatically)\n  overrides 0.5 from memory"
+        );
+
+        let explanation = config.explain(&IdentPath::parse("database.port").unwrap());
+        assert!(explanation.value.is_none() && explanation.set);
+
+        let explanation = config.explain(&IdentPath::parse("database_url").unwrap());
+        assert!(!explanation.set);
+    }
+
+    #[test]
+    fn test_diff() {
+        let prod = Config::new()
+            .with_provider(MemoryProvider::from_pairs([
+                ("batch_size", "4"),
+                ("name", "merc"),
+            ]))
+            .build()
+            .unwrap();
+        let dev = Config::new()
+            .with_provider(MemoryProvider::from_pairs([
+                ("batch_size", "32"),
+                ("name", "merc"),
+            ]))
+            .build()
+            .unwrap();
+
+        let changes = prod.diff(&dev);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].path, "batch_size");
+        assert_eq!(changes[0].before, Some(Value::from("4")));
+        assert_eq!(changes[0].after, Some(Value::from("32")));
+    }
 }
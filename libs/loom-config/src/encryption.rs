@@ -0,0 +1,422 @@
+use std::sync::Arc;
+
+use loom_core::value::Value;
+use serde::de::DeserializeOwned;
+
+use super::ConfigError;
+
+/// Marks a config value as encrypted, e.g. `url: enc:q2Vk...`
+pub const ENCRYPTED_PREFIX: &str = "enc:";
+
+/// Decrypts `enc:` config values when a config is bound, so database URLs
+/// and API keys can live in the same file as the settings around them.
+///
+/// Add one with
+/// [`ConfigBuilder::with_key_provider`](crate::ConfigBuilder::with_key_provider).
+/// [`EnvKey`] decrypts with an AES-256-GCM key from an env var (`encryption`
+/// feature); implement this trait to decrypt with a KMS instead.
+pub trait KeyProvider: Send + Sync {
+    fn name(&self) -> &str;
+
+    /// Plaintext of `ciphertext`, the text of a value after `enc:`
+    fn decrypt(&self, ciphertext: &str) -> Result<String, ConfigError>;
+}
+
+/// The key provider of a config and its sections, if any
+#[derive(Clone, Default)]
+pub(crate) struct Keys(Option<Arc<dyn KeyProvider>>);
+
+impl Keys {
+    pub(crate) fn new<K: KeyProvider + 'static>(provider: K) -> Self {
+        Self(Some(Arc::new(provider)))
+    }
+
+    /// These keys if set, else `other`
+    pub(crate) fn or(self, other: Self) -> Self {
+        if self.0.is_some() { self } else { other }
+    }
+
+    /// `value` (at `path`) with every `enc:` string under it decrypted.
+    /// Plaintexts stay strings, whatever they look like.
+    pub(crate) fn decrypt(&self, mut value: Value, path: &str) -> Result<Value, ConfigError> {
+        let mut failed = Vec::new();
+        self.decrypt_in(&mut value, path, String::new(), &mut failed);
+
+        match failed.into_iter().next() {
+            Some((_, err)) => Err(err),
+            None => Ok(value),
+        }
+    }
+
+    /// Deserialize `value` (at `path`) into `T`, decrypting `enc:` values on
+    /// the way. A value that can't be decrypted only fails the bind when `T`
+    /// reads it, so one bad secret doesn't break binding unrelated settings.
+    pub(crate) fn bind<T: DeserializeOwned>(
+        &self,
+        mut value: Value,
+        path: &str,
+    ) -> Result<T, ConfigError> {
+        let mut failed = Vec::new();
+        self.decrypt_in(&mut value, path, String::new(), &mut failed);
+        let json: serde_json::Value = (&value).into();
+
+        if failed.is_empty() {
+            return serde_json::from_value(json).map_err(ConfigError::deserialize);
+        }
+
+        let mut ignored = Vec::new();
+        let result = serde_ignored::deserialize(json, |path| ignored.push(relative(&path)));
+
+        // An ignored key is skipped with everything under it
+        let read = failed.into_iter().find(|(failed, _)| {
+            !ignored.iter().any(|ignored| {
+                failed.starts_with(ignored.as_str())
+                    && matches!(
+                        failed.as_bytes().get(ignored.len()),
+                        None | Some(b'.' | b'[')
+                    )
+            })
+        });
+
+        match read {
+            Some((_, err)) => Err(err),
+            None => result.map_err(ConfigError::deserialize),
+        }
+    }
+
+    /// Decrypt every `enc:` string under `value`, which is at `rel` within
+    /// the value being decrypted (at `path`), collecting the relative paths
+    /// of those that fail, which keep their ciphertext
+    fn decrypt_in(
+        &self,
+        value: &mut Value,
+        path: &str,
+        rel: String,
+        failed: &mut Vec<(String, ConfigError)>,
+    ) {
+        match value {
+            Value::String(text) => {
+                let Some(ciphertext) = text.strip_prefix(ENCRYPTED_PREFIX) else {
+                    return;
+                };
+
+                let full = match (path.is_empty(), rel.is_empty()) {
+                    (_, true) => path.to_string(),
+                    (true, false) => rel.clone(),
+                    (false, false) if rel.starts_with('[') => format!("{}{}", path, rel),
+                    (false, false) => format!("{}.{}", path, rel),
+                };
+
+                let Some(provider) = self.0.as_ref() else {
+                    failed.push((
+                        rel,
+                        ConfigError::decrypt(format!(
+                            "'{}' is encrypted but no key provider is set",
+                            full
+                        )),
+                    ));
+                    return;
+                };
+
+                match provider.decrypt(ciphertext) {
+                    Ok(plaintext) => *value = Value::String(plaintext),
+                    Err(err) => failed.push((
+                        rel,
+                        ConfigError::decrypt(format!(
+                            "'{}' could not be decrypted by {}: {}",
+                            full,
+                            provider.name(),
+                            err
+                        )),
+                    )),
+                }
+            }
+            Value::Array(array) => {
+                for (i, child) in array.iter_mut().enumerate() {
+                    self.decrypt_in(child, path, format!("{}[{}]", rel, i), failed);
+                }
+            }
+            Value::Object(object) => {
+                for (key, child) in object.iter_mut() {
+                    let child_rel = if rel.is_empty() {
+                        key.clone()
+                    } else {
+                        format!("{}.{}", rel, key)
+                    };
+
+                    self.decrypt_in(child, path, child_rel, failed);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Plaintext of every `enc:` string under `value` that decrypts, for
+    /// redacting it wherever it is printed
+    pub(crate) fn plaintexts(&self, value: &Value) -> Vec<String> {
+        let Some(provider) = &self.0 else {
+            return Vec::new();
+        };
+
+        match value {
+            Value::String(text) => text
+                .strip_prefix(ENCRYPTED_PREFIX)
+                .and_then(|ciphertext| provider.decrypt(ciphertext).ok())
+                .into_iter()
+                .collect(),
+            Value::Array(array) => array.iter().flat_map(|v| self.plaintexts(v)).collect(),
+            Value::Object(object) => object.values().flat_map(|v| self.plaintexts(v)).collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// `path` in the form of [`Keys::decrypt_in`]'s relative paths, without the
+/// `Option` and newtype wrappers that don't appear in the config
+fn relative(path: &serde_ignored::Path) -> String {
+    use serde_ignored::Path;
+
+    match path {
+        Path::Root => String::new(),
+        Path::Seq { parent, index } => format!("{}[{}]", relative(parent), index),
+        Path::Map { parent, key } => match relative(parent) {
+            parent if parent.is_empty() => key.clone(),
+            parent => format!("{}.{}", parent, key),
+        },
+        Path::Some { parent }
+        | Path::NewtypeStruct { parent }
+        | Path::NewtypeVariant { parent } => relative(parent),
+    }
+}
+
+// Keys don't change what a config holds, only how it binds
+impl PartialEq for Keys {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for Keys {}
+
+impl std::fmt::Debug for Keys {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.0 {
+            Some(provider) => write!(f, "Keys({})", provider.name()),
+            None => write!(f, "Keys(None)"),
+        }
+    }
+}
+
+#[cfg(feature = "encryption")]
+mod env_key {
+    use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+    use aes_gcm::{Aes256Gcm, Key, Nonce};
+    use base64::Engine;
+    use base64::engine::general_purpose::STANDARD as BASE64;
+
+    use super::{ConfigError, ENCRYPTED_PREFIX, KeyProvider};
+
+    const NONCE_LEN: usize = 12;
+
+    /// Decrypts `enc:` values with an AES-256-GCM key, given base64-encoded
+    /// in an env var (`encryption` feature)
+    ///
+    /// Values are `enc:` followed by the base64 of the 12 byte nonce and the
+    /// ciphertext; [`encrypt`](EnvKey::encrypt) writes them.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let config = Config::new()
+    ///     .with_provider(FileProvider::builder("config.yaml").build())
+    ///     .with_key_provider(EnvKey::new("LOOM_CONFIG_KEY")?)
+    ///     .build()?;
+    /// ```
+    pub struct EnvKey {
+        var: String,
+        aes: Aes256Gcm,
+    }
+
+    impl EnvKey {
+        /// Read the key from the env var `var`
+        pub fn new(var: impl Into<String>) -> Result<Self, ConfigError> {
+            let var = var.into();
+            let key = std::env::var(&var)
+                .map_err(|_| ConfigError::decrypt(format!("key env var {} is not set", var)))?;
+            let key = BASE64
+                .decode(key.trim())
+                .map_err(|_| ConfigError::decrypt(format!("key in {} is not base64", var)))?;
+
+            Self::from_bytes(var, &key)
+        }
+
+        /// Use `key`, 32 bytes, named `name` in errors
+        pub fn from_bytes(name: impl Into<String>, key: &[u8]) -> Result<Self, ConfigError> {
+            let var = name.into();
+
+            if key.len() != 32 {
+                return Err(ConfigError::decrypt(format!(
+                    "key {} must be 32 bytes, found {}",
+                    var,
+                    key.len()
+                )));
+            }
+
+            Ok(Self {
+                var,
+                aes: Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key)),
+            })
+        }
+
+        /// A new random key, base64-encoded for an env var
+        pub fn generate() -> String {
+            BASE64.encode(Aes256Gcm::generate_key(&mut OsRng))
+        }
+
+        /// `plaintext` as an `enc:` value to put in a config file
+        pub fn encrypt(&self, plaintext: &str) -> Result<String, ConfigError> {
+            let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+            let ciphertext = self
+                .aes
+                .encrypt(&nonce, plaintext.as_bytes())
+                .map_err(|_| ConfigError::decrypt("encryption failed"))?;
+
+            let mut bytes = nonce.to_vec();
+            bytes.extend(ciphertext);
+            Ok(format!("{}{}", ENCRYPTED_PREFIX, BASE64.encode(bytes)))
+        }
+    }
+
+    impl KeyProvider for EnvKey {
+        fn name(&self) -> &str {
+            &self.var
+        }
+
+        fn decrypt(&self, ciphertext: &str) -> Result<String, ConfigError> {
+            let invalid = || ConfigError::decrypt("invalid ciphertext or wrong key");
+            let bytes = BASE64.decode(ciphertext).map_err(|_| invalid())?;
+
+            if bytes.len() < NONCE_LEN {
+                return Err(invalid());
+            }
+
+            let (nonce, ciphertext) = bytes.split_at(NONCE_LEN);
+            let plaintext = self
+                .aes
+                .decrypt(Nonce::from_slice(nonce), ciphertext)
+                .map_err(|_| invalid())?;
+
+            String::from_utf8(plaintext).map_err(|_| invalid())
+        }
+    }
+}
+
+#[cfg(feature = "encryption")]
+pub use env_key::EnvKey;
+
+#[cfg(test)]
+mod tests {
+    use loom_core::value::Object;
+
+    use super::*;
+
+    /// Reverses the ciphertext, enough to tell decrypted values apart
+    struct Reverse;
+
+    impl KeyProvider for Reverse {
+        fn name(&self) -> &str {
+            "reverse"
+        }
+
+        fn decrypt(&self, ciphertext: &str) -> Result<String, ConfigError> {
+            Ok(ciphertext.chars().rev().collect())
+        }
+    }
+
+    fn value() -> Value {
+        let mut database = Object::new();
+        database.insert("url".to_string(), Value::from("enc:ppa/tsohlacol"));
+        database.insert("port".to_string(), Value::from("enc:2345"));
+        database.insert("pool".to_string(), Value::from(8i64));
+
+        let mut root = Object::new();
+        root.insert("database".to_string(), Value::Object(database));
+        Value::Object(root)
+    }
+
+    #[test]
+    fn test_decrypt() {
+        let value = Keys::new(Reverse).decrypt(value(), "").unwrap();
+        let database = value.as_object().unwrap()["database"].as_object().unwrap();
+
+        assert_eq!(database["url"], Value::from("localhost/app"));
+        assert_eq!(database["port"], Value::from("5432"));
+        assert_eq!(database["pool"], Value::from(8i64));
+    }
+
+    #[test]
+    fn test_decrypt_without_provider() {
+        let err = Keys::default().decrypt(value(), "").unwrap_err();
+
+        assert!(err.is_decrypt());
+        assert!(err.to_string().contains("database.port"));
+    }
+
+    #[test]
+    fn test_bind_skips_unread_values() {
+        #[derive(Debug, serde::Deserialize)]
+        struct Database {
+            pool: i64,
+        }
+
+        #[derive(Debug, serde::Deserialize)]
+        struct Root {
+            database: Database,
+        }
+
+        let root: Root = Keys::default().bind(value(), "").unwrap();
+        assert_eq!(root.database.pool, 8);
+
+        #[derive(Debug, serde::Deserialize)]
+        struct Port {
+            #[allow(dead_code)]
+            port: Option<String>,
+        }
+
+        let err = Keys::default()
+            .bind::<Port>(value().as_object().unwrap()["database"].clone(), "database")
+            .unwrap_err();
+        assert!(err.is_decrypt());
+        assert!(err.to_string().contains("database.port"));
+    }
+
+    #[test]
+    fn test_plaintexts() {
+        let mut plaintexts = Keys::new(Reverse).plaintexts(&value());
+        plaintexts.sort();
+
+        assert_eq!(plaintexts, ["5432", "localhost/app"]);
+        assert!(Keys::default().plaintexts(&value()).is_empty());
+    }
+
+    #[cfg(feature = "encryption")]
+    #[test]
+    fn test_env_key_round_trip() {
+        use base64::Engine;
+
+        let key = base64::engine::general_purpose::STANDARD
+            .decode(EnvKey::generate())
+            .unwrap();
+        let env_key = EnvKey::from_bytes("test", &key).unwrap();
+        let value = env_key.encrypt("postgres://app:hunter2@db/app").unwrap();
+
+        assert!(value.starts_with(ENCRYPTED_PREFIX));
+        assert_eq!(
+            env_key.decrypt(&value[ENCRYPTED_PREFIX.len()..]).unwrap(),
+            "postgres://app:hunter2@db/app"
+        );
+
+        let other = EnvKey::from_bytes("other", &[7; 32]).unwrap();
+        assert!(other.decrypt(&value[ENCRYPTED_PREFIX.len()..]).is_err());
+        assert!(EnvKey::from_bytes("short", &[0; 16]).is_err());
+    }
+}
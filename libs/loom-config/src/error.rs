@@ -2,6 +2,8 @@ use std::io;
 
 use loom_core::path::IdentPathError;
 
+use super::ValidationError;
+
 /// Errors that can occur during configuration operations
 #[derive(Debug)]
 pub enum ConfigError {
@@ -28,6 +30,12 @@ pub enum ConfigError {
 
     /// Include file not found
     IncludeNotFound { path: String, source_file: String },
+
+    /// Config doesn't match the type it binds to, with every problem found
+    Validation(Vec<ValidationError>),
+
+    /// `enc:` value that couldn't be decrypted
+    Decrypt(String),
 }
 
 impl ConfigError {
@@ -61,6 +69,14 @@ impl ConfigError {
         }
     }
 
+    pub fn validation(errors: Vec<ValidationError>) -> Self {
+        Self::Validation(errors)
+    }
+
+    pub fn decrypt<S: Into<String>>(msg: S) -> Self {
+        Self::Decrypt(msg.into())
+    }
+
     pub fn is_not_found(&self) -> bool {
         matches!(self, Self::NotFound(_))
     }
@@ -92,6 +108,14 @@ impl ConfigError {
     pub fn is_include_not_found(&self) -> bool {
         matches!(self, Self::IncludeNotFound { .. })
     }
+
+    pub fn is_validation(&self) -> bool {
+        matches!(self, Self::Validation(_))
+    }
+
+    pub fn is_decrypt(&self) -> bool {
+        matches!(self, Self::Decrypt(_))
+    }
 }
 
 impl std::fmt::Display for ConfigError {
@@ -118,6 +142,16 @@ impl std::fmt::Display for ConfigError {
                     path, source_file
                 )
             }
+            Self::Validation(errors) => {
+                write!(f, "invalid configuration ({} errors)", errors.len())?;
+
+                for err in errors {
+                    write!(f, "\n  {}", err)?;
+                }
+
+                Ok(())
+            }
+            Self::Decrypt(msg) => write!(f, "decrypt error: {}", msg),
         }
     }
 }
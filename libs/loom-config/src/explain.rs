@@ -0,0 +1,52 @@
+use loom_core::path::IdentPath;
+use loom_core::value::Value;
+
+use super::ConfigSource;
+
+/// Where a config value came from, see [`Config::explain`](crate::Config::explain)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Explanation {
+    pub path: IdentPath,
+
+    /// The effective value, `None` if nothing sets it
+    pub value: Option<Value>,
+
+    /// The highest-priority source setting the value, `None` if none does
+    /// or the value was [`set`](crate::Config::set) over it
+    pub source: Option<ConfigSource>,
+
+    /// Whether the value was [`set`](crate::Config::set) after loading,
+    /// overriding every source's
+    pub set: bool,
+
+    /// Lower-priority sources' values for the path, highest priority first
+    pub overridden: Vec<Override>,
+}
+
+/// A value for the explained path that a higher-priority source replaced
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Override {
+    pub source: ConfigSource,
+    pub value: Value,
+}
+
+impl std::fmt::Display for Explanation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (&self.value, &self.source) {
+            (None, _) => write!(f, "{} is not set", self.path)?,
+            (Some(value), Some(source)) => {
+                write!(f, "{} = {} (from {})", self.path, value, source.name)?
+            }
+            (Some(value), None) if self.set => {
+                write!(f, "{} = {} (set programmatically)", self.path, value)?
+            }
+            (Some(value), None) => write!(f, "{} = {}", self.path, value)?,
+        }
+
+        for over in &self.overridden {
+            write!(f, "\n  overrides {} from {}", over.value, over.source.name)?;
+        }
+
+        Ok(())
+    }
+}
@@ -1,20 +1,43 @@
 mod builder;
 mod config;
+mod encryption;
 mod env;
 mod error;
+mod explain;
 mod include;
 mod logging;
+mod merge;
 pub mod providers;
+#[cfg(feature = "schema")]
+mod schema;
 mod section;
+mod units;
+mod validate;
 
 pub use builder::*;
 pub use config::*;
+#[cfg(feature = "encryption")]
+pub use encryption::EnvKey;
+pub use encryption::{ENCRYPTED_PREFIX, KeyProvider};
 pub use env::*;
 pub use error::*;
+pub use explain::*;
 pub use include::IncludeResolver;
 pub use logging::*;
-pub use providers::{EnvProvider, FileProvider, MemoryProvider, Provider};
+pub use merge::MergeStrategy;
+#[cfg(feature = "vault")]
+pub use providers::VaultSecrets;
+pub use providers::{
+    EnvFileSecrets, EnvProvider, FileProvider, MemoryProvider, MountedSecrets, Provider,
+    SecretsProvider,
+};
+#[cfg(feature = "kv")]
+pub use providers::{KvBackend, KvProvider, KvWatch};
+#[cfg(feature = "schema")]
+pub use schema::{JsonSchema, schema};
 pub use section::*;
+pub use units::{ByteSize, HumanDuration, UnitParseError};
+pub use validate::{ValidationError, ValidationErrorKind};
 
 #[macro_export]
 macro_rules! get {
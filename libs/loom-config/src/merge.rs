@@ -0,0 +1,164 @@
+use std::collections::BTreeMap;
+
+use loom_core::value::{Array, Value};
+
+/// How an array from a higher-priority provider combines with the array a
+/// lower-priority provider set at the same path
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum MergeStrategy {
+    /// The higher-priority array replaces the other (the default)
+    #[default]
+    Replace,
+
+    /// The higher-priority array's elements are appended to the other's
+    Append,
+
+    /// Elements are objects identified by the value of their `key` field:
+    /// an element with the same key as an existing one is merged into it,
+    /// any other is appended
+    MergeByKey(String),
+}
+
+/// Merge strategies of a [`ConfigBuilder`](crate::ConfigBuilder) by dotted
+/// path. Paths inside array elements leave out the index, e.g.
+/// `layers.score.categories.labels` for the `labels` of every category.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct MergeStrategies(BTreeMap<String, MergeStrategy>);
+
+impl MergeStrategies {
+    pub(crate) fn insert(&mut self, path: String, strategy: MergeStrategy) {
+        self.0.insert(path, strategy);
+    }
+
+    /// Deep-merge `source` into `target` like [`Value::merge`], combining
+    /// arrays by their path's strategy
+    pub(crate) fn merge(&self, target: &mut Value, source: Value) {
+        self.merge_at("", target, source);
+    }
+
+    fn merge_at(&self, path: &str, target: &mut Value, source: Value) {
+        match (target, source) {
+            (Value::Object(target), Value::Object(mut source)) => {
+                for (key, value) in std::mem::take(&mut *source) {
+                    let child = if path.is_empty() {
+                        key.clone()
+                    } else {
+                        format!("{}.{}", path, key)
+                    };
+
+                    match target.get_mut(&key) {
+                        Some(existing) => self.merge_at(&child, existing, value),
+                        None => {
+                            target.insert(key, value);
+                        }
+                    }
+                }
+            }
+            (Value::Array(target), Value::Array(source)) => {
+                let strategy = self.0.get(path).unwrap_or(&MergeStrategy::Replace);
+                *target = self.merge_arrays(path, strategy, target, source);
+            }
+            (target, source) => *target = source,
+        }
+    }
+
+    fn merge_arrays(
+        &self,
+        path: &str,
+        strategy: &MergeStrategy,
+        target: &Array,
+        source: Array,
+    ) -> Array {
+        match strategy {
+            MergeStrategy::Replace => source,
+            MergeStrategy::Append => {
+                let mut items = target.to_vec();
+                items.extend(source.iter().cloned());
+                Array::from(items)
+            }
+            MergeStrategy::MergeByKey(key) => {
+                let mut items = target.to_vec();
+
+                for element in source.iter().cloned() {
+                    let id = element.as_object().and_then(|object| object.get(key));
+                    let existing = id.and_then(|id| {
+                        items.iter().position(|item| {
+                            item.as_object().and_then(|object| object.get(key)) == Some(id)
+                        })
+                    });
+
+                    match existing {
+                        Some(index) => self.merge_at(path, &mut items[index], element),
+                        None => items.push(element),
+                    }
+                }
+
+                Array::from(items)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use loom_core::value::Object;
+
+    fn category(name: &str, threshold: f64) -> Value {
+        let mut object = Object::new();
+        object.insert("name".to_string(), Value::from(name));
+        object.insert("threshold".to_string(), Value::from(threshold));
+        Value::Object(object)
+    }
+
+    fn categories(items: Vec<Value>) -> Value {
+        let mut object = Object::new();
+        object.insert("categories".to_string(), Value::Array(items.into()));
+        Value::Object(object)
+    }
+
+    fn merged(strategy: MergeStrategy) -> Value {
+        let mut strategies = MergeStrategies::default();
+        strategies.insert("categories".to_string(), strategy);
+
+        let mut target = categories(vec![category("spam", 0.5), category("toxic", 0.5)]);
+        strategies.merge(
+            &mut target,
+            categories(vec![category("toxic", 0.9), category("pii", 0.7)]),
+        );
+        target
+    }
+
+    #[test]
+    fn test_replace() {
+        assert_eq!(
+            merged(MergeStrategy::Replace),
+            categories(vec![category("toxic", 0.9), category("pii", 0.7)])
+        );
+    }
+
+    #[test]
+    fn test_append() {
+        assert_eq!(
+            merged(MergeStrategy::Append),
+            categories(vec![
+                category("spam", 0.5),
+                category("toxic", 0.5),
+                category("toxic", 0.9),
+                category("pii", 0.7),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_merge_by_key() {
+        assert_eq!(
+            merged(MergeStrategy::MergeByKey("name".to_string())),
+            categories(vec![
+                category("spam", 0.5),
+                category("toxic", 0.9),
+                category("pii", 0.7),
+            ])
+        );
+    }
+}
@@ -0,0 +1,144 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use loom_core::path::{FilePath, Path};
+
+use super::{ConfigError, EnvProvider, SecretsProvider};
+
+pub struct EnvFileSecretsBuilder {
+    path: PathBuf,
+    prefix: Option<String>,
+    optional: bool,
+}
+
+impl EnvFileSecretsBuilder {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            prefix: None,
+            optional: false,
+        }
+    }
+
+    /// Only load keys starting with `prefix`, stripped from the config path
+    pub fn prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = Some(prefix.into());
+        self
+    }
+
+    /// Skip the file instead of failing if it doesn't exist
+    pub fn optional(mut self, optional: bool) -> Self {
+        self.optional = optional;
+        self
+    }
+
+    pub fn build(self) -> EnvFileSecrets {
+        EnvFileSecrets {
+            path: self.path,
+            keys: EnvProvider::new(self.prefix.as_deref()),
+            is_optional: self.optional,
+        }
+    }
+}
+
+/// Secrets from a dotenv-style file of `KEY=VALUE` lines
+///
+/// Keys map to config paths like [`EnvProvider`]'s: with prefix `APP_`,
+/// `APP_DATABASE_PASSWORD` becomes `database.password`. Blank lines, `#`
+/// comments and a leading `export ` are ignored, and values may be wrapped
+/// in single or double quotes.
+pub struct EnvFileSecrets {
+    path: PathBuf,
+    keys: EnvProvider,
+    is_optional: bool,
+}
+
+impl EnvFileSecrets {
+    pub fn builder(path: impl Into<PathBuf>) -> EnvFileSecretsBuilder {
+        EnvFileSecretsBuilder::new(path)
+    }
+
+    fn parse(&self, content: &str) -> BTreeMap<String, String> {
+        content
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .filter_map(|line| {
+                let line = line.strip_prefix("export ").unwrap_or(line);
+                let (key, value) = line.split_once('=')?;
+                let key = self.keys.parse_key(key.trim())?;
+
+                Some((key, unquote(value.trim()).to_string()))
+            })
+            .collect()
+    }
+}
+
+/// `value` without one pair of matching surrounding quotes
+fn unquote(value: &str) -> &str {
+    for quote in ['"', '\''] {
+        if let Some(inner) = value
+            .strip_prefix(quote)
+            .and_then(|rest| rest.strip_suffix(quote))
+        {
+            return inner;
+        }
+    }
+
+    value
+}
+
+impl SecretsProvider for EnvFileSecrets {
+    fn name(&self) -> &str {
+        "env_file_secrets"
+    }
+
+    fn load(&self) -> Result<BTreeMap<String, String>, ConfigError> {
+        match std::fs::read_to_string(&self.path) {
+            Ok(content) => Ok(self.parse(&content)),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                Err(ConfigError::not_found(self.path.display().to_string()))
+            }
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    fn path(&self) -> Path {
+        Path::File(FilePath::parse(&self.path.to_string_lossy()))
+    }
+
+    fn optional(&self) -> bool {
+        self.is_optional
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let secrets = EnvFileSecrets::builder(".env").prefix("APP_").build().parse(
+            "# database\nexport APP_DATABASE_PASSWORD=\"hunter2\"\nAPP_API__KEY='abc=123'\n\nOTHER=skipped\n",
+        );
+
+        assert_eq!(
+            secrets,
+            BTreeMap::from([
+                ("api_key".to_string(), "abc=123".to_string()),
+                ("database.password".to_string(), "hunter2".to_string()),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_missing_file() {
+        let secrets = EnvFileSecrets::builder("/nonexistent/.env").build();
+        assert!(secrets.load().unwrap_err().is_not_found());
+
+        let secrets = EnvFileSecrets::builder("/nonexistent/.env")
+            .optional(true)
+            .build();
+        assert!(secrets.optional());
+    }
+}
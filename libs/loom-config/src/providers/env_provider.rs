@@ -16,16 +16,26 @@ use super::{ConfigError, Provider};
 /// - APP_DATABASE__CONNECTION_STRING -> database.connection_string
 pub struct EnvProvider {
     prefix: Option<String>,
+
+    /// Prefix of the active profile's variables, loaded by their own provider
+    profile_prefix: Option<String>,
 }
 
 impl EnvProvider {
     pub fn new(prefix: Option<&str>) -> Self {
         Self {
             prefix: prefix.map(|s| s.to_uppercase()),
+            profile_prefix: None,
         }
     }
 
-    fn parse_key(&self, key: &str) -> Option<String> {
+    pub(crate) fn parse_key(&self, key: &str) -> Option<String> {
+        if let Some(prefix) = &self.profile_prefix
+            && key.starts_with(prefix.as_str())
+        {
+            return None;
+        }
+
         let key = match &self.prefix {
             Some(prefix) => {
                 if key.starts_with(prefix) {
@@ -55,7 +65,7 @@ impl EnvProvider {
         }
     }
 
-    fn parse_value(s: &str) -> Value {
+    pub(crate) fn parse_value(s: &str) -> Value {
         // Try to parse as various types
         if s.eq_ignore_ascii_case("true") {
             return Value::Bool(true);
@@ -80,7 +90,7 @@ impl EnvProvider {
         Value::String(s.to_string())
     }
 
-    fn set_by_path(root: &mut Value, path_str: &str, value: Value) {
+    pub(crate) fn set_by_path(root: &mut Value, path_str: &str, value: Value) {
         let path = match IdentPath::parse(path_str) {
             Ok(p) => p,
             Err(_) => return,
@@ -144,6 +154,19 @@ impl Provider for EnvProvider {
         true
     }
 
+    /// Variables prefixed with the profile too, e.g. `LOOM_PROD_` for
+    /// `LOOM_`, which this provider then skips
+    fn profile(&mut self, profile: &str) -> Option<Box<dyn Provider>> {
+        let prefix = format!(
+            "{}{}_",
+            self.prefix.as_deref().unwrap_or_default(),
+            profile.to_uppercase()
+        );
+
+        self.profile_prefix = Some(prefix.clone());
+        Some(Box::new(EnvProvider::new(Some(&prefix))))
+    }
+
     fn load(&self) -> Result<Option<Value>, ConfigError> {
         let mut root = Value::Object(Object::new());
 
@@ -204,6 +227,23 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_profile_skips_profile_keys() {
+        let mut provider = EnvProvider::new(Some("LOOM_"));
+        assert!(provider.profile("prod").is_some());
+
+        assert_eq!(provider.profile_prefix.as_deref(), Some("LOOM_PROD_"));
+        assert_eq!(provider.parse_key("LOOM_PROD_WORKERS"), None);
+        assert_eq!(
+            provider.parse_key("LOOM_WORKERS"),
+            Some("workers".to_string())
+        );
+        assert_eq!(
+            EnvProvider::new(Some("LOOM_PROD_")).parse_key("LOOM_PROD_WORKERS"),
+            Some("workers".to_string())
+        );
+    }
+
     #[test]
     fn test_parse_value_bool() {
         assert_eq!(EnvProvider::parse_value("true"), Value::Bool(true));
@@ -1,18 +1,20 @@
 use std::path::PathBuf;
+#[cfg(feature = "codec")]
+use std::sync::Arc;
 
+#[cfg(feature = "codec")]
+use loom_codec::{CodecRegistry, Record};
 use loom_core::path::FilePath;
 use loom_core::value::Value;
-use loom_core::{Format, path::Path};
+use loom_core::{Format, MediaType, path::Path};
 
 use super::{ConfigError, Provider};
 use crate::include::IncludeResolver;
 
 fn infer_format(path: &std::path::Path) -> Format {
-    match path.extension().and_then(|e| e.to_str()) {
-        Some("json") => Format::Json,
-        Some("yaml") | Some("yml") => Format::Yaml,
-        Some("toml") => Format::Toml,
-        _ => Format::Json,
+    match MediaType::from_path(path).format() {
+        Format::Text | Format::Binary => Format::Json,
+        format => format,
     }
 }
 
@@ -22,6 +24,8 @@ pub struct FileProviderBuilder {
     format: Option<Format>,
     optional: bool,
     includes: bool,
+    #[cfg(feature = "codec")]
+    codecs: Option<Arc<CodecRegistry>>,
 }
 
 impl FileProviderBuilder {
@@ -31,6 +35,8 @@ impl FileProviderBuilder {
             format: None,
             optional: false,
             includes: true,
+            #[cfg(feature = "codec")]
+            codecs: None,
         }
     }
 
@@ -51,6 +57,14 @@ impl FileProviderBuilder {
         self
     }
 
+    /// Decode formats without a built-in parser (e.g. INI) with the codec
+    /// registered for them in `codecs`.
+    #[cfg(feature = "codec")]
+    pub fn codecs(mut self, codecs: Arc<CodecRegistry>) -> Self {
+        self.codecs = Some(codecs);
+        self
+    }
+
     pub fn build(self) -> FileProvider {
         let format = self.format.unwrap_or_else(|| infer_format(&self.path));
         FileProvider {
@@ -58,6 +72,8 @@ impl FileProviderBuilder {
             format,
             is_optional: self.optional,
             includes: self.includes,
+            #[cfg(feature = "codec")]
+            codecs: self.codecs,
         }
     }
 }
@@ -67,6 +83,8 @@ pub struct FileProvider {
     format: Format,
     is_optional: bool,
     includes: bool,
+    #[cfg(feature = "codec")]
+    codecs: Option<Arc<CodecRegistry>>,
 }
 
 impl FileProvider {
@@ -74,6 +92,21 @@ impl FileProvider {
         FileProviderBuilder::new(path)
     }
 
+    /// `config.yaml` as `config.<profile>.yaml`
+    fn profile_path(&self, profile: &str) -> PathBuf {
+        let stem = self
+            .path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let name = match self.path.extension() {
+            Some(ext) => format!("{}.{}.{}", stem, profile, ext.to_string_lossy()),
+            None => format!("{}.{}", stem, profile),
+        };
+
+        self.path.with_file_name(name)
+    }
+
     fn parse_content(&self, content: &str) -> Result<Value, ConfigError> {
         #[cfg(feature = "json")]
         if self.format == Format::Json {
@@ -98,6 +131,20 @@ impl FileProvider {
             return Ok(toml_value.into());
         }
 
+        #[cfg(feature = "codec")]
+        if let Some(codec) = self.codecs.as_ref().and_then(|c| c.get(self.format)) {
+            let record = Record::from_str(self.path(), self.format.media_type(), content);
+            let mut entities = codec.decode(record).map_err(ConfigError::parse)?.content;
+
+            return Ok(match entities.len() {
+                0 => Value::Null,
+                1 => entities.remove(0).content,
+                _ => Value::Array(loom_core::value::Array::from(
+                    entities.into_iter().map(|e| e.content).collect::<Vec<_>>(),
+                )),
+            });
+        }
+
         Err(ConfigError::provider(format!(
             "unsupported format: {:?}",
             self.format
@@ -122,6 +169,19 @@ impl Provider for FileProvider {
         self.format
     }
 
+    /// The same file for `profile`, e.g. `config.prod.yaml`, loaded if it
+    /// exists
+    fn profile(&mut self, profile: &str) -> Option<Box<dyn Provider>> {
+        Some(Box::new(FileProvider {
+            path: self.profile_path(&profile.to_lowercase()),
+            format: self.format,
+            is_optional: true,
+            includes: self.includes,
+            #[cfg(feature = "codec")]
+            codecs: self.codecs.clone(),
+        }))
+    }
+
     fn load(&self) -> Result<Option<Value>, ConfigError> {
         if !self.path.exists() {
             return Ok(None);
@@ -171,6 +231,18 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_infer_format_ini() {
+        assert_eq!(
+            infer_format(std::path::Path::new("config.ini")),
+            Format::Ini
+        );
+        assert_eq!(
+            infer_format(std::path::Path::new("app.properties")),
+            Format::Ini
+        );
+    }
+
     #[test]
     fn test_infer_format_default() {
         assert_eq!(infer_format(std::path::Path::new("config")), Format::Json);
@@ -217,4 +289,51 @@ mod tests {
         let result = provider.load().unwrap();
         assert!(result.is_none());
     }
+
+    #[cfg(feature = "ini")]
+    #[test]
+    fn test_file_provider_decodes_with_codec_registry() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("legacy.ini");
+        std::fs::write(
+            &path,
+            "concurrency = 4\n\n[layers.score]\nthreshold = 0.8\n",
+        )
+        .unwrap();
+
+        let codecs = CodecRegistry::new()
+            .codec(loom_codec::IniCodec::new())
+            .build();
+        let provider = FileProvider::builder(&path)
+            .codecs(Arc::new(codecs))
+            .build();
+        let value = provider.load().unwrap().unwrap();
+
+        assert_eq!(value["concurrency"].as_int(), Some(4));
+        assert_eq!(value["layers"]["score"]["threshold"].as_float(), Some(0.8));
+    }
+
+    #[cfg(feature = "ini")]
+    #[test]
+    fn test_file_provider_without_codec_rejects_ini() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("legacy.ini");
+        std::fs::write(&path, "concurrency = 4\n").unwrap();
+
+        let provider = FileProvider::builder(&path).build();
+
+        assert!(matches!(provider.load(), Err(ConfigError::Provider(_))));
+    }
+
+    #[test]
+    fn test_profile_path() {
+        let provider = FileProvider::builder("conf/config.yaml").build();
+        assert_eq!(
+            provider.profile_path("prod"),
+            PathBuf::from("conf/config.prod.yaml")
+        );
+
+        let provider = FileProvider::builder("config").build();
+        assert_eq!(provider.profile_path("dev"), PathBuf::from("config.dev"));
+    }
 }
@@ -0,0 +1,474 @@
+use std::io::{BufRead, BufReader, Read};
+use std::time::Duration;
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use loom_core::path::Path;
+use loom_core::value::{Object, Value};
+
+use super::{ConfigError, EnvProvider, Provider};
+
+/// How long a Consul blocking query waits for a change before returning
+const CONSUL_WAIT: Duration = Duration::from_secs(300);
+
+/// Key-value store a [`KvProvider`] reads from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KvBackend {
+    /// Consul's KV store, through its HTTP API
+    Consul,
+
+    /// etcd v3, through its JSON gateway
+    Etcd,
+}
+
+impl KvBackend {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Consul => "consul",
+            Self::Etcd => "etcd",
+        }
+    }
+}
+
+pub struct KvProviderBuilder {
+    backend: KvBackend,
+    address: String,
+    prefix: String,
+    token: Option<String>,
+    section: Option<String>,
+    timeout: Duration,
+    optional: bool,
+}
+
+impl KvProviderBuilder {
+    pub fn new(backend: KvBackend, address: impl Into<String>, prefix: impl Into<String>) -> Self {
+        Self {
+            backend,
+            address: address.into(),
+            prefix: prefix.into(),
+            token: None,
+            section: None,
+            timeout: Duration::from_secs(10),
+            optional: false,
+        }
+    }
+
+    /// Consul ACL token, or etcd auth token
+    pub fn token(mut self, token: impl Into<String>) -> Self {
+        self.token = Some(token.into());
+        self
+    }
+
+    /// Nest the keys under `section`, e.g. `layers.score`
+    pub fn section(mut self, section: impl Into<String>) -> Self {
+        self.section = Some(section.into());
+        self
+    }
+
+    /// Give up on a read after `timeout` (default: 10s)
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Load nothing instead of failing if no key has the prefix
+    pub fn optional(mut self, optional: bool) -> Self {
+        self.optional = optional;
+        self
+    }
+
+    pub fn build(self) -> KvProvider {
+        KvProvider {
+            backend: self.backend,
+            address: self.address.trim_end_matches('/').to_string(),
+            prefix: self.prefix,
+            token: self.token,
+            section: self.section,
+            timeout: self.timeout,
+            is_optional: self.optional,
+        }
+    }
+}
+
+/// Config from every key under a prefix of a Consul or etcd key-value store
+/// (`kv` feature)
+///
+/// Key paths below the prefix become config paths, so with prefix
+/// `merc/scoring/` the key `merc/scoring/layers/score/threshold` sets
+/// `layers.score.threshold`. Values are typed like [`EnvProvider`]'s.
+///
+/// Clone the provider before adding it to a config to
+/// [`watch`](KvProvider::watch) it for changes.
+#[derive(Clone)]
+pub struct KvProvider {
+    backend: KvBackend,
+    address: String,
+    prefix: String,
+    token: Option<String>,
+    section: Option<String>,
+    timeout: Duration,
+    is_optional: bool,
+}
+
+impl KvProvider {
+    pub fn consul(address: impl Into<String>, prefix: impl Into<String>) -> KvProviderBuilder {
+        KvProviderBuilder::new(KvBackend::Consul, address, prefix)
+    }
+
+    pub fn etcd(address: impl Into<String>, prefix: impl Into<String>) -> KvProviderBuilder {
+        KvProviderBuilder::new(KvBackend::Etcd, address, prefix)
+    }
+
+    /// Block until a key under the prefix changes, then yield the reloaded
+    /// values; run it on its own thread and rebuild the config on each item
+    ///
+    /// # Example
+    /// ```ignore
+    /// let kv = KvProvider::consul("http://127.0.0.1:8500", "merc/").build();
+    /// let config = Config::new().with_provider(kv.clone()).build()?;
+    ///
+    /// std::thread::spawn(move || {
+    ///     for change in kv.watch() {
+    ///         // rebuild the config
+    ///     }
+    /// });
+    /// ```
+    pub fn watch(&self) -> KvWatch {
+        KvWatch {
+            provider: self.clone(),
+            index: None,
+            events: None,
+        }
+    }
+
+    /// Every key under the prefix with its value, and the store's index (a
+    /// Consul index or etcd revision) as of the read
+    fn fetch(&self) -> Result<(Vec<(String, String)>, u64), ConfigError> {
+        match self.backend {
+            KvBackend::Consul => self.consul_fetch(None),
+            KvBackend::Etcd => self.etcd_fetch(),
+        }
+    }
+
+    /// Read the prefix from Consul, blocking until the index passes `index`
+    /// if set
+    fn consul_fetch(
+        &self,
+        index: Option<u64>,
+    ) -> Result<(Vec<(String, String)>, u64), ConfigError> {
+        let mut request = ureq::get(&format!("{}/v1/kv/{}", self.address, self.prefix))
+            .query("recurse", "true")
+            .timeout(self.timeout);
+
+        if let Some(index) = index {
+            request = request
+                .query("index", &index.to_string())
+                .query("wait", &format!("{}s", CONSUL_WAIT.as_secs()))
+                .timeout(CONSUL_WAIT + self.timeout);
+        }
+
+        if let Some(token) = &self.token {
+            request = request.set("X-Consul-Token", token);
+        }
+
+        let response = match request.call() {
+            Ok(response) => response,
+            Err(ureq::Error::Status(404, response)) => {
+                return Ok((Vec::new(), consul_index(&response)));
+            }
+            Err(err) => return Err(self.error(err)),
+        };
+
+        let index = consul_index(&response);
+        let body: serde_json::Value = response.into_json()?;
+        Ok((parse_consul(&body), index))
+    }
+
+    fn etcd_fetch(&self) -> Result<(Vec<(String, String)>, u64), ConfigError> {
+        let mut request =
+            ureq::post(&format!("{}/v3/kv/range", self.address)).timeout(self.timeout);
+
+        if let Some(token) = &self.token {
+            request = request.set("Authorization", token);
+        }
+
+        let body: serde_json::Value = request
+            .send_json(serde_json::json!({
+                "key": BASE64.encode(&self.prefix),
+                "range_end": BASE64.encode(prefix_end(self.prefix.as_bytes())),
+            }))
+            .map_err(|err| self.error(err))?
+            .into_json()?;
+
+        Ok((parse_etcd(&body), etcd_revision(&body)))
+    }
+
+    /// Open an etcd watch of the prefix from after `revision`
+    fn etcd_watch(&self, revision: u64) -> Result<Box<dyn Read + Send + Sync>, ConfigError> {
+        let mut request = ureq::post(&format!("{}/v3/watch", self.address));
+
+        if let Some(token) = &self.token {
+            request = request.set("Authorization", token);
+        }
+
+        let response = request
+            .send_json(serde_json::json!({
+                "create_request": {
+                    "key": BASE64.encode(&self.prefix),
+                    "range_end": BASE64.encode(prefix_end(self.prefix.as_bytes())),
+                    "start_revision": (revision + 1).to_string(),
+                }
+            }))
+            .map_err(|err| self.error(err))?;
+
+        Ok(response.into_reader())
+    }
+
+    /// The keys as a config value, or `None` if there are none
+    fn to_value(&self, pairs: Vec<(String, String)>) -> Option<Value> {
+        let mut root = Value::Object(Object::new());
+
+        for (key, value) in pairs {
+            let Some(path) = key.strip_prefix(&self.prefix) else {
+                continue;
+            };
+
+            let path = path
+                .split('/')
+                .filter(|segment| !segment.is_empty())
+                .collect::<Vec<_>>()
+                .join(".");
+
+            if path.is_empty() {
+                continue;
+            }
+
+            let path = match &self.section {
+                Some(section) => format!("{}.{}", section, path),
+                None => path,
+            };
+
+            EnvProvider::set_by_path(&mut root, &path, EnvProvider::parse_value(&value));
+        }
+
+        (!root.is_empty()).then_some(root)
+    }
+
+    fn error(&self, err: ureq::Error) -> ConfigError {
+        ConfigError::provider(format!(
+            "reading {} prefix '{}' failed: {}",
+            self.backend.name(),
+            self.prefix,
+            err
+        ))
+    }
+}
+
+impl Provider for KvProvider {
+    fn name(&self) -> &str {
+        self.backend.name()
+    }
+
+    fn path(&self) -> Path {
+        Path::Empty
+    }
+
+    fn optional(&self) -> bool {
+        self.is_optional
+    }
+
+    fn load(&self) -> Result<Option<Value>, ConfigError> {
+        let (pairs, _) = self.fetch()?;
+        Ok(self.to_value(pairs))
+    }
+}
+
+/// Changes to a [`KvProvider`]'s keys, from [`KvProvider::watch`]. Each item
+/// is the provider's reloaded values; iteration blocks until the next
+/// change.
+pub struct KvWatch {
+    provider: KvProvider,
+    index: Option<u64>,
+    events: Option<std::io::Lines<BufReader<Box<dyn Read + Send + Sync>>>>,
+}
+
+impl KvWatch {
+    /// Block until the keys change, returning their new values and index
+    fn next_change(&mut self) -> Result<(Vec<(String, String)>, u64), ConfigError> {
+        let index = match self.index {
+            Some(index) => index,
+            None => self.provider.fetch()?.1,
+        };
+
+        match self.provider.backend {
+            KvBackend::Consul => loop {
+                let (pairs, next) = self.provider.consul_fetch(Some(index))?;
+
+                // Consul returns on timeout too, with the index unchanged
+                if next != index {
+                    return Ok((pairs, next));
+                }
+            },
+            KvBackend::Etcd => {
+                if self.events.is_none() {
+                    let reader = self.provider.etcd_watch(index)?;
+                    self.events = Some(BufReader::new(reader).lines());
+                }
+
+                let events = self.events.as_mut().expect("watch is open");
+
+                for line in events {
+                    let event: serde_json::Value =
+                        serde_json::from_str(&line?).map_err(ConfigError::parse)?;
+
+                    if event["result"]["events"]
+                        .as_array()
+                        .is_some_and(|events| !events.is_empty())
+                    {
+                        return self.provider.etcd_fetch();
+                    }
+                }
+
+                self.events = None;
+                Err(ConfigError::provider("etcd watch closed"))
+            }
+        }
+    }
+}
+
+impl Iterator for KvWatch {
+    type Item = Result<Option<Value>, ConfigError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.next_change().map(|(pairs, index)| {
+            self.index = Some(index);
+            self.provider.to_value(pairs)
+        }))
+    }
+}
+
+/// The `X-Consul-Index` of a response, `0` if missing
+fn consul_index(response: &ureq::Response) -> u64 {
+    response
+        .header("X-Consul-Index")
+        .and_then(|index| index.parse().ok())
+        .unwrap_or(0)
+}
+
+/// Keys and values of a Consul `?recurse` read, without folders
+fn parse_consul(body: &serde_json::Value) -> Vec<(String, String)> {
+    body.as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| {
+            let key = entry["Key"].as_str()?;
+            let value = BASE64.decode(entry["Value"].as_str()?).ok()?;
+            Some((key.to_string(), String::from_utf8(value).ok()?))
+        })
+        .collect()
+}
+
+/// Keys and values of an etcd range response
+fn parse_etcd(body: &serde_json::Value) -> Vec<(String, String)> {
+    body["kvs"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|kv| {
+            let key = BASE64.decode(kv["key"].as_str()?).ok()?;
+            let value = BASE64
+                .decode(kv["value"].as_str().unwrap_or_default())
+                .ok()?;
+            Some((String::from_utf8(key).ok()?, String::from_utf8(value).ok()?))
+        })
+        .collect()
+}
+
+/// The revision of an etcd response; the gateway encodes int64s as strings
+fn etcd_revision(body: &serde_json::Value) -> u64 {
+    body["header"]["revision"]
+        .as_str()
+        .and_then(|revision| revision.parse().ok())
+        .unwrap_or(0)
+}
+
+/// The end of etcd's range of keys starting with `prefix`
+fn prefix_end(prefix: &[u8]) -> Vec<u8> {
+    let mut end = prefix.to_vec();
+
+    while let Some(last) = end.pop() {
+        if last < u8::MAX {
+            end.push(last + 1);
+            return end;
+        }
+    }
+
+    // Every key
+    vec![0]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use loom_core::path::IdentPath;
+
+    #[test]
+    fn test_parse_consul() {
+        let body = serde_json::json!([
+            { "Key": "merc/", "Value": null },
+            { "Key": "merc/layers/score/threshold", "Value": BASE64.encode("0.8") },
+            { "Key": "merc/workers", "Value": BASE64.encode("4") },
+        ]);
+
+        assert_eq!(
+            parse_consul(&body),
+            [
+                ("merc/layers/score/threshold".to_string(), "0.8".to_string()),
+                ("merc/workers".to_string(), "4".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_etcd() {
+        let body = serde_json::json!({
+            "header": { "revision": "42" },
+            "kvs": [{ "key": BASE64.encode("merc/workers"), "value": BASE64.encode("4") }],
+        });
+
+        assert_eq!(
+            parse_etcd(&body),
+            [("merc/workers".to_string(), "4".to_string())]
+        );
+        assert_eq!(etcd_revision(&body), 42);
+    }
+
+    #[test]
+    fn test_to_value() {
+        let provider = KvProvider::consul("http://127.0.0.1:8500/", "merc/")
+            .section("fleet")
+            .build();
+        let value = provider
+            .to_value(vec![
+                ("merc/layers/score/threshold".to_string(), "0.8".to_string()),
+                ("merc/workers".to_string(), "4".to_string()),
+                ("other/key".to_string(), "skipped".to_string()),
+            ])
+            .unwrap();
+
+        let get = |path: &str| value.get_by_path(&IdentPath::parse(path).unwrap()).cloned();
+        assert_eq!(get("fleet.layers.score.threshold"), Some(Value::from(0.8)));
+        assert_eq!(get("fleet.workers"), Some(Value::from(4i64)));
+        assert!(get("other").is_none());
+        assert_eq!(provider.address, "http://127.0.0.1:8500");
+
+        assert!(provider.to_value(Vec::new()).is_none());
+    }
+
+    #[test]
+    fn test_prefix_end() {
+        assert_eq!(prefix_end(b"merc/"), b"merc0");
+        assert_eq!(prefix_end(&[b'a', u8::MAX]), b"b");
+        assert_eq!(prefix_end(&[]), [0]);
+    }
+}
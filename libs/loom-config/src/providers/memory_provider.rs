@@ -36,7 +36,7 @@ impl MemoryProvider {
         Self { data: root }
     }
 
-    fn set_by_path(root: &mut Value, path: &IdentPath, value: Value) {
+    pub(crate) fn set_by_path(root: &mut Value, path: &IdentPath, value: Value) {
         let segments = path.segments();
         if segments.is_empty() {
             return;
@@ -67,6 +67,17 @@ impl MemoryProvider {
                 }
             }
         }
+
+        // Indexes only reach into existing array elements
+        if let (IdentSegment::Index(index), Value::Array(arr)) = (segment, current)
+            && let Some(child) = arr.get_mut(*index)
+        {
+            if is_last {
+                *child = value;
+            } else {
+                Self::set_nested(child, &segments[1..], value);
+            }
+        }
     }
 }
 
@@ -1,14 +1,28 @@
+mod env_file_secrets;
 mod env_provider;
 mod file_provider;
+#[cfg(feature = "kv")]
+mod kv_provider;
 mod memory_provider;
+mod mounted_secrets;
+#[cfg(feature = "vault")]
+mod vault_secrets;
 
+pub use env_file_secrets::*;
 pub use env_provider::*;
 pub use file_provider::*;
+#[cfg(feature = "kv")]
+pub use kv_provider::*;
 pub use memory_provider::*;
+pub use mounted_secrets::*;
+#[cfg(feature = "vault")]
+pub use vault_secrets::*;
+
+use std::collections::BTreeMap;
 
 use loom_core::Format;
 use loom_core::path::Path;
-use loom_core::value::Value;
+use loom_core::value::{Object, Value};
 
 use super::ConfigError;
 
@@ -24,4 +38,76 @@ pub trait Provider: Send + Sync {
     fn format(&self) -> Format {
         Format::Binary
     }
+
+    /// Whether every value this provider loads is secret, so the built
+    /// [`Config`](crate::Config) redacts it
+    fn secret(&self) -> bool {
+        false
+    }
+
+    /// Provider of `profile`'s overrides for this one's values, loaded right
+    /// after it when the config is built
+    /// [`with_profile`](crate::ConfigBuilder::with_profile), e.g.
+    /// `config.prod.yaml` for `config.yaml`
+    fn profile(&mut self, _profile: &str) -> Option<Box<dyn Provider>> {
+        None
+    }
+}
+
+/// Source of secret config values, e.g. passwords and API tokens.
+///
+/// Values are bound like any other config, but the built
+/// [`Config`](crate::Config) masks them in its `Debug` output and lists them
+/// in [`Config::secret_values`](crate::Config::secret_values) so emitters can
+/// redact them. Add one with
+/// [`ConfigBuilder::with_secrets`](crate::ConfigBuilder::with_secrets).
+pub trait SecretsProvider: Send + Sync {
+    fn name(&self) -> &str;
+
+    /// Secrets by dotted key, e.g. `database.password`
+    fn load(&self) -> Result<BTreeMap<String, String>, ConfigError>;
+
+    fn path(&self) -> Path {
+        Path::Empty
+    }
+
+    fn optional(&self) -> bool {
+        false
+    }
+}
+
+/// A [`SecretsProvider`] as a [`Provider`] of secret values
+pub(crate) struct Secrets<P>(pub(crate) P);
+
+impl<P: SecretsProvider> Provider for Secrets<P> {
+    fn name(&self) -> &str {
+        self.0.name()
+    }
+
+    fn load(&self) -> Result<Option<Value>, ConfigError> {
+        let secrets = self.0.load()?;
+
+        if secrets.is_empty() {
+            return Ok(None);
+        }
+
+        let mut root = Value::Object(Object::new());
+        for (key, value) in secrets {
+            EnvProvider::set_by_path(&mut root, &key, Value::String(value));
+        }
+
+        Ok(Some(root))
+    }
+
+    fn path(&self) -> Path {
+        self.0.path()
+    }
+
+    fn optional(&self) -> bool {
+        self.0.optional()
+    }
+
+    fn secret(&self) -> bool {
+        true
+    }
 }
@@ -0,0 +1,145 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use loom_core::path::{FilePath, Path};
+
+use super::{ConfigError, EnvProvider, SecretsProvider};
+
+pub struct MountedSecretsBuilder {
+    dir: PathBuf,
+    optional: bool,
+}
+
+impl MountedSecretsBuilder {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            optional: false,
+        }
+    }
+
+    /// Skip the directory instead of failing if it doesn't exist
+    pub fn optional(mut self, optional: bool) -> Self {
+        self.optional = optional;
+        self
+    }
+
+    pub fn build(self) -> MountedSecrets {
+        MountedSecrets {
+            dir: self.dir,
+            is_optional: self.optional,
+        }
+    }
+}
+
+/// Secrets mounted as one file per value, as Docker and Kubernetes do under
+/// e.g. `/run/secrets`
+///
+/// Each file's content (without trailing newlines) is the value of the key
+/// named by its path below the directory: `database/password` and
+/// `DATABASE_PASSWORD` both become `database.password`, mapped like
+/// [`EnvProvider`] keys. Hidden entries, such as Kubernetes' `..data`
+/// links, are skipped.
+pub struct MountedSecrets {
+    dir: PathBuf,
+    is_optional: bool,
+}
+
+impl MountedSecrets {
+    pub fn builder(dir: impl Into<PathBuf>) -> MountedSecretsBuilder {
+        MountedSecretsBuilder::new(dir)
+    }
+
+    /// Read every secret under `dir`, keyed under `prefix`
+    fn read_dir(
+        dir: &std::path::Path,
+        prefix: &str,
+        secrets: &mut BTreeMap<String, String>,
+    ) -> Result<(), ConfigError> {
+        let keys = EnvProvider::new(None);
+
+        for entry in std::fs::read_dir(dir)? {
+            let entry = entry?;
+            let name = entry.file_name().to_string_lossy().to_string();
+
+            if name.starts_with('.') {
+                continue;
+            }
+
+            let Some(key) = keys.parse_key(&name) else {
+                continue;
+            };
+            let key = if prefix.is_empty() {
+                key
+            } else {
+                format!("{}.{}", prefix, key)
+            };
+
+            // Follows symlinks, which is how Kubernetes mounts each key
+            let meta = std::fs::metadata(entry.path())?;
+
+            if meta.is_dir() {
+                Self::read_dir(&entry.path(), &key, secrets)?;
+            } else if meta.is_file() {
+                let value = std::fs::read_to_string(entry.path())?;
+                secrets.insert(key, value.trim_end_matches(['\n', '\r']).to_string());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl SecretsProvider for MountedSecrets {
+    fn name(&self) -> &str {
+        "mounted_secrets"
+    }
+
+    fn load(&self) -> Result<BTreeMap<String, String>, ConfigError> {
+        if !self.dir.is_dir() {
+            return Err(ConfigError::not_found(self.dir.display().to_string()));
+        }
+
+        let mut secrets = BTreeMap::new();
+        Self::read_dir(&self.dir, "", &mut secrets)?;
+        Ok(secrets)
+    }
+
+    fn path(&self) -> Path {
+        Path::File(FilePath::parse(&self.dir.to_string_lossy()))
+    }
+
+    fn optional(&self) -> bool {
+        self.is_optional
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("database")).unwrap();
+        std::fs::write(dir.path().join("database/password"), "hunter2\n").unwrap();
+        std::fs::write(dir.path().join("API_TOKEN"), "abc").unwrap();
+        std::fs::write(dir.path().join(".hidden"), "skipped").unwrap();
+
+        let secrets = MountedSecrets::builder(dir.path()).build().load().unwrap();
+
+        assert_eq!(
+            secrets,
+            BTreeMap::from([
+                ("api.token".to_string(), "abc".to_string()),
+                ("database.password".to_string(), "hunter2".to_string()),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_missing_dir() {
+        let secrets = MountedSecrets::builder("/nonexistent/secrets").build();
+        assert!(secrets.load().unwrap_err().is_not_found());
+    }
+}
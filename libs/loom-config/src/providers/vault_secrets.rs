@@ -0,0 +1,203 @@
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use super::{ConfigError, SecretsProvider};
+
+pub struct VaultSecretsBuilder {
+    address: String,
+    token: String,
+    path: String,
+    mount: String,
+    section: Option<String>,
+    timeout: Duration,
+    optional: bool,
+}
+
+impl VaultSecretsBuilder {
+    pub fn new(
+        address: impl Into<String>,
+        token: impl Into<String>,
+        path: impl Into<String>,
+    ) -> Self {
+        Self {
+            address: address.into(),
+            token: token.into(),
+            path: path.into(),
+            mount: "secret".to_string(),
+            section: None,
+            timeout: Duration::from_secs(10),
+            optional: false,
+        }
+    }
+
+    /// KV version 2 engine the secret lives in (default: `secret`)
+    pub fn mount(mut self, mount: impl Into<String>) -> Self {
+        self.mount = mount.into();
+        self
+    }
+
+    /// Nest the secret's keys under `section`, e.g. `database`
+    pub fn section(mut self, section: impl Into<String>) -> Self {
+        self.section = Some(section.into());
+        self
+    }
+
+    /// Give up on the request after `timeout` (default: 10s)
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Load nothing instead of failing if the secret doesn't exist
+    pub fn optional(mut self, optional: bool) -> Self {
+        self.optional = optional;
+        self
+    }
+
+    pub fn build(self) -> VaultSecrets {
+        VaultSecrets {
+            address: self.address,
+            token: self.token,
+            path: self.path,
+            mount: self.mount,
+            section: self.section,
+            timeout: self.timeout,
+            is_optional: self.optional,
+        }
+    }
+}
+
+/// Secrets from one secret of a HashiCorp Vault KV version 2 engine (`vault`
+/// feature)
+///
+/// Reads `<address>/v1/<mount>/data/<path>` with the token and loads each
+/// key of the secret, under the configured section if any. Non-string
+/// values are loaded as their JSON text.
+pub struct VaultSecrets {
+    address: String,
+    token: String,
+    path: String,
+    mount: String,
+    section: Option<String>,
+    timeout: Duration,
+    is_optional: bool,
+}
+
+impl VaultSecrets {
+    pub fn builder(
+        address: impl Into<String>,
+        token: impl Into<String>,
+        path: impl Into<String>,
+    ) -> VaultSecretsBuilder {
+        VaultSecretsBuilder::new(address, token, path)
+    }
+
+    fn url(&self) -> String {
+        format!(
+            "{}/v1/{}/data/{}",
+            self.address.trim_end_matches('/'),
+            self.mount.trim_matches('/'),
+            self.path.trim_matches('/')
+        )
+    }
+
+    /// Keys of the `data.data` object of a KV v2 read response
+    fn parse(&self, body: &serde_json::Value) -> Result<BTreeMap<String, String>, ConfigError> {
+        let data = body["data"]["data"].as_object().ok_or_else(|| {
+            ConfigError::provider(format!("vault secret '{}' has no data", self.path))
+        })?;
+
+        Ok(data
+            .iter()
+            .map(|(key, value)| {
+                let key = match &self.section {
+                    Some(section) => format!("{}.{}", section, key),
+                    None => key.clone(),
+                };
+                let value = match value {
+                    serde_json::Value::String(value) => value.clone(),
+                    value => value.to_string(),
+                };
+
+                (key, value)
+            })
+            .collect())
+    }
+}
+
+impl SecretsProvider for VaultSecrets {
+    fn name(&self) -> &str {
+        "vault_secrets"
+    }
+
+    fn load(&self) -> Result<BTreeMap<String, String>, ConfigError> {
+        let response = ureq::get(&self.url())
+            .set("X-Vault-Token", &self.token)
+            .timeout(self.timeout)
+            .call();
+
+        let body: serde_json::Value = match response {
+            Ok(response) => response.into_json()?,
+            Err(ureq::Error::Status(404, _)) => {
+                return Err(ConfigError::not_found(format!(
+                    "vault secret '{}'",
+                    self.path
+                )));
+            }
+            Err(ureq::Error::Status(status, _)) => {
+                return Err(ConfigError::provider(format!(
+                    "reading vault secret '{}' failed: {}",
+                    self.path, status
+                )));
+            }
+            Err(err) => {
+                return Err(ConfigError::provider(format!(
+                    "reading vault secret '{}' failed: {}",
+                    self.path, err
+                )));
+            }
+        };
+
+        self.parse(&body)
+    }
+
+    fn optional(&self) -> bool {
+        self.is_optional
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_url() {
+        let secrets = VaultSecrets::builder("https://vault.internal:8200/", "token", "/merc/api")
+            .mount("kv")
+            .build();
+
+        assert_eq!(
+            secrets.url(),
+            "https://vault.internal:8200/v1/kv/data/merc/api"
+        );
+    }
+
+    #[test]
+    fn test_parse() {
+        let secrets = VaultSecrets::builder("http://127.0.0.1:8200", "token", "merc/db")
+            .section("database")
+            .build();
+        let body = serde_json::json!({
+            "data": { "data": { "password": "hunter2", "port": 5432 }, "metadata": {} }
+        });
+
+        assert_eq!(
+            secrets.parse(&body).unwrap(),
+            BTreeMap::from([
+                ("database.password".to_string(), "hunter2".to_string()),
+                ("database.port".to_string(), "5432".to_string()),
+            ])
+        );
+        assert!(secrets.parse(&serde_json::json!({})).is_err());
+    }
+}
@@ -0,0 +1,46 @@
+pub use schemars::JsonSchema;
+
+/// JSON Schema (draft 2020-12) of the config bound to `T` (`schema`
+/// feature), for editors to check config files against and services to
+/// validate uploaded configs before binding them.
+///
+/// Field doc comments become descriptions, and fields with a serde default
+/// aren't required.
+///
+/// # Example
+/// ```ignore
+/// let schema = loom_config::schema::<ScoreConfig>();
+/// std::fs::write("score.schema.json", serde_json::to_string_pretty(&schema)?)?;
+/// ```
+pub fn schema<T: JsonSchema>() -> serde_json::Value {
+    schemars::schema_for!(T).to_value()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[allow(dead_code)]
+    #[derive(serde::Deserialize, JsonSchema)]
+    struct Database {
+        /// Host to connect to
+        host: String,
+
+        #[serde(default)]
+        port: u16,
+    }
+
+    #[test]
+    fn test_schema() {
+        let schema = schema::<Database>();
+
+        assert_eq!(schema["title"], "Database");
+        assert_eq!(schema["type"], "object");
+        assert_eq!(
+            schema["properties"]["host"]["description"],
+            "Host to connect to"
+        );
+        assert_eq!(schema["properties"]["port"]["type"], "integer");
+        assert_eq!(schema["required"], serde_json::json!(["host"]));
+    }
+}
@@ -3,26 +3,36 @@ use serde::de::DeserializeOwned;
 use loom_core::path::{IdentPath, IdentSegment};
 use loom_core::value::Value;
 
-use super::ConfigError;
+use super::encryption::Keys;
+use super::{ConfigError, validate};
 
 #[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
 pub struct ConfigSection {
     value: Value,
     path: IdentPath,
+
+    #[serde(skip)]
+    keys: Keys,
 }
 
 impl ConfigSection {
     pub(crate) fn new(value: Value, path: IdentPath) -> Self {
-        Self { value, path }
-    }
-
-    pub(crate) fn root(value: Value) -> Self {
         Self {
             value,
-            path: IdentPath::parse("root").expect("valid path"),
+            path,
+            keys: Keys::default(),
         }
     }
 
+    pub(crate) fn root(value: Value) -> Self {
+        Self::new(value, IdentPath::parse("root").expect("valid path"))
+    }
+
+    pub(crate) fn with_keys(mut self, keys: Keys) -> Self {
+        self.keys = keys;
+        self
+    }
+
     pub fn path(&self) -> &IdentPath {
         &self.path
     }
@@ -66,7 +76,7 @@ impl ConfigSection {
 
         let child_path = IdentPath::parse(&child_path_str).unwrap_or(self.path.clone());
 
-        ConfigSection::new(child_value, child_path)
+        ConfigSection::new(child_value, child_path).with_keys(self.keys.clone())
     }
 
     pub fn get_index(&self, index: usize) -> ConfigSection {
@@ -83,16 +93,48 @@ impl ConfigSection {
 
         let child_path = IdentPath::parse(&child_path_str).unwrap_or(self.path.clone());
 
-        ConfigSection::new(child_value, child_path)
+        ConfigSection::new(child_value, child_path).with_keys(self.keys.clone())
     }
 
+    /// Deserialize the section into `T`, decrypting the `enc:` values it
+    /// reads on the way
     pub fn bind<T: DeserializeOwned>(&self) -> Result<T, ConfigError> {
         if self.value.is_null() {
             return Err(ConfigError::not_found(self.path.to_string()));
         }
 
-        let json: serde_json::Value = (&self.value).into();
-        serde_json::from_value(json).map_err(ConfigError::deserialize)
+        self.keys.bind(self.value.clone(), &self.base())
+    }
+
+    /// Check the section against the type it binds to, reporting every
+    /// unknown key, missing field and mismatched value by its full path
+    pub fn validate<T: DeserializeOwned>(&self) -> Result<(), ConfigError> {
+        let base = if self.path.to_string() == "root" {
+            IdentPath::from(Vec::new())
+        } else {
+            self.path.clone()
+        };
+
+        let errors = validate::validate::<T>(&self.decrypted()?, &base);
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ConfigError::validation(errors))
+        }
+    }
+
+    /// The section's value with its `enc:` values decrypted
+    fn decrypted(&self) -> Result<Value, ConfigError> {
+        self.keys.decrypt(self.value.clone(), &self.base())
+    }
+
+    /// The section's path for errors, empty for the root
+    fn base(&self) -> String {
+        match self.path.to_string() {
+            path if path == "root" => String::new(),
+            path => path,
+        }
     }
 
     pub fn keys(&self) -> Option<impl Iterator<Item = &str>> {
@@ -121,7 +163,7 @@ impl ConfigSection {
                         format!("{}.{}", self.path, k)
                     };
                     let child_path = IdentPath::parse(&child_path_str).unwrap_or(self.path.clone());
-                    ConfigSection::new(v.clone(), child_path)
+                    ConfigSection::new(v.clone(), child_path).with_keys(self.keys.clone())
                 })
                 .collect(),
             Value::Array(arr) => arr
@@ -134,7 +176,7 @@ impl ConfigSection {
                         format!("{}[{}]", self.path, i)
                     };
                     let child_path = IdentPath::parse(&child_path_str).unwrap_or(self.path.clone());
-                    ConfigSection::new(v.clone(), child_path)
+                    ConfigSection::new(v.clone(), child_path).with_keys(self.keys.clone())
                 })
                 .collect(),
             _ => Vec::new(),
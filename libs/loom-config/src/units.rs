@@ -0,0 +1,360 @@
+use std::str::FromStr;
+use std::time::Duration;
+
+/// A [`Duration`] bound from a human-friendly string such as `"250ms"`,
+/// `"30s"`, `"5m"` or `"1h30m"`, or from a number of seconds.
+///
+/// Units: `ns`, `us` (or `µs`), `ms`, `s`, `m`, `h` and `d`. Amounts may be
+/// fractional (`"1.5s"`), and an amount without a unit is in seconds.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct HumanDuration(pub Duration);
+
+/// A number of bytes bound from a human-friendly string such as `"512MB"` or
+/// `"1.5 GiB"`, or from a plain number of bytes.
+///
+/// `KB`, `MB`, `GB` and `TB` are powers of 1000; `KiB`, `MiB`, `GiB` and
+/// `TiB` are powers of 1024. Units are case-insensitive.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ByteSize(pub u64);
+
+/// Error parsing a [`HumanDuration`] or [`ByteSize`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnitParseError {
+    input: String,
+    expected: &'static str,
+}
+
+impl UnitParseError {
+    fn new(input: &str, expected: &'static str) -> Self {
+        Self {
+            input: input.to_string(),
+            expected,
+        }
+    }
+}
+
+impl std::fmt::Display for UnitParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid {}: '{}'", self.expected, self.input)
+    }
+}
+
+impl std::error::Error for UnitParseError {}
+
+/// Split `input` into `(amount, unit)` pairs, e.g. `1h30m` into
+/// `[(1.0, "h"), (30.0, "m")]`
+fn amounts(input: &str) -> Option<Vec<(f64, String)>> {
+    let mut pairs = Vec::new();
+    let mut chars = input.trim().chars().peekable();
+
+    while chars.peek().is_some() {
+        let mut amount = String::new();
+        while let Some(c) = chars.next_if(|c| c.is_ascii_digit() || *c == '.') {
+            amount.push(c);
+        }
+
+        if amount.is_empty() {
+            return None;
+        }
+
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+
+        let mut unit = String::new();
+        while let Some(c) = chars.next_if(|c| c.is_alphabetic()) {
+            unit.push(c);
+        }
+
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+
+        pairs.push((amount.parse::<f64>().ok()?, unit.to_lowercase()));
+    }
+
+    (!pairs.is_empty()).then_some(pairs)
+}
+
+impl HumanDuration {
+    pub fn as_duration(&self) -> Duration {
+        self.0
+    }
+}
+
+impl FromStr for HumanDuration {
+    type Err = UnitParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let err = || UnitParseError::new(input, "duration");
+        let mut nanos = 0.0;
+
+        for (amount, unit) in amounts(input).ok_or_else(err)? {
+            let scale = match unit.as_str() {
+                "ns" => 1.0,
+                "us" | "µs" => 1e3,
+                "ms" => 1e6,
+                "" | "s" => 1e9,
+                "m" => 60e9,
+                "h" => 3_600e9,
+                "d" => 86_400e9,
+                _ => return Err(err()),
+            };
+
+            nanos += amount * scale;
+        }
+
+        if nanos > u64::MAX as f64 {
+            return Err(err());
+        }
+
+        Ok(Self(Duration::from_nanos(nanos.round() as u64)))
+    }
+}
+
+impl std::fmt::Display for HumanDuration {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let nanos = self.0.as_nanos();
+
+        for (unit, size) in [
+            ("d", 86_400_000_000_000),
+            ("h", 3_600_000_000_000),
+            ("m", 60_000_000_000),
+            ("s", 1_000_000_000),
+            ("ms", 1_000_000),
+            ("us", 1_000),
+        ] {
+            if nanos >= size && nanos % size == 0 {
+                return write!(f, "{}{}", nanos / size, unit);
+            }
+        }
+
+        write!(f, "{}ns", nanos)
+    }
+}
+
+impl From<Duration> for HumanDuration {
+    fn from(value: Duration) -> Self {
+        Self(value)
+    }
+}
+
+impl From<HumanDuration> for Duration {
+    fn from(value: HumanDuration) -> Self {
+        value.0
+    }
+}
+
+impl std::ops::Deref for HumanDuration {
+    type Target = Duration;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl ByteSize {
+    pub fn as_u64(&self) -> u64 {
+        self.0
+    }
+}
+
+impl FromStr for ByteSize {
+    type Err = UnitParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let err = || UnitParseError::new(input, "byte size");
+        let pairs = amounts(input).ok_or_else(err)?;
+
+        let [(amount, unit)] = pairs.as_slice() else {
+            return Err(err());
+        };
+
+        let scale: u64 = match unit.as_str() {
+            "" | "b" => 1,
+            "kb" | "k" => 1_000,
+            "mb" | "m" => 1_000_000,
+            "gb" | "g" => 1_000_000_000,
+            "tb" | "t" => 1_000_000_000_000,
+            "kib" => 1 << 10,
+            "mib" => 1 << 20,
+            "gib" => 1 << 30,
+            "tib" => 1 << 40,
+            _ => return Err(err()),
+        };
+
+        let bytes = amount * scale as f64;
+
+        if bytes.fract() != 0.0 || bytes > u64::MAX as f64 {
+            return Err(err());
+        }
+
+        Ok(Self(bytes as u64))
+    }
+}
+
+impl std::fmt::Display for ByteSize {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (unit, size) in [
+            ("TiB", 1u64 << 40),
+            ("GiB", 1 << 30),
+            ("MiB", 1 << 20),
+            ("KiB", 1 << 10),
+            ("TB", 1_000_000_000_000),
+            ("GB", 1_000_000_000),
+            ("MB", 1_000_000),
+            ("KB", 1_000),
+        ] {
+            if self.0 >= size && self.0 % size == 0 {
+                return write!(f, "{}{}", self.0 / size, unit);
+            }
+        }
+
+        write!(f, "{}B", self.0)
+    }
+}
+
+impl From<u64> for ByteSize {
+    fn from(value: u64) -> Self {
+        Self(value)
+    }
+}
+
+impl From<ByteSize> for u64 {
+    fn from(value: ByteSize) -> Self {
+        value.0
+    }
+}
+
+/// Deserializes a unit type from its string form or a plain number
+struct UnitVisitor<T>(std::marker::PhantomData<T>);
+
+impl<T: FromStr<Err = UnitParseError>> serde::de::Visitor<'_> for UnitVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "a number or a string with a unit, e.g. \"30s\" or \"512MB\""
+        )
+    }
+
+    fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<T, E> {
+        v.parse().map_err(E::custom)
+    }
+
+    fn visit_u64<E: serde::de::Error>(self, v: u64) -> Result<T, E> {
+        self.visit_str(&v.to_string())
+    }
+
+    fn visit_i64<E: serde::de::Error>(self, v: i64) -> Result<T, E> {
+        self.visit_str(&v.to_string())
+    }
+
+    fn visit_f64<E: serde::de::Error>(self, v: f64) -> Result<T, E> {
+        self.visit_str(&v.to_string())
+    }
+}
+
+macro_rules! impl_serde {
+    ($($ty:ty),*) => {
+        $(
+            impl<'de> serde::Deserialize<'de> for $ty {
+                fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    deserializer.deserialize_any(UnitVisitor(std::marker::PhantomData))
+                }
+            }
+
+            impl serde::Serialize for $ty {
+                fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    serializer.collect_str(self)
+                }
+            }
+
+            #[cfg(feature = "schema")]
+            impl schemars::JsonSchema for $ty {
+                fn schema_name() -> std::borrow::Cow<'static, str> {
+                    stringify!($ty).into()
+                }
+
+                fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+                    schemars::json_schema!({ "type": ["string", "number"] })
+                }
+            }
+        )*
+    };
+}
+
+impl_serde!(HumanDuration, ByteSize);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_duration() {
+        let parse = |s: &str| s.parse::<HumanDuration>().map(|d| d.0);
+
+        assert_eq!(parse("30s"), Ok(Duration::from_secs(30)));
+        assert_eq!(parse("5m"), Ok(Duration::from_secs(300)));
+        assert_eq!(parse("1h30m"), Ok(Duration::from_secs(5_400)));
+        assert_eq!(parse("250ms"), Ok(Duration::from_millis(250)));
+        assert_eq!(parse("1.5s"), Ok(Duration::from_millis(1_500)));
+        assert_eq!(parse("10"), Ok(Duration::from_secs(10)));
+        assert!(parse("5 fortnights").is_err());
+        assert!(parse("").is_err());
+        assert!(parse("-1s").is_err());
+    }
+
+    #[test]
+    fn test_parse_byte_size() {
+        let parse = |s: &str| s.parse::<ByteSize>().map(|b| b.0);
+
+        assert_eq!(parse("512MB"), Ok(512_000_000));
+        assert_eq!(parse("512 MiB"), Ok(512 << 20));
+        assert_eq!(parse("1.5gib"), Ok(3 << 29));
+        assert_eq!(parse("1024"), Ok(1_024));
+        assert!(parse("1.5B").is_err());
+        assert!(parse("12 parsecs").is_err());
+        assert!(parse("1MB 2KB").is_err());
+    }
+
+    #[test]
+    fn test_display_round_trips() {
+        for input in ["30s", "5m", "90m", "250ms", "2d"] {
+            assert_eq!(input.parse::<HumanDuration>().unwrap().to_string(), input);
+        }
+
+        for input in ["512MB", "512MiB", "1023B"] {
+            assert_eq!(input.parse::<ByteSize>().unwrap().to_string(), input);
+        }
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_bind() {
+        use crate::{Config, MemoryProvider};
+
+        #[derive(serde::Deserialize)]
+        struct Limits {
+            timeout: HumanDuration,
+            retry_after: HumanDuration,
+            batch_memory: ByteSize,
+        }
+
+        let config = Config::new()
+            .with_provider(MemoryProvider::from_pairs([
+                ("limits.timeout", loom_core::value::Value::from("30s")),
+                ("limits.retry_after", loom_core::value::Value::from(5i64)),
+                (
+                    "limits.batch_memory",
+                    loom_core::value::Value::from("512MB"),
+                ),
+            ]))
+            .build()
+            .unwrap();
+
+        let limits: Limits = config
+            .bind_section(&loom_core::path::IdentPath::parse("limits").unwrap())
+            .unwrap();
+        assert_eq!(*limits.timeout, Duration::from_secs(30));
+        assert_eq!(*limits.retry_after, Duration::from_secs(5));
+        assert_eq!(limits.batch_memory, ByteSize(512_000_000));
+    }
+}
@@ -0,0 +1,745 @@
+use std::cell::RefCell;
+use std::collections::HashSet;
+
+use serde::de::value::StrDeserializer;
+use serde::de::{self, DeserializeOwned, DeserializeSeed, Visitor};
+
+use loom_core::path::{IdentPath, IdentSegment};
+use loom_core::value::{Number, Value};
+
+/// Upper bound on re-deserializing a config to get past its errors
+const MAX_PASSES: usize = 256;
+
+/// One problem found by validating a config against the type it binds to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationError {
+    pub path: IdentPath,
+    pub kind: ValidationErrorKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationErrorKind {
+    /// Key the type has no field for, usually a typo
+    UnknownKey { expected: Vec<String> },
+
+    /// Required field that isn't set
+    MissingField,
+
+    /// Value of the wrong type, or otherwise rejected by the type
+    Invalid(String),
+}
+
+impl ValidationError {
+    pub fn is_unknown_key(&self) -> bool {
+        matches!(self.kind, ValidationErrorKind::UnknownKey { .. })
+    }
+
+    pub fn is_missing_field(&self) -> bool {
+        matches!(self.kind, ValidationErrorKind::MissingField)
+    }
+
+    pub fn is_invalid(&self) -> bool {
+        matches!(self.kind, ValidationErrorKind::Invalid(_))
+    }
+}
+
+impl std::fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let path = if self.path.is_empty() {
+            "(root)".to_string()
+        } else {
+            self.path.to_string()
+        };
+
+        match &self.kind {
+            ValidationErrorKind::UnknownKey { expected } if expected.is_empty() => {
+                write!(f, "{}: unknown key", path)
+            }
+            ValidationErrorKind::UnknownKey { expected } => {
+                write!(
+                    f,
+                    "{}: unknown key, expected one of `{}`",
+                    path,
+                    expected.join("`, `")
+                )
+            }
+            ValidationErrorKind::MissingField => write!(f, "{}: missing field", path),
+            ValidationErrorKind::Invalid(msg) => write!(f, "{}: {}", path, msg),
+        }
+    }
+}
+
+/// Every error deserializing `value` (found at `base`) into `T`, in path
+/// order.
+///
+/// Unknown keys are noted as structs are visited. Any other error aborts
+/// deserialization, so it is recorded and the value at its path replaced
+/// with a placeholder of whatever type is asked for, and `T` deserialized
+/// again, until it succeeds or an error repeats.
+pub(crate) fn validate<T: DeserializeOwned>(
+    value: &Value,
+    base: &IdentPath,
+) -> Vec<ValidationError> {
+    let mut placeholders = HashSet::new();
+    let mut errors: Vec<(Vec<IdentSegment>, ValidationErrorKind)> = Vec::new();
+
+    for _ in 0..MAX_PASSES {
+        let state = State {
+            unknown: RefCell::new(Vec::new()),
+            placeholders: &placeholders,
+        };
+        let result = T::deserialize(ValueDeserializer::new(Some(value), Vec::new(), &state));
+
+        for unknown in state.unknown.into_inner() {
+            if !errors.contains(&unknown) {
+                errors.push(unknown);
+            }
+        }
+
+        let Err(err) = result else {
+            break;
+        };
+
+        let mut path = err.path.unwrap_or_default();
+        let kind = match err.kind {
+            ErrorKind::Missing(field) => {
+                path.push(IdentSegment::Key(field));
+                ValidationErrorKind::MissingField
+            }
+            ErrorKind::Invalid(msg) => ValidationErrorKind::Invalid(msg),
+        };
+
+        errors.push((path.clone(), kind));
+
+        if !placeholders.insert(path) {
+            break;
+        }
+    }
+
+    let mut errors: Vec<ValidationError> = errors
+        .into_iter()
+        .map(|(path, kind)| {
+            let mut segments = base.segments().to_vec();
+            segments.extend(path);
+
+            ValidationError {
+                path: IdentPath::from(segments),
+                kind,
+            }
+        })
+        .collect();
+
+    errors.sort_by_cached_key(|err| err.path.to_string());
+    errors
+}
+
+struct State<'a> {
+    unknown: RefCell<Vec<(Vec<IdentSegment>, ValidationErrorKind)>>,
+    placeholders: &'a HashSet<Vec<IdentSegment>>,
+}
+
+#[derive(Debug)]
+enum ErrorKind {
+    Missing(String),
+    Invalid(String),
+}
+
+/// Deserialization error, tagged with the path it was raised at once it
+/// leaves the value's deserializer
+#[derive(Debug)]
+struct Error {
+    path: Option<Vec<IdentSegment>>,
+    kind: ErrorKind,
+}
+
+impl Error {
+    fn at(mut self, path: &[IdentSegment]) -> Self {
+        if self.path.is_none() {
+            self.path = Some(path.to_vec());
+        }
+
+        self
+    }
+}
+
+impl de::Error for Error {
+    fn custom<T: std::fmt::Display>(msg: T) -> Self {
+        Self {
+            path: None,
+            kind: ErrorKind::Invalid(msg.to_string()),
+        }
+    }
+
+    fn missing_field(field: &'static str) -> Self {
+        Self {
+            path: None,
+            kind: ErrorKind::Missing(field.to_string()),
+        }
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.kind {
+            ErrorKind::Missing(field) => write!(f, "missing field `{}`", field),
+            ErrorKind::Invalid(msg) => write!(f, "{}", msg),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Deserializes a config [`Value`], or a placeholder for one (`None`) that
+/// gives each visitor an empty value of the type it asks for
+struct ValueDeserializer<'a> {
+    value: Option<&'a Value>,
+    path: Vec<IdentSegment>,
+    state: &'a State<'a>,
+}
+
+impl<'a> ValueDeserializer<'a> {
+    fn new(value: Option<&'a Value>, path: Vec<IdentSegment>, state: &'a State<'a>) -> Self {
+        let value = value.filter(|_| !state.placeholders.contains(&path));
+        Self { value, path, state }
+    }
+
+    fn child(&self, value: Option<&'a Value>, segment: IdentSegment) -> Self {
+        let mut path = self.path.clone();
+        path.push(segment);
+        Self::new(value, path, self.state)
+    }
+
+    fn unexpected(value: &Value) -> de::Unexpected<'_> {
+        match value {
+            Value::Null => de::Unexpected::Unit,
+            Value::Bool(v) => de::Unexpected::Bool(*v),
+            Value::Number(Number::Int(v)) => de::Unexpected::Signed(*v),
+            Value::Number(Number::Float(v)) => de::Unexpected::Float(*v),
+            Value::String(v) => de::Unexpected::Str(v),
+            Value::Array(_) => de::Unexpected::Seq,
+            Value::Object(_) => de::Unexpected::Map,
+        }
+    }
+
+    fn any<'de, V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        let Some(value) = self.value else {
+            return visitor.visit_unit();
+        };
+
+        match value {
+            Value::Null => visitor.visit_unit(),
+            Value::Bool(v) => visitor.visit_bool(*v),
+            Value::Number(Number::Int(v)) => visitor.visit_i64(*v),
+            Value::Number(Number::Float(v)) => visitor.visit_f64(*v),
+            Value::String(v) => visitor.visit_str(v),
+            Value::Array(items) => visitor.visit_seq(Seq {
+                items: items
+                    .iter()
+                    .enumerate()
+                    .map(|(i, item)| self.child(Some(item), IdentSegment::Index(i)))
+                    .collect::<Vec<_>>()
+                    .into_iter(),
+            }),
+            Value::Object(object) => visitor.visit_map(Map {
+                entries: object
+                    .iter()
+                    .map(|(key, value)| {
+                        let child = self.child(Some(value), IdentSegment::Key(key.clone()));
+                        (key.as_str(), child)
+                    })
+                    .collect::<Vec<_>>()
+                    .into_iter(),
+                value: None,
+            }),
+        }
+    }
+
+    /// Placeholder tuple of `len` placeholders
+    fn placeholder_seq<'de, V: Visitor<'de>>(
+        self,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_seq(Seq {
+            items: (0..len)
+                .map(|i| self.child(None, IdentSegment::Index(i)))
+                .collect::<Vec<_>>()
+                .into_iter(),
+        })
+    }
+
+    fn structure<'de, V: Visitor<'de>>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        let object = match self.value {
+            Some(Value::Object(object)) => object,
+            Some(_) => return self.any(visitor),
+            None => {
+                return visitor.visit_map(Map {
+                    entries: fields
+                        .iter()
+                        .map(|field| {
+                            (
+                                *field,
+                                self.child(None, IdentSegment::Key(field.to_string())),
+                            )
+                        })
+                        .collect::<Vec<_>>()
+                        .into_iter(),
+                    value: None,
+                });
+            }
+        };
+
+        let mut entries = Vec::new();
+
+        for (key, value) in object.iter() {
+            let child = self.child(Some(value), IdentSegment::Key(key.clone()));
+
+            if !fields.is_empty() && !fields.contains(&key.as_str()) {
+                let expected = fields.iter().map(|field| field.to_string()).collect();
+                self.state
+                    .unknown
+                    .borrow_mut()
+                    .push((child.path, ValidationErrorKind::UnknownKey { expected }));
+                continue;
+            }
+
+            entries.push((key.as_str(), child));
+        }
+
+        // Stand in for required fields already reported missing
+        for field in fields {
+            let child = self.child(None, IdentSegment::Key(field.to_string()));
+
+            if !object.contains_key(*field) && self.state.placeholders.contains(&child.path) {
+                entries.push((*field, child));
+            }
+        }
+
+        visitor.visit_map(Map {
+            entries: entries.into_iter(),
+            value: None,
+        })
+    }
+
+    fn enumeration<'de, V: Visitor<'de>>(
+        self,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        match self.value {
+            Some(Value::String(variant)) => visitor.visit_enum(Enum {
+                variant,
+                value: None,
+            }),
+            Some(Value::Object(object)) if object.len() == 1 => {
+                let (variant, value) = object.iter().next().expect("one entry");
+                let value = self.child(Some(value), IdentSegment::Key(variant.clone()));
+
+                visitor.visit_enum(Enum {
+                    variant,
+                    value: Some(value),
+                })
+            }
+            Some(value) => Err(de::Error::invalid_type(
+                Self::unexpected(value),
+                &"a variant name or a map with one key",
+            )),
+            None => {
+                let variant = variants
+                    .first()
+                    .copied()
+                    .ok_or_else(|| <Error as de::Error>::custom("enum has no variants"))?;
+                let value = self.child(None, IdentSegment::Key(variant.to_string()));
+
+                visitor.visit_enum(Enum {
+                    variant,
+                    value: Some(value),
+                })
+            }
+        }
+    }
+}
+
+/// Typed deserialize methods: placeholders visit `$visit($default)`, values
+/// are visited as whatever they hold
+macro_rules! deserialize_typed {
+    ($($method:ident => $visit:ident($($default:expr)?)),* $(,)?) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+                let path = self.path.clone();
+
+                match self.value {
+                    None => visitor.$visit($($default)?),
+                    Some(_) => self.any(visitor),
+                }
+                .map_err(|err| err.at(&path))
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for ValueDeserializer<'_> {
+    type Error = Error;
+
+    deserialize_typed! {
+        deserialize_any => visit_unit(),
+        deserialize_bool => visit_bool(false),
+        deserialize_i8 => visit_i64(0),
+        deserialize_i16 => visit_i64(0),
+        deserialize_i32 => visit_i64(0),
+        deserialize_i64 => visit_i64(0),
+        deserialize_u8 => visit_u64(0),
+        deserialize_u16 => visit_u64(0),
+        deserialize_u32 => visit_u64(0),
+        deserialize_u64 => visit_u64(0),
+        deserialize_f32 => visit_f64(0.0),
+        deserialize_f64 => visit_f64(0.0),
+        deserialize_char => visit_char('\0'),
+        deserialize_str => visit_str(""),
+        deserialize_string => visit_str(""),
+        deserialize_bytes => visit_bytes(&[]),
+        deserialize_byte_buf => visit_bytes(&[]),
+        deserialize_unit => visit_unit(),
+        deserialize_identifier => visit_str(""),
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        let path = self.path.clone();
+
+        match self.value {
+            None | Some(Value::Null) => visitor.visit_none(),
+            Some(_) => visitor.visit_some(self),
+        }
+        .map_err(|err| err.at(&path))
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_unit(visitor)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        let path = self.path.clone();
+        visitor
+            .visit_newtype_struct(self)
+            .map_err(|err| err.at(&path))
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_tuple(0, visitor)
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value, Error> {
+        let path = self.path.clone();
+
+        match self.value {
+            None => self.placeholder_seq(len, visitor),
+            Some(_) => self.any(visitor),
+        }
+        .map_err(|err| err.at(&path))
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_tuple(len, visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_struct("", &[], visitor)
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        let path = self.path.clone();
+        self.structure(fields, visitor).map_err(|err| err.at(&path))
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        let path = self.path.clone();
+        self.enumeration(variants, visitor)
+            .map_err(|err| err.at(&path))
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_unit()
+    }
+}
+
+struct Seq<'a> {
+    items: std::vec::IntoIter<ValueDeserializer<'a>>,
+}
+
+impl<'de> de::SeqAccess<'de> for Seq<'_> {
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Error> {
+        self.items
+            .next()
+            .map(|item| seed.deserialize(item))
+            .transpose()
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.items.len())
+    }
+}
+
+struct Map<'a> {
+    entries: std::vec::IntoIter<(&'a str, ValueDeserializer<'a>)>,
+    value: Option<ValueDeserializer<'a>>,
+}
+
+impl<'de> de::MapAccess<'de> for Map<'_> {
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Error> {
+        let Some((key, value)) = self.entries.next() else {
+            return Ok(None);
+        };
+
+        let path = value.path.clone();
+        self.value = Some(value);
+
+        seed.deserialize(StrDeserializer::<Error>::new(key))
+            .map(Some)
+            .map_err(|err| err.at(&path))
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Error> {
+        let value = self
+            .value
+            .take()
+            .ok_or_else(|| <Error as de::Error>::custom("value requested before key"))?;
+
+        seed.deserialize(value)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.entries.len())
+    }
+}
+
+struct Enum<'a> {
+    variant: &'a str,
+    value: Option<ValueDeserializer<'a>>,
+}
+
+impl<'de, 'a> de::EnumAccess<'de> for Enum<'a> {
+    type Error = Error;
+    type Variant = Variant<'a>;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, Variant<'a>), Error> {
+        let variant = seed.deserialize(StrDeserializer::<Error>::new(self.variant))?;
+        Ok((variant, Variant(self.value)))
+    }
+}
+
+struct Variant<'a>(Option<ValueDeserializer<'a>>);
+
+impl<'de> de::VariantAccess<'de> for Variant<'_> {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, Error> {
+        match self.0 {
+            Some(value) => seed.deserialize(value),
+            None => Err(de::Error::invalid_type(
+                de::Unexpected::UnitVariant,
+                &"newtype variant",
+            )),
+        }
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value, Error> {
+        match self.0 {
+            Some(value) => de::Deserializer::deserialize_tuple(value, len, visitor),
+            None => Err(de::Error::invalid_type(
+                de::Unexpected::UnitVariant,
+                &"tuple variant",
+            )),
+        }
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        match self.0 {
+            Some(value) => de::Deserializer::deserialize_struct(value, "", fields, visitor),
+            None => Err(de::Error::invalid_type(
+                de::Unexpected::UnitVariant,
+                &"struct variant",
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Config, ConfigError, MemoryProvider};
+
+    #[derive(Debug, serde::Deserialize)]
+    #[allow(dead_code)]
+    struct AppConfig {
+        name: String,
+        database: DatabaseConfig,
+        #[serde(default)]
+        servers: Vec<ServerConfig>,
+    }
+
+    #[derive(Debug, serde::Deserialize)]
+    #[allow(dead_code)]
+    struct DatabaseConfig {
+        host: String,
+        port: u16,
+        #[serde(default)]
+        mode: Option<Mode>,
+    }
+
+    #[derive(Debug, serde::Deserialize)]
+    #[allow(dead_code)]
+    struct ServerConfig {
+        port: u16,
+    }
+
+    #[derive(Debug, serde::Deserialize)]
+    #[serde(rename_all = "lowercase")]
+    enum Mode {
+        Primary,
+        Replica,
+    }
+
+    fn config(pairs: &[(&str, Value)]) -> Config {
+        let mut root = Value::Object(loom_core::value::Object::new());
+
+        for (key, value) in pairs {
+            crate::EnvProvider::set_by_path(&mut root, key, value.clone());
+        }
+
+        Config::new()
+            .with_provider(MemoryProvider::from_value(root))
+            .build()
+            .unwrap()
+    }
+
+    fn paths(errors: &[ValidationError]) -> Vec<String> {
+        errors.iter().map(|err| err.path.to_string()).collect()
+    }
+
+    #[test]
+    fn test_valid_config() {
+        let config = config(&[
+            ("name", Value::from("merc")),
+            ("database.host", Value::from("localhost")),
+            ("database.port", Value::from(5432i64)),
+            ("database.mode", Value::from("replica")),
+        ]);
+
+        assert!(config.validate_against::<AppConfig>().is_ok());
+    }
+
+    #[test]
+    fn test_reports_every_error() {
+        let config = config(&[
+            ("name", Value::from("merc")),
+            ("database.hots", Value::from("localhost")),
+            ("database.port", Value::from("5432")),
+            ("database.mode", Value::from("leader")),
+        ]);
+
+        let errors = validate::<AppConfig>(config.as_value(), &IdentPath::from(Vec::new()));
+
+        assert_eq!(
+            paths(&errors),
+            [
+                "database.host",
+                "database.hots",
+                "database.mode",
+                "database.port"
+            ]
+        );
+        assert!(errors[0].is_missing_field());
+        assert_eq!(
+            errors[1].kind,
+            ValidationErrorKind::UnknownKey {
+                expected: vec!["host".into(), "port".into(), "mode".into()]
+            }
+        );
+        assert!(errors[2].is_invalid());
+        assert!(
+            errors[3]
+                .to_string()
+                .starts_with("database.port: invalid type")
+        );
+    }
+
+    #[test]
+    fn test_reports_nested_paths() {
+        let mut server = loom_core::value::Object::new();
+        server.insert("port".to_string(), Value::from(-1i64));
+
+        let config = config(&[
+            ("database.host", Value::from("localhost")),
+            ("database.port", Value::from(5432i64)),
+            ("servers", Value::Array(vec![Value::Object(server)].into())),
+        ]);
+
+        let err = config.validate_against::<AppConfig>().unwrap_err();
+        assert!(err.is_validation());
+
+        let ConfigError::Validation(errors) = err else {
+            unreachable!();
+        };
+        assert_eq!(paths(&errors), ["name", "servers[0].port"]);
+    }
+
+    #[test]
+    fn test_section_paths_include_section() {
+        let config = config(&[("database.host", Value::from(1i64))]);
+        let section = config.get_section(&IdentPath::parse("database").unwrap());
+
+        let ConfigError::Validation(errors) = section.validate::<DatabaseConfig>().unwrap_err()
+        else {
+            unreachable!();
+        };
+        assert_eq!(paths(&errors), ["database.host", "database.port"]);
+    }
+}
@@ -0,0 +1,41 @@
+//! Benchmarks for `IdentPath` parsing used by config section lookups.
+//!
+//! Run with `cargo bench -p loom-core --bench path`.
+//! Set `LOOM_BENCH_SAMPLE_SIZE` to trade accuracy for speed (default: 100).
+
+use std::hint::black_box;
+
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use loom_core::path::IdentPath;
+
+#[path = "../../bench.rs"]
+mod bench;
+
+fn bench_parse(c: &mut Criterion) {
+    let mut group = c.benchmark_group("ident_path/parse");
+    let inputs = [
+        ("key", "threshold"),
+        ("nested", "layers.score.threshold"),
+        ("indexed", "layers.score.categories[2].labels[10].weight"),
+        (
+            "deep",
+            "a.b.c.d.e.f.g.h.i.j.k.l.m.n.o.p[0].q[1].r[2].s[3].t[4]",
+        ),
+    ];
+
+    for (name, input) in inputs {
+        group.bench_with_input(BenchmarkId::from_parameter(name), input, |b, input| {
+            b.iter(|| IdentPath::parse(black_box(input)).expect("valid path"))
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group! {
+    name = benches;
+    config = bench::config(100);
+    targets = bench_parse
+}
+
+criterion_main!(benches);
@@ -0,0 +1,63 @@
+//! Benchmarks for `Value` operations on the config/scoring hot path.
+//!
+//! Run with `cargo bench -p loom-core --bench value`.
+//! Set `LOOM_BENCH_SAMPLE_SIZE` to trade accuracy for speed (default: 100).
+
+use std::hint::black_box;
+
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use loom_core::value::{Object, Value};
+
+#[path = "../../bench.rs"]
+mod bench;
+
+/// Build an object `depth` levels deep with `width` keys per level.
+fn object(depth: usize, width: usize, seed: i64) -> Value {
+    let mut obj = Object::new();
+
+    for i in 0..width {
+        let value = if depth == 0 {
+            Value::from(seed + i as i64)
+        } else {
+            object(depth - 1, width, seed)
+        };
+
+        obj.insert(format!("key_{}", i), value);
+    }
+
+    Value::Object(obj)
+}
+
+fn bench_merge(c: &mut Criterion) {
+    let mut group = c.benchmark_group("value/merge");
+
+    for (depth, width) in [(1, 8), (2, 8), (3, 8), (2, 32)] {
+        let target = object(depth, width, 0);
+        let source = object(depth, width, 1);
+
+        group.bench_with_input(
+            BenchmarkId::from_parameter(format!("{}x{}", depth, width)),
+            &(target, source),
+            |b, (target, source)| {
+                b.iter_batched(
+                    || (target.clone(), source.clone()),
+                    |(mut target, source)| {
+                        target.merge(source);
+                        black_box(target)
+                    },
+                    criterion::BatchSize::SmallInput,
+                )
+            },
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group! {
+    name = benches;
+    config = bench::config(100);
+    targets = bench_merge
+}
+
+criterion_main!(benches);
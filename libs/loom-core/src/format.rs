@@ -1,9 +1,25 @@
+use crate::MediaType;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Deserialize, serde::Serialize)]
 #[serde(rename_all = "snake_case")]
 pub enum Format {
     Json,
+    /// Newline-delimited JSON (JSON Lines)
+    Ndjson,
     Yaml,
     Toml,
+    /// INI / Java `.properties` key-value files
+    Ini,
+    /// MessagePack binary serialization
+    MsgPack,
+    /// BSON, MongoDB's binary document format
+    Bson,
+    /// Protocol Buffers, decoded against a message descriptor
+    Protobuf,
+    /// Apache Parquet columnar storage
+    Parquet,
+    /// Apache Arrow IPC files and streams (Feather v2)
+    Arrow,
     Xml,
     Csv,
     Markdown,
@@ -12,12 +28,65 @@ pub enum Format {
     Binary,
 }
 
+impl Format {
+    /// Canonical media type of records in this format.
+    pub fn media_type(self) -> MediaType {
+        match self {
+            Self::Json => MediaType::TextJson,
+            Self::Ndjson => MediaType::TextNdjson,
+            Self::Yaml => MediaType::TextYaml,
+            Self::Toml => MediaType::TextToml,
+            Self::Ini => MediaType::TextIni,
+            Self::MsgPack => MediaType::MsgPack,
+            Self::Bson => MediaType::Bson,
+            Self::Protobuf => MediaType::Protobuf,
+            Self::Parquet => MediaType::Parquet,
+            Self::Arrow => MediaType::Arrow,
+            Self::Xml => MediaType::TextXml,
+            Self::Csv => MediaType::TextCsv,
+            Self::Markdown => MediaType::TextMarkdown,
+            Self::Html => MediaType::TextHtml,
+            Self::Text => MediaType::TextPlain,
+            Self::Binary => MediaType::Binary,
+        }
+    }
+
+    /// Canonical file extension (without the leading dot).
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Json => "json",
+            Self::Ndjson => "jsonl",
+            Self::Yaml => "yaml",
+            Self::Toml => "toml",
+            Self::Ini => "ini",
+            Self::MsgPack => "msgpack",
+            Self::Bson => "bson",
+            Self::Protobuf => "pb",
+            Self::Parquet => "parquet",
+            Self::Arrow => "arrow",
+            Self::Xml => "xml",
+            Self::Csv => "csv",
+            Self::Markdown => "md",
+            Self::Html => "html",
+            Self::Text => "txt",
+            Self::Binary => "bin",
+        }
+    }
+}
+
 impl std::fmt::Display for Format {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::Json => write!(f, "json"),
+            Self::Ndjson => write!(f, "ndjson"),
             Self::Yaml => write!(f, "yaml"),
             Self::Toml => write!(f, "toml"),
+            Self::Ini => write!(f, "ini"),
+            Self::MsgPack => write!(f, "msgpack"),
+            Self::Bson => write!(f, "bson"),
+            Self::Protobuf => write!(f, "protobuf"),
+            Self::Parquet => write!(f, "parquet"),
+            Self::Arrow => write!(f, "arrow"),
             Self::Xml => write!(f, "xml"),
             Self::Csv => write!(f, "csv"),
             Self::Markdown => write!(f, "markdown"),
@@ -12,6 +12,10 @@ pub use id::*;
 pub use map::*;
 pub use media_type::*;
 
+/// Stands in for secret values wherever they would be shown, e.g. `Debug`
+/// output, serialized configs and redacted signals
+pub const REDACTED: &str = "***";
+
 /// Encode a value to a string in the specified format.
 ///
 /// # Usage
@@ -23,6 +27,7 @@ pub use media_type::*;
 ///
 /// // Encode using Format enum (runtime dispatch)
 /// let s = encode!(&data, Format::Json)?;
+/// let jsonl = encode!(&rows, Format::Ndjson)?;
 /// ```
 #[macro_export]
 macro_rules! encode {
@@ -64,6 +69,17 @@ macro_rules! encode {
             $crate::Format::Json => {
                 ::serde_json::to_string_pretty($value).map_err(|e| e.to_string())
             }
+            // Arrays are written one element per line
+            #[cfg(feature = "json")]
+            $crate::Format::Ndjson => ::serde_json::to_value($value)
+                .and_then(|value| match value {
+                    ::serde_json::Value::Array(items) => items
+                        .iter()
+                        .map(|item| ::serde_json::to_string(item).map(|line| line + "\n"))
+                        .collect::<Result<String, _>>(),
+                    other => ::serde_json::to_string(&other).map(|line| line + "\n"),
+                })
+                .map_err(|e| e.to_string()),
             #[cfg(feature = "yaml")]
             $crate::Format::Yaml => ::serde_saphyr::to_string($value).map_err(|e| e.to_string()),
             #[cfg(feature = "toml")]
@@ -85,6 +101,7 @@ macro_rules! encode {
 ///
 /// // Decode using Format enum (runtime dispatch)
 /// let data: MyType = decode!(content, Format::Json)?;
+/// let rows: Vec<Row> = decode!(content, Format::Ndjson)?;
 /// ```
 #[macro_export]
 macro_rules! decode {
@@ -124,6 +141,15 @@ macro_rules! decode {
         match $format {
             #[cfg(feature = "json")]
             $crate::Format::Json => ::serde_json::from_str($value).map_err(|e| e.to_string()),
+            // Each non-empty line is one element of an array
+            #[cfg(feature = "json")]
+            $crate::Format::Ndjson => $value
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .map(::serde_json::from_str::<::serde_json::Value>)
+                .collect::<Result<Vec<_>, _>>()
+                .and_then(|items| ::serde_json::from_value(::serde_json::Value::Array(items)))
+                .map_err(|e| e.to_string()),
             #[cfg(feature = "yaml")]
             $crate::Format::Yaml => ::serde_saphyr::from_str($value).map_err(|e| e.to_string()),
             #[cfg(feature = "toml")]
@@ -12,8 +12,10 @@ pub enum MediaType {
     TextXml,
     TextCsv,
     TextToml,
+    TextIni,
     TextYaml,
     TextJson,
+    TextNdjson,
 
     // --- Code (optional but handy for memory services) ---
     CodeRust,
@@ -37,7 +39,11 @@ pub enum MediaType {
     Pptx,
     Xlsx,
     Parquet,
+    Arrow,
     Avro,
+    MsgPack,
+    Bson,
+    Protobuf,
 
     // --- Images ---
     ImagePng,
@@ -57,6 +63,7 @@ pub enum MediaType {
     ArchiveZip,
     ArchiveTar,
     ArchiveGzip,
+    ArchiveZstd,
 
     // --- Fallbacks ---
     /// Known to be text, but not otherwise classified.
@@ -82,8 +89,10 @@ impl MediaType {
             Self::TextXml => "text/xml",
             Self::TextCsv => "text/csv",
             Self::TextToml => "application/toml",
+            Self::TextIni => "text/x-ini",
             Self::TextYaml => "application/yaml",
             Self::TextJson => "application/json",
+            Self::TextNdjson => "application/x-ndjson",
 
             Self::CodeRust => "text/x-rust",
             Self::CodeCSharp => "text/x-csharp",
@@ -107,7 +116,11 @@ impl MediaType {
             }
             Self::Xlsx => "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
             Self::Parquet => "application/x-parquet",
+            Self::Arrow => "application/vnd.apache.arrow.file",
             Self::Avro => "application/avro",
+            Self::MsgPack => "application/msgpack",
+            Self::Bson => "application/bson",
+            Self::Protobuf => "application/x-protobuf",
 
             Self::ImagePng => "image/png",
             Self::ImageJpeg => "image/jpeg",
@@ -124,6 +137,7 @@ impl MediaType {
             Self::ArchiveZip => "application/zip",
             Self::ArchiveTar => "application/x-tar",
             Self::ArchiveGzip => "application/gzip",
+            Self::ArchiveZstd => "application/zstd",
 
             Self::Text => "text/plain",
             Self::Binary => "application/octet-stream",
@@ -140,8 +154,10 @@ impl MediaType {
                 | Self::TextXml
                 | Self::TextCsv
                 | Self::TextToml
+                | Self::TextIni
                 | Self::TextYaml
                 | Self::TextJson
+                | Self::TextNdjson
                 | Self::CodeRust
                 | Self::CodeCSharp
                 | Self::CodeTypeScript
@@ -163,12 +179,19 @@ impl MediaType {
     pub fn format(self) -> Format {
         match self {
             Self::TextJson => Format::Json,
+            Self::TextNdjson => Format::Ndjson,
             Self::TextYaml => Format::Yaml,
             Self::TextToml => Format::Toml,
+            Self::TextIni => Format::Ini,
             Self::TextXml => Format::Xml,
             Self::TextCsv => Format::Csv,
             Self::TextMarkdown => Format::Markdown,
             Self::TextHtml => Format::Html,
+            Self::MsgPack => Format::MsgPack,
+            Self::Bson => Format::Bson,
+            Self::Protobuf => Format::Protobuf,
+            Self::Parquet => Format::Parquet,
+            Self::Arrow => Format::Arrow,
             Self::TextPlain
             | Self::Text
             | Self::CodeRust
@@ -204,8 +227,10 @@ impl MediaType {
             Some("xml") => Self::TextXml,
             Some("csv") => Self::TextCsv,
             Some("toml") => Self::TextToml,
+            Some("ini") | Some("cfg") | Some("properties") => Self::TextIni,
             Some("yaml") | Some("yml") => Self::TextYaml,
             Some("json") => Self::TextJson,
+            Some("jsonl") | Some("ndjson") => Self::TextNdjson,
 
             Some("rs") => Self::CodeRust,
             Some("cs") => Self::CodeCSharp,
@@ -228,7 +253,11 @@ impl MediaType {
             Some("pptx") => Self::Pptx,
             Some("xlsx") => Self::Xlsx,
             Some("parquet") => Self::Parquet,
+            Some("arrow") | Some("arrows") | Some("feather") | Some("ipc") => Self::Arrow,
             Some("avro") => Self::Avro,
+            Some("msgpack") | Some("mpk") => Self::MsgPack,
+            Some("bson") => Self::Bson,
+            Some("pb") | Some("binpb") => Self::Protobuf,
 
             Some("png") => Self::ImagePng,
             Some("jpg") | Some("jpeg") => Self::ImageJpeg,
@@ -245,6 +274,7 @@ impl MediaType {
             Some("zip") => Self::ArchiveZip,
             Some("tar") => Self::ArchiveTar,
             Some("gz") | Some("gzip") => Self::ArchiveGzip,
+            Some("zst") | Some("zstd") => Self::ArchiveZstd,
 
             _ => Self::Unknown,
         }
@@ -259,10 +289,23 @@ impl MediaType {
             "text/xml" | "application/xml" => Self::TextXml,
             "text/csv" => Self::TextCsv,
             "application/toml" => Self::TextToml,
+            "text/x-ini" | "text/x-java-properties" => Self::TextIni,
             "application/yaml" | "text/yaml" => Self::TextYaml,
             "application/json" | "text/json" => Self::TextJson,
+            "application/x-ndjson" | "application/ndjson" | "application/jsonl" => Self::TextNdjson,
 
             "application/pdf" => Self::Pdf,
+            "application/x-parquet" | "application/vnd.apache.parquet" => Self::Parquet,
+            "application/vnd.apache.arrow.file"
+            | "application/vnd.apache.arrow.stream"
+            | "application/x-arrow" => Self::Arrow,
+            "application/msgpack" | "application/x-msgpack" | "application/vnd.msgpack" => {
+                Self::MsgPack
+            }
+            "application/bson" => Self::Bson,
+            "application/x-protobuf"
+            | "application/protobuf"
+            | "application/vnd.google.protobuf" => Self::Protobuf,
             "application/octet-stream" => Self::Binary,
             "image/png" => Self::ImagePng,
             "image/jpeg" => Self::ImageJpeg,
@@ -279,6 +322,7 @@ impl MediaType {
             "application/zip" => Self::ArchiveZip,
             "application/x-tar" => Self::ArchiveTar,
             "application/gzip" => Self::ArchiveGzip,
+            "application/zstd" => Self::ArchiveZstd,
 
             _ => {
                 if m.starts_with("text/") {
@@ -289,6 +333,97 @@ impl MediaType {
             }
         }
     }
+
+    /// Best-effort inference from content, for input without a name such as
+    /// stdin. Recognizes common binary signatures and JSON / NDJSON, YAML and
+    /// TOML documents, falling back to `Text` or `Binary`.
+    pub fn sniff(content: &[u8]) -> Self {
+        const SIGNATURES: &[(&[u8], MediaType)] = &[
+            (b"PAR1", MediaType::Parquet),
+            (b"ARROW1", MediaType::Arrow),
+            (b"%PDF-", MediaType::Pdf),
+            (b"\x89PNG", MediaType::ImagePng),
+            (b"\xff\xd8\xff", MediaType::ImageJpeg),
+            (b"GIF8", MediaType::ImageGif),
+            (b"PK\x03\x04", MediaType::ArchiveZip),
+            (b"\x1f\x8b", MediaType::ArchiveGzip),
+            (b"\x28\xb5\x2f\xfd", MediaType::ArchiveZstd),
+        ];
+
+        if let Some((_, media_type)) = SIGNATURES.iter().find(|(sig, _)| content.starts_with(sig)) {
+            return *media_type;
+        }
+
+        let Ok(text) = std::str::from_utf8(content) else {
+            return Self::Binary;
+        };
+
+        let text = text.trim_start_matches('\u{feff}').trim();
+        let mut lines = text.lines().map(str::trim).filter(|l| !l.is_empty());
+        let Some(first) = lines.next() else {
+            return Self::Text;
+        };
+
+        if first.starts_with('{') || first.starts_with('[') {
+            let ndjson = first.starts_with('{')
+                && first.ends_with('}')
+                && text.lines().filter(|l| !l.trim().is_empty()).count() > 1
+                && lines.all(|l| l.starts_with('{') && l.ends_with('}'));
+
+            // `[section]` headers are TOML, unless the array holds JSON values
+            if first.starts_with('[') && first.ends_with(']') && is_toml_table(first) {
+                return Self::TextToml;
+            }
+
+            return if ndjson {
+                Self::TextNdjson
+            } else {
+                Self::TextJson
+            };
+        }
+
+        if first == "---" || first.starts_with("- ") || is_yaml_mapping(first) {
+            return Self::TextYaml;
+        }
+
+        if is_toml_pair(first) {
+            return Self::TextToml;
+        }
+
+        Self::Text
+    }
+}
+
+/// `[name]` or `[[name]]` with a bare or dotted key, as opposed to a JSON array
+fn is_toml_table(line: &str) -> bool {
+    let name = line.trim_start_matches('[').trim_end_matches(']').trim();
+    name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "_-.".contains(c))
+}
+
+/// `key = value` with a bare key
+fn is_toml_pair(line: &str) -> bool {
+    line.split_once('=').is_some_and(|(key, _)| {
+        let key = key.trim();
+        !key.is_empty()
+            && key
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || "_-.".contains(c))
+    })
+}
+
+/// `key: value` or `key:` with a bare key
+fn is_yaml_mapping(line: &str) -> bool {
+    line.split_once(':').is_some_and(|(key, rest)| {
+        let key = key.trim();
+        (rest.is_empty() || rest.starts_with(' '))
+            && !key.is_empty()
+            && key
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || "_-".contains(c))
+    })
 }
 
 impl std::fmt::Display for MediaType {
@@ -296,3 +431,45 @@ impl std::fmt::Display for MediaType {
         write!(f, "{}", self.as_mime_str())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sniffs_structured_text() {
+        assert_eq!(MediaType::sniff(b"{\"a\": 1}"), MediaType::TextJson);
+        assert_eq!(MediaType::sniff(b"  [1, 2, 3]\n"), MediaType::TextJson);
+        assert_eq!(MediaType::sniff(b"[1]"), MediaType::TextJson);
+        assert_eq!(
+            MediaType::sniff(b"{\"a\": 1}\n{\"a\": 2}\n"),
+            MediaType::TextNdjson
+        );
+        assert_eq!(MediaType::sniff(b"{\n  \"a\": 1\n}"), MediaType::TextJson);
+        assert_eq!(MediaType::sniff(b"---\nname: x\n"), MediaType::TextYaml);
+        assert_eq!(
+            MediaType::sniff(b"name: x\nitems:\n  - 1\n"),
+            MediaType::TextYaml
+        );
+        assert_eq!(
+            MediaType::sniff(b"[layers.score]\nthreshold = 0.5\n"),
+            MediaType::TextToml
+        );
+        assert_eq!(MediaType::sniff(b"name = \"x\"\n"), MediaType::TextToml);
+        assert_eq!(MediaType::sniff(b"just some words"), MediaType::Text);
+        assert_eq!(MediaType::sniff(b""), MediaType::Text);
+    }
+
+    #[test]
+    fn sniffs_binary_signatures() {
+        assert_eq!(MediaType::sniff(b"PAR1\x00\x00"), MediaType::Parquet);
+        assert_eq!(
+            MediaType::sniff(b"\x1f\x8b\x08\x00"),
+            MediaType::ArchiveGzip
+        );
+        assert_eq!(
+            MediaType::sniff(&[0xff, 0xfe, 0x00, 0x01]),
+            MediaType::Binary
+        );
+    }
+}
@@ -40,6 +40,12 @@ impl IdentPath {
     }
 }
 
+impl From<Vec<IdentSegment>> for IdentPath {
+    fn from(segments: Vec<IdentSegment>) -> Self {
+        Self(segments)
+    }
+}
+
 impl std::fmt::Display for IdentPath {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         for (i, segment) in self.0.iter().enumerate() {
@@ -0,0 +1,144 @@
+use super::Value;
+
+/// Kind of change between two values
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Modified,
+}
+
+impl std::fmt::Display for ChangeKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Added => write!(f, "added"),
+            Self::Removed => write!(f, "removed"),
+            Self::Modified => write!(f, "modified"),
+        }
+    }
+}
+
+/// A single difference produced by [`Value::diff`]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Deserialize, serde::Serialize)]
+pub struct Change {
+    /// Path to the changed value (e.g. `tags[0]`), empty for the root
+    pub path: String,
+    /// Value before the change, `None` if added
+    pub before: Option<Value>,
+    /// Value after the change, `None` if removed
+    pub after: Option<Value>,
+}
+
+impl Change {
+    pub fn kind(&self) -> ChangeKind {
+        match (&self.before, &self.after) {
+            (None, _) => ChangeKind::Added,
+            (_, None) => ChangeKind::Removed,
+            _ => ChangeKind::Modified,
+        }
+    }
+}
+
+impl Value {
+    /// Compute the leaf-level changes required to turn `self` into `other`.
+    /// Objects are compared by key and arrays by index; any other
+    /// mismatch (including a change of kind) is reported as a single modification.
+    pub fn diff(&self, other: &Value) -> Vec<Change> {
+        let mut changes = Vec::new();
+        diff_into(String::new(), Some(self), Some(other), &mut changes);
+        changes
+    }
+}
+
+fn diff_into(path: String, before: Option<&Value>, after: Option<&Value>, out: &mut Vec<Change>) {
+    match (before, after) {
+        (Some(Value::Object(a)), Some(Value::Object(b))) => {
+            let keys: std::collections::BTreeSet<&String> = a.keys().chain(b.keys()).collect();
+
+            for key in keys {
+                let path = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", path, key)
+                };
+
+                diff_into(path, a.get(key), b.get(key), out);
+            }
+        }
+        (Some(Value::Array(a)), Some(Value::Array(b))) => {
+            for i in 0..a.len().max(b.len()) {
+                diff_into(format!("{}[{}]", path, i), a.get(i), b.get(i), out);
+            }
+        }
+        (a, b) if a == b => {}
+        (a, b) => out.push(Change {
+            path,
+            before: a.cloned(),
+            after: b.cloned(),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value::{Array, Object};
+
+    fn object(entries: &[(&str, Value)]) -> Value {
+        let mut object = Object::new();
+
+        for (key, value) in entries {
+            object.insert(key.to_string(), value.clone());
+        }
+
+        Value::Object(object)
+    }
+
+    #[test]
+    fn equal_values_have_no_changes() {
+        let value = object(&[("a", 1.into()), ("b", "x".into())]);
+        assert!(value.diff(&value.clone()).is_empty());
+    }
+
+    #[test]
+    fn scalar_change_is_reported_at_root() {
+        let changes = Value::from(1).diff(&Value::from(2));
+
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].path, "");
+        assert_eq!(changes[0].kind(), ChangeKind::Modified);
+    }
+
+    #[test]
+    fn object_changes_are_reported_by_key() {
+        let before = object(&[("a", 1.into()), ("b", 2.into())]);
+        let after = object(&[("b", 3.into()), ("c", 4.into())]);
+        let changes = before.diff(&after);
+
+        let summary: Vec<_> = changes
+            .iter()
+            .map(|c| (c.path.as_str(), c.kind()))
+            .collect();
+
+        assert_eq!(
+            summary,
+            vec![
+                ("a", ChangeKind::Removed),
+                ("b", ChangeKind::Modified),
+                ("c", ChangeKind::Added),
+            ]
+        );
+    }
+
+    #[test]
+    fn nested_paths_use_ident_path_syntax() {
+        let before = object(&[("tags", Value::Array(Array::from(["a"])))]);
+        let after = object(&[("tags", Value::Array(Array::from(["a", "b"])))]);
+        let changes = before.diff(&after);
+
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].path, "tags[1]");
+        assert_eq!(changes[0].after, Some("b".into()));
+    }
+}
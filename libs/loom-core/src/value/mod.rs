@@ -1,8 +1,10 @@
 mod array;
+mod diff;
 mod number;
 mod object;
 
 pub use array::*;
+pub use diff::*;
 pub use number::*;
 pub use object::*;
 
@@ -557,3 +559,197 @@ impl From<Value> for toml::Value {
         }
     }
 }
+
+/// A MessagePack value with no lossless [`Value`] equivalent
+#[cfg(feature = "msgpack")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MsgPackValueError {
+    /// An unsigned integer above `i64::MAX`
+    IntegerOverflow(u64),
+    /// A string that isn't valid UTF-8
+    InvalidUtf8,
+    /// Raw bytes, which would otherwise read back as an array of numbers
+    Binary,
+    /// An extension type, with its type tag
+    Ext(i8),
+    /// A map key that isn't a string, integer or boolean
+    NonScalarKey,
+}
+
+#[cfg(feature = "msgpack")]
+impl std::fmt::Display for MsgPackValueError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::IntegerOverflow(i) => write!(f, "integer {} does not fit in an i64", i),
+            Self::InvalidUtf8 => write!(f, "string is not valid UTF-8"),
+            Self::Binary => write!(f, "binary values are not supported"),
+            Self::Ext(tag) => write!(f, "extension type {} is not supported", tag),
+            Self::NonScalarKey => write!(f, "map keys must be strings, integers or booleans"),
+        }
+    }
+}
+
+#[cfg(feature = "msgpack")]
+impl std::error::Error for MsgPackValueError {}
+
+#[cfg(feature = "msgpack")]
+impl TryFrom<rmpv::Value> for Value {
+    type Error = MsgPackValueError;
+
+    fn try_from(msgpack: rmpv::Value) -> Result<Self, Self::Error> {
+        Ok(match msgpack {
+            rmpv::Value::Nil => Self::Null,
+            rmpv::Value::Boolean(b) => Self::Bool(b),
+            rmpv::Value::Integer(i) => match i.as_i64() {
+                Some(i) => Self::Number(Number::Int(i)),
+                None => {
+                    return Err(MsgPackValueError::IntegerOverflow(
+                        i.as_u64().unwrap_or_default(),
+                    ));
+                }
+            },
+            rmpv::Value::F32(f) => Self::Number(Number::Float(f as f64)),
+            rmpv::Value::F64(f) => Self::Number(Number::Float(f)),
+            rmpv::Value::String(s) => {
+                Self::String(s.into_str().ok_or(MsgPackValueError::InvalidUtf8)?)
+            }
+            rmpv::Value::Binary(_) => return Err(MsgPackValueError::Binary),
+            rmpv::Value::Array(arr) => Self::Array(Array::from(
+                arr.into_iter()
+                    .map(Self::try_from)
+                    .collect::<Result<Vec<_>, _>>()?,
+            )),
+            rmpv::Value::Map(entries) => {
+                let mut map = Object::new();
+                for (k, v) in entries {
+                    let key = match k {
+                        rmpv::Value::String(s) => {
+                            s.into_str().ok_or(MsgPackValueError::InvalidUtf8)?
+                        }
+                        rmpv::Value::Integer(i) => i.to_string(),
+                        rmpv::Value::Boolean(b) => b.to_string(),
+                        _ => return Err(MsgPackValueError::NonScalarKey),
+                    };
+                    map.insert(key, Self::try_from(v)?);
+                }
+                Self::Object(map)
+            }
+            rmpv::Value::Ext(tag, _) => return Err(MsgPackValueError::Ext(tag)),
+        })
+    }
+}
+
+#[cfg(feature = "msgpack")]
+impl From<&Value> for rmpv::Value {
+    fn from(value: &Value) -> Self {
+        match value {
+            Value::Null => Self::Nil,
+            Value::Bool(b) => Self::Boolean(*b),
+            Value::Number(Number::Int(i)) => Self::Integer((*i).into()),
+            Value::Number(Number::Float(f)) => Self::F64(*f),
+            Value::String(s) => Self::String(s.clone().into()),
+            Value::Array(arr) => Self::Array(arr.iter().map(Self::from).collect()),
+            Value::Object(obj) => Self::Map(
+                obj.iter()
+                    .map(|(k, v)| (Self::String(k.clone().into()), Self::from(v)))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "msgpack")]
+impl From<Value> for rmpv::Value {
+    fn from(value: Value) -> Self {
+        match value {
+            Value::Null => Self::Nil,
+            Value::Bool(b) => Self::Boolean(b),
+            Value::Number(Number::Int(i)) => Self::Integer(i.into()),
+            Value::Number(Number::Float(f)) => Self::F64(f),
+            Value::String(s) => Self::String(s.into()),
+            Value::Array(arr) => Self::Array(arr.into_iter().map(Self::from).collect()),
+            Value::Object(obj) => Self::Map(
+                obj.iter()
+                    .map(|(k, v)| (Self::String(k.clone().into()), Self::from(v)))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "bson")]
+impl From<bson::Bson> for Value {
+    fn from(bson: bson::Bson) -> Self {
+        match bson {
+            bson::Bson::Null | bson::Bson::Undefined => Self::Null,
+            bson::Bson::MaxKey | bson::Bson::MinKey | bson::Bson::DbPointer(_) => Self::Null,
+            bson::Bson::Boolean(b) => Self::Bool(b),
+            bson::Bson::Int32(i) => Self::Number(Number::Int(i as i64)),
+            bson::Bson::Int64(i) => Self::Number(Number::Int(i)),
+            bson::Bson::Double(f) => Self::Number(Number::Float(f)),
+            bson::Bson::Decimal128(d) => Self::String(d.to_string()),
+            bson::Bson::String(s) | bson::Bson::Symbol(s) | bson::Bson::JavaScriptCode(s) => {
+                Self::String(s)
+            }
+            bson::Bson::JavaScriptCodeWithScope(code) => Self::String(code.code),
+            bson::Bson::RegularExpression(regex) => {
+                Self::String(format!("/{}/{}", regex.pattern, regex.options))
+            }
+            bson::Bson::ObjectId(id) => Self::String(id.to_hex()),
+            bson::Bson::DateTime(dt) => match dt.try_to_rfc3339_string() {
+                Ok(s) => Self::String(s),
+                Err(_) => Self::Number(Number::Int(dt.timestamp_millis())),
+            },
+            bson::Bson::Timestamp(ts) => {
+                let mut map = Object::new();
+                map.insert("t".to_string(), Self::Number(Number::Int(ts.time as i64)));
+                map.insert(
+                    "i".to_string(),
+                    Self::Number(Number::Int(ts.increment as i64)),
+                );
+                Self::Object(map)
+            }
+            bson::Bson::Binary(binary) => Self::Array(Array::from(
+                binary
+                    .bytes
+                    .into_iter()
+                    .map(|b| b as i64)
+                    .collect::<Vec<_>>(),
+            )),
+            bson::Bson::Array(arr) => Self::Array(Array::from(
+                arr.into_iter().map(Self::from).collect::<Vec<_>>(),
+            )),
+            bson::Bson::Document(doc) => doc.into(),
+        }
+    }
+}
+
+#[cfg(feature = "bson")]
+impl From<bson::Document> for Value {
+    fn from(doc: bson::Document) -> Self {
+        let mut map = Object::new();
+        for (k, v) in doc {
+            map.insert(k, Self::from(v));
+        }
+        Self::Object(map)
+    }
+}
+
+#[cfg(feature = "bson")]
+impl From<&Value> for bson::Bson {
+    fn from(value: &Value) -> Self {
+        match value {
+            Value::Null => Self::Null,
+            Value::Bool(b) => Self::Boolean(*b),
+            Value::Number(Number::Int(i)) => Self::Int64(*i),
+            Value::Number(Number::Float(f)) => Self::Double(*f),
+            Value::String(s) => Self::String(s.clone()),
+            Value::Array(arr) => Self::Array(arr.iter().map(Self::from).collect()),
+            Value::Object(obj) => Self::Document(
+                obj.iter()
+                    .map(|(k, v)| (k.clone(), Self::from(v)))
+                    .collect(),
+            ),
+        }
+    }
+}
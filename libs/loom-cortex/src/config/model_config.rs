@@ -14,6 +14,7 @@ use crate::{CortexDevice, CortexModelSource, CortexModelType};
 
 /// Serializable configuration for all pipeline types
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum CortexModelConfig {
     Conversation(CortexConversationConfig),
@@ -128,6 +129,34 @@ impl CortexModelConfig {
         })
     }
 
+    /// Returns the pipeline category name (matches the serialized `type` tag).
+    pub fn category(&self) -> &'static str {
+        match self {
+            Self::Conversation(_) => "conversation",
+            Self::MaskedLanguage(_) => "masked_language",
+            Self::Ner(_) => "ner",
+            Self::PosTagging(_) => "pos_tagging",
+            Self::QuestionAnswering(_) => "question_answering",
+            Self::SentenceEmbeddings(_) => "sentence_embeddings",
+            Self::Sentiment(_) => "sentiment",
+            Self::SequenceClassification(_) => "sequence_classification",
+            Self::Summarization(_) => "summarization",
+            Self::TextGeneration(_) => "text_generation",
+            Self::TokenClassification(_) => "token_classification",
+            Self::Translation(_) => "translation",
+            Self::ZeroShotClassification(_) => "zero_shot_classification",
+        }
+    }
+
+    /// Returns a model identifier combining category and model type
+    /// (e.g. `zero_shot_classification/bart`).
+    pub fn id(&self) -> String {
+        match self.model() {
+            Some(model) => format!("{}/{}", self.category(), model.as_str()),
+            None => self.category().to_string(),
+        }
+    }
+
     /// Returns a reference to the device configuration.
     /// All config variants have this field.
     pub fn device(&self) -> &CortexDevice {
@@ -148,6 +177,27 @@ impl CortexModelConfig {
         }
     }
 
+    /// Returns the same configuration on a different device.
+    pub fn with_device(mut self, device: CortexDevice) -> Self {
+        match &mut self {
+            Self::Conversation(c) => c.device = device,
+            Self::MaskedLanguage(c) => c.device = device,
+            Self::Ner(c) => c.device = device,
+            Self::PosTagging(c) => c.device = device,
+            Self::QuestionAnswering(c) => c.device = device,
+            Self::SentenceEmbeddings(c) => c.device = device,
+            Self::Sentiment(c) => c.device = device,
+            Self::SequenceClassification(c) => c.device = device,
+            Self::Summarization(c) => c.device = device,
+            Self::TextGeneration(c) => c.device = device,
+            Self::TokenClassification(c) => c.device = device,
+            Self::Translation(c) => c.device = device,
+            Self::ZeroShotClassification(c) => c.device = device,
+        }
+
+        self
+    }
+
     /// Returns a reference to the model type.
     /// Returns `None` for SentenceEmbeddings which uses a different model type.
     pub fn model(&self) -> Option<&CortexModelType> {
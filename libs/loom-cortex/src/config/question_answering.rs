@@ -4,6 +4,7 @@ use serde::{Deserialize, Serialize};
 use crate::{CortexDevice, CortexModelSource, CortexModelType};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct CortexQuestionAnsweringConfig {
     pub model: CortexModelType,
 
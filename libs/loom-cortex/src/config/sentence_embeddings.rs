@@ -7,6 +7,7 @@ use crate::CortexDevice;
 
 /// Pre-defined sentence embeddings model types
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum CortexSentenceEmbeddingsModelType {
     #[default]
@@ -84,6 +85,7 @@ impl From<SentenceEmbeddingsModelType> for CortexSentenceEmbeddingsModelType {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct CortexSentenceEmbeddingsConfig {
     pub model: CortexSentenceEmbeddingsModelType,
 
@@ -3,6 +3,7 @@ use tch::Device;
 
 /// Serializable device specification
 #[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum CortexDevice {
     #[default]
@@ -1,12 +1,16 @@
 pub mod bench;
 pub mod config;
 mod device;
+mod memory;
 mod model;
 mod model_type;
 mod resource;
+mod timeout;
 
 pub use bench::*;
 pub use device::*;
+pub use memory::*;
 pub use model::*;
 pub use model_type::*;
 pub use resource::*;
+pub use timeout::*;
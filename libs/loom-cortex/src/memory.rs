@@ -0,0 +1,172 @@
+use std::process::Command;
+
+use serde::{Deserialize, Serialize};
+
+use crate::CortexDevice;
+
+/// Memory usage of a device, in bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeviceMemory {
+    pub used: u64,
+    pub total: u64,
+}
+
+impl DeviceMemory {
+    pub fn free(&self) -> u64 {
+        self.total.saturating_sub(self.used)
+    }
+
+    /// Fraction of device memory in use.
+    pub fn usage(&self) -> f32 {
+        if self.total > 0 {
+            self.used as f32 / self.total as f32
+        } else {
+            0.0
+        }
+    }
+}
+
+impl CortexDevice {
+    /// Query the device's current memory usage.
+    ///
+    /// Only CUDA devices are supported, through `nvidia-smi`; returns `None` for
+    /// other devices or when the query fails.
+    pub fn memory(&self) -> Option<DeviceMemory> {
+        let index = match self {
+            Self::Cuda(n) => *n,
+            Self::CudaIfAvailable if tch::Cuda::is_available() => 0,
+            _ => return None,
+        };
+
+        let output = Command::new("nvidia-smi")
+            .args([
+                "--query-gpu=memory.used,memory.total",
+                "--format=csv,noheader,nounits",
+                &format!("--id={}", index),
+            ])
+            .output()
+            .ok()?;
+
+        if !output.status.success() {
+            return None;
+        }
+
+        parse_nvidia_smi(&String::from_utf8_lossy(&output.stdout))
+    }
+}
+
+/// Parse a `used, total` line (in MiB) from `nvidia-smi`.
+fn parse_nvidia_smi(output: &str) -> Option<DeviceMemory> {
+    let mut values = output.lines().next()?.split(',').map(|v| v.trim());
+    let used: u64 = values.next()?.parse().ok()?;
+    let total: u64 = values.next()?.parse().ok()?;
+
+    Some(DeviceMemory {
+        used: used * 1024 * 1024,
+        total: total * 1024 * 1024,
+    })
+}
+
+/// Whether an error message reports the device running out of memory.
+pub fn is_out_of_memory(message: &str) -> bool {
+    let message = message.to_lowercase();
+    message.contains("out of memory") || message.contains("outofmemory")
+}
+
+/// Adaptive batch size policy applied when a batch runs out of device memory.
+///
+/// On OOM the batch size is multiplied by `factor` (never below
+/// `min_batch_size`) and the batch is retried, up to `max_retries` times.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct BatchBackoff {
+    /// Multiplier applied to the batch size on each retry
+    #[serde(default = "BatchBackoff::default_factor")]
+    pub factor: f32,
+
+    /// Smallest batch size to retry with
+    #[serde(default = "BatchBackoff::default_min_batch_size")]
+    pub min_batch_size: usize,
+
+    /// Retries per batch before giving up
+    #[serde(default = "BatchBackoff::default_max_retries")]
+    pub max_retries: usize,
+}
+
+impl BatchBackoff {
+    fn default_factor() -> f32 {
+        0.5
+    }
+
+    fn default_min_batch_size() -> usize {
+        1
+    }
+
+    fn default_max_retries() -> usize {
+        4
+    }
+
+    /// Batch size to retry with after an OOM at `batch_size`, or `None` if it
+    /// cannot shrink any further.
+    pub fn shrink(&self, batch_size: usize) -> Option<usize> {
+        let min = self.min_batch_size.max(1);
+
+        if batch_size <= min {
+            return None;
+        }
+
+        let shrunk = (batch_size as f32 * self.factor) as usize;
+        Some(shrunk.clamp(min, batch_size - 1))
+    }
+}
+
+impl Default for BatchBackoff {
+    fn default() -> Self {
+        Self {
+            factor: Self::default_factor(),
+            min_batch_size: Self::default_min_batch_size(),
+            max_retries: Self::default_max_retries(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shrink_halves_down_to_min() {
+        let backoff = BatchBackoff::default();
+
+        assert_eq!(backoff.shrink(16), Some(8));
+        assert_eq!(backoff.shrink(3), Some(1));
+        assert_eq!(backoff.shrink(1), None);
+    }
+
+    #[test]
+    fn shrink_always_makes_progress() {
+        let backoff = BatchBackoff {
+            factor: 0.99,
+            ..Default::default()
+        };
+
+        assert_eq!(backoff.shrink(10), Some(9));
+    }
+
+    #[test]
+    fn detects_out_of_memory_errors() {
+        assert!(is_out_of_memory(
+            "Torch(\"CUDA out of memory. Tried to allocate 20.00 MiB\")"
+        ));
+        assert!(!is_out_of_memory("invalid label"));
+    }
+
+    #[test]
+    fn parses_nvidia_smi_output() {
+        let memory = parse_nvidia_smi("1024, 4096\n").unwrap();
+
+        assert_eq!(memory.used, 1024 * 1024 * 1024);
+        assert!((memory.usage() - 0.25).abs() < 0.001);
+        assert!(parse_nvidia_smi("").is_none());
+    }
+}
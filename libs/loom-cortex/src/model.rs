@@ -107,6 +107,23 @@ impl CortexModel {
         }
     }
 
+    /// Number of tokens the model's tokenizer splits `text` into, excluding
+    /// special tokens. Returns `None` for pipelines that don't expose their tokenizer.
+    pub fn tokenize_len(&self, text: &str) -> Option<usize> {
+        let tokenizer = match self {
+            Self::MaskedLanguage { model, .. } => model.get_tokenizer(),
+            Self::QuestionAnswering { model, .. } => model.get_tokenizer(),
+            Self::SentenceEmbeddings { model, .. } => model.get_tokenizer(),
+            Self::SequenceClassification { model, .. } => model.get_tokenizer(),
+            Self::TextGeneration { model, .. } => model.get_tokenizer(),
+            Self::TokenClassification { model, .. } => model.get_tokenizer(),
+            Self::ZeroShotClassification { model, .. } => model.get_tokenizer(),
+            _ => return None,
+        };
+
+        Some(tokenizer.tokenize(text).len())
+    }
+
     pub fn is_conversation(&self) -> bool {
         matches!(self, Self::Conversation { .. })
     }
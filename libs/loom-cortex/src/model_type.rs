@@ -5,6 +5,7 @@ use serde::{Deserialize, Serialize};
 
 /// Model architecture type for transformer models
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum CortexModelType {
     #[default]
     Bart,
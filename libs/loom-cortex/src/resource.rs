@@ -6,6 +6,7 @@ use serde::{Deserialize, Serialize};
 
 /// Serializable resource specification for model files
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum CortexResource {
     /// Load from a local file path
@@ -51,6 +52,7 @@ impl CortexResource {
 
 /// Simplified model source - either use defaults or specify custom resources
 #[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum CortexModelSource {
     /// Use HuggingFace defaults for the model type
@@ -0,0 +1,42 @@
+use std::time::Duration;
+
+/// A forward pass that did not finish within the configured inference timeout.
+///
+/// The pass itself cannot be interrupted, so it is abandoned and keeps running
+/// in the background; the model stays busy until it returns.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InferenceTimeout {
+    pub timeout: Duration,
+    pub batch_size: usize,
+}
+
+impl std::fmt::Display for InferenceTimeout {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "inference timed out after {}ms (batch of {} texts)",
+            self.timeout.as_millis(),
+            self.batch_size
+        )
+    }
+}
+
+impl std::error::Error for InferenceTimeout {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn displays_timeout_and_batch_size() {
+        let timeout = InferenceTimeout {
+            timeout: Duration::from_millis(1500),
+            batch_size: 8,
+        };
+
+        assert_eq!(
+            timeout.to_string(),
+            "inference timed out after 1500ms (batch of 8 texts)"
+        );
+    }
+}
@@ -6,6 +6,7 @@ pub enum ErrorCode {
     Cancel,
     NotFound,
     BadArguments,
+    Timeout,
 }
 
 impl ErrorCode {
@@ -36,6 +37,13 @@ impl ErrorCode {
             _ => false,
         }
     }
+
+    pub fn is_timeout(&self) -> bool {
+        match self {
+            Self::Timeout => true,
+            _ => false,
+        }
+    }
 }
 
 impl Default for ErrorCode {
@@ -51,6 +59,7 @@ impl std::fmt::Display for ErrorCode {
             Self::Unknown => write!(f, "unknown"),
             Self::NotFound => write!(f, "not-found"),
             Self::BadArguments => write!(f, "bad-arguments"),
+            Self::Timeout => write!(f, "timeout"),
         }
     }
 }
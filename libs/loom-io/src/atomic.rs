@@ -0,0 +1,438 @@
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant, SystemTime};
+
+static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// A file written to a temporary sibling and moved into place on
+/// [`commit`](AtomicFile::commit).
+///
+/// Readers see either the old file or the complete new one, and two
+/// processes writing the same path can't interleave their output: the last
+/// commit wins whole. Dropping without committing removes the temp file.
+///
+/// The rename alone survives a process crash but not a power loss; with
+/// [`with_sync`](AtomicFile::with_sync) the content and the directory entry
+/// are also flushed to disk before `commit` returns.
+///
+/// ```ignore
+/// let mut file = AtomicFile::create("output/scores.json")?.with_sync(true);
+/// file.write_all(json.as_bytes())?;
+/// file.commit()?;
+/// ```
+#[derive(Debug)]
+pub struct AtomicFile {
+    path: PathBuf,
+    temp: PathBuf,
+    file: Option<File>,
+    sync: bool,
+}
+
+impl AtomicFile {
+    /// Start writing `path`, creating its parent directories
+    pub fn create(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let dir = match path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+            _ => PathBuf::from("."),
+        };
+
+        fs::create_dir_all(&dir)?;
+
+        // Same directory as the target, so the final rename never crosses
+        // file systems
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let temp = dir.join(format!(".{}.{}.tmp", name, unique()));
+        let file = File::options().write(true).create_new(true).open(&temp)?;
+
+        Ok(Self {
+            path,
+            temp,
+            file: Some(file),
+            sync: false,
+        })
+    }
+
+    /// Fsync the file and its directory on commit
+    pub fn with_sync(mut self, sync: bool) -> Self {
+        self.sync = sync;
+        self
+    }
+
+    /// Write all of `content` to `path` at once
+    pub fn write(path: impl AsRef<Path>, content: &[u8]) -> io::Result<()> {
+        let mut file = Self::create(path)?;
+        file.write_all(content)?;
+        file.commit()
+    }
+
+    /// Like [`AtomicFile::write`], but fails with `AlreadyExists` instead of
+    /// replacing an existing file, even one created concurrently
+    pub fn write_new(path: impl AsRef<Path>, content: &[u8]) -> io::Result<()> {
+        let mut file = Self::create(path)?;
+        file.write_all(content)?;
+        file.commit_new()
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Replace `path` with the written content
+    pub fn commit(mut self) -> io::Result<()> {
+        self.close()?;
+        fs::rename(&self.temp, &self.path)?;
+        self.sync_dir()
+    }
+
+    /// Move the written content to `path` only if nothing is there
+    pub fn commit_new(mut self) -> io::Result<()> {
+        self.close()?;
+
+        // Linking fails if the target exists, unlike rename
+        fs::hard_link(&self.temp, &self.path)?;
+        fs::remove_file(&self.temp)?;
+        self.sync_dir()
+    }
+
+    fn close(&mut self) -> io::Result<()> {
+        match self.file.take() {
+            Some(mut file) => {
+                file.flush()?;
+
+                if self.sync {
+                    file.sync_all()?;
+                }
+
+                Ok(())
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Make the rename itself durable
+    fn sync_dir(&self) -> io::Result<()> {
+        if self.sync {
+            sync_parent(&self.path)?;
+        }
+
+        Ok(())
+    }
+
+    fn file(&mut self) -> io::Result<&mut File> {
+        self.file
+            .as_mut()
+            .ok_or_else(|| io::Error::other("atomic file already committed"))
+    }
+}
+
+impl Write for AtomicFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.file()?.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file()?.flush()
+    }
+}
+
+impl Drop for AtomicFile {
+    fn drop(&mut self) {
+        // Gone already once committed; otherwise discard the partial write
+        let _ = fs::remove_file(&self.temp);
+    }
+}
+
+/// Exclusive hold on a path across processes, for a check-then-write that
+/// must not interleave with another (e.g. a conditional update).
+///
+/// Held as a `.{name}.lock` sibling created with `create_new`, holding a
+/// token unique to the holder, and removed on drop if it still holds that
+/// token. A lock left behind by a crashed process is taken over once it is
+/// older than [`LockFile::STALE`].
+#[derive(Debug)]
+pub(crate) struct LockFile {
+    path: PathBuf,
+    token: String,
+}
+
+impl LockFile {
+    /// How long to wait for another holder before giving up
+    const TIMEOUT: Duration = Duration::from_secs(5);
+
+    /// Age after which a lock is assumed abandoned
+    const STALE: Duration = Duration::from_secs(30);
+
+    /// Lock `path`, waiting for the current holder if there is one
+    pub(crate) async fn acquire(path: &Path) -> io::Result<Self> {
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let lock = path.with_file_name(format!(".{}.lock", name));
+        let deadline = Instant::now() + Self::TIMEOUT;
+
+        loop {
+            match File::options().write(true).create_new(true).open(&lock) {
+                Ok(mut file) => {
+                    let held = Self {
+                        path: lock,
+                        token: unique(),
+                    };
+
+                    file.write_all(held.token.as_bytes())?;
+                    return Ok(held);
+                }
+                Err(err) if err.kind() == io::ErrorKind::AlreadyExists => {
+                    if Self::take_over(&lock) {
+                        continue;
+                    }
+
+                    if Instant::now() >= deadline {
+                        return Err(io::Error::new(
+                            io::ErrorKind::TimedOut,
+                            format!("timed out waiting for lock {}", lock.display()),
+                        ));
+                    }
+
+                    tokio::time::sleep(Duration::from_millis(10)).await;
+                }
+                Err(err) => return Err(err),
+            }
+        }
+    }
+
+    /// Remove `lock` if it is stale, returning whether it was. The lock is
+    /// renamed aside before it is removed, and only removed if it still
+    /// holds the token read when it was judged stale; otherwise another
+    /// waiter took it over first and the fresh lock is put back.
+    fn take_over(lock: &Path) -> bool {
+        let stale = fs::metadata(lock)
+            .and_then(|meta| meta.modified())
+            .ok()
+            .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+            .is_some_and(|age| age > Self::STALE);
+
+        let Some(token) = stale.then(|| fs::read(lock).ok()).flatten() else {
+            return false;
+        };
+
+        let aside = lock.with_extension(format!("{}.stale", unique()));
+
+        // Only one waiter can move a given lock file
+        if fs::rename(lock, &aside).is_err() {
+            return false;
+        }
+
+        let taken = fs::read(&aside).is_ok_and(|moved| moved == token);
+
+        if !taken {
+            // Fails if yet another lock was created meanwhile
+            let _ = fs::hard_link(&aside, lock);
+        }
+
+        let _ = fs::remove_file(&aside);
+        taken
+    }
+}
+
+impl Drop for LockFile {
+    fn drop(&mut self) {
+        // Leave the lock alone if it was taken over as stale
+        if fs::read(&self.path).is_ok_and(|token| token == self.token.as_bytes()) {
+            let _ = fs::remove_file(&self.path);
+        }
+    }
+}
+
+/// A name unique to this process and call, for temp and lock files
+fn unique() -> String {
+    format!(
+        "{}.{}",
+        std::process::id(),
+        COUNTER.fetch_add(1, Ordering::Relaxed)
+    )
+}
+
+/// Fsync the directory holding `path`, so a new or renamed entry survives a
+/// power loss. Directories can't be opened for syncing on Windows, where
+/// this is a no-op.
+pub(crate) fn sync_parent(path: &Path) -> io::Result<()> {
+    #[cfg(unix)]
+    {
+        let dir = match path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent,
+            _ => Path::new("."),
+        };
+
+        File::open(dir)?.sync_all()?;
+    }
+
+    #[cfg(not(unix))]
+    let _ = path;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env::temp_dir;
+
+    fn test_dir(name: &str) -> PathBuf {
+        let dir = temp_dir().join("loom_atomic_file_test").join(name);
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn entries(dir: &Path) -> Vec<String> {
+        fs::read_dir(dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .collect()
+    }
+
+    #[test]
+    fn test_commit_replaces_file() {
+        let dir = test_dir("commit");
+        let path = dir.join("out.json");
+        fs::write(&path, "old").unwrap();
+
+        let mut file = AtomicFile::create(&path).unwrap();
+        file.write_all(b"new").unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "old");
+
+        file.commit().unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "new");
+        assert_eq!(entries(&dir), vec!["out.json"]);
+    }
+
+    #[test]
+    fn test_synced_commit() {
+        let dir = test_dir("sync");
+        let path = dir.join("out.json");
+
+        let mut file = AtomicFile::create(&path).unwrap().with_sync(true);
+        file.write_all(b"durable").unwrap();
+        file.commit().unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "durable");
+        assert_eq!(entries(&dir), vec!["out.json"]);
+    }
+
+    #[test]
+    fn test_drop_discards_temp_file() {
+        let dir = test_dir("drop");
+        let path = dir.join("out.json");
+
+        {
+            let mut file = AtomicFile::create(&path).unwrap();
+            file.write_all(b"partial").unwrap();
+        }
+
+        assert!(!path.exists());
+        assert!(entries(&dir).is_empty());
+    }
+
+    #[test]
+    fn test_write_new_fails_if_exists() {
+        let dir = test_dir("write_new");
+        let path = dir.join("out.json");
+
+        AtomicFile::write_new(&path, b"first").unwrap();
+        let err = AtomicFile::write_new(&path, b"second").unwrap_err();
+
+        assert_eq!(err.kind(), io::ErrorKind::AlreadyExists);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "first");
+        assert_eq!(entries(&dir), vec!["out.json"]);
+    }
+
+    #[tokio::test]
+    async fn test_lock_file_is_exclusive() {
+        let dir = test_dir("lock");
+        let path = dir.join("out.json");
+
+        let lock = LockFile::acquire(&path).await.unwrap();
+        assert_eq!(entries(&dir), vec![".out.json.lock"]);
+
+        let waiter = tokio::spawn({
+            let path = path.clone();
+            async move { LockFile::acquire(&path).await.map(drop) }
+        });
+
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(!waiter.is_finished());
+
+        drop(lock);
+        waiter.await.unwrap().unwrap();
+        assert!(entries(&dir).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_stale_lock_is_taken_over() {
+        let dir = test_dir("stale_lock");
+        let path = dir.join("out.json");
+        let lock = dir.join(".out.json.lock");
+        fs::write(&lock, "crashed").unwrap();
+        File::options()
+            .write(true)
+            .open(&lock)
+            .unwrap()
+            .set_modified(SystemTime::now() - LockFile::STALE * 2)
+            .unwrap();
+
+        let held = LockFile::acquire(&path).await.unwrap();
+        assert_eq!(entries(&dir), vec![".out.json.lock"]);
+        assert_eq!(fs::read_to_string(&lock).unwrap(), held.token);
+
+        // A fresh lock is never taken over
+        assert!(!LockFile::take_over(&lock));
+        assert_eq!(fs::read_to_string(&lock).unwrap(), held.token);
+
+        drop(held);
+        assert!(entries(&dir).is_empty());
+    }
+
+    #[test]
+    fn test_drop_leaves_lock_taken_over() {
+        let dir = test_dir("lock_taken_over");
+        let lock = dir.join(".out.json.lock");
+        fs::write(&lock, "other").unwrap();
+
+        drop(LockFile {
+            path: lock.clone(),
+            token: unique(),
+        });
+
+        assert_eq!(fs::read_to_string(&lock).unwrap(), "other");
+    }
+
+    #[test]
+    fn test_concurrent_writes_do_not_interleave() {
+        let dir = test_dir("concurrent");
+        let path = dir.join("out.json");
+        let contents: Vec<String> = (0..8)
+            .map(|i| format!("[{}]", vec![i.to_string(); 20_000].join(",")))
+            .collect();
+
+        std::thread::scope(|scope| {
+            for content in &contents {
+                let path = &path;
+                scope.spawn(move || AtomicFile::write(path, content.as_bytes()).unwrap());
+            }
+        });
+
+        let written = fs::read_to_string(&path).unwrap();
+
+        assert!(contents.contains(&written));
+        assert_eq!(entries(&dir), vec!["out.json"]);
+    }
+}
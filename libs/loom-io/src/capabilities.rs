@@ -0,0 +1,80 @@
+/// Operations a [`DataSource`](crate::DataSource) supports, so callers can
+/// refuse e.g. a save to a read-only source before encoding anything.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Deserialize, serde::Serialize)]
+pub struct Capabilities {
+    /// Reads records with `find_one`
+    pub read: bool,
+
+    /// Writes records with `create`, `update`, `upsert` and `delete`
+    pub write: bool,
+
+    /// Reports changes through [`as_watch`](crate::DataSource::as_watch)
+    pub watch: bool,
+
+    /// Lists every record under a path with `find`, `count` and `find_page`,
+    /// rather than only the one at it
+    pub list: bool,
+}
+
+impl Capabilities {
+    /// Read, write and list, but not watch
+    pub fn new() -> Self {
+        Self {
+            read: true,
+            write: true,
+            watch: false,
+            list: true,
+        }
+    }
+
+    pub fn read_only(mut self) -> Self {
+        self.write = false;
+        self
+    }
+
+    pub fn watch(mut self, watch: bool) -> Self {
+        self.watch = watch;
+        self
+    }
+
+    pub fn list(mut self, list: bool) -> Self {
+        self.list = list;
+        self
+    }
+}
+
+impl Default for Capabilities {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Display for Capabilities {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let names = [
+            (self.read, "read"),
+            (self.write, "write"),
+            (self.watch, "watch"),
+            (self.list, "list"),
+        ]
+        .into_iter()
+        .filter_map(|(enabled, name)| enabled.then_some(name))
+        .collect::<Vec<_>>();
+
+        write!(f, "{}", names.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Capabilities::new().to_string(), "read, write, list");
+        assert_eq!(
+            Capabilities::new().read_only().watch(true).to_string(),
+            "read, watch, list"
+        );
+    }
+}
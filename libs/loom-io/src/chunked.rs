@@ -0,0 +1,193 @@
+use std::collections::VecDeque;
+
+use futures::stream::{BoxStream, StreamExt, TryStreamExt};
+
+use crate::{ReadError, Record};
+
+/// A record read a chunk at a time by
+/// [`DataSource::find_one_chunked`](crate::DataSource::find_one_chunked), so
+/// content too large to hold in memory can be piped through as it is read.
+pub struct ChunkedRecord<'a> {
+    /// The record without its content: path, media type and attributes, and
+    /// `size` set to the content length if the source knows it up front (`0`
+    /// otherwise). Its `content` is empty, so its ETag is not the content's.
+    pub header: Record,
+
+    /// The content, in order, in chunks of at most the requested size
+    pub chunks: BoxStream<'a, Result<Vec<u8>, ReadError>>,
+}
+
+impl<'a> ChunkedRecord<'a> {
+    pub fn new(header: Record, chunks: BoxStream<'a, Result<Vec<u8>, ReadError>>) -> Self {
+        Self { header, chunks }
+    }
+
+    /// A record whose content is already in memory, split into chunks of at
+    /// most `chunk_size` bytes
+    pub fn from_record(record: Record, chunk_size: usize) -> Self {
+        let chunks = split(record.content.clone(), chunk_size);
+        let header = Record::new(record.path, record.media_type, Vec::new())
+            .with_attributes(record.attributes);
+
+        Self {
+            header: Record {
+                size: record.size,
+                ..header
+            },
+            chunks: futures::stream::iter(chunks.into_iter().map(Ok)).boxed(),
+        }
+    }
+
+    /// Read every chunk back into a whole [`Record`], with its ETag computed
+    /// over the full content
+    pub async fn collect(self) -> Result<Record, ReadError> {
+        let content = self
+            .chunks
+            .try_fold(Vec::new(), |mut content, chunk| async move {
+                content.extend_from_slice(&chunk);
+                Ok(content)
+            })
+            .await?;
+
+        Ok(
+            Record::new(self.header.path, self.header.media_type, content)
+                .with_attributes(self.header.attributes),
+        )
+    }
+
+    /// The content split into lines (without their `\n` or `\r\n`), read
+    /// chunk by chunk so no more than the current line is held in memory
+    pub fn lines(self) -> BoxStream<'a, Result<Vec<u8>, ReadError>> {
+        let state = Lines {
+            chunks: self.chunks,
+            pending: Vec::new(),
+            ready: VecDeque::new(),
+            done: false,
+        };
+
+        futures::stream::unfold(state, |mut state| async move {
+            loop {
+                if let Some(line) = state.ready.pop_front() {
+                    return Some((Ok(line), state));
+                }
+
+                if state.done {
+                    return None;
+                }
+
+                match state.chunks.next().await {
+                    Some(Ok(chunk)) => state.push(&chunk),
+                    Some(Err(e)) => {
+                        state.done = true;
+                        return Some((Err(e), state));
+                    }
+                    None => {
+                        state.done = true;
+
+                        if !state.pending.is_empty() {
+                            let line = std::mem::take(&mut state.pending);
+                            state.ready.push_back(line);
+                        }
+                    }
+                }
+            }
+        })
+        .boxed()
+    }
+}
+
+/// State of [`ChunkedRecord::lines`]: the unread chunks, the start of a line
+/// whose end hasn't been read yet, and complete lines not yet yielded
+struct Lines<'a> {
+    chunks: BoxStream<'a, Result<Vec<u8>, ReadError>>,
+    pending: Vec<u8>,
+    ready: VecDeque<Vec<u8>>,
+    done: bool,
+}
+
+impl Lines<'_> {
+    fn push(&mut self, chunk: &[u8]) {
+        for part in chunk.split_inclusive(|b| *b == b'\n') {
+            self.pending.extend_from_slice(part);
+
+            if self.pending.last() == Some(&b'\n') {
+                let mut line = std::mem::take(&mut self.pending);
+                line.pop();
+
+                if line.last() == Some(&b'\r') {
+                    line.pop();
+                }
+
+                self.ready.push_back(line);
+            }
+        }
+    }
+}
+
+/// `content` in chunks of at most `chunk_size` bytes (at least one byte each)
+pub(crate) fn split(content: Vec<u8>, chunk_size: usize) -> Vec<Vec<u8>> {
+    content
+        .chunks(chunk_size.max(1))
+        .map(<[u8]>::to_vec)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MediaType;
+    use crate::path::{FilePath, Path};
+
+    #[tokio::test]
+    async fn test_from_record_round_trips() {
+        let record = Record::from_str(
+            Path::File(FilePath::parse("corpus.txt")),
+            MediaType::TextPlain,
+            "hello world",
+        )
+        .with_attribute("size", 11u64);
+
+        let chunked = ChunkedRecord::from_record(record.clone(), 4);
+        assert_eq!(chunked.header.size, 11);
+        assert!(chunked.header.content.is_empty());
+
+        let chunks = ChunkedRecord::from_record(record.clone(), 4)
+            .chunks
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+        assert_eq!(
+            chunks,
+            [b"hell".to_vec(), b"o wo".to_vec(), b"rld".to_vec()]
+        );
+
+        let collected = chunked.collect().await.unwrap();
+        assert_eq!(collected, record);
+        assert_eq!(collected.attribute("size"), record.attribute("size"));
+    }
+
+    #[tokio::test]
+    async fn test_lines_span_chunks() {
+        let record = Record::from_str(
+            Path::File(FilePath::parse("samples.jsonl")),
+            MediaType::TextNdjson,
+            "{\"a\":1}\r\n\n{\"b\":22}\n{\"c\":3}",
+        );
+
+        let lines = ChunkedRecord::from_record(record, 3)
+            .lines()
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+
+        assert_eq!(
+            lines,
+            [
+                b"{\"a\":1}".to_vec(),
+                Vec::new(),
+                b"{\"b\":22}".to_vec(),
+                b"{\"c\":3}".to_vec()
+            ]
+        );
+    }
+}
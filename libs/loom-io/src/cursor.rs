@@ -0,0 +1,30 @@
+use crate::path::Path;
+
+/// Position in a listing paged with [`DataSource::find_page`](crate::DataSource::find_page).
+///
+/// Opaque to callers: pass back the cursor a page returned to get the next
+/// one. Built-in sources page by path, so a cursor names the last record of
+/// its page and stays valid when records are added or removed meanwhile.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Deserialize, serde::Serialize)]
+pub struct Cursor(String);
+
+impl Cursor {
+    pub fn new(value: impl Into<String>) -> Self {
+        Self(value.into())
+    }
+
+    /// Cursor resuming after the record at `path`
+    pub fn after(path: &Path) -> Self {
+        Self(path.to_string())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::fmt::Display for Cursor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
@@ -1,3 +1,7 @@
+use std::collections::BTreeMap;
+
+use loom_core::value::Value;
+
 use crate::{ETag, Entity, Id, MediaType, path::Path};
 
 #[derive(Debug, Clone, Hash, serde::Deserialize, serde::Serialize)]
@@ -8,6 +12,11 @@ pub struct Document {
     pub size: usize,
     pub media_type: MediaType,
     pub content: Vec<Entity>,
+
+    /// Attributes of the [`Record`](crate::Record) this was decoded from, kept
+    /// so encoding the document back yields a record carrying them
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub attributes: BTreeMap<String, Value>,
 }
 
 impl Document {
@@ -25,8 +34,15 @@ impl Document {
             size: raw.len(),
             media_type,
             content,
+            attributes: BTreeMap::new(),
         }
     }
+
+    /// Add `attributes`, replacing any existing ones with the same keys
+    pub fn with_attributes(mut self, attributes: BTreeMap<String, Value>) -> Self {
+        self.attributes.extend(attributes);
+        self
+    }
 }
 
 impl Eq for Document {}
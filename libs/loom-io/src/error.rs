@@ -1,5 +1,20 @@
 use std::io;
 
+fn is_transient(err: &io::Error) -> bool {
+    matches!(
+        err.kind(),
+        io::ErrorKind::TimedOut
+            | io::ErrorKind::Interrupted
+            | io::ErrorKind::WouldBlock
+            | io::ErrorKind::ConnectionRefused
+            | io::ErrorKind::ConnectionReset
+            | io::ErrorKind::ConnectionAborted
+            | io::ErrorKind::NotConnected
+            | io::ErrorKind::BrokenPipe
+            | io::ErrorKind::UnexpectedEof
+    )
+}
+
 /// Errors that can occur during data source read operations
 #[derive(Debug)]
 pub enum ReadError {
@@ -18,6 +33,12 @@ impl ReadError {
         matches!(self, Self::IO(_))
     }
 
+    /// Whether the same operation may succeed if tried again, e.g. after a
+    /// timeout or dropped connection
+    pub fn is_transient(&self) -> bool {
+        matches!(self, Self::IO(e) if is_transient(e))
+    }
+
     pub fn is_panic(&self) -> bool {
         matches!(self, Self::Panic(_))
     }
@@ -35,6 +56,11 @@ impl ReadError {
     pub fn panic<S: Into<String>>(msg: S) -> Self {
         Self::Panic(msg.into())
     }
+
+    /// `find_page` asked for pages of 0 records
+    pub(crate) fn zero_limit() -> Self {
+        Self::Custom("find_page limit must be at least 1".to_string())
+    }
 }
 
 impl std::fmt::Display for ReadError {
@@ -71,6 +97,10 @@ pub enum WriteError {
     /// Write operation panicked during execution
     Panic(String),
 
+    /// The stored record no longer matches the expected ETag, i.e. someone
+    /// else changed it since it was read
+    PreconditionFailed(String),
+
     /// Custom error with a message
     Custom(String),
 }
@@ -80,6 +110,12 @@ impl WriteError {
         matches!(self, Self::IO(_))
     }
 
+    /// Whether the same operation may succeed if tried again, e.g. after a
+    /// timeout or dropped connection
+    pub fn is_transient(&self) -> bool {
+        matches!(self, Self::IO(e) if is_transient(e))
+    }
+
     pub fn is_panic(&self) -> bool {
         matches!(self, Self::Panic(_))
     }
@@ -88,6 +124,10 @@ impl WriteError {
         matches!(self, Self::Custom(_))
     }
 
+    pub fn is_precondition_failed(&self) -> bool {
+        matches!(self, Self::PreconditionFailed(_))
+    }
+
     /// Create a custom error from any error type
     pub fn custom<E: std::error::Error>(err: E) -> Self {
         Self::Custom(err.to_string())
@@ -104,6 +144,7 @@ impl std::fmt::Display for WriteError {
         match self {
             Self::IO(e) => write!(f, "io error: {}", e),
             Self::Panic(msg) => write!(f, "write panicked: {}", msg),
+            Self::PreconditionFailed(msg) => write!(f, "precondition failed: {}", msg),
             Self::Custom(msg) => write!(f, "{}", msg),
         }
     }
@@ -123,3 +164,30 @@ impl From<io::Error> for WriteError {
         Self::IO(err)
     }
 }
+
+impl From<ReadError> for WriteError {
+    fn from(err: ReadError) -> Self {
+        match err {
+            ReadError::IO(io) => Self::IO(io),
+            ReadError::Panic(msg) => Self::Panic(msg),
+            ReadError::Custom(msg) => Self::Custom(msg),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_transient() {
+        let timed_out = io::Error::new(io::ErrorKind::TimedOut, "timed out");
+        let not_found = io::Error::new(io::ErrorKind::NotFound, "missing");
+
+        assert!(ReadError::IO(timed_out).is_transient());
+        assert!(!ReadError::IO(not_found).is_transient());
+        assert!(!ReadError::Custom("bad path".to_string()).is_transient());
+        assert!(WriteError::IO(io::ErrorKind::ConnectionReset.into()).is_transient());
+        assert!(!WriteError::Panic("lock poisoned".to_string()).is_transient());
+    }
+}
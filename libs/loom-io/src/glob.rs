@@ -0,0 +1,221 @@
+use std::path::{Component, Path, PathBuf};
+
+/// A file path pattern, matched component by component.
+///
+/// - `*` matches any run of characters within a component
+/// - `?` matches one character
+/// - `[abc]`, `[a-z]` match one character from a set (`[!abc]` negates)
+/// - `**` as a whole component matches any number of components
+///
+/// Wildcards don't match a leading `.`, so hidden files are only matched by
+/// patterns that spell the dot out (`.cache/*`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Glob {
+    components: Vec<String>,
+}
+
+impl Glob {
+    pub fn new(pattern: impl AsRef<Path>) -> Self {
+        Self {
+            components: components(pattern.as_ref()),
+        }
+    }
+
+    /// Whether `input` contains glob syntax, as opposed to a plain path
+    pub fn is_pattern(input: &str) -> bool {
+        input.contains(['*', '?', '['])
+    }
+
+    /// The literal directory the pattern starts in, which holds every match
+    pub fn base(&self) -> PathBuf {
+        let literal = self
+            .components
+            .iter()
+            .take_while(|component| !Self::is_pattern(component))
+            .count();
+
+        // The last component names the matched entries themselves
+        let literal = literal.min(self.components.len().saturating_sub(1));
+        let base: PathBuf = self.components[..literal].iter().collect();
+
+        if base.as_os_str().is_empty() {
+            PathBuf::from(".")
+        } else {
+            base
+        }
+    }
+
+    /// How many components below [`Glob::base`] a match can be, or `None`
+    /// if `**` makes it unbounded
+    pub fn depth(&self) -> Option<usize> {
+        if self.components.iter().any(|component| component == "**") {
+            return None;
+        }
+
+        Some(self.components.len() - components(&self.base()).len())
+    }
+
+    pub fn matches(&self, path: impl AsRef<Path>) -> bool {
+        match_components(&self.components, &components(path.as_ref()))
+    }
+}
+
+impl std::fmt::Display for Glob {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let path: PathBuf = self.components.iter().collect();
+        write!(f, "{}", path.display())
+    }
+}
+
+fn components(path: &Path) -> Vec<String> {
+    path.components()
+        .filter(|component| !matches!(component, Component::CurDir))
+        .map(|component| component.as_os_str().to_string_lossy().into_owned())
+        .collect()
+}
+
+fn match_components(pattern: &[String], path: &[String]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((first, rest)) if first == "**" => {
+            (0..=path.len()).any(|skip| match_components(rest, &path[skip..]))
+        }
+        Some((first, rest)) => match path.split_first() {
+            Some((name, path)) => match_name(first, name) && match_components(rest, path),
+            None => false,
+        },
+    }
+}
+
+fn match_name(pattern: &str, name: &str) -> bool {
+    if name.starts_with('.') && !pattern.starts_with('.') {
+        return false;
+    }
+
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+
+    match_chars(&pattern, &name)
+}
+
+fn match_chars(pattern: &[char], name: &[char]) -> bool {
+    match pattern.first() {
+        None => name.is_empty(),
+        Some('*') => {
+            let rest = &pattern[1..];
+            (0..=name.len()).any(|skip| match_chars(rest, &name[skip..]))
+        }
+        Some('?') => !name.is_empty() && match_chars(&pattern[1..], &name[1..]),
+        Some('[') => match (class(&pattern[1..]), name.first()) {
+            (Some((len, accepts)), Some(c)) => {
+                accepts(*c) && match_chars(&pattern[len + 1..], &name[1..])
+            }
+            (Some(_), None) => false,
+            // Unclosed `[` is literal
+            (None, _) => name.first() == Some(&'[') && match_chars(&pattern[1..], &name[1..]),
+        },
+        Some(c) => name.first() == Some(c) && match_chars(&pattern[1..], &name[1..]),
+    }
+}
+
+/// Parse a `[...]` set body, returning its length (through the closing `]`)
+/// and a predicate for the characters it accepts
+fn class(body: &[char]) -> Option<(usize, impl Fn(char) -> bool)> {
+    let negated = matches!(body.first(), Some('!' | '^'));
+    let start = usize::from(negated);
+
+    // A `]` right after the opening is part of the set
+    let close = body
+        .iter()
+        .skip(start + 1)
+        .position(|&c| c == ']')
+        .map(|i| i + start + 1)?;
+
+    let set = &body[start..close];
+    let mut ranges = Vec::new();
+    let mut i = 0;
+
+    while i < set.len() {
+        if i + 2 < set.len() && set[i + 1] == '-' {
+            ranges.push((set[i], set[i + 2]));
+            i += 3;
+        } else {
+            ranges.push((set[i], set[i]));
+            i += 1;
+        }
+    }
+
+    Some((close + 1, move |c: char| {
+        ranges.iter().any(|&(lo, hi)| lo <= c && c <= hi) != negated
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_pattern() {
+        assert!(Glob::is_pattern("datasets/*.json"));
+        assert!(Glob::is_pattern("datasets/**/x.json"));
+        assert!(Glob::is_pattern("file?.txt"));
+        assert!(Glob::is_pattern("file[0-9].txt"));
+        assert!(!Glob::is_pattern("datasets/x.json"));
+    }
+
+    #[test]
+    fn test_star() {
+        let glob = Glob::new("datasets/*.json");
+
+        assert!(glob.matches("datasets/a.json"));
+        assert!(glob.matches("./datasets/b.json"));
+        assert!(!glob.matches("datasets/a.yaml"));
+        assert!(!glob.matches("datasets/nested/a.json"));
+    }
+
+    #[test]
+    fn test_double_star() {
+        let glob = Glob::new("datasets/**/*.json");
+
+        assert!(glob.matches("datasets/a.json"));
+        assert!(glob.matches("datasets/x/a.json"));
+        assert!(glob.matches("datasets/x/y/z/a.json"));
+        assert!(!glob.matches("other/a.json"));
+    }
+
+    #[test]
+    fn test_question_and_class() {
+        assert!(Glob::new("run?.json").matches("run1.json"));
+        assert!(!Glob::new("run?.json").matches("run10.json"));
+        assert!(Glob::new("run[0-9].json").matches("run7.json"));
+        assert!(!Glob::new("run[0-9].json").matches("runx.json"));
+        assert!(Glob::new("run[!0-9].json").matches("runx.json"));
+        assert!(Glob::new("run[ab].json").matches("runb.json"));
+        assert!(Glob::new("a[b.json").matches("a[b.json"));
+    }
+
+    #[test]
+    fn test_hidden_files() {
+        assert!(!Glob::new("out/*").matches("out/.scores.json.tmp"));
+        assert!(Glob::new("out/.*").matches("out/.scores.json.tmp"));
+    }
+
+    #[test]
+    fn test_base_and_depth() {
+        let glob = Glob::new("datasets/train/*.json");
+        assert_eq!(glob.base(), PathBuf::from("datasets/train"));
+        assert_eq!(glob.depth(), Some(1));
+
+        let glob = Glob::new("datasets/**/*.json");
+        assert_eq!(glob.base(), PathBuf::from("datasets"));
+        assert_eq!(glob.depth(), None);
+
+        let glob = Glob::new("*.json");
+        assert_eq!(glob.base(), PathBuf::from("."));
+        assert_eq!(glob.depth(), Some(1));
+
+        let glob = Glob::new("/data/*/scores.json");
+        assert_eq!(glob.base(), PathBuf::from("/data"));
+        assert_eq!(glob.depth(), Some(2));
+    }
+}
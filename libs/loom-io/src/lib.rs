@@ -1,36 +1,248 @@
+mod atomic;
+mod capabilities;
+mod chunked;
+mod cursor;
 mod document;
 mod entity;
 mod error;
 mod etag;
+mod glob;
 mod record;
 mod registry;
 pub mod sources;
+mod watch;
 
+pub use atomic::*;
+pub use capabilities::*;
+pub use chunked::*;
+pub use cursor::*;
 pub use document::*;
 pub use entity::*;
 pub use error::*;
 pub use etag::*;
+pub use glob::*;
 pub use record::*;
 pub use registry::*;
+pub use watch::*;
 
 // Re-export loom-core types for convenience
 pub use loom_core::{Format, Id, MediaType, path, value};
 
 use async_trait::async_trait;
+use futures::stream::{self, BoxStream, StreamExt, TryStreamExt};
 
-use crate::path::Path;
+use crate::path::{Path, Scheme};
 
 #[async_trait]
 pub trait DataSource: Send + Sync {
     fn name(&self) -> &str;
 
+    /// URI schemes this source serves; a [`DataSourceRegistry`] routes paths
+    /// with these schemes here unless told otherwise
+    fn schemes(&self) -> &[Scheme] {
+        &[]
+    }
+
+    /// This source as a [`WatchSource`], if it can report changes
+    fn as_watch(&self) -> Option<&dyn WatchSource> {
+        None
+    }
+
+    /// What this source supports. The default can read, write and list, and
+    /// watch if [`as_watch`](DataSource::as_watch) returns a watcher.
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::new().watch(self.as_watch().is_some())
+    }
+
+    /// Check that the source is reachable, e.g. that its directory exists or
+    /// its server answers, so a misconfigured source fails before the first
+    /// read. The default assumes it is.
+    async fn health(&self) -> Result<(), ReadError> {
+        Ok(())
+    }
+
     async fn exists(&self, path: &Path) -> Result<bool, ReadError>;
     async fn count(&self, path: &Path) -> Result<usize, ReadError>;
     async fn find_one(&self, path: &Path) -> Result<Record, ReadError>;
     async fn find(&self, path: &Path) -> Result<Vec<Record>, ReadError>;
 
+    /// Records under `path`, yielded as they are read. The default waits for
+    /// [`find`](DataSource::find); sources with large result sets override
+    /// it so callers can start on the first record before the last is read.
+    fn find_stream<'a>(&'a self, path: &'a Path) -> BoxStream<'a, Result<Record, ReadError>> {
+        stream::once(self.find(path))
+            .map_ok(|records| stream::iter(records.into_iter().map(Ok)))
+            .try_flatten()
+            .boxed()
+    }
+
+    /// The record at `path` as a header plus its content in chunks of at most
+    /// `chunk_size` bytes, so files too large to hold in memory can be piped
+    /// through as they are read. The default reads the whole record with
+    /// [`find_one`](DataSource::find_one) and splits it; sources that can
+    /// read incrementally override it.
+    async fn find_one_chunked<'a>(
+        &'a self,
+        path: &'a Path,
+        chunk_size: usize,
+    ) -> Result<ChunkedRecord<'a>, ReadError> {
+        Ok(ChunkedRecord::from_record(
+            self.find_one(path).await?,
+            chunk_size,
+        ))
+    }
+
+    /// Up to `limit` records under `path`, starting after `cursor` (from the
+    /// start if `None`), and the cursor of the next page if there is one, so
+    /// large listings can be consumed a page at a time. Records come sorted
+    /// by path. Fails if `limit` is 0, which could never make progress.
+    /// The default pages through [`find`](DataSource::find); sources that can
+    /// list without reading override it to read only the page.
+    async fn find_page(
+        &self,
+        path: &Path,
+        cursor: Option<Cursor>,
+        limit: usize,
+    ) -> Result<(Vec<Record>, Option<Cursor>), ReadError> {
+        if limit == 0 {
+            return Err(ReadError::zero_limit());
+        }
+
+        let mut records = self.find(path).await?;
+        records.sort_by_cached_key(|record| record.path.to_string());
+
+        let start = match &cursor {
+            None => 0,
+            Some(cursor) => records
+                .partition_point(|record| record.path.to_string().as_str() <= cursor.as_str()),
+        };
+
+        let remaining = records.len() - start;
+        let page: Vec<Record> = records.into_iter().skip(start).take(limit).collect();
+        let next = if page.len() == remaining {
+            None
+        } else {
+            page.last().map(|last| Cursor::after(&last.path)).or(cursor)
+        };
+
+        Ok((page, next))
+    }
+
+    /// ETag of the record stored at `path` now, or `None` if there is none.
+    /// The default reads the record with [`find_one`](DataSource::find_one);
+    /// sources that cache reads override it to check the stored record.
+    async fn etag(&self, path: &Path) -> Result<Option<ETag>, ReadError> {
+        if !self.exists(path).await? {
+            return Ok(None);
+        }
+
+        Ok(Some(self.find_one(path).await?.etag))
+    }
+
     async fn create(&self, record: Record) -> Result<(), WriteError>;
     async fn update(&self, record: Record) -> Result<(), WriteError>;
+
+    /// Like [`update`](DataSource::update), but only if the stored record's
+    /// ETag is still `expected`; fails with [`WriteError::PreconditionFailed`]
+    /// if someone else changed it since it was read. The default checks and
+    /// then writes, leaving a window for another writer; sources that can
+    /// compare and replace atomically override it.
+    async fn update_if(&self, record: Record, expected: &ETag) -> Result<(), WriteError> {
+        let current = self.find_one(&record.path).await?;
+
+        if current.etag != *expected {
+            return Err(WriteError::PreconditionFailed(format!(
+                "{} changed since it was read",
+                record.path
+            )));
+        }
+
+        self.update(record).await
+    }
     async fn upsert(&self, record: Record) -> Result<(), WriteError>;
     async fn delete(&self, path: &Path) -> Result<(), WriteError>;
 }
+
+#[async_trait]
+impl DataSource for Box<dyn DataSource> {
+    fn name(&self) -> &str {
+        self.as_ref().name()
+    }
+
+    fn schemes(&self) -> &[Scheme] {
+        self.as_ref().schemes()
+    }
+
+    fn as_watch(&self) -> Option<&dyn WatchSource> {
+        self.as_ref().as_watch()
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        self.as_ref().capabilities()
+    }
+
+    async fn health(&self) -> Result<(), ReadError> {
+        self.as_ref().health().await
+    }
+
+    async fn exists(&self, path: &Path) -> Result<bool, ReadError> {
+        self.as_ref().exists(path).await
+    }
+
+    async fn count(&self, path: &Path) -> Result<usize, ReadError> {
+        self.as_ref().count(path).await
+    }
+
+    async fn find_one(&self, path: &Path) -> Result<Record, ReadError> {
+        self.as_ref().find_one(path).await
+    }
+
+    async fn find(&self, path: &Path) -> Result<Vec<Record>, ReadError> {
+        self.as_ref().find(path).await
+    }
+
+    fn find_stream<'a>(&'a self, path: &'a Path) -> BoxStream<'a, Result<Record, ReadError>> {
+        self.as_ref().find_stream(path)
+    }
+
+    async fn find_page(
+        &self,
+        path: &Path,
+        cursor: Option<Cursor>,
+        limit: usize,
+    ) -> Result<(Vec<Record>, Option<Cursor>), ReadError> {
+        self.as_ref().find_page(path, cursor, limit).await
+    }
+
+    async fn find_one_chunked<'a>(
+        &'a self,
+        path: &'a Path,
+        chunk_size: usize,
+    ) -> Result<ChunkedRecord<'a>, ReadError> {
+        self.as_ref().find_one_chunked(path, chunk_size).await
+    }
+
+    async fn etag(&self, path: &Path) -> Result<Option<ETag>, ReadError> {
+        self.as_ref().etag(path).await
+    }
+
+    async fn create(&self, record: Record) -> Result<(), WriteError> {
+        self.as_ref().create(record).await
+    }
+
+    async fn update(&self, record: Record) -> Result<(), WriteError> {
+        self.as_ref().update(record).await
+    }
+
+    async fn update_if(&self, record: Record, expected: &ETag) -> Result<(), WriteError> {
+        self.as_ref().update_if(record, expected).await
+    }
+
+    async fn upsert(&self, record: Record) -> Result<(), WriteError> {
+        self.as_ref().upsert(record).await
+    }
+
+    async fn delete(&self, path: &Path) -> Result<(), WriteError> {
+        self.as_ref().delete(path).await
+    }
+}
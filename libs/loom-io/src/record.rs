@@ -1,4 +1,7 @@
+use std::collections::BTreeMap;
+
 use loom_core::Id;
+use loom_core::value::Value;
 
 use crate::{ETag, MediaType, path::Path};
 
@@ -10,6 +13,11 @@ pub struct Record {
     pub size: usize,
     pub media_type: MediaType,
     pub content: Vec<u8>,
+
+    /// Source-specific metadata, e.g. a file's `mtime` or an HTTP response's
+    /// `headers`; not part of the content, so it doesn't change the ETag
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub attributes: BTreeMap<String, Value>,
 }
 
 impl Record {
@@ -21,6 +29,7 @@ impl Record {
             path,
             media_type,
             content,
+            attributes: BTreeMap::new(),
         }
     }
 
@@ -28,6 +37,21 @@ impl Record {
         Self::new(path, media_type, content.as_bytes().to_vec())
     }
 
+    /// Add `attributes`, replacing any existing ones with the same keys
+    pub fn with_attributes(mut self, attributes: BTreeMap<String, Value>) -> Self {
+        self.attributes.extend(attributes);
+        self
+    }
+
+    pub fn with_attribute(mut self, key: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.attributes.insert(key.into(), value.into());
+        self
+    }
+
+    pub fn attribute(&self, key: &str) -> Option<&Value> {
+        self.attributes.get(key)
+    }
+
     pub fn content_str(&self) -> Result<&str, std::str::Utf8Error> {
         std::str::from_utf8(&self.content)
     }
@@ -86,4 +110,20 @@ mod tests {
         assert_eq!(record1, record2);
         assert_ne!(record1, record3);
     }
+
+    #[test]
+    fn test_record_attributes() {
+        let path = Path::File(FilePath::parse("/test/file.txt"));
+        let record = Record::from_str(path.clone(), MediaType::TextPlain, "hello")
+            .with_attribute("size", 5usize)
+            .with_attribute("mtime", 1_700_000_000_000i64);
+
+        assert_eq!(record.attribute("size"), Some(&Value::from(5usize)));
+        assert_eq!(record.attribute("etag"), None);
+        assert_eq!(
+            record,
+            Record::from_str(path, MediaType::TextPlain, "hello"),
+            "attributes don't affect equality"
+        );
+    }
 }
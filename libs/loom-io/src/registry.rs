@@ -1,9 +1,23 @@
 use std::collections::HashMap;
+use std::sync::Arc;
+
+use loom_signal::Emitter;
 
 use super::DataSource;
+use crate::path::{Path, Scheme};
+use crate::sources::InstrumentedSource;
+use crate::{Capabilities, ReadError};
 
+/// Named data sources, plus routes that pick one from a path's scheme.
+///
+/// Sources claim the schemes they report in [`DataSource::schemes`] (the
+/// first registered wins); explicit [`route`](DataSourceRegistryBuilder::route)
+/// calls override that. Paths without a routed scheme go to the default
+/// source, which is the first registered unless set.
 pub struct DataSourceRegistry {
     sources: HashMap<String, Box<dyn DataSource>>,
+    routes: HashMap<Scheme, String>,
+    default: Option<String>,
 }
 
 impl DataSourceRegistry {
@@ -26,11 +40,55 @@ impl DataSourceRegistry {
     pub fn get(&self, name: &str) -> Option<&dyn DataSource> {
         self.sources.get(name).map(|c| c.as_ref())
     }
+
+    /// Source used for paths no route claims
+    pub fn default_source(&self) -> Option<&dyn DataSource> {
+        self.default.as_deref().and_then(|name| self.get(name))
+    }
+
+    /// Source routed for `scheme`
+    pub fn route(&self, scheme: &Scheme) -> Option<&dyn DataSource> {
+        self.routes.get(scheme).and_then(|name| self.get(name))
+    }
+
+    /// Source for `path`: URIs by their scheme, file paths by the `file`
+    /// route, and anything else (or an unrouted file path) by the default.
+    /// URIs with an unrouted scheme resolve to nothing rather than to a
+    /// source that can't read them.
+    pub fn resolve(&self, path: &Path) -> Option<&dyn DataSource> {
+        match path {
+            Path::Uri(uri) => self.route(uri.scheme()),
+            Path::File(_) => self.route(&Scheme::File).or_else(|| self.default_source()),
+            _ => self.default_source(),
+        }
+    }
+
+    /// Capabilities of the source named `name`
+    pub fn capabilities(&self, name: &str) -> Option<Capabilities> {
+        self.get(name).map(|source| source.capabilities())
+    }
+
+    /// [`health`](DataSource::health) of every source, by name
+    pub async fn health(&self) -> Vec<(String, Result<(), ReadError>)> {
+        let mut names = self.sources.keys().collect::<Vec<_>>();
+        names.sort();
+
+        let mut health = Vec::with_capacity(names.len());
+        for name in names {
+            health.push((name.clone(), self.sources[name].health().await));
+        }
+
+        health
+    }
 }
 
 #[derive(Default)]
 pub struct DataSourceRegistryBuilder {
     sources: HashMap<String, Box<dyn DataSource>>,
+    routes: HashMap<Scheme, String>,
+    default: Option<String>,
+    first: Option<String>,
+    emitter: Option<Arc<dyn Emitter + Send + Sync>>,
 }
 
 impl DataSourceRegistryBuilder {
@@ -39,14 +97,183 @@ impl DataSourceRegistryBuilder {
     }
 
     pub fn source<T: DataSource + 'static>(mut self, source: T) -> Self {
-        self.sources
-            .insert(source.name().to_string(), Box::new(source));
+        let name = source.name().to_string();
+
+        for scheme in source.schemes() {
+            self.routes
+                .entry(scheme.clone())
+                .or_insert_with(|| name.clone());
+        }
+
+        self.first.get_or_insert_with(|| name.clone());
+        self.sources.insert(name, Box::new(source));
+        self
+    }
+
+    /// Send paths with `scheme` to the source named `name`
+    pub fn route(mut self, scheme: Scheme, name: impl Into<String>) -> Self {
+        self.routes.insert(scheme, name.into());
+        self
+    }
+
+    /// Send paths no route claims to the source named `name`
+    pub fn default_source(mut self, name: impl Into<String>) -> Self {
+        self.default = Some(name.into());
+        self
+    }
+
+    /// Wrap every source in an [`InstrumentedSource`] emitting to `emitter`
+    pub fn emitter(mut self, emitter: Arc<dyn Emitter + Send + Sync>) -> Self {
+        self.emitter = Some(emitter);
         self
     }
 
     pub fn build(self) -> DataSourceRegistry {
+        let sources = match self.emitter {
+            None => self.sources,
+            Some(emitter) => self
+                .sources
+                .into_iter()
+                .map(|(name, source)| {
+                    let source: Box<dyn DataSource> =
+                        Box::new(InstrumentedSource::new(source, emitter.clone()));
+                    (name, source)
+                })
+                .collect(),
+        };
+
         DataSourceRegistry {
-            sources: self.sources,
+            sources,
+            routes: self.routes,
+            default: self.default.or(self.first),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::path::{FilePath, IdentPath, UriPath};
+    use crate::sources::{FileSystemSource, MemorySource};
+
+    fn uri(input: &str) -> Path {
+        Path::Uri(UriPath::parse(input).unwrap())
+    }
+
+    fn file(input: &str) -> Path {
+        Path::File(FilePath::parse(input))
+    }
+
+    fn resolved(registry: &DataSourceRegistry, path: &Path) -> Option<String> {
+        registry
+            .resolve(path)
+            .map(|source| source.name().to_string())
+    }
+
+    #[tokio::test]
+    async fn test_health_and_capabilities() {
+        let registry = DataSourceRegistry::new()
+            .source(MemorySource::builder().build())
+            .source(
+                FileSystemSource::builder()
+                    .path("/nonexistent/loom")
+                    .build(),
+            )
+            .build();
+
+        let health = registry.health().await;
+        assert_eq!(health.len(), 2);
+        assert_eq!(health[0].0, "file_system");
+        assert!(health[0].1.is_err());
+        assert_eq!(health[1].0, "memory");
+        assert!(health[1].1.is_ok());
+
+        assert_eq!(registry.capabilities("memory"), Some(Capabilities::new()));
+        assert!(registry.capabilities("file_system").unwrap().watch);
+        assert_eq!(registry.capabilities("missing"), None);
+    }
+
+    #[test]
+    fn test_sources_claim_their_schemes() {
+        let registry = DataSourceRegistry::new()
+            .source(MemorySource::builder().build())
+            .source(FileSystemSource::builder().build())
+            .build();
+
+        assert_eq!(
+            resolved(&registry, &uri("file:///data/samples.json")),
+            Some("file_system".to_string())
+        );
+        assert_eq!(
+            resolved(&registry, &file("data/samples.json")),
+            Some("file_system".to_string())
+        );
+    }
+
+    #[test]
+    fn test_default_source() {
+        let registry = DataSourceRegistry::new()
+            .source(MemorySource::builder().build())
+            .source(MemorySource::builder().name("cache").build())
+            .build();
+
+        assert_eq!(registry.default_source().unwrap().name(), "memory");
+        assert_eq!(
+            resolved(&registry, &file("data/samples.json")),
+            Some("memory".to_string())
+        );
+
+        let registry = DataSourceRegistry::new()
+            .source(MemorySource::builder().build())
+            .source(MemorySource::builder().name("cache").build())
+            .default_source("cache")
+            .build();
+
+        assert_eq!(
+            resolved(&registry, &Path::Ident(IdentPath::parse("a.b").unwrap())),
+            Some("cache".to_string())
+        );
+    }
+
+    #[test]
+    fn test_explicit_route_overrides_claim() {
+        let registry = DataSourceRegistry::new()
+            .source(FileSystemSource::builder().build())
+            .source(MemorySource::builder().build())
+            .route(Scheme::File, "memory")
+            .route(Scheme::S3, "memory")
+            .build();
+
+        assert_eq!(
+            resolved(&registry, &file("data/samples.json")),
+            Some("memory".to_string())
+        );
+        assert_eq!(
+            resolved(&registry, &uri("s3://bucket/samples.json")),
+            Some("memory".to_string())
+        );
+    }
+
+    #[test]
+    fn test_unrouted_scheme_resolves_to_nothing() {
+        let registry = DataSourceRegistry::new()
+            .source(FileSystemSource::builder().build())
+            .build();
+
+        assert!(registry.resolve(&uri("s3://bucket/samples.json")).is_none());
+        assert!(
+            registry
+                .resolve(&uri("postgres://localhost/loom"))
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_empty_registry() {
+        let registry = DataSourceRegistry::new().build();
+
+        assert!(registry.is_empty());
+        assert!(registry.default_source().is_none());
+        assert!(registry.resolve(&file("samples.json")).is_none());
+    }
+}
@@ -1,18 +1,34 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::io::{Read, Write};
 use std::path::PathBuf;
 use std::sync::RwLock;
+use std::time::{Duration, SystemTime};
 
 use async_trait::async_trait;
+use futures::channel::mpsc;
+use futures::stream::{self, BoxStream, StreamExt};
 
 use crate::MediaType;
-use crate::path::Path;
+use crate::path::{Path, Scheme};
+use crate::value::Value;
 
-use crate::{DataSource, Id, ReadError, Record, WriteError};
+use crate::atomic::{LockFile, sync_parent};
+use crate::{
+    AtomicFile, Capabilities, ChangeEvent, ChangeKind, ChunkedRecord, Cursor, DataSource, ETag,
+    Glob, Id, ReadError, Record, WatchSource, WriteError,
+};
+
+/// Modification time and size of each watched file
+type Snapshot = BTreeMap<PathBuf, (Option<SystemTime>, u64)>;
 
 #[derive(Debug, Clone)]
 pub struct FileSystemSourceConfig {
     path: PathBuf,
     name: String,
+    write_tmp_then_rename: bool,
+    fsync: bool,
+    recursive: bool,
+    watch_interval: Duration,
 }
 
 impl FileSystemSourceConfig {
@@ -23,12 +39,36 @@ impl FileSystemSourceConfig {
     pub fn name(&self) -> &str {
         &self.name
     }
+
+    /// Whether writes go through a temp file renamed into place
+    pub fn write_tmp_then_rename(&self) -> bool {
+        self.write_tmp_then_rename
+    }
+
+    /// Whether writes are flushed to disk before returning
+    pub fn fsync(&self) -> bool {
+        self.fsync
+    }
+
+    /// Whether `find` descends into subdirectories
+    pub fn recursive(&self) -> bool {
+        self.recursive
+    }
+
+    /// How often `watch` checks for changes
+    pub fn watch_interval(&self) -> Duration {
+        self.watch_interval
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct FileSystemSourceBuilder {
     path: PathBuf,
     name: Option<String>,
+    write_tmp_then_rename: bool,
+    fsync: bool,
+    recursive: bool,
+    watch_interval: Duration,
 }
 
 impl FileSystemSourceBuilder {
@@ -36,6 +76,10 @@ impl FileSystemSourceBuilder {
         Self {
             path: PathBuf::from("."),
             name: None,
+            write_tmp_then_rename: true,
+            fsync: false,
+            recursive: true,
+            watch_interval: Duration::from_secs(1),
         }
     }
 
@@ -49,11 +93,42 @@ impl FileSystemSourceBuilder {
         self
     }
 
+    /// Write to a temp file and rename it into place, so a crash mid-write
+    /// never leaves a truncated file behind (default: `true`)
+    pub fn write_tmp_then_rename(mut self, enabled: bool) -> Self {
+        self.write_tmp_then_rename = enabled;
+        self
+    }
+
+    /// Fsync each written file and its directory, so completed writes also
+    /// survive a power loss (default: `false`)
+    pub fn fsync(mut self, enabled: bool) -> Self {
+        self.fsync = enabled;
+        self
+    }
+
+    /// Include files in subdirectories when `find` lists a directory or
+    /// prefix (default: `true`); glob patterns recurse only through `**`
+    pub fn recursive(mut self, enabled: bool) -> Self {
+        self.recursive = enabled;
+        self
+    }
+
+    /// How often `watch` polls the file system for changes (default: 1s)
+    pub fn watch_interval(mut self, interval: Duration) -> Self {
+        self.watch_interval = interval;
+        self
+    }
+
     pub fn build(self) -> FileSystemSource {
         FileSystemSource {
             config: FileSystemSourceConfig {
                 path: self.path,
                 name: self.name.unwrap_or_else(|| "file_system".to_string()),
+                write_tmp_then_rename: self.write_tmp_then_rename,
+                fsync: self.fsync,
+                recursive: self.recursive,
+                watch_interval: self.watch_interval,
             },
             cache: RwLock::new(HashMap::new()),
         }
@@ -66,6 +141,31 @@ impl Default for FileSystemSourceBuilder {
     }
 }
 
+/// Data source over a directory of files.
+///
+/// By default writes go through [`AtomicFile`], so a crash mid-save can't
+/// leave a truncated file and concurrent writers to the same path (e.g. two
+/// CLI runs sharing an output directory) never interleave. Enable
+/// [`fsync`](FileSystemSourceBuilder::fsync) to also make them durable
+/// across power loss.
+///
+/// `find` and `count` take a file, a directory (every file under it), a
+/// [`Glob`] pattern such as `datasets/**/*.json`, or a name prefix such as
+/// `results/run-*` (every non-hidden entry in `results` starting with
+/// `run-`). Matches come back sorted by path, which
+/// [`find_page`](DataSource::find_page) pages through, reading only the
+/// files on the requested page.
+///
+/// [`watch`](WatchSource::watch) polls the same selection every
+/// [`watch_interval`](FileSystemSourceBuilder::watch_interval), comparing
+/// modification times and sizes.
+///
+/// Records read from disk carry the file's `size` in bytes and `mtime` (Unix
+/// milliseconds) as [`attributes`](Record::attributes).
+///
+/// File system calls are blocking `std::fs` calls made on the calling task,
+/// so a timeout around them (e.g. the runtime's `io.timeout_ms`) only fires
+/// once the call returns and can't cut short a read stuck on a hung mount.
 pub struct FileSystemSource {
     config: FileSystemSourceConfig,
     cache: RwLock<HashMap<Id, Record>>,
@@ -90,28 +190,215 @@ impl FileSystemSource {
                     Ok(self.config.path.join(path_buf))
                 }
             }
+            Path::Uri(uri) if *uri.scheme() == Scheme::File => {
+                let path_buf = std::path::Path::new(uri.path());
+                if path_buf.is_absolute() {
+                    Ok(path_buf.to_path_buf())
+                } else {
+                    Ok(self.config.path.join(path_buf))
+                }
+            }
             _ => Err(ReadError::Custom(
-                "FileSystemSource only supports File paths".to_string(),
+                "FileSystemSource only supports File paths and file:// URIs".to_string(),
             )),
         }
     }
 
-    fn list_files(&self, dir_path: &std::path::Path) -> Result<Vec<PathBuf>, ReadError> {
+    /// Files under `dir_path`, descending at most `depth` levels (`None` for
+    /// no limit)
+    fn list_files(
+        &self,
+        dir_path: &std::path::Path,
+        depth: Option<usize>,
+    ) -> Result<Vec<PathBuf>, ReadError> {
         let mut files = Vec::new();
-        if dir_path.is_dir() {
+        if dir_path.is_dir() && depth != Some(0) {
             for entry in std::fs::read_dir(dir_path)? {
-                let entry = entry?;
-                let entry_path = entry.path();
+                let entry_path = entry?.path();
                 if entry_path.is_file() {
                     files.push(entry_path);
                 } else if entry_path.is_dir() {
-                    files.extend(self.list_files(&entry_path)?);
+                    files.extend(self.list_files(&entry_path, depth.map(|d| d - 1))?);
                 }
             }
         }
         Ok(files)
     }
 
+    /// Files under the entries of `full_path`'s directory whose names start
+    /// with its last component. Hidden entries (e.g. in-progress atomic
+    /// writes) are skipped unless the prefix itself starts with a `.`.
+    fn prefixed_files(
+        &self,
+        full_path: &std::path::Path,
+        depth: Option<usize>,
+    ) -> Result<Vec<PathBuf>, ReadError> {
+        let (Some(parent), Some(prefix)) = (full_path.parent(), full_path.file_name()) else {
+            return Ok(Vec::new());
+        };
+        let parent = if parent.as_os_str().is_empty() {
+            std::path::Path::new(".")
+        } else {
+            parent
+        };
+
+        if !parent.is_dir() {
+            return Ok(Vec::new());
+        }
+
+        let prefix = prefix.to_string_lossy();
+        let hidden = prefix.starts_with('.');
+        let mut files = Vec::new();
+
+        for entry in std::fs::read_dir(parent)? {
+            let entry_path = entry?.path();
+            let matched = entry_path.file_name().is_some_and(|name| {
+                let name = name.to_string_lossy();
+                name.starts_with(prefix.as_ref()) && (hidden || !name.starts_with('.'))
+            });
+
+            if !matched {
+                continue;
+            }
+
+            if entry_path.is_file() {
+                files.push(entry_path);
+            } else if entry_path.is_dir() {
+                files.extend(self.list_files(&entry_path, depth)?);
+            }
+        }
+
+        Ok(files)
+    }
+
+    /// Files `full_path` selects for `find` and `count`, sorted.
+    ///
+    /// A path ending in `*` with no other glob syntax is a name prefix; any
+    /// other glob syntax makes it a [`Glob`]. Only the part of the path below
+    /// the source root is read as a pattern, so a root containing `[`, `*` or
+    /// `?` is taken literally. A plain path that doesn't exist is `NotFound`.
+    fn matching_files(&self, full_path: &std::path::Path) -> Result<Vec<PathBuf>, ReadError> {
+        let depth = if self.config.recursive { None } else { Some(1) };
+        let (root, relative) = match full_path.strip_prefix(&self.config.path) {
+            Ok(relative) => (self.config.path.as_path(), relative),
+            Err(_) => (std::path::Path::new(""), full_path),
+        };
+        let pattern = relative.to_string_lossy();
+        let prefix = pattern
+            .strip_suffix('*')
+            .filter(|prefix| !prefix.is_empty() && !prefix.ends_with('/'))
+            .filter(|prefix| !Glob::is_pattern(prefix));
+
+        let mut files = if full_path.is_file() {
+            vec![full_path.to_path_buf()]
+        } else if full_path.is_dir() {
+            self.list_files(full_path, depth)?
+        } else if let Some(prefix) = prefix {
+            self.prefixed_files(&root.join(prefix), depth)?
+        } else if Glob::is_pattern(&pattern) {
+            let glob = Glob::new(relative);
+            self.list_files(&root.join(glob.base()), glob.depth())?
+                .into_iter()
+                .filter(|file| glob.matches(file.strip_prefix(root).unwrap_or(file)))
+                .collect()
+        } else {
+            return Err(ReadError::IO(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("{} not found", full_path.display()),
+            )));
+        };
+
+        files.sort();
+        Ok(files)
+    }
+
+    /// Stamp every file `full_path` selects, or just `full_path` itself if
+    /// `single`, so a deleted file doesn't fall back to a prefix listing
+    fn snapshot(&self, full_path: &std::path::Path, single: bool) -> Snapshot {
+        let files = if single {
+            vec![full_path.to_path_buf()]
+        } else {
+            self.matching_files(full_path).unwrap_or_default()
+        };
+
+        files
+            .into_iter()
+            .filter_map(|file| {
+                let meta = std::fs::metadata(&file).ok()?;
+                meta.is_file()
+                    .then(|| (file, (meta.modified().ok(), meta.len())))
+            })
+            .collect()
+    }
+
+    /// Read the file at `path` from disk, bypassing the cache
+    fn read(&self, path: &Path) -> Result<Record, ReadError> {
+        let full_path = self.full_path(path)?;
+        let content = std::fs::read(&full_path)?;
+        let media_type = MediaType::from_path(&full_path);
+
+        Ok(Record::new(path.clone(), media_type, content)
+            .with_attributes(file_attributes(&std::fs::metadata(&full_path)?)))
+    }
+
+    /// Drop every cached read, so the next `find_one` sees changed files
+    fn clear_cache(&self) {
+        if let Ok(mut cache) = self.cache.write() {
+            cache.clear();
+        }
+    }
+
+    /// Path of a listed file relative to the source root
+    fn relative_path(&self, file_path: &std::path::Path) -> Path {
+        let relative = file_path
+            .strip_prefix(&self.config.path)
+            .unwrap_or(file_path);
+
+        Path::File(crate::path::FilePath::parse(
+            relative.to_str().unwrap_or(""),
+        ))
+    }
+
+    /// Write `content` to `path` in the configured durability mode. With
+    /// `create_new`, fails with `AlreadyExists` instead of replacing a file.
+    fn write_file(
+        &self,
+        path: &std::path::Path,
+        content: &[u8],
+        create_new: bool,
+    ) -> std::io::Result<()> {
+        if self.config.write_tmp_then_rename {
+            let mut file = AtomicFile::create(path)?.with_sync(self.config.fsync);
+            file.write_all(content)?;
+
+            return if create_new {
+                file.commit_new()
+            } else {
+                file.commit()
+            };
+        }
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let mut file = std::fs::File::options()
+            .write(true)
+            .create(true)
+            .create_new(create_new)
+            .truncate(true)
+            .open(path)?;
+
+        file.write_all(content)?;
+
+        if self.config.fsync {
+            file.sync_all()?;
+            sync_parent(path)?;
+        }
+
+        Ok(())
+    }
+
     pub fn clear(&self) -> Result<(), ReadError> {
         let mut cache = self
             .cache
@@ -134,6 +421,40 @@ impl DataSource for FileSystemSource {
         &self.config.name
     }
 
+    fn schemes(&self) -> &[Scheme] {
+        &[Scheme::File]
+    }
+
+    fn as_watch(&self) -> Option<&dyn WatchSource> {
+        Some(self)
+    }
+
+    /// Read-only if the root directory's permissions forbid writing
+    fn capabilities(&self) -> Capabilities {
+        let capabilities = Capabilities::new().watch(true);
+
+        match std::fs::metadata(&self.config.path) {
+            Ok(meta) if meta.permissions().readonly() => capabilities.read_only(),
+            _ => capabilities,
+        }
+    }
+
+    /// Fails unless the root directory exists
+    async fn health(&self) -> Result<(), ReadError> {
+        let meta = std::fs::metadata(&self.config.path).map_err(|e| {
+            std::io::Error::new(e.kind(), format!("directory {:?}: {}", self.config.path, e))
+        })?;
+
+        if !meta.is_dir() {
+            return Err(ReadError::Custom(format!(
+                "{:?} is not a directory",
+                self.config.path
+            )));
+        }
+
+        Ok(())
+    }
+
     async fn exists(&self, path: &Path) -> Result<bool, ReadError> {
         let full_path = self.full_path(path)?;
         Ok(full_path.exists())
@@ -141,14 +462,7 @@ impl DataSource for FileSystemSource {
 
     async fn count(&self, path: &Path) -> Result<usize, ReadError> {
         let full_path = self.full_path(path)?;
-        if full_path.is_file() {
-            return Ok(1);
-        }
-        if full_path.is_dir() {
-            let files = self.list_files(&full_path)?;
-            return Ok(files.len());
-        }
-        Ok(0)
+        Ok(self.matching_files(&full_path)?.len())
     }
 
     async fn find_one(&self, path: &Path) -> Result<Record, ReadError> {
@@ -164,10 +478,7 @@ impl DataSource for FileSystemSource {
             }
         }
 
-        let full_path = self.full_path(path)?;
-        let content = std::fs::read(&full_path)?;
-        let media_type = MediaType::from_path(&full_path);
-        let record = Record::new(path.clone(), media_type, content);
+        let record = self.read(path)?;
 
         {
             let mut cache = self
@@ -187,22 +498,117 @@ impl DataSource for FileSystemSource {
             return Ok(vec![self.find_one(path).await?]);
         }
 
-        if full_path.is_dir() {
-            let files = self.list_files(&full_path)?;
-            let mut records = Vec::new();
-            for file_path in files {
-                let relative = file_path
-                    .strip_prefix(&self.config.path)
-                    .unwrap_or(&file_path);
-                let path = Path::File(crate::path::FilePath::parse(
-                    relative.to_str().unwrap_or(""),
-                ));
-                records.push(self.find_one(&path).await?);
+        let mut records = Vec::new();
+        for file_path in self.matching_files(&full_path)? {
+            records.push(self.find_one(&self.relative_path(&file_path)).await?);
+        }
+
+        Ok(records)
+    }
+
+    /// Reads each file when it is polled, without caching it, so streaming
+    /// a large directory doesn't hold every file in memory
+    fn find_stream<'a>(&'a self, path: &'a Path) -> BoxStream<'a, Result<Record, ReadError>> {
+        // Listing is cheap; only the reads are deferred until polled
+        let files = match self.full_path(path) {
+            Ok(full_path) if full_path.is_file() => {
+                return stream::once(async move { self.read(path) }).boxed();
             }
-            return Ok(records);
+            Ok(full_path) => self.matching_files(&full_path),
+            Err(err) => Err(err),
+        };
+
+        match files {
+            Ok(files) => stream::iter(files)
+                .map(move |file_path| self.read(&self.relative_path(&file_path)))
+                .boxed(),
+            Err(err) => stream::once(async { Err(err) }).boxed(),
+        }
+    }
+
+    /// Streams the file from disk without caching it, reading each chunk
+    /// only when the consumer asks for it
+    async fn find_one_chunked<'a>(
+        &'a self,
+        path: &'a Path,
+        chunk_size: usize,
+    ) -> Result<ChunkedRecord<'a>, ReadError> {
+        let full_path = self.full_path(path)?;
+        let meta = std::fs::metadata(&full_path)?;
+        let file = std::fs::File::open(&full_path)?;
+        let header = Record {
+            size: meta.len() as usize,
+            ..Record::new(path.clone(), MediaType::from_path(&full_path), Vec::new())
         }
+        .with_attributes(file_attributes(&meta));
+
+        let chunks = stream::unfold(Some(file), move |file| async move {
+            let mut file = file?;
+            let mut chunk = Vec::with_capacity(chunk_size.max(1));
+
+            match (&mut file)
+                .take(chunk_size.max(1) as u64)
+                .read_to_end(&mut chunk)
+            {
+                Ok(0) => None,
+                Ok(_) => Some((Ok(chunk), Some(file))),
+                // End the stream after the first failure
+                Err(err) => Some((Err(ReadError::IO(err)), None)),
+            }
+        });
 
-        Ok(Vec::new())
+        Ok(ChunkedRecord::new(header, chunks.boxed()))
+    }
+
+    async fn find_page(
+        &self,
+        path: &Path,
+        cursor: Option<Cursor>,
+        limit: usize,
+    ) -> Result<(Vec<Record>, Option<Cursor>), ReadError> {
+        if limit == 0 {
+            return Err(ReadError::zero_limit());
+        }
+
+        let files = self.matching_files(&self.full_path(path)?)?;
+        let start = match &cursor {
+            None => 0,
+            Some(cursor) => {
+                let after = self.config.path.join(cursor.as_str());
+                files.partition_point(|file| *file <= after)
+            }
+        };
+
+        let mut records = Vec::new();
+        for file_path in files.iter().skip(start).take(limit) {
+            records.push(self.find_one(&self.relative_path(file_path)).await?);
+        }
+
+        let next = if start + records.len() == files.len() {
+            None
+        } else {
+            records
+                .last()
+                .map(|last| Cursor::after(&last.path))
+                .or(cursor)
+        };
+
+        Ok((records, next))
+    }
+
+    /// Hashes the file on disk rather than a cached read, which may predate
+    /// changes made by other processes
+    async fn etag(&self, path: &Path) -> Result<Option<ETag>, ReadError> {
+        let full_path = self.full_path(path)?;
+
+        match std::fs::read(&full_path) {
+            Ok(content) => Ok(Some(ETag::from_bytes(
+                MediaType::from_path(&full_path),
+                &content,
+            ))),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err.into()),
+        }
     }
 
     async fn create(&self, record: Record) -> Result<(), WriteError> {
@@ -212,18 +618,18 @@ impl DataSource for FileSystemSource {
             ReadError::Panic(msg) => WriteError::Panic(msg),
         })?;
 
-        if full_path.exists() {
-            return Err(WriteError::Custom(format!(
-                "file already exists: {}",
-                record.path
-            )));
-        }
+        let exists = || WriteError::Custom(format!("file already exists: {}", record.path));
 
-        if let Some(parent) = full_path.parent() {
-            std::fs::create_dir_all(parent)?;
+        if full_path.exists() {
+            return Err(exists());
         }
 
-        std::fs::write(&full_path, &record.content)?;
+        // Fails rather than replacing a file another process created meanwhile
+        self.write_file(&full_path, &record.content, true)
+            .map_err(|e| match e.kind() {
+                std::io::ErrorKind::AlreadyExists => exists(),
+                _ => WriteError::IO(e),
+            })?;
 
         let id = record.id;
         {
@@ -251,7 +657,46 @@ impl DataSource for FileSystemSource {
             )));
         }
 
-        std::fs::write(&full_path, &record.content)?;
+        self.write_file(&full_path, &record.content, false)?;
+
+        let id = record.id;
+        {
+            let mut cache = self
+                .cache
+                .write()
+                .map_err(|e| WriteError::Panic(e.to_string()))?;
+            cache.insert(id, record);
+        }
+
+        Ok(())
+    }
+
+    /// Holds a lock file next to the target while comparing the ETag of its
+    /// content and replacing it, so conditional updates from other processes
+    /// can't slip in between.
+    async fn update_if(&self, record: Record, expected: &ETag) -> Result<(), WriteError> {
+        let full_path = self.full_path(&record.path)?;
+
+        if !full_path.exists() {
+            return Err(WriteError::Custom(format!(
+                "file not found: {}",
+                record.path
+            )));
+        }
+
+        {
+            let _lock = LockFile::acquire(&full_path).await?;
+            let content = std::fs::read(&full_path)?;
+
+            if ETag::from_bytes(MediaType::from_path(&full_path), &content) != *expected {
+                return Err(WriteError::PreconditionFailed(format!(
+                    "{} changed since it was read",
+                    record.path
+                )));
+            }
+
+            self.write_file(&full_path, &record.content, false)?;
+        }
 
         let id = record.id;
         {
@@ -272,11 +717,7 @@ impl DataSource for FileSystemSource {
             ReadError::Panic(msg) => WriteError::Panic(msg),
         })?;
 
-        if let Some(parent) = full_path.parent() {
-            std::fs::create_dir_all(parent)?;
-        }
-
-        std::fs::write(&full_path, &record.content)?;
+        self.write_file(&full_path, &record.content, false)?;
 
         let id = record.id;
         {
@@ -311,6 +752,80 @@ impl DataSource for FileSystemSource {
     }
 }
 
+impl WatchSource for FileSystemSource {
+    /// Polls from a background thread, which stops within one interval of
+    /// the stream being dropped. Paths the source can't read yield nothing.
+    /// Cached reads are dropped as each event is yielded.
+    fn watch<'a>(&'a self, path: &'a Path) -> BoxStream<'a, ChangeEvent> {
+        let Ok(full_path) = self.full_path(path) else {
+            return stream::empty().boxed();
+        };
+
+        let (tx, rx) = mpsc::unbounded();
+        let single = full_path.is_file();
+        let interval = self.config.watch_interval;
+        let poller = FileSystemSource {
+            config: self.config.clone(),
+            cache: RwLock::new(HashMap::new()),
+        };
+
+        // Taken before returning, so no change made after `watch` is missed
+        let mut seen = poller.snapshot(&full_path, single);
+
+        std::thread::spawn(move || {
+            while !tx.is_closed() {
+                std::thread::sleep(interval);
+                let current = poller.snapshot(&full_path, single);
+
+                for (kind, file) in changes(&seen, &current) {
+                    let event = ChangeEvent::new(kind, poller.relative_path(&file));
+
+                    if tx.unbounded_send(event).is_err() {
+                        return;
+                    }
+                }
+
+                seen = current;
+            }
+        });
+
+        rx.inspect(move |_| self.clear_cache()).boxed()
+    }
+}
+
+/// `size` and `mtime` (Unix milliseconds, if the platform records it) of a file
+fn file_attributes(meta: &std::fs::Metadata) -> BTreeMap<String, Value> {
+    let mut attributes = BTreeMap::from([("size".to_string(), Value::from(meta.len()))]);
+
+    if let Some(mtime) = meta
+        .modified()
+        .ok()
+        .and_then(|t| t.duration_since(SystemTime::UNIX_EPOCH).ok())
+    {
+        attributes.insert("mtime".to_string(), Value::from(mtime.as_millis() as u64));
+    }
+
+    attributes
+}
+
+/// Files deleted, created and modified between two snapshots
+fn changes(before: &Snapshot, after: &Snapshot) -> Vec<(ChangeKind, PathBuf)> {
+    let deleted = before
+        .keys()
+        .filter(|file| !after.contains_key(*file))
+        .map(|file| (ChangeKind::Delete, file.clone()));
+
+    let changed = after
+        .iter()
+        .filter_map(|(file, stamp)| match before.get(file) {
+            None => Some((ChangeKind::Create, file.clone())),
+            Some(seen) if seen != stamp => Some((ChangeKind::Update, file.clone())),
+            Some(_) => None,
+        });
+
+    deleted.chain(changed).collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -359,6 +874,8 @@ mod tests {
 
         assert_eq!(record.media_type, MediaType::TextPlain);
         assert_eq!(record.content_str().unwrap(), "hello world");
+        assert_eq!(record.attribute("size"), Some(&Value::from(11u64)));
+        assert!(record.attribute("mtime").is_some_and(|v| v.is_number()));
 
         let _ = std::fs::remove_file(&file_path);
     }
@@ -414,6 +931,58 @@ mod tests {
         let _ = std::fs::remove_file(&file_path);
     }
 
+    #[tokio::test]
+    async fn test_update_if() {
+        let ds = test_source();
+        let file_path = test_dir().join("update_if_test.txt");
+        let path = Path::File(FilePath::parse(file_path.to_str().unwrap()));
+
+        std::fs::create_dir_all(test_dir()).unwrap();
+        std::fs::write(&file_path, "old").unwrap();
+
+        let read = ds.find_one(&path).await.unwrap();
+        ds.update_if(make_record(&path, "first"), &read.etag)
+            .await
+            .unwrap();
+
+        // A second writer still holding the etag it read before the first write
+        let err = ds
+            .update_if(make_record(&path, "second"), &read.etag)
+            .await
+            .unwrap_err();
+
+        assert!(err.is_precondition_failed());
+        assert_eq!(std::fs::read_to_string(&file_path).unwrap(), "first");
+        assert!(!test_dir().join(".update_if_test.txt.lock").exists());
+
+        let _ = std::fs::remove_file(&file_path);
+    }
+
+    #[tokio::test]
+    async fn test_etag_bypasses_cache() {
+        let ds = test_source();
+        let file_path = test_dir().join("etag_test.txt");
+        let path = Path::File(FilePath::parse(file_path.to_str().unwrap()));
+
+        std::fs::create_dir_all(test_dir()).unwrap();
+        std::fs::write(&file_path, "old").unwrap();
+
+        let read = ds.find_one(&path).await.unwrap();
+        assert_eq!(ds.etag(&path).await.unwrap(), Some(read.etag));
+
+        // Changed by another process, behind the cached read
+        std::fs::write(&file_path, "new").unwrap();
+
+        let etag = ds.etag(&path).await.unwrap().unwrap();
+        assert_ne!(etag, read.etag);
+        ds.update_if(make_record(&path, "newer"), &etag)
+            .await
+            .unwrap();
+
+        std::fs::remove_file(&file_path).unwrap();
+        assert_eq!(ds.etag(&path).await.unwrap(), None);
+    }
+
     #[tokio::test]
     async fn test_update_not_found() {
         let ds = test_source();
@@ -488,6 +1057,22 @@ mod tests {
         let _ = std::fs::remove_file(&file_path);
     }
 
+    #[tokio::test]
+    async fn test_file_uri() {
+        let dir = test_dir();
+        std::fs::create_dir_all(&dir).unwrap();
+        let file_path = dir.join("file_uri_test.txt");
+        std::fs::write(&file_path, "from uri").unwrap();
+
+        let ds = test_source();
+        let uri = crate::path::UriPath::parse(&format!("file://{}", file_path.display())).unwrap();
+        let record = ds.find_one(&Path::Uri(uri)).await.unwrap();
+
+        assert_eq!(record.content_str().unwrap(), "from uri");
+
+        let _ = std::fs::remove_file(&file_path);
+    }
+
     #[tokio::test]
     async fn test_find_one_not_found() {
         let ds = test_source();
@@ -498,6 +1083,292 @@ mod tests {
         assert!(result.unwrap_err().is_io());
     }
 
+    fn tree(name: &str, files: &[&str]) -> PathBuf {
+        let dir = test_dir().join(name);
+        let _ = std::fs::remove_dir_all(&dir);
+
+        for file in files {
+            let path = dir.join(file);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(&path, *file).unwrap();
+        }
+
+        dir
+    }
+
+    fn contents(records: &[Record]) -> Vec<&str> {
+        records.iter().map(|r| r.content_str().unwrap()).collect()
+    }
+
+    #[tokio::test]
+    async fn test_find_stream() {
+        use futures::TryStreamExt;
+
+        let dir = tree("stream", &["a.json", "b.yaml", "train/c.json"]);
+        let ds = FileSystemSource::builder().path(&dir).build();
+
+        let path = Path::File(FilePath::parse("**/*.json"));
+        let records: Vec<Record> = ds.find_stream(&path).try_collect().await.unwrap();
+        assert_eq!(contents(&records), vec!["a.json", "train/c.json"]);
+
+        let path = Path::File(FilePath::parse("b.yaml"));
+        let records: Vec<Record> = ds.find_stream(&path).try_collect().await.unwrap();
+        assert_eq!(contents(&records), vec!["b.yaml"]);
+
+        let path = Path::File(FilePath::parse("missing/*.json"));
+        let records: Vec<Record> = ds.find_stream(&path).try_collect().await.unwrap();
+        assert!(records.is_empty());
+
+        // Streamed files aren't cached
+        assert!(ds.cache.read().unwrap().is_empty());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_health() {
+        let dir = tree("health", &["a.json"]);
+
+        let ds = FileSystemSource::builder().path(&dir).build();
+        assert!(ds.health().await.is_ok());
+        assert!(ds.capabilities().watch);
+
+        let ds = FileSystemSource::builder().path(dir.join("a.json")).build();
+        assert!(ds.health().await.unwrap_err().is_custom());
+
+        let ds = FileSystemSource::builder()
+            .path(dir.join("missing"))
+            .build();
+        assert!(ds.health().await.unwrap_err().is_io());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_find_page() {
+        let dir = tree(
+            "page",
+            &["a.json", "b.json", "c.yaml", "d/e.json", "f.json"],
+        );
+        let ds = FileSystemSource::builder().path(&dir).build();
+        let path = Path::File(FilePath::parse("**/*.json"));
+
+        let (page, cursor) = ds.find_page(&path, None, 2).await.unwrap();
+        assert_eq!(contents(&page), vec!["a.json", "b.json"]);
+
+        // Files added before the cursor don't shift the next page
+        std::fs::write(dir.join("0.json"), "0.json").unwrap();
+
+        let (page, cursor) = ds.find_page(&path, cursor, 2).await.unwrap();
+        assert_eq!(contents(&page), vec!["d/e.json", "f.json"]);
+        assert_eq!(cursor, None);
+
+        let (page, cursor) = ds.find_page(&path, None, 10).await.unwrap();
+        assert_eq!(page.len(), 5);
+        assert_eq!(cursor, None);
+
+        assert!(ds.find_page(&path, None, 0).await.unwrap_err().is_custom());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_find_one_chunked() {
+        use futures::TryStreamExt;
+
+        let dir = tree("chunked", &["corpus/large.jsonl"]);
+        let ds = FileSystemSource::builder().path(&dir).build();
+        let path = Path::File(FilePath::parse("corpus/large.jsonl"));
+
+        let chunked = ds.find_one_chunked(&path, 7).await.unwrap();
+        assert_eq!(chunked.header.size, 18);
+        assert_eq!(chunked.header.media_type, MediaType::TextNdjson);
+        assert!(chunked.header.attribute("mtime").is_some());
+
+        let chunks = chunked.chunks.try_collect::<Vec<_>>().await.unwrap();
+        assert_eq!(
+            chunks,
+            [b"corpus/".to_vec(), b"large.j".to_vec(), b"sonl".to_vec()]
+        );
+
+        let record = ds
+            .find_one_chunked(&path, 4)
+            .await
+            .unwrap()
+            .collect()
+            .await
+            .unwrap();
+        assert_eq!(record, ds.find_one(&path).await.unwrap());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_watch() {
+        let dir = tree("watch", &["a.json"]);
+        let ds = FileSystemSource::builder()
+            .path(&dir)
+            .watch_interval(Duration::from_millis(10))
+            .build();
+
+        let a = Path::File(FilePath::parse("a.json"));
+        let b = Path::File(FilePath::parse("b.json"));
+        let pattern = Path::File(FilePath::parse("*.json"));
+        let mut events = ds.watch(&pattern);
+
+        assert_eq!(
+            ds.find_one(&a).await.unwrap().content_str().unwrap(),
+            "a.json"
+        );
+
+        std::fs::write(dir.join("b.json"), "b").unwrap();
+        let event = events.next().await.unwrap();
+        assert_eq!(event, ChangeEvent::new(ChangeKind::Create, b.clone()));
+
+        std::fs::write(dir.join("a.json"), "changed").unwrap();
+        let event = events.next().await.unwrap();
+        assert_eq!(event, ChangeEvent::new(ChangeKind::Update, a.clone()));
+        assert_eq!(
+            ds.find_one(&a).await.unwrap().content_str().unwrap(),
+            "changed"
+        );
+
+        std::fs::remove_file(dir.join("b.json")).unwrap();
+        let event = events.next().await.unwrap();
+        assert_eq!(event, ChangeEvent::new(ChangeKind::Delete, b));
+
+        drop(events);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_changes() {
+        let stamp = |len| (Some(SystemTime::UNIX_EPOCH), len);
+        let before = Snapshot::from([
+            (PathBuf::from("kept"), stamp(1)),
+            (PathBuf::from("modified"), stamp(1)),
+            (PathBuf::from("removed"), stamp(1)),
+        ]);
+        let after = Snapshot::from([
+            (PathBuf::from("added"), stamp(1)),
+            (PathBuf::from("kept"), stamp(1)),
+            (PathBuf::from("modified"), stamp(2)),
+        ]);
+
+        assert_eq!(
+            changes(&before, &after),
+            vec![
+                (ChangeKind::Delete, PathBuf::from("removed")),
+                (ChangeKind::Create, PathBuf::from("added")),
+                (ChangeKind::Update, PathBuf::from("modified")),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_find_glob() {
+        let dir = tree(
+            "glob",
+            &["a.json", "b.yaml", "train/c.json", "train/deep/d.json"],
+        );
+        let ds = FileSystemSource::builder().path(&dir).build();
+
+        let records = ds
+            .find(&Path::File(FilePath::parse("*.json")))
+            .await
+            .unwrap();
+        assert_eq!(contents(&records), vec!["a.json"]);
+
+        let path = Path::File(FilePath::parse("**/*.json"));
+        let records = ds.find(&path).await.unwrap();
+        assert_eq!(
+            contents(&records),
+            vec!["a.json", "train/c.json", "train/deep/d.json"]
+        );
+        assert_eq!(ds.count(&path).await.unwrap(), 3);
+
+        let records = ds
+            .find(&Path::File(FilePath::parse("train/*/*.json")))
+            .await
+            .unwrap();
+        assert_eq!(contents(&records), vec!["train/deep/d.json"]);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_find_prefix() {
+        let dir = tree(
+            "prefix",
+            &[
+                "run-1.json",
+                "run-2.json",
+                "run-3/scores.json",
+                "other.json",
+            ],
+        );
+        let ds = FileSystemSource::builder().path(&dir).build();
+
+        // An in-progress atomic write of another run
+        std::fs::write(dir.join(".run-4.json.123.0.tmp"), "partial").unwrap();
+
+        let records = ds
+            .find(&Path::File(FilePath::parse("run-*")))
+            .await
+            .unwrap();
+        assert_eq!(
+            contents(&records),
+            vec!["run-1.json", "run-2.json", "run-3/scores.json"]
+        );
+
+        let records = ds
+            .find(&Path::File(FilePath::parse("missing-*")))
+            .await
+            .unwrap();
+        assert!(records.is_empty());
+
+        // Without the `*` a name is a file, not a prefix
+        let err = ds
+            .find(&Path::File(FilePath::parse("run-")))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ReadError::IO(e) if e.kind() == std::io::ErrorKind::NotFound));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_find_glob_under_literal_root() {
+        let dir = tree("glob[1]", &["a.json", "b.yaml"]);
+        let ds = FileSystemSource::builder().path(&dir).build();
+
+        let path = Path::File(FilePath::parse("*.json"));
+        assert_eq!(contents(&ds.find(&path).await.unwrap()), vec!["a.json"]);
+
+        let path = Path::File(FilePath::parse("a*"));
+        assert_eq!(contents(&ds.find(&path).await.unwrap()), vec!["a.json"]);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_find_non_recursive() {
+        let dir = tree("non_recursive", &["a.json", "nested/b.json"]);
+        let path = Path::File(FilePath::parse(dir.to_str().unwrap()));
+
+        let ds = FileSystemSource::builder().path(&dir).build();
+        assert_eq!(ds.count(&path).await.unwrap(), 2);
+
+        let ds = FileSystemSource::builder()
+            .path(&dir)
+            .recursive(false)
+            .build();
+        let records = ds.find(&path).await.unwrap();
+        assert_eq!(contents(&records), vec!["a.json"]);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
     #[test]
     fn test_builder() {
         let ds = FileSystemSource::builder()
@@ -515,5 +1386,59 @@ mod tests {
 
         assert_eq!(ds.config().path(), std::path::Path::new("."));
         assert_eq!(ds.config().name(), "file_system");
+        assert!(ds.config().write_tmp_then_rename());
+        assert!(!ds.config().fsync());
+        assert!(ds.config().recursive());
+    }
+
+    #[test]
+    fn test_builder_durability() {
+        let ds = FileSystemSource::builder()
+            .write_tmp_then_rename(false)
+            .fsync(true)
+            .build();
+
+        assert!(!ds.config().write_tmp_then_rename());
+        assert!(ds.config().fsync());
+    }
+
+    #[tokio::test]
+    async fn test_direct_writes() {
+        let ds = FileSystemSource::builder()
+            .path(test_dir())
+            .write_tmp_then_rename(false)
+            .fsync(true)
+            .build();
+        let file_path = test_dir().join("direct").join("direct_test.txt");
+        let path = Path::File(FilePath::parse(file_path.to_str().unwrap()));
+
+        let _ = std::fs::remove_file(&file_path);
+
+        ds.create(make_record(&path, "first version"))
+            .await
+            .unwrap();
+        assert!(ds.create(make_record(&path, "again")).await.is_err());
+
+        ds.update(make_record(&path, "second")).await.unwrap();
+        assert_eq!(std::fs::read_to_string(&file_path).unwrap(), "second");
+
+        let _ = std::fs::remove_file(&file_path);
+    }
+
+    #[tokio::test]
+    async fn test_synced_atomic_writes() {
+        let ds = FileSystemSource::builder()
+            .path(test_dir())
+            .fsync(true)
+            .build();
+        let file_path = test_dir().join("synced_test.txt");
+        let path = Path::File(FilePath::parse(file_path.to_str().unwrap()));
+
+        let _ = std::fs::remove_file(&file_path);
+
+        ds.upsert(make_record(&path, "synced")).await.unwrap();
+        assert_eq!(std::fs::read_to_string(&file_path).unwrap(), "synced");
+
+        let _ = std::fs::remove_file(&file_path);
     }
 }
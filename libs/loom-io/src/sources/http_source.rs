@@ -0,0 +1,940 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use futures::stream::{self, StreamExt, TryStreamExt};
+use reqwest::header::{CONTENT_TYPE, IF_MATCH, IF_NONE_MATCH};
+use reqwest::{Method, RequestBuilder, Response, StatusCode};
+
+use crate::MediaType;
+use crate::path::{Path, Scheme};
+use crate::value::{Object, Value};
+
+use crate::chunked::split;
+use crate::{Capabilities, ChunkedRecord, DataSource, ReadError, Record, WriteError};
+
+/// Credentials sent with every request
+#[derive(Clone)]
+pub enum HttpAuth {
+    Bearer(String),
+    Basic {
+        username: String,
+        password: Option<String>,
+    },
+}
+
+impl std::fmt::Debug for HttpAuth {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Never print secrets
+        match self {
+            Self::Bearer(_) => write!(f, "Bearer(***)"),
+            Self::Basic { username, .. } => write!(f, "Basic({}:***)", username),
+        }
+    }
+}
+
+/// Method used to write records
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WriteMethod {
+    #[default]
+    Put,
+    Post,
+}
+
+impl WriteMethod {
+    fn method(self) -> Method {
+        match self {
+            Self::Put => Method::PUT,
+            Self::Post => Method::POST,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct HttpSourceConfig {
+    name: String,
+    base_url: Option<String>,
+    headers: Vec<(String, String)>,
+    auth: Option<HttpAuth>,
+    write_method: WriteMethod,
+    timeout: Option<Duration>,
+}
+
+impl HttpSourceConfig {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Prefix for relative file paths
+    pub fn base_url(&self) -> Option<&str> {
+        self.base_url.as_deref()
+    }
+
+    pub fn headers(&self) -> &[(String, String)] {
+        &self.headers
+    }
+
+    pub fn auth(&self) -> Option<&HttpAuth> {
+        self.auth.as_ref()
+    }
+
+    pub fn write_method(&self) -> WriteMethod {
+        self.write_method
+    }
+
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct HttpSourceBuilder {
+    name: Option<String>,
+    base_url: Option<String>,
+    headers: Vec<(String, String)>,
+    auth: Option<HttpAuth>,
+    write_method: WriteMethod,
+    timeout: Option<Duration>,
+}
+
+impl HttpSourceBuilder {
+    pub fn new() -> Self {
+        Self {
+            name: None,
+            base_url: None,
+            headers: Vec::new(),
+            auth: None,
+            write_method: WriteMethod::default(),
+            timeout: None,
+        }
+    }
+
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Resolve `Path::File` paths against this URL
+    pub fn base_url(mut self, url: impl Into<String>) -> Self {
+        self.base_url = Some(url.into());
+        self
+    }
+
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    pub fn bearer_auth(mut self, token: impl Into<String>) -> Self {
+        self.auth = Some(HttpAuth::Bearer(token.into()));
+        self
+    }
+
+    pub fn basic_auth(mut self, username: impl Into<String>, password: Option<String>) -> Self {
+        self.auth = Some(HttpAuth::Basic {
+            username: username.into(),
+            password,
+        });
+        self
+    }
+
+    /// PUT (default) or POST for create, update and upsert
+    pub fn write_method(mut self, method: WriteMethod) -> Self {
+        self.write_method = method;
+        self
+    }
+
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub fn build(self) -> HttpSource {
+        HttpSource {
+            config: HttpSourceConfig {
+                name: self.name.unwrap_or_else(|| "http".to_string()),
+                base_url: self.base_url,
+                headers: self.headers,
+                auth: self.auth,
+                write_method: self.write_method,
+                timeout: self.timeout,
+            },
+            client: reqwest::Client::new(),
+        }
+    }
+}
+
+impl Default for HttpSourceBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Data source over HTTP(S) URLs.
+///
+/// Reads are GETs whose `Content-Type` sets the record's media type (falling
+/// back to the URL's extension); writes send the record's content with the
+/// configured [`WriteMethod`]. Paths are either `http`/`https` URIs or file
+/// paths relative to the base URL.
+///
+/// Records read carry the response's `status` and `headers` (lowercase names
+/// to values, repeated headers joined with `, `) as
+/// [`attributes`](Record::attributes). Credential headers (cookies and
+/// authorization) are left out, as attributes end up in signals and logs.
+pub struct HttpSource {
+    config: HttpSourceConfig,
+    client: reqwest::Client,
+}
+
+impl HttpSource {
+    pub fn builder() -> HttpSourceBuilder {
+        HttpSourceBuilder::new()
+    }
+
+    pub fn config(&self) -> &HttpSourceConfig {
+        &self.config
+    }
+
+    fn url(&self, path: &Path) -> Result<String, String> {
+        match path {
+            Path::Uri(uri) => match uri.scheme() {
+                Scheme::Http | Scheme::Https => Ok(uri.to_string()),
+                scheme => Err(format!(
+                    "HttpSource only supports http and https URIs, got '{}'",
+                    scheme
+                )),
+            },
+            Path::File(file_path) => match &self.config.base_url {
+                Some(base) => Ok(format!(
+                    "{}/{}",
+                    base.trim_end_matches('/'),
+                    file_path.to_string_lossy().trim_start_matches('/')
+                )),
+                None => Err(format!("HttpSource needs a base url to resolve '{}'", path)),
+            },
+            _ => Err("HttpSource only supports Uri and File paths".to_string()),
+        }
+    }
+
+    fn request(&self, method: Method, url: &str) -> RequestBuilder {
+        let mut request = self.client.request(method, url);
+
+        for (name, value) in &self.config.headers {
+            request = request.header(name.as_str(), value.as_str());
+        }
+
+        if let Some(timeout) = self.config.timeout {
+            request = request.timeout(timeout);
+        }
+
+        match &self.config.auth {
+            Some(HttpAuth::Bearer(token)) => request.bearer_auth(token),
+            Some(HttpAuth::Basic { username, password }) => {
+                request.basic_auth(username, password.as_ref())
+            }
+            None => request,
+        }
+    }
+
+    /// GET `path`, failing unless the response is a success
+    async fn get(&self, path: &Path) -> Result<(String, Response), ReadError> {
+        let url = self.url(path).map_err(ReadError::Custom)?;
+        let response = self
+            .request(Method::GET, &url)
+            .send()
+            .await
+            .map_err(|e| ReadError::IO(transport_error(e)))?;
+
+        match response.status() {
+            status if status.is_success() => Ok((url, response)),
+            // Same shape as a missing file, so callers can check the kind
+            StatusCode::NOT_FOUND | StatusCode::GONE => Err(ReadError::IO(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("not found: {}", url),
+            ))),
+            status => Err(status_error("GET", &url, status, ReadError::Custom)),
+        }
+    }
+
+    /// Send `record` with the write method, plus an optional precondition
+    async fn write(
+        &self,
+        record: &Record,
+        condition: Option<(&str, &str)>,
+    ) -> Result<Response, WriteError> {
+        let url = self.url(&record.path).map_err(WriteError::Custom)?;
+        let mut request = self
+            .request(self.config.write_method.method(), &url)
+            .header(CONTENT_TYPE, record.media_type.as_mime_str())
+            .body(record.content.clone());
+
+        if let Some((name, value)) = condition {
+            request = request.header(name, value);
+        }
+
+        request
+            .send()
+            .await
+            .map_err(|e| WriteError::IO(transport_error(e)))
+    }
+
+    /// [`DataSource::exists`] for the write operations
+    async fn present(&self, path: &Path) -> Result<bool, WriteError> {
+        self.exists(path).await.map_err(|e| match e {
+            ReadError::IO(io) => WriteError::IO(io),
+            ReadError::Panic(msg) => WriteError::Panic(msg),
+            ReadError::Custom(msg) => WriteError::Custom(msg),
+        })
+    }
+
+    fn write_failed(&self, response: &Response) -> WriteError {
+        status_error(
+            self.config.write_method.method().as_str(),
+            response.url().as_str(),
+            response.status(),
+            WriteError::Custom,
+        )
+    }
+}
+
+impl Default for HttpSource {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+/// Media type from a `Content-Type` header, ignoring parameters such as
+/// `charset`, else from the URL's extension
+fn media_type(content_type: Option<&str>, url: &str) -> MediaType {
+    let from_header = content_type
+        .and_then(|value| value.split(';').next())
+        .map(MediaType::from_mime_str)
+        .unwrap_or(MediaType::Unknown);
+
+    match from_header {
+        // Too generic to pick a codec; the extension may know better
+        MediaType::Unknown | MediaType::Binary | MediaType::Text => {
+            let path = url.split(['?', '#']).next().unwrap_or(url);
+
+            match MediaType::from_path(path) {
+                MediaType::Unknown => from_header,
+                media_type => media_type,
+            }
+        }
+        media_type => media_type,
+    }
+}
+
+/// Headers that carry credentials, never copied into record attributes
+const SENSITIVE_HEADERS: &[&str] = &[
+    "set-cookie",
+    "cookie",
+    "authorization",
+    "proxy-authorization",
+];
+
+/// Record for `path` without content, with the media type and attributes
+/// of `response`
+fn header(path: &Path, url: &str, response: &Response) -> Record {
+    let content_type = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok());
+
+    Record::new(path.clone(), media_type(content_type, url), Vec::new())
+        .with_attribute("status", response.status().as_u16())
+        .with_attribute("headers", headers(response.headers()))
+}
+
+/// Response headers as an object of lowercase names to values, joining
+/// repeated headers with `, ` and skipping [`SENSITIVE_HEADERS`] and values
+/// that aren't valid text
+fn headers(map: &reqwest::header::HeaderMap) -> Value {
+    let mut headers = Object::new();
+
+    for name in map.keys() {
+        if SENSITIVE_HEADERS.contains(&name.as_str()) {
+            continue;
+        }
+
+        let values = map
+            .get_all(name)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .collect::<Vec<_>>();
+
+        if !values.is_empty() {
+            headers.insert(name.as_str().to_string(), Value::from(values.join(", ")));
+        }
+    }
+
+    Value::Object(headers)
+}
+
+/// Failed status as an error; statuses worth retrying (408, 429, 5xx) become
+/// `Interrupted` IO errors, so they read as transient
+fn status_error<E: From<std::io::Error>>(
+    method: &str,
+    url: &str,
+    status: StatusCode,
+    custom: fn(String) -> E,
+) -> E {
+    let message = format!("{} {} failed: {}", method, url, status);
+
+    if status == StatusCode::REQUEST_TIMEOUT
+        || status == StatusCode::TOO_MANY_REQUESTS
+        || status.is_server_error()
+    {
+        std::io::Error::new(std::io::ErrorKind::Interrupted, message).into()
+    } else {
+        custom(message)
+    }
+}
+
+/// Request failures as IO errors whose kind tells timeouts and dropped
+/// connections apart from other failures
+fn transport_error(err: reqwest::Error) -> std::io::Error {
+    let kind = if err.is_timeout() {
+        std::io::ErrorKind::TimedOut
+    } else if err.is_connect() {
+        std::io::ErrorKind::ConnectionRefused
+    } else if err.is_body() {
+        std::io::ErrorKind::UnexpectedEof
+    } else {
+        std::io::ErrorKind::Other
+    };
+
+    std::io::Error::new(kind, err)
+}
+
+#[async_trait]
+impl DataSource for HttpSource {
+    fn name(&self) -> &str {
+        &self.config.name
+    }
+
+    fn schemes(&self) -> &[Scheme] {
+        &[Scheme::Http, Scheme::Https]
+    }
+
+    /// Reads and writes single resources; there is no listing or watching
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::new().list(false)
+    }
+
+    /// Sends a HEAD to the base URL, if there is one. Any response short of
+    /// a 5xx counts as reachable, since the base itself may not be a resource.
+    async fn health(&self) -> Result<(), ReadError> {
+        let Some(url) = &self.config.base_url else {
+            return Ok(());
+        };
+
+        let response = self
+            .request(Method::HEAD, url)
+            .send()
+            .await
+            .map_err(|e| ReadError::IO(transport_error(e)))?;
+
+        match response.status() {
+            status if status.is_server_error() => {
+                Err(status_error("HEAD", url, status, ReadError::Custom))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    async fn exists(&self, path: &Path) -> Result<bool, ReadError> {
+        let url = self.url(path).map_err(ReadError::Custom)?;
+        let response = self
+            .request(Method::HEAD, &url)
+            .send()
+            .await
+            .map_err(|e| ReadError::IO(transport_error(e)))?;
+
+        match response.status() {
+            status if status.is_success() => Ok(true),
+            StatusCode::NOT_FOUND | StatusCode::GONE => Ok(false),
+            status => Err(status_error("HEAD", &url, status, ReadError::Custom)),
+        }
+    }
+
+    async fn count(&self, path: &Path) -> Result<usize, ReadError> {
+        Ok(usize::from(self.exists(path).await?))
+    }
+
+    async fn find_one(&self, path: &Path) -> Result<Record, ReadError> {
+        let (url, response) = self.get(path).await?;
+        let header = header(path, &url, &response);
+        let content = response
+            .bytes()
+            .await
+            .map_err(|e| ReadError::IO(transport_error(e)))?;
+
+        Ok(
+            Record::new(header.path, header.media_type, content.to_vec())
+                .with_attributes(header.attributes),
+        )
+    }
+
+    /// Streams the response body as it arrives; chunks are the body's
+    /// network chunks, split where longer than `chunk_size`
+    async fn find_one_chunked<'a>(
+        &'a self,
+        path: &'a Path,
+        chunk_size: usize,
+    ) -> Result<ChunkedRecord<'a>, ReadError> {
+        let (url, response) = self.get(path).await?;
+        let mut header = header(path, &url, &response);
+        header.size = response.content_length().unwrap_or_default() as usize;
+
+        let chunks = response
+            .bytes_stream()
+            .map_err(|e| ReadError::IO(transport_error(e)))
+            .map_ok(move |bytes| {
+                stream::iter(split(bytes.to_vec(), chunk_size).into_iter().map(Ok))
+            })
+            .try_flatten();
+
+        Ok(ChunkedRecord::new(header, chunks.boxed()))
+    }
+
+    async fn find(&self, path: &Path) -> Result<Vec<Record>, ReadError> {
+        match self.find_one(path).await {
+            Ok(record) => Ok(vec![record]),
+            Err(ReadError::IO(e)) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+            Err(e) => Err(e),
+        }
+    }
+
+    async fn create(&self, record: Record) -> Result<(), WriteError> {
+        let exists = || WriteError::Custom(format!("resource already exists: {}", record.path));
+
+        if self.present(&record.path).await? {
+            return Err(exists());
+        }
+
+        // Servers honoring the precondition reject a concurrent create
+        let response = self
+            .write(&record, Some((IF_NONE_MATCH.as_str(), "*")))
+            .await?;
+
+        match response.status() {
+            status if status.is_success() => Ok(()),
+            StatusCode::PRECONDITION_FAILED => Err(exists()),
+            _ => Err(self.write_failed(&response)),
+        }
+    }
+
+    async fn update(&self, record: Record) -> Result<(), WriteError> {
+        let not_found = || WriteError::Custom(format!("resource not found: {}", record.path));
+
+        if !self.present(&record.path).await? {
+            return Err(not_found());
+        }
+
+        let response = self.write(&record, Some((IF_MATCH.as_str(), "*"))).await?;
+
+        match response.status() {
+            status if status.is_success() => Ok(()),
+            StatusCode::PRECONDITION_FAILED => Err(not_found()),
+            _ => Err(self.write_failed(&response)),
+        }
+    }
+
+    async fn upsert(&self, record: Record) -> Result<(), WriteError> {
+        let response = self.write(&record, None).await?;
+
+        if !response.status().is_success() {
+            return Err(self.write_failed(&response));
+        }
+
+        Ok(())
+    }
+
+    async fn delete(&self, path: &Path) -> Result<(), WriteError> {
+        let url = self.url(path).map_err(WriteError::Custom)?;
+        let response = self
+            .request(Method::DELETE, &url)
+            .send()
+            .await
+            .map_err(|e| WriteError::IO(transport_error(e)))?;
+
+        match response.status() {
+            status if status.is_success() => Ok(()),
+            StatusCode::NOT_FOUND | StatusCode::GONE => {
+                Err(WriteError::Custom(format!("resource not found: {}", path)))
+            }
+            status => Err(status_error("DELETE", &url, status, WriteError::Custom)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+    use crate::path::{FilePath, UriPath};
+
+    fn uri(url: &str) -> Path {
+        Path::Uri(UriPath::parse(url).unwrap())
+    }
+
+    /// A request the mock server received: the request line, lowercased
+    /// header lines and the body
+    #[derive(Debug, Clone)]
+    struct Received {
+        line: String,
+        headers: Vec<String>,
+        body: String,
+    }
+
+    impl Received {
+        fn has_header(&self, header: &str) -> bool {
+            self.headers.iter().any(|h| h == header)
+        }
+    }
+
+    /// Serve `/data` on a local port from a background thread, one request
+    /// per connection. Requests without the bearer token `secret` get a 401;
+    /// the rest are answered by path:
+    /// - `samples.json`: exists, read as JSON
+    /// - `taken.json`: missing, but writes fail their precondition as if
+    ///   created concurrently
+    /// - `gone.json`: exists, but writes fail their precondition as if
+    ///   deleted concurrently
+    /// - anything else: missing, writes succeed
+    fn serve() -> (String, Arc<Mutex<Vec<Received>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/data", listener.local_addr().unwrap());
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let log = received.clone();
+
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(mut stream) = stream else { break };
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+
+                let mut headers = Vec::new();
+                loop {
+                    let mut header = String::new();
+                    reader.read_line(&mut header).unwrap();
+
+                    match header.trim_end() {
+                        "" => break,
+                        header => headers.push(header.to_lowercase()),
+                    }
+                }
+
+                let length = headers
+                    .iter()
+                    .find_map(|h| h.strip_prefix("content-length: "))
+                    .map_or(0, |n| n.parse().unwrap());
+                let mut body = vec![0; length];
+                reader.read_exact(&mut body).unwrap();
+
+                let request = Received {
+                    line: line.trim_end().to_string(),
+                    headers,
+                    body: String::from_utf8(body).unwrap(),
+                };
+                let mut parts = request.line.split(' ');
+                let method = parts.next().unwrap().to_string();
+                let path = parts.next().unwrap().to_string();
+                let authorized = request.has_header("authorization: bearer secret");
+                log.lock().unwrap().push(request);
+
+                let (status, body) = match (method.as_str(), path.as_str()) {
+                    _ if !authorized => ("401 Unauthorized", ""),
+                    ("GET" | "HEAD", "/data/samples.json" | "/data/gone.json") => {
+                        ("200 OK", r#"{"score":0.5}"#)
+                    }
+                    ("GET" | "HEAD", _) => ("404 Not Found", ""),
+                    (_, "/data/taken.json" | "/data/gone.json") => ("412 Precondition Failed", ""),
+                    _ => ("204 No Content", ""),
+                };
+                let body = if method == "HEAD" { "" } else { body };
+
+                write!(
+                    stream,
+                    "HTTP/1.1 {}\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                )
+                .unwrap();
+            }
+        });
+
+        (url, received)
+    }
+
+    fn source(url: &str) -> HttpSource {
+        HttpSource::builder()
+            .base_url(url)
+            .bearer_auth("secret")
+            .timeout(Duration::from_secs(5))
+            .build()
+    }
+
+    fn file(path: &str) -> Path {
+        Path::File(FilePath::parse(path))
+    }
+
+    #[test]
+    fn test_builder() {
+        let ds = HttpSource::builder()
+            .name("api")
+            .base_url("https://example.com/data/")
+            .header("X-Api-Version", "2")
+            .bearer_auth("secret")
+            .write_method(WriteMethod::Post)
+            .timeout(Duration::from_secs(5))
+            .build();
+
+        assert_eq!(ds.config().name(), "api");
+        assert_eq!(ds.config().base_url(), Some("https://example.com/data/"));
+        assert_eq!(
+            ds.config().headers(),
+            &[("X-Api-Version".to_string(), "2".to_string())]
+        );
+        assert!(matches!(ds.config().auth(), Some(HttpAuth::Bearer(_))));
+        assert_eq!(ds.config().write_method(), WriteMethod::Post);
+        assert_eq!(ds.config().timeout(), Some(Duration::from_secs(5)));
+    }
+
+    #[test]
+    fn test_builder_defaults() {
+        let ds = HttpSource::builder().build();
+
+        assert_eq!(ds.config().name(), "http");
+        assert_eq!(ds.config().base_url(), None);
+        assert!(ds.config().headers().is_empty());
+        assert!(ds.config().auth().is_none());
+        assert_eq!(ds.config().write_method(), WriteMethod::Put);
+        assert_eq!(ds.config().timeout(), None);
+    }
+
+    #[test]
+    fn test_auth_debug_hides_secrets() {
+        let bearer = format!("{:?}", HttpAuth::Bearer("secret".to_string()));
+        let basic = format!(
+            "{:?}",
+            HttpAuth::Basic {
+                username: "user".to_string(),
+                password: Some("secret".to_string()),
+            }
+        );
+
+        assert!(!bearer.contains("secret"));
+        assert!(!basic.contains("secret"));
+        assert!(basic.contains("user"));
+    }
+
+    #[test]
+    fn test_url_from_uri() {
+        let ds = HttpSource::default();
+
+        assert_eq!(
+            ds.url(&uri("https://example.com/config.yaml")).unwrap(),
+            "https://example.com/config.yaml"
+        );
+        assert!(ds.url(&uri("s3://bucket/config.yaml")).is_err());
+    }
+
+    #[test]
+    fn test_url_from_relative_file() {
+        let ds = HttpSource::builder()
+            .base_url("https://example.com/data/")
+            .build();
+        let path = Path::File(FilePath::parse("/samples.json"));
+
+        assert_eq!(
+            ds.url(&path).unwrap(),
+            "https://example.com/data/samples.json"
+        );
+        assert!(HttpSource::default().url(&path).is_err());
+    }
+
+    #[test]
+    fn test_media_type_from_content_type() {
+        assert_eq!(
+            media_type(Some("application/json; charset=utf-8"), "https://x.io/a"),
+            MediaType::TextJson
+        );
+        assert_eq!(
+            media_type(Some("application/yaml"), "https://x.io/a.json"),
+            MediaType::TextYaml
+        );
+    }
+
+    #[test]
+    fn test_media_type_falls_back_to_extension() {
+        assert_eq!(
+            media_type(Some("application/octet-stream"), "https://x.io/a.yaml?v=1"),
+            MediaType::TextYaml
+        );
+        assert_eq!(
+            media_type(None, "https://x.io/data.ndjson"),
+            MediaType::TextNdjson
+        );
+        assert_eq!(
+            media_type(Some("text/x-custom"), "https://x.io/data"),
+            MediaType::Text
+        );
+    }
+
+    #[test]
+    fn test_headers_attribute() {
+        let mut map = reqwest::header::HeaderMap::new();
+        map.insert(CONTENT_TYPE, "application/json".parse().unwrap());
+        map.append("Link", "</a>; rel=next".parse().unwrap());
+        map.append("Link", "</b>; rel=last".parse().unwrap());
+        map.append("Set-Cookie", "session=1".parse().unwrap());
+        map.insert("Authorization", "Bearer token".parse().unwrap());
+        map.insert("Proxy-Authorization", "Basic abc".parse().unwrap());
+        map.insert("Cookie", "a=1".parse().unwrap());
+
+        let Value::Object(headers) = headers(&map) else {
+            panic!("expected an object");
+        };
+
+        assert_eq!(
+            headers.get("content-type"),
+            Some(&Value::from("application/json"))
+        );
+        assert_eq!(
+            headers.get("link"),
+            Some(&Value::from("</a>; rel=next, </b>; rel=last"))
+        );
+        assert!(headers.get("set-cookie").is_none());
+        assert!(headers.get("authorization").is_none());
+        assert!(headers.get("proxy-authorization").is_none());
+        assert!(headers.get("cookie").is_none());
+    }
+
+    #[test]
+    fn test_status_error_transience() {
+        let error = status_error(
+            "GET",
+            "https://x.io/a",
+            StatusCode::SERVICE_UNAVAILABLE,
+            ReadError::Custom,
+        );
+        assert!(error.is_transient());
+
+        let error = status_error(
+            "GET",
+            "https://x.io/a",
+            StatusCode::FORBIDDEN,
+            ReadError::Custom,
+        );
+        assert!(error.is_custom());
+        assert!(!error.is_transient());
+    }
+
+    #[tokio::test]
+    async fn test_health_without_base_url() {
+        let ds = HttpSource::default();
+
+        assert!(ds.health().await.is_ok());
+        assert!(!ds.capabilities().list);
+    }
+
+    #[tokio::test]
+    async fn test_find_one_unsupported_path() {
+        let ds = HttpSource::default();
+        let path = Path::File(FilePath::parse("config.yaml"));
+
+        let result = ds.find_one(&path).await;
+        assert!(result.unwrap_err().is_custom());
+    }
+
+    #[tokio::test]
+    async fn test_find_one_from_server() {
+        let (url, received) = serve();
+        let ds = source(&url);
+
+        let record = ds.find_one(&file("samples.json")).await.unwrap();
+
+        assert_eq!(record.content_str().unwrap(), r#"{"score":0.5}"#);
+        assert_eq!(record.media_type, MediaType::TextJson);
+        assert_eq!(record.attribute("status"), Some(&Value::from(200u16)));
+
+        let received = received.lock().unwrap();
+        assert_eq!(received[0].line, "GET /data/samples.json HTTP/1.1");
+        assert!(received[0].has_header("authorization: bearer secret"));
+    }
+
+    #[tokio::test]
+    async fn test_missing_resource_is_not_found() {
+        let (url, _) = serve();
+        let ds = source(&url);
+
+        let err = ds.find_one(&file("missing.json")).await.unwrap_err();
+        assert!(matches!(err, ReadError::IO(e) if e.kind() == std::io::ErrorKind::NotFound));
+        assert!(ds.find(&file("missing.json")).await.unwrap().is_empty());
+        assert!(!ds.exists(&file("missing.json")).await.unwrap());
+        assert!(ds.exists(&file("samples.json")).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_missing_credentials_are_rejected() {
+        let (url, _) = serve();
+        let ds = HttpSource::builder().base_url(&url).build();
+
+        let err = ds.find_one(&file("samples.json")).await.unwrap_err();
+        assert!(err.is_custom());
+        assert!(err.to_string().contains("401"));
+    }
+
+    #[tokio::test]
+    async fn test_upsert_puts_content() {
+        let (url, received) = serve();
+        let ds = source(&url);
+        let record = Record::from_str(file("new.json"), MediaType::TextJson, r#"{"a":1}"#);
+
+        ds.upsert(record).await.unwrap();
+
+        let received = received.lock().unwrap();
+        assert_eq!(received[0].line, "PUT /data/new.json HTTP/1.1");
+        assert!(received[0].has_header("content-type: application/json"));
+        assert!(received[0].has_header("authorization: bearer secret"));
+        assert_eq!(received[0].body, r#"{"a":1}"#);
+    }
+
+    #[tokio::test]
+    async fn test_create_sends_precondition() {
+        let (url, received) = serve();
+        let ds = source(&url);
+        let record = Record::from_str(file("new.json"), MediaType::TextJson, "{}");
+
+        ds.create(record).await.unwrap();
+
+        let received = received.lock().unwrap();
+        assert_eq!(received[0].line, "HEAD /data/new.json HTTP/1.1");
+        assert_eq!(received[1].line, "PUT /data/new.json HTTP/1.1");
+        assert!(received[1].has_header("if-none-match: *"));
+    }
+
+    #[tokio::test]
+    async fn test_failed_precondition_mapping() {
+        let (url, _) = serve();
+        let ds = source(&url);
+
+        let taken = Record::from_str(file("taken.json"), MediaType::TextJson, "{}");
+        let err = ds.create(taken).await.unwrap_err();
+        assert!(err.to_string().contains("already exists"));
+
+        let gone = Record::from_str(file("gone.json"), MediaType::TextJson, "{}");
+        let err = ds.update(gone).await.unwrap_err();
+        assert!(err.to_string().contains("not found"));
+
+        let existing = Record::from_str(file("samples.json"), MediaType::TextJson, "{}");
+        let err = ds.create(existing).await.unwrap_err();
+        assert!(err.to_string().contains("already exists"));
+    }
+}
@@ -0,0 +1,272 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use futures::stream::BoxStream;
+use loom_signal::{Emitter, Level, Span};
+
+use crate::path::{Path, Scheme};
+use crate::{
+    Capabilities, ChunkedRecord, Cursor, DataSource, ETag, ReadError, Record, WatchSource,
+    WriteError,
+};
+
+/// Wraps a [`DataSource`] to emit a span [`Signal`](loom_signal::Signal) for
+/// every read and write, so IO latency shows up next to the rest of a run's
+/// telemetry.
+///
+/// Reads are named `io.read` and writes `io.write`, at `Debug` level (`Error`
+/// on failure). Each carries the `source` name, the `op` (e.g. `find_one`,
+/// `upsert`), the `path`, the `outcome` (`ok` or `error`) and `duration_ms`;
+/// successful ones add the `records` and `bytes` read or written, failed ones
+/// the `error`.
+///
+/// [`find_one_chunked`](DataSource::find_one_chunked) is timed up to the
+/// opening of the record, reporting the size its header announces.
+/// [`find_stream`](DataSource::find_stream) and [`watch`](WatchSource::watch)
+/// are passed through uninstrumented, as they have no single duration.
+pub struct InstrumentedSource<S> {
+    inner: S,
+    emitter: Arc<dyn Emitter + Send + Sync>,
+}
+
+impl<S: DataSource> InstrumentedSource<S> {
+    pub fn new(inner: S, emitter: Arc<dyn Emitter + Send + Sync>) -> Self {
+        Self { inner, emitter }
+    }
+
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+
+    fn span(&self, name: &str, op: &str, path: &Path) -> Span {
+        Span::new(name)
+            .with_level(Level::Debug)
+            .with_attr("source", self.inner.name())
+            .with_attr("op", op)
+            .with_attr("path", path.to_string())
+    }
+
+    /// Emit `span` for `result`, sized by `size` as `(records, bytes)`
+    fn finish<T, E: std::fmt::Display>(
+        &self,
+        span: Span,
+        result: &Result<T, E>,
+        size: impl FnOnce(&T) -> (usize, usize),
+    ) {
+        let signal = match result {
+            Ok(value) => {
+                let (records, bytes) = size(value);
+
+                span.with_attr("outcome", "ok")
+                    .with_attr("records", records)
+                    .with_attr("bytes", bytes)
+                    .finish()
+            }
+            Err(err) => span
+                .with_attr("outcome", "error")
+                .finish_with_error(err.to_string()),
+        };
+
+        self.emitter.emit(signal);
+    }
+
+    /// Span for writing `record`, and the bytes it writes
+    fn write_span(&self, op: &str, record: &Record) -> (Span, usize) {
+        (
+            self.span("io.write", op, &record.path),
+            record.content.len(),
+        )
+    }
+}
+
+/// Total content size of `records`
+fn bytes(records: &[Record]) -> usize {
+    records.iter().map(|record| record.content.len()).sum()
+}
+
+#[async_trait]
+impl<S: DataSource> DataSource for InstrumentedSource<S> {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn schemes(&self) -> &[Scheme] {
+        self.inner.schemes()
+    }
+
+    fn as_watch(&self) -> Option<&dyn WatchSource> {
+        self.inner.as_watch()
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        self.inner.capabilities()
+    }
+
+    async fn health(&self) -> Result<(), ReadError> {
+        self.inner.health().await
+    }
+
+    async fn exists(&self, path: &Path) -> Result<bool, ReadError> {
+        let span = self.span("io.read", "exists", path);
+        let result = self.inner.exists(path).await;
+
+        self.finish(span, &result, |_| (0, 0));
+        result
+    }
+
+    async fn count(&self, path: &Path) -> Result<usize, ReadError> {
+        let span = self.span("io.read", "count", path);
+        let result = self.inner.count(path).await;
+
+        self.finish(span, &result, |count| (*count, 0));
+        result
+    }
+
+    async fn find_one(&self, path: &Path) -> Result<Record, ReadError> {
+        let span = self.span("io.read", "find_one", path);
+        let result = self.inner.find_one(path).await;
+
+        self.finish(span, &result, |record| (1, record.content.len()));
+        result
+    }
+
+    async fn find(&self, path: &Path) -> Result<Vec<Record>, ReadError> {
+        let span = self.span("io.read", "find", path);
+        let result = self.inner.find(path).await;
+
+        self.finish(span, &result, |records| (records.len(), bytes(records)));
+        result
+    }
+
+    async fn find_page(
+        &self,
+        path: &Path,
+        cursor: Option<Cursor>,
+        limit: usize,
+    ) -> Result<(Vec<Record>, Option<Cursor>), ReadError> {
+        let span = self.span("io.read", "find_page", path);
+        let result = self.inner.find_page(path, cursor, limit).await;
+
+        self.finish(span, &result, |(records, _)| {
+            (records.len(), bytes(records))
+        });
+        result
+    }
+
+    fn find_stream<'a>(&'a self, path: &'a Path) -> BoxStream<'a, Result<Record, ReadError>> {
+        self.inner.find_stream(path)
+    }
+
+    async fn find_one_chunked<'a>(
+        &'a self,
+        path: &'a Path,
+        chunk_size: usize,
+    ) -> Result<ChunkedRecord<'a>, ReadError> {
+        let span = self.span("io.read", "find_one_chunked", path);
+        let result = self.inner.find_one_chunked(path, chunk_size).await;
+
+        self.finish(span, &result, |chunked| (1, chunked.header.size));
+        result
+    }
+
+    async fn etag(&self, path: &Path) -> Result<Option<ETag>, ReadError> {
+        let span = self.span("io.read", "etag", path);
+        let result = self.inner.etag(path).await;
+
+        self.finish(span, &result, |etag| (usize::from(etag.is_some()), 0));
+        result
+    }
+
+    async fn create(&self, record: Record) -> Result<(), WriteError> {
+        let (span, bytes) = self.write_span("create", &record);
+        let result = self.inner.create(record).await;
+
+        self.finish(span, &result, |_| (1, bytes));
+        result
+    }
+
+    async fn update(&self, record: Record) -> Result<(), WriteError> {
+        let (span, bytes) = self.write_span("update", &record);
+        let result = self.inner.update(record).await;
+
+        self.finish(span, &result, |_| (1, bytes));
+        result
+    }
+
+    async fn update_if(&self, record: Record, expected: &ETag) -> Result<(), WriteError> {
+        let (span, bytes) = self.write_span("update_if", &record);
+        let result = self.inner.update_if(record, expected).await;
+
+        self.finish(span, &result, |_| (1, bytes));
+        result
+    }
+
+    async fn upsert(&self, record: Record) -> Result<(), WriteError> {
+        let (span, bytes) = self.write_span("upsert", &record);
+        let result = self.inner.upsert(record).await;
+
+        self.finish(span, &result, |_| (1, bytes));
+        result
+    }
+
+    async fn delete(&self, path: &Path) -> Result<(), WriteError> {
+        let span = self.span("io.write", "delete", path);
+        let result = self.inner.delete(path).await;
+
+        self.finish(span, &result, |_| (1, 0));
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use loom_signal::consumers::MemoryEmitter;
+
+    use super::*;
+    use crate::sources::MemorySource;
+    use crate::value::Value;
+    use crate::{MediaType, path::FilePath};
+
+    fn path() -> Path {
+        Path::File(FilePath::parse("/test/file.txt"))
+    }
+
+    #[tokio::test]
+    async fn test_emits_reads_and_writes() {
+        let signals = MemoryEmitter::new();
+        let ds = InstrumentedSource::new(MemorySource::default(), Arc::new(signals.clone()));
+
+        ds.upsert(Record::from_str(path(), MediaType::TextPlain, "hello"))
+            .await
+            .unwrap();
+        ds.find_one(&path()).await.unwrap();
+
+        let write = &signals.find_by_name("io.write")[0];
+        assert_eq!(write.attributes()["op"], Value::from("upsert"));
+        assert_eq!(write.attributes()["bytes"], Value::from(5usize));
+        assert_eq!(write.attributes()["outcome"], Value::from("ok"));
+        assert!(write.attributes().exists("duration_ms"));
+
+        let read = &signals.find_by_name("io.read")[0];
+        assert_eq!(read.attributes()["source"], Value::from("memory"));
+        assert_eq!(read.attributes()["path"], Value::from("/test/file.txt"));
+        assert_eq!(read.attributes()["records"], Value::from(1usize));
+    }
+
+    #[tokio::test]
+    async fn test_emits_failures() {
+        let signals = MemoryEmitter::new();
+        let ds = InstrumentedSource::new(MemorySource::default(), Arc::new(signals.clone()));
+
+        assert!(ds.find_one(&path()).await.is_err());
+
+        let read = signals.last().unwrap();
+        assert_eq!(read.level(), Level::Error);
+        assert_eq!(read.attributes()["outcome"], Value::from("error"));
+        assert!(read.attributes().exists("error"));
+    }
+}
@@ -5,7 +5,7 @@ use async_trait::async_trait;
 
 use crate::path::Path;
 
-use crate::{DataSource, Id, ReadError, Record, WriteError};
+use crate::{DataSource, ETag, Id, ReadError, Record, WriteError};
 
 #[derive(Debug, Clone)]
 pub struct MemorySourceConfig {
@@ -180,6 +180,32 @@ impl DataSource for MemorySource {
         Ok(())
     }
 
+    async fn update_if(&self, record: Record, expected: &ETag) -> Result<(), WriteError> {
+        let mut records = self
+            .records
+            .write()
+            .map_err(|e| WriteError::Panic(e.to_string()))?;
+
+        match records.get(&record.id) {
+            None => {
+                return Err(WriteError::Custom(format!(
+                    "record not found: {}",
+                    record.path
+                )));
+            }
+            Some(current) if current.etag != *expected => {
+                return Err(WriteError::PreconditionFailed(format!(
+                    "{} changed since it was read",
+                    record.path
+                )));
+            }
+            Some(_) => {}
+        }
+
+        records.insert(record.id, record);
+        Ok(())
+    }
+
     async fn upsert(&self, record: Record) -> Result<(), WriteError> {
         let mut records = self
             .records
@@ -207,7 +233,7 @@ impl DataSource for MemorySource {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{MediaType, path::FilePath};
+    use crate::{Cursor, MediaType, path::FilePath};
 
     fn make_record(path: &Path) -> Record {
         Record::from_str(path.clone(), MediaType::TextPlain, "hello")
@@ -267,6 +293,45 @@ mod tests {
         assert_eq!(results.len(), 2);
     }
 
+    #[tokio::test]
+    async fn test_find_page_defaults_to_find() {
+        let ds = MemorySource::builder().build();
+        for name in ["c", "a", "b"] {
+            let path = Path::File(FilePath::parse(&format!("/test/{}.txt", name)));
+            ds.create(make_record(&path)).await.unwrap();
+        }
+
+        let test_path = Path::File(FilePath::parse("/test"));
+        let (page, cursor) = ds.find_page(&test_path, None, 2).await.unwrap();
+        let paths: Vec<String> = page.iter().map(|r| r.path.to_string()).collect();
+        assert_eq!(paths, vec!["/test/a.txt", "/test/b.txt"]);
+        assert_eq!(cursor, Some(Cursor::new("/test/b.txt")));
+
+        let (page, cursor) = ds.find_page(&test_path, cursor, 2).await.unwrap();
+        assert_eq!(page[0].path.to_string(), "/test/c.txt");
+        assert_eq!(cursor, None);
+
+        let err = ds.find_page(&test_path, None, 0).await.unwrap_err();
+        assert!(err.is_custom());
+    }
+
+    #[tokio::test]
+    async fn test_find_stream_defaults_to_find() {
+        use futures::TryStreamExt;
+
+        let ds = MemorySource::builder().build();
+        let path1 = Path::File(FilePath::parse("/test/file1.txt"));
+        let path2 = Path::File(FilePath::parse("/other/file.txt"));
+
+        ds.create(make_record(&path1)).await.unwrap();
+        ds.create(make_record(&path2)).await.unwrap();
+
+        let test_path = Path::File(FilePath::parse("/test"));
+        let results: Vec<Record> = ds.find_stream(&test_path).try_collect().await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].path, path1);
+    }
+
     #[tokio::test]
     async fn test_create_duplicate_fails() {
         let ds = MemorySource::builder().build();
@@ -288,6 +353,22 @@ mod tests {
         ds.update(record).await.unwrap();
     }
 
+    #[tokio::test]
+    async fn test_update_if() {
+        let ds = MemorySource::builder().build();
+        let path = Path::File(FilePath::parse("/test/file.txt"));
+        let record = make_record(&path);
+        let etag = record.etag;
+
+        ds.create(record).await.unwrap();
+
+        let changed = Record::from_str(path.clone(), MediaType::TextPlain, "changed");
+        ds.update_if(changed.clone(), &etag).await.unwrap();
+
+        let err = ds.update_if(changed, &etag).await.unwrap_err();
+        assert!(err.is_precondition_failed());
+    }
+
     #[tokio::test]
     async fn test_update_not_found() {
         let ds = MemorySource::builder().build();
@@ -1,5 +1,23 @@
 mod file_system_source;
+#[cfg(feature = "http")]
+mod http_source;
+mod instrumented_source;
 mod memory_source;
+#[cfg(feature = "redis")]
+mod redis_source;
+mod retry_source;
+#[cfg(feature = "sftp")]
+mod sftp_source;
+mod std_stream_source;
 
 pub use file_system_source::*;
+#[cfg(feature = "http")]
+pub use http_source::*;
+pub use instrumented_source::*;
 pub use memory_source::*;
+#[cfg(feature = "redis")]
+pub use redis_source::*;
+pub use retry_source::*;
+#[cfg(feature = "sftp")]
+pub use sftp_source::*;
+pub use std_stream_source::*;
@@ -0,0 +1,563 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use redis::aio::ConnectionManager;
+use tokio::sync::OnceCell;
+
+use crate::MediaType;
+use crate::path::{FilePath, Path, Scheme, UriPath};
+
+use crate::{DataSource, ETag, ReadError, Record, WriteError};
+
+/// Keys scanned per `SCAN` round trip
+const SCAN_COUNT: usize = 100;
+
+/// Writes a record's hash unless the key's state rules it out, then sets or
+/// clears its expiry, all in one step so concurrent writers can't interleave.
+///
+/// `KEYS[1]` is the record's key; `ARGV` is the mode (`create`, `update`,
+/// `update_if` or `upsert`), the expected ETag, the media type, the content,
+/// the new ETag and the TTL in milliseconds (`0` for none).
+const WRITE_SCRIPT: &str = r#"
+local current = redis.call('HGET', KEYS[1], 'etag')
+local mode = ARGV[1]
+
+if mode == 'create' and current then
+    return 'exists'
+end
+if (mode == 'update' or mode == 'update_if') and not current then
+    return 'missing'
+end
+if mode == 'update_if' and current ~= ARGV[2] then
+    return 'changed'
+end
+
+redis.call('HSET', KEYS[1], 'media_type', ARGV[3], 'content', ARGV[4], 'etag', ARGV[5])
+
+if tonumber(ARGV[6]) > 0 then
+    redis.call('PEXPIRE', KEYS[1], ARGV[6])
+else
+    redis.call('PERSIST', KEYS[1])
+end
+
+return 'ok'
+"#;
+
+#[derive(Debug, Clone)]
+pub struct RedisSourceConfig {
+    name: String,
+    url: String,
+    prefix: String,
+    ttl: Option<Duration>,
+}
+
+impl RedisSourceConfig {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Server to connect to, e.g. `redis://127.0.0.1:6379/0`
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Prepended to every path to form its key
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    /// Expiry set on every write, or `None` to keep records until deleted
+    pub fn ttl(&self) -> Option<Duration> {
+        self.ttl
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RedisSourceBuilder {
+    name: Option<String>,
+    url: Option<String>,
+    prefix: String,
+    ttl: Option<Duration>,
+}
+
+impl RedisSourceBuilder {
+    pub fn new() -> Self {
+        Self {
+            name: None,
+            url: None,
+            prefix: String::new(),
+            ttl: None,
+        }
+    }
+
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Connect to `url` (default: `redis://127.0.0.1:6379`)
+    pub fn url(mut self, url: impl Into<String>) -> Self {
+        self.url = Some(url.into());
+        self
+    }
+
+    /// Namespace keys under `prefix`, e.g. `merc:scores:`
+    pub fn prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    /// Expire records `ttl` after they are written
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    pub fn build(self) -> RedisSource {
+        RedisSource {
+            config: RedisSourceConfig {
+                name: self.name.unwrap_or_else(|| "redis".to_string()),
+                url: self
+                    .url
+                    .unwrap_or_else(|| "redis://127.0.0.1:6379".to_string()),
+                prefix: self.prefix,
+                ttl: self.ttl,
+            },
+            connection: OnceCell::new(),
+        }
+    }
+}
+
+impl Default for RedisSourceBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Data source over a Redis server (`redis` feature).
+///
+/// Each record is a hash at the key `<prefix><path>` holding its media type,
+/// content and ETag, so conditional writes compare and replace atomically on
+/// the server. [`find`](DataSource::find) and [`count`](DataSource::count)
+/// cover every key starting with the path's key. Records can expire: the
+/// configured [`ttl`](RedisSourceBuilder::ttl) applies to every write, and
+/// [`upsert_with_ttl`](RedisSource::upsert_with_ttl) overrides it for one.
+///
+/// The connection is opened on first use and re-established if it drops.
+pub struct RedisSource {
+    config: RedisSourceConfig,
+    connection: OnceCell<ConnectionManager>,
+}
+
+impl RedisSource {
+    pub fn builder() -> RedisSourceBuilder {
+        RedisSourceBuilder::new()
+    }
+
+    pub fn config(&self) -> &RedisSourceConfig {
+        &self.config
+    }
+
+    /// Like [`upsert`](DataSource::upsert), expiring the record after `ttl`
+    /// (or never, if `None`) instead of the configured TTL
+    pub async fn upsert_with_ttl(
+        &self,
+        record: Record,
+        ttl: Option<Duration>,
+    ) -> Result<(), WriteError> {
+        self.write("upsert", &record, None, ttl).await
+    }
+
+    fn key(&self, path: &Path) -> String {
+        format!("{}{}", self.config.prefix, path)
+    }
+
+    /// Path of the record at `key`, shaped like `like` (the path it was
+    /// found under)
+    fn path(&self, key: &str, like: &Path) -> Path {
+        let path = key.strip_prefix(&self.config.prefix).unwrap_or(key);
+
+        match like {
+            Path::Uri(_) => UriPath::parse(path)
+                .map(Path::Uri)
+                .unwrap_or_else(|_| Path::File(FilePath::parse(path))),
+            _ => Path::File(FilePath::parse(path)),
+        }
+    }
+
+    async fn connection(&self) -> Result<ConnectionManager, std::io::Error> {
+        self.connection
+            .get_or_try_init(|| async {
+                let client = redis::Client::open(self.config.url.as_str()).map_err(redis_error)?;
+                ConnectionManager::new(client).await.map_err(redis_error)
+            })
+            .await
+            .cloned()
+    }
+
+    /// Keys starting with the key of `path`
+    async fn keys(&self, path: &Path) -> Result<Vec<String>, ReadError> {
+        let mut connection = self.connection().await?;
+        let pattern = format!("{}*", escape(&self.key(path)));
+        let mut cursor = 0u64;
+        let mut keys = Vec::new();
+
+        loop {
+            let (next, batch): (u64, Vec<String>) = redis::cmd("SCAN")
+                .arg(cursor)
+                .arg("MATCH")
+                .arg(&pattern)
+                .arg("COUNT")
+                .arg(SCAN_COUNT)
+                .query_async(&mut connection)
+                .await
+                .map_err(redis_error)?;
+
+            keys.extend(batch);
+            cursor = next;
+
+            if cursor == 0 {
+                break;
+            }
+        }
+
+        // SCAN may return a key more than once
+        keys.sort();
+        keys.dedup();
+        Ok(keys)
+    }
+
+    /// Record at `key`, or `None` if it is missing or expired
+    async fn read(&self, key: &str, path: Path) -> Result<Option<Record>, ReadError> {
+        let mut connection = self.connection().await?;
+        let (media_type, content): (Option<String>, Option<Vec<u8>>) = redis::cmd("HMGET")
+            .arg(key)
+            .arg("media_type")
+            .arg("content")
+            .query_async(&mut connection)
+            .await
+            .map_err(redis_error)?;
+
+        let Some(content) = content else {
+            return Ok(None);
+        };
+
+        let media_type = match media_type {
+            Some(mime) => MediaType::from_mime_str(&mime),
+            None => MediaType::from_path(&path.to_string()),
+        };
+
+        Ok(Some(Record::new(path, media_type, content)))
+    }
+
+    /// Run [`WRITE_SCRIPT`] for `record`, expiring it after `ttl`
+    async fn write(
+        &self,
+        mode: &str,
+        record: &Record,
+        expected: Option<&ETag>,
+        ttl: Option<Duration>,
+    ) -> Result<(), WriteError> {
+        let mut connection = self.connection().await?;
+        let outcome: String = redis::Script::new(WRITE_SCRIPT)
+            .key(self.key(&record.path))
+            .arg(mode)
+            .arg(
+                expected
+                    .map(|etag| etag.as_bytes().as_slice())
+                    .unwrap_or_default(),
+            )
+            .arg(record.media_type.as_mime_str())
+            .arg(record.content.as_slice())
+            .arg(record.etag.as_bytes().as_slice())
+            .arg(ttl.map(|ttl| ttl.as_millis().max(1) as u64).unwrap_or(0))
+            .invoke_async(&mut connection)
+            .await
+            .map_err(redis_error)?;
+
+        match outcome.as_str() {
+            "ok" => Ok(()),
+            "exists" => Err(WriteError::Custom(format!(
+                "record already exists: {}",
+                record.path
+            ))),
+            "missing" => Err(WriteError::Custom(format!(
+                "record not found: {}",
+                record.path
+            ))),
+            "changed" => Err(WriteError::PreconditionFailed(format!(
+                "{} changed since it was read",
+                record.path
+            ))),
+            other => Err(WriteError::Custom(format!(
+                "unexpected write outcome '{}' for {}",
+                other, record.path
+            ))),
+        }
+    }
+}
+
+impl Default for RedisSource {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+/// `key` with the `SCAN MATCH` wildcards escaped, so it matches literally
+fn escape(key: &str) -> String {
+    let mut escaped = String::with_capacity(key.len());
+
+    for c in key.chars() {
+        if matches!(c, '*' | '?' | '[' | ']' | '\\') {
+            escaped.push('\\');
+        }
+
+        escaped.push(c);
+    }
+
+    escaped
+}
+
+/// Redis failures as IO errors whose kind tells timeouts and dropped
+/// connections apart from other failures
+fn redis_error(err: redis::RedisError) -> std::io::Error {
+    let kind = if err.is_timeout() {
+        std::io::ErrorKind::TimedOut
+    } else if err.is_connection_refusal() {
+        std::io::ErrorKind::ConnectionRefused
+    } else if err.is_connection_dropped() {
+        std::io::ErrorKind::ConnectionReset
+    } else {
+        std::io::ErrorKind::Other
+    };
+
+    std::io::Error::new(kind, err)
+}
+
+#[async_trait]
+impl DataSource for RedisSource {
+    fn name(&self) -> &str {
+        &self.config.name
+    }
+
+    fn schemes(&self) -> &[Scheme] {
+        &[Scheme::Redis, Scheme::Rediss]
+    }
+
+    /// Sends a `PING`
+    async fn health(&self) -> Result<(), ReadError> {
+        let mut connection = self.connection().await?;
+        let _: String = redis::cmd("PING")
+            .query_async(&mut connection)
+            .await
+            .map_err(redis_error)?;
+
+        Ok(())
+    }
+
+    async fn exists(&self, path: &Path) -> Result<bool, ReadError> {
+        let mut connection = self.connection().await?;
+        let exists: bool = redis::cmd("EXISTS")
+            .arg(self.key(path))
+            .query_async(&mut connection)
+            .await
+            .map_err(redis_error)?;
+
+        Ok(exists)
+    }
+
+    async fn count(&self, path: &Path) -> Result<usize, ReadError> {
+        Ok(self.keys(path).await?.len())
+    }
+
+    async fn find_one(&self, path: &Path) -> Result<Record, ReadError> {
+        match self.read(&self.key(path), path.clone()).await? {
+            Some(record) => Ok(record),
+            // Same shape as a missing file, so callers can check the kind
+            None => Err(ReadError::IO(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("record not found: {}", path),
+            ))),
+        }
+    }
+
+    async fn find(&self, path: &Path) -> Result<Vec<Record>, ReadError> {
+        let mut records = Vec::new();
+
+        for key in self.keys(path).await? {
+            // Keys may expire between the scan and the read
+            if let Some(record) = self.read(&key, self.path(&key, path)).await? {
+                records.push(record);
+            }
+        }
+
+        Ok(records)
+    }
+
+    async fn create(&self, record: Record) -> Result<(), WriteError> {
+        self.write("create", &record, None, self.config.ttl).await
+    }
+
+    async fn update(&self, record: Record) -> Result<(), WriteError> {
+        self.write("update", &record, None, self.config.ttl).await
+    }
+
+    async fn update_if(&self, record: Record, expected: &ETag) -> Result<(), WriteError> {
+        self.write("update_if", &record, Some(expected), self.config.ttl)
+            .await
+    }
+
+    async fn upsert(&self, record: Record) -> Result<(), WriteError> {
+        self.write("upsert", &record, None, self.config.ttl).await
+    }
+
+    async fn delete(&self, path: &Path) -> Result<(), WriteError> {
+        let mut connection = self.connection().await?;
+        let deleted: usize = redis::cmd("DEL")
+            .arg(self.key(path))
+            .query_async(&mut connection)
+            .await
+            .map_err(redis_error)?;
+
+        if deleted == 0 {
+            return Err(WriteError::Custom(format!("record not found: {}", path)));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builder() {
+        let ds = RedisSource::builder()
+            .name("staging")
+            .url("redis://cache:6379/1")
+            .prefix("merc:scores:")
+            .ttl(Duration::from_secs(60))
+            .build();
+
+        assert_eq!(ds.config().name(), "staging");
+        assert_eq!(ds.config().url(), "redis://cache:6379/1");
+        assert_eq!(ds.config().prefix(), "merc:scores:");
+        assert_eq!(ds.config().ttl(), Some(Duration::from_secs(60)));
+    }
+
+    #[test]
+    fn test_builder_defaults() {
+        let ds = RedisSource::builder().build();
+
+        assert_eq!(ds.config().name(), "redis");
+        assert_eq!(ds.config().url(), "redis://127.0.0.1:6379");
+        assert_eq!(ds.config().prefix(), "");
+        assert_eq!(ds.config().ttl(), None);
+    }
+
+    #[test]
+    fn test_keys_round_trip_paths() {
+        let ds = RedisSource::builder().prefix("merc:").build();
+        let path = Path::File(FilePath::parse("scores/batch-1.json"));
+        let key = ds.key(&path);
+
+        assert_eq!(key, "merc:scores/batch-1.json");
+        assert_eq!(ds.path(&key, &path), path);
+    }
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(escape("scores/batch-1.json"), "scores/batch-1.json");
+        assert_eq!(escape("a*b?[c]\\"), "a\\*b\\?\\[c\\]\\\\");
+    }
+
+    /// Source on the server at `REDIS_URL`, under a prefix of its own so
+    /// tests don't see each other's keys
+    fn server() -> RedisSource {
+        let url = std::env::var("REDIS_URL").expect("REDIS_URL is set");
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+
+        RedisSource::builder()
+            .url(url)
+            .prefix(format!("loom-test:{}:", nanos))
+            .build()
+    }
+
+    fn record(path: &str, content: &str) -> Record {
+        Record::from_str(
+            Path::File(FilePath::parse(path)),
+            MediaType::TextJson,
+            content,
+        )
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a Redis server at REDIS_URL"]
+    async fn test_round_trip() {
+        let ds = server();
+        let path = Path::File(FilePath::parse("scores/a.json"));
+
+        ds.health().await.unwrap();
+        ds.create(record("scores/a.json", r#"{"a":1}"#))
+            .await
+            .unwrap();
+        ds.create(record("scores/b.json", r#"{"b":2}"#))
+            .await
+            .unwrap();
+
+        let found = ds.find_one(&path).await.unwrap();
+        assert_eq!(found.content_str().unwrap(), r#"{"a":1}"#);
+        assert_eq!(found.media_type, MediaType::TextJson);
+        assert_eq!(
+            ds.count(&Path::File(FilePath::parse("scores/")))
+                .await
+                .unwrap(),
+            2
+        );
+
+        let err = ds.create(record("scores/a.json", "{}")).await.unwrap_err();
+        assert!(err.to_string().contains("already exists"));
+
+        ds.delete(&path).await.unwrap();
+        assert!(!ds.exists(&path).await.unwrap());
+        assert!(ds.delete(&path).await.is_err());
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a Redis server at REDIS_URL"]
+    async fn test_update_if() {
+        let ds = server();
+        let original = record("a.json", r#"{"a":1}"#);
+        let etag = original.etag;
+        ds.create(original).await.unwrap();
+
+        ds.update_if(record("a.json", r#"{"a":2}"#), &etag)
+            .await
+            .unwrap();
+
+        let err = ds
+            .update_if(record("a.json", r#"{"a":3}"#), &etag)
+            .await
+            .unwrap_err();
+        assert!(err.is_precondition_failed());
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a Redis server at REDIS_URL"]
+    async fn test_ttl_expires_records() {
+        let ds = server();
+        let path = Path::File(FilePath::parse("a.json"));
+
+        ds.upsert_with_ttl(record("a.json", "{}"), Some(Duration::from_millis(50)))
+            .await
+            .unwrap();
+        assert!(ds.exists(&path).await.unwrap());
+
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        assert!(!ds.exists(&path).await.unwrap());
+    }
+}
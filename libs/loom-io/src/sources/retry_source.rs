@@ -0,0 +1,389 @@
+use std::hash::{BuildHasher, RandomState};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use futures::stream::BoxStream;
+
+use crate::path::{Path, Scheme};
+use crate::{
+    Capabilities, ChunkedRecord, Cursor, DataSource, ETag, ReadError, Record, WatchSource,
+    WriteError,
+};
+
+/// How a [`RetrySource`] spaces its retries.
+///
+/// Retry `n` (0-based) waits `backoff * factor^n`, capped at `max_backoff`,
+/// then shortened by a random share of up to `jitter` so clients that failed
+/// together don't retry together.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    /// Retries per operation before giving up
+    pub max_retries: usize,
+
+    /// Delay before the first retry
+    pub backoff: Duration,
+
+    /// Multiplier applied to the delay on each retry
+    pub factor: f32,
+
+    /// Longest delay between retries
+    pub max_backoff: Duration,
+
+    /// Share of each delay, in `[0, 1]`, that may be randomly cut
+    pub jitter: f32,
+}
+
+impl RetryPolicy {
+    /// Delay before retry number `retry` (0-based), without jitter
+    pub fn delay(&self, retry: usize) -> Duration {
+        let delay = self.backoff.as_secs_f64() * (self.factor.max(1.0) as f64).powi(retry as i32);
+        Duration::from_secs_f64(delay.min(self.max_backoff.as_secs_f64()))
+    }
+
+    /// Delay before retry number `retry` (0-based), with jitter applied
+    pub fn jittered_delay(&self, retry: usize) -> Duration {
+        let jitter = self.jitter.clamp(0.0, 1.0) as f64;
+        let random = RandomState::new().hash_one(retry) as f64 / u64::MAX as f64;
+
+        self.delay(retry).mul_f64(1.0 - jitter * random)
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            backoff: Duration::from_millis(500),
+            factor: 2.0,
+            max_backoff: Duration::from_secs(30),
+            jitter: 0.5,
+        }
+    }
+}
+
+/// Wraps a [`DataSource`] to retry its failed reads and writes.
+///
+/// Failures are classified by [`ReadError::is_transient`] and
+/// [`WriteError::is_transient`] unless overridden: transient ones are retried
+/// per the [`RetryPolicy`], terminal ones (a missing file, a failed
+/// precondition) are returned at once. Writes are retried as is, so `create`
+/// may fail on retry if the first attempt landed before its error.
+///
+/// [`find_stream`](DataSource::find_stream), [`watch`](WatchSource::watch)
+/// and the chunks of [`find_one_chunked`](DataSource::find_one_chunked) are
+/// passed through without retries, as they may have already yielded items
+/// when they fail.
+///
+/// A loom `Runtime` already retries its sources under its `io` config, so
+/// wrapping a source registered there stacks the two retry loops; disable one
+/// of them (e.g. `io.max_retries: 0`).
+pub struct RetrySource<S> {
+    inner: S,
+    policy: RetryPolicy,
+    retry_read: fn(&ReadError) -> bool,
+    retry_write: fn(&WriteError) -> bool,
+}
+
+impl<S: DataSource> RetrySource<S> {
+    pub fn new(inner: S, policy: RetryPolicy) -> Self {
+        Self {
+            inner,
+            policy,
+            retry_read: ReadError::is_transient,
+            retry_write: WriteError::is_transient,
+        }
+    }
+
+    /// Retry reads failing with errors for which `retryable` returns `true`
+    pub fn retry_read_if(mut self, retryable: fn(&ReadError) -> bool) -> Self {
+        self.retry_read = retryable;
+        self
+    }
+
+    /// Retry writes failing with errors for which `retryable` returns `true`
+    pub fn retry_write_if(mut self, retryable: fn(&WriteError) -> bool) -> Self {
+        self.retry_write = retryable;
+        self
+    }
+
+    pub fn policy(&self) -> &RetryPolicy {
+        &self.policy
+    }
+
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+
+    async fn retry<T, E, F, Fut>(&self, retryable: fn(&E) -> bool, mut attempt: F) -> Result<T, E>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        let mut retries = 0;
+
+        loop {
+            match attempt().await {
+                Ok(value) => return Ok(value),
+                Err(err) if retries >= self.policy.max_retries || !retryable(&err) => {
+                    return Err(err);
+                }
+                Err(_) => {
+                    tokio::time::sleep(self.policy.jittered_delay(retries)).await;
+                    retries += 1;
+                }
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl<S: DataSource> DataSource for RetrySource<S> {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn schemes(&self) -> &[Scheme] {
+        self.inner.schemes()
+    }
+
+    fn as_watch(&self) -> Option<&dyn WatchSource> {
+        self.inner.as_watch()
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        self.inner.capabilities()
+    }
+
+    async fn health(&self) -> Result<(), ReadError> {
+        self.retry(self.retry_read, || self.inner.health()).await
+    }
+
+    async fn exists(&self, path: &Path) -> Result<bool, ReadError> {
+        self.retry(self.retry_read, || self.inner.exists(path))
+            .await
+    }
+
+    async fn count(&self, path: &Path) -> Result<usize, ReadError> {
+        self.retry(self.retry_read, || self.inner.count(path)).await
+    }
+
+    async fn find_one(&self, path: &Path) -> Result<Record, ReadError> {
+        self.retry(self.retry_read, || self.inner.find_one(path))
+            .await
+    }
+
+    async fn find(&self, path: &Path) -> Result<Vec<Record>, ReadError> {
+        self.retry(self.retry_read, || self.inner.find(path)).await
+    }
+
+    async fn find_page(
+        &self,
+        path: &Path,
+        cursor: Option<Cursor>,
+        limit: usize,
+    ) -> Result<(Vec<Record>, Option<Cursor>), ReadError> {
+        self.retry(self.retry_read, || {
+            self.inner.find_page(path, cursor.clone(), limit)
+        })
+        .await
+    }
+
+    fn find_stream<'a>(&'a self, path: &'a Path) -> BoxStream<'a, Result<Record, ReadError>> {
+        self.inner.find_stream(path)
+    }
+
+    async fn find_one_chunked<'a>(
+        &'a self,
+        path: &'a Path,
+        chunk_size: usize,
+    ) -> Result<ChunkedRecord<'a>, ReadError> {
+        self.retry(self.retry_read, || {
+            self.inner.find_one_chunked(path, chunk_size)
+        })
+        .await
+    }
+
+    async fn etag(&self, path: &Path) -> Result<Option<ETag>, ReadError> {
+        self.retry(self.retry_read, || self.inner.etag(path)).await
+    }
+
+    async fn create(&self, record: Record) -> Result<(), WriteError> {
+        self.retry(self.retry_write, || self.inner.create(record.clone()))
+            .await
+    }
+
+    async fn update(&self, record: Record) -> Result<(), WriteError> {
+        self.retry(self.retry_write, || self.inner.update(record.clone()))
+            .await
+    }
+
+    async fn update_if(&self, record: Record, expected: &ETag) -> Result<(), WriteError> {
+        self.retry(self.retry_write, || {
+            self.inner.update_if(record.clone(), expected)
+        })
+        .await
+    }
+
+    async fn upsert(&self, record: Record) -> Result<(), WriteError> {
+        self.retry(self.retry_write, || self.inner.upsert(record.clone()))
+            .await
+    }
+
+    async fn delete(&self, path: &Path) -> Result<(), WriteError> {
+        self.retry(self.retry_write, || self.inner.delete(path))
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+    use crate::sources::MemorySource;
+    use crate::{MediaType, path::FilePath};
+
+    /// Fails the first `failures` calls to `find_one` and `upsert` with `kind`
+    struct FlakySource {
+        inner: MemorySource,
+        kind: io::ErrorKind,
+        failures: usize,
+        calls: AtomicUsize,
+    }
+
+    impl FlakySource {
+        fn new(kind: io::ErrorKind, failures: usize) -> Self {
+            Self {
+                inner: MemorySource::default(),
+                kind,
+                failures,
+                calls: AtomicUsize::new(0),
+            }
+        }
+
+        fn fail(&self) -> Option<io::Error> {
+            let call = self.calls.fetch_add(1, Ordering::SeqCst);
+            (call < self.failures).then(|| self.kind.into())
+        }
+    }
+
+    #[async_trait]
+    impl DataSource for FlakySource {
+        fn name(&self) -> &str {
+            "flaky"
+        }
+
+        async fn exists(&self, path: &Path) -> Result<bool, ReadError> {
+            self.inner.exists(path).await
+        }
+
+        async fn count(&self, path: &Path) -> Result<usize, ReadError> {
+            self.inner.count(path).await
+        }
+
+        async fn find_one(&self, path: &Path) -> Result<Record, ReadError> {
+            match self.fail() {
+                Some(err) => Err(err.into()),
+                None => self.inner.find_one(path).await,
+            }
+        }
+
+        async fn find(&self, path: &Path) -> Result<Vec<Record>, ReadError> {
+            self.inner.find(path).await
+        }
+
+        async fn create(&self, record: Record) -> Result<(), WriteError> {
+            self.inner.create(record).await
+        }
+
+        async fn update(&self, record: Record) -> Result<(), WriteError> {
+            self.inner.update(record).await
+        }
+
+        async fn upsert(&self, record: Record) -> Result<(), WriteError> {
+            match self.fail() {
+                Some(err) => Err(err.into()),
+                None => self.inner.upsert(record).await,
+            }
+        }
+
+        async fn delete(&self, path: &Path) -> Result<(), WriteError> {
+            self.inner.delete(path).await
+        }
+    }
+
+    fn policy(max_retries: usize) -> RetryPolicy {
+        RetryPolicy {
+            max_retries,
+            backoff: Duration::ZERO,
+            ..RetryPolicy::default()
+        }
+    }
+
+    fn path() -> Path {
+        Path::File(FilePath::parse("/test/file.txt"))
+    }
+
+    #[tokio::test]
+    async fn test_retries_transient_errors() {
+        let ds = RetrySource::new(FlakySource::new(io::ErrorKind::TimedOut, 2), policy(3));
+        let record = Record::from_str(path(), MediaType::TextPlain, "hello");
+
+        ds.upsert(record.clone()).await.unwrap();
+        assert_eq!(ds.find_one(&path()).await.unwrap(), record);
+        assert_eq!(ds.inner().calls.load(Ordering::SeqCst), 4);
+    }
+
+    #[tokio::test]
+    async fn test_gives_up_after_max_retries() {
+        let ds = RetrySource::new(FlakySource::new(io::ErrorKind::TimedOut, 5), policy(2));
+        let err = ds.find_one(&path()).await.unwrap_err();
+
+        assert!(err.is_transient());
+        assert_eq!(ds.inner().calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_does_not_retry_terminal_errors() {
+        let ds = RetrySource::new(FlakySource::new(io::ErrorKind::NotFound, 1), policy(3));
+
+        assert!(ds.find_one(&path()).await.is_err());
+        assert_eq!(ds.inner().calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_custom_classification() {
+        let ds = RetrySource::new(FlakySource::new(io::ErrorKind::NotFound, 1), policy(3))
+            .retry_write_if(|err| err.is_io());
+        let record = Record::from_str(path(), MediaType::TextPlain, "hello");
+
+        ds.upsert(record).await.unwrap();
+        assert_eq!(ds.inner().calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_delay_grows_to_cap_with_jitter() {
+        let policy = RetryPolicy {
+            max_retries: 5,
+            backoff: Duration::from_millis(100),
+            factor: 2.0,
+            max_backoff: Duration::from_millis(500),
+            jitter: 0.5,
+        };
+
+        assert_eq!(policy.delay(0), Duration::from_millis(100));
+        assert_eq!(policy.delay(1), Duration::from_millis(200));
+        assert_eq!(policy.delay(3), Duration::from_millis(500));
+
+        for retry in 0..5 {
+            let delay = policy.jittered_delay(retry);
+            assert!(delay <= policy.delay(retry));
+            assert!(delay >= policy.delay(retry) / 2);
+        }
+    }
+}
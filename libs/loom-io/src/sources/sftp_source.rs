@@ -0,0 +1,738 @@
+use std::io::{self, Read};
+use std::net::TcpStream;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD_NO_PAD;
+use ssh2::{CheckResult, FileStat, HashType, KnownHostFileKind, Session, Sftp};
+
+use crate::path::{FilePath, Path};
+use crate::{Capabilities, DataSource, Glob, MediaType, ReadError, Record, WriteError};
+
+/// Credentials used to log in
+#[derive(Clone)]
+pub enum SftpAuth {
+    Password(String),
+    /// Private key file, e.g. `~/.ssh/id_ed25519`
+    PublicKey {
+        private_key: PathBuf,
+        passphrase: Option<String>,
+    },
+    /// Keys held by the running ssh-agent
+    Agent,
+}
+
+impl std::fmt::Debug for SftpAuth {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Never print secrets
+        match self {
+            Self::Password(_) => write!(f, "Password(***)"),
+            Self::PublicKey { private_key, .. } => {
+                write!(f, "PublicKey({})", private_key.display())
+            }
+            Self::Agent => write!(f, "Agent"),
+        }
+    }
+}
+
+/// How the server's host key is verified before logging in
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HostKeyCheck {
+    /// Require the key to be listed for the host in an OpenSSH `known_hosts`
+    /// file (default: `~/.ssh/known_hosts`)
+    KnownHosts(PathBuf),
+
+    /// Require the key's SHA-256 fingerprint as `ssh-keygen -l` prints it,
+    /// e.g. `SHA256:nThbg6kXUpJWGl7E1IGOCspRomTxdCARLviKw6E5SY8`
+    Fingerprint(String),
+
+    /// Accept any key. Only for tests: anyone on the network path can
+    /// impersonate the server.
+    AcceptAny,
+}
+
+impl Default for HostKeyCheck {
+    fn default() -> Self {
+        let home = std::env::var_os("HOME").unwrap_or_default();
+        Self::KnownHosts(PathBuf::from(home).join(".ssh").join("known_hosts"))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SftpSourceConfig {
+    name: String,
+    host: String,
+    port: u16,
+    username: String,
+    auth: SftpAuth,
+    host_key: HostKeyCheck,
+    root: PathBuf,
+    pool_size: usize,
+    timeout: Option<Duration>,
+}
+
+impl SftpSourceConfig {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn host(&self) -> &str {
+        &self.host
+    }
+
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+
+    pub fn username(&self) -> &str {
+        &self.username
+    }
+
+    pub fn auth(&self) -> &SftpAuth {
+        &self.auth
+    }
+
+    pub fn host_key(&self) -> &HostKeyCheck {
+        &self.host_key
+    }
+
+    /// Remote directory file paths are resolved against
+    pub fn root(&self) -> &std::path::Path {
+        &self.root
+    }
+
+    /// Most idle connections kept open between reads
+    pub fn pool_size(&self) -> usize {
+        self.pool_size
+    }
+
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SftpSourceBuilder {
+    name: Option<String>,
+    host: String,
+    port: u16,
+    username: Option<String>,
+    auth: SftpAuth,
+    host_key: HostKeyCheck,
+    root: PathBuf,
+    pool_size: usize,
+    timeout: Option<Duration>,
+}
+
+impl SftpSourceBuilder {
+    pub fn new(host: impl Into<String>) -> Self {
+        Self {
+            name: None,
+            host: host.into(),
+            port: 22,
+            username: None,
+            auth: SftpAuth::Agent,
+            host_key: HostKeyCheck::default(),
+            root: PathBuf::from("."),
+            pool_size: 4,
+            timeout: None,
+        }
+    }
+
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    pub fn port(mut self, port: u16) -> Self {
+        self.port = port;
+        self
+    }
+
+    /// User to log in as (default: `$USER`)
+    pub fn username(mut self, username: impl Into<String>) -> Self {
+        self.username = Some(username.into());
+        self
+    }
+
+    pub fn password(mut self, password: impl Into<String>) -> Self {
+        self.auth = SftpAuth::Password(password.into());
+        self
+    }
+
+    pub fn private_key(
+        mut self,
+        private_key: impl Into<PathBuf>,
+        passphrase: Option<String>,
+    ) -> Self {
+        self.auth = SftpAuth::PublicKey {
+            private_key: private_key.into(),
+            passphrase,
+        };
+        self
+    }
+
+    /// Log in with the ssh-agent's keys (the default)
+    pub fn agent(mut self) -> Self {
+        self.auth = SftpAuth::Agent;
+        self
+    }
+
+    pub fn host_key(mut self, check: HostKeyCheck) -> Self {
+        self.host_key = check;
+        self
+    }
+
+    /// Resolve file paths against this remote directory (default: the
+    /// login directory)
+    pub fn root(mut self, root: impl Into<PathBuf>) -> Self {
+        self.root = root.into();
+        self
+    }
+
+    /// Keep up to `size` idle connections open (default: 4)
+    pub fn pool_size(mut self, size: usize) -> Self {
+        self.pool_size = size;
+        self
+    }
+
+    /// Give up on connecting or on a single SFTP request after `timeout`
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub fn build(self) -> SftpSource {
+        SftpSource {
+            config: Arc::new(SftpSourceConfig {
+                name: self.name.unwrap_or_else(|| "sftp".to_string()),
+                host: self.host,
+                port: self.port,
+                username: self
+                    .username
+                    .or_else(|| std::env::var("USER").ok())
+                    .unwrap_or_default(),
+                auth: self.auth,
+                host_key: self.host_key,
+                root: self.root,
+                pool_size: self.pool_size,
+                timeout: self.timeout,
+            }),
+            idle: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+}
+
+/// An authenticated session and its SFTP channel
+struct Connection {
+    _session: Session,
+    sftp: Sftp,
+}
+
+impl Connection {
+    fn open(config: &SftpSourceConfig) -> io::Result<Self> {
+        let address = (config.host.as_str(), config.port);
+        let tcp = match config.timeout {
+            None => TcpStream::connect(address)?,
+            Some(timeout) => {
+                let address = std::net::ToSocketAddrs::to_socket_addrs(&address)?
+                    .next()
+                    .ok_or_else(|| {
+                        io::Error::new(
+                            io::ErrorKind::NotFound,
+                            format!("can't resolve '{}'", config.host),
+                        )
+                    })?;
+                TcpStream::connect_timeout(&address, timeout)?
+            }
+        };
+
+        let mut session = Session::new()?;
+        session.set_tcp_stream(tcp);
+
+        if let Some(timeout) = config.timeout {
+            session.set_timeout(timeout.as_millis().min(u32::MAX as u128) as u32);
+        }
+
+        session.handshake()?;
+        verify_host_key(&session, config)?;
+
+        match &config.auth {
+            SftpAuth::Password(password) => {
+                session.userauth_password(&config.username, password)?
+            }
+            SftpAuth::PublicKey {
+                private_key,
+                passphrase,
+            } => session.userauth_pubkey_file(
+                &config.username,
+                None,
+                private_key,
+                passphrase.as_deref(),
+            )?,
+            SftpAuth::Agent => session.userauth_agent(&config.username)?,
+        }
+
+        let sftp = session.sftp()?;
+        Ok(Self {
+            _session: session,
+            sftp,
+        })
+    }
+
+    /// Files `full_path` selects: itself if it is a file, everything below
+    /// it if it is a directory, or the matches if it is a glob pattern
+    fn matching_files(&self, full_path: &std::path::Path) -> io::Result<Vec<PathBuf>> {
+        let mut files = match self.sftp.stat(full_path) {
+            Ok(stat) if stat.is_file() => vec![full_path.to_path_buf()],
+            Ok(stat) if stat.is_dir() => self.list_files(full_path, None)?,
+            Ok(_) => Vec::new(),
+            Err(_) if Glob::is_pattern(&full_path.to_string_lossy()) => {
+                let glob = Glob::new(full_path);
+                self.list_files(&glob.base(), glob.depth())?
+                    .into_iter()
+                    .filter(|file| glob.matches(file))
+                    .collect()
+            }
+            Err(err) => match io::Error::from(err) {
+                err if err.kind() == io::ErrorKind::NotFound => Vec::new(),
+                err => return Err(err),
+            },
+        };
+
+        files.sort();
+        Ok(files)
+    }
+
+    /// Files under `dir`, at most `depth` levels down
+    fn list_files(&self, dir: &std::path::Path, depth: Option<usize>) -> io::Result<Vec<PathBuf>> {
+        let mut files = Vec::new();
+
+        if depth == Some(0) {
+            return Ok(files);
+        }
+
+        for (path, stat) in self.sftp.readdir(dir)? {
+            if stat.is_dir() {
+                files.extend(self.list_files(&path, depth.map(|depth| depth - 1))?);
+            } else if stat.is_file() {
+                files.push(path);
+            }
+        }
+
+        Ok(files)
+    }
+
+    fn read(&self, full_path: &std::path::Path) -> io::Result<(Vec<u8>, FileStat)> {
+        let mut file = self.sftp.open(full_path)?;
+        let stat = file.stat()?;
+        let mut content = Vec::new();
+        file.read_to_end(&mut content)?;
+        Ok((content, stat))
+    }
+}
+
+/// Fail unless the server's host key passes `config.host_key`
+fn verify_host_key(session: &Session, config: &SftpSourceConfig) -> io::Result<()> {
+    let rejected = |reason: String| {
+        io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            format!("host key of '{}' rejected: {}", config.host, reason),
+        )
+    };
+
+    match &config.host_key {
+        HostKeyCheck::AcceptAny => Ok(()),
+        HostKeyCheck::Fingerprint(expected) => {
+            let hash = session
+                .host_key_hash(HashType::Sha256)
+                .ok_or_else(|| rejected("server sent no host key".to_string()))?;
+            let actual = fingerprint(hash);
+
+            if actual == *expected {
+                Ok(())
+            } else {
+                Err(rejected(format!("fingerprint is {}", actual)))
+            }
+        }
+        HostKeyCheck::KnownHosts(file) => {
+            let (key, _) = session
+                .host_key()
+                .ok_or_else(|| rejected("server sent no host key".to_string()))?;
+            let mut known_hosts = session.known_hosts()?;
+            known_hosts.read_file(file, KnownHostFileKind::OpenSSH)?;
+
+            match known_hosts.check_port(&config.host, config.port, key) {
+                CheckResult::Match => Ok(()),
+                CheckResult::NotFound => Err(rejected(format!("not listed in {}", file.display()))),
+                CheckResult::Mismatch => Err(rejected(format!("differs from {}", file.display()))),
+                CheckResult::Failure => Err(rejected("check failed".to_string())),
+            }
+        }
+    }
+}
+
+/// SHA-256 host key hash formatted like `ssh-keygen -l`
+fn fingerprint(hash: &[u8]) -> String {
+    format!("SHA256:{}", STANDARD_NO_PAD.encode(hash))
+}
+
+/// Read-only data source over a remote directory served by SFTP (`sftp`
+/// feature), e.g. a partner's dataset drop.
+///
+/// File paths resolve against the configured root. [`find`](DataSource::find)
+/// takes a file, a directory (listed recursively) or a glob pattern, like
+/// [`FileSystemSource`](super::FileSystemSource). Records carry the remote
+/// file's `size` and `mtime` (Unix milliseconds) as
+/// [`attributes`](Record::attributes).
+///
+/// Each operation checks out an authenticated connection from a pool,
+/// opening one if none is idle, and runs on the blocking thread pool. The
+/// server's host key is verified before logging in, against
+/// `~/.ssh/known_hosts` unless configured otherwise.
+pub struct SftpSource {
+    config: Arc<SftpSourceConfig>,
+    idle: Arc<Mutex<Vec<Connection>>>,
+}
+
+impl SftpSource {
+    pub fn builder(host: impl Into<String>) -> SftpSourceBuilder {
+        SftpSourceBuilder::new(host)
+    }
+
+    pub fn config(&self) -> &SftpSourceConfig {
+        &self.config
+    }
+
+    /// `path` below the root; `..` segments are rejected, as they could
+    /// reach files outside it
+    fn full_path(&self, path: &Path) -> Result<PathBuf, ReadError> {
+        let Path::File(file) = path else {
+            return Err(ReadError::Custom(
+                "SftpSource only supports File paths".to_string(),
+            ));
+        };
+
+        let relative = file.to_string_lossy();
+        let relative = std::path::Path::new(relative.trim_start_matches('/'));
+
+        if relative
+            .components()
+            .any(|c| c == std::path::Component::ParentDir)
+        {
+            return Err(ReadError::Custom(format!(
+                "SftpSource paths may not leave the root: {}",
+                path
+            )));
+        }
+
+        Ok(self.config.root.join(relative))
+    }
+
+    fn relative_path(&self, full_path: &std::path::Path) -> Path {
+        let relative = full_path
+            .strip_prefix(&self.config.root)
+            .unwrap_or(full_path);
+        Path::File(FilePath::parse(&relative.to_string_lossy()))
+    }
+
+    /// Run `op` on a pooled connection on the blocking thread pool. The
+    /// connection goes back to the pool unless `op` failed in a way that may
+    /// have broken it.
+    async fn with_connection<T, F>(&self, op: F) -> Result<T, ReadError>
+    where
+        T: Send + 'static,
+        F: FnOnce(&Connection) -> io::Result<T> + Send + 'static,
+    {
+        let idle = self
+            .idle
+            .lock()
+            .map_err(|e| ReadError::panic(e.to_string()))?
+            .pop();
+        let config = self.config.clone();
+
+        let (connection, result) = tokio::task::spawn_blocking(move || {
+            let connection = match idle {
+                Some(connection) => connection,
+                None => Connection::open(&config)?,
+            };
+
+            let result = op(&connection);
+            Ok::<_, io::Error>((connection, result))
+        })
+        .await
+        .map_err(|e| ReadError::panic(e.to_string()))??;
+
+        let reusable = match &result {
+            Ok(_) => true,
+            Err(err) => matches!(
+                err.kind(),
+                io::ErrorKind::NotFound | io::ErrorKind::PermissionDenied
+            ),
+        };
+
+        if reusable
+            && let Ok(mut idle) = self.idle.lock()
+            && idle.len() < self.config.pool_size
+        {
+            idle.push(connection);
+        }
+
+        Ok(result?)
+    }
+}
+
+fn read_only() -> WriteError {
+    WriteError::Custom("SftpSource is read-only".to_string())
+}
+
+#[async_trait]
+impl DataSource for SftpSource {
+    fn name(&self) -> &str {
+        &self.config.name
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::new().read_only()
+    }
+
+    /// Connects, logs in and checks the root directory exists
+    async fn health(&self) -> Result<(), ReadError> {
+        let root = self.config.root.clone();
+
+        self.with_connection(move |connection| match connection.sftp.stat(&root)? {
+            stat if stat.is_dir() => Ok(()),
+            _ => Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("'{}' is not a directory", root.display()),
+            )),
+        })
+        .await
+    }
+
+    async fn exists(&self, path: &Path) -> Result<bool, ReadError> {
+        let full_path = self.full_path(path)?;
+
+        self.with_connection(move |connection| match connection.sftp.stat(&full_path) {
+            Ok(stat) => Ok(stat.is_file()),
+            Err(err) => match io::Error::from(err) {
+                err if err.kind() == io::ErrorKind::NotFound => Ok(false),
+                err => Err(err),
+            },
+        })
+        .await
+    }
+
+    async fn count(&self, path: &Path) -> Result<usize, ReadError> {
+        let full_path = self.full_path(path)?;
+
+        self.with_connection(move |connection| Ok(connection.matching_files(&full_path)?.len()))
+            .await
+    }
+
+    async fn find_one(&self, path: &Path) -> Result<Record, ReadError> {
+        let full_path = self.full_path(path)?;
+        let (content, stat) = self
+            .with_connection(move |connection| connection.read(&full_path))
+            .await?;
+
+        Ok(record(path.clone(), content, &stat))
+    }
+
+    async fn find(&self, path: &Path) -> Result<Vec<Record>, ReadError> {
+        let full_path = self.full_path(path)?;
+        let files = self
+            .with_connection(move |connection| {
+                connection
+                    .matching_files(&full_path)?
+                    .into_iter()
+                    .map(|file| {
+                        let (content, stat) = connection.read(&file)?;
+                        Ok((file, content, stat))
+                    })
+                    .collect::<io::Result<Vec<_>>>()
+            })
+            .await?;
+
+        Ok(files
+            .into_iter()
+            .map(|(file, content, stat)| record(self.relative_path(&file), content, &stat))
+            .collect())
+    }
+
+    async fn create(&self, _record: Record) -> Result<(), WriteError> {
+        Err(read_only())
+    }
+
+    async fn update(&self, _record: Record) -> Result<(), WriteError> {
+        Err(read_only())
+    }
+
+    async fn upsert(&self, _record: Record) -> Result<(), WriteError> {
+        Err(read_only())
+    }
+
+    async fn delete(&self, _path: &Path) -> Result<(), WriteError> {
+        Err(read_only())
+    }
+}
+
+/// Record for a remote file, with its `size` and `mtime` as attributes
+fn record(path: Path, content: Vec<u8>, stat: &FileStat) -> Record {
+    let media_type = MediaType::from_path(path.to_string());
+    let mut record = Record::new(path, media_type, content);
+
+    if let Some(size) = stat.size {
+        record = record.with_attribute("size", size);
+    }
+
+    if let Some(mtime) = stat.mtime {
+        record = record.with_attribute("mtime", mtime.saturating_mul(1000));
+    }
+
+    record
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builder() {
+        let ds = SftpSource::builder("sftp.partner.example")
+            .name("partner")
+            .port(2222)
+            .username("merc")
+            .private_key("/keys/id_ed25519", None)
+            .host_key(HostKeyCheck::Fingerprint("SHA256:abc".to_string()))
+            .root("/drop/datasets")
+            .pool_size(2)
+            .timeout(Duration::from_secs(10))
+            .build();
+
+        assert_eq!(ds.config().name(), "partner");
+        assert_eq!(ds.config().host(), "sftp.partner.example");
+        assert_eq!(ds.config().port(), 2222);
+        assert_eq!(ds.config().username(), "merc");
+        assert!(matches!(ds.config().auth(), SftpAuth::PublicKey { .. }));
+        assert_eq!(
+            ds.config().host_key(),
+            &HostKeyCheck::Fingerprint("SHA256:abc".to_string())
+        );
+        assert_eq!(ds.config().root(), std::path::Path::new("/drop/datasets"));
+        assert_eq!(ds.config().pool_size(), 2);
+        assert_eq!(ds.config().timeout(), Some(Duration::from_secs(10)));
+        assert!(!ds.capabilities().write);
+    }
+
+    #[test]
+    fn test_builder_defaults() {
+        let ds = SftpSource::builder("localhost").build();
+
+        assert_eq!(ds.config().name(), "sftp");
+        assert_eq!(ds.config().port(), 22);
+        assert!(matches!(ds.config().auth(), SftpAuth::Agent));
+        assert!(
+            matches!(ds.config().host_key(), HostKeyCheck::KnownHosts(file) if file.ends_with(".ssh/known_hosts"))
+        );
+        assert_eq!(ds.config().pool_size(), 4);
+    }
+
+    #[test]
+    fn test_paths_resolve_against_root() {
+        let ds = SftpSource::builder("localhost").root("/drop").build();
+        let path = Path::File(FilePath::parse("2026/dialogs.jsonl"));
+        let full_path = ds.full_path(&path).unwrap();
+
+        assert_eq!(full_path, PathBuf::from("/drop/2026/dialogs.jsonl"));
+        assert_eq!(ds.relative_path(&full_path), path);
+    }
+
+    #[test]
+    fn test_paths_may_not_leave_root() {
+        let ds = SftpSource::builder("localhost").root("/drop").build();
+
+        for path in ["../etc/passwd", "/2026/../../etc/passwd", "2026/.."] {
+            let err = ds
+                .full_path(&Path::File(FilePath::parse(path)))
+                .unwrap_err();
+            assert!(err.is_custom());
+        }
+
+        let path = Path::File(FilePath::parse("2026/..data/dialogs.jsonl"));
+        assert!(ds.full_path(&path).is_ok());
+    }
+
+    #[test]
+    fn test_auth_debug_hides_password() {
+        let auth = SftpAuth::Password("hunter2".to_string());
+        assert_eq!(format!("{:?}", auth), "Password(***)");
+    }
+
+    #[test]
+    fn test_fingerprint() {
+        assert_eq!(fingerprint(&[0u8; 3]), "SHA256:AAAA");
+    }
+
+    /// Source on the server at `SFTP_HOST` (and `SFTP_PORT`), logged in with
+    /// `SFTP_USERNAME` and `SFTP_PASSWORD`, under `SFTP_ROOT`
+    fn server() -> SftpSource {
+        let env = |name: &str| std::env::var(name).ok();
+        let mut builder = SftpSource::builder(env("SFTP_HOST").expect("SFTP_HOST is set"))
+            .host_key(HostKeyCheck::AcceptAny)
+            .root(env("SFTP_ROOT").unwrap_or_else(|| "/".to_string()))
+            .timeout(Duration::from_secs(10));
+
+        if let Some(port) = env("SFTP_PORT") {
+            builder = builder.port(port.parse().expect("SFTP_PORT is a port"));
+        }
+
+        if let Some(username) = env("SFTP_USERNAME") {
+            builder = builder.username(username);
+        }
+
+        if let Some(password) = env("SFTP_PASSWORD") {
+            builder = builder.password(password);
+        }
+
+        builder.build()
+    }
+
+    #[tokio::test]
+    #[ignore = "needs an SFTP server at SFTP_HOST"]
+    async fn test_reads_from_server() {
+        let ds = server();
+        let root = Path::File(FilePath::parse("/"));
+
+        ds.health().await.unwrap();
+
+        let records = ds.find(&root).await.unwrap();
+        assert_eq!(ds.count(&root).await.unwrap(), records.len());
+
+        for found in records {
+            assert!(ds.exists(&found.path).await.unwrap());
+            assert_eq!(
+                ds.find_one(&found.path).await.unwrap().content,
+                found.content
+            );
+        }
+    }
+
+    #[tokio::test]
+    #[ignore = "needs an SFTP server at SFTP_HOST"]
+    async fn test_missing_file_and_writes() {
+        let ds = server();
+        let path = Path::File(FilePath::parse("loom-test-missing.json"));
+
+        assert!(!ds.exists(&path).await.unwrap());
+
+        let err = ds.find_one(&path).await.unwrap_err();
+        assert!(matches!(err, ReadError::IO(e) if e.kind() == io::ErrorKind::NotFound));
+
+        let record = Record::from_str(path, MediaType::TextJson, "{}");
+        assert!(ds.upsert(record).await.is_err());
+    }
+}
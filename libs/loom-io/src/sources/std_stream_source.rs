@@ -0,0 +1,318 @@
+use std::io::{self, Read, Write};
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+
+use crate::path::{FilePath, Path};
+use crate::{Capabilities, DataSource, ETag, MediaType, ReadError, Record, WriteError};
+
+/// File path that reads stdin and writes stdout
+pub const STD_STREAM_PATH: &str = "-";
+
+#[derive(Debug, Clone)]
+pub struct StdStreamSourceConfig {
+    name: String,
+    media_type: Option<MediaType>,
+}
+
+impl StdStreamSourceConfig {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Media type of the input, or `None` to sniff it
+    pub fn media_type(&self) -> Option<MediaType> {
+        self.media_type
+    }
+}
+
+pub struct StdStreamSourceBuilder {
+    name: Option<String>,
+    media_type: Option<MediaType>,
+    reader: Option<Box<dyn Read + Send>>,
+    writer: Option<Box<dyn Write + Send>>,
+}
+
+impl StdStreamSourceBuilder {
+    pub fn new() -> Self {
+        Self {
+            name: None,
+            media_type: None,
+            reader: None,
+            writer: None,
+        }
+    }
+
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Treat the input as `media_type` instead of sniffing its content
+    pub fn media_type(mut self, media_type: MediaType) -> Self {
+        self.media_type = Some(media_type);
+        self
+    }
+
+    /// Read from `reader` instead of stdin
+    pub fn reader(mut self, reader: impl Read + Send + 'static) -> Self {
+        self.reader = Some(Box::new(reader));
+        self
+    }
+
+    /// Write to `writer` instead of stdout
+    pub fn writer(mut self, writer: impl Write + Send + 'static) -> Self {
+        self.writer = Some(Box::new(writer));
+        self
+    }
+
+    pub fn build(self) -> StdStreamSource {
+        StdStreamSource {
+            config: StdStreamSourceConfig {
+                name: self.name.unwrap_or_else(|| "std".to_string()),
+                media_type: self.media_type,
+            },
+            reader: Mutex::new(self.reader.unwrap_or_else(|| Box::new(io::stdin()))),
+            writer: Mutex::new(self.writer.unwrap_or_else(|| Box::new(io::stdout()))),
+            input: Mutex::new(None),
+        }
+    }
+}
+
+impl Default for StdStreamSourceBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Reads stdin and writes stdout through the `-` path, for shell pipelines.
+///
+/// Stdin can only be read once, so the first read buffers it whole and later
+/// reads return the same record. Its media type is sniffed from the content
+/// unless set on the builder. Every write to `-` appends the record's content
+/// to stdout. Other paths don't exist in this source.
+pub struct StdStreamSource {
+    config: StdStreamSourceConfig,
+    reader: Mutex<Box<dyn Read + Send>>,
+    writer: Mutex<Box<dyn Write + Send>>,
+    input: Mutex<Option<Record>>,
+}
+
+impl StdStreamSource {
+    pub fn builder() -> StdStreamSourceBuilder {
+        StdStreamSourceBuilder::new()
+    }
+
+    pub fn config(&self) -> &StdStreamSourceConfig {
+        &self.config
+    }
+
+    /// The `-` path
+    pub fn path() -> Path {
+        Path::File(FilePath::parse(STD_STREAM_PATH))
+    }
+
+    /// Whether `path` is the `-` path
+    pub fn is_std(path: &Path) -> bool {
+        matches!(path, Path::File(file) if file.as_os_str() == STD_STREAM_PATH)
+    }
+
+    fn read_input(&self) -> Result<Record, ReadError> {
+        let mut input = self
+            .input
+            .lock()
+            .map_err(|e| ReadError::panic(e.to_string()))?;
+
+        if let Some(record) = input.as_ref() {
+            return Ok(record.clone());
+        }
+
+        let mut content = Vec::new();
+        self.reader
+            .lock()
+            .map_err(|e| ReadError::panic(e.to_string()))?
+            .read_to_end(&mut content)?;
+
+        let media_type = self
+            .config
+            .media_type
+            .unwrap_or_else(|| MediaType::sniff(&content));
+
+        let record = Record::new(Self::path(), media_type, content);
+        *input = Some(record.clone());
+        Ok(record)
+    }
+
+    fn write_output(&self, record: &Record) -> Result<(), WriteError> {
+        if !Self::is_std(&record.path) {
+            return Err(not_found(&record.path).into());
+        }
+
+        let mut writer = self
+            .writer
+            .lock()
+            .map_err(|e| WriteError::panic(e.to_string()))?;
+
+        writer.write_all(&record.content)?;
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+impl Default for StdStreamSource {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+fn not_found(path: &Path) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::NotFound,
+        format!("'{}' is not '{}'", path, STD_STREAM_PATH),
+    )
+}
+
+#[async_trait]
+impl DataSource for StdStreamSource {
+    fn name(&self) -> &str {
+        &self.config.name
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::new().list(false)
+    }
+
+    async fn exists(&self, path: &Path) -> Result<bool, ReadError> {
+        Ok(Self::is_std(path))
+    }
+
+    async fn count(&self, path: &Path) -> Result<usize, ReadError> {
+        Ok(Self::is_std(path) as usize)
+    }
+
+    async fn find_one(&self, path: &Path) -> Result<Record, ReadError> {
+        if !Self::is_std(path) {
+            return Err(not_found(path).into());
+        }
+
+        self.read_input()
+    }
+
+    async fn find(&self, path: &Path) -> Result<Vec<Record>, ReadError> {
+        if !Self::is_std(path) {
+            return Ok(vec![]);
+        }
+
+        Ok(vec![self.read_input()?])
+    }
+
+    async fn create(&self, record: Record) -> Result<(), WriteError> {
+        self.write_output(&record)
+    }
+
+    async fn update(&self, record: Record) -> Result<(), WriteError> {
+        self.write_output(&record)
+    }
+
+    async fn update_if(&self, _record: Record, _expected: &ETag) -> Result<(), WriteError> {
+        Err(WriteError::Custom(
+            "stdout does not support conditional updates".to_string(),
+        ))
+    }
+
+    async fn upsert(&self, record: Record) -> Result<(), WriteError> {
+        self.write_output(&record)
+    }
+
+    async fn delete(&self, path: &Path) -> Result<(), WriteError> {
+        Err(WriteError::Custom(format!("'{}' can't be deleted", path)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+
+    /// Writer whose output the test can read back
+    #[derive(Clone, Default)]
+    struct Output(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Output {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_reads_input_once_and_sniffs() {
+        let ds = StdStreamSource::builder()
+            .reader(io::Cursor::new(b"{\"a\": 1}\n{\"a\": 2}\n".to_vec()))
+            .build();
+
+        let record = ds.find_one(&StdStreamSource::path()).await.unwrap();
+        let again = ds.find_one(&StdStreamSource::path()).await.unwrap();
+
+        assert_eq!(record.media_type, MediaType::TextNdjson);
+        assert_eq!(record.content_str().unwrap(), "{\"a\": 1}\n{\"a\": 2}\n");
+        assert_eq!(again, record);
+        assert_eq!(ds.find(&StdStreamSource::path()).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_configured_media_type() {
+        let ds = StdStreamSource::builder()
+            .reader(io::Cursor::new(b"a: 1".to_vec()))
+            .media_type(MediaType::TextPlain)
+            .build();
+
+        let record = ds.find_one(&StdStreamSource::path()).await.unwrap();
+        assert_eq!(record.media_type, MediaType::TextPlain);
+    }
+
+    #[tokio::test]
+    async fn test_writes_output() {
+        let output = Output::default();
+        let ds = StdStreamSource::builder().writer(output.clone()).build();
+
+        ds.upsert(Record::from_str(
+            StdStreamSource::path(),
+            MediaType::TextJson,
+            "{}\n",
+        ))
+        .await
+        .unwrap();
+        ds.create(Record::from_str(
+            StdStreamSource::path(),
+            MediaType::TextJson,
+            "[]\n",
+        ))
+        .await
+        .unwrap();
+
+        assert_eq!(&*output.0.lock().unwrap(), b"{}\n[]\n");
+    }
+
+    #[tokio::test]
+    async fn test_other_paths_do_not_exist() {
+        let ds = StdStreamSource::builder()
+            .reader(io::Cursor::new(Vec::new()))
+            .writer(Output::default())
+            .build();
+        let path = Path::File(FilePath::parse("data.json"));
+
+        assert!(!ds.exists(&path).await.unwrap());
+        assert!(ds.find_one(&path).await.is_err());
+        assert!(ds.find(&path).await.unwrap().is_empty());
+        assert!(
+            ds.upsert(Record::from_str(path, MediaType::TextJson, "{}"))
+                .await
+                .is_err()
+        );
+    }
+}
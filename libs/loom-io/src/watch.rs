@@ -0,0 +1,46 @@
+use futures::stream::BoxStream;
+
+use crate::DataSource;
+use crate::path::Path;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    Create,
+    Update,
+    Delete,
+}
+
+impl std::fmt::Display for ChangeKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Create => write!(f, "create"),
+            Self::Update => write!(f, "update"),
+            Self::Delete => write!(f, "delete"),
+        }
+    }
+}
+
+/// A record created, changed or removed under a watched path
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub struct ChangeEvent {
+    pub kind: ChangeKind,
+    pub path: Path,
+}
+
+impl ChangeEvent {
+    pub fn new(kind: ChangeKind, path: Path) -> Self {
+        Self { kind, path }
+    }
+}
+
+/// A [`DataSource`] that can report changes to its records.
+///
+/// Sources opt in by implementing this trait and returning themselves from
+/// [`DataSource::as_watch`], so callers holding a `&dyn DataSource` (e.g.
+/// from a [`DataSourceRegistry`](crate::DataSourceRegistry)) can reach it.
+pub trait WatchSource: DataSource {
+    /// Changes to the records `find(path)` would return, in the order they
+    /// are noticed. The stream runs until dropped.
+    fn watch<'a>(&'a self, path: &'a Path) -> BoxStream<'a, ChangeEvent>;
+}
@@ -5,7 +5,8 @@ mod source;
 mod transformer;
 
 pub use pipeline::{
-    AnyLayer, Layer, LayerContext, LayerNode, LayerResult, Pipeline, PipelineBuilder,
+    AnyLayer, Layer, LayerContext, LayerMeta, LayerNode, LayerResult, MetaValue, Pipeline,
+    PipelineBuilder,
 };
 pub use source::*;
 pub use transformer::*;
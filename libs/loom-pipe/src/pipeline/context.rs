@@ -1,3 +1,5 @@
+use std::time::{Duration, Instant};
+
 use loom_core::Map;
 
 /// Trait for layer input contexts
@@ -10,6 +12,28 @@ pub trait LayerContext: Send + 'static {
 
     /// Get metadata
     fn meta(&self) -> &Map;
+
+    /// Get the point in time by which processing should finish, if any
+    fn deadline(&self) -> Option<Instant> {
+        None
+    }
+
+    /// Set the deadline (contexts that don't track one ignore it)
+    fn set_deadline(&mut self, deadline: Instant) {
+        let _ = deadline;
+    }
+
+    /// Get the time left until the deadline, zero once it has passed
+    fn remaining(&self) -> Option<Duration> {
+        self.deadline()
+            .map(|deadline| deadline.saturating_duration_since(Instant::now()))
+    }
+
+    /// Check whether the deadline has passed
+    fn is_expired(&self) -> bool {
+        self.deadline()
+            .is_some_and(|deadline| Instant::now() >= deadline)
+    }
 }
 
 /// Result wrapper for layer outputs
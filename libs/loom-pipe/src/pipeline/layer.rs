@@ -11,8 +11,22 @@ pub trait Layer: Send {
     /// Process input and produce output.
     fn process(&self, input: Self::Input) -> Result<LayerResult<Self::Output>>;
 
+    /// Optional: process borrowed input.
+    /// Layers that only read their input can override this so callers holding
+    /// a reference avoid moving or cloning it; `None` means unsupported.
+    fn process_ref(&self, _input: &Self::Input) -> Option<Result<LayerResult<Self::Output>>> {
+        None
+    }
+
     /// Optional: name for debugging/tracing
     fn name(&self) -> &'static str {
         std::any::type_name::<Self>()
     }
+
+    /// Optional: version of the layer's contract (input, output, metadata).
+    /// Bump it when that contract changes so pipelines pinning the old
+    /// version fail instead of silently running the new behaviour.
+    fn version(&self) -> &'static str {
+        "1.0.0"
+    }
 }
@@ -0,0 +1,238 @@
+use std::time::Duration;
+
+use loom_core::{Map, value::Value};
+
+/// Well-known layer metadata keys.
+pub mod keys {
+    /// Execution time in milliseconds, see [`LayerMeta::elapsed`](super::LayerMeta::elapsed)
+    pub const ELAPSED_MS: &str = "elapsed_ms";
+    /// Model inference time only (excludes overhead), in milliseconds
+    pub const INFERENCE_MS: &str = "inference_ms";
+    /// ISO-8601 timestamp of execution start
+    pub const START_TIME: &str = "start_time";
+    /// Processing step number in the pipeline
+    pub const STEP: &str = "step";
+    /// Input text that was processed
+    pub const TEXT: &str = "text";
+    /// blake3 hash (hex) of the input text
+    pub const TEXT_HASH: &str = "text_hash";
+}
+
+/// A type that can be stored in layer metadata.
+pub trait MetaValue: Sized {
+    fn from_meta(value: &Value) -> Option<Self>;
+    fn into_meta(self) -> Value;
+}
+
+impl MetaValue for Value {
+    fn from_meta(value: &Value) -> Option<Self> {
+        Some(value.clone())
+    }
+
+    fn into_meta(self) -> Value {
+        self
+    }
+}
+
+impl MetaValue for bool {
+    fn from_meta(value: &Value) -> Option<Self> {
+        value.as_bool()
+    }
+
+    fn into_meta(self) -> Value {
+        self.into()
+    }
+}
+
+impl MetaValue for i64 {
+    fn from_meta(value: &Value) -> Option<Self> {
+        value.as_int()
+    }
+
+    fn into_meta(self) -> Value {
+        self.into()
+    }
+}
+
+impl MetaValue for usize {
+    fn from_meta(value: &Value) -> Option<Self> {
+        value.as_int().and_then(|v| usize::try_from(v).ok())
+    }
+
+    fn into_meta(self) -> Value {
+        self.into()
+    }
+}
+
+impl MetaValue for f64 {
+    fn from_meta(value: &Value) -> Option<Self> {
+        value.as_float()
+    }
+
+    fn into_meta(self) -> Value {
+        self.into()
+    }
+}
+
+impl MetaValue for String {
+    fn from_meta(value: &Value) -> Option<Self> {
+        value.as_str().map(|v| v.to_string())
+    }
+
+    fn into_meta(self) -> Value {
+        self.into()
+    }
+}
+
+/// Durations are stored as whole milliseconds.
+impl MetaValue for Duration {
+    fn from_meta(value: &Value) -> Option<Self> {
+        value
+            .as_int()
+            .and_then(|ms| u64::try_from(ms).ok())
+            .map(Duration::from_millis)
+    }
+
+    fn into_meta(self) -> Value {
+        i64::try_from(self.as_millis()).unwrap_or(i64::MAX).into()
+    }
+}
+
+/// Typed access to layer metadata.
+///
+/// Metadata is still stored as [`Value`]s keyed by name, so results serialize
+/// as before; these accessors only convert on the way in and out.
+pub trait LayerMeta {
+    /// Get `key` as `T`, or `None` if it is missing or has another type.
+    fn typed<T: MetaValue>(&self, key: &str) -> Option<T>;
+
+    /// Set `key` to `value`.
+    fn set_typed<T: MetaValue>(&mut self, key: &str, value: T);
+
+    /// Merge the metadata of the next layer in a pipeline into this one.
+    ///
+    /// - `elapsed_ms` and `inference_ms` are summed
+    /// - `step` keeps the highest step
+    /// - `start_time`, `text` and `text_hash` keep the first layer's value
+    /// - any other key is overwritten by the next layer
+    fn compose(&mut self, next: &Map);
+
+    fn elapsed(&self) -> Option<Duration> {
+        self.typed(keys::ELAPSED_MS)
+    }
+
+    fn set_elapsed(&mut self, elapsed: Duration) {
+        self.set_typed(keys::ELAPSED_MS, elapsed);
+    }
+
+    fn step(&self) -> Option<usize> {
+        self.typed(keys::STEP)
+    }
+
+    fn set_step(&mut self, step: usize) {
+        self.set_typed(keys::STEP, step);
+    }
+
+    fn text_hash(&self) -> Option<String> {
+        self.typed(keys::TEXT_HASH)
+    }
+
+    /// Store the blake3 hash of `text`, identifying the input without keeping it.
+    fn set_text_hash(&mut self, text: &str) {
+        self.set_typed(keys::TEXT_HASH, hash_text(text));
+    }
+}
+
+impl LayerMeta for Map {
+    fn typed<T: MetaValue>(&self, key: &str) -> Option<T> {
+        self.get(key).and_then(T::from_meta)
+    }
+
+    fn set_typed<T: MetaValue>(&mut self, key: &str, value: T) {
+        self.set(key, value.into_meta());
+    }
+
+    fn compose(&mut self, next: &Map) {
+        for (key, value) in next.iter() {
+            match key.as_str() {
+                keys::ELAPSED_MS | keys::INFERENCE_MS => {
+                    let total = self.typed::<Duration>(key).unwrap_or_default()
+                        + Duration::from_meta(value).unwrap_or_default();
+                    self.set_typed(key, total);
+                }
+                keys::STEP => {
+                    let step = self.step().max(usize::from_meta(value));
+                    if let Some(step) = step {
+                        self.set_step(step);
+                    }
+                }
+                keys::START_TIME | keys::TEXT | keys::TEXT_HASH if self.exists(key) => {}
+                _ => {
+                    self.set(key, value.clone());
+                }
+            }
+        }
+    }
+}
+
+fn hash_text(text: &str) -> String {
+    blake3::hash(text.as_bytes())
+        .as_bytes()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn typed_accessors_roundtrip() {
+        let mut meta = Map::new();
+        meta.set_elapsed(Duration::from_millis(1500));
+        meta.set_step(2);
+        meta.set_text_hash("hello");
+
+        assert_eq!(meta.elapsed(), Some(Duration::from_millis(1500)));
+        assert_eq!(
+            meta.get(keys::ELAPSED_MS).and_then(|v| v.as_int()),
+            Some(1500)
+        );
+        assert_eq!(meta.step(), Some(2));
+        assert_eq!(meta.text_hash().map(|h| h.len()), Some(64));
+    }
+
+    #[test]
+    fn typed_returns_none_on_type_mismatch() {
+        let mut meta = Map::new();
+        meta.set(keys::STEP, "first".into());
+
+        assert_eq!(meta.step(), None);
+        assert_eq!(meta.typed::<String>(keys::STEP).as_deref(), Some("first"));
+    }
+
+    #[test]
+    fn compose_applies_propagation_rules() {
+        let mut meta = Map::new();
+        meta.set_elapsed(Duration::from_millis(10));
+        meta.set_step(0);
+        meta.set_typed(keys::TEXT, "input".to_string());
+        meta.set("guard", "phatic".into());
+
+        let mut next = Map::new();
+        next.set_elapsed(Duration::from_millis(5));
+        next.set_step(1);
+        next.set_typed(keys::TEXT, "rewritten".to_string());
+        next.set("guard", "none".into());
+        next.set("items", 3i64.into());
+
+        meta.compose(&next);
+
+        assert_eq!(meta.elapsed(), Some(Duration::from_millis(15)));
+        assert_eq!(meta.step(), Some(1));
+        assert_eq!(meta.typed::<String>(keys::TEXT).as_deref(), Some("input"));
+        assert_eq!(meta.typed::<String>("guard").as_deref(), Some("none"));
+        assert_eq!(meta.typed::<i64>("items"), Some(3));
+    }
+}
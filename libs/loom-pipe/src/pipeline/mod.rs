@@ -1,11 +1,13 @@
 mod builder;
 mod context;
 mod layer;
+mod meta;
 mod node;
 mod pipeline;
 
 pub use builder::*;
 pub use context::*;
 pub use layer::*;
+pub use meta::*;
 pub use node::*;
 pub use pipeline::*;
@@ -1,13 +1,34 @@
 use std::any::{Any, TypeId};
+use std::time::Instant;
 
 use loom_error::{Error, ErrorCode, Result};
 
-use super::Layer;
+use super::{Layer, LayerContext, LayerResult};
 
 /// Type-erased layer for dynamic pipeline construction
 pub trait AnyLayer: Send + Sync {
-    fn process_any(&self, input: Box<dyn Any + Send>) -> Result<Box<dyn Any + Send>>;
+    /// Process input, keeping the layer's metadata alongside the output.
+    /// When a `deadline` is given it is set on the input context first.
+    fn process_until(
+        &self,
+        input: Box<dyn Any + Send>,
+        deadline: Option<Instant>,
+    ) -> Result<LayerResult<Box<dyn Any + Send>>>;
+
+    /// Process input, keeping the layer's metadata alongside the output.
+    fn process_meta(&self, input: Box<dyn Any + Send>) -> Result<LayerResult<Box<dyn Any + Send>>> {
+        self.process_until(input, None)
+    }
+
+    fn process_any(&self, input: Box<dyn Any + Send>) -> Result<Box<dyn Any + Send>> {
+        self.process_meta(input).map(|result| result.output)
+    }
+
+    /// Process borrowed input, or `None` if the layer only accepts owned input.
+    fn process_ref_any(&self, input: &dyn Any) -> Option<Result<LayerResult<Box<dyn Any + Send>>>>;
+
     fn name(&self) -> &'static str;
+    fn version(&self) -> &'static str;
     fn input_type_id(&self) -> TypeId;
     fn output_type_id(&self) -> TypeId;
 }
@@ -28,23 +49,54 @@ where
     L::Input: 'static,
     L::Output: 'static,
 {
-    fn process_any(&self, input: Box<dyn Any + Send>) -> Result<Box<dyn Any + Send>> {
-        let typed_input = input.downcast::<L::Input>().map_err(|_| {
+    fn process_until(
+        &self,
+        input: Box<dyn Any + Send>,
+        deadline: Option<Instant>,
+    ) -> Result<LayerResult<Box<dyn Any + Send>>> {
+        let mut typed_input = input.downcast::<L::Input>().map_err(|_| {
             Error::builder()
                 .code(ErrorCode::BadArguments)
                 .message("Type mismatch in pipeline")
                 .build()
         })?;
 
+        if let Some(deadline) = deadline {
+            typed_input.set_deadline(deadline);
+        }
+
         let result = self.layer.process(*typed_input)?;
-        // Extract the inner output from LayerResult
-        Ok(Box::new(result.output))
+
+        Ok(LayerResult {
+            meta: result.meta,
+            output: Box::new(result.output),
+        })
+    }
+
+    fn process_ref_any(&self, input: &dyn Any) -> Option<Result<LayerResult<Box<dyn Any + Send>>>> {
+        let Some(typed_input) = input.downcast_ref::<L::Input>() else {
+            return Some(Err(Error::builder()
+                .code(ErrorCode::BadArguments)
+                .message("Type mismatch in pipeline")
+                .build()));
+        };
+
+        let result = self.layer.process_ref(typed_input)?;
+
+        Some(result.map(|result| LayerResult {
+            meta: result.meta,
+            output: Box::new(result.output) as Box<dyn Any + Send>,
+        }))
     }
 
     fn name(&self) -> &'static str {
         self.layer.name()
     }
 
+    fn version(&self) -> &'static str {
+        self.layer.version()
+    }
+
     fn input_type_id(&self) -> TypeId {
         TypeId::of::<L::Input>()
     }
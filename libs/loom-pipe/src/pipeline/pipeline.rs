@@ -1,8 +1,9 @@
 use std::any::Any;
+use std::time::Instant;
 
 use loom_error::{Error, ErrorCode, Result};
 
-use super::AnyLayer;
+use super::{AnyLayer, LayerMeta, LayerResult};
 
 /// Internal stage representation
 pub enum PipelineStage {
@@ -25,20 +26,65 @@ impl<Input: Send + 'static, Output: Send + 'static> Pipeline<Input, Output> {
 
     /// Execute pipeline synchronously
     pub fn execute(&self, input: Input) -> Result<Output> {
+        self.run(input, None).map(|result| result.output)
+    }
+
+    /// Execute pipeline synchronously, keeping the metadata of every stage.
+    ///
+    /// Each stage's metadata is merged into the result with [`LayerMeta::compose`],
+    /// so timings add up across stages while the input text stays that of the first stage.
+    pub fn execute_with_meta(&self, input: Input) -> Result<LayerResult<Output>> {
+        self.run(input, None)
+    }
+
+    /// Execute pipeline synchronously within a time budget.
+    ///
+    /// The deadline is set on every stage's context, so layers can consult
+    /// [`LayerContext::deadline`](super::LayerContext::deadline) before starting
+    /// expensive work, and no further stage starts once it has passed; the
+    /// pipeline then fails with a `Timeout` error.
+    ///
+    /// ```ignore
+    /// let deadline = Instant::now() + Duration::from_secs(2);
+    /// let result = pipeline.run_with_deadline(ctx, deadline)?;
+    /// ```
+    pub fn run_with_deadline(&self, input: Input, deadline: Instant) -> Result<Output> {
+        self.run(input, Some(deadline)).map(|result| result.output)
+    }
+
+    fn run(&self, input: Input, deadline: Option<Instant>) -> Result<LayerResult<Output>> {
         let mut current: Box<dyn Any + Send> = Box::new(input);
+        let mut meta = loom_core::Map::new();
 
         for stage in &self.stages {
-            current = match stage {
-                PipelineStage::Layer(layer) => layer.process_any(current)?,
+            let result = match stage {
+                PipelineStage::Layer(layer) => {
+                    if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                        return Err(Error::builder()
+                            .code(ErrorCode::Timeout)
+                            .message(format!(
+                                "Pipeline deadline exceeded before layer '{}'",
+                                layer.name()
+                            ))
+                            .build());
+                    }
+
+                    layer.process_until(current, deadline)?
+                }
             };
+
+            meta.compose(&result.meta);
+            current = result.output;
         }
 
-        current.downcast::<Output>().map(|b| *b).map_err(|_| {
+        let output = current.downcast::<Output>().map(|b| *b).map_err(|_| {
             Error::builder()
                 .code(ErrorCode::Unknown)
                 .message("Pipeline output type mismatch")
                 .build()
-        })
+        })?;
+
+        Ok(LayerResult { meta, output })
     }
 
     /// Get the number of stages in the pipeline
@@ -51,3 +97,80 @@ impl<Input: Send + 'static, Output: Send + 'static> Pipeline<Input, Output> {
         self.stages.is_empty()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use loom_core::Map;
+
+    use super::*;
+    use crate::pipeline::{Layer, LayerContext, PipelineBuilder};
+
+    struct Ctx {
+        meta: Map,
+        deadline: Option<Instant>,
+    }
+
+    impl LayerContext for Ctx {
+        fn text(&self) -> &str {
+            ""
+        }
+
+        fn step(&self) -> usize {
+            0
+        }
+
+        fn meta(&self) -> &Map {
+            &self.meta
+        }
+
+        fn deadline(&self) -> Option<Instant> {
+            self.deadline
+        }
+
+        fn set_deadline(&mut self, deadline: Instant) {
+            self.deadline = Some(deadline);
+        }
+    }
+
+    /// Reports the time left until its context's deadline.
+    struct Remaining;
+
+    impl Layer for Remaining {
+        type Input = Ctx;
+        type Output = Option<Duration>;
+
+        fn process(&self, input: Ctx) -> Result<LayerResult<Option<Duration>>> {
+            Ok(LayerResult::new(input.remaining()))
+        }
+    }
+
+    fn ctx() -> Ctx {
+        Ctx {
+            meta: Map::new(),
+            deadline: None,
+        }
+    }
+
+    #[test]
+    fn deadline_is_set_on_context() {
+        let pipeline = PipelineBuilder::<Ctx, Ctx>::new().then(Remaining).build();
+
+        assert_eq!(pipeline.execute(ctx()).unwrap(), None);
+
+        let deadline = Instant::now() + Duration::from_secs(60);
+        let remaining = pipeline.run_with_deadline(ctx(), deadline).unwrap();
+        assert!(remaining.is_some_and(|r| r > Duration::ZERO));
+    }
+
+    #[test]
+    fn expired_deadline_fails_with_timeout() {
+        let pipeline = PipelineBuilder::<Ctx, Ctx>::new().then(Remaining).build();
+
+        let err = pipeline
+            .run_with_deadline(ctx(), Instant::now())
+            .unwrap_err();
+        assert!(err.code().is_timeout());
+    }
+}
@@ -0,0 +1,73 @@
+//! Benchmarks for `ScoreLayer::score_batch` across batch sizes.
+//!
+//! Loads the zero-shot model, so it is gated behind the `int` feature:
+//! `cargo bench -p loom-runtime --features int --bench score`.
+//! Set `LOOM_BENCH_SAMPLE_SIZE` to trade accuracy for speed (default: 10,
+//! criterion's minimum, since each iteration runs model inference).
+
+use std::hint::black_box;
+
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use loom_runtime::ScoreConfig;
+
+#[path = "../../bench.rs"]
+mod bench;
+
+const TEXTS: &[&str] = &[
+    "oh my god, I'm going to be late for work!",
+    "I need to pick up groceries after the meeting tomorrow.",
+    "My sister just got engaged and I'm so happy for her.",
+    "hi how are you?",
+    "I prefer tea over coffee in the mornings.",
+    "The deadline for the quarterly report moved to Friday.",
+    "I've been feeling really anxious about the surgery next week.",
+    "thanks!",
+];
+
+fn score_config() -> ScoreConfig {
+    let json = r#"{
+        "model": { "type": "zero_shot_classification", "model": "Bart" },
+        "categories": {
+            "sentiment": {
+                "labels": {
+                    "positive": { "hypothesis": "The speaker is expressing a positive, happy, or optimistic sentiment." },
+                    "negative": { "hypothesis": "The speaker is expressing a negative, unhappy, or pessimistic sentiment." }
+                }
+            },
+            "context": {
+                "labels": {
+                    "task": { "hypothesis": "The speaker is describing something they need to do, remember, or a task to complete." },
+                    "phatic": { "hypothesis": "This is just social pleasantry, small talk, or acknowledgment with no substantive information." }
+                }
+            }
+        }
+    }"#;
+
+    serde_json::from_str(json).expect("valid score config")
+}
+
+fn bench_score_batch(c: &mut Criterion) {
+    let layer = score_config().build().expect("score layer");
+    let mut group = c.benchmark_group("score/batch");
+
+    for batch_size in [1, 4, 8, 16, 32] {
+        let texts: Vec<&str> = TEXTS.iter().copied().cycle().take(batch_size).collect();
+        group.throughput(Throughput::Elements(batch_size as u64));
+
+        group.bench_with_input(
+            BenchmarkId::from_parameter(batch_size),
+            &texts,
+            |b, texts| b.iter(|| layer.score_batch(black_box(texts)).expect("score_batch")),
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group! {
+    name = benches;
+    config = bench::config(10);
+    targets = bench_score_batch
+}
+
+criterion_main!(benches);
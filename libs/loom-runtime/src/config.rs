@@ -1,5 +1,10 @@
+use std::collections::BTreeMap;
 use std::path::PathBuf;
+use std::time::Duration;
 
+use loom_cortex::BatchBackoff;
+use loom_io::sources::RetryPolicy;
+use loom_signal::{Emitter, FilterEmitter, Level};
 use serde::{Deserialize, Serialize};
 use serde_valid::Validate;
 
@@ -23,6 +28,7 @@ use serde_valid::Validate;
 /// let score_config: ScoreConfig = config.get_section(&score_path).bind()?;
 /// ```
 #[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct LoomConfig {
     /// Output path for results
     #[serde(default)]
@@ -41,6 +47,25 @@ pub struct LoomConfig {
     #[serde(default = "LoomConfig::default_batch_size")]
     #[validate(minimum = 1)]
     pub batch_size: usize,
+
+    /// How batches shrink and retry when the device runs out of memory
+    #[serde(default)]
+    pub backoff: BatchBackoff,
+
+    /// Abandon a batch whose forward pass runs longer than this many
+    /// milliseconds and fail with a timeout error (unset waits indefinitely).
+    /// An abandoned pass keeps the scorer busy until it returns, so scoring
+    /// calls also stop waiting for the scorer after this long
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub inference_timeout_ms: Option<u64>,
+
+    /// How `load` and `save` retry and time out DataSource calls
+    #[serde(default)]
+    pub io: IoRetry,
+
+    /// Which signals reach the runtime's emitters
+    #[serde(default)]
+    pub signals: SignalsConfig,
 }
 
 impl LoomConfig {
@@ -53,6 +78,39 @@ impl LoomConfig {
     }
 }
 
+#[cfg(feature = "schema")]
+impl LoomConfig {
+    /// JSON Schema of a whole config file (`schema` feature): these settings
+    /// at the root and the score layer's [`ScoreConfig`](crate::ScoreConfig)
+    /// under `layers.score`. Other layers are left unchecked.
+    pub fn schema() -> serde_json::Value {
+        loom_config::schema::<ConfigFile>()
+    }
+}
+
+/// Shape of a config file, for [`LoomConfig::schema`]
+#[cfg(feature = "schema")]
+#[allow(dead_code)]
+#[derive(Deserialize, schemars::JsonSchema)]
+#[schemars(title = "LoomConfig")]
+struct ConfigFile {
+    #[serde(flatten)]
+    loom: LoomConfig,
+
+    /// Layer configs, keyed by layer name
+    #[serde(default)]
+    layers: ConfigFileLayers,
+}
+
+#[cfg(feature = "schema")]
+#[allow(dead_code)]
+#[derive(Default, Deserialize, schemars::JsonSchema)]
+struct ConfigFileLayers {
+    /// Zero-shot scoring layer
+    #[serde(default)]
+    score: Option<crate::ScoreConfig>,
+}
+
 impl Default for LoomConfig {
     fn default() -> Self {
         Self {
@@ -60,7 +118,193 @@ impl Default for LoomConfig {
             strict: false,
             concurrency: Self::default_concurrency(),
             batch_size: Self::default_batch_size(),
+            backoff: BatchBackoff::default(),
+            inference_timeout_ms: None,
+            io: IoRetry::default(),
+            signals: SignalsConfig::default(),
+        }
+    }
+}
+
+/// Retry policy for DataSource reads and writes.
+///
+/// Transient failures (timeouts, dropped connections, 5xx responses) are
+/// retried up to `max_retries` times, waiting `backoff_ms` before the first
+/// retry and `factor` times longer before each next one, up to
+/// `max_backoff_ms`, less a random share of up to `jitter`. Other failures,
+/// such as a missing file, fail at once.
+///
+/// This is the [`RetryPolicy`] of a `RetrySource` in config form, plus a per
+/// attempt timeout. The runtime already retries its sources under it, so
+/// don't also register them wrapped in a `RetrySource`: the two layers would
+/// stack, running up to `(max_retries + 1)²` attempts per call. Set
+/// `max_retries` to 0 to leave retrying to the source instead.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct IoRetry {
+    /// Retries per operation before giving up
+    #[serde(default = "IoRetry::default_max_retries")]
+    pub max_retries: usize,
+
+    /// Delay before the first retry, in milliseconds
+    #[serde(default = "IoRetry::default_backoff_ms")]
+    pub backoff_ms: u64,
+
+    /// Multiplier applied to the delay on each retry
+    #[serde(default = "IoRetry::default_factor")]
+    pub factor: f32,
+
+    /// Longest delay between retries, in milliseconds
+    #[serde(default = "IoRetry::default_max_backoff_ms")]
+    pub max_backoff_ms: u64,
+
+    /// Share of each delay, in `[0, 1]`, that may be randomly cut
+    #[serde(default = "IoRetry::default_jitter")]
+    pub jitter: f32,
+
+    /// Abandon an attempt running longer than this many milliseconds and
+    /// count it as a transient failure (unset waits indefinitely). Only
+    /// sources that await their I/O can be abandoned mid-call; a blocking
+    /// `FileSystemSource` read runs to completion first
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_ms: Option<u64>,
+}
+
+impl IoRetry {
+    fn default_max_retries() -> usize {
+        3
+    }
+
+    fn default_backoff_ms() -> u64 {
+        500
+    }
+
+    fn default_factor() -> f32 {
+        2.0
+    }
+
+    fn default_max_backoff_ms() -> u64 {
+        30_000
+    }
+
+    fn default_jitter() -> f32 {
+        0.5
+    }
+
+    /// Delay before retry number `retry` (0-based), without jitter
+    pub fn delay(&self, retry: usize) -> Duration {
+        RetryPolicy::from(self).delay(retry)
+    }
+
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout_ms.map(Duration::from_millis)
+    }
+}
+
+impl Default for IoRetry {
+    fn default() -> Self {
+        Self {
+            max_retries: Self::default_max_retries(),
+            backoff_ms: Self::default_backoff_ms(),
+            factor: Self::default_factor(),
+            max_backoff_ms: Self::default_max_backoff_ms(),
+            jitter: Self::default_jitter(),
+            timeout_ms: None,
+        }
+    }
+}
+
+impl From<&IoRetry> for RetryPolicy {
+    fn from(io: &IoRetry) -> Self {
+        Self {
+            max_retries: io.max_retries,
+            backoff: Duration::from_millis(io.backoff_ms),
+            factor: io.factor,
+            max_backoff: Duration::from_millis(io.max_backoff_ms),
+            jitter: io.jitter,
+        }
+    }
+}
+
+/// Filters applied to signals before they reach the runtime's emitters,
+/// e.g. to keep `eval.progress` events from drowning out the rest.
+///
+/// Name globs match the whole signal name, with `*` matching any run of
+/// characters and `?` any one character.
+///
+/// # Example
+/// ```yaml
+/// signals:
+///   level: info
+///   exclude: ["io.*"]
+///   sample:
+///     eval.progress: 0.01
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct SignalsConfig {
+    /// Drop signals below this level
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub level: Option<Level>,
+
+    /// Keep only signals whose name matches one of these globs (all when empty)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub include: Vec<String>,
+
+    /// Drop signals whose name matches one of these globs
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclude: Vec<String>,
+
+    /// Fraction (0 to 1) of the signals matching each glob to keep
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub sample: BTreeMap<String, f64>,
+}
+
+impl SignalsConfig {
+    /// Check every sample ratio is a number from 0 to 1
+    pub fn validate(&self) -> loom_error::Result<()> {
+        match self
+            .sample
+            .iter()
+            .find(|(_, ratio)| !(0.0..=1.0).contains(*ratio))
+        {
+            None => Ok(()),
+            Some((glob, ratio)) => Err(loom_error::Error::builder()
+                .code(loom_error::ErrorCode::BadArguments)
+                .message(format!(
+                    "signals.sample.{}: ratio {} is not between 0 and 1",
+                    glob, ratio
+                ))
+                .build()),
+        }
+    }
+
+    /// `inner` wrapped in a [`FilterEmitter`] applying these filters. Sample
+    /// globs are tried longest first, so `eval.progress` takes precedence
+    /// over `eval.*`.
+    pub fn emitter<E: Emitter>(&self, inner: E) -> FilterEmitter<E> {
+        let mut emitter = FilterEmitter::new(inner);
+
+        if let Some(level) = self.level {
+            emitter = emitter.with_level(level);
+        }
+
+        for glob in &self.include {
+            emitter = emitter.with_include(glob.as_str());
         }
+
+        for glob in &self.exclude {
+            emitter = emitter.with_exclude(glob.as_str());
+        }
+
+        let mut samples: Vec<_> = self.sample.iter().collect();
+        samples.sort_by(|(a, _), (b, _)| b.len().cmp(&a.len()));
+
+        for (glob, ratio) in samples {
+            emitter = emitter.with_sample(glob.as_str(), *ratio);
+        }
+
+        emitter
     }
 }
 
@@ -75,6 +319,36 @@ mod tests {
         assert_eq!(config.batch_size, 8);
         assert!(!config.strict);
         assert!(config.output.is_none());
+        assert_eq!(config.backoff, BatchBackoff::default());
+        assert!(config.inference_timeout_ms.is_none());
+        assert_eq!(config.io, IoRetry::default());
+        assert_eq!(config.signals, SignalsConfig::default());
+    }
+
+    #[test]
+    fn io_retry_delay_grows_to_cap() {
+        let retry = IoRetry {
+            backoff_ms: 100,
+            factor: 2.0,
+            max_backoff_ms: 500,
+            ..IoRetry::default()
+        };
+
+        assert_eq!(retry.delay(0), Duration::from_millis(100));
+        assert_eq!(retry.delay(1), Duration::from_millis(200));
+        assert_eq!(retry.delay(2), Duration::from_millis(400));
+        assert_eq!(retry.delay(3), Duration::from_millis(500));
+        assert_eq!(retry.delay(40), Duration::from_millis(500));
+    }
+
+    #[test]
+    fn io_retry_deserializes_with_defaults() {
+        let json = r#"{ "io": { "max_retries": 5, "timeout_ms": 30000 } }"#;
+        let config: LoomConfig = serde_json::from_str(json).unwrap();
+
+        assert_eq!(config.io.max_retries, 5);
+        assert_eq!(config.io.timeout(), Some(Duration::from_secs(30)));
+        assert_eq!(config.io.backoff_ms, 500);
     }
 
     #[test]
@@ -124,6 +398,19 @@ mod tests {
         assert_eq!(config.output, Some(PathBuf::from("results.json")));
     }
 
+    #[cfg(feature = "schema")]
+    #[test]
+    fn schema_covers_file_layout() {
+        let schema = LoomConfig::schema();
+        let properties = &schema["properties"];
+
+        assert_eq!(schema["title"], "LoomConfig");
+        assert!(properties["concurrency"].is_object());
+        assert!(properties["io"].is_object());
+        assert!(properties["layers"].is_object());
+        assert!(schema["$defs"]["ScoreConfig"]["properties"]["threshold"].is_object());
+    }
+
     #[test]
     fn config_ignores_unknown_fields() {
         // LoomConfig should deserialize successfully even with layer configs present
@@ -139,4 +426,36 @@ mod tests {
         let config: LoomConfig = serde_json::from_str(json).unwrap();
         assert_eq!(config.concurrency, 8);
     }
+
+    #[test]
+    fn signals_config_filters_emitter() {
+        use loom_signal::Signal;
+        use loom_signal::consumers::MemoryEmitter;
+
+        let json = r#"{
+            "signals": {
+                "level": "info",
+                "exclude": ["io.*"],
+                "sample": { "eval.*": 0.0, "eval.progress": 0.5 }
+            }
+        }"#;
+        let config: LoomConfig = serde_json::from_str(json).unwrap();
+        assert_eq!(config.signals.level, Some(Level::Info));
+
+        let memory = MemoryEmitter::new();
+        let emitter = config.signals.emitter(memory.clone());
+        let emit = |name: &str, level: Level| {
+            emitter.emit(Signal::new().name(name).level(level).build());
+        };
+
+        emit("score.invoke", Level::Debug);
+        emit("io.read", Level::Info);
+        emit("eval.batch", Level::Info);
+
+        for _ in 0..4 {
+            emit("eval.progress", Level::Info);
+        }
+
+        assert_eq!(memory.len(), 2);
+    }
 }
@@ -0,0 +1,216 @@
+use std::collections::HashSet;
+
+use loom_cortex::config::{CortexModelConfig, CortexSummarizationConfig};
+use loom_error::{Error, ErrorCode, Result};
+use serde::{Deserialize, Serialize};
+use serde_valid::Validate;
+
+use super::{ConsolidateItem, ConsolidateLayer};
+
+/// Configuration for the memory consolidation layer
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct ConsolidateConfig {
+    /// Summarization model used to write the consolidated text
+    #[serde(default = "ConsolidateConfig::model")]
+    pub model: CortexModelConfig,
+
+    /// Maximum span in seconds between the first and last item of a group
+    #[serde(default = "ConsolidateConfig::window_secs")]
+    #[validate(minimum = 1)]
+    pub window_secs: u64,
+
+    /// Minimum number of items required to form a group
+    #[serde(default = "ConsolidateConfig::min_group_size")]
+    #[validate(minimum = 2)]
+    pub min_group_size: usize,
+
+    /// Maximum number of items per group, larger windows are split
+    #[serde(default = "ConsolidateConfig::max_group_size")]
+    #[validate(minimum = 2)]
+    pub max_group_size: usize,
+}
+
+impl ConsolidateConfig {
+    fn model() -> CortexModelConfig {
+        CortexModelConfig::Summarization(CortexSummarizationConfig::default())
+    }
+
+    fn window_secs() -> u64 {
+        3600
+    }
+
+    fn min_group_size() -> usize {
+        2
+    }
+
+    fn max_group_size() -> usize {
+        20
+    }
+
+    /// The grouping window as a duration
+    pub fn window(&self) -> chrono::Duration {
+        chrono::Duration::seconds(self.window_secs as i64)
+    }
+
+    /// Group items that share a group key and fall within the same time window.
+    ///
+    /// Items are ordered by (group, created_at); a new group starts when the key
+    /// changes, the window is exceeded or `max_group_size` is reached. Groups
+    /// smaller than `min_group_size` are dropped, as are repeated item ids.
+    pub fn group(
+        &self,
+        items: impl IntoIterator<Item = ConsolidateItem>,
+    ) -> Vec<Vec<ConsolidateItem>> {
+        let mut seen = HashSet::new();
+        let mut items: Vec<_> = items
+            .into_iter()
+            .filter(|item| seen.insert(item.id.clone()))
+            .collect();
+
+        items.sort_by(|a, b| {
+            a.group
+                .cmp(&b.group)
+                .then_with(|| a.created_at.cmp(&b.created_at))
+        });
+
+        let mut groups = Vec::new();
+        let mut current: Vec<ConsolidateItem> = Vec::new();
+
+        for item in items {
+            let split = match current.first() {
+                None => false,
+                Some(first) => {
+                    first.group != item.group
+                        || item.created_at - first.created_at > self.window()
+                        || current.len() >= self.max_group_size
+                }
+            };
+
+            if split {
+                groups.push(std::mem::take(&mut current));
+            }
+
+            current.push(item);
+        }
+
+        if !current.is_empty() {
+            groups.push(current);
+        }
+
+        groups.retain(|g| g.len() >= self.min_group_size);
+        groups
+    }
+
+    /// Build a ConsolidateLayer from this configuration
+    pub fn build(self) -> Result<ConsolidateLayer> {
+        self.validate()
+            .map_err(|e| Error::builder().message(e.to_string()).build())?;
+
+        if self.min_group_size > self.max_group_size {
+            return Err(Error::builder()
+                .code(ErrorCode::BadArguments)
+                .message("min_group_size must not exceed max_group_size")
+                .build());
+        }
+
+        if !self.model.is_summarization() {
+            return Err(Error::builder()
+                .code(ErrorCode::BadArguments)
+                .message("ConsolidateLayer requires a Summarization model")
+                .build());
+        }
+
+        let model = self.model.clone().build()?;
+        Ok(ConsolidateLayer::new(model, self))
+    }
+}
+
+impl Default for ConsolidateConfig {
+    fn default() -> Self {
+        Self {
+            model: Self::model(),
+            window_secs: Self::window_secs(),
+            min_group_size: Self::min_group_size(),
+            max_group_size: Self::max_group_size(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(id: &str, group: &str, minutes: i64) -> ConsolidateItem {
+        let at =
+            chrono::DateTime::from_timestamp(0, 0).unwrap() + chrono::Duration::minutes(minutes);
+        ConsolidateItem::new(id, group, format!("text {}", id), at)
+    }
+
+    fn ids(groups: &[Vec<ConsolidateItem>]) -> Vec<Vec<&str>> {
+        groups
+            .iter()
+            .map(|g| g.iter().map(|i| i.id.as_str()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn empty_config_uses_defaults() {
+        let config: ConsolidateConfig = serde_json::from_str("{}").unwrap();
+
+        assert!(config.model.is_summarization());
+        assert_eq!(config.window_secs, 3600);
+        assert_eq!(config.min_group_size, 2);
+        assert_eq!(config.max_group_size, 20);
+    }
+
+    #[test]
+    fn groups_by_key_and_window() {
+        let groups = ConsolidateConfig::default().group([
+            item("a", "s1", 0),
+            item("c", "s1", 90),
+            item("b", "s1", 30),
+            item("d", "s1", 100),
+            item("e", "s2", 0),
+            item("f", "s2", 10),
+        ]);
+
+        assert_eq!(
+            ids(&groups),
+            vec![vec!["a", "b"], vec!["c", "d"], vec!["e", "f"]]
+        );
+    }
+
+    #[test]
+    fn drops_small_groups_and_repeated_ids() {
+        let groups = ConsolidateConfig::default().group([
+            item("a", "s1", 0),
+            item("a", "s2", 0),
+            item("b", "s2", 200),
+        ]);
+
+        assert!(groups.is_empty());
+    }
+
+    #[test]
+    fn splits_groups_at_max_size() {
+        let config = ConsolidateConfig {
+            max_group_size: 2,
+            ..Default::default()
+        };
+
+        let groups = config.group((0..5).map(|i| item(&i.to_string(), "s1", i)));
+        assert_eq!(ids(&groups), vec![vec!["0", "1"], vec!["2", "3"]]);
+    }
+
+    #[test]
+    fn build_rejects_invalid_group_sizes() {
+        let config = ConsolidateConfig {
+            min_group_size: 5,
+            max_group_size: 3,
+            ..Default::default()
+        };
+
+        let err = config.build().err().unwrap();
+        assert_eq!(*err.code(), ErrorCode::BadArguments);
+    }
+}
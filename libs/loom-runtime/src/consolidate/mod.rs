@@ -0,0 +1,114 @@
+//! Memory consolidation: groups related memories (same source, same time
+//! window) and summarizes them into a single consolidated text.
+
+mod config;
+
+pub use config::*;
+
+use loom_cortex::CortexModel;
+use loom_error::{Error, ErrorCode, Result};
+use loom_pipe::{LayerMeta, LayerResult};
+use serde::{Deserialize, Serialize};
+
+use crate::Context;
+
+/// A memory candidate for consolidation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConsolidateItem {
+    /// Identifier of the stored memory
+    pub id: String,
+    /// Grouping key, typically the source the memory was extracted from
+    pub group: String,
+    /// Memory text
+    pub text: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl ConsolidateItem {
+    pub fn new(
+        id: impl Into<String>,
+        group: impl Into<String>,
+        text: impl Into<String>,
+        created_at: chrono::DateTime<chrono::Utc>,
+    ) -> Self {
+        Self {
+            id: id.into(),
+            group: group.into(),
+            text: text.into(),
+            created_at,
+        }
+    }
+}
+
+/// Summarizes groups of related memories with a summarization model.
+///
+/// Grouping is done with [`ConsolidateConfig::group`]; the layer turns one
+/// group into the text of the consolidated memory.
+pub struct ConsolidateLayer {
+    model: CortexModel,
+    config: ConsolidateConfig,
+}
+
+impl ConsolidateLayer {
+    pub(crate) fn new(model: CortexModel, config: ConsolidateConfig) -> Self {
+        Self { model, config }
+    }
+
+    /// Get the configuration for this layer
+    pub fn config(&self) -> &ConsolidateConfig {
+        &self.config
+    }
+
+    /// Group items into consolidation candidates
+    pub fn group(
+        &self,
+        items: impl IntoIterator<Item = ConsolidateItem>,
+    ) -> Vec<Vec<ConsolidateItem>> {
+        self.config.group(items)
+    }
+
+    /// Summarize a group of items into a single text
+    pub fn summarize(&self, items: &[ConsolidateItem]) -> Result<String> {
+        let model = match &self.model {
+            CortexModel::Summarization { model, .. } => model,
+            _ => {
+                return Err(Error::builder()
+                    .code(ErrorCode::BadArguments)
+                    .message("ConsolidateLayer requires a Summarization model")
+                    .build());
+            }
+        };
+
+        let text = items
+            .iter()
+            .map(|i| i.text.trim())
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let mut summaries = model.summarize(&[text])?;
+        Ok(summaries.pop().unwrap_or_default())
+    }
+
+    /// Invoke the consolidate layer directly with a context
+    pub fn invoke(&self, ctx: Context<Vec<ConsolidateItem>>) -> Result<LayerResult<String>> {
+        let summary = self.summarize(&ctx.input)?;
+        let mut result = LayerResult::new(summary);
+
+        result.meta.set_step(ctx.step);
+        result.meta.set("items", ctx.input.len().into());
+        Ok(result)
+    }
+}
+
+impl loom_pipe::Layer for ConsolidateLayer {
+    type Input = Context<Vec<ConsolidateItem>>;
+    type Output = String;
+
+    fn process(&self, input: Self::Input) -> Result<LayerResult<Self::Output>> {
+        self.invoke(input)
+    }
+
+    fn name(&self) -> &'static str {
+        "consolidate"
+    }
+}
@@ -1,19 +1,56 @@
 use std::sync::Arc;
+use std::time::Instant;
 
 use loom_core::Map;
 use loom_io::DataSource;
 use loom_pipe::LayerContext;
-use loom_signal::Signal;
+use loom_signal::{Emitter, Signal, SpanGuard};
+use serde::{Deserialize, Serialize};
 
 use crate::Runtime;
 use crate::eval::Sample;
 
+/// A previous turn in the conversation a text belongs to.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Turn {
+    pub speaker: String,
+    /// Position of the turn in the conversation
+    pub turn: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<chrono::DateTime<chrono::Utc>>,
+    pub text: String,
+}
+
+impl Turn {
+    pub fn new(speaker: &str, turn: usize, text: &str) -> Self {
+        Self {
+            speaker: speaker.to_string(),
+            turn,
+            timestamp: None,
+            text: text.to_string(),
+        }
+    }
+
+    pub fn with_timestamp(mut self, timestamp: chrono::DateTime<chrono::Utc>) -> Self {
+        self.timestamp = Some(timestamp);
+        self
+    }
+}
+
 /// Single-item context bound to runtime (internal).
 pub struct Context<Input> {
     runtime: Option<Arc<Runtime>>,
     pub meta: Map,
     pub step: usize,
     pub text: String,
+    /// Speaker of `text`, if known
+    pub speaker: Option<String>,
+    /// Previous turns of the conversation, oldest first
+    pub history: Vec<Turn>,
+    /// Point in time by which processing should finish, if any
+    pub deadline: Option<Instant>,
+    /// Trace the spans of this item join, e.g. a request's correlation id
+    pub trace_id: Option<String>,
     pub input: Input,
 }
 
@@ -26,10 +63,66 @@ impl<Input> Context<Input> {
             meta: Map::default(),
             step: 0,
             text: text.to_string(),
+            speaker: None,
+            history: Vec::new(),
+            deadline: None,
+            trace_id: None,
             input,
         }
     }
 
+    /// Set the speaker of the text.
+    pub fn with_speaker(mut self, speaker: &str) -> Self {
+        self.speaker = Some(speaker.to_string());
+        self
+    }
+
+    /// Set the conversation history preceding the text (oldest first).
+    pub fn with_history(mut self, history: Vec<Turn>) -> Self {
+        self.history = history;
+        self
+    }
+
+    /// Set the point in time by which processing should finish.
+    pub fn with_deadline(mut self, deadline: Instant) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Set the trace the spans of this item join.
+    pub fn with_trace_id(mut self, trace_id: &str) -> Self {
+        self.trace_id = Some(trace_id.to_string());
+        self
+    }
+
+    /// The text preceded by up to `turns` previous turns, one `speaker: text`
+    /// line each (just `text` for turns without a speaker). Returns the bare
+    /// text when there is no history to include.
+    pub fn premise(&self, turns: usize) -> String {
+        let start = self.history.len().saturating_sub(turns);
+        let previous = &self.history[start..];
+
+        if previous.is_empty() {
+            return self.text.clone();
+        }
+
+        let mut premise = String::new();
+
+        for turn in previous {
+            match turn.speaker.as_str() {
+                "" => premise.push_str(&format!("{}\n", turn.text)),
+                speaker => premise.push_str(&format!("{}: {}\n", speaker, turn.text)),
+            }
+        }
+
+        match &self.speaker {
+            Some(speaker) => premise.push_str(&format!("{}: {}", speaker, self.text)),
+            None => premise.push_str(&self.text),
+        }
+
+        premise
+    }
+
     /// Emit a signal through the runtime's emitter.
     /// No-op if context was created without a runtime.
     pub fn emit(&self, signal: Signal) {
@@ -38,6 +131,23 @@ impl<Input> Context<Input> {
         }
     }
 
+    /// Start a span emitted through the runtime's emitter when the returned
+    /// guard is dropped, in the context's trace if it has one.
+    /// Discarded if context was created without a runtime.
+    pub fn span(&self, name: &str) -> SpanGuard<'_, dyn Emitter + Send + Sync> {
+        let emitter: &(dyn Emitter + Send + Sync) = match &self.runtime {
+            Some(runtime) => runtime.signals.as_ref(),
+            None => &Discard,
+        };
+
+        let span = SpanGuard::new(emitter, name);
+
+        match &self.trace_id {
+            Some(trace_id) => span.with_trace_id(trace_id.as_str()),
+            None => span,
+        }
+    }
+
     /// Get a data source by name from the runtime.
     /// Returns None if context was created without a runtime.
     pub fn data_source(&self, name: &str) -> Option<&dyn DataSource> {
@@ -62,6 +172,21 @@ impl<Input: Send + 'static> LayerContext for Context<Input> {
     fn meta(&self) -> &Map {
         &self.meta
     }
+
+    fn deadline(&self) -> Option<Instant> {
+        self.deadline
+    }
+
+    fn set_deadline(&mut self, deadline: Instant) {
+        self.deadline = Some(deadline);
+    }
+}
+
+/// Emitter of spans started by a context without a runtime
+struct Discard;
+
+impl Emitter for Discard {
+    fn emit(&self, _: Signal) {}
 }
 
 /// Batch context for processing multiple samples (internal).
@@ -70,6 +195,8 @@ pub struct BatchContext {
     samples: Vec<Sample>,
     step: usize,
     meta: Map,
+    deadline: Option<Instant>,
+    trace_id: Option<String>,
 }
 
 impl BatchContext {
@@ -80,9 +207,28 @@ impl BatchContext {
             samples,
             step: 0,
             meta: Map::default(),
+            deadline: None,
+            trace_id: None,
         }
     }
 
+    /// Set the point in time by which processing should finish.
+    pub fn with_deadline(mut self, deadline: Instant) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Set the trace the spans of this batch join.
+    pub fn with_trace_id(mut self, trace_id: &str) -> Self {
+        self.trace_id = Some(trace_id.to_string());
+        self
+    }
+
+    /// The trace the spans of this batch join, if set
+    pub fn trace_id(&self) -> Option<&str> {
+        self.trace_id.as_deref()
+    }
+
     pub fn samples(&self) -> &[Sample] {
         &self.samples
     }
@@ -122,4 +268,69 @@ impl LayerContext for BatchContext {
     fn meta(&self) -> &Map {
         &self.meta
     }
+
+    fn deadline(&self) -> Option<Instant> {
+        self.deadline
+    }
+
+    fn set_deadline(&mut self, deadline: Instant) {
+        self.deadline = Some(deadline);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context() -> Context<()> {
+        Context::new("the train broke down", ())
+            .with_speaker("alice")
+            .with_history(vec![
+                Turn::new("alice", 0, "running late again"),
+                Turn::new("bob", 1, "what happened?"),
+            ])
+    }
+
+    #[test]
+    fn span_joins_the_context_trace() {
+        let ctx = context().with_trace_id("request-1");
+        let span = ctx.span("score.invoke");
+
+        assert_eq!(span.trace_id(), "request-1");
+        assert_eq!(span.child("score.predict").trace_id(), "request-1");
+        assert_ne!(context().span("score.invoke").trace_id(), "request-1");
+    }
+
+    #[test]
+    fn premise_without_turns_is_the_text() {
+        assert_eq!(context().premise(0), "the train broke down");
+        assert_eq!(
+            Context::new("hello", ()).premise(3),
+            "hello",
+            "no history to include"
+        );
+    }
+
+    #[test]
+    fn premise_includes_most_recent_turns() {
+        assert_eq!(
+            context().premise(1),
+            "bob: what happened?\nalice: the train broke down"
+        );
+        assert_eq!(
+            context().premise(5),
+            "alice: running late again\nbob: what happened?\nalice: the train broke down"
+        );
+    }
+
+    #[test]
+    fn deadline_is_reported_through_layer_context() {
+        let ctx = Context::new("hello", ());
+        assert!(!ctx.is_expired());
+        assert_eq!(ctx.remaining(), None);
+
+        let ctx = ctx.with_deadline(Instant::now());
+        assert!(ctx.is_expired());
+        assert_eq!(ctx.remaining(), Some(std::time::Duration::ZERO));
+    }
 }
@@ -0,0 +1,127 @@
+use loom_cortex::config::{CortexModelConfig, CortexSentenceEmbeddingsConfig};
+use loom_error::{Error, ErrorCode, Result};
+use serde::{Deserialize, Serialize};
+use serde_valid::Validate;
+
+use super::{DedupDecision, DedupLayer, Duplicate};
+
+/// Configuration for the memory deduplication layer
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct DedupConfig {
+    /// Sentence embeddings model used to embed incoming text
+    #[serde(default = "DedupConfig::model")]
+    pub model: CortexModelConfig,
+
+    /// Minimum cosine similarity for a stored memory to count as a duplicate
+    #[serde(default = "DedupConfig::threshold")]
+    #[validate(minimum = 0.0)]
+    #[validate(maximum = 1.0)]
+    pub threshold: f32,
+
+    /// Maximum number of near-duplicate candidates to fetch per memory
+    #[serde(default = "DedupConfig::limit")]
+    #[validate(minimum = 1)]
+    pub limit: usize,
+}
+
+impl DedupConfig {
+    fn model() -> CortexModelConfig {
+        CortexModelConfig::SentenceEmbeddings(CortexSentenceEmbeddingsConfig::default())
+    }
+
+    fn threshold() -> f32 {
+        0.9
+    }
+
+    fn limit() -> usize {
+        5
+    }
+
+    /// Decide whether to insert or merge given candidate matches.
+    /// The most similar candidate at or above `threshold` wins.
+    pub fn decide(&self, candidates: impl IntoIterator<Item = Duplicate>) -> DedupDecision {
+        candidates
+            .into_iter()
+            .filter(|c| c.similarity >= self.threshold)
+            .max_by(|a, b| a.similarity.total_cmp(&b.similarity))
+            .map(DedupDecision::Merge)
+            .unwrap_or(DedupDecision::Insert)
+    }
+
+    /// Build a DedupLayer from this configuration
+    pub fn build(self) -> Result<DedupLayer> {
+        self.validate()
+            .map_err(|e| Error::builder().message(e.to_string()).build())?;
+
+        if !self.model.is_sentence_embeddings() {
+            return Err(Error::builder()
+                .code(ErrorCode::BadArguments)
+                .message("DedupLayer requires a SentenceEmbeddings model")
+                .build());
+        }
+
+        let model = self.model.clone().build()?;
+        Ok(DedupLayer::new(model, self))
+    }
+}
+
+impl Default for DedupConfig {
+    fn default() -> Self {
+        Self {
+            model: Self::model(),
+            threshold: Self::threshold(),
+            limit: Self::limit(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_config_uses_defaults() {
+        let config: DedupConfig = serde_json::from_str("{}").unwrap();
+
+        assert!(config.model.is_sentence_embeddings());
+        assert_eq!(config.threshold, 0.9);
+        assert_eq!(config.limit, 5);
+    }
+
+    #[test]
+    fn decide_picks_most_similar_above_threshold() {
+        let config = DedupConfig::default();
+        let decision = config.decide([
+            Duplicate::new("a", 0.91),
+            Duplicate::new("b", 0.97),
+            Duplicate::new("c", 0.5),
+        ]);
+
+        assert_eq!(decision, DedupDecision::Merge(Duplicate::new("b", 0.97)));
+    }
+
+    #[test]
+    fn decide_inserts_below_threshold() {
+        let config = DedupConfig {
+            threshold: 0.95,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            config.decide([Duplicate::new("a", 0.94)]),
+            DedupDecision::Insert
+        );
+        assert_eq!(config.decide([]), DedupDecision::Insert);
+    }
+
+    #[test]
+    fn build_rejects_non_embedding_models() {
+        let config = DedupConfig {
+            model: CortexModelConfig::default(),
+            ..Default::default()
+        };
+
+        let err = config.build().err().unwrap();
+        assert_eq!(*err.code(), ErrorCode::BadArguments);
+    }
+}
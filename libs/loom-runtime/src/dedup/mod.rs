@@ -0,0 +1,156 @@
+//! Memory deduplication: embeds incoming text so near-duplicates can be
+//! looked up (e.g. via pgvector) and merged instead of inserted.
+
+mod config;
+
+pub use config::*;
+
+use loom_cortex::CortexModel;
+use loom_error::Result;
+use loom_pipe::LayerResult;
+use serde::{Deserialize, Serialize};
+
+use crate::{Context, embed};
+
+/// A stored memory whose embedding is close to an incoming one
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Duplicate {
+    /// Identifier of the stored memory
+    pub id: String,
+    /// Cosine similarity to the incoming memory
+    pub similarity: f32,
+}
+
+impl Duplicate {
+    pub fn new(id: impl Into<String>, similarity: f32) -> Self {
+        Self {
+            id: id.into(),
+            similarity,
+        }
+    }
+}
+
+/// Outcome of a deduplication check
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum DedupDecision {
+    /// No near-duplicate found, insert a new memory
+    Insert,
+    /// Merge into (and link to) an existing memory
+    Merge(Duplicate),
+}
+
+impl DedupDecision {
+    pub fn is_insert(&self) -> bool {
+        matches!(self, Self::Insert)
+    }
+
+    pub fn is_merge(&self) -> bool {
+        matches!(self, Self::Merge(_))
+    }
+}
+
+/// Cosine similarity of two vectors, `0.0` if their lengths differ or either is zero
+pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() || a.is_empty() {
+        return 0.0;
+    }
+
+    let mut dot = 0.0;
+    let mut norm_a = 0.0;
+    let mut norm_b = 0.0;
+
+    for (x, y) in a.iter().zip(b) {
+        dot += x * y;
+        norm_a += x * x;
+        norm_b += y * y;
+    }
+
+    if norm_a == 0.0 || norm_b == 0.0 {
+        return 0.0;
+    }
+
+    dot / (norm_a.sqrt() * norm_b.sqrt())
+}
+
+/// Embeds memory text with a sentence embeddings model.
+///
+/// The layer only produces the embedding; looking up stored candidates is left
+/// to the caller, which then resolves them with [`DedupLayer::decide`].
+pub struct DedupLayer {
+    model: CortexModel,
+    config: DedupConfig,
+}
+
+impl DedupLayer {
+    pub(crate) fn new(model: CortexModel, config: DedupConfig) -> Self {
+        Self { model, config }
+    }
+
+    /// Get the configuration for this layer
+    pub fn config(&self) -> &DedupConfig {
+        &self.config
+    }
+
+    /// Embed a single text
+    pub fn embed(&self, text: &str) -> Result<Vec<f32>> {
+        embed::embed(&self.model, "DedupLayer", text)
+    }
+
+    /// Decide whether to insert or merge given candidate matches
+    pub fn decide(&self, candidates: impl IntoIterator<Item = Duplicate>) -> DedupDecision {
+        self.config.decide(candidates)
+    }
+
+    /// Invoke the dedup layer directly with a context
+    pub fn invoke<Input>(&self, ctx: Context<Input>) -> Result<LayerResult<Vec<f32>>> {
+        let mut result = embed::embedding_result(&ctx, self.embed(&ctx.text)?);
+
+        result.meta.set("threshold", self.config.threshold.into());
+        Ok(result)
+    }
+}
+
+impl loom_pipe::Layer for DedupLayer {
+    type Input = Context<()>;
+    type Output = Vec<f32>;
+
+    fn process(&self, input: Self::Input) -> Result<LayerResult<Self::Output>> {
+        self.invoke(input)
+    }
+
+    fn name(&self) -> &'static str {
+        "dedup"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cosine_similarity_of_identical_vectors_is_one() {
+        let v = [0.3, 0.4, 0.5];
+        assert!((cosine_similarity(&v, &v) - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn cosine_similarity_of_orthogonal_vectors_is_zero() {
+        assert_eq!(cosine_similarity(&[1.0, 0.0], &[0.0, 1.0]), 0.0);
+    }
+
+    #[test]
+    fn cosine_similarity_handles_mismatched_or_zero_vectors() {
+        assert_eq!(cosine_similarity(&[1.0], &[1.0, 2.0]), 0.0);
+        assert_eq!(cosine_similarity(&[0.0, 0.0], &[1.0, 2.0]), 0.0);
+        assert_eq!(cosine_similarity(&[], &[]), 0.0);
+    }
+
+    #[test]
+    fn decision_serializes_with_action_tag() {
+        let json = serde_json::to_value(DedupDecision::Merge(Duplicate::new("a", 0.95))).unwrap();
+
+        assert_eq!(json["action"], "merge");
+        assert_eq!(json["id"], "a");
+    }
+}
@@ -0,0 +1,42 @@
+//! Single text embedding shared by the layers built on a sentence embeddings
+//! model (dedup, retrieve).
+
+use loom_cortex::CortexModel;
+use loom_error::{Error, ErrorCode, Result};
+use loom_pipe::{LayerMeta, LayerResult};
+
+use crate::Context;
+
+/// Embed `text` with `model`, which `layer` requires to be a sentence embeddings model
+pub(crate) fn embed(model: &CortexModel, layer: &str, text: &str) -> Result<Vec<f32>> {
+    let model = match model {
+        CortexModel::SentenceEmbeddings { model, .. } => model,
+        _ => {
+            return Err(Error::builder()
+                .code(ErrorCode::BadArguments)
+                .message(format!("{} requires a SentenceEmbeddings model", layer))
+                .build());
+        }
+    };
+
+    let mut embeddings = model.encode(&[text])?;
+    Ok(embeddings.pop().unwrap_or_default())
+}
+
+/// The embedding of a context's text, with its step, text and trace id (if
+/// any) as meta
+pub(crate) fn embedding_result<Input>(
+    ctx: &Context<Input>,
+    embedding: Vec<f32>,
+) -> LayerResult<Vec<f32>> {
+    let mut result = LayerResult::new(embedding);
+
+    result.meta.set_step(ctx.step);
+    result.meta.set("text", ctx.text.clone().into());
+
+    if let Some(trace_id) = &ctx.trace_id {
+        result.meta.set("trace_id", trace_id.clone().into());
+    }
+
+    result
+}
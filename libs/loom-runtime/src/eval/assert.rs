@@ -0,0 +1,233 @@
+//! [`loom_assert`] subjects for scoring and evaluation types, so tests can use
+//! `assert_score!`, `assert_decision!` and `assert_eval!` on them directly.
+
+use loom_assert::{DecisionSubject, EvalSubject, ScoreSubject, format_score};
+
+use super::score::{ScoreLayerOutput, ScoreResult};
+use super::{Decision, EvalMetrics, EvalResult, SampleResult};
+
+impl ScoreSubject for ScoreResult {
+    fn overall_score(&self) -> f32 {
+        self.score
+    }
+
+    /// Labels of every category, highest score first
+    fn label_scores(&self) -> Vec<(String, f32)> {
+        let mut scores: Vec<(String, f32)> = self
+            .categories
+            .values()
+            .flat_map(|category| {
+                category
+                    .labels
+                    .iter()
+                    .map(|(name, label)| (name.clone(), label.score))
+            })
+            .collect();
+
+        scores.sort_by(|a, b| b.1.total_cmp(&a.1));
+        scores
+    }
+
+    fn label_score(&self, label: &str) -> Option<f32> {
+        self.label(label).map(|l| l.score)
+    }
+}
+
+impl ScoreSubject for ScoreLayerOutput {
+    fn overall_score(&self) -> f32 {
+        self.inner().overall_score()
+    }
+
+    fn label_scores(&self) -> Vec<(String, f32)> {
+        self.inner().label_scores()
+    }
+
+    fn label_score(&self, label: &str) -> Option<f32> {
+        self.inner().label_score(label)
+    }
+}
+
+impl DecisionSubject for ScoreLayerOutput {
+    fn decision_name(&self) -> String {
+        decision_name(self.decision())
+    }
+
+    fn decision_context(&self) -> Vec<(String, String)> {
+        std::iter::once(("score".to_string(), format_score(self.score())))
+            .chain(
+                self.label_scores()
+                    .into_iter()
+                    .map(|(name, score)| (name, format_score(score))),
+            )
+            .collect()
+    }
+}
+
+impl DecisionSubject for SampleResult {
+    fn decision_name(&self) -> String {
+        decision_name(self.actual_decision)
+    }
+
+    fn decision_context(&self) -> Vec<(String, String)> {
+        vec![
+            ("sample".to_string(), self.id.clone()),
+            (
+                "expected".to_string(),
+                decision_name(self.expected_decision),
+            ),
+            ("score".to_string(), format_score(self.score)),
+            (
+                "expected_labels".to_string(),
+                self.expected_labels.join(", "),
+            ),
+            (
+                "detected_labels".to_string(),
+                self.detected_labels.join(", "),
+            ),
+        ]
+    }
+}
+
+/// Overall metrics first, then `category.{name}.accuracy`,
+/// `source.{name}.accuracy` and `label.{name}.{precision,recall,f1}`, each
+/// group sorted by name.
+impl EvalSubject for EvalMetrics {
+    fn eval_metrics(&self) -> Vec<(String, f32)> {
+        let mut metrics = vec![
+            ("accuracy".to_string(), self.accuracy),
+            ("precision".to_string(), self.precision),
+            ("recall".to_string(), self.recall),
+            ("f1".to_string(), self.f1),
+        ];
+
+        let mut categories: Vec<_> = self.per_category.iter().collect();
+        categories.sort_by_key(|(name, _)| *name);
+        metrics.extend(
+            categories
+                .into_iter()
+                .map(|(name, m)| (format!("category.{}.accuracy", name), m.accuracy)),
+        );
+
+        let mut sources: Vec<_> = self.per_source.iter().collect();
+        sources.sort_by_key(|(name, _)| *name);
+        metrics.extend(
+            sources
+                .into_iter()
+                .map(|(name, m)| (format!("source.{}.accuracy", name), m.accuracy)),
+        );
+
+        let mut labels: Vec<_> = self.per_label.iter().collect();
+        labels.sort_by_key(|(name, _)| *name);
+
+        for (name, m) in labels {
+            metrics.push((format!("label.{}.precision", name), m.precision));
+            metrics.push((format!("label.{}.recall", name), m.recall));
+            metrics.push((format!("label.{}.f1", name), m.f1));
+        }
+
+        metrics
+    }
+}
+
+impl EvalSubject for EvalResult {
+    fn eval_metrics(&self) -> Vec<(String, f32)> {
+        self.metrics().eval_metrics()
+    }
+}
+
+fn decision_name(decision: Decision) -> String {
+    format!("{:?}", decision)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{BTreeMap, HashMap};
+
+    use loom_assert::{ValueMatcher, assert_decision, assert_eval, assert_score, assert_value_eq};
+
+    use super::*;
+    use crate::eval::score::{ScoreCategory, ScoreLabel};
+    use crate::eval::{CategoryMetrics, LabelMetrics};
+
+    fn result() -> ScoreResult {
+        let label = |score: f32| ScoreLabel {
+            score,
+            raw_score: score,
+            sentence: 0,
+        };
+
+        let labels = BTreeMap::from([
+            ("stress".to_string(), label(0.82)),
+            ("joy".to_string(), label(0.1)),
+        ]);
+
+        let mut result = ScoreResult::new(BTreeMap::from([(
+            "emotion".to_string(),
+            ScoreCategory::new(labels),
+        )]));
+        result.score = 0.82;
+        result
+    }
+
+    #[test]
+    fn score_result_is_a_score_subject() {
+        let result = result();
+        let names: Vec<String> = result.label_scores().into_iter().map(|(n, _)| n).collect();
+
+        assert_eq!(names, ["stress", "joy"]);
+        assert_score!(result, label "stress" >= 0.7);
+        assert_score!(result, score > 0.5);
+    }
+
+    #[test]
+    fn score_output_is_a_decision_subject() {
+        let output = ScoreLayerOutput::new(result());
+
+        assert_decision!(output, Accept);
+        assert_eq!(
+            output.decision_context()[1],
+            ("stress".to_string(), "0.820".to_string())
+        );
+    }
+
+    #[test]
+    fn score_results_match_within_tolerance() {
+        let mut jittered = result();
+        jittered.score += 1e-6;
+        jittered
+            .categories
+            .get_mut("emotion")
+            .unwrap()
+            .labels
+            .get_mut("joy")
+            .unwrap()
+            .score -= 1e-6;
+
+        assert_value_eq!(jittered, result(), ValueMatcher::new().tolerance(1e-4));
+    }
+
+    #[test]
+    #[should_panic(expected = "label.stress.f1 >= 0.8\n  actual: 0.500")]
+    fn eval_metrics_report_breakdowns() {
+        let metrics = EvalMetrics {
+            accuracy: 0.9,
+            per_category: HashMap::from([(
+                "emotion".to_string(),
+                CategoryMetrics { accuracy: 0.9 },
+            )]),
+            per_label: HashMap::from([(
+                "stress".to_string(),
+                LabelMetrics {
+                    precision: 0.5,
+                    recall: 0.5,
+                    f1: 0.5,
+                },
+            )]),
+            ..Default::default()
+        };
+
+        assert_eval!(metrics, accuracy >= 0.9);
+        assert_eval!(metrics, "category.emotion.accuracy" >= 0.9);
+        assert_eval!(metrics, "label.stress.f1" >= 0.8);
+    }
+}
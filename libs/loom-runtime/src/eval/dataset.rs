@@ -2,7 +2,10 @@ use std::collections::HashSet;
 
 use serde::{Deserialize, Serialize};
 
-use super::{Sample, ValidationError};
+use super::rng::SplitMix64;
+use super::score::{ScoreComparator, ScoreConfig, ScoreGuardAction};
+use super::validation::within_one_edit;
+use super::{Decision, Sample, ValidationError, ValidationKind, ValidationReport};
 
 /// A benchmark dataset containing samples for evaluation.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -22,6 +25,29 @@ impl SampleDataset {
         }
     }
 
+    /// Shuffle the samples in place, deterministically for a given seed.
+    ///
+    /// Evaluation scores samples in dataset order, so shuffling with a fixed
+    /// seed makes batch composition reproducible across runs.
+    pub fn shuffle(&mut self, seed: u64) {
+        let mut rng = SplitMix64::new(seed);
+
+        // Fisher-Yates
+        for i in (1..self.samples.len()).rev() {
+            self.samples.swap(i, rng.below(i + 1));
+        }
+    }
+
+    /// blake3 hash (hex) of the samples, independent of their order, so a
+    /// shuffled dataset hashes the same as the original.
+    pub fn content_hash(&self) -> String {
+        let mut samples: Vec<&Sample> = self.samples.iter().collect();
+        samples.sort_by(|a, b| a.id.cmp(&b.id));
+
+        let bytes = serde_json::to_vec(&samples).expect("Sample is serializable");
+        blake3::hash(&bytes).to_hex().to_string()
+    }
+
     /// Validate the dataset without label validation.
     pub fn validate(&self) -> Vec<ValidationError> {
         self.validate_with_labels(None)
@@ -34,12 +60,13 @@ impl SampleDataset {
 
     /// Validate the dataset with optional category and label validation.
     ///
-    /// This is the most comprehensive validation method that checks:
+    /// Checks for:
     /// - Duplicate sample IDs
     /// - Empty text
     /// - Missing expected labels
     /// - Invalid categories (if valid_categories is provided)
-    /// - Invalid labels (if valid_labels is provided)
+    /// - Invalid labels (if valid_labels is provided), flagged as typos when
+    ///   within one edit of a valid label
     pub fn validate_with_config(
         &self,
         valid_categories: Option<&[String]>,
@@ -55,51 +82,147 @@ impl SampleDataset {
 
         for sample in &self.samples {
             if !seen_ids.insert(&sample.id) {
-                errors.push(ValidationError {
-                    sample_id: sample.id.clone(),
-                    message: "Duplicate sample ID".to_string(),
-                });
+                errors.push(ValidationError::error(
+                    &sample.id,
+                    ValidationKind::DuplicateId,
+                    "Duplicate sample ID",
+                ));
             }
 
             if sample.text.trim().is_empty() {
-                errors.push(ValidationError {
-                    sample_id: sample.id.clone(),
-                    message: "Empty text".to_string(),
-                });
+                errors.push(ValidationError::error(
+                    &sample.id,
+                    ValidationKind::EmptyText,
+                    "Empty text",
+                ));
             }
 
             if sample.expected_labels.is_empty() {
-                errors.push(ValidationError {
-                    sample_id: sample.id.clone(),
-                    message: "No expected labels".to_string(),
-                });
+                errors.push(ValidationError::error(
+                    &sample.id,
+                    ValidationKind::MissingLabels,
+                    "No expected labels",
+                ));
             }
 
             // Validate category against config
             if let Some(ref valid) = valid_category_set {
                 if !valid.contains(&sample.primary_category) {
-                    errors.push(ValidationError {
-                        sample_id: sample.id.clone(),
-                        message: format!("Invalid category: '{}'", sample.primary_category),
-                    });
+                    errors.push(ValidationError::error(
+                        &sample.id,
+                        ValidationKind::InvalidCategory,
+                        format!("Invalid category: '{}'", sample.primary_category),
+                    ));
                 }
             }
 
             // Validate labels against config
             if let Some(ref valid) = valid_label_set {
                 for label in &sample.expected_labels {
-                    if !valid.contains(label) {
-                        errors.push(ValidationError {
-                            sample_id: sample.id.clone(),
-                            message: format!("Invalid label: '{}'", label),
-                        });
+                    if valid.contains(label) {
+                        continue;
                     }
+
+                    let typo = valid_labels
+                        .unwrap_or_default()
+                        .iter()
+                        .find(|v| within_one_edit(&v.to_lowercase(), &label.to_lowercase()));
+
+                    errors.push(match typo {
+                        Some(suggestion) => ValidationError::error(
+                            &sample.id,
+                            ValidationKind::LabelTypo,
+                            format!(
+                                "Invalid label: '{}' (did you mean '{}'?)",
+                                label, suggestion
+                            ),
+                        )
+                        .suggestion(suggestion.as_str()),
+                        None => ValidationError::error(
+                            &sample.id,
+                            ValidationKind::InvalidLabel,
+                            format!("Invalid label: '{}'", label),
+                        ),
+                    });
+                }
+            }
+        }
+
+        errors
+    }
+
+    /// Validate the dataset against a score config.
+    ///
+    /// Runs [`SampleDataset::validate_with_config`] with the config's categories
+    /// and labels, then warns about samples whose expected labels contradict them:
+    /// - None of the expected labels belong to the primary category
+    /// - An expected label has a guard deciding the opposite of the expected decision
+    pub fn validate_with_score_config(&self, config: &ScoreConfig) -> Vec<ValidationError> {
+        let categories: Vec<String> = config.categories.keys().cloned().collect();
+        let labels: Vec<String> = config.labels().into_iter().map(|(name, _)| name).collect();
+        let mut errors = self.validate_with_config(Some(&categories), Some(&labels));
+
+        for sample in &self.samples {
+            if let Some(category) = config.category(&sample.primary_category) {
+                let known: Vec<&String> = sample
+                    .expected_labels
+                    .iter()
+                    .filter(|l| config.label(l).is_some())
+                    .collect();
+
+                if !known.is_empty()
+                    && !known
+                        .iter()
+                        .any(|l| category.labels.contains_key(l.as_str()))
+                {
+                    errors.push(ValidationError::warning(
+                        &sample.id,
+                        ValidationKind::Inconsistent,
+                        format!(
+                            "No expected label belongs to primary category '{}'",
+                            sample.primary_category
+                        ),
+                    ));
+                }
+            }
+
+            // only guards matching high scores fire when a label is present
+            let guards = config.guards.iter().filter(|g| {
+                matches!(g.comparator, ScoreComparator::Gt | ScoreComparator::Gte)
+                    && sample.expected_labels.contains(&g.label)
+            });
+
+            for guard in guards {
+                let contradicts = match guard.action {
+                    ScoreGuardAction::Reject => sample.expected_decision == Decision::Accept,
+                    ScoreGuardAction::Accept => sample.expected_decision == Decision::Reject,
+                };
+
+                if contradicts {
+                    errors.push(ValidationError::warning(
+                        &sample.id,
+                        ValidationKind::Inconsistent,
+                        format!(
+                            "Expected decision '{:?}' contradicts guard '{}' ({:?})",
+                            sample.expected_decision, guard, guard.action
+                        ),
+                    ));
                 }
             }
         }
 
         errors
     }
+
+    /// Validate the dataset, against a score config if given, grouping findings by severity.
+    pub fn report(&self, config: Option<&ScoreConfig>) -> ValidationReport {
+        let findings = match config {
+            Some(config) => self.validate_with_score_config(config),
+            None => self.validate(),
+        };
+
+        ValidationReport::new(self.samples.len(), findings)
+    }
 }
 
 impl Default for SampleDataset {
@@ -110,7 +233,8 @@ impl Default for SampleDataset {
 
 #[cfg(test)]
 mod tests {
-    use crate::eval::{Decision, Difficulty};
+    use crate::eval::fixtures::sample;
+    use crate::eval::{Decision, Severity};
 
     use super::*;
 
@@ -121,31 +245,74 @@ mod tests {
         assert!(dataset.samples.is_empty());
     }
 
+    #[test]
+    fn dataset_shuffle_is_deterministic_per_seed() {
+        let mut a = SampleDataset::new();
+        for i in 0..20 {
+            a.samples.push(sample(
+                &format!("test-{:03}", i),
+                "Hello",
+                Decision::Accept,
+                "",
+                &[],
+            ));
+        }
+
+        let mut b = a.clone();
+        let mut c = a.clone();
+        a.shuffle(7);
+        b.shuffle(7);
+        c.shuffle(8);
+
+        let ids = |d: &SampleDataset| d.samples.iter().map(|s| s.id.clone()).collect::<Vec<_>>();
+        assert_eq!(ids(&a), ids(&b));
+        assert_ne!(ids(&a), ids(&c));
+
+        let mut sorted = ids(&a);
+        sorted.sort();
+        assert_eq!(sorted.first().unwrap(), "test-000");
+        assert_eq!(sorted.len(), 20);
+    }
+
+    #[test]
+    fn content_hash_ignores_sample_order() {
+        let mut a = SampleDataset::new();
+        for i in 0..5 {
+            a.samples.push(sample(
+                &format!("test-{:03}", i),
+                "Hello",
+                Decision::Accept,
+                "",
+                &[],
+            ));
+        }
+
+        let mut shuffled = a.clone();
+        shuffled.shuffle(3);
+        let mut fewer = a.clone();
+        fewer.samples.pop();
+
+        assert_eq!(a.content_hash(), shuffled.content_hash());
+        assert_ne!(a.content_hash(), fewer.content_hash());
+    }
+
     #[test]
     fn dataset_validate_catches_duplicate_ids() {
         let mut dataset = SampleDataset::new();
-        dataset.samples.push(Sample {
-            id: "test-001".to_string(),
-            text: "Hello".to_string(),
-            context: None,
-            expected_decision: Decision::Accept,
-            expected_labels: vec!["positive".to_string()],
-            primary_category: "emotional".to_string(),
-            difficulty: Difficulty::Easy,
-            notes: None,
-            metadata: None,
-        });
-        dataset.samples.push(Sample {
-            id: "test-001".to_string(),
-            text: "World".to_string(),
-            context: None,
-            expected_decision: Decision::Accept,
-            expected_labels: vec!["positive".to_string()],
-            primary_category: "emotional".to_string(),
-            difficulty: Difficulty::Easy,
-            notes: None,
-            metadata: None,
-        });
+        dataset.samples.push(sample(
+            "test-001",
+            "Hello",
+            Decision::Accept,
+            "emotional",
+            &["positive"],
+        ));
+        dataset.samples.push(sample(
+            "test-001",
+            "World",
+            Decision::Accept,
+            "emotional",
+            &["positive"],
+        ));
 
         let errors = dataset.validate();
         assert_eq!(errors.len(), 1);
@@ -155,17 +322,13 @@ mod tests {
     #[test]
     fn dataset_validate_catches_empty_text() {
         let mut dataset = SampleDataset::new();
-        dataset.samples.push(Sample {
-            id: "test-001".to_string(),
-            text: "  ".to_string(),
-            context: None,
-            expected_decision: Decision::Accept,
-            expected_labels: vec!["positive".to_string()],
-            primary_category: "emotional".to_string(),
-            difficulty: Difficulty::Easy,
-            notes: None,
-            metadata: None,
-        });
+        dataset.samples.push(sample(
+            "test-001",
+            "  ",
+            Decision::Accept,
+            "emotional",
+            &["positive"],
+        ));
 
         let errors = dataset.validate();
         assert!(errors.iter().any(|e| e.message.contains("Empty text")));
@@ -174,17 +337,13 @@ mod tests {
     #[test]
     fn dataset_validate_catches_invalid_labels() {
         let mut dataset = SampleDataset::new();
-        dataset.samples.push(Sample {
-            id: "test-001".to_string(),
-            text: "Hello".to_string(),
-            context: None,
-            expected_decision: Decision::Accept,
-            expected_labels: vec!["NotARealLabel".to_string()],
-            primary_category: "emotional".to_string(),
-            difficulty: Difficulty::Easy,
-            notes: None,
-            metadata: None,
-        });
+        dataset.samples.push(sample(
+            "test-001",
+            "Hello",
+            Decision::Accept,
+            "emotional",
+            &["NotARealLabel"],
+        ));
 
         let valid_labels = vec!["positive".to_string(), "negative".to_string()];
         let errors = dataset.validate_with_labels(Some(&valid_labels));
@@ -194,17 +353,13 @@ mod tests {
     #[test]
     fn dataset_validate_catches_invalid_categories() {
         let mut dataset = SampleDataset::new();
-        dataset.samples.push(Sample {
-            id: "test-001".to_string(),
-            text: "Hello".to_string(),
-            context: None,
-            expected_decision: Decision::Accept,
-            expected_labels: vec!["positive".to_string()],
-            primary_category: "unknown_category".to_string(),
-            difficulty: Difficulty::Easy,
-            notes: None,
-            metadata: None,
-        });
+        dataset.samples.push(sample(
+            "test-001",
+            "Hello",
+            Decision::Accept,
+            "unknown_category",
+            &["positive"],
+        ));
 
         let valid_categories = vec!["sentiment".to_string(), "emotion".to_string()];
         let errors = dataset.validate_with_config(Some(&valid_categories), None);
@@ -214,4 +369,115 @@ mod tests {
                 .any(|e| e.message.contains("Invalid category"))
         );
     }
+
+    fn score_config() -> ScoreConfig {
+        serde_json::from_value(serde_json::json!({
+            "categories": {
+                "sentiment": {
+                    "labels": {
+                        "positive": { "hypothesis": "This is positive." },
+                        "negative": { "hypothesis": "This is negative." }
+                    }
+                },
+                "spam": {
+                    "labels": {
+                        "spam": { "hypothesis": "This is spam." }
+                    }
+                }
+            },
+            "guards": [{ "label": "spam", "threshold": 0.8 }]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn dataset_validate_flags_label_typos() {
+        let mut dataset = SampleDataset::new();
+        dataset.samples.push(sample(
+            "test-001",
+            "Hello",
+            Decision::Accept,
+            "sentiment",
+            &["postive"],
+        ));
+
+        let errors = dataset.validate_with_score_config(&score_config());
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].kind, ValidationKind::LabelTypo);
+        assert_eq!(errors[0].suggestion.as_deref(), Some("positive"));
+    }
+
+    #[test]
+    fn dataset_validate_warns_on_guard_contradiction() {
+        let mut dataset = SampleDataset::new();
+        dataset.samples.push(sample(
+            "test-001",
+            "Hello",
+            Decision::Accept,
+            "spam",
+            &["spam"],
+        ));
+        dataset.samples.push(sample(
+            "test-002",
+            "Hello",
+            Decision::Reject,
+            "spam",
+            &["spam"],
+        ));
+
+        let errors = dataset.validate_with_score_config(&score_config());
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].sample_id, "test-001");
+        assert_eq!(errors[0].kind, ValidationKind::Inconsistent);
+    }
+
+    #[test]
+    fn dataset_validate_warns_on_labels_outside_category() {
+        let mut dataset = SampleDataset::new();
+        dataset.samples.push(sample(
+            "test-001",
+            "Hello",
+            Decision::Accept,
+            "spam",
+            &["positive"],
+        ));
+
+        let errors = dataset.validate_with_score_config(&score_config());
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].severity, Severity::Warning);
+    }
+
+    #[test]
+    fn dataset_report_groups_by_severity() {
+        let mut dataset = SampleDataset::new();
+        dataset.samples.push(sample(
+            "test-001",
+            "Hello",
+            Decision::Accept,
+            "spam",
+            &["spam"],
+        ));
+        dataset.samples.push(sample(
+            "test-001",
+            "Hello",
+            Decision::Accept,
+            "sentiment",
+            &["positive"],
+        ));
+
+        let report = dataset.report(Some(&score_config()));
+        assert_eq!(report.samples, 2);
+        assert_eq!(report.errors.len(), 1);
+        assert_eq!(report.warnings.len(), 1);
+        assert!(!report.is_valid());
+    }
+
+    #[test]
+    fn within_one_edit_matches_single_edits() {
+        assert!(within_one_edit("positive", "postive"));
+        assert!(within_one_edit("positive", "positives"));
+        assert!(within_one_edit("positive", "posjtive"));
+        assert!(!within_one_edit("positive", "negative"));
+        assert!(!within_one_edit("positive", "pstive"));
+    }
 }
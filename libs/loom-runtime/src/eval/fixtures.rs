@@ -0,0 +1,63 @@
+//! Sample and result factories shared by the eval tests.
+
+use super::{Decision, Difficulty, EvalResult, Sample, SampleResult};
+
+/// An easy sample expecting `decision` and `labels`, in `category`
+pub(crate) fn sample(
+    id: &str,
+    text: &str,
+    decision: Decision,
+    category: &str,
+    labels: &[&str],
+) -> Sample {
+    Sample {
+        id: id.to_string(),
+        text: text.to_string(),
+        context: None,
+        expected_decision: decision,
+        expected_labels: labels.iter().map(|l| l.to_string()).collect(),
+        primary_category: category.to_string(),
+        difficulty: Difficulty::Easy,
+        notes: None,
+        metadata: None,
+        weight: None,
+        source: None,
+    }
+}
+
+/// The result of an accepted sample expecting `labels`: accepted with every
+/// label detected if `correct`, rejected with none otherwise
+pub(crate) fn sample_result(id: &str, correct: bool, labels: &[&str]) -> SampleResult {
+    let labels: Vec<String> = labels.iter().map(|l| l.to_string()).collect();
+
+    SampleResult {
+        id: id.to_string(),
+        expected_decision: Decision::Accept,
+        actual_decision: if correct {
+            Decision::Accept
+        } else {
+            Decision::Reject
+        },
+        correct,
+        score: 0.0,
+        detected_labels: if correct { labels.clone() } else { vec![] },
+        expected_labels: labels,
+        elapsed_ms: None,
+        weight: 1.0,
+    }
+}
+
+/// The result of an accepted sample expecting `expected` labels that detected `detected`
+pub(crate) fn labeled_result(id: &str, expected: &[&str], detected: &[&str]) -> SampleResult {
+    SampleResult {
+        detected_labels: detected.iter().map(|l| l.to_string()).collect(),
+        ..sample_result(id, true, expected)
+    }
+}
+
+/// A run holding `sample_results`, in order
+pub(crate) fn result(sample_results: impl IntoIterator<Item = SampleResult>) -> EvalResult {
+    let mut result = EvalResult::new();
+    result.sample_results.extend(sample_results);
+    result
+}
@@ -27,11 +27,20 @@
 //! ```
 
 // Operational types - owned by runtime
+#[cfg(feature = "assert")]
+mod assert;
 mod dataset;
 mod difficulty;
+#[cfg(test)]
+mod fixtures;
 pub mod result;
+mod resume;
+mod rng;
 mod sample;
+mod schema;
 pub mod score;
+mod select;
+mod stats;
 mod validation;
 
 // Public exports - operational types
@@ -39,4 +48,7 @@ pub use dataset::*;
 pub use difficulty::*;
 pub use result::*;
 pub use sample::*;
+pub use schema::SCHEMA_VERSION;
+pub use select::*;
+pub use stats::*;
 pub use validation::*;
@@ -0,0 +1,209 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::{EvalResult, eval::weighted_metrics_of};
+use crate::eval::rng::SplitMix64;
+
+/// Confidence interval bounds for a metric.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct ConfidenceInterval {
+    pub lower: f32,
+    pub upper: f32,
+}
+
+impl ConfidenceInterval {
+    /// Width of the interval.
+    pub fn width(&self) -> f32 {
+        self.upper - self.lower
+    }
+
+    /// Whether `value` falls within the interval.
+    pub fn contains(&self, value: f32) -> bool {
+        value >= self.lower && value <= self.upper
+    }
+
+    /// Percentile interval of `values` at the given confidence level.
+    fn of(values: &mut [f32], confidence: f32) -> Self {
+        if values.is_empty() {
+            return Self::default();
+        }
+
+        values.sort_by(|a, b| a.total_cmp(b));
+        let tail = (1.0 - confidence) / 2.0;
+        let at = |p: f32| values[((values.len() - 1) as f32 * p).round() as usize];
+
+        Self {
+            lower: at(tail),
+            upper: at(1.0 - tail),
+        }
+    }
+}
+
+impl std::fmt::Display for ConfidenceInterval {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[{:.3}, {:.3}]", self.lower, self.upper)
+    }
+}
+
+/// Bootstrap confidence intervals for benchmark metrics.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BootstrapMetrics {
+    /// Number of resamples drawn.
+    pub iterations: usize,
+    /// Confidence level of the intervals (e.g. 0.95).
+    pub confidence: f32,
+    pub accuracy: ConfidenceInterval,
+    pub f1: ConfidenceInterval,
+    /// F1 interval per label.
+    pub per_label: HashMap<String, ConfidenceInterval>,
+}
+
+/// Bootstrap resampler over an [`EvalResult`]'s sample results.
+///
+/// Each iteration draws `sample_results.len()` samples with replacement and
+/// recomputes the metrics (honoring sample weights); intervals are the
+/// percentiles of the resampled metrics. Resampling is seeded, so the same
+/// result always yields the same intervals.
+///
+/// The intervals bracket [`EvalResult::weighted_metrics`], not
+/// [`EvalResult::metrics`]; report them beside the former.
+///
+/// # Example
+/// ```ignore
+/// let ci = Bootstrap::new().iterations(2000).run(&result);
+/// println!("accuracy 95% CI: {}", ci.accuracy);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Bootstrap {
+    iterations: usize,
+    confidence: f32,
+    seed: u64,
+}
+
+impl Bootstrap {
+    pub fn new() -> Self {
+        Self {
+            iterations: 1000,
+            confidence: 0.95,
+            seed: 42,
+        }
+    }
+
+    /// Number of resamples to draw (default: 1000).
+    pub fn iterations(mut self, iterations: usize) -> Self {
+        self.iterations = iterations;
+        self
+    }
+
+    /// Confidence level of the intervals, between 0 and 1 (default: 0.95).
+    pub fn confidence(mut self, confidence: f32) -> Self {
+        self.confidence = confidence.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Seed of the resampling generator (default: 42).
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Compute confidence intervals for the result's accuracy, macro F1 and per-label F1.
+    pub fn run(&self, result: &EvalResult) -> BootstrapMetrics {
+        let samples = &result.sample_results;
+        let mut metrics = BootstrapMetrics {
+            iterations: self.iterations,
+            confidence: self.confidence,
+            ..Default::default()
+        };
+
+        if samples.is_empty() || self.iterations == 0 {
+            return metrics;
+        }
+
+        let mut rng = SplitMix64::new(self.seed);
+        let mut accuracy = Vec::with_capacity(self.iterations);
+        let mut f1 = Vec::with_capacity(self.iterations);
+        let mut per_label: HashMap<String, Vec<f32>> = HashMap::new();
+
+        for _ in 0..self.iterations {
+            let resample =
+                weighted_metrics_of((0..samples.len()).map(|_| &samples[rng.below(samples.len())]));
+
+            accuracy.push(resample.accuracy);
+            f1.push(resample.f1);
+
+            for (label, label_metrics) in resample.per_label {
+                per_label.entry(label).or_default().push(label_metrics.f1);
+            }
+        }
+
+        metrics.accuracy = ConfidenceInterval::of(&mut accuracy, self.confidence);
+        metrics.f1 = ConfidenceInterval::of(&mut f1, self.confidence);
+        metrics.per_label = per_label
+            .into_iter()
+            .map(|(label, mut values)| {
+                (label, ConfidenceInterval::of(&mut values, self.confidence))
+            })
+            .collect();
+
+        metrics
+    }
+}
+
+impl Default for Bootstrap {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::eval::fixtures;
+
+    use super::*;
+
+    fn result(correct: usize, total: usize) -> EvalResult {
+        fixtures::result(
+            (0..total).map(|i| {
+                fixtures::sample_result(&format!("test-{:03}", i), i < correct, &["task"])
+            }),
+        )
+    }
+
+    #[test]
+    fn bootstrap_interval_contains_observed_accuracy() {
+        let result = result(80, 100);
+        let ci = result.bootstrap();
+
+        assert_eq!(ci.iterations, 1000);
+        assert!(ci.accuracy.contains(0.8));
+        assert!(ci.accuracy.lower > 0.6 && ci.accuracy.upper < 0.95);
+        assert!(ci.per_label.get("task").unwrap().contains(0.889));
+    }
+
+    #[test]
+    fn bootstrap_interval_narrows_with_more_samples() {
+        let small = result(8, 10).bootstrap();
+        let large = result(800, 1000).bootstrap();
+
+        assert!(large.accuracy.width() < small.accuracy.width());
+    }
+
+    #[test]
+    fn bootstrap_is_reproducible() {
+        let result = result(7, 10);
+        let a = Bootstrap::new().seed(7).run(&result);
+        let b = Bootstrap::new().seed(7).run(&result);
+
+        assert_eq!(a.accuracy, b.accuracy);
+        assert_eq!(a.f1, b.f1);
+    }
+
+    #[test]
+    fn bootstrap_empty_result_has_zero_intervals() {
+        let ci = EvalResult::new().bootstrap();
+        assert_eq!(ci.accuracy, ConfidenceInterval::default());
+        assert!(ci.per_label.is_empty());
+    }
+}
@@ -0,0 +1,210 @@
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use super::EvalResult;
+
+/// Paired comparison of two eval runs on the same dataset.
+///
+/// Samples are matched by ID; an ID repeated within a run is compared once,
+/// by its first result. Significance uses an exact McNemar test on the
+/// discordant pairs: samples only the baseline got right versus samples only
+/// the candidate got right.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EvalComparison {
+    /// Number of sample IDs present in both runs.
+    pub paired: usize,
+    /// Number of sample IDs present in only one of the runs (ignored).
+    pub unpaired: usize,
+    /// Baseline accuracy over the paired samples.
+    pub baseline_accuracy: f32,
+    /// Candidate accuracy over the paired samples.
+    pub candidate_accuracy: f32,
+    /// Samples the baseline classified correctly and the candidate did not.
+    pub baseline_only: usize,
+    /// Samples the candidate classified correctly and the baseline did not.
+    pub candidate_only: usize,
+    /// Two-sided McNemar p-value.
+    pub p_value: f64,
+    /// Significance level the p-value is tested against.
+    pub alpha: f64,
+}
+
+impl EvalComparison {
+    /// Candidate minus baseline accuracy.
+    pub fn delta(&self) -> f32 {
+        self.candidate_accuracy - self.baseline_accuracy
+    }
+
+    /// Whether the difference between the runs is statistically significant.
+    pub fn is_significant(&self) -> bool {
+        self.p_value < self.alpha
+    }
+
+    /// Whether the candidate is significantly worse than the baseline.
+    pub fn is_regression(&self) -> bool {
+        self.is_significant() && self.baseline_only > self.candidate_only
+    }
+
+    /// Whether the candidate is significantly better than the baseline.
+    pub fn is_improvement(&self) -> bool {
+        self.is_significant() && self.candidate_only > self.baseline_only
+    }
+}
+
+impl EvalResult {
+    /// Compare this (baseline) run against a candidate run on the same dataset,
+    /// testing significance at `alpha` (e.g. 0.05).
+    pub fn compare(&self, candidate: &EvalResult, alpha: f64) -> EvalComparison {
+        let mut candidates: HashMap<&str, bool> = HashMap::new();
+
+        for sample in &candidate.sample_results {
+            candidates
+                .entry(sample.id.as_str())
+                .or_insert(sample.correct);
+        }
+
+        let mut baseline_ids = HashSet::new();
+
+        let mut paired = 0;
+        let mut baseline_correct = 0;
+        let mut candidate_correct = 0;
+        let mut baseline_only = 0;
+        let mut candidate_only = 0;
+
+        for sample in &self.sample_results {
+            if !baseline_ids.insert(sample.id.as_str()) {
+                continue;
+            }
+
+            let Some(&correct) = candidates.get(sample.id.as_str()) else {
+                continue;
+            };
+
+            paired += 1;
+            baseline_correct += sample.correct as usize;
+            candidate_correct += correct as usize;
+
+            match (sample.correct, correct) {
+                (true, false) => baseline_only += 1,
+                (false, true) => candidate_only += 1,
+                _ => {}
+            }
+        }
+
+        let accuracy = |correct: usize| {
+            if paired > 0 {
+                correct as f32 / paired as f32
+            } else {
+                0.0
+            }
+        };
+
+        EvalComparison {
+            paired,
+            unpaired: baseline_ids.len() + candidates.len() - 2 * paired,
+            baseline_accuracy: accuracy(baseline_correct),
+            candidate_accuracy: accuracy(candidate_correct),
+            baseline_only,
+            candidate_only,
+            p_value: mcnemar(baseline_only, candidate_only),
+            alpha,
+        }
+    }
+}
+
+/// Exact two-sided McNemar p-value for `b` and `c` discordant pairs:
+/// the binomial probability of a split at least this uneven under p = 0.5.
+fn mcnemar(b: usize, c: usize) -> f64 {
+    let n = b + c;
+
+    if n == 0 {
+        return 1.0;
+    }
+
+    // sum P(X = k) for k <= min(b, c), computed in log space to avoid underflow
+    let mut ln_term = -(n as f64) * std::f64::consts::LN_2;
+    let mut tail = 0.0;
+
+    for k in 0..=b.min(c) {
+        tail += ln_term.exp();
+        ln_term += ((n - k) as f64).ln() - ((k + 1) as f64).ln();
+    }
+
+    (2.0 * tail).min(1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::eval::fixtures;
+
+    use super::*;
+
+    fn result(correct: &[bool]) -> EvalResult {
+        fixtures::result(
+            correct.iter().enumerate().map(|(i, &correct)| {
+                fixtures::sample_result(&format!("test-{:03}", i), correct, &[])
+            }),
+        )
+    }
+
+    #[test]
+    fn mcnemar_matches_exact_binomial() {
+        assert_eq!(mcnemar(0, 0), 1.0);
+        assert!((mcnemar(0, 5) - 0.0625).abs() < 1e-9);
+        assert!((mcnemar(2, 8) - 0.109375).abs() < 1e-9);
+        assert_eq!(mcnemar(5, 5), 1.0);
+    }
+
+    #[test]
+    fn compare_flags_significant_regression() {
+        let baseline = result(&[true; 40]);
+        let mut flags = vec![true; 40];
+        flags[..12].iter_mut().for_each(|c| *c = false);
+        let candidate = result(&flags);
+
+        let cmp = baseline.compare(&candidate, 0.05);
+        assert_eq!(cmp.paired, 40);
+        assert_eq!(cmp.baseline_only, 12);
+        assert!((cmp.delta() + 0.3).abs() < 0.001);
+        assert!(cmp.is_regression());
+        assert!(!cmp.is_improvement());
+    }
+
+    #[test]
+    fn compare_small_difference_is_not_significant() {
+        let mut flags = vec![true; 100];
+        flags[0] = false;
+        let baseline = result(&flags);
+        let candidate = result(&[true; 100]);
+
+        let cmp = baseline.compare(&candidate, 0.05);
+        assert_eq!(cmp.candidate_only, 1);
+        assert!(!cmp.is_significant());
+    }
+
+    #[test]
+    fn compare_ignores_unpaired_samples() {
+        let baseline = result(&[true, true, false]);
+        let candidate = result(&[true, true]);
+
+        let cmp = baseline.compare(&candidate, 0.05);
+        assert_eq!(cmp.paired, 2);
+        assert_eq!(cmp.unpaired, 1);
+    }
+
+    #[test]
+    fn compare_counts_repeated_ids_once() {
+        let mut baseline = result(&[true, false, true]);
+        baseline.sample_results[1].id = "test-000".to_string();
+        let mut candidate = result(&[true, true, false, false]);
+        candidate.sample_results[2].id = "test-001".to_string();
+        candidate.sample_results[3].id = "test-001".to_string();
+
+        let cmp = baseline.compare(&candidate, 0.05);
+        assert_eq!(cmp.paired, 1);
+        assert_eq!(cmp.unpaired, 2);
+        assert_eq!(cmp.baseline_only, 0);
+        assert_eq!(cmp.candidate_accuracy, 1.0);
+    }
+}
@@ -0,0 +1,184 @@
+use std::collections::{BTreeSet, HashMap};
+
+use serde::{Deserialize, Serialize};
+
+use super::{EvalResult, csv_field};
+
+/// Label co-confusion matrix built from sample results.
+///
+/// `counts[i][j]` is the number of samples where `labels[i]` was expected and
+/// `labels[j]` detected. The diagonal holds correct detections; off-diagonal
+/// cells count samples where `labels[i]` was missed while `labels[j]` was
+/// detected without being expected, i.e. `i` was likely mistaken for `j`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LabelConfusion {
+    pub labels: Vec<String>,
+    pub counts: Vec<Vec<usize>>,
+}
+
+/// A pair of labels confused with each other.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConfusedPair {
+    pub expected: String,
+    pub detected: String,
+    pub count: usize,
+}
+
+impl LabelConfusion {
+    /// Build the matrix over every label expected or detected in `result`.
+    pub fn from_result(result: &EvalResult) -> Self {
+        let labels: Vec<String> = result
+            .sample_results
+            .iter()
+            .flat_map(|s| s.expected_labels.iter().chain(s.detected_labels.iter()))
+            .cloned()
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+
+        let index: HashMap<&str, usize> = labels
+            .iter()
+            .enumerate()
+            .map(|(i, l)| (l.as_str(), i))
+            .collect();
+
+        let mut counts = vec![vec![0; labels.len()]; labels.len()];
+
+        for sample in &result.sample_results {
+            for expected in &sample.expected_labels {
+                let i = index[expected.as_str()];
+
+                if sample.detected_labels.contains(expected) {
+                    counts[i][i] += 1;
+                    continue;
+                }
+
+                for detected in &sample.detected_labels {
+                    if !sample.expected_labels.contains(detected) {
+                        counts[i][index[detected.as_str()]] += 1;
+                    }
+                }
+            }
+        }
+
+        Self { labels, counts }
+    }
+
+    /// Number of samples where `expected` was expected and `detected` detected.
+    pub fn get(&self, expected: &str, detected: &str) -> usize {
+        let i = self.labels.iter().position(|l| l == expected);
+        let j = self.labels.iter().position(|l| l == detected);
+
+        match (i, j) {
+            (Some(i), Some(j)) => self.counts[i][j],
+            _ => 0,
+        }
+    }
+
+    /// Off-diagonal cells, most confused first.
+    pub fn pairs(&self) -> Vec<ConfusedPair> {
+        let mut pairs: Vec<ConfusedPair> = self
+            .counts
+            .iter()
+            .enumerate()
+            .flat_map(|(i, row)| {
+                row.iter()
+                    .enumerate()
+                    .filter(move |&(j, &count)| i != j && count > 0)
+                    .map(move |(j, &count)| ConfusedPair {
+                        expected: self.labels[i].clone(),
+                        detected: self.labels[j].clone(),
+                        count,
+                    })
+            })
+            .collect();
+
+        pairs.sort_by(|a, b| {
+            b.count
+                .cmp(&a.count)
+                .then_with(|| a.expected.cmp(&b.expected))
+                .then_with(|| a.detected.cmp(&b.detected))
+        });
+
+        pairs
+    }
+
+    /// Render the matrix as CSV, one row per expected label and one column per detected label.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("expected");
+
+        for label in &self.labels {
+            csv.push(',');
+            csv.push_str(&csv_field(label));
+        }
+
+        csv.push('\n');
+
+        for (label, row) in self.labels.iter().zip(&self.counts) {
+            csv.push_str(&csv_field(label));
+
+            for count in row {
+                csv.push(',');
+                csv.push_str(&count.to_string());
+            }
+
+            csv.push('\n');
+        }
+
+        csv
+    }
+}
+
+impl EvalResult {
+    /// Build the label co-confusion matrix for this run.
+    pub fn confusion(&self) -> LabelConfusion {
+        LabelConfusion::from_result(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::eval::fixtures;
+
+    #[test]
+    fn confusion_counts_missed_labels_against_spurious_detections() {
+        let confusion = fixtures::result([
+            fixtures::labeled_result("a", &["task"], &["task"]),
+            fixtures::labeled_result("b", &["task"], &["plan"]),
+            fixtures::labeled_result("c", &["task"], &["plan"]),
+            fixtures::labeled_result("d", &["time", "plan"], &["plan", "date"]),
+        ])
+        .confusion();
+
+        assert_eq!(confusion.labels, vec!["date", "plan", "task", "time"]);
+        assert_eq!(confusion.get("task", "task"), 1);
+        assert_eq!(confusion.get("task", "plan"), 2);
+        assert_eq!(confusion.get("time", "date"), 1);
+        assert_eq!(confusion.get("plan", "date"), 0);
+        assert_eq!(confusion.get("plan", "plan"), 1);
+    }
+
+    #[test]
+    fn confusion_pairs_sorted_by_count() {
+        let pairs = fixtures::result([
+            fixtures::labeled_result("a", &["time"], &["date"]),
+            fixtures::labeled_result("b", &["task"], &["plan"]),
+            fixtures::labeled_result("c", &["task"], &["plan"]),
+        ])
+        .confusion()
+        .pairs();
+
+        assert_eq!(pairs.len(), 2);
+        assert_eq!(pairs[0].expected, "task");
+        assert_eq!(pairs[0].detected, "plan");
+        assert_eq!(pairs[0].count, 2);
+    }
+
+    #[test]
+    fn confusion_to_csv() {
+        let csv = fixtures::result([fixtures::labeled_result("a", &["task"], &["plan"])])
+            .confusion()
+            .to_csv();
+        assert_eq!(csv, "expected,plan,task\nplan,0,0\ntask,1,0\n");
+    }
+}
@@ -0,0 +1,134 @@
+use std::process::Command;
+
+use loom_cortex::CortexDevice;
+use serde::{Deserialize, Serialize};
+
+use crate::eval::SampleDataset;
+use crate::eval::score::ScoreConfig;
+
+/// Where and with what an evaluation ran, recorded on [`EvalResult`](super::EvalResult)
+/// so stored results can be reproduced and compared.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EvalEnvironment {
+    /// Host the evaluation ran on
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hostname: Option<String>,
+    /// Git commit of the working directory, if it is a checkout
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub git_commit: Option<String>,
+    /// Version of loom-runtime that ran the evaluation
+    pub version: String,
+    /// Model identifier (e.g. `zero_shot_classification/bart`)
+    pub model: String,
+    /// Device the model was configured for
+    pub device: CortexDevice,
+    /// blake3 hash (hex) of the serialized score configuration
+    pub config_hash: String,
+    /// Order-independent hash of the dataset, see [`SampleDataset::content_hash`]
+    pub dataset_hash: String,
+}
+
+impl EvalEnvironment {
+    /// Capture the current host and checkout along with the scoring setup.
+    ///
+    /// The git commit comes from `LOOM_GIT_COMMIT` or `GIT_COMMIT` when set
+    /// (e.g. in CI or containers without `.git`), otherwise from `git rev-parse`.
+    pub fn capture(config: &ScoreConfig, dataset: &SampleDataset) -> Self {
+        let provenance = config.provenance();
+
+        Self {
+            hostname: hostname(),
+            git_commit: git_commit(),
+            version: provenance.version,
+            model: provenance.model,
+            device: config.model.device().clone(),
+            config_hash: provenance.config_hash,
+            dataset_hash: dataset.content_hash(),
+        }
+    }
+
+    /// Whether results from both environments measure the same thing: same
+    /// model, configuration and dataset. Host, commit and device may differ.
+    pub fn is_comparable(&self, other: &EvalEnvironment) -> bool {
+        self.model == other.model
+            && self.config_hash == other.config_hash
+            && self.dataset_hash == other.dataset_hash
+    }
+}
+
+fn hostname() -> Option<String> {
+    std::env::var("HOSTNAME")
+        .or_else(|_| std::env::var("COMPUTERNAME"))
+        .ok()
+        .or_else(|| std::fs::read_to_string("/etc/hostname").ok())
+        .or_else(|| command_output("hostname", &[]))
+        .map(|h| h.trim().to_string())
+        .filter(|h| !h.is_empty())
+}
+
+fn git_commit() -> Option<String> {
+    std::env::var("LOOM_GIT_COMMIT")
+        .or_else(|_| std::env::var("GIT_COMMIT"))
+        .ok()
+        .or_else(|| command_output("git", &["rev-parse", "HEAD"]))
+        .map(|c| c.trim().to_string())
+        .filter(|c| !c.is_empty())
+}
+
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).output().ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    String::from_utf8(output.stdout).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn environment(model: &str, dataset_hash: &str) -> EvalEnvironment {
+        EvalEnvironment {
+            hostname: Some("host-a".to_string()),
+            model: model.to_string(),
+            config_hash: "abc".to_string(),
+            dataset_hash: dataset_hash.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn comparable_ignores_host_and_commit() {
+        let a = environment("bart", "d1");
+        let b = EvalEnvironment {
+            hostname: Some("host-b".to_string()),
+            git_commit: Some("deadbeef".to_string()),
+            ..a.clone()
+        };
+
+        assert!(a.is_comparable(&b));
+        assert!(!a.is_comparable(&environment("deberta", "d1")));
+        assert!(!a.is_comparable(&environment("bart", "d2")));
+    }
+
+    #[test]
+    fn capture_records_config_and_dataset() {
+        let config = ScoreConfig::default();
+        let dataset = SampleDataset::new();
+        let env = EvalEnvironment::capture(&config, &dataset);
+
+        assert_eq!(env.config_hash, config.provenance().config_hash);
+        assert_eq!(env.dataset_hash, dataset.content_hash());
+        assert_eq!(env.version, env!("CARGO_PKG_VERSION"));
+    }
+
+    #[test]
+    fn omits_unknown_host_and_commit_when_serialized() {
+        let json = serde_json::to_value(EvalEnvironment::default()).unwrap();
+
+        assert!(json.get("hostname").is_none());
+        assert!(json.get("git_commit").is_none());
+    }
+}
@@ -1,18 +1,26 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use serde::{Deserialize, Serialize};
 
 use super::{
-    CategoryMetrics, CategoryResult, EvalMetrics, LabelMetrics, LabelResult, SampleResult,
+    Bootstrap, BootstrapMetrics, CategoryMetrics, CategoryResult, EvalEnvironment, EvalMetrics,
+    LabelMetrics, LabelResult, SampleResult, SourceMetrics, SourceResult,
 };
+use crate::eval::{Sample, schema};
 
 /// Raw benchmark results (counts only).
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EvalResult {
+    /// Schema version of the serialized result, see [`SCHEMA_VERSION`](crate::eval::SCHEMA_VERSION)
+    #[serde(default = "schema::current", deserialize_with = "schema::upgrade")]
+    pub schema_version: u32,
     pub total: usize,
     pub correct: usize,
     pub per_category: HashMap<String, CategoryResult>,
     pub per_label: HashMap<String, LabelResult>,
+    /// Counts per source dataset, for samples that have a source.
+    #[serde(default)]
+    pub per_source: HashMap<String, SourceResult>,
     pub sample_results: Vec<SampleResult>,
     /// Total evaluation time in milliseconds.
     #[serde(default)]
@@ -20,20 +28,89 @@ pub struct EvalResult {
     /// Throughput in samples per second.
     #[serde(default)]
     pub throughput: f32,
+    /// Seed the dataset was shuffled with before evaluation, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
+    /// Bootstrap confidence intervals, if computed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<BootstrapMetrics>,
+    /// Host, checkout, model and input hashes the evaluation ran with.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub environment: Option<EvalEnvironment>,
 }
 
 impl EvalResult {
     /// Create a new empty result.
     pub fn new() -> Self {
         Self {
+            schema_version: schema::SCHEMA_VERSION,
             total: 0,
             correct: 0,
             per_category: HashMap::new(),
             per_label: HashMap::new(),
+            per_source: HashMap::new(),
             sample_results: Vec::new(),
             elapsed_ms: 0,
             throughput: 0.0,
+            seed: None,
+            confidence: None,
+            environment: None,
+        }
+    }
+
+    /// Add a sample's result, updating the overall, per-category, per-source
+    /// and per-label counts.
+    pub fn push(&mut self, sample: &Sample, sample_result: SampleResult) {
+        self.total += 1;
+        if sample_result.correct {
+            self.correct += 1;
+        }
+
+        let cat_result = self
+            .per_category
+            .entry(sample.primary_category.clone())
+            .or_default();
+        cat_result.total += 1;
+        if sample_result.correct {
+            cat_result.correct += 1;
+        }
+
+        if let Some(source) = sample.source() {
+            let source_result = self.per_source.entry(source.to_string()).or_default();
+            source_result.total += 1;
+            if sample_result.correct {
+                source_result.correct += 1;
+            }
+        }
+
+        // Update per-label metrics
+        let expected_set: HashSet<_> = sample.expected_labels.iter().collect();
+        let detected_set: HashSet<_> = sample_result.detected_labels.iter().collect();
+
+        for label in &sample.expected_labels {
+            let entry = self.per_label.entry(label.clone()).or_default();
+            entry.expected_count += 1;
+        }
+
+        for label in &sample_result.detected_labels {
+            let entry = self.per_label.entry(label.clone()).or_default();
+            entry.detected_count += 1;
+
+            if expected_set.contains(label) {
+                entry.true_positives += 1;
+            } else {
+                entry.false_positives += 1;
+            }
         }
+
+        for label in &sample.expected_labels {
+            if !detected_set.contains(label) {
+                let entry = self.per_label.entry(label.clone()).or_default();
+                entry.false_negatives += 1;
+            }
+        }
+
+        self.sample_results.push(sample_result);
     }
 
     /// Compute metrics from the collected counts.
@@ -54,53 +131,125 @@ impl EvalResult {
             metrics.per_category.insert(category.clone(), cat_metrics);
         }
 
-        // Per-label precision/recall/F1
-        let mut total_precision = 0.0;
-        let mut total_recall = 0.0;
-        let mut label_count = 0;
+        // Per-source accuracy
+        for (source, result) in &self.per_source {
+            let mut source_metrics = SourceMetrics::default();
+            if result.total > 0 {
+                source_metrics.accuracy = result.correct as f32 / result.total as f32;
+            }
+            metrics.per_source.insert(source.clone(), source_metrics);
+        }
 
+        // Per-label precision/recall/F1
         for (label, result) in &self.per_label {
-            let mut label_metrics = LabelMetrics::default();
+            metrics.per_label.insert(
+                label.clone(),
+                LabelMetrics::from_counts(
+                    result.true_positives as f32,
+                    result.false_positives as f32,
+                    result.false_negatives as f32,
+                ),
+            );
+        }
 
-            // Precision = TP / (TP + FP)
-            let tp_fp = result.true_positives + result.false_positives;
-            if tp_fp > 0 {
-                label_metrics.precision = result.true_positives as f32 / tp_fp as f32;
-            }
+        metrics.macro_average(
+            self.per_label
+                .iter()
+                .filter(|(_, result)| result.expected_count > 0)
+                .map(|(label, _)| label),
+        );
 
-            // Recall = TP / (TP + FN)
-            let tp_fn = result.true_positives + result.false_negatives;
-            if tp_fn > 0 {
-                label_metrics.recall = result.true_positives as f32 / tp_fn as f32;
-            }
+        metrics
+    }
 
-            // F1 = 2 * (precision * recall) / (precision + recall)
-            let pr_sum = label_metrics.precision + label_metrics.recall;
-            if pr_sum > 0.0 {
-                label_metrics.f1 = 2.0 * label_metrics.precision * label_metrics.recall / pr_sum;
-            }
+    /// Whether any sample has a weight other than 1.0.
+    pub fn is_weighted(&self) -> bool {
+        self.sample_results.iter().any(|s| s.weight != 1.0)
+    }
 
-            if result.expected_count > 0 {
-                total_precision += label_metrics.precision;
-                total_recall += label_metrics.recall;
-                label_count += 1;
-            }
+    /// Compute metrics counting each sample by its weight.
+    ///
+    /// Accuracy and per-label precision/recall/F1 are recomputed from
+    /// `sample_results`, so over-represented kinds of samples can be down-weighted.
+    /// Per-category accuracy is left empty since sample results carry no category.
+    pub fn weighted_metrics(&self) -> EvalMetrics {
+        weighted_metrics_of(&self.sample_results)
+    }
+
+    /// Bootstrap 95% confidence intervals for accuracy and F1 over `sample_results`.
+    /// See [`Bootstrap`] to change the iterations, confidence level or seed.
+    pub fn bootstrap(&self) -> BootstrapMetrics {
+        Bootstrap::new().run(self)
+    }
+}
 
-            metrics.per_label.insert(label.clone(), label_metrics);
+/// Compute weighted metrics over the given sample results.
+pub(crate) fn weighted_metrics_of<'a>(
+    samples: impl IntoIterator<Item = &'a SampleResult>,
+) -> EvalMetrics {
+    let mut metrics = EvalMetrics::default();
+    let mut total = 0.0;
+    let mut correct = 0.0;
+    let mut per_label: HashMap<&String, WeightedLabelCounts> = HashMap::new();
+
+    for sample in samples {
+        total += sample.weight;
+        if sample.correct {
+            correct += sample.weight;
         }
 
-        // Macro-averaged precision/recall/F1
-        if label_count > 0 {
-            metrics.precision = total_precision / label_count as f32;
-            metrics.recall = total_recall / label_count as f32;
-            let pr_sum = metrics.precision + metrics.recall;
-            if pr_sum > 0.0 {
-                metrics.f1 = 2.0 * metrics.precision * metrics.recall / pr_sum;
+        for label in &sample.expected_labels {
+            let counts = per_label.entry(label).or_default();
+            counts.expected += sample.weight;
+
+            if !sample.detected_labels.contains(label) {
+                counts.false_negatives += sample.weight;
             }
         }
 
-        metrics
+        for label in &sample.detected_labels {
+            let counts = per_label.entry(label).or_default();
+
+            if sample.expected_labels.contains(label) {
+                counts.true_positives += sample.weight;
+            } else {
+                counts.false_positives += sample.weight;
+            }
+        }
+    }
+
+    if total > 0.0 {
+        metrics.accuracy = correct / total;
+    }
+
+    for (label, counts) in &per_label {
+        metrics.per_label.insert(
+            (*label).clone(),
+            LabelMetrics::from_counts(
+                counts.true_positives,
+                counts.false_positives,
+                counts.false_negatives,
+            ),
+        );
     }
+
+    metrics.macro_average(
+        per_label
+            .iter()
+            .filter(|(_, counts)| counts.expected > 0.0)
+            .map(|(label, _)| *label),
+    );
+
+    metrics
+}
+
+/// Weighted label counts accumulated from sample results.
+#[derive(Default)]
+struct WeightedLabelCounts {
+    expected: f32,
+    true_positives: f32,
+    false_positives: f32,
+    false_negatives: f32,
 }
 
 impl Default for EvalResult {
@@ -111,6 +260,8 @@ impl Default for EvalResult {
 
 #[cfg(test)]
 mod tests {
+    use crate::eval::fixtures;
+
     use super::*;
 
     #[test]
@@ -138,6 +289,22 @@ mod tests {
         assert!((cat.accuracy - 0.8).abs() < 0.001);
     }
 
+    #[test]
+    fn source_result_computes_accuracy() {
+        let mut result = EvalResult::new();
+        result.per_source.insert(
+            "daily_dialog".to_string(),
+            SourceResult {
+                total: 4,
+                correct: 3,
+            },
+        );
+        let metrics = result.metrics();
+
+        let source = metrics.per_source.get("daily_dialog").unwrap();
+        assert!((source.accuracy - 0.75).abs() < 0.001);
+    }
+
     #[test]
     fn label_result_computes_precision_recall_f1() {
         let mut result = EvalResult::new();
@@ -158,4 +325,86 @@ mod tests {
         assert!((label.recall - 0.6).abs() < 0.001);
         assert!((label.f1 - 0.667).abs() < 0.01);
     }
+
+    #[test]
+    fn weighted_metrics_scale_accuracy_by_weight() {
+        let result = fixtures::result([
+            SampleResult {
+                weight: 0.25,
+                ..fixtures::sample_result("a", true, &["phatic"])
+            },
+            fixtures::sample_result("b", false, &["task"]),
+        ]);
+
+        assert!(result.is_weighted());
+        let metrics = result.weighted_metrics();
+        assert!((metrics.accuracy - 0.2).abs() < 0.001);
+    }
+
+    #[test]
+    fn weighted_metrics_scale_label_counts_by_weight() {
+        let result = fixtures::result([
+            SampleResult {
+                weight: 2.0,
+                ..fixtures::sample_result("a", true, &["task"])
+            },
+            fixtures::labeled_result("b", &[], &["task"]),
+            fixtures::sample_result("c", false, &["task"]),
+        ]);
+
+        let metrics = result.weighted_metrics();
+        let label = metrics.per_label.get("task").unwrap();
+        assert!((label.precision - 0.667).abs() < 0.001);
+        assert!((label.recall - 0.667).abs() < 0.001);
+        assert!((metrics.f1 - 0.667).abs() < 0.001);
+    }
+
+    #[test]
+    fn weighted_metrics_match_metrics_without_weights() {
+        let mut result = fixtures::result([
+            fixtures::sample_result("a", true, &["task"]),
+            fixtures::sample_result("b", false, &["task"]),
+        ]);
+        result.total = 2;
+        result.correct = 1;
+
+        assert!(!result.is_weighted());
+        assert!((result.weighted_metrics().accuracy - result.metrics().accuracy).abs() < 0.001);
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn loads_exports_without_schema_version() {
+        let json = r#"{
+            "total": 1,
+            "correct": 1,
+            "per_category": {},
+            "per_label": {},
+            "sample_results": [{
+                "id": "a",
+                "expected_decision": "accept",
+                "actual_decision": "accept",
+                "correct": true,
+                "score": 0.9,
+                "expected_labels": [],
+                "detected_labels": []
+            }]
+        }"#;
+
+        let result: EvalResult = serde_json::from_str(json).unwrap();
+
+        assert_eq!(result.schema_version, schema::SCHEMA_VERSION);
+        assert!(result.per_source.is_empty());
+        assert_eq!(result.sample_results[0].weight, 1.0);
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn rejects_exports_from_newer_schema() {
+        let mut json = serde_json::to_value(EvalResult::new()).unwrap();
+        json["schema_version"] = (schema::SCHEMA_VERSION + 1).into();
+
+        let err = serde_json::from_value::<EvalResult>(json).unwrap_err();
+        assert!(err.to_string().contains("unsupported schema version"));
+    }
 }
@@ -11,6 +11,34 @@ pub struct EvalMetrics {
     pub f1: f32,
     pub per_category: HashMap<String, CategoryMetrics>,
     pub per_label: HashMap<String, LabelMetrics>,
+    #[serde(default)]
+    pub per_source: HashMap<String, SourceMetrics>,
+}
+
+impl EvalMetrics {
+    /// Set the macro-averaged precision/recall/F1 over the given labels of `per_label`.
+    pub(crate) fn macro_average<'a>(&mut self, labels: impl Iterator<Item = &'a String>) {
+        let mut total_precision = 0.0;
+        let mut total_recall = 0.0;
+        let mut label_count = 0;
+
+        for label in labels {
+            if let Some(metrics) = self.per_label.get(label) {
+                total_precision += metrics.precision;
+                total_recall += metrics.recall;
+                label_count += 1;
+            }
+        }
+
+        if label_count > 0 {
+            self.precision = total_precision / label_count as f32;
+            self.recall = total_recall / label_count as f32;
+            let pr_sum = self.precision + self.recall;
+            if pr_sum > 0.0 {
+                self.f1 = 2.0 * self.precision * self.recall / pr_sum;
+            }
+        }
+    }
 }
 
 /// Computed metrics for a specific category.
@@ -19,6 +47,12 @@ pub struct CategoryMetrics {
     pub accuracy: f32,
 }
 
+/// Computed metrics for a specific source dataset.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SourceMetrics {
+    pub accuracy: f32,
+}
+
 /// Computed metrics for a specific label.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct LabelMetrics {
@@ -26,3 +60,31 @@ pub struct LabelMetrics {
     pub recall: f32,
     pub f1: f32,
 }
+
+impl LabelMetrics {
+    /// Compute precision/recall/F1 from (possibly weighted) true positive,
+    /// false positive and false negative counts.
+    pub fn from_counts(true_positives: f32, false_positives: f32, false_negatives: f32) -> Self {
+        let mut metrics = Self::default();
+
+        // Precision = TP / (TP + FP)
+        let tp_fp = true_positives + false_positives;
+        if tp_fp > 0.0 {
+            metrics.precision = true_positives / tp_fp;
+        }
+
+        // Recall = TP / (TP + FN)
+        let tp_fn = true_positives + false_negatives;
+        if tp_fn > 0.0 {
+            metrics.recall = true_positives / tp_fn;
+        }
+
+        // F1 = 2 * (precision * recall) / (precision + recall)
+        let pr_sum = metrics.precision + metrics.recall;
+        if pr_sum > 0.0 {
+            metrics.f1 = 2.0 * metrics.precision * metrics.recall / pr_sum;
+        }
+
+        metrics
+    }
+}
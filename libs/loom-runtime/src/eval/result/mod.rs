@@ -1,13 +1,34 @@
+mod bootstrap;
 mod category;
+mod compare;
+mod confusion;
+mod environment;
 mod eval;
 mod export;
 mod label;
 mod metrics;
+mod predictions;
 mod sample;
+mod source;
 
+pub use bootstrap::*;
 pub use category::*;
+pub use compare::*;
+pub use confusion::*;
+pub use environment::*;
 pub use eval::*;
 pub use export::*;
 pub use label::*;
 pub use metrics::*;
+pub use predictions::*;
 pub use sample::*;
+pub use source::*;
+
+/// Quote a CSV field if it contains a delimiter, quote or newline.
+pub(crate) fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
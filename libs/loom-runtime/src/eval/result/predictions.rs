@@ -0,0 +1,142 @@
+use serde::{Deserialize, Serialize};
+
+use super::{EvalResult, SampleResult, csv_field};
+use crate::eval::Decision;
+
+/// Per-sample prediction in the flat layout expected by common analysis tools
+/// (e.g. `datasets.load_dataset("json", ...)` or `sklearn.metrics`).
+///
+/// Decisions are binary with accept as the positive class, so `y_true` and
+/// `y_pred` are 1 for accept and 0 for reject, and `score` is the positive
+/// class score.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Prediction {
+    pub id: String,
+    pub y_true: u8,
+    pub y_pred: u8,
+    pub score: f32,
+    pub weight: f32,
+    pub expected_labels: Vec<String>,
+    pub detected_labels: Vec<String>,
+}
+
+impl From<&SampleResult> for Prediction {
+    fn from(sample: &SampleResult) -> Self {
+        Self {
+            id: sample.id.clone(),
+            y_true: class(&sample.expected_decision),
+            y_pred: class(&sample.actual_decision),
+            score: sample.score,
+            weight: sample.weight,
+            expected_labels: sample.expected_labels.clone(),
+            detected_labels: sample.detected_labels.clone(),
+        }
+    }
+}
+
+impl EvalResult {
+    /// Per-sample predictions, in sample order.
+    pub fn predictions(&self) -> Vec<Prediction> {
+        self.sample_results.iter().map(Prediction::from).collect()
+    }
+
+    /// Render predictions as JSON Lines, one object per sample.
+    #[cfg(feature = "json")]
+    pub fn predictions_jsonl(&self) -> serde_json::Result<String> {
+        let mut jsonl = String::new();
+
+        for prediction in self.predictions() {
+            jsonl.push_str(&serde_json::to_string(&prediction)?);
+            jsonl.push('\n');
+        }
+
+        Ok(jsonl)
+    }
+
+    /// Render predictions as CSV. Label lists are joined with `;`.
+    pub fn predictions_csv(&self) -> String {
+        let mut csv =
+            String::from("id,y_true,y_pred,score,weight,expected_labels,detected_labels\n");
+
+        for p in self.predictions() {
+            csv.push_str(&format!(
+                "{},{},{},{},{},{},{}\n",
+                csv_field(&p.id),
+                p.y_true,
+                p.y_pred,
+                p.score,
+                p.weight,
+                csv_field(&p.expected_labels.join(";")),
+                csv_field(&p.detected_labels.join(";")),
+            ));
+        }
+
+        csv
+    }
+}
+
+fn class(decision: &Decision) -> u8 {
+    match decision {
+        Decision::Accept => 1,
+        Decision::Reject => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::eval::fixtures;
+
+    use super::*;
+
+    fn result() -> EvalResult {
+        fixtures::result([
+            SampleResult {
+                score: 0.25,
+                ..fixtures::sample_result("a,1", false, &["task", "time"])
+            },
+            SampleResult {
+                expected_decision: Decision::Reject,
+                actual_decision: Decision::Reject,
+                score: 0.1,
+                weight: 2.0,
+                ..fixtures::sample_result("b", true, &["phatic"])
+            },
+        ])
+    }
+
+    #[test]
+    fn predictions_map_decisions_to_binary_classes() {
+        let predictions = result().predictions();
+
+        assert_eq!(predictions.len(), 2);
+        assert_eq!(predictions[0].y_true, 1);
+        assert_eq!(predictions[0].y_pred, 0);
+        assert_eq!(predictions[1].y_true, 0);
+        assert_eq!(predictions[1].weight, 2.0);
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    fn predictions_jsonl_writes_one_object_per_line() {
+        let jsonl = result().predictions_jsonl().unwrap();
+        let lines: Vec<&str> = jsonl.lines().collect();
+
+        assert_eq!(lines.len(), 2);
+
+        let first: Prediction = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!(first.id, "a,1");
+        assert_eq!(first.expected_labels, vec!["task", "time"]);
+    }
+
+    #[test]
+    fn predictions_csv_quotes_fields() {
+        let csv = result().predictions_csv();
+
+        assert_eq!(
+            csv,
+            "id,y_true,y_pred,score,weight,expected_labels,detected_labels\n\
+             \"a,1\",1,0,0.25,1,task;time,\n\
+             b,0,0,0.1,2,phatic,phatic\n"
+        );
+    }
+}
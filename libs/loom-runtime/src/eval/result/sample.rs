@@ -15,4 +15,13 @@ pub struct SampleResult {
     /// Per-sample inference time in milliseconds (if available).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub elapsed_ms: Option<i64>,
+    /// Weight of the sample in weighted metrics.
+    #[serde(default = "SampleResult::weight")]
+    pub weight: f32,
+}
+
+impl SampleResult {
+    fn weight() -> f32 {
+        1.0
+    }
 }
@@ -0,0 +1,8 @@
+use serde::{Deserialize, Serialize};
+
+/// Raw counts for samples derived from a specific source dataset.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SourceResult {
+    pub total: usize,
+    pub correct: usize,
+}
@@ -0,0 +1,149 @@
+use std::collections::{HashMap, HashSet};
+
+use super::{EvalEnvironment, EvalResult, SampleDataset, SampleResult};
+
+impl SampleDataset {
+    /// Samples with no result in `partial`, in dataset order.
+    pub fn pending(&self, partial: &EvalResult) -> SampleDataset {
+        let evaluated: HashSet<&str> = partial
+            .sample_results
+            .iter()
+            .map(|r| r.id.as_str())
+            .collect();
+
+        SampleDataset {
+            version: self.version.clone(),
+            created: self.created.clone(),
+            samples: self
+                .samples
+                .iter()
+                .filter(|s| !evaluated.contains(s.id.as_str()))
+                .cloned()
+                .collect(),
+        }
+    }
+}
+
+impl EvalResult {
+    /// Merge this (partial) result with `rest`, the result of evaluating the
+    /// dataset's pending samples.
+    ///
+    /// Counts are rebuilt from `dataset` in dataset order, so categories and
+    /// sources are recovered for the previously evaluated samples. Results for
+    /// IDs no longer in the dataset are dropped, and `rest` wins when both
+    /// contain an ID. Timings are summed; bootstrap intervals are not carried
+    /// over since they no longer match the merged samples.
+    pub fn merge(self, rest: EvalResult, dataset: &SampleDataset) -> EvalResult {
+        let elapsed_ms = self.elapsed_ms + rest.elapsed_ms;
+        let seed = self.seed;
+        let environment = rest.environment.or(self.environment);
+
+        let mut by_id: HashMap<String, SampleResult> = self
+            .sample_results
+            .into_iter()
+            .chain(rest.sample_results)
+            .map(|r| (r.id.clone(), r))
+            .collect();
+
+        let mut result = EvalResult::new();
+
+        for sample in &dataset.samples {
+            if let Some(sample_result) = by_id.remove(&sample.id) {
+                result.push(sample, sample_result);
+            }
+        }
+
+        result.elapsed_ms = elapsed_ms;
+        result.throughput = if elapsed_ms > 0 {
+            result.total as f32 / (elapsed_ms as f32 / 1000.0)
+        } else {
+            0.0
+        };
+        result.seed = seed;
+        result.environment = environment.map(|env| EvalEnvironment {
+            dataset_hash: dataset.content_hash(),
+            ..env
+        });
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::eval::{Decision, Sample, fixtures};
+
+    use super::*;
+
+    fn sample(id: &str, category: &str) -> Sample {
+        fixtures::sample(
+            id,
+            &format!("text {}", id),
+            Decision::Accept,
+            category,
+            &["task"],
+        )
+    }
+
+    fn dataset() -> SampleDataset {
+        let mut dataset = SampleDataset::new();
+        dataset.samples.push(sample("1", "task"));
+        dataset.samples.push(sample("2", "task"));
+        dataset.samples.push(sample("3", "emotion"));
+        dataset
+    }
+
+    fn evaluate(dataset: &SampleDataset, correct: bool) -> EvalResult {
+        let mut result = EvalResult::new();
+
+        for sample in &dataset.samples {
+            result.push(
+                sample,
+                SampleResult {
+                    score: 0.5,
+                    ..fixtures::sample_result(&sample.id, correct, &["task"])
+                },
+            );
+        }
+
+        result.elapsed_ms = 1000;
+        result
+    }
+
+    #[test]
+    fn pending_skips_evaluated_samples() {
+        let dataset = dataset();
+        let mut partial = SampleDataset::new();
+        partial.samples.push(sample("2", "task"));
+
+        let pending = dataset.pending(&evaluate(&partial, true));
+        let ids: Vec<&str> = pending.samples.iter().map(|s| s.id.as_str()).collect();
+
+        assert_eq!(ids, vec!["1", "3"]);
+    }
+
+    #[test]
+    fn merge_rebuilds_counts_in_dataset_order() {
+        let dataset = dataset();
+        let mut partial_dataset = SampleDataset::new();
+        partial_dataset.samples.push(sample("3", "emotion"));
+        partial_dataset.samples.push(sample("gone", "task"));
+
+        let partial = evaluate(&partial_dataset, true);
+        let rest = evaluate(&dataset.pending(&partial), false);
+        let merged = partial.merge(rest, &dataset);
+        let ids: Vec<&str> = merged
+            .sample_results
+            .iter()
+            .map(|r| r.id.as_str())
+            .collect();
+
+        assert_eq!(ids, vec!["1", "2", "3"]);
+        assert_eq!(merged.total, 3);
+        assert_eq!(merged.correct, 1);
+        assert_eq!(merged.per_category["task"].total, 2);
+        assert_eq!(merged.per_category["emotion"].correct, 1);
+        assert_eq!(merged.per_label["task"].false_negatives, 2);
+        assert_eq!(merged.elapsed_ms, 2000);
+        assert!((merged.throughput - 1.5).abs() < 0.001);
+    }
+}
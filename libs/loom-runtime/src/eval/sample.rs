@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 
 use super::Difficulty;
+use crate::{Context, Turn};
 
 // Re-export Decision from cortex (where Scorer trait lives)
 pub use loom_cortex::bench::Decision;
@@ -20,4 +21,80 @@ pub struct Sample {
     pub notes: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub metadata: Option<serde_json::Value>,
+    /// Relative weight in weighted metrics (default: 1.0)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub weight: Option<f32>,
+    /// Dataset the sample was derived from (e.g. "daily_dialog")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+}
+
+impl Sample {
+    /// Weight of this sample in weighted metrics, 1.0 unless set
+    pub fn weight(&self) -> f32 {
+        self.weight.unwrap_or(1.0)
+    }
+
+    /// Source dataset of this sample, falling back to `metadata.source`
+    /// for datasets fetched before `source` was a field
+    pub fn source(&self) -> Option<&str> {
+        self.source.as_deref().or_else(|| {
+            self.metadata
+                .as_ref()
+                .and_then(|m| m.get("source"))
+                .and_then(|s| s.as_str())
+        })
+    }
+
+    /// Previous turns of the conversation, one per non-empty `context` line.
+    /// A `speaker: text` line is attributed to `speaker`, others to no one.
+    pub fn history(&self) -> Vec<Turn> {
+        let Some(context) = &self.context else {
+            return vec![];
+        };
+
+        context
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .enumerate()
+            .map(|(i, line)| match line.split_once(": ") {
+                Some((speaker, text)) if !speaker.contains(char::is_whitespace) => {
+                    Turn::new(speaker, i, text)
+                }
+                _ => Turn::new("", i, line),
+            })
+            .collect()
+    }
+
+    /// Context to score this sample in, with its [`history`](Self::history)
+    pub fn to_context(&self) -> Context<()> {
+        Context::new(&self.text, ()).with_history(self.history())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eval::fixtures;
+
+    #[test]
+    fn history_reads_context_lines_as_turns() {
+        let mut sample = fixtures::sample("s1", "ship it", Decision::Accept, "task", &[]);
+        assert!(sample.history().is_empty());
+
+        sample.context = Some("bob: morning\n\nthe build is green\nalice: ready?".to_string());
+        let history = sample.history();
+
+        assert_eq!(history.len(), 3);
+        assert_eq!(
+            (history[0].speaker.as_str(), history[0].text.as_str()),
+            ("bob", "morning")
+        );
+        assert_eq!(history[1].speaker, "");
+        assert_eq!(
+            sample.to_context().premise(3),
+            "bob: morning\nthe build is green\nalice: ready?\nship it"
+        );
+    }
 }
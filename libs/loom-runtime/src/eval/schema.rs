@@ -0,0 +1,32 @@
+use serde::{Deserialize, Deserializer, de::Error};
+
+/// Schema version written with serialized [`ScoreResult`](super::score::ScoreResult)
+/// and [`EvalResult`](super::EvalResult) exports.
+///
+/// Bump this when a change to either struct needs more than serde defaults to
+/// read older exports, and upgrade those exports in [`upgrade`].
+/// Exports written before versioning was introduced have no `schema_version`
+/// and are treated as version 0.
+pub const SCHEMA_VERSION: u32 = 1;
+
+pub(crate) fn current() -> u32 {
+    SCHEMA_VERSION
+}
+
+/// Deserialize a `schema_version`, upgrading older exports to the current version.
+///
+/// Fields added since an older release fall back to their serde defaults, so
+/// loading is all an upgrade takes. Exports from a newer release are rejected,
+/// since they may carry fields this release would silently drop.
+pub(crate) fn upgrade<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u32, D::Error> {
+    let version = u32::deserialize(deserializer)?;
+
+    if version > SCHEMA_VERSION {
+        return Err(D::Error::custom(format!(
+            "unsupported schema version {} (newest supported is {})",
+            version, SCHEMA_VERSION
+        )));
+    }
+
+    Ok(SCHEMA_VERSION)
+}
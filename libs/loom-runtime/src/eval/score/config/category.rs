@@ -8,6 +8,7 @@ use super::ScoreLabelConfig;
 /// Category definition containing labels
 /// Note: Category name is the key in the parent BTreeMap
 #[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ScoreCategoryConfig {
     /// Number of top labels to consider for this category
     #[serde(default = "ScoreCategoryConfig::top_k")]
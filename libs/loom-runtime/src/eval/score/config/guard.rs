@@ -0,0 +1,145 @@
+use serde::{Deserialize, Serialize};
+use serde_valid::Validate;
+
+/// Comparison applied between a label score and a guard threshold
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum ScoreComparator {
+    /// `score > threshold`
+    Gt,
+    /// `score >= threshold`
+    Gte,
+    /// `score < threshold`
+    Lt,
+    /// `score <= threshold`
+    Lte,
+}
+
+impl ScoreComparator {
+    /// Compare a score against a threshold
+    pub fn compare(&self, score: f32, threshold: f32) -> bool {
+        match self {
+            Self::Gt => score > threshold,
+            Self::Gte => score >= threshold,
+            Self::Lt => score < threshold,
+            Self::Lte => score <= threshold,
+        }
+    }
+}
+
+impl std::fmt::Display for ScoreComparator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Gt => write!(f, ">"),
+            Self::Gte => write!(f, ">="),
+            Self::Lt => write!(f, "<"),
+            Self::Lte => write!(f, "<="),
+        }
+    }
+}
+
+/// Action taken when a guard rule matches
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum ScoreGuardAction {
+    /// Cancel the result regardless of the overall score
+    Reject,
+    /// Keep the result even if the overall score is below threshold
+    Accept,
+}
+
+/// Guard rule evaluated against a label's raw score after scoring.
+/// Guards run in order and the first matching rule decides the outcome.
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ScoreGuardConfig {
+    /// Name of the label whose raw score is checked
+    #[validate(min_length = 1)]
+    pub label: String,
+
+    /// Comparison applied between the label score and threshold
+    #[serde(default = "ScoreGuardConfig::comparator")]
+    pub comparator: ScoreComparator,
+
+    /// Threshold the label score is compared against
+    #[validate(minimum = 0.0)]
+    #[validate(maximum = 1.0)]
+    pub threshold: f32,
+
+    /// Action taken when the rule matches
+    #[serde(default = "ScoreGuardConfig::action")]
+    pub action: ScoreGuardAction,
+}
+
+impl ScoreGuardConfig {
+    fn comparator() -> ScoreComparator {
+        ScoreComparator::Gte
+    }
+
+    fn action() -> ScoreGuardAction {
+        ScoreGuardAction::Reject
+    }
+
+    /// Check whether this rule matches the given label score
+    pub fn matches(&self, score: f32) -> bool {
+        self.comparator.compare(score, self.threshold)
+    }
+}
+
+impl std::fmt::Display for ScoreGuardConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {} {}", self.label, self.comparator, self.threshold)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn guard(comparator: ScoreComparator) -> ScoreGuardConfig {
+        ScoreGuardConfig {
+            label: "phatic".to_string(),
+            comparator,
+            threshold: 0.80,
+            action: ScoreGuardAction::Reject,
+        }
+    }
+
+    #[test]
+    fn comparators_match_expected_scores() {
+        assert!(guard(ScoreComparator::Gte).matches(0.80));
+        assert!(!guard(ScoreComparator::Gt).matches(0.80));
+        assert!(guard(ScoreComparator::Lt).matches(0.79));
+        assert!(guard(ScoreComparator::Lte).matches(0.80));
+        assert!(!guard(ScoreComparator::Lte).matches(0.81));
+    }
+
+    #[test]
+    fn guard_uses_defaults() {
+        let json = r#"{"label": "phatic", "threshold": 0.8}"#;
+        let guard: ScoreGuardConfig = serde_json::from_str(json).unwrap();
+
+        assert_eq!(guard.comparator, ScoreComparator::Gte);
+        assert_eq!(guard.action, ScoreGuardAction::Reject);
+        assert!(guard.validate().is_ok());
+    }
+
+    #[test]
+    fn guard_parses_snake_case_fields() {
+        let json = r#"{"label": "task", "comparator": "lt", "threshold": 0.2, "action": "accept"}"#;
+        let guard: ScoreGuardConfig = serde_json::from_str(json).unwrap();
+
+        assert_eq!(guard.comparator, ScoreComparator::Lt);
+        assert_eq!(guard.action, ScoreGuardAction::Accept);
+        assert_eq!(guard.to_string(), "task < 0.2");
+    }
+
+    #[test]
+    fn invalid_threshold_fails_validation() {
+        let mut guard = guard(ScoreComparator::Gte);
+        guard.threshold = 1.5;
+        assert!(guard.validate().is_err());
+    }
+}
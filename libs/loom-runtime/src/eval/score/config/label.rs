@@ -4,6 +4,7 @@ use serde_valid::Validate;
 /// Complete label definition
 /// Note: Label name is the key in the parent BTreeMap
 #[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ScoreLabelConfig {
     /// Hypothesis text for zero-shot classification
     #[validate(min_length = 1)]
@@ -42,6 +43,11 @@ impl ScoreLabelConfig {
     fn platt_a() -> f32 {
         1.0
     }
+
+    /// Apply this label's Platt scaling to a raw model score
+    pub fn calibrate(&self, raw: f32) -> f32 {
+        crate::eval::score::result::calibrate(raw, self.platt_a, self.platt_b)
+    }
 }
 
 impl Default for ScoreLabelConfig {
@@ -1,23 +1,30 @@
 mod category;
+mod guard;
 mod label;
 mod modifier;
+mod preprocess;
+mod schedule;
 
 pub use category::*;
+pub use guard::*;
 pub use label::*;
 pub use modifier::*;
+pub use preprocess::*;
+pub use schedule::*;
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
 
 use loom_cortex::config::{CortexModelConfig, CortexZeroShotConfig};
-use loom_error::Result;
+use loom_error::{Error, ErrorCode, Result};
 
 use serde::{Deserialize, Serialize};
 use serde_valid::Validate;
 
-use super::ScoreLayer;
+use super::{ScoreCategory, ScoreLabel, ScoreLayer, ScoreProvenance, ScoreResult};
 
 /// Root configuration for the scoring engine
 #[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ScoreConfig {
     /// Model configuration for zero-shot classification
     #[serde(default)]
@@ -41,6 +48,22 @@ pub struct ScoreConfig {
 
     /// Category definitions with their labels (keyed by category name)
     pub categories: BTreeMap<String, ScoreCategoryConfig>,
+
+    /// Guard rules evaluated in order after scoring
+    #[serde(default)]
+    pub guards: Vec<ScoreGuardConfig>,
+
+    /// Route small requests to an interactive (CPU) replica of the model
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schedule: Option<ScoreScheduleConfig>,
+
+    /// Text transformations applied before inference
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preprocess: Option<ScorePreprocessConfig>,
+
+    /// Number of previous conversation turns included in the premise
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context_turns: Option<usize>,
 }
 
 impl ScoreConfig {
@@ -86,6 +109,67 @@ impl ScoreConfig {
             .collect()
     }
 
+    /// Find the first guard rule matching the given result, if any
+    pub fn guard_of(&self, result: &ScoreResult) -> Option<&ScoreGuardConfig> {
+        self.guards.iter().find(|g| {
+            result
+                .label(&g.label)
+                .is_some_and(|l| g.matches(l.raw_score))
+        })
+    }
+
+    /// Build a ScoreResult from raw per-label model scores.
+    /// Labels missing from `raw_scores` score 0.
+    pub fn result_of(&self, raw_scores: &HashMap<&str, f32>) -> ScoreResult {
+        let mut categories = BTreeMap::new();
+
+        for (cat_name, cat_config) in &self.categories {
+            let mut labels = BTreeMap::new();
+
+            for (label_name, label_config) in &cat_config.labels {
+                let raw_score = raw_scores.get(label_name.as_str()).copied().unwrap_or(0.0);
+
+                labels.insert(
+                    label_name.clone(),
+                    ScoreLabel::new(raw_score, 0, label_config),
+                );
+            }
+
+            categories.insert(
+                cat_name.clone(),
+                ScoreCategory::topk(labels, cat_config.top_k),
+            );
+        }
+
+        ScoreResult::new(categories)
+    }
+
+    /// Decide whether a result is kept, applying guards before the text-length adjusted threshold.
+    /// Returns the accepting guard (if any), or a `Cancel` error when the result is rejected.
+    pub fn check(
+        &self,
+        text_len: usize,
+        result: &ScoreResult,
+    ) -> Result<Option<&ScoreGuardConfig>> {
+        let effective_threshold = self.threshold_of(text_len);
+
+        match self.guard_of(result) {
+            Some(guard) if guard.action == ScoreGuardAction::Reject => Err(Error::builder()
+                .code(ErrorCode::Cancel)
+                .message(format!("rejected by guard '{}'", guard))
+                .build()),
+            Some(guard) => Ok(Some(guard)),
+            None if result.score < effective_threshold => Err(Error::builder()
+                .code(ErrorCode::Cancel)
+                .message(format!(
+                    "score {} is less than minimum threshold {}",
+                    result.score, effective_threshold
+                ))
+                .build()),
+            None => Ok(None),
+        }
+    }
+
     /// Get hypothesis for a label by name
     pub fn hypothesis(&self, label_name: &str) -> String {
         self.label(label_name)
@@ -93,23 +177,42 @@ impl ScoreConfig {
             .unwrap_or_else(|| format!("This example is {}.", label_name))
     }
 
+    /// Compute the provenance for scores produced by this configuration.
+    /// The config hash covers every field, so any change marks stored scores as outdated.
+    /// Scheduling is left out of the hash: it moves work between devices
+    /// without changing what a score means.
+    pub fn provenance(&self) -> ScoreProvenance {
+        let scored = Self {
+            schedule: None,
+            ..self.clone()
+        };
+        let bytes = serde_json::to_vec(&scored).expect("ScoreConfig is serializable");
+        let config_hash = blake3::hash(&bytes)
+            .as_bytes()
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect::<String>();
+
+        ScoreProvenance::new(config_hash, self.model.id())
+    }
+
     /// Build a ScoreLayer from this configuration
     pub fn build(self) -> Result<ScoreLayer> {
         self.validate()
-            .map_err(|e| loom_error::Error::builder().message(&e.to_string()).build())?;
+            .map_err(|e| loom_error::Error::builder().message(e.to_string()).build())?;
 
         // Manual nested BTreeMap validation (serde_valid doesn't support this)
         for (cat_name, cat_config) in &self.categories {
             cat_config.validate().map_err(|e| {
                 loom_error::Error::builder()
-                    .message(&format!("Category '{}': {}", cat_name, e))
+                    .message(format!("Category '{}': {}", cat_name, e))
                     .build()
             })?;
 
             for (label_name, label_config) in &cat_config.labels {
                 label_config.validate().map_err(|e| {
                     loom_error::Error::builder()
-                        .message(&format!(
+                        .message(format!(
                             "Category '{}', Label '{}': {}",
                             cat_name, label_name, e
                         ))
@@ -118,6 +221,21 @@ impl ScoreConfig {
             }
         }
 
+        // Guards must reference a configured label
+        for guard in &self.guards {
+            guard.validate().map_err(|e| {
+                loom_error::Error::builder()
+                    .message(format!("Guard '{}': {}", guard.label, e))
+                    .build()
+            })?;
+
+            if self.label(&guard.label).is_none() {
+                return Err(loom_error::Error::builder()
+                    .message(format!("Guard references unknown label '{}'", guard.label))
+                    .build());
+            }
+        }
+
         // Validate modifier limits relationship
         if self.modifiers.short_text_limit >= self.modifiers.long_text_limit {
             return Err(loom_error::Error::builder()
@@ -125,8 +243,37 @@ impl ScoreConfig {
                 .build());
         }
 
+        if let Some(schedule) = &self.schedule {
+            schedule.validate().map_err(|e| {
+                loom_error::Error::builder()
+                    .message(format!("Schedule: {}", e))
+                    .build()
+            })?;
+        }
+
         let model = self.model.clone().build()?;
-        Ok(ScoreLayer::new(model, self))
+
+        // A replica is only worth loading when it runs on a different device
+        let interactive = match &self.schedule {
+            Some(schedule) if &schedule.interactive_device != self.model.device() => {
+                let provenance = self.provenance();
+                let config = Self {
+                    model: self
+                        .model
+                        .clone()
+                        .with_device(schedule.interactive_device.clone()),
+                    schedule: None,
+                    ..self.clone()
+                };
+                let model = config.model.clone().build()?;
+
+                // Replica scores are the same scores, so they keep this config's provenance
+                Some(ScoreLayer::new(model, config).with_provenance(provenance))
+            }
+            _ => None,
+        };
+
+        Ok(ScoreLayer::new(model, self).with_interactive(interactive))
     }
 }
 
@@ -138,6 +285,10 @@ impl Default for ScoreConfig {
             top_k: Self::top_k(),
             modifiers: ScoreModifierConfig::default(),
             categories: BTreeMap::new(),
+            guards: Vec::new(),
+            schedule: None,
+            preprocess: None,
+            context_turns: None,
         }
     }
 }
@@ -178,6 +329,10 @@ mod tests {
             top_k: 2,
             modifiers: ScoreModifierConfig::default(),
             categories,
+            guards: Vec::new(),
+            schedule: None,
+            preprocess: None,
+            context_turns: None,
         }
     }
 
@@ -302,6 +457,138 @@ mod tests {
         assert_eq!(config.modifiers.long_text_delta, 0.05);
         // serde(default) uses CortexModelConfig::default() which is Conversation
         assert!(config.model.is_conversation());
+        assert!(config.guards.is_empty());
         assert!(config.validate().is_ok());
     }
+
+    #[test]
+    fn provenance_is_stable_for_equal_configs() {
+        let a = test_config().provenance();
+        let b = test_config().provenance();
+        assert_eq!(a, b);
+        assert_eq!(a.config_hash.len(), 64);
+        assert_eq!(a.model, "conversation/gpt2");
+    }
+
+    #[test]
+    fn provenance_changes_with_config() {
+        let stored = test_config().provenance();
+        let mut config = test_config();
+        config.threshold = 0.60;
+        assert!(stored.is_outdated(&config.provenance()));
+    }
+
+    #[test]
+    fn guard_of_returns_first_matching_rule() {
+        let mut config = test_config();
+        config.guards = vec![
+            ScoreGuardConfig {
+                label: "label1".to_string(),
+                comparator: ScoreComparator::Gte,
+                threshold: 0.90,
+                action: ScoreGuardAction::Reject,
+            },
+            ScoreGuardConfig {
+                label: "label2".to_string(),
+                comparator: ScoreComparator::Gte,
+                threshold: 0.50,
+                action: ScoreGuardAction::Accept,
+            },
+        ];
+
+        let cat = config.category("test").unwrap();
+        let mut labels = BTreeMap::new();
+        labels.insert(
+            "label1".to_string(),
+            ScoreLabel::new(0.85, 0, &cat.labels["label1"]),
+        );
+        labels.insert(
+            "label2".to_string(),
+            ScoreLabel::new(0.60, 0, &cat.labels["label2"]),
+        );
+
+        let mut categories = BTreeMap::new();
+        categories.insert("test".to_string(), ScoreCategory::new(labels));
+        let result = ScoreResult::new(categories);
+
+        let guard = config.guard_of(&result).unwrap();
+        assert_eq!(guard.label, "label2");
+        assert_eq!(guard.action, ScoreGuardAction::Accept);
+    }
+
+    #[test]
+    fn result_of_defaults_missing_labels_to_zero() {
+        let config = test_config();
+        let mut raw_scores = HashMap::new();
+        raw_scores.insert("label1", 0.9);
+
+        let result = config.result_of(&raw_scores);
+        assert_eq!(result.label("label1").unwrap().raw_score, 0.9);
+        assert_eq!(result.label("label2").unwrap().raw_score, 0.0);
+    }
+
+    #[test]
+    fn check_rejects_below_threshold() {
+        let config = test_config();
+        let result = config.result_of(&HashMap::new());
+        let err = config.check(100, &result).unwrap_err();
+        assert!(err.code().is_cancel());
+    }
+
+    #[test]
+    fn check_accept_guard_overrides_threshold() {
+        let mut config = test_config();
+        config.guards = vec![ScoreGuardConfig {
+            label: "label1".to_string(),
+            comparator: ScoreComparator::Gte,
+            threshold: 0.50,
+            action: ScoreGuardAction::Accept,
+        }];
+
+        let mut raw_scores = HashMap::new();
+        raw_scores.insert("label1", 0.6);
+        let result = config.result_of(&raw_scores);
+
+        let guard = config.check(100, &result).unwrap();
+        assert_eq!(guard.map(|g| g.label.as_str()), Some("label1"));
+    }
+
+    #[test]
+    fn guard_of_ignores_missing_labels() {
+        let mut config = test_config();
+        config.guards = vec![ScoreGuardConfig {
+            label: "phatic".to_string(),
+            comparator: ScoreComparator::Gte,
+            threshold: 0.0,
+            action: ScoreGuardAction::Reject,
+        }];
+
+        let result = ScoreResult::default();
+        assert!(config.guard_of(&result).is_none());
+    }
+
+    #[test]
+    fn schedule_defaults_to_cpu_replica() {
+        let config: ScoreConfig = serde_json::from_value(serde_json::json!({
+            "categories": {},
+            "schedule": {}
+        }))
+        .unwrap();
+
+        let schedule = config.schedule.unwrap();
+        assert_eq!(schedule.interactive_device, loom_cortex::CortexDevice::Cpu);
+    }
+
+    #[test]
+    fn provenance_ignores_schedule() {
+        let stored = test_config().provenance();
+        let mut config = test_config();
+        config.schedule = Some(ScoreScheduleConfig::default());
+        assert!(!stored.is_outdated(&config.provenance()));
+
+        config.schedule = Some(ScoreScheduleConfig {
+            interactive_device: loom_cortex::CortexDevice::Cuda(0),
+        });
+        assert!(!stored.is_outdated(&config.provenance()));
+    }
 }
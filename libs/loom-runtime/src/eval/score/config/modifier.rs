@@ -3,6 +3,7 @@ use serde_valid::Validate;
 
 /// Dynamic threshold configuration based on text length
 #[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ScoreModifierConfig {
     /// Delta subtracted from baseline for short text
     #[serde(default = "ScoreModifierConfig::short_text_delta")]
@@ -0,0 +1,174 @@
+use serde::{Deserialize, Serialize};
+
+/// How emoji are treated before scoring
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum ScoreEmojiMode {
+    /// Leave emoji untouched
+    #[default]
+    Keep,
+    /// Remove emoji (and their modifiers) entirely
+    Strip,
+    /// Drop variation selectors, skin tones and joiners, and collapse repeats of the same emoji
+    Normalize,
+}
+
+/// Text transformations applied before inference.
+/// The same steps run for single and batch scoring, so both see identical model input.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ScorePreprocessConfig {
+    /// Lowercase text before scoring
+    #[serde(default)]
+    pub lowercase: bool,
+
+    /// How emoji are treated
+    #[serde(default)]
+    pub emoji: ScoreEmojiMode,
+
+    /// Replace URLs with this placeholder (e.g. `"[URL]"`); unset leaves URLs intact
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url_mask: Option<String>,
+}
+
+impl ScorePreprocessConfig {
+    /// Apply the configured steps to `text`: URL masking, then emoji handling, then lowercasing.
+    pub fn apply(&self, text: &str) -> String {
+        let mut text = match &self.url_mask {
+            Some(mask) => mask_urls(text, mask),
+            None => text.to_string(),
+        };
+
+        text = match self.emoji {
+            ScoreEmojiMode::Keep => text,
+            ScoreEmojiMode::Strip => text
+                .chars()
+                .filter(|c| !is_emoji(*c) && !is_emoji_modifier(*c))
+                .collect(),
+            ScoreEmojiMode::Normalize => normalize_emoji(&text),
+        };
+
+        if self.lowercase {
+            text = text.to_lowercase();
+        }
+
+        text
+    }
+}
+
+/// Replace whitespace-delimited URLs with `mask`, keeping trailing punctuation.
+fn mask_urls(text: &str, mask: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+
+    for piece in text.split_inclusive(char::is_whitespace) {
+        let word = piece.trim_end_matches(char::is_whitespace);
+        let space = &piece[word.len()..];
+        let url = word.trim_end_matches(['.', ',', ';', ':', '!', '?', ')', ']', '"', '\'']);
+        let lower = url.to_ascii_lowercase();
+
+        if lower.starts_with("http://")
+            || lower.starts_with("https://")
+            || lower.starts_with("www.")
+        {
+            out.push_str(mask);
+            out.push_str(&word[url.len()..]);
+        } else {
+            out.push_str(word);
+        }
+
+        out.push_str(space);
+    }
+
+    out
+}
+
+fn normalize_emoji(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut last_emoji = None;
+
+    for c in text.chars().filter(|c| !is_emoji_modifier(*c)) {
+        if is_emoji(c) {
+            if last_emoji == Some(c) {
+                continue;
+            }
+
+            last_emoji = Some(c);
+        } else {
+            last_emoji = None;
+        }
+
+        out.push(c);
+    }
+
+    out
+}
+
+fn is_emoji(c: char) -> bool {
+    matches!(
+        c as u32,
+        0x1F000..=0x1F2FF // mahjong, cards, enclosed alphanumerics, regional indicators
+            | 0x1F300..=0x1F5FF // symbols and pictographs
+            | 0x1F600..=0x1F64F // emoticons
+            | 0x1F680..=0x1F6FF // transport and map
+            | 0x1F900..=0x1FAFF // supplemental symbols and pictographs
+            | 0x2600..=0x27BF // misc symbols and dingbats
+            | 0x2B00..=0x2BFF // arrows and stars
+    ) && !is_emoji_modifier(c)
+}
+
+/// Characters that only modify a neighbouring emoji.
+fn is_emoji_modifier(c: char) -> bool {
+    matches!(
+        c as u32,
+        0xFE0E..=0xFE0F // variation selectors
+            | 0x200D // zero width joiner
+            | 0x1F3FB..=0x1F3FF // skin tones
+            | 0xE0020..=0xE007F // tags
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_leaves_text_unchanged() {
+        let text = "Check https://example.com 👍🏽";
+        assert_eq!(ScorePreprocessConfig::default().apply(text), text);
+    }
+
+    #[test]
+    fn masks_urls_keeping_punctuation_and_whitespace() {
+        let config = ScorePreprocessConfig {
+            url_mask: Some("[URL]".to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            config.apply("see https://example.com/a?b=1.\n\tor www.test.org, ok"),
+            "see [URL].\n\tor [URL], ok"
+        );
+    }
+
+    #[test]
+    fn strips_emoji_and_modifiers() {
+        let config = ScorePreprocessConfig {
+            emoji: ScoreEmojiMode::Strip,
+            ..Default::default()
+        };
+
+        assert_eq!(config.apply("great job 👍🏽❤️!"), "great job !");
+    }
+
+    #[test]
+    fn normalizes_emoji_repeats_and_modifiers() {
+        let config = ScorePreprocessConfig {
+            emoji: ScoreEmojiMode::Normalize,
+            lowercase: true,
+            ..Default::default()
+        };
+
+        assert_eq!(config.apply("LATE again 😡😡😡 👍🏽"), "late again 😡 👍");
+    }
+}
@@ -0,0 +1,35 @@
+use loom_cortex::CortexDevice;
+use serde::{Deserialize, Serialize};
+use serde_valid::Validate;
+
+/// Routing of scoring work between the model on its configured device and an
+/// interactive replica (CPU by default).
+///
+/// Single-text calls (`ScoreLayer::invoke`, e.g. an API request through
+/// `runtime.eval()`) run on the interactive replica, so they are not queued
+/// behind eval batches on the GPU; `score_batch` always runs on the configured
+/// device. The runtime keeps the replica behind its own lock.
+///
+/// Scheduling only moves work between devices, so it is not part of a score's
+/// provenance.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Validate)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ScoreScheduleConfig {
+    /// Device of the interactive replica
+    #[serde(default = "ScoreScheduleConfig::interactive_device")]
+    pub interactive_device: CortexDevice,
+}
+
+impl ScoreScheduleConfig {
+    fn interactive_device() -> CortexDevice {
+        CortexDevice::Cpu
+    }
+}
+
+impl Default for ScoreScheduleConfig {
+    fn default() -> Self {
+        Self {
+            interactive_device: Self::interactive_device(),
+        }
+    }
+}
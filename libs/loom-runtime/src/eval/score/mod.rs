@@ -1,27 +1,101 @@
 mod config;
+mod provenance;
 mod result;
+mod scorer;
 
 pub use config::*;
+pub use provenance::*;
 pub use result::*;
+pub use scorer::*;
 
-use std::collections::{BTreeMap, HashMap};
+use std::borrow::Cow;
+use std::collections::HashMap;
 
 use loom_cortex::CortexModel;
 use loom_cortex::bench::Decision;
 use loom_error::{Error, ErrorCode};
 use loom_pipe::Build;
+use loom_signal::{Emitter, SpanGuard};
 
 use crate::Context;
-use loom_pipe::LayerResult;
+use loom_pipe::{LayerMeta, LayerResult};
+
+/// Hook run on every result after scoring, with the original (unprocessed) text.
+pub type ScorePostprocessHook = Box<dyn Fn(&str, &mut ScoreResult) + Send + Sync>;
 
 pub struct ScoreLayer {
     model: CortexModel,
+    /// Layer on the schedule's interactive device that single-text calls
+    /// run on, if configured
+    interactive: Option<Box<ScoreLayer>>,
     config: ScoreConfig,
+    provenance: ScoreProvenance,
+    postprocess: Vec<ScorePostprocessHook>,
 }
 
 impl ScoreLayer {
     pub(crate) fn new(model: CortexModel, config: ScoreConfig) -> Self {
-        Self { model, config }
+        let provenance = config.provenance();
+        Self {
+            model,
+            interactive: None,
+            config,
+            provenance,
+            postprocess: Vec::new(),
+        }
+    }
+
+    pub(crate) fn with_interactive(mut self, interactive: Option<ScoreLayer>) -> Self {
+        self.interactive = interactive.map(Box::new);
+        self
+    }
+
+    pub(crate) fn with_provenance(mut self, provenance: ScoreProvenance) -> Self {
+        self.provenance = provenance;
+        self
+    }
+
+    /// Take the interactive replica, so single-text calls can be run on it
+    /// behind a lock of their own; `invoke` then uses the configured device.
+    pub fn take_interactive(&mut self) -> Option<ScoreLayer> {
+        self.interactive.take().map(|layer| *layer)
+    }
+
+    /// Register a hook that runs on every result after scoring, before guards.
+    /// Hooks run in registration order, for both single and batch scoring.
+    pub fn with_postprocess(
+        mut self,
+        hook: impl Fn(&str, &mut ScoreResult) + Send + Sync + 'static,
+    ) -> Self {
+        self.postprocess.push(Box::new(hook));
+        self
+    }
+
+    /// Text as given to the model, after the configured preprocessing.
+    pub fn preprocess<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        match &self.config.preprocess {
+            Some(preprocess) => Cow::Owned(preprocess.apply(text)),
+            None => Cow::Borrowed(text),
+        }
+    }
+
+    /// Build a result from raw label scores and run the postprocess hooks on it.
+    fn result_of(&self, text: &str, raw_scores: &HashMap<&str, f32>) -> ScoreResult {
+        let mut result = self
+            .config
+            .result_of(raw_scores)
+            .with_provenance(self.provenance.clone());
+
+        for hook in &self.postprocess {
+            hook(text, &mut result);
+        }
+
+        result
+    }
+
+    /// Number of tokens the model's tokenizer splits `text` into.
+    pub fn tokenize_len(&self, text: &str) -> Option<usize> {
+        self.model.tokenize_len(text)
     }
 
     /// Get the configuration for this layer
@@ -29,14 +103,62 @@ impl ScoreLayer {
         &self.config
     }
 
+    /// Get the provenance attached to every result produced by this layer
+    pub fn provenance(&self) -> &ScoreProvenance {
+        &self.provenance
+    }
+
     /// Invoke the score layer directly with a context reference.
     /// This is useful for benchmarking and other cases where you need to reuse the layer.
+    ///
+    /// The call is traced as a `score.invoke` span in the context's trace,
+    /// whose id is added to the result meta as `trace_id`.
+    ///
+    /// Runs on the schedule's interactive replica when there is one.
     pub fn invoke<Input>(
         &self,
         ctx: Context<Input>,
+    ) -> loom_error::Result<LayerResult<ScoreResult>> {
+        if let Some(interactive) = &self.interactive {
+            return interactive.invoke(ctx);
+        }
+
+        let mut span = ctx
+            .span("score.invoke")
+            .with_attr("step", ctx.step as i64)
+            .with_attr("text_len", ctx.text.len() as i64);
+
+        match self.score_in(&ctx, &span) {
+            Ok(mut result) => {
+                result.meta.set("trace_id", span.trace_id().into());
+                Ok(result)
+            }
+            Err(err) => {
+                span.fail(err.to_string());
+                Err(err)
+            }
+        }
+    }
+
+    /// Score `ctx` within the `score.invoke` span
+    fn score_in<Input>(
+        &self,
+        ctx: &Context<Input>,
+        span: &SpanGuard<'_, dyn Emitter + Send + Sync>,
     ) -> loom_error::Result<LayerResult<ScoreResult>> {
         let started_at = chrono::Utc::now();
 
+        // Don't start inference for a request whose budget is already spent
+        if ctx
+            .deadline
+            .is_some_and(|deadline| std::time::Instant::now() >= deadline)
+        {
+            return Err(Error::builder()
+                .code(ErrorCode::Timeout)
+                .message("ScoreLayer deadline exceeded before inference")
+                .build());
+        }
+
         // Extract the zero-shot model
         let zs_model = match &self.model {
             CortexModel::ZeroShotClassification { model, .. } => model,
@@ -76,13 +198,16 @@ impl ScoreLayer {
                 .unwrap_or_else(|| format!("This example is {}.", label))
         });
 
-        // Run zero-shot classification
-        let predictions = zs_model.predict_multilabel(
-            &[ctx.text.as_str()],
-            &label_names,
-            Some(hypothesis_fn),
-            128,
-        )?;
+        // Run zero-shot classification, with previous turns as premise context
+        let premise = ctx.premise(self.config.context_turns.unwrap_or(0));
+        let text = self.preprocess(&premise);
+        let mut predict = span
+            .child("score.predict")
+            .with_attr("labels", label_names.len() as i64);
+        let predictions = zs_model
+            .predict_multilabel(&[text.as_ref()], &label_names, Some(hypothesis_fn), 128)
+            .inspect_err(|err| predict.fail(err.to_string()))?;
+        predict.end();
 
         // Build a lookup map for predictions by label name
         let mut prediction_map: HashMap<&str, f32> = HashMap::new();
@@ -100,53 +225,24 @@ impl ScoreLayer {
             }
         }
 
-        // Build ScoreCategory for each category in config
-        let mut categories = BTreeMap::new();
-
-        for (cat_name, cat_config) in &self.config.categories {
-            let mut labels = BTreeMap::new();
-
-            for (label_name, label_config) in &cat_config.labels {
-                let raw_score = prediction_map
-                    .get(label_name.as_str())
-                    .copied()
-                    .unwrap_or(0.0);
-
-                let score_label = ScoreLabel::new(raw_score, 0, label_config);
-                labels.insert(label_name.clone(), score_label);
-            }
+        let mut result = LayerResult::new(self.result_of(&ctx.text, &prediction_map));
 
-            let top_k = cat_config.top_k;
-            categories.insert(cat_name.clone(), ScoreCategory::topk(labels, top_k));
-        }
-
-        let mut result = LayerResult::new(ScoreResult::new(categories));
-        let effective_threshold = self.config.threshold_of(ctx.text.len());
-        let phatic_score = result.output.label_score("phatic");
-        let phatic_threshold = self
-            .config
-            .label("phatic")
-            .map(|l| l.threshold)
-            .unwrap_or(0.80);
-
-        if result.output.score < effective_threshold || phatic_score >= phatic_threshold {
-            return Err(Error::builder()
-                .code(ErrorCode::Cancel)
-                .message(&format!(
-                    "score {} is less than minimum threshold {}",
-                    result.output.score, effective_threshold
-                ))
-                .build());
+        // Guard rules take precedence over the overall score threshold
+        if let Some(guard) = self.config.check(ctx.text.len(), &result.output)? {
+            result.meta.set("guard", guard.to_string().into());
         }
 
         // Add timing metadata
-        let elapsed_ms = (chrono::Utc::now() - started_at).num_milliseconds();
-        result.meta.set("elapsed_ms", elapsed_ms.into());
+        let elapsed = (chrono::Utc::now() - started_at)
+            .to_std()
+            .unwrap_or_default();
+        result.meta.set_elapsed(elapsed);
         result
             .meta
             .set("start_time", started_at.to_rfc3339().into());
-        result.meta.set("step", ctx.step.into());
+        result.meta.set_step(ctx.step);
         result.meta.set("text", ctx.text.clone().into());
+        result.meta.set_text_hash(&ctx.text);
         Ok(result)
     }
 }
@@ -173,33 +269,51 @@ impl loom_pipe::Layer for ScoreLayer {
 }
 
 /// Wrapper around ScoreResult for evaluation output.
-pub struct ScoreLayerOutput(ScoreResult);
+pub struct ScoreLayerOutput {
+    result: ScoreResult,
+    decision: Decision,
+}
 
 impl ScoreLayerOutput {
     pub fn new(result: ScoreResult) -> Self {
-        Self(result)
+        Self {
+            result,
+            decision: Decision::Accept,
+        }
+    }
+
+    /// Output for a batch `result` with the guard rules of `config` applied:
+    /// rejected when the first matching guard rejects, accepted otherwise.
+    /// The acceptance threshold isn't applied to batch outputs.
+    pub fn guarded(result: ScoreResult, config: &ScoreConfig) -> Self {
+        let decision = match config.guard_of(&result) {
+            Some(guard) if guard.action == ScoreGuardAction::Reject => Decision::Reject,
+            _ => Decision::Accept,
+        };
+
+        Self { result, decision }
     }
 
     /// Get the underlying ScoreResult.
     pub fn inner(&self) -> &ScoreResult {
-        &self.0
+        &self.result
     }
 
     /// The decision (Accept/Reject) for this scoring.
-    /// If we got a successful result, it's Accept.
-    /// (Reject happens when invoke returns an error)
+    /// Accept unless a guard rule rejected the result
+    /// (single-text rejections are returned as errors by invoke instead)
     pub fn decision(&self) -> Decision {
-        Decision::Accept
+        self.decision
     }
 
     /// The overall score value.
     pub fn score(&self) -> f32 {
-        self.0.score
+        self.result.score
     }
 
     /// Labels with their raw (uncalibrated) scores.
     pub fn labels(&self) -> Vec<(String, f32)> {
-        self.0.raw_scores()
+        self.result.raw_scores()
     }
 
     /// Labels that were detected (score > 0).
@@ -222,7 +336,31 @@ impl ScoreLayer {
     /// Score multiple texts in a single batch.
     /// This is more efficient than scoring texts one at a time.
     pub fn score_batch(&self, texts: &[&str]) -> loom_error::Result<Vec<ScoreLayerOutput>> {
-        if texts.is_empty() {
+        self.predict_batch(texts, texts)
+    }
+
+    /// Score multiple contexts in a single batch, each with up to
+    /// `context_turns` previous turns as premise like [`invoke`](Self::invoke).
+    pub fn score_contexts<Input>(
+        &self,
+        ctxs: &[Context<Input>],
+    ) -> loom_error::Result<Vec<ScoreLayerOutput>> {
+        let turns = self.config.context_turns.unwrap_or(0);
+        let premises: Vec<String> = ctxs.iter().map(|ctx| ctx.premise(turns)).collect();
+        let inputs: Vec<&str> = premises.iter().map(String::as_str).collect();
+        let texts: Vec<&str> = ctxs.iter().map(|ctx| ctx.text.as_str()).collect();
+
+        self.predict_batch(&inputs, &texts)
+    }
+
+    /// Run the model on `inputs` in one batch, scoring each result as the
+    /// matching entry of `texts` (the input without its premise turns)
+    fn predict_batch(
+        &self,
+        inputs: &[&str],
+        texts: &[&str],
+    ) -> loom_error::Result<Vec<ScoreLayerOutput>> {
+        if inputs.is_empty() {
             return Ok(vec![]);
         }
 
@@ -266,13 +404,15 @@ impl ScoreLayer {
         });
 
         // Run zero-shot classification on ALL texts at once (batch inference)
+        let processed: Vec<Cow<str>> = inputs.iter().map(|t| self.preprocess(t)).collect();
+        let inputs: Vec<&str> = processed.iter().map(|t| t.as_ref()).collect();
         let predictions =
-            zs_model.predict_multilabel(texts, &label_names, Some(hypothesis_fn), 128)?;
+            zs_model.predict_multilabel(&inputs, &label_names, Some(hypothesis_fn), 128)?;
 
         // Process predictions for each text
         let mut outputs = Vec::with_capacity(texts.len());
 
-        for sentence_predictions in &predictions {
+        for (text, sentence_predictions) in texts.iter().zip(&predictions) {
             // Build a lookup map for this text's predictions by label name
             let mut prediction_map: HashMap<&str, f32> = HashMap::new();
             for pred in sentence_predictions {
@@ -286,27 +426,11 @@ impl ScoreLayer {
                 );
             }
 
-            // Build ScoreCategory for each category in config
-            let mut categories = BTreeMap::new();
-
-            for (cat_name, cat_config) in &self.config.categories {
-                let mut labels = BTreeMap::new();
-
-                for (label_name, label_config) in &cat_config.labels {
-                    let raw_score = prediction_map
-                        .get(label_name.as_str())
-                        .copied()
-                        .unwrap_or(0.0);
-
-                    let score_label = ScoreLabel::new(raw_score, 0, label_config);
-                    labels.insert(label_name.clone(), score_label);
-                }
-
-                let top_k = cat_config.top_k;
-                categories.insert(cat_name.clone(), ScoreCategory::topk(labels, top_k));
-            }
-
-            outputs.push(ScoreLayerOutput::new(ScoreResult::new(categories)));
+            // Guard rules apply to every output, as in invoke
+            outputs.push(ScoreLayerOutput::guarded(
+                self.result_of(text, &prediction_map),
+                &self.config,
+            ));
         }
 
         Ok(outputs)
@@ -504,6 +628,15 @@ mod tests {
             top_k: 2,
             modifiers: ScoreModifierConfig::default(),
             categories,
+            guards: vec![ScoreGuardConfig {
+                label: "phatic".to_string(),
+                comparator: ScoreComparator::Gte,
+                threshold: 0.80,
+                action: ScoreGuardAction::Reject,
+            }],
+            schedule: None,
+            preprocess: None,
+            context_turns: None,
         }
     }
 
@@ -525,6 +658,18 @@ mod tests {
         Ok(())
     }
 
+    #[cfg(feature = "int")]
+    #[test]
+    fn batch_applies_guards() -> Result<()> {
+        let layer = int_test_config().build()?;
+        let outputs =
+            layer.score_batch(&["hi how are you?", "I need to file my taxes by friday"])?;
+
+        assert_eq!(outputs[0].decision(), Decision::Reject);
+        assert_eq!(outputs[1].decision(), Decision::Accept);
+        Ok(())
+    }
+
     #[cfg(feature = "int")]
     #[test]
     fn should_be_stressed() -> Result<()> {
@@ -0,0 +1,64 @@
+use serde::{Deserialize, Serialize};
+
+/// Identifies the configuration, model, and library version that produced a score.
+/// Stored alongside scores so re-scoring jobs can find results from outdated configs.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScoreProvenance {
+    /// blake3 hash (hex) of the serialized score configuration
+    pub config_hash: String,
+    /// Model identifier (e.g. `zero_shot_classification/bart`)
+    pub model: String,
+    /// Version of loom-runtime that produced the score
+    pub version: String,
+}
+
+impl ScoreProvenance {
+    pub fn new(config_hash: impl Into<String>, model: impl Into<String>) -> Self {
+        Self {
+            config_hash: config_hash.into(),
+            model: model.into(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+        }
+    }
+
+    /// Returns true if the score was produced by a different config or model than `current`.
+    /// Library version changes alone do not invalidate scores.
+    pub fn is_outdated(&self, current: &ScoreProvenance) -> bool {
+        self.config_hash != current.config_hash || self.model != current.model
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_sets_library_version() {
+        let provenance = ScoreProvenance::new("abc", "zero_shot_classification/bart");
+        assert_eq!(provenance.version, env!("CARGO_PKG_VERSION"));
+    }
+
+    #[test]
+    fn is_outdated_detects_config_change() {
+        let stored = ScoreProvenance::new("abc", "zero_shot_classification/bart");
+        let current = ScoreProvenance::new("def", "zero_shot_classification/bart");
+        assert!(stored.is_outdated(&current));
+    }
+
+    #[test]
+    fn is_outdated_detects_model_change() {
+        let stored = ScoreProvenance::new("abc", "zero_shot_classification/bart");
+        let current = ScoreProvenance::new("abc", "zero_shot_classification/roberta");
+        assert!(stored.is_outdated(&current));
+    }
+
+    #[test]
+    fn is_outdated_ignores_version_change() {
+        let stored = ScoreProvenance {
+            version: "0.0.0".to_string(),
+            ..ScoreProvenance::new("abc", "zero_shot_classification/bart")
+        };
+        let current = ScoreProvenance::new("abc", "zero_shot_classification/bart");
+        assert!(!stored.is_outdated(&current));
+    }
+}
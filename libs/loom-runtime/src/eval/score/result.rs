@@ -3,13 +3,14 @@ use std::collections::BTreeMap;
 use loom_core::value::Value;
 use serde::{Deserialize, Serialize};
 
-use super::ScoreLabelConfig;
+use super::{ScoreLabelConfig, ScoreProvenance};
+use crate::eval::schema;
 
 /// Apply Platt scaling to calibrate raw model scores.
 /// P(y|x) = 1 / (1 + exp(-Ax - B))
 /// With identity params (a=1.0, b=0.0), returns raw score unchanged.
 #[inline]
-fn calibrate(raw: f32, a: f32, b: f32) -> f32 {
+pub(crate) fn calibrate(raw: f32, a: f32, b: f32) -> f32 {
     // Identity: skip calibration
     if (a - 1.0).abs() < f32::EPSILON && b.abs() < f32::EPSILON {
         return raw;
@@ -17,18 +18,39 @@ fn calibrate(raw: f32, a: f32, b: f32) -> f32 {
     1.0 / (1.0 + (-a * raw - b).exp())
 }
 
-#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ScoreResult {
+    /// Schema version of the serialized result, see [`SCHEMA_VERSION`](crate::eval::SCHEMA_VERSION)
+    #[serde(default = "schema::current", deserialize_with = "schema::upgrade")]
+    pub schema_version: u32,
     /// Overall score (max of category scores)
     pub score: f32,
     /// Categories keyed by name (mirrors config structure)
     pub categories: BTreeMap<String, ScoreCategory>,
+    /// Config, model, and version that produced this result
+    #[serde(default)]
+    pub provenance: ScoreProvenance,
 }
 
 impl ScoreResult {
     pub fn new(categories: BTreeMap<String, ScoreCategory>) -> Self {
         let score = categories.values().map(|c| c.score).fold(0.0f32, f32::max);
-        Self { score, categories }
+        Self {
+            schema_version: schema::SCHEMA_VERSION,
+            score,
+            categories,
+            provenance: ScoreProvenance::default(),
+        }
+    }
+
+    pub fn with_provenance(mut self, provenance: ScoreProvenance) -> Self {
+        self.provenance = provenance;
+        self
+    }
+
+    /// Returns true if this result was produced by a different config or model than `current`
+    pub fn is_outdated(&self, current: &ScoreProvenance) -> bool {
+        self.provenance.is_outdated(current)
     }
 
     pub fn category(&self, name: &str) -> Option<&ScoreCategory> {
@@ -59,6 +81,12 @@ impl ScoreResult {
     }
 }
 
+impl Default for ScoreResult {
+    fn default() -> Self {
+        Self::new(BTreeMap::new())
+    }
+}
+
 #[cfg(feature = "json")]
 impl From<ScoreResult> for Value {
     fn from(result: ScoreResult) -> Self {
@@ -380,4 +408,16 @@ mod tests {
         assert_eq!(result.label_score("positive"), 0.8);
         assert_eq!(result.label_score("nonexistent"), 0.0);
     }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn score_result_roundtrips_with_schema_version() {
+        let json = serde_json::to_value(ScoreResult::default()).unwrap();
+        assert_eq!(json["schema_version"], crate::eval::SCHEMA_VERSION);
+
+        let legacy = r#"{"score": 0.5, "categories": {}}"#;
+        let result: ScoreResult = serde_json::from_str(legacy).unwrap();
+        assert_eq!(result.schema_version, crate::eval::SCHEMA_VERSION);
+        assert_eq!(result.score, 0.5);
+    }
 }
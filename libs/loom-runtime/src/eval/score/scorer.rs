@@ -0,0 +1,89 @@
+use loom_error::Result;
+use loom_pipe::LayerResult;
+
+use super::{ScoreConfig, ScoreLayer, ScoreLayerOutput, ScoreResult};
+use crate::Context;
+
+/// Scores a single text, applying the configured guards and threshold.
+///
+/// Implemented by [`ScoreLayer`]; alternative implementations (e.g. lookup-table
+/// mocks) can be registered with `Runtime::new().scorer(...)` to avoid loading a model.
+pub trait Scorer: Send {
+    /// The configuration this scorer was built from.
+    fn config(&self) -> &ScoreConfig;
+
+    /// Score a context. Returns a `Cancel` error when the text is rejected.
+    fn invoke(&self, ctx: Context<()>) -> Result<LayerResult<ScoreResult>>;
+
+    /// Number of model tokens in `text`, if the scorer has a tokenizer.
+    fn tokenize_len(&self, _text: &str) -> Option<usize> {
+        None
+    }
+
+    /// Take a replica for single-text calls, which the runtime then runs
+    /// behind its own lock instead of the shared scorer's (see
+    /// [`ScoreScheduleConfig`](super::ScoreScheduleConfig)).
+    fn take_interactive(&mut self) -> Option<Box<dyn Scorer>> {
+        None
+    }
+}
+
+/// Scores multiple texts in a single batch.
+/// Batch outputs are not filtered by the acceptance threshold, but guard rules
+/// decide them (see [`ScoreLayerOutput::guarded`]).
+pub trait BatchScorer: Scorer {
+    fn score_batch(&self, texts: &[&str]) -> Result<Vec<ScoreLayerOutput>>;
+
+    /// Score contexts in a single batch, with up to the config's
+    /// `context_turns` previous turns as premise, as [`Scorer::invoke`] does.
+    /// By default each premise is scored as a plain text.
+    fn score_contexts(&self, ctxs: &[Context<()>]) -> Result<Vec<ScoreLayerOutput>> {
+        let turns = self.config().context_turns.unwrap_or(0);
+        let premises: Vec<String> = ctxs.iter().map(|ctx| ctx.premise(turns)).collect();
+        let texts: Vec<&str> = premises.iter().map(String::as_str).collect();
+
+        self.score_batch(&texts)
+    }
+}
+
+/// Scores batches asynchronously, for scorers that wait on I/O (e.g. a remote
+/// inference service) rather than compute on the calling thread.
+///
+/// Registered with `Runtime::new().async_scorer(...)`, it is awaited directly by
+/// `eval_scoring()` instead of going through `spawn_blocking`.
+#[async_trait::async_trait]
+pub trait AsyncBatchScorer: Send + Sync {
+    /// The configuration this scorer was built from.
+    fn config(&self) -> &ScoreConfig;
+
+    /// Score a batch of texts, returning one output per text in order.
+    async fn score_batch(&self, texts: &[String]) -> Result<Vec<ScoreLayerOutput>>;
+}
+
+impl Scorer for ScoreLayer {
+    fn config(&self) -> &ScoreConfig {
+        ScoreLayer::config(self)
+    }
+
+    fn invoke(&self, ctx: Context<()>) -> Result<LayerResult<ScoreResult>> {
+        ScoreLayer::invoke(self, ctx)
+    }
+
+    fn tokenize_len(&self, text: &str) -> Option<usize> {
+        ScoreLayer::tokenize_len(self, text)
+    }
+
+    fn take_interactive(&mut self) -> Option<Box<dyn Scorer>> {
+        ScoreLayer::take_interactive(self).map(|layer| Box::new(layer) as Box<dyn Scorer>)
+    }
+}
+
+impl BatchScorer for ScoreLayer {
+    fn score_batch(&self, texts: &[&str]) -> Result<Vec<ScoreLayerOutput>> {
+        ScoreLayer::score_batch(self, texts)
+    }
+
+    fn score_contexts(&self, ctxs: &[Context<()>]) -> Result<Vec<ScoreLayerOutput>> {
+        ScoreLayer::score_contexts(self, ctxs)
+    }
+}
@@ -0,0 +1,195 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::score::ScoreConfig;
+use super::{EvalResult, Sample, SampleDataset};
+
+/// A sample picked for human labeling, with why it was picked.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SelectedSample {
+    pub sample: Sample,
+    /// Mean score across the runs that scored the sample.
+    pub score: f32,
+    /// Text-length adjusted acceptance threshold.
+    pub threshold: f32,
+    /// Distance between the mean score and the threshold.
+    pub margin: f32,
+    /// Standard deviation of the score across runs (0 for a single run).
+    pub disagreement: f32,
+    /// Ranking key: higher is more worth labeling.
+    pub uncertainty: f32,
+}
+
+impl SelectedSample {
+    /// The sample with its selection details recorded under `metadata.selection`,
+    /// so they travel with the sample through labeling.
+    pub fn into_sample(self) -> Sample {
+        let mut sample = self.sample;
+        let selection = serde_json::json!({
+            "score": self.score,
+            "threshold": self.threshold,
+            "margin": self.margin,
+            "disagreement": self.disagreement,
+            "uncertainty": self.uncertainty,
+        });
+
+        match sample.metadata.as_mut().and_then(|m| m.as_object_mut()) {
+            Some(metadata) => {
+                metadata.insert("selection".to_string(), selection);
+            }
+            None => sample.metadata = Some(serde_json::json!({ "selection": selection })),
+        }
+
+        sample
+    }
+}
+
+impl SampleDataset {
+    /// Rank samples for human labeling and return the `n` most uncertain.
+    ///
+    /// `runs` are eval results of this dataset, e.g. from several configs or
+    /// models acting as an ensemble. A sample is uncertain when its mean score
+    /// is close to the acceptance threshold (`1 - margin`) and when the runs
+    /// disagree on its score (standard deviation); uncertainty is their sum.
+    /// Unlabeled samples (no expected labels) rank ahead of labeled ones with
+    /// the same uncertainty. Samples no run scored are skipped.
+    pub fn select(
+        &self,
+        runs: &[EvalResult],
+        config: &ScoreConfig,
+        n: usize,
+    ) -> Vec<SelectedSample> {
+        let mut scores: HashMap<&str, Vec<f32>> = HashMap::new();
+
+        for run in runs {
+            for result in &run.sample_results {
+                scores
+                    .entry(result.id.as_str())
+                    .or_default()
+                    .push(result.score);
+            }
+        }
+
+        let mut selected: Vec<SelectedSample> = self
+            .samples
+            .iter()
+            .filter_map(|sample| {
+                let scores = scores.get(sample.id.as_str())?;
+                let count = scores.len() as f32;
+                let score = scores.iter().sum::<f32>() / count;
+                let variance = scores.iter().map(|s| (s - score).powi(2)).sum::<f32>() / count;
+                let threshold = config.threshold_of(sample.text.len());
+                let margin = (score - threshold).abs();
+                let disagreement = variance.sqrt();
+
+                Some(SelectedSample {
+                    sample: sample.clone(),
+                    score,
+                    threshold,
+                    margin,
+                    disagreement,
+                    uncertainty: (1.0 - margin).max(0.0) + disagreement,
+                })
+            })
+            .collect();
+
+        selected.sort_by(|a, b| {
+            b.uncertainty
+                .total_cmp(&a.uncertainty)
+                .then_with(|| {
+                    a.sample
+                        .expected_labels
+                        .is_empty()
+                        .cmp(&b.sample.expected_labels.is_empty())
+                        .reverse()
+                })
+                .then_with(|| a.sample.id.cmp(&b.sample.id))
+        });
+
+        selected.truncate(n);
+        selected
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::eval::{Decision, SampleResult, fixtures};
+
+    use super::*;
+
+    fn sample(id: &str, labels: &[&str]) -> Sample {
+        fixtures::sample(
+            id,
+            "some text that is neither short nor long, right in the middle of the range",
+            Decision::Accept,
+            "task",
+            labels,
+        )
+    }
+
+    fn run(scores: &[(&str, f32)]) -> EvalResult {
+        fixtures::result(scores.iter().map(|(id, score)| SampleResult {
+            score: *score,
+            ..fixtures::sample_result(id, true, &[])
+        }))
+    }
+
+    fn config() -> ScoreConfig {
+        ScoreConfig::default()
+    }
+
+    #[test]
+    fn select_ranks_scores_near_threshold_first() {
+        let mut dataset = SampleDataset::new();
+        dataset.samples.push(sample("far", &["task"]));
+        dataset.samples.push(sample("near", &["task"]));
+
+        let selected = dataset.select(&[run(&[("far", 0.05), ("near", 0.74)])], &config(), 10);
+        assert_eq!(selected[0].sample.id, "near");
+        assert!((selected[0].margin - 0.01).abs() < 0.001);
+    }
+
+    #[test]
+    fn select_ranks_ensemble_disagreement_higher() {
+        let mut dataset = SampleDataset::new();
+        dataset.samples.push(sample("agree", &["task"]));
+        dataset.samples.push(sample("disagree", &["task"]));
+
+        let runs = [
+            run(&[("agree", 0.5), ("disagree", 0.2)]),
+            run(&[("agree", 0.5), ("disagree", 0.8)]),
+        ];
+
+        let selected = dataset.select(&runs, &config(), 10);
+        assert_eq!(selected[0].sample.id, "disagree");
+        assert!((selected[0].disagreement - 0.3).abs() < 0.001);
+    }
+
+    #[test]
+    fn select_prefers_unlabeled_and_truncates() {
+        let mut dataset = SampleDataset::new();
+        dataset.samples.push(sample("labeled", &["task"]));
+        dataset.samples.push(sample("unlabeled", &[]));
+        dataset.samples.push(sample("unscored", &[]));
+
+        let selected = dataset.select(
+            &[run(&[("labeled", 0.5), ("unlabeled", 0.5)])],
+            &config(),
+            1,
+        );
+        assert_eq!(selected.len(), 1);
+        assert_eq!(selected[0].sample.id, "unlabeled");
+    }
+
+    #[test]
+    fn selected_sample_records_selection_metadata() {
+        let mut dataset = SampleDataset::new();
+        dataset.samples.push(sample("test-001", &["task"]));
+
+        let selected = dataset.select(&[run(&[("test-001", 0.7)])], &config(), 1);
+        let sample = selected.into_iter().next().unwrap().into_sample();
+        let selection = &sample.metadata.unwrap()["selection"];
+        assert!((selection["score"].as_f64().unwrap() - 0.7).abs() < 0.001);
+    }
+}
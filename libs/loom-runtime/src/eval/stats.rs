@@ -0,0 +1,337 @@
+use std::collections::{BTreeMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use super::score::ScoreConfig;
+use super::{Decision, Difficulty, SampleDataset};
+
+/// Upper bounds (exclusive, in characters) of the text length histogram buckets.
+/// 20 and 200 line up with the default short/long text threshold modifiers.
+const LENGTH_BUCKETS: &[usize] = &[20, 50, 100, 200, 500];
+
+/// Summary statistics of a dataset.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DatasetStats {
+    pub total: usize,
+    pub accept: usize,
+    pub reject: usize,
+    pub per_category: BTreeMap<String, usize>,
+    pub per_label: BTreeMap<String, usize>,
+    pub per_difficulty: DifficultyCounts,
+    pub text_length: LengthStats,
+    /// Text length in model tokens, if computed with [`SampleDataset::stats_with_tokens`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token_length: Option<LengthStats>,
+    /// Samples whose text (trimmed, case-insensitive) already appeared earlier.
+    pub duplicate_texts: usize,
+    /// Samples whose ID already appeared earlier.
+    pub duplicate_ids: usize,
+}
+
+/// Sample counts per difficulty.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DifficultyCounts {
+    pub easy: usize,
+    pub medium: usize,
+    pub hard: usize,
+}
+
+/// Text length distribution, in characters or tokens.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LengthStats {
+    pub min: usize,
+    pub max: usize,
+    pub mean: f32,
+    pub median: usize,
+    pub histogram: Vec<LengthBucket>,
+}
+
+/// Histogram bucket of text lengths in `min..max` (`max` is `None` for the last bucket).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LengthBucket {
+    pub min: usize,
+    pub max: Option<usize>,
+    pub count: usize,
+}
+
+impl DatasetStats {
+    /// Share of samples that duplicate an earlier sample's text.
+    pub fn duplicate_rate(&self) -> f32 {
+        ratio(self.duplicate_texts, self.total)
+    }
+
+    /// Add the config's categories and labels that no sample expects with a
+    /// count of 0, so the imbalance ratios account for them.
+    pub fn with_score_config(mut self, config: &ScoreConfig) -> Self {
+        for category in config.categories.keys() {
+            self.per_category.entry(category.clone()).or_insert(0);
+        }
+
+        for (label, _) in config.labels() {
+            self.per_label.entry(label).or_insert(0);
+        }
+
+        self
+    }
+
+    /// Ratio of the majority to the minority decision (1.0 is balanced).
+    pub fn decision_imbalance(&self) -> f32 {
+        imbalance([self.accept, self.reject].into_iter())
+    }
+
+    /// Ratio of the largest to the smallest category.
+    pub fn category_imbalance(&self) -> f32 {
+        imbalance(self.per_category.values().copied())
+    }
+
+    /// Ratio of the most to the least frequent expected label.
+    pub fn label_imbalance(&self) -> f32 {
+        imbalance(self.per_label.values().copied())
+    }
+}
+
+impl SampleDataset {
+    /// Compute summary statistics: counts per decision, category, label and
+    /// difficulty, text length distribution, and duplicates.
+    pub fn stats(&self) -> DatasetStats {
+        let mut stats = DatasetStats {
+            total: self.samples.len(),
+            ..Default::default()
+        };
+
+        let mut ids = HashSet::new();
+        let mut texts = HashSet::new();
+        let mut lengths = Vec::with_capacity(self.samples.len());
+
+        for sample in &self.samples {
+            match sample.expected_decision {
+                Decision::Accept => stats.accept += 1,
+                Decision::Reject => stats.reject += 1,
+            }
+
+            match sample.difficulty {
+                Difficulty::Easy => stats.per_difficulty.easy += 1,
+                Difficulty::Medium => stats.per_difficulty.medium += 1,
+                Difficulty::Hard => stats.per_difficulty.hard += 1,
+            }
+
+            *stats
+                .per_category
+                .entry(sample.primary_category.clone())
+                .or_default() += 1;
+
+            for label in &sample.expected_labels {
+                *stats.per_label.entry(label.clone()).or_default() += 1;
+            }
+
+            if !ids.insert(sample.id.as_str()) {
+                stats.duplicate_ids += 1;
+            }
+
+            if !texts.insert(sample.text.trim().to_lowercase()) {
+                stats.duplicate_texts += 1;
+            }
+
+            lengths.push(sample.text.chars().count());
+        }
+
+        stats.text_length = LengthStats::of(lengths);
+        stats
+    }
+
+    /// Compute [`stats`](Self::stats) plus the distribution of text lengths in
+    /// tokens, using `tokenize_len` (e.g. `Runtime::tokenize_len`). Token lengths
+    /// are left out if any text cannot be tokenized.
+    pub fn stats_with_tokens(&self, tokenize_len: impl Fn(&str) -> Option<usize>) -> DatasetStats {
+        let mut stats = self.stats();
+        stats.token_length = self
+            .samples
+            .iter()
+            .map(|s| tokenize_len(&s.text))
+            .collect::<Option<Vec<_>>>()
+            .map(LengthStats::of);
+        stats
+    }
+}
+
+impl LengthStats {
+    fn of(mut lengths: Vec<usize>) -> Self {
+        let mut histogram: Vec<LengthBucket> = std::iter::once(0)
+            .chain(LENGTH_BUCKETS.iter().copied())
+            .zip(LENGTH_BUCKETS.iter().copied().map(Some).chain([None]))
+            .map(|(min, max)| LengthBucket { min, max, count: 0 })
+            .collect();
+
+        if lengths.is_empty() {
+            return Self {
+                histogram,
+                ..Default::default()
+            };
+        }
+
+        lengths.sort_unstable();
+
+        for len in &lengths {
+            let bucket = LENGTH_BUCKETS
+                .iter()
+                .position(|max| len < max)
+                .unwrap_or(LENGTH_BUCKETS.len());
+            histogram[bucket].count += 1;
+        }
+
+        Self {
+            min: lengths[0],
+            max: lengths[lengths.len() - 1],
+            mean: lengths.iter().sum::<usize>() as f32 / lengths.len() as f32,
+            median: lengths[lengths.len() / 2],
+            histogram,
+        }
+    }
+}
+
+fn ratio(part: usize, total: usize) -> f32 {
+    if total > 0 {
+        part as f32 / total as f32
+    } else {
+        0.0
+    }
+}
+
+/// Largest over smallest count: infinite if a class has no samples, 0 if
+/// none has any.
+fn imbalance(counts: impl Iterator<Item = usize>) -> f32 {
+    let (min, max) = counts.fold((usize::MAX, 0), |(min, max), c| (min.min(c), max.max(c)));
+
+    if max == 0 {
+        0.0
+    } else if min == 0 {
+        f32::INFINITY
+    } else {
+        max as f32 / min as f32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::eval::fixtures::sample;
+
+    use super::*;
+
+    fn dataset() -> SampleDataset {
+        let mut dataset = SampleDataset::new();
+        dataset
+            .samples
+            .push(sample("1", "hi", Decision::Reject, "phatic", &["phatic"]));
+        dataset
+            .samples
+            .push(sample("2", "Hi ", Decision::Reject, "phatic", &["phatic"]));
+        dataset.samples.push(sample(
+            "3",
+            "hello there",
+            Decision::Reject,
+            "phatic",
+            &["phatic"],
+        ));
+        dataset.samples.push(sample(
+            "4",
+            "remind me to call the dentist tomorrow at noon please",
+            Decision::Accept,
+            "task",
+            &["task", "time"],
+        ));
+        dataset
+    }
+
+    #[test]
+    fn stats_counts_per_decision_category_and_label() {
+        let stats = dataset().stats();
+
+        assert_eq!(stats.total, 4);
+        assert_eq!(stats.accept, 1);
+        assert_eq!(stats.reject, 3);
+        assert_eq!(stats.per_category["phatic"], 3);
+        assert_eq!(stats.per_label["time"], 1);
+        assert_eq!(stats.per_difficulty.easy, 4);
+    }
+
+    #[test]
+    fn stats_detects_duplicate_texts() {
+        let stats = dataset().stats();
+
+        assert_eq!(stats.duplicate_texts, 1);
+        assert_eq!(stats.duplicate_ids, 0);
+        assert!((stats.duplicate_rate() - 0.25).abs() < 0.001);
+    }
+
+    #[test]
+    fn stats_computes_imbalance_ratios() {
+        let stats = dataset().stats();
+
+        assert!((stats.decision_imbalance() - 3.0).abs() < 0.001);
+        assert!((stats.category_imbalance() - 3.0).abs() < 0.001);
+        assert!((stats.label_imbalance() - 3.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn stats_imbalance_counts_empty_classes() {
+        let mut dataset = dataset();
+        dataset
+            .samples
+            .retain(|s| s.expected_decision == Decision::Reject);
+        let stats = dataset.stats();
+
+        assert_eq!(stats.decision_imbalance(), f32::INFINITY);
+        assert!((stats.category_imbalance() - 1.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn stats_with_score_config_adds_unused_classes() {
+        let config: ScoreConfig = serde_json::from_value(serde_json::json!({
+            "categories": {
+                "phatic": { "labels": { "phatic": { "hypothesis": "This is small talk." } } },
+                "task": { "labels": { "task": { "hypothesis": "This is a task." } } },
+                "emotion": { "labels": { "joy": { "hypothesis": "This is joyful." } } }
+            }
+        }))
+        .unwrap();
+        let stats = dataset().stats().with_score_config(&config);
+
+        assert_eq!(stats.per_category["emotion"], 0);
+        assert_eq!(stats.per_label["joy"], 0);
+        assert_eq!(stats.per_category["phatic"], 3);
+        assert_eq!(stats.category_imbalance(), f32::INFINITY);
+        assert_eq!(stats.label_imbalance(), f32::INFINITY);
+    }
+
+    #[test]
+    fn stats_buckets_text_lengths() {
+        let stats = dataset().stats();
+        let histogram = &stats.text_length.histogram;
+
+        assert_eq!(histogram.len(), LENGTH_BUCKETS.len() + 1);
+        assert_eq!(histogram[0].count, 3);
+        assert_eq!(histogram[2].count, 1);
+        assert_eq!(histogram[5].max, None);
+        assert_eq!(stats.text_length.min, 2);
+    }
+
+    #[test]
+    fn stats_with_tokens_computes_token_lengths() {
+        let stats = dataset().stats_with_tokens(|text| Some(text.split_whitespace().count()));
+        let tokens = stats.token_length.unwrap();
+
+        assert_eq!(tokens.min, 1);
+        assert_eq!(tokens.max, 10);
+        assert!(dataset().stats().token_length.is_none());
+        assert!(dataset().stats_with_tokens(|_| None).token_length.is_none());
+    }
+
+    #[test]
+    fn stats_of_empty_dataset() {
+        let stats = SampleDataset::new().stats();
+
+        assert_eq!(stats.total, 0);
+        assert_eq!(stats.duplicate_rate(), 0.0);
+        assert_eq!(stats.decision_imbalance(), 0.0);
+    }
+}
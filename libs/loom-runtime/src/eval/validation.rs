@@ -1,10 +1,92 @@
 use serde::{Deserialize, Serialize};
 
-/// Validation error for a benchmark sample.
+/// Severity of a validation finding.
+/// Errors make a dataset invalid, warnings point at samples worth reviewing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+impl std::fmt::Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Error => write!(f, "error"),
+            Self::Warning => write!(f, "warning"),
+        }
+    }
+}
+
+/// Kind of problem found on a sample.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ValidationKind {
+    /// Sample ID already used by an earlier sample
+    DuplicateId,
+    /// Text is empty or whitespace only
+    EmptyText,
+    /// No expected labels
+    MissingLabels,
+    /// Primary category is not defined in the config
+    InvalidCategory,
+    /// Label is not defined in the config
+    InvalidLabel,
+    /// Label is not defined in the config but is one edit away from a label that is
+    LabelTypo,
+    /// Expected labels contradict the expected decision or primary category
+    Inconsistent,
+}
+
+/// Validation finding for a benchmark sample.
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct ValidationError {
     pub sample_id: String,
+    pub kind: ValidationKind,
+    pub severity: Severity,
     pub message: String,
+
+    /// Configured name the offending value most likely meant (label typos only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub suggestion: Option<String>,
+}
+
+impl ValidationError {
+    /// Create an error level finding
+    pub fn error(
+        sample_id: impl Into<String>,
+        kind: ValidationKind,
+        message: impl Into<String>,
+    ) -> Self {
+        Self {
+            sample_id: sample_id.into(),
+            kind,
+            severity: Severity::Error,
+            message: message.into(),
+            suggestion: None,
+        }
+    }
+
+    /// Create a warning level finding
+    pub fn warning(
+        sample_id: impl Into<String>,
+        kind: ValidationKind,
+        message: impl Into<String>,
+    ) -> Self {
+        Self {
+            severity: Severity::Warning,
+            ..Self::error(sample_id, kind, message)
+        }
+    }
+
+    pub fn suggestion(mut self, suggestion: impl Into<String>) -> Self {
+        self.suggestion = Some(suggestion.into());
+        self
+    }
+
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
 }
 
 impl std::fmt::Display for ValidationError {
@@ -12,3 +94,67 @@ impl std::fmt::Display for ValidationError {
         write!(f, "[{}] {}", self.sample_id, self.message)
     }
 }
+
+/// Validation findings grouped by severity.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct ValidationReport {
+    pub samples: usize,
+    pub errors: Vec<ValidationError>,
+    pub warnings: Vec<ValidationError>,
+}
+
+impl ValidationReport {
+    /// Group findings for a dataset of `samples` samples by severity
+    pub fn new(samples: usize, findings: Vec<ValidationError>) -> Self {
+        let (errors, warnings) = findings.into_iter().partition(|f| f.is_error());
+
+        Self {
+            samples,
+            errors,
+            warnings,
+        }
+    }
+
+    /// A dataset is valid when it has no error level findings
+    pub fn is_valid(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.errors.len() + self.warnings.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Whether `a` and `b` are at most one insertion, deletion or substitution apart
+pub(crate) fn within_one_edit(a: &str, b: &str) -> bool {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let (short, long) = if a.len() <= b.len() {
+        (&a, &b)
+    } else {
+        (&b, &a)
+    };
+
+    if long.len() - short.len() > 1 {
+        return false;
+    }
+
+    let prefix = short
+        .iter()
+        .zip(long.iter())
+        .take_while(|(x, y)| x == y)
+        .count();
+
+    if short.len() == long.len() {
+        short[prefix..]
+            .iter()
+            .skip(1)
+            .eq(long[prefix..].iter().skip(1))
+    } else {
+        short[prefix..].iter().eq(long[prefix..].iter().skip(1))
+    }
+}
@@ -0,0 +1,106 @@
+use serde::{Deserialize, Serialize};
+use serde_valid::Validate;
+
+use super::FacetType;
+
+/// Configuration for the facet extraction layer
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct FacetConfig {
+    /// Score category whose detected labels become emotion facets
+    #[serde(default = "FacetConfig::emotion_category")]
+    pub emotion_category: String,
+
+    /// Score category whose detected labels become task facets
+    #[serde(default = "FacetConfig::task_category")]
+    pub task_category: String,
+
+    /// Facet type assigned to emotion facets
+    #[serde(default = "FacetConfig::emotion_type")]
+    pub emotion_type: FacetType,
+
+    /// Facet type assigned to task facets
+    #[serde(default = "FacetConfig::task_type")]
+    pub task_type: FacetType,
+
+    /// Facet type assigned to people mentioned in the text
+    #[serde(default = "FacetConfig::person_type")]
+    pub person_type: FacetType,
+
+    /// Facet type assigned to dates mentioned in the text
+    #[serde(default = "FacetConfig::date_type")]
+    pub date_type: FacetType,
+
+    /// Minimum entity score for people/date facets
+    #[serde(default = "FacetConfig::min_entity_score")]
+    #[validate(minimum = 0.0)]
+    #[validate(maximum = 1.0)]
+    pub min_entity_score: f64,
+}
+
+impl FacetConfig {
+    fn emotion_category() -> String {
+        "emotion".to_string()
+    }
+
+    fn task_category() -> String {
+        "task".to_string()
+    }
+
+    fn emotion_type() -> FacetType {
+        FacetType::Status
+    }
+
+    fn task_type() -> FacetType {
+        FacetType::Plan
+    }
+
+    fn person_type() -> FacetType {
+        FacetType::Relationship
+    }
+
+    fn date_type() -> FacetType {
+        FacetType::Episode
+    }
+
+    fn min_entity_score() -> f64 {
+        0.5
+    }
+}
+
+impl Default for FacetConfig {
+    fn default() -> Self {
+        Self {
+            emotion_category: Self::emotion_category(),
+            task_category: Self::task_category(),
+            emotion_type: Self::emotion_type(),
+            task_type: Self::task_type(),
+            person_type: Self::person_type(),
+            date_type: Self::date_type(),
+            min_entity_score: Self::min_entity_score(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_config_uses_defaults() {
+        let config: FacetConfig = serde_json::from_str("{}").unwrap();
+
+        assert_eq!(config.emotion_category, "emotion");
+        assert_eq!(config.task_type, FacetType::Plan);
+        assert_eq!(config.min_entity_score, 0.5);
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn facet_types_are_configurable() {
+        let json = r#"{"emotion_type": "feedback", "min_entity_score": 0.9}"#;
+        let config: FacetConfig = serde_json::from_str(json).unwrap();
+
+        assert_eq!(config.emotion_type, FacetType::Feedback);
+        assert_eq!(config.min_entity_score, 0.9);
+    }
+}
@@ -0,0 +1,136 @@
+use serde::{Deserialize, Serialize};
+
+use crate::eval::score::ScoreProvenance;
+
+/// Kind of memory attribute a facet describes.
+/// Variants and their snake_case names mirror merc-storage's `FacetType`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FacetType {
+    Preference,
+    Profile,
+    Relationship,
+    Fact,
+    Decision,
+    Policy,
+    Plan,
+    Episode,
+    ProjectContext,
+    Insight,
+    Status,
+    Feedback,
+}
+
+impl FacetType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Preference => "preference",
+            Self::Profile => "profile",
+            Self::Relationship => "relationship",
+            Self::Fact => "fact",
+            Self::Decision => "decision",
+            Self::Policy => "policy",
+            Self::Plan => "plan",
+            Self::Episode => "episode",
+            Self::ProjectContext => "project_context",
+            Self::Insight => "insight",
+            Self::Status => "status",
+            Self::Feedback => "feedback",
+        }
+    }
+}
+
+impl std::fmt::Display for FacetType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Typed payload of a facet, stored as the facet's `data` column
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum FacetData {
+    /// Emotion label detected by the score layer (e.g. `joy`, `stress`)
+    Emotion { label: String },
+    /// Task label detected by the score layer
+    Task { label: String },
+    /// Person mentioned in the text
+    Person { name: String },
+    /// Date or time expression mentioned in the text
+    Date { text: String },
+}
+
+/// Structured memory attribute produced by the `FacetLayer`.
+/// Maps one-to-one onto a merc-storage facet row.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Facet {
+    #[serde(rename = "type")]
+    pub ty: FacetType,
+    /// Confidence in `[0, 1]`
+    pub confidence: f32,
+    pub data: FacetData,
+    /// Provenance of the score result the facet was derived from
+    #[serde(default)]
+    pub provenance: ScoreProvenance,
+}
+
+impl Facet {
+    pub fn new(ty: FacetType, confidence: f32, data: FacetData) -> Self {
+        Self {
+            ty,
+            confidence: confidence.clamp(0.0, 1.0),
+            data,
+            provenance: ScoreProvenance::default(),
+        }
+    }
+
+    pub fn with_provenance(mut self, provenance: ScoreProvenance) -> Self {
+        self.provenance = provenance;
+        self
+    }
+
+    /// Encode the payload as JSON bytes for the facet's `data` column
+    pub fn encode_data(&self) -> Vec<u8> {
+        serde_json::to_vec(&self.data).expect("FacetData is serializable")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn facet_type_names_are_snake_case() {
+        let json = serde_json::to_string(&FacetType::ProjectContext).unwrap();
+        assert_eq!(json, "\"project_context\"");
+        assert_eq!(FacetType::ProjectContext.as_str(), "project_context");
+    }
+
+    #[test]
+    fn encode_data_is_tagged() {
+        let facet = Facet::new(
+            FacetType::Relationship,
+            0.9,
+            FacetData::Person {
+                name: "Alice".to_string(),
+            },
+        );
+
+        let data: serde_json::Value = serde_json::from_slice(&facet.encode_data()).unwrap();
+        assert_eq!(data["kind"], "person");
+        assert_eq!(data["name"], "Alice");
+    }
+
+    #[test]
+    fn confidence_is_clamped() {
+        let facet = Facet::new(
+            FacetType::Status,
+            1.5,
+            FacetData::Emotion {
+                label: "joy".to_string(),
+            },
+        );
+
+        assert_eq!(facet.confidence, 1.0);
+    }
+}
@@ -0,0 +1,57 @@
+use serde::{Deserialize, Serialize};
+
+/// Named entity recognized in a text (e.g. NER model output)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NamedEntity {
+    /// Entity text as it appears in the input
+    pub word: String,
+    /// Entity label, with or without an IOB prefix (e.g. `I-PER`, `DATE`)
+    pub label: String,
+    /// Model confidence
+    pub score: f64,
+}
+
+impl NamedEntity {
+    pub fn new(word: impl Into<String>, label: impl Into<String>, score: f64) -> Self {
+        Self {
+            word: word.into(),
+            label: label.into(),
+            score,
+        }
+    }
+
+    /// The label without its IOB prefix (`B-PER` -> `PER`)
+    pub fn kind(&self) -> &str {
+        self.label
+            .strip_prefix("B-")
+            .or_else(|| self.label.strip_prefix("I-"))
+            .unwrap_or(&self.label)
+    }
+
+    pub fn is_person(&self) -> bool {
+        matches!(self.kind(), "PER" | "PERSON")
+    }
+
+    pub fn is_date(&self) -> bool {
+        matches!(self.kind(), "DATE" | "TIME")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn kind_strips_iob_prefix() {
+        assert_eq!(NamedEntity::new("Alice", "I-PER", 0.9).kind(), "PER");
+        assert_eq!(NamedEntity::new("Paris", "B-LOC", 0.9).kind(), "LOC");
+        assert_eq!(NamedEntity::new("Friday", "DATE", 0.9).kind(), "DATE");
+    }
+
+    #[test]
+    fn classifies_people_and_dates() {
+        assert!(NamedEntity::new("Alice", "I-PER", 0.9).is_person());
+        assert!(NamedEntity::new("tomorrow", "DATE", 0.9).is_date());
+        assert!(!NamedEntity::new("Paris", "I-LOC", 0.9).is_person());
+    }
+}
@@ -0,0 +1,284 @@
+//! Facet extraction: turns score results and named entities into
+//! structured memory attributes (emotion, task, people, dates).
+
+mod config;
+mod data;
+mod entity;
+
+pub use config::*;
+pub use data::*;
+pub use entity::*;
+
+use std::collections::BTreeMap;
+
+use loom_error::Result;
+use loom_pipe::{LayerMeta, LayerResult};
+
+use crate::Context;
+use crate::eval::score::ScoreResult;
+
+/// Input to the facet layer: a score result and the entities found in the same text
+#[derive(Debug, Clone, Default)]
+pub struct FacetInput {
+    pub score: ScoreResult,
+    pub entities: Vec<NamedEntity>,
+}
+
+impl FacetInput {
+    pub fn new(score: ScoreResult, entities: Vec<NamedEntity>) -> Self {
+        Self { score, entities }
+    }
+}
+
+/// Converts a ScoreResult plus NER output into typed facets.
+///
+/// - detected labels (score > 0) in the emotion/task categories become one facet each
+/// - person and date entities above `min_entity_score` become one facet per distinct
+///   (case-insensitive) mention, keeping the highest score
+///
+/// Every facet carries the score result's provenance.
+pub struct FacetLayer {
+    config: FacetConfig,
+}
+
+impl FacetLayer {
+    pub fn new(config: FacetConfig) -> Self {
+        Self { config }
+    }
+
+    /// Get the configuration for this layer
+    pub fn config(&self) -> &FacetConfig {
+        &self.config
+    }
+
+    /// Extract facets without a context
+    pub fn extract(&self, input: &FacetInput) -> Vec<Facet> {
+        let mut facets = Vec::new();
+
+        if let Some(category) = input.score.category(&self.config.emotion_category) {
+            for (label, score) in &category.labels {
+                if score.score > 0.0 {
+                    facets.push(Facet::new(
+                        self.config.emotion_type,
+                        score.score,
+                        FacetData::Emotion {
+                            label: label.clone(),
+                        },
+                    ));
+                }
+            }
+        }
+
+        if let Some(category) = input.score.category(&self.config.task_category) {
+            for (label, score) in &category.labels {
+                if score.score > 0.0 {
+                    facets.push(Facet::new(
+                        self.config.task_type,
+                        score.score,
+                        FacetData::Task {
+                            label: label.clone(),
+                        },
+                    ));
+                }
+            }
+        }
+
+        let mut people: BTreeMap<String, &NamedEntity> = BTreeMap::new();
+        let mut dates: BTreeMap<String, &NamedEntity> = BTreeMap::new();
+
+        for entity in &input.entities {
+            if entity.score < self.config.min_entity_score {
+                continue;
+            }
+
+            let mentions = match entity {
+                e if e.is_person() => &mut people,
+                e if e.is_date() => &mut dates,
+                _ => continue,
+            };
+
+            mentions
+                .entry(entity.word.trim().to_lowercase())
+                .and_modify(|e| {
+                    if entity.score > e.score {
+                        *e = entity;
+                    }
+                })
+                .or_insert(entity);
+        }
+
+        for entity in people.values() {
+            facets.push(Facet::new(
+                self.config.person_type,
+                entity.score as f32,
+                FacetData::Person {
+                    name: entity.word.trim().to_string(),
+                },
+            ));
+        }
+
+        for entity in dates.values() {
+            facets.push(Facet::new(
+                self.config.date_type,
+                entity.score as f32,
+                FacetData::Date {
+                    text: entity.word.trim().to_string(),
+                },
+            ));
+        }
+
+        facets
+            .into_iter()
+            .map(|f| f.with_provenance(input.score.provenance.clone()))
+            .collect()
+    }
+
+    /// Invoke the facet layer directly with a context
+    pub fn invoke(&self, ctx: Context<FacetInput>) -> Result<LayerResult<Vec<Facet>>> {
+        let facets = self.extract(&ctx.input);
+        let mut result = LayerResult::new(facets);
+
+        result.meta.set_step(ctx.step);
+        result.meta.set("text", ctx.text.clone().into());
+        Ok(result)
+    }
+}
+
+impl Default for FacetLayer {
+    fn default() -> Self {
+        Self::new(FacetConfig::default())
+    }
+}
+
+impl loom_pipe::Layer for FacetLayer {
+    type Input = Context<FacetInput>;
+    type Output = Vec<Facet>;
+
+    fn process(&self, input: Self::Input) -> Result<LayerResult<Self::Output>> {
+        self.invoke(input)
+    }
+
+    fn name(&self) -> &'static str {
+        "facet"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eval::score::{ScoreCategory, ScoreLabel, ScoreProvenance};
+
+    fn score() -> ScoreResult {
+        let label = |score: f32| ScoreLabel {
+            score,
+            raw_score: score,
+            sentence: 0,
+        };
+
+        let mut emotion = BTreeMap::new();
+        emotion.insert("joy".to_string(), label(0.8));
+        emotion.insert("fear".to_string(), label(0.0));
+
+        let mut task = BTreeMap::new();
+        task.insert("task".to_string(), label(0.9));
+
+        let mut categories = BTreeMap::new();
+        categories.insert("emotion".to_string(), ScoreCategory::new(emotion));
+        categories.insert("task".to_string(), ScoreCategory::new(task));
+
+        ScoreResult::new(categories).with_provenance(ScoreProvenance::new("abc", "test"))
+    }
+
+    fn input() -> FacetInput {
+        FacetInput::new(
+            score(),
+            vec![
+                NamedEntity::new("Alice", "I-PER", 0.7),
+                NamedEntity::new("alice", "B-PER", 0.95),
+                NamedEntity::new("Bob", "I-PER", 0.2),
+                NamedEntity::new("Paris", "I-LOC", 0.99),
+                NamedEntity::new("Friday", "DATE", 0.8),
+            ],
+        )
+    }
+
+    fn data(facets: &[Facet], ty: FacetType) -> Vec<(FacetData, f32)> {
+        facets
+            .iter()
+            .filter(|f| f.ty == ty)
+            .map(|f| (f.data.clone(), f.confidence))
+            .collect()
+    }
+
+    #[test]
+    fn detected_labels_become_facets() {
+        let facets = FacetLayer::default().extract(&input());
+
+        assert_eq!(
+            data(&facets, FacetType::Status),
+            vec![(
+                FacetData::Emotion {
+                    label: "joy".to_string()
+                },
+                0.8
+            )]
+        );
+        assert_eq!(
+            data(&facets, FacetType::Plan),
+            vec![(
+                FacetData::Task {
+                    label: "task".to_string()
+                },
+                0.9
+            )]
+        );
+    }
+
+    #[test]
+    fn people_are_deduplicated_and_filtered() {
+        let facets = FacetLayer::default().extract(&input());
+
+        assert_eq!(
+            data(&facets, FacetType::Relationship),
+            vec![(
+                FacetData::Person {
+                    name: "alice".to_string()
+                },
+                0.95
+            )]
+        );
+    }
+
+    #[test]
+    fn dates_become_facets() {
+        let facets = FacetLayer::default().extract(&input());
+
+        assert_eq!(
+            data(&facets, FacetType::Episode),
+            vec![(
+                FacetData::Date {
+                    text: "Friday".to_string()
+                },
+                0.8
+            )]
+        );
+    }
+
+    #[test]
+    fn facets_carry_score_provenance() {
+        let facets = FacetLayer::default().extract(&input());
+
+        assert!(!facets.is_empty());
+        assert!(facets.iter().all(|f| f.provenance.config_hash == "abc"));
+    }
+
+    #[test]
+    fn invoke_sets_metadata() {
+        let result = FacetLayer::default()
+            .invoke(Context::new("Alice joined on Friday", input()))
+            .unwrap();
+
+        assert_eq!(result.output.len(), 4);
+        assert!(result.meta.exists("text"));
+    }
+}
@@ -0,0 +1,229 @@
+use std::collections::HashMap;
+
+use loom_config::ConfigSection;
+use loom_error::{Error, ErrorCode, Result};
+use loom_pipe::Layer;
+use serde::de::DeserializeOwned;
+
+use super::LayerRegistry;
+use crate::consolidate::ConsolidateConfig;
+use crate::dedup::DedupConfig;
+use crate::facet::{FacetConfig, FacetLayer};
+use crate::ner::NerConfig;
+use crate::prefilter::PrefilterConfig;
+use crate::retrieve::RetrieveConfig;
+
+type BuildFn = Box<dyn Fn(&str, &ConfigSection, &mut LayerRegistry) -> Result<()> + Send + Sync>;
+
+/// Builds layers from `layers.<name>` config sections, keyed by type tag.
+///
+/// A section's `type` key picks the factory and defaults to the section's
+/// name, so `layers.prefilter` needs no tag while `layers.strict` with
+/// `type: prefilter` declares a second pre-filter. The section is bound to
+/// the factory's config type and the layer registered under the section's
+/// name.
+///
+/// The default factory knows every built-in layer except `score`, which the
+/// runtime builds as its scorer.
+pub struct LayerFactory {
+    factories: HashMap<String, BuildFn>,
+}
+
+impl LayerFactory {
+    /// A factory with no layer types
+    pub fn new() -> Self {
+        Self {
+            factories: HashMap::new(),
+        }
+    }
+
+    /// Build layers tagged `tag` by binding their section to `C` and calling
+    /// `build`. An empty section builds from `C::default()`.
+    pub fn register<C, L, F>(&mut self, tag: impl Into<String>, build: F)
+    where
+        C: DeserializeOwned + Default + 'static,
+        L: Layer + Sync + 'static,
+        L::Input: 'static,
+        L::Output: 'static,
+        F: Fn(C) -> Result<L> + Send + Sync + 'static,
+    {
+        let factory = move |name: &str, section: &ConfigSection, layers: &mut LayerRegistry| {
+            let config = if section.exists() {
+                section.bind::<C>().map_err(|e| {
+                    Error::builder()
+                        .code(ErrorCode::BadArguments)
+                        .message(format!("Layer '{}' has invalid config: {}", name, e))
+                        .build()
+                })?
+            } else {
+                C::default()
+            };
+
+            layers.register_as(name, build(config)?);
+            Ok(())
+        };
+
+        self.factories.insert(tag.into(), Box::new(factory));
+    }
+
+    /// Check if layers tagged `tag` can be built.
+    pub fn contains(&self, tag: &str) -> bool {
+        self.factories.contains_key(tag)
+    }
+
+    /// Build the layer declared by `section` and register it as `name`.
+    ///
+    /// Returns `false` without building anything when the section has no
+    /// `type` and its name is not a known tag, since such sections may
+    /// configure something other than a layer. An explicit `type` without a
+    /// factory is a `NotFound` error.
+    pub fn build(
+        &self,
+        name: &str,
+        section: &ConfigSection,
+        layers: &mut LayerRegistry,
+    ) -> Result<bool> {
+        let tagged = section.get_section("type");
+
+        let tag = match tagged.value().as_str() {
+            Some(tag) => tag,
+            None if tagged.exists() => {
+                return Err(Error::builder()
+                    .code(ErrorCode::BadArguments)
+                    .message(format!("Layer '{}' type must be a string", name))
+                    .build());
+            }
+            None => name,
+        };
+
+        let Some(factory) = self.factories.get(tag) else {
+            if !tagged.exists() {
+                return Ok(false);
+            }
+
+            return Err(Error::builder()
+                .code(ErrorCode::NotFound)
+                .message(format!("Layer '{}' has unknown type '{}'", name, tag))
+                .build());
+        };
+
+        factory(name, section, layers)?;
+        Ok(true)
+    }
+}
+
+impl Default for LayerFactory {
+    fn default() -> Self {
+        let mut factory = Self::new();
+        factory.register("prefilter", PrefilterConfig::build);
+        factory.register("dedup", DedupConfig::build);
+        factory.register("ner", NerConfig::build);
+        factory.register("retrieve", RetrieveConfig::build);
+        factory.register("consolidate", ConsolidateConfig::build);
+        factory.register("facet", |config: FacetConfig| Ok(FacetLayer::new(config)));
+        factory
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Context;
+    use loom_config::{Config, MemoryProvider};
+    use loom_core::{ident_path, value::Value};
+
+    fn layers_section(pairs: &[(&str, Value)]) -> ConfigSection {
+        let config = Config::new()
+            .with_provider(MemoryProvider::from_pairs(pairs.iter().cloned()))
+            .build()
+            .unwrap();
+
+        config.get_section(&ident_path!("layers"))
+    }
+
+    fn build_all(factory: &LayerFactory, section: &ConfigSection) -> Result<LayerRegistry> {
+        let mut layers = LayerRegistry::new();
+
+        for name in section.keys().into_iter().flatten() {
+            factory.build(name, &section.get_section(name), &mut layers)?;
+        }
+
+        Ok(layers)
+    }
+
+    #[test]
+    fn builds_sections_by_name_and_type() {
+        let section = layers_section(&[
+            ("layers.prefilter.min_chars", Value::from(3)),
+            ("layers.strict.type", Value::from("prefilter")),
+            ("layers.strict.min_chars", Value::from(20)),
+            ("layers.facet.min_entity_score", Value::from(0.8)),
+        ]);
+
+        let layers = build_all(&LayerFactory::default(), &section).unwrap();
+
+        assert!(layers.contains("prefilter"));
+        assert!(layers.contains("strict"));
+        assert!(layers.contains("facet"));
+        assert_eq!(layers.get("strict").unwrap().name(), "prefilter");
+
+        let text = || Box::new(Context::new("short text", ()));
+
+        assert!(layers.get("strict").unwrap().process_any(text()).is_err());
+        assert!(layers.get("prefilter").unwrap().process_any(text()).is_ok());
+    }
+
+    #[test]
+    fn skips_untagged_unknown_sections() {
+        let section = layers_section(&[("layers.score.threshold", Value::from(0.75))]);
+
+        let mut layers = LayerRegistry::new();
+        let built = LayerFactory::default()
+            .build("score", &section.get_section("score"), &mut layers)
+            .unwrap();
+
+        assert!(!built);
+        assert!(!layers.contains("score"));
+    }
+
+    #[test]
+    fn rejects_unknown_types() {
+        let section = layers_section(&[("layers.custom.type", Value::from("missing"))]);
+        let err = build_all(&LayerFactory::default(), &section).unwrap_err();
+
+        assert_eq!(*err.code(), ErrorCode::NotFound);
+    }
+
+    #[test]
+    fn rejects_invalid_config() {
+        let section = layers_section(&[("layers.prefilter.max_chars", Value::from(0))]);
+
+        assert!(build_all(&LayerFactory::default(), &section).is_err());
+
+        let section = layers_section(&[("layers.prefilter.min_chars", Value::from("many"))]);
+        let err = build_all(&LayerFactory::default(), &section).unwrap_err();
+
+        assert_eq!(*err.code(), ErrorCode::BadArguments);
+    }
+
+    #[test]
+    fn registers_custom_types() {
+        let mut factory = LayerFactory::new();
+        factory.register("short_text", |mut config: PrefilterConfig| {
+            config.max_chars = Some(5);
+            config.build()
+        });
+
+        let section = layers_section(&[("layers.tiny.type", Value::from("short_text"))]);
+        let layers = build_all(&factory, &section).unwrap();
+        let layer = layers.get("tiny").unwrap();
+
+        assert!(factory.contains("short_text"));
+        assert!(!factory.contains("prefilter"));
+        assert!(
+            layer
+                .process_any(Box::new(Context::new("too long", ())))
+                .is_err()
+        );
+    }
+}
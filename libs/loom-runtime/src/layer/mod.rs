@@ -1,3 +1,5 @@
+mod factory;
 mod registry;
 
+pub use factory::*;
 pub use registry::*;
@@ -23,8 +23,20 @@ impl LayerRegistry {
         L::Input: 'static,
         L::Output: 'static,
     {
-        let name = layer.name().to_string();
-        self.layers.insert(name, Box::new(LayerNode::new(layer)));
+        let name = layer.name();
+        self.register_as(name, layer);
+    }
+
+    /// Register a layer under `name` instead of its name(), so one layer
+    /// type can be registered several times with different configs.
+    pub fn register_as<L>(&mut self, name: impl Into<String>, layer: L)
+    where
+        L: Layer + Sync + 'static,
+        L::Input: 'static,
+        L::Output: 'static,
+    {
+        self.layers
+            .insert(name.into(), Box::new(LayerNode::new(layer)));
     }
 
     /// Get a layer by name.
@@ -32,6 +44,41 @@ impl LayerRegistry {
         self.layers.get(name).map(|l| l.as_ref())
     }
 
+    /// Check if a layer is registered under `name`.
+    pub fn contains(&self, name: &str) -> bool {
+        self.layers.contains_key(name)
+    }
+
+    /// Version of the layer registered under `name`.
+    pub fn version(&self, name: &str) -> Option<&'static str> {
+        self.get(name).map(|l| l.version())
+    }
+
+    /// Check that the layer registered under `name` satisfies a pinned version.
+    ///
+    /// A pin matches the layer's version exactly or as a dotted prefix, so
+    /// `"1"` accepts `1.4.0` and `"1.4"` accepts `1.4.2` but not `1.40.0`.
+    pub fn check_version(&self, name: &str, pinned: &str) -> Result<()> {
+        let version = self.version(name).ok_or_else(|| {
+            Error::builder()
+                .code(ErrorCode::NotFound)
+                .message(format!("Layer '{}' not found", name))
+                .build()
+        })?;
+
+        if !version_matches(version, pinned) {
+            return Err(Error::builder()
+                .code(ErrorCode::BadArguments)
+                .message(format!(
+                    "Layer '{}' is version {} but version {} is pinned",
+                    name, version, pinned
+                ))
+                .build());
+        }
+
+        Ok(())
+    }
+
     /// Get a layer by name with type checking.
     pub fn get_checked(
         &self,
@@ -69,3 +116,42 @@ impl Default for LayerRegistry {
         Self::new()
     }
 }
+
+fn version_matches(version: &str, pinned: &str) -> bool {
+    match version.strip_prefix(pinned) {
+        Some(rest) => rest.is_empty() || rest.starts_with('.'),
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prefilter::PrefilterLayer;
+
+    #[test]
+    fn version_pins_match_dotted_prefixes() {
+        assert!(version_matches("1.4.2", "1"));
+        assert!(version_matches("1.4.2", "1.4"));
+        assert!(version_matches("1.4.2", "1.4.2"));
+        assert!(!version_matches("1.40.0", "1.4"));
+        assert!(!version_matches("2.0.0", "1"));
+    }
+
+    #[test]
+    fn check_version_reports_mismatch() {
+        let mut layers = LayerRegistry::new();
+        layers.register(PrefilterLayer::default());
+
+        assert_eq!(layers.version("prefilter"), Some("1.0.0"));
+        assert!(layers.check_version("prefilter", "1").is_ok());
+        assert_eq!(
+            *layers.check_version("prefilter", "2").unwrap_err().code(),
+            ErrorCode::BadArguments
+        );
+        assert_eq!(
+            *layers.check_version("missing", "1").unwrap_err().code(),
+            ErrorCode::NotFound
+        );
+    }
+}
@@ -1,57 +1,118 @@
 mod config;
+pub mod consolidate;
 mod context;
+pub mod dedup;
+mod embed;
 pub mod eval;
+pub mod facet;
 mod layer;
+pub mod ner;
+pub mod pipeline;
+pub mod prefilter;
+mod progress;
 mod result;
+pub mod retrieve;
+mod shutdown;
 
 pub use config::*;
 pub use context::*;
 pub use eval::score::ScoreConfig;
 pub use layer::*;
+pub use progress::*;
 pub use result::*;
+pub use shutdown::*;
 
 use std::sync::{Arc, Mutex};
 
+use futures::future::{BoxFuture, FutureExt};
+use futures::stream::{BoxStream, StreamExt, TryStreamExt};
 use loom_codec::{CodecRegistry, CodecRegistryBuilder};
 use loom_config::Config;
 use loom_core::{Format, MediaType, decode, encode, ident_path};
 use loom_error::Result;
-use loom_io::{DataSourceRegistry, DataSourceRegistryBuilder, path::Path};
+use loom_io::{
+    ChangeEvent, DataSource, DataSourceRegistry, DataSourceRegistryBuilder, ETag, ReadError,
+    WriteError,
+    path::{Path, Scheme},
+    sources::RetryPolicy,
+};
+
+use crate::eval::score::{AsyncBatchScorer, BatchScorer, Scorer};
 
 // Re-export config types
 pub use loom_config::{Config as RConfig, ConfigError};
 pub use loom_pipe::{
-    Layer, LayerContext, LayerResult, Pipeline, PipelineBuilder,
+    Layer, LayerContext, LayerMeta, LayerResult, Pipeline, PipelineBuilder,
     operators::{Await, FanOut, Filter, Fork, Parallel, Router, TryMap},
 };
 use serde::{Serialize, de::DeserializeOwned};
 
 // Re-export commonly used types for convenience
+#[cfg(feature = "arrow")]
+pub use loom_codec::ArrowCodec;
+#[cfg(feature = "bson")]
+pub use loom_codec::BsonCodec;
+#[cfg(feature = "ini")]
+pub use loom_codec::IniCodec;
+#[cfg(feature = "msgpack")]
+pub use loom_codec::MsgPackCodec;
+#[cfg(feature = "parquet")]
+pub use loom_codec::ParquetCodec;
+#[cfg(feature = "protobuf")]
+pub use loom_codec::ProtobufCodec;
 #[cfg(feature = "toml")]
 pub use loom_codec::TomlCodec;
 #[cfg(feature = "yaml")]
 pub use loom_codec::YamlCodec;
-pub use loom_codec::{JsonCodec, TextCodec};
+pub use loom_codec::{
+    CodecError, EncodeOptions, JsonCodec, NdjsonCodec, PartialDocument, Position, TextCodec,
+};
+#[cfg(any(feature = "gzip", feature = "zstd"))]
+pub use loom_codec::{CompressedCodec, Compression};
 pub use loom_io::Record;
 pub use loom_io::sources::FileSystemSource;
 
 // Re-export signal types for convenience
 pub use loom_signal::{
-    Emitter, Level, NoopEmitter, Signal, SignalBroadcaster, Span, Type as SignalType,
-    consumers::{FileEmitter, MemoryEmitter, StdoutEmitter},
+    Attributes, Emitter, FilterEmitter, Level, NoopEmitter, RedactingEmitter, Signal,
+    SignalBroadcaster, Span, SpanGuard, Type as SignalType,
+    consumers::{FileEmitter, MemoryEmitter, SignalQuery, StdoutEmitter},
 };
 
-/// Wrapper that bridges Arc<Mutex<ScoreLayer>> to the Layer trait.
+/// Shared handle to the runtime's scorer.
+pub type SharedScorer = Arc<Mutex<Box<dyn BatchScorer>>>;
+
+/// Wrapper that bridges the shared scorer to the Layer trait.
 /// This allows the scorer to be used via runtime.eval().
-struct ScorerLayerWrapper(Arc<Mutex<eval::score::ScoreLayer>>);
+///
+/// Single-text calls go to the scorer's interactive replica when it has one,
+/// which has a lock of its own so they don't wait on batches holding the
+/// shared scorer. A context with a deadline fails with a `Timeout` error
+/// rather than wait for the lock past it.
+struct ScorerLayerWrapper {
+    scorer: SharedScorer,
+    interactive: Option<Arc<Mutex<Box<dyn Scorer>>>>,
+}
 
 impl Layer for ScorerLayerWrapper {
     type Input = Context<()>;
     type Output = eval::score::ScoreResult;
 
     fn process(&self, input: Self::Input) -> Result<LayerResult<Self::Output>> {
-        let scorer = self.0.lock().expect("scorer lock poisoned");
-        scorer.process(input)
+        if input.is_expired() {
+            return Err(deadline_exceeded("score"));
+        }
+
+        match &self.interactive {
+            Some(interactive) => {
+                let scorer = lock_until(interactive, input.deadline(), "interactive scorer")?;
+                scorer.invoke(input)
+            }
+            None => {
+                let scorer = lock_until(&self.scorer, input.deadline(), "scorer")?;
+                scorer.invoke(input)
+            }
+        }
     }
 
     fn name(&self) -> &'static str {
@@ -59,15 +120,120 @@ impl Layer for ScorerLayerWrapper {
     }
 }
 
+/// Batch counterpart of [`ScorerLayerWrapper`], scoring the samples of a
+/// [`BatchContext`] in one call to the shared scorer via
+/// `runtime.eval("score.batch", ctx)`, under the same deadline rules. Each
+/// sample is scored with its `context` lines as previous turns (see
+/// [`Sample::to_context`](eval::Sample::to_context)).
+///
+/// Each call is traced as a `score.batch` span in the context's trace.
+struct ScorerBatchLayerWrapper {
+    scorer: SharedScorer,
+    signals: Arc<dyn Emitter + Send + Sync>,
+}
+
+impl Layer for ScorerBatchLayerWrapper {
+    type Input = BatchContext;
+    type Output = Vec<eval::score::ScoreLayerOutput>;
+
+    fn process(&self, input: Self::Input) -> Result<LayerResult<Self::Output>> {
+        if input.is_expired() {
+            return Err(deadline_exceeded("score.batch"));
+        }
+
+        let mut span = SpanGuard::new(self.signals.as_ref(), "score.batch")
+            .with_attr("size", input.len() as i64);
+
+        if let Some(trace_id) = input.trace_id() {
+            span = span.with_trace_id(trace_id);
+        }
+
+        let outputs = lock_until(&self.scorer, input.deadline(), "scorer").and_then(|scorer| {
+            let ctxs: Vec<Context<()>> = input.samples().iter().map(|s| s.to_context()).collect();
+            scorer.score_contexts(&ctxs)
+        });
+
+        match outputs {
+            Ok(outputs) => Ok(LayerResult::new(outputs)),
+            Err(err) => {
+                span.fail(err.to_string());
+                Err(err)
+            }
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "score.batch"
+    }
+}
+
+/// Lock `mutex`, polling for it once there is a `deadline` so a caller
+/// doesn't wait past it on a pass holding the lock
+fn lock_until<'a, T: ?Sized>(
+    mutex: &'a Mutex<T>,
+    deadline: Option<std::time::Instant>,
+    name: &str,
+) -> Result<std::sync::MutexGuard<'a, T>> {
+    let Some(deadline) = deadline else {
+        return Ok(mutex
+            .lock()
+            .unwrap_or_else(|_| panic!("{} lock poisoned", name)));
+    };
+
+    loop {
+        match mutex.try_lock() {
+            Ok(guard) => return Ok(guard),
+            Err(std::sync::TryLockError::Poisoned(_)) => panic!("{} lock poisoned", name),
+            Err(std::sync::TryLockError::WouldBlock) => {
+                let remaining = deadline.saturating_duration_since(std::time::Instant::now());
+
+                if remaining.is_zero() {
+                    return Err(loom_error::Error::builder()
+                        .code(loom_error::ErrorCode::Timeout)
+                        .message(format!("{} lock not acquired before the deadline", name))
+                        .build());
+                }
+
+                std::thread::sleep(remaining.min(std::time::Duration::from_millis(1)));
+            }
+        }
+    }
+}
+
+fn deadline_exceeded(layer: &str) -> loom_error::Error {
+    loom_error::Error::builder()
+        .code(loom_error::ErrorCode::Timeout)
+        .message(format!("{} deadline exceeded before inference", layer))
+        .build()
+}
+
+/// A read of `path` that failed after it was opened
+fn read_failed(path: &Path, error: ReadError) -> loom_error::Error {
+    loom_error::Error::builder()
+        .code(loom_error::ErrorCode::Unknown)
+        .message(format!("Failed to load from path '{}': {}", path, error))
+        .build()
+}
+
 pub struct Runtime {
     codecs: CodecRegistry,
     sources: DataSourceRegistry,
     layers: LayerRegistry,
     rconfig: Config,
-    scorer: Arc<Mutex<eval::score::ScoreLayer>>,
+    scorer: SharedScorer,
+    async_scorer: Option<Arc<dyn AsyncBatchScorer>>,
     signals: Arc<dyn Emitter + Send + Sync>,
+    shutdown: ShutdownHooks,
+    /// When device memory was last queried for an `eval.memory` signal
+    memory_polled: Mutex<Option<std::time::Instant>>,
 }
 
+/// Chunk size, in bytes, records are read in by `load` and `load_stream`
+const READ_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Least time between two device memory queries, each of which runs `nvidia-smi`
+const MEMORY_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
+
 impl Runtime {
     pub fn new() -> Builder {
         Builder::new()
@@ -81,6 +247,86 @@ impl Runtime {
         &self.sources
     }
 
+    /// The DataSource routed for `path`, by its scheme or the default source
+    pub fn resolve(&self, path: &Path) -> Result<&dyn DataSource> {
+        self.sources.resolve(path).ok_or_else(|| {
+            loom_error::Error::builder()
+                .code(loom_error::ErrorCode::NotFound)
+                .message(format!("No DataSource routed for path '{}'", path))
+                .build()
+        })
+    }
+
+    /// Check every registered DataSource is reachable (see
+    /// [`DataSource::health`]), failing with one error naming each that
+    /// isn't, e.g. at startup before any work is queued.
+    pub async fn health(&self) -> Result<()> {
+        let failures = self
+            .sources
+            .health()
+            .await
+            .into_iter()
+            .filter_map(|(name, health)| {
+                health
+                    .err()
+                    .map(|e| format!("DataSource '{}' is unreachable: {}", name, e))
+            })
+            .collect::<Vec<_>>();
+
+        if failures.is_empty() {
+            return Ok(());
+        }
+
+        Err(loom_error::Error::builder()
+            .code(loom_error::ErrorCode::NotFound)
+            .message(failures.join("; "))
+            .build())
+    }
+
+    /// Fail if `path` names a compressed file (e.g. `.json.gz`), which would
+    /// otherwise be written uncompressed
+    fn uncompressed(path: &Path) -> Result<()> {
+        if !matches!(
+            MediaType::from_path(&path.to_string()),
+            MediaType::ArchiveGzip | MediaType::ArchiveZstd
+        ) {
+            return Ok(());
+        }
+
+        Err(loom_error::Error::builder()
+            .code(loom_error::ErrorCode::BadArguments)
+            .message(format!(
+                "Can't save compressed '{}' without a codec registered for its extension",
+                path
+            ))
+            .build())
+    }
+
+    /// Fail unless `source` can write, before encoding anything for it
+    fn writable(source: &dyn DataSource, path: &Path) -> Result<()> {
+        if source.capabilities().write {
+            return Ok(());
+        }
+
+        Err(loom_error::Error::builder()
+            .code(loom_error::ErrorCode::BadArguments)
+            .message(format!(
+                "DataSource '{}' is read-only, can't save to '{}'",
+                source.name(),
+                path
+            ))
+            .build())
+    }
+
+    fn named_source(&self, name: &str) -> Result<&dyn DataSource> {
+        self.sources.get(name).ok_or_else(|| {
+            loom_error::Error::builder()
+                .code(loom_error::ErrorCode::NotFound)
+                .message(format!("DataSource '{}' not found", name))
+                .build()
+        })
+    }
+
     pub fn layers(&self) -> &LayerRegistry {
         &self.layers
     }
@@ -105,14 +351,59 @@ impl Runtime {
         self.signals.emit(signal);
     }
 
+    /// Hooks [`Runtime::shutdown`] has yet to run.
+    pub fn shutdown_hooks(&self) -> &ShutdownHooks {
+        &self.shutdown
+    }
+
+    /// Run the shutdown hooks in registration order, then flush the emitter.
+    ///
+    /// Every hook runs even if an earlier one fails; each failure is emitted
+    /// as a `runtime.shutdown_failed` signal and returned, grouped if there
+    /// are several. Hooks run once, so later calls only flush.
+    pub async fn shutdown(&self) -> Result<()> {
+        let mut errors = Vec::new();
+
+        for (name, hook) in self.shutdown.take() {
+            if let Err(err) = hook(self).await {
+                self.emit(
+                    Signal::new()
+                        .otype(SignalType::Event)
+                        .level(Level::Error)
+                        .name("runtime.shutdown_failed")
+                        .attr("hook", name)
+                        .attr("error", err.to_string())
+                        .build(),
+                );
+
+                errors.push(err);
+            }
+        }
+
+        self.signals.flush();
+
+        match errors.len() {
+            0 => Ok(()),
+            1 => Err(errors.remove(0)),
+            n => Err(loom_error::Error::builder()
+                .code(loom_error::ErrorCode::Unknown)
+                .message(format!("{} shutdown hooks failed", n))
+                .inner(loom_error::ErrorGroup::from(errors))
+                .build()),
+        }
+    }
+
     /// Get access to the scorer for direct batch operations.
-    pub fn scorer(&self) -> &Arc<Mutex<eval::score::ScoreLayer>> {
+    pub fn scorer(&self) -> &SharedScorer {
         &self.scorer
     }
 
     /// Score a single text using the registered score layer.
     ///
     /// This uses `runtime.eval()` internally for type-checked layer invocation.
+    /// With an `inference_timeout_ms` configured, the call fails with a
+    /// `Timeout` error instead of waiting longer for the scorer, e.g. while
+    /// an abandoned batch still holds it.
     ///
     /// # Example
     /// ```ignore
@@ -120,13 +411,18 @@ impl Runtime {
     /// println!("Score: {}", result.score);
     /// ```
     pub fn score(&self, text: &str) -> Result<eval::score::ScoreResult> {
-        let ctx = Context::new(text, ());
+        let ctx = match self.inference_deadline() {
+            Some(deadline) => Context::new(text, ()).with_deadline(deadline),
+            None => Context::new(text, ()),
+        };
+
         self.eval::<Context<()>, eval::score::ScoreResult>("score", ctx)
     }
 
     /// Score multiple texts in a batch using the registered score layer.
     ///
     /// More efficient than calling `score()` repeatedly for ML inference.
+    /// Waits for the scorer no longer than `score()` does.
     ///
     /// # Example
     /// ```ignore
@@ -137,14 +433,57 @@ impl Runtime {
     /// }
     /// ```
     pub fn score_batch(&self, texts: &[&str]) -> Result<Vec<eval::score::ScoreLayerOutput>> {
-        let scorer = self.scorer.lock().expect("scorer lock poisoned");
+        let scorer = lock_until(&self.scorer, self.inference_deadline(), "scorer")?;
         scorer.score_batch(texts)
     }
 
+    /// When a scoring call started now must finish, per `inference_timeout_ms`
+    fn inference_deadline(&self) -> Option<std::time::Instant> {
+        self.config()
+            .inference_timeout_ms
+            .map(|ms| std::time::Instant::now() + std::time::Duration::from_millis(ms))
+    }
+
+    /// Number of tokens the scorer's model splits `text` into, or `None` when the
+    /// scorer has no tokenizer. Prefer this over character length for chunking
+    /// and batching decisions.
+    pub fn tokenize_len(&self, text: &str) -> Option<usize> {
+        let scorer = self.scorer.lock().expect("scorer lock poisoned");
+        scorer.tokenize_len(text)
+    }
+
     pub fn pipeline<Input: Send + 'static>(&self) -> PipelineBuilder<Input, Input> {
         PipelineBuilder::new()
     }
 
+    /// Resolve a config-defined pipeline against the registered layers; see
+    /// [`pipeline::PipelineConfig::resolve`].
+    ///
+    /// Stages whose pinned version the layer does not satisfy are reported as
+    /// `pipeline.version_mismatch` warnings when the policy lets them through.
+    pub fn resolve_pipeline(
+        &self,
+        config: &pipeline::PipelineConfig,
+        native: &[&str],
+    ) -> Result<Vec<String>> {
+        let stages = config.resolve(&self.layers, native)?;
+
+        for (stage, err) in config.version_mismatches(&self.layers) {
+            self.emit(
+                Signal::new()
+                    .otype(SignalType::Event)
+                    .level(Level::Warn)
+                    .name("pipeline.version_mismatch")
+                    .attr("stage", stage.name.clone())
+                    .attr("pinned", stage.version.clone().unwrap_or_default())
+                    .attr("error", err.to_string())
+                    .build(),
+            );
+        }
+
+        Ok(stages)
+    }
+
     /// Evaluate input using a named layer.
     ///
     /// Returns the layer's output, performing runtime type checks.
@@ -175,6 +514,251 @@ impl Runtime {
         })
     }
 
+    /// Evaluate borrowed input using a named layer.
+    ///
+    /// Layers that implement [`Layer::process_ref`] read `input` in place; for
+    /// the rest it is cloned and passed by value, as with [`Runtime::eval`].
+    ///
+    /// # Example
+    /// ```ignore
+    /// let ctx = Context::new(text, ());
+    /// runtime.eval_ref::<Context<()>, ()>("prefilter", &ctx)?;
+    /// let entities: Vec<NamedEntity> = runtime.eval_ref("ner", &ctx)?;
+    /// ```
+    pub fn eval_ref<I, O>(&self, layer_name: &str, input: &I) -> Result<O>
+    where
+        I: Clone + Send + 'static,
+        O: Send + 'static,
+    {
+        use std::any::TypeId;
+
+        let layer = self
+            .layers
+            .get_checked(layer_name, TypeId::of::<I>(), TypeId::of::<O>())?;
+
+        let boxed_output = match layer.process_ref_any(input) {
+            Some(result) => result?.output,
+            None => layer.process_any(Box::new(input.clone()))?,
+        };
+
+        boxed_output.downcast::<O>().map(|b| *b).map_err(|_| {
+            loom_error::Error::builder()
+                .code(loom_error::ErrorCode::Unknown)
+                .message("Output type mismatch after layer execution")
+                .build()
+        })
+    }
+
+    /// Evaluate input using a named layer that produces multiple outputs
+    /// (`Vec<O>`), iterating over them.
+    ///
+    /// # Example
+    /// ```ignore
+    /// for entity in runtime.eval_iter::<Context<()>, NamedEntity>("ner", ctx)? {
+    ///     println!("{}: {}", entity.label, entity.word);
+    /// }
+    /// ```
+    pub fn eval_iter<I, O>(&self, layer_name: &str, input: I) -> Result<std::vec::IntoIter<O>>
+    where
+        I: Send + 'static,
+        O: Send + 'static,
+    {
+        self.eval::<I, Vec<O>>(layer_name, input)
+            .map(IntoIterator::into_iter)
+    }
+
+    /// Score texts in sub-batches of at most `batch_size`.
+    ///
+    /// When the device runs out of memory, `batch_size` is shrunk according to
+    /// the configured [`loom_cortex::BatchBackoff`] and the sub-batch retried, so
+    /// later batches keep the smaller size. Each retry emits an `eval.oom` signal,
+    /// and device memory usage is reported through `eval.memory` signals when it
+    /// can be queried, at most once per second.
+    ///
+    /// Batches go to the registered [`eval::score::AsyncBatchScorer`] when there
+    /// is one, and otherwise to the shared scorer on a blocking thread.
+    async fn score_adaptive(
+        &self,
+        texts: Vec<String>,
+        batch_size: &mut usize,
+    ) -> Result<Vec<eval::score::ScoreLayerOutput>> {
+        let config = self.config();
+        let backoff = config.backoff;
+        let timeout = config
+            .inference_timeout_ms
+            .map(std::time::Duration::from_millis);
+        let device = match &self.async_scorer {
+            Some(scorer) => scorer.config().model.device().clone(),
+            None => self
+                .scorer
+                .lock()
+                .expect("scorer lock poisoned")
+                .config()
+                .model
+                .device()
+                .clone(),
+        };
+
+        let mut outputs = Vec::with_capacity(texts.len());
+        let mut offset = 0;
+        let mut retries = 0;
+
+        while offset < texts.len() {
+            let end = (offset + *batch_size).min(texts.len());
+            let batch = texts[offset..end].to_vec();
+
+            let scoring: BoxFuture<'_, Result<Vec<eval::score::ScoreLayerOutput>>> = match &self
+                .async_scorer
+            {
+                // Async scorers await natively without tying up a blocking thread
+                Some(scorer) => {
+                    let scorer = scorer.clone();
+                    async move { scorer.score_batch(&batch).await }.boxed()
+                }
+                None => {
+                    let scorer = self.scorer.clone();
+                    let shared = self.scorer.clone();
+                    // Behind a pass abandoned on timeout, give up rather than
+                    // start this batch after its caller has stopped waiting
+                    let deadline = timeout.map(|timeout| std::time::Instant::now() + timeout);
+
+                    // Process batch in spawn_blocking; tch reports CUDA OOM by panicking
+                    let task = tokio::task::spawn_blocking(move || {
+                        let scorer = lock_until(&scorer, deadline, "scorer")?;
+                        let text_refs: Vec<&str> = batch.iter().map(|s| s.as_str()).collect();
+                        scorer.score_batch(&text_refs)
+                    });
+
+                    async move {
+                        task.await.unwrap_or_else(|e| {
+                            // The panic poisoned the scorer lock; the scorer itself is still usable
+                            shared.clear_poison();
+
+                            let message = match e.try_into_panic() {
+                                Ok(panic) => panic
+                                    .downcast_ref::<String>()
+                                    .cloned()
+                                    .or_else(|| panic.downcast_ref::<&str>().map(|s| s.to_string()))
+                                    .unwrap_or_else(|| "scorer panicked".to_string()),
+                                Err(e) => e.to_string(),
+                            };
+
+                            Err(loom_error::Error::builder()
+                                .code(loom_error::ErrorCode::Unknown)
+                                .message(message)
+                                .build())
+                        })
+                    }
+                    .boxed()
+                }
+            };
+
+            let scored = match timeout {
+                Some(timeout) => match tokio::time::timeout(timeout, scoring).await {
+                    Ok(scored) => scored,
+                    Err(_) => {
+                        // The forward pass cannot be interrupted; abandon it and
+                        // fail the run rather than hang on it
+                        let error = loom_cortex::InferenceTimeout {
+                            timeout,
+                            batch_size: end - offset,
+                        };
+
+                        self.emit(
+                            Signal::new()
+                                .otype(SignalType::Event)
+                                .level(Level::Error)
+                                .name("eval.timeout")
+                                .attr("batch_size", (end - offset) as i64)
+                                .attr("timeout_ms", timeout.as_millis() as i64)
+                                .build(),
+                        );
+
+                        return Err(loom_error::Error::builder()
+                            .code(loom_error::ErrorCode::Timeout)
+                            .message(error.to_string())
+                            .inner(error)
+                            .build());
+                    }
+                },
+                None => scoring.await,
+            };
+
+            if let Some(memory) = self.poll_memory(&device).await {
+                self.emit(
+                    Signal::new()
+                        .otype(SignalType::Event)
+                        .name("eval.memory")
+                        .attr("used", memory.used as i64)
+                        .attr("total", memory.total as i64)
+                        .attr("usage", memory.usage() as f64)
+                        .build(),
+                );
+            }
+
+            match scored {
+                Ok(batch_outputs) => {
+                    outputs.extend(batch_outputs);
+                    offset = end;
+                    retries = 0;
+                }
+                Err(e) => {
+                    let shrunk = backoff.shrink(end - offset).filter(|_| {
+                        retries < backoff.max_retries
+                            && loom_cortex::is_out_of_memory(&e.to_string())
+                    });
+
+                    let Some(shrunk) = shrunk else {
+                        return Err(e);
+                    };
+
+                    self.emit(
+                        Signal::new()
+                            .otype(SignalType::Event)
+                            .level(Level::Warn)
+                            .name("eval.oom")
+                            .attr("batch_size", (end - offset) as i64)
+                            .attr("retry_batch_size", shrunk as i64)
+                            .attr("error", e.to_string())
+                            .build(),
+                    );
+
+                    *batch_size = shrunk;
+                    retries += 1;
+                }
+            }
+        }
+
+        Ok(outputs)
+    }
+
+    /// Query `device`'s memory usage on a blocking thread, or `None` if it was
+    /// already queried within [`MEMORY_POLL_INTERVAL`].
+    async fn poll_memory(
+        &self,
+        device: &loom_cortex::CortexDevice,
+    ) -> Option<loom_cortex::DeviceMemory> {
+        {
+            let mut polled = self
+                .memory_polled
+                .lock()
+                .expect("memory poll lock poisoned");
+            let now = std::time::Instant::now();
+
+            if polled.is_some_and(|at| now.duration_since(at) < MEMORY_POLL_INTERVAL) {
+                return None;
+            }
+
+            *polled = Some(now);
+        }
+
+        let device = device.clone();
+        tokio::task::spawn_blocking(move || device.memory())
+            .await
+            .ok()
+            .flatten()
+    }
+
     /// Evaluate a dataset using the registered scorer.
     ///
     /// Progress is emitted through the runtime's signal system.
@@ -189,20 +773,15 @@ impl Runtime {
         batch_size: usize,
     ) -> Result<eval::EvalResult> {
         use loom_cortex::bench::Decision;
-        use std::collections::HashSet;
 
-        let scorer = self.scorer.clone();
-        let eval_start = std::time::Instant::now();
         let total = dataset.samples.len();
-
-        // Emit start signal
-        self.emit(
-            Signal::new()
-                .otype(SignalType::Event)
-                .name("eval.start")
-                .attr("total", total as i64)
-                .build(),
-        );
+        let mut progress = ProgressReporter::start(self.signals.as_ref(), "eval", total);
+        let mut span = self
+            .signals
+            .as_ref()
+            .span("eval.scoring")
+            .with_attr("total", total as i64)
+            .with_attr("batch_size", batch_size as i64);
 
         // Collect all samples with their original indices
         let indexed_samples: Vec<(usize, eval::Sample)> =
@@ -210,21 +789,17 @@ impl Runtime {
 
         // Process samples in batches
         let mut all_results: Vec<(eval::Sample, eval::SampleResult)> = Vec::with_capacity(total);
-        let mut processed = 0;
+        let mut scoring_batch_size = batch_size;
 
         for chunk in indexed_samples.chunks(batch_size) {
             let batch_samples: Vec<(usize, eval::Sample)> = chunk.to_vec();
             let texts: Vec<String> = batch_samples.iter().map(|(_, s)| s.text.clone()).collect();
-            let scorer = scorer.clone();
+            let mut batch_span = span
+                .child("eval.batch")
+                .with_attr("size", batch_samples.len() as i64);
 
-            // Process batch in spawn_blocking
-            let batch_outputs = tokio::task::spawn_blocking(move || {
-                let scorer = scorer.lock().expect("scorer lock poisoned");
-                let text_refs: Vec<&str> = texts.iter().map(|s| s.as_str()).collect();
-                scorer.score_batch(&text_refs)
-            })
-            .await
-            .expect("spawn_blocking failed");
+            // Process batch, shrinking it on device OOM
+            let batch_outputs = self.score_adaptive(texts, &mut scoring_batch_size).await;
 
             // Evaluate each sample in the batch
             match batch_outputs {
@@ -245,26 +820,17 @@ impl Runtime {
                             expected_labels: sample.expected_labels.clone(),
                             detected_labels,
                             elapsed_ms: None,
+                            weight: sample.weight(),
                         };
 
-                        processed += 1;
-
-                        // Emit progress signal
-                        self.emit(
-                            Signal::new()
-                                .otype(SignalType::Event)
-                                .name("eval.progress")
-                                .attr("current", processed as i64)
-                                .attr("total", total as i64)
-                                .attr("sample_id", sample.id.clone())
-                                .attr("correct", sample_result.correct)
-                                .build(),
-                        );
+                        progress.step(&sample.id, sample_result.correct);
 
                         all_results.push((sample, sample_result));
                     }
                 }
                 Err(e) => {
+                    batch_span.fail(e.to_string());
+
                     // Emit error signal
                     self.emit(
                         Signal::new()
@@ -286,21 +852,10 @@ impl Runtime {
                             expected_labels: sample.expected_labels.clone(),
                             detected_labels: vec![],
                             elapsed_ms: None,
+                            weight: sample.weight(),
                         };
 
-                        processed += 1;
-
-                        // Emit progress signal
-                        self.emit(
-                            Signal::new()
-                                .otype(SignalType::Event)
-                                .name("eval.progress")
-                                .attr("current", processed as i64)
-                                .attr("total", total as i64)
-                                .attr("sample_id", sample.id.clone())
-                                .attr("correct", sample_result.correct)
-                                .build(),
-                        );
+                        progress.step(&sample.id, sample_result.correct);
 
                         all_results.push((sample, sample_result));
                     }
@@ -308,81 +863,61 @@ impl Runtime {
             }
         }
 
-        // Calculate timing metrics
-        let elapsed = eval_start.elapsed();
-        let elapsed_ms = elapsed.as_millis() as i64;
-        let throughput = if elapsed.as_secs_f32() > 0.0 {
-            total as f32 / elapsed.as_secs_f32()
-        } else {
-            0.0
-        };
+        let correct = all_results.iter().filter(|(_, r)| r.correct).count();
+        span.set_attr("correct", correct as i64);
+        span.end();
 
-        // Emit completion signal
-        self.emit(
-            Signal::new()
-                .otype(SignalType::Event)
-                .name("eval.complete")
-                .attr("elapsed_ms", elapsed_ms)
-                .attr("throughput", throughput as f64)
-                .attr("total", total as i64)
-                .attr(
-                    "correct",
-                    all_results.iter().filter(|(_, r)| r.correct).count() as i64,
-                )
-                .build(),
-        );
+        let summary = progress.finish(Attributes::new().attr("correct", correct as i64).build());
+        let elapsed_ms = summary.elapsed.as_millis() as i64;
+        let throughput = summary.throughput;
 
         // Build result
         let mut result = eval::EvalResult::new();
-        result.total = all_results.len();
         result.elapsed_ms = elapsed_ms;
         result.throughput = throughput;
 
-        for (sample, sample_result) in all_results {
-            if sample_result.correct {
-                result.correct += 1;
-            }
-
-            let cat_result = result
-                .per_category
-                .entry(sample.primary_category.clone())
-                .or_default();
-            cat_result.total += 1;
-            if sample_result.correct {
-                cat_result.correct += 1;
-            }
-
-            // Update per-label metrics
-            let expected_set: HashSet<_> = sample.expected_labels.iter().collect();
-            let detected_set: HashSet<_> = sample_result.detected_labels.iter().collect();
-
-            for label in &sample.expected_labels {
-                let entry = result.per_label.entry(label.clone()).or_default();
-                entry.expected_count += 1;
-            }
-
-            for label in &sample_result.detected_labels {
-                let entry = result.per_label.entry(label.clone()).or_default();
-                entry.detected_count += 1;
+        result.environment = Some(self.eval_environment(dataset));
 
-                if expected_set.contains(label) {
-                    entry.true_positives += 1;
-                } else {
-                    entry.false_positives += 1;
-                }
-            }
+        for (sample, sample_result) in all_results {
+            result.push(&sample, sample_result);
+        }
 
-            for label in &sample.expected_labels {
-                if !detected_set.contains(label) {
-                    let entry = result.per_label.entry(label.clone()).or_default();
-                    entry.false_negatives += 1;
-                }
-            }
+        Ok(result)
+    }
 
-            result.sample_results.push(sample_result);
+    /// Capture the environment an evaluation of `dataset` runs in, using the
+    /// async scorer's config when there is one.
+    fn eval_environment(&self, dataset: &eval::SampleDataset) -> eval::EvalEnvironment {
+        match &self.async_scorer {
+            Some(scorer) => eval::EvalEnvironment::capture(scorer.config(), dataset),
+            None => eval::EvalEnvironment::capture(
+                self.scorer.lock().expect("scorer lock poisoned").config(),
+                dataset,
+            ),
         }
+    }
 
-        Ok(result)
+    /// Resume an evaluation from a partial result.
+    ///
+    /// Samples already in `partial` are skipped; the rest are scored and merged
+    /// with the partial results (see [`eval::EvalResult::merge`]).
+    ///
+    /// # Example
+    /// ```ignore
+    /// let partial: EvalResult = runtime.load("file_system", &path).await?;
+    /// let result = runtime.eval_scoring_resume(&dataset, 16, partial).await?;
+    /// ```
+    pub async fn eval_scoring_resume(
+        &self,
+        dataset: &eval::SampleDataset,
+        batch_size: usize,
+        partial: eval::EvalResult,
+    ) -> Result<eval::EvalResult> {
+        let rest = self
+            .eval_scoring(&dataset.pending(&partial), batch_size)
+            .await?;
+
+        Ok(partial.merge(rest, dataset))
     }
 
     /// Evaluate a dataset and return both results and raw scores.
@@ -403,20 +938,10 @@ impl Runtime {
         std::collections::HashMap<String, std::collections::HashMap<String, f32>>,
     )> {
         use loom_cortex::bench::Decision;
-        use std::collections::{HashMap, HashSet};
+        use std::collections::HashMap;
 
-        let scorer = self.scorer.clone();
-        let eval_start = std::time::Instant::now();
         let total = dataset.samples.len();
-
-        // Emit start signal
-        self.emit(
-            Signal::new()
-                .otype(SignalType::Event)
-                .name("eval.start")
-                .attr("total", total as i64)
-                .build(),
-        );
+        let mut progress = ProgressReporter::start(self.signals.as_ref(), "eval", total);
 
         // Collect all samples with their original indices
         let indexed_samples: Vec<(usize, eval::Sample)> =
@@ -425,21 +950,14 @@ impl Runtime {
         // Process samples in batches
         let mut all_results: Vec<(eval::Sample, eval::SampleResult, HashMap<String, f32>)> =
             Vec::with_capacity(total);
-        let mut processed = 0;
+        let mut scoring_batch_size = batch_size;
 
         for chunk in indexed_samples.chunks(batch_size) {
             let batch_samples: Vec<(usize, eval::Sample)> = chunk.to_vec();
             let texts: Vec<String> = batch_samples.iter().map(|(_, s)| s.text.clone()).collect();
-            let scorer = scorer.clone();
 
-            // Process batch in spawn_blocking
-            let batch_outputs = tokio::task::spawn_blocking(move || {
-                let scorer = scorer.lock().expect("scorer lock poisoned");
-                let text_refs: Vec<&str> = texts.iter().map(|s| s.as_str()).collect();
-                scorer.score_batch(&text_refs)
-            })
-            .await
-            .expect("spawn_blocking failed");
+            // Process batch, shrinking it on device OOM
+            let batch_outputs = self.score_adaptive(texts, &mut scoring_batch_size).await;
 
             // Evaluate each sample in the batch
             match batch_outputs {
@@ -462,21 +980,10 @@ impl Runtime {
                             expected_labels: sample.expected_labels.clone(),
                             detected_labels,
                             elapsed_ms: None,
+                            weight: sample.weight(),
                         };
 
-                        processed += 1;
-
-                        // Emit progress signal
-                        self.emit(
-                            Signal::new()
-                                .otype(SignalType::Event)
-                                .name("eval.progress")
-                                .attr("current", processed as i64)
-                                .attr("total", total as i64)
-                                .attr("sample_id", sample.id.clone())
-                                .attr("correct", sample_result.correct)
-                                .build(),
-                        );
+                        progress.step(&sample.id, sample_result.correct);
 
                         all_results.push((sample, sample_result, raw_scores));
                     }
@@ -503,21 +1010,10 @@ impl Runtime {
                             expected_labels: sample.expected_labels.clone(),
                             detected_labels: vec![],
                             elapsed_ms: None,
+                            weight: sample.weight(),
                         };
 
-                        processed += 1;
-
-                        // Emit progress signal
-                        self.emit(
-                            Signal::new()
-                                .otype(SignalType::Event)
-                                .name("eval.progress")
-                                .attr("current", processed as i64)
-                                .attr("total", total as i64)
-                                .attr("sample_id", sample.id.clone())
-                                .attr("correct", sample_result.correct)
-                                .build(),
-                        );
+                        progress.step(&sample.id, sample_result.correct);
 
                         all_results.push((sample, sample_result, HashMap::new()));
                     }
@@ -525,82 +1021,22 @@ impl Runtime {
             }
         }
 
-        // Calculate timing metrics
-        let elapsed = eval_start.elapsed();
-        let elapsed_ms = elapsed.as_millis() as i64;
-        let throughput = if elapsed.as_secs_f32() > 0.0 {
-            total as f32 / elapsed.as_secs_f32()
-        } else {
-            0.0
-        };
-
-        // Emit completion signal
-        self.emit(
-            Signal::new()
-                .otype(SignalType::Event)
-                .name("eval.complete")
-                .attr("elapsed_ms", elapsed_ms)
-                .attr("throughput", throughput as f64)
-                .attr("total", total as i64)
-                .attr(
-                    "correct",
-                    all_results.iter().filter(|(_, r, _)| r.correct).count() as i64,
-                )
-                .build(),
-        );
+        let correct = all_results.iter().filter(|(_, r, _)| r.correct).count();
+        let summary = progress.finish(Attributes::new().attr("correct", correct as i64).build());
+        let elapsed_ms = summary.elapsed.as_millis() as i64;
+        let throughput = summary.throughput;
 
         // Build result and raw_scores map
         let mut result = eval::EvalResult::new();
         let mut raw_scores_map: HashMap<String, HashMap<String, f32>> = HashMap::new();
-        result.total = all_results.len();
         result.elapsed_ms = elapsed_ms;
         result.throughput = throughput;
+        result.environment = Some(self.eval_environment(dataset));
 
         for (sample, sample_result, raw_scores) in all_results {
-            if sample_result.correct {
-                result.correct += 1;
-            }
-
-            let cat_result = result
-                .per_category
-                .entry(sample.primary_category.clone())
-                .or_default();
-            cat_result.total += 1;
-            if sample_result.correct {
-                cat_result.correct += 1;
-            }
-
-            // Update per-label metrics
-            let expected_set: HashSet<_> = sample.expected_labels.iter().collect();
-            let detected_set: HashSet<_> = sample_result.detected_labels.iter().collect();
-
-            for label in &sample.expected_labels {
-                let entry = result.per_label.entry(label.clone()).or_default();
-                entry.expected_count += 1;
-            }
-
-            for label in &sample_result.detected_labels {
-                let entry = result.per_label.entry(label.clone()).or_default();
-                entry.detected_count += 1;
-
-                if expected_set.contains(label) {
-                    entry.true_positives += 1;
-                } else {
-                    entry.false_positives += 1;
-                }
-            }
-
-            for label in &sample.expected_labels {
-                if !detected_set.contains(label) {
-                    let entry = result.per_label.entry(label.clone()).or_default();
-                    entry.false_negatives += 1;
-                }
-            }
-
             // Store raw scores by sample ID
             raw_scores_map.insert(sample_result.id.clone(), raw_scores);
-
-            result.sample_results.push(sample_result);
+            result.push(&sample, sample_result);
         }
 
         Ok((result, raw_scores_map))
@@ -608,6 +1044,10 @@ impl Runtime {
 
     /// Load and deserialize data from a DataSource.
     ///
+    /// JSON, NDJSON, YAML and TOML records are deserialized directly. Other
+    /// formats (e.g. Parquet) are decoded by the codec registered for them, with
+    /// a single root entity loaded as-is and multiple entities (rows) as an array.
+    ///
     /// # Arguments
     /// * `source` - The name of the registered DataSource (e.g., "file_system")
     /// * `path` - The path to load from
@@ -617,35 +1057,370 @@ impl Runtime {
     /// let dataset: SampleDataset = runtime.load("file_system", &path).await?;
     /// ```
     pub async fn load<T: DeserializeOwned>(&self, source: &str, path: &Path) -> Result<T> {
-        let source = self.sources.get(source).ok_or_else(|| {
+        self.load_from(self.named_source(source)?, path).await
+    }
+
+    /// Changes to the records under `path`, from the DataSource the path
+    /// resolves to, e.g. to reload a config or dataset when its file changes.
+    /// Fails if that source can't watch (see [`loom_io::WatchSource`]).
+    ///
+    /// # Example
+    /// ```ignore
+    /// let mut changes = runtime.watch(&path)?;
+    ///
+    /// while let Some(change) = changes.next().await {
+    ///     let dataset: SampleDataset = runtime.fetch(&change.path).await?;
+    /// }
+    /// ```
+    pub fn watch<'a>(&'a self, path: &'a Path) -> Result<BoxStream<'a, ChangeEvent>> {
+        let source = self.resolve(path)?;
+        let watcher = source.as_watch().ok_or_else(|| {
             loom_error::Error::builder()
-                .code(loom_error::ErrorCode::NotFound)
-                .message(format!("DataSource '{}' not found", source))
+                .code(loom_error::ErrorCode::BadArguments)
+                .message(format!(
+                    "DataSource '{}' can't watch for changes",
+                    source.name()
+                ))
                 .build()
         })?;
 
-        let record = source.find_one(path).await.map_err(|e| {
+        Ok(watcher.watch(path))
+    }
+
+    /// Like [`Runtime::load`], with the DataSource picked from the path's
+    /// scheme (see [`loom_io::DataSourceRegistry::resolve`]): `file://` URIs
+    /// and plain file paths go to the source serving files, `https://` URIs
+    /// to an `HttpSource`, and so on.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let dataset: SampleDataset = runtime.fetch(&path).await?;
+    /// ```
+    pub async fn fetch<T: DeserializeOwned>(&self, path: &Path) -> Result<T> {
+        self.load_from(self.resolve(path)?, path).await
+    }
+
+    async fn load_from<T: DeserializeOwned>(
+        &self,
+        source: &dyn DataSource,
+        path: &Path,
+    ) -> Result<T> {
+        let chunked = self
+            .retry_io("load from", path, ReadError::is_transient, || {
+                source.find_one_chunked(path, READ_CHUNK_SIZE)
+            })
+            .await?;
+
+        // JSON Lines are parsed as they are read rather than buffered whole
+        #[cfg(feature = "json")]
+        if chunked.header.media_type.format() == Format::Ndjson
+            && self
+                .codecs
+                .compound(&chunked.header.path.to_string())
+                .is_none()
+        {
+            let items = Self::json_lines::<serde_json::Value>(path, chunked)
+                .try_collect::<Vec<_>>()
+                .await?;
+
+            return serde_json::from_value(serde_json::Value::Array(items)).map_err(|e| {
+                loom_error::Error::builder()
+                    .code(loom_error::ErrorCode::Unknown)
+                    .message(format!("Deserialization failed: {}", e))
+                    .build()
+            });
+        }
+
+        let record = chunked.collect().await.map_err(|e| read_failed(path, e))?;
+
+        if let Some(codec) = self.codecs.compound(&record.path.to_string()) {
+            return Self::decode_with(codec, record);
+        }
+
+        let format = record.media_type.format();
+
+        if !matches!(
+            format,
+            Format::Json | Format::Ndjson | Format::Yaml | Format::Toml
+        ) && self.codecs.exists(format)
+        {
+            return self.load_with_codec(format, record);
+        }
+
+        let content = record.content_str().map_err(|e| {
             loom_error::Error::builder()
                 .code(loom_error::ErrorCode::Unknown)
-                .message(format!("Failed to load from path '{}': {}", path, e))
+                .message(format!("Invalid UTF-8 content: {}", e))
                 .build()
         })?;
 
-        let content = record.content_str().map_err(|e| {
+        decode!(content, format).map_err(|e| {
             loom_error::Error::builder()
                 .code(loom_error::ErrorCode::Unknown)
-                .message(format!("Invalid UTF-8 content: {}", e))
+                .message(format!("Deserialization failed: {}", e))
+                .build()
+        })
+    }
+
+    /// Decode a record with its registered codec and deserialize the resulting value.
+    fn load_with_codec<T: DeserializeOwned>(&self, format: Format, record: Record) -> Result<T> {
+        let codec = self.codecs.get(format).ok_or_else(|| {
+            loom_error::Error::builder()
+                .code(loom_error::ErrorCode::NotFound)
+                .message(format!("No codec registered for format '{}'", format))
                 .build()
         })?;
 
-        decode!(content, record.media_type.format()).map_err(|e| {
+        Self::decode_with(codec, record)
+    }
+
+    /// Decode a record with `codec` and deserialize the resulting value.
+    fn decode_with<T: DeserializeOwned>(
+        codec: &dyn loom_codec::Codec,
+        record: Record,
+    ) -> Result<T> {
+        let mut document = codec.decode(record).map_err(|e| {
             loom_error::Error::builder()
                 .code(loom_error::ErrorCode::Unknown)
-                .message(format!("Deserialization failed: {}", e))
+                .message(format!("Decoding failed: {}", e))
+                .build()
+        })?;
+
+        let value = match document.content.len() {
+            1 if document.content[0].path.to_string() == "root" => {
+                document.content.remove(0).content
+            }
+            _ => loom_core::value::Value::Array(
+                document
+                    .content
+                    .into_iter()
+                    .map(|entity| entity.content)
+                    .collect::<Vec<_>>()
+                    .into(),
+            ),
+        };
+
+        #[cfg(feature = "json")]
+        {
+            serde_json::from_value(serde_json::Value::from(value)).map_err(|e| {
+                loom_error::Error::builder()
+                    .code(loom_error::ErrorCode::Unknown)
+                    .message(format!("Deserialization failed: {}", e))
+                    .build()
+            })
+        }
+
+        #[cfg(not(feature = "json"))]
+        {
+            let _ = value;
+            Err(loom_error::Error::builder()
+                .code(loom_error::ErrorCode::BadArguments)
+                .message(format!(
+                    "Loading '{}' records requires the json feature",
+                    codec.format()
+                ))
+                .build())
+        }
+    }
+
+    /// Decode a record from a DataSource with its registered codec, keeping
+    /// whatever parses.
+    ///
+    /// Unlike [`Runtime::load`], which stops at the first parse error, every
+    /// problem comes back in [`PartialDocument::errors`], with its line and
+    /// column where the codec knows them, so a file can be checked in one pass.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let partial = runtime.decode_lenient("file_system", &path).await?;
+    /// for error in &partial.errors {
+    ///     eprintln!("{}", error);
+    /// }
+    /// ```
+    pub async fn decode_lenient(&self, source: &str, path: &Path) -> Result<PartialDocument> {
+        let source = self.named_source(source)?;
+
+        let record = self
+            .retry_io("load from", path, ReadError::is_transient, || {
+                source.find_one(path)
+            })
+            .await?;
+
+        let format = record.media_type.format();
+        let codec = self
+            .codecs
+            .compound(&record.path.to_string())
+            .or_else(|| self.codecs.get(format))
+            .ok_or_else(|| {
+                loom_error::Error::builder()
+                    .code(loom_error::ErrorCode::NotFound)
+                    .message(format!("No codec registered for format '{}'", format))
+                    .build()
+            })?;
+
+        codec.decode_lenient(record).map_err(|e| {
+            loom_error::Error::builder()
+                .code(loom_error::ErrorCode::BadArguments)
+                .message(format!("Decoding failed: {}", e))
                 .build()
         })
     }
 
+    /// Lazily load a JSON Lines record from the DataSource the path resolves
+    /// to, deserializing one item per non-empty line.
+    ///
+    /// Unlike [`Runtime::load`], which deserializes the whole record at once,
+    /// the record is read through [`DataSource::find_one_chunked`] and each
+    /// line deserialized as it arrives, so dumps larger than memory can be
+    /// processed item by item.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let mut samples = runtime.load_stream::<Sample>(&path).await?;
+    ///
+    /// while let Some(sample) = samples.next().await {
+    ///     let sample = sample?;
+    /// }
+    /// ```
+    #[cfg(feature = "json")]
+    pub async fn load_stream<'a, T: DeserializeOwned + Send + 'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> Result<BoxStream<'a, Result<T>>> {
+        let source = self.resolve(path)?;
+        let chunked = self
+            .retry_io("load from", path, ReadError::is_transient, || {
+                source.find_one_chunked(path, READ_CHUNK_SIZE)
+            })
+            .await?;
+
+        Ok(Self::json_lines(path, chunked))
+    }
+
+    /// Deserialize each non-empty line of `chunked` as it is read
+    #[cfg(feature = "json")]
+    fn json_lines<'a, T: DeserializeOwned + Send + 'a>(
+        path: &'a Path,
+        chunked: loom_io::ChunkedRecord<'a>,
+    ) -> BoxStream<'a, Result<T>> {
+        chunked
+            .lines()
+            .try_filter(|line| std::future::ready(!line.trim_ascii().is_empty()))
+            .map(move |line| {
+                let line = line.map_err(|e| read_failed(path, e))?;
+
+                serde_json::from_slice(&line).map_err(|e| {
+                    loom_error::Error::builder()
+                        .code(loom_error::ErrorCode::Unknown)
+                        .message(format!("Deserialization failed: {}", e))
+                        .build()
+                })
+            })
+            .boxed()
+    }
+
+    /// Serialize `items` one at a time into a file on the local file system,
+    /// with the codec registered for `format` (NDJSON falls back to
+    /// [`NdjsonCodec`]). Returns the number of items written. JSON is written
+    /// as one array and YAML as a document stream; formats whose codec can't
+    /// stream (e.g. TOML) fail without touching the file, as do paths whose
+    /// DataSource is read-only and compressed paths (e.g. `.json.gz`).
+    ///
+    /// Unlike [`Runtime::save`], which serializes everything into one record
+    /// before handing it to a DataSource, each item is encoded and written as it
+    /// is produced (see [`loom_codec::Codec::encode_stream`]), so exports with
+    /// hundreds of thousands of items never sit in memory as a single string.
+    /// The file is replaced only once every item is written (see
+    /// [`loom_io::AtomicFile`]), so a failed or concurrent export never leaves
+    /// a partial file behind.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let written = runtime.save_stream("scores.jsonl", export.samples(), Format::Ndjson)?;
+    /// ```
+    #[cfg(feature = "json")]
+    pub fn save_stream<T: Serialize>(
+        &self,
+        path: impl AsRef<std::path::Path>,
+        items: impl IntoIterator<Item = T>,
+        format: Format,
+    ) -> Result<usize> {
+        let path = path.as_ref();
+        let ndjson = NdjsonCodec::new();
+        let codec: &dyn loom_codec::Codec = match self.codecs.get(format) {
+            Some(codec) => codec,
+            None if format == Format::Ndjson => &ndjson,
+            None => {
+                return Err(loom_error::Error::builder()
+                    .code(loom_error::ErrorCode::NotFound)
+                    .message(format!("No codec registered for format '{}'", format))
+                    .build());
+            }
+        };
+
+        let write_error = |e: std::io::Error| {
+            loom_error::Error::builder()
+                .code(loom_error::ErrorCode::Unknown)
+                .message(format!("Failed to write '{}': {}", path.display(), e))
+                .build()
+        };
+
+        let file_path = Path::File(loom_io::path::FilePath::from(path.to_path_buf()));
+        Self::uncompressed(&file_path)?;
+
+        if let Some(source) = self.sources.resolve(&file_path) {
+            Self::writable(source, &file_path)?;
+        }
+
+        let mut out =
+            std::io::BufWriter::new(loom_io::AtomicFile::create(path).map_err(write_error)?);
+        let media_type = format.media_type();
+        let mut serialize_error = None;
+
+        let written = {
+            // Stop at the first item that fails to serialize and report it below
+            let mut documents =
+                items
+                    .into_iter()
+                    .map_while(|item| match serde_json::to_value(&item) {
+                        Ok(json) => Some(loom_io::Document::new(
+                            file_path.clone(),
+                            media_type,
+                            vec![loom_io::Entity::new(
+                                ident_path!("root"),
+                                media_type.as_mime_str(),
+                                json.into(),
+                            )],
+                        )),
+                        Err(e) => {
+                            serialize_error = Some(e);
+                            None
+                        }
+                    });
+
+            codec.encode_stream(&mut documents, &mut out).map_err(|e| {
+                loom_error::Error::builder()
+                    .code(loom_error::ErrorCode::Unknown)
+                    .message(format!("Encoding failed: {}", e))
+                    .build()
+            })?
+        };
+
+        if let Some(e) = serialize_error {
+            return Err(loom_error::Error::builder()
+                .code(loom_error::ErrorCode::Unknown)
+                .message(format!("Serialization failed: {}", e))
+                .build());
+        }
+
+        out.into_inner()
+            .map_err(|e| write_error(e.into_error()))?
+            .commit()
+            .map_err(write_error)?;
+
+        Ok(written)
+    }
+
     /// Save and serialize data to a DataSource.
     ///
     /// # Arguments
@@ -654,6 +1429,12 @@ impl Runtime {
     /// * `data` - The data to serialize and save
     /// * `format` - The format to serialize as
     ///
+    /// JSON, YAML and TOML output follows the [`loom_codec::EncodeOptions`]
+    /// registered for the format with [`Builder::encode_options`], if any.
+    /// Other formats (e.g. MessagePack) are encoded by their registered codec.
+    /// For large item sequences prefer [`Runtime::save_stream`], which writes
+    /// items incrementally instead of building the whole output first.
+    ///
     /// # Example
     /// ```ignore
     /// runtime.save("file_system", &path, &export, Format::Json).await?;
@@ -665,14 +1446,120 @@ impl Runtime {
         data: &T,
         format: Format,
     ) -> Result<()> {
-        let source = self.sources.get(source).ok_or_else(|| {
-            loom_error::Error::builder()
-                .code(loom_error::ErrorCode::NotFound)
-                .message(format!("DataSource '{}' not found", source))
-                .build()
-        })?;
+        self.save_to(self.named_source(source)?, path, data, format)
+            .await
+    }
 
-        let content = encode!(data, format).map_err(|e| {
+    /// Like [`Runtime::save`], with the DataSource picked from the path's
+    /// scheme, as in [`Runtime::fetch`].
+    ///
+    /// # Example
+    /// ```ignore
+    /// runtime.store(&path, &export, Format::Json).await?;
+    /// ```
+    pub async fn store<T: Serialize>(&self, path: &Path, data: &T, format: Format) -> Result<()> {
+        self.save_to(self.resolve(path)?, path, data, format).await
+    }
+
+    /// ETag of the record at `path`, or `None` if there is none yet; pass it
+    /// to [`Runtime::store_if`] to write back only if nobody changed it since.
+    pub async fn etag(&self, path: &Path) -> Result<Option<ETag>> {
+        Ok(self.resolve(path)?.etag(path).await?)
+    }
+
+    /// Like [`Runtime::store`], but only if the record at `path` still has
+    /// the `expected` ETag from [`Runtime::etag`], or still doesn't exist if
+    /// `expected` is `None`. Otherwise fails without writing, e.g. when a
+    /// concurrent run exported to the same file first.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let expected = runtime.etag(&path).await?;
+    /// // ... long-running scoring ...
+    /// runtime.store_if(&path, &export, Format::Json, expected).await?;
+    /// ```
+    pub async fn store_if<T: Serialize>(
+        &self,
+        path: &Path,
+        data: &T,
+        format: Format,
+        expected: Option<ETag>,
+    ) -> Result<()> {
+        let source = self.resolve(path)?;
+        Self::writable(source, path)?;
+        let record = self.encode_record(path, data, format)?;
+
+        self.retry_io("save to", path, WriteError::is_transient, || {
+            let record = record.clone();
+
+            async move {
+                match &expected {
+                    Some(etag) => source.update_if(record, etag).await,
+                    None => source.create(record).await,
+                }
+            }
+        })
+        .await
+    }
+
+    async fn save_to<T: Serialize>(
+        &self,
+        source: &dyn DataSource,
+        path: &Path,
+        data: &T,
+        format: Format,
+    ) -> Result<()> {
+        Self::writable(source, path)?;
+        let record = self.encode_record(path, data, format)?;
+
+        self.retry_io("save to", path, WriteError::is_transient, || {
+            source.upsert(record.clone())
+        })
+        .await
+    }
+
+    fn encode_record<T: Serialize>(
+        &self,
+        path: &Path,
+        data: &T,
+        format: Format,
+    ) -> Result<loom_io::Record> {
+        // Compressed paths (e.g. `.json.gz`) go through the codec registered
+        // for their compound extension, which compresses what it encodes
+        if let Some(codec) = self.codecs.compound(&path.to_string()) {
+            if codec.format() != format {
+                return Err(loom_error::Error::builder()
+                    .code(loom_error::ErrorCode::BadArguments)
+                    .message(format!(
+                        "Can't save {} to '{}', whose codec encodes {}",
+                        format,
+                        path,
+                        codec.format()
+                    ))
+                    .build());
+            }
+
+            return Self::encode_with(codec, path, data);
+        }
+
+        Self::uncompressed(path)?;
+
+        // Binary and columnar formats go through their registered codec
+        if !matches!(
+            format,
+            Format::Json | Format::Ndjson | Format::Yaml | Format::Toml
+        ) && let Some(codec) = self.codecs.get(format)
+        {
+            return Self::encode_with(codec, path, data);
+        }
+
+        let content = match self.codecs.options(format) {
+            Some(options) if matches!(format, Format::Json | Format::Yaml | Format::Toml) => {
+                options.encode(data, format).map_err(|e| e.to_string())
+            }
+            _ => encode!(data, format).map_err(|e| e.to_string()),
+        }
+        .map_err(|e| {
             loom_error::Error::builder()
                 .code(loom_error::ErrorCode::Unknown)
                 .message(format!("Serialization failed: {}", e))
@@ -681,21 +1568,143 @@ impl Runtime {
 
         let media_type = match format {
             Format::Json => MediaType::TextJson,
+            Format::Ndjson => MediaType::TextNdjson,
             Format::Yaml => MediaType::TextYaml,
             Format::Toml => MediaType::TextToml,
             _ => MediaType::TextPlain,
         };
 
-        let record = loom_io::Record::from_str(path.clone(), media_type, &content);
+        Ok(loom_io::Record::from_str(
+            path.clone(),
+            media_type,
+            &content,
+        ))
+    }
 
-        source.upsert(record).await.map_err(|e| {
-            loom_error::Error::builder()
-                .code(loom_error::ErrorCode::Unknown)
-                .message(format!("Failed to save to path '{}': {}", path, e))
-                .build()
-        })?;
+    /// Serialize `data` and encode it as one record with `codec`.
+    fn encode_with<T: Serialize>(
+        codec: &dyn loom_codec::Codec,
+        path: &Path,
+        data: &T,
+    ) -> Result<loom_io::Record> {
+        #[cfg(feature = "json")]
+        {
+            let json = serde_json::to_value(data).map_err(|e| {
+                loom_error::Error::builder()
+                    .code(loom_error::ErrorCode::Unknown)
+                    .message(format!("Serialization failed: {}", e))
+                    .build()
+            })?;
+            let media_type = codec.format().media_type();
+            let document = loom_io::Document::new(
+                path.clone(),
+                media_type,
+                vec![loom_io::Entity::new(
+                    ident_path!("root"),
+                    media_type.as_mime_str(),
+                    json.into(),
+                )],
+            );
+
+            codec.encode(document).map_err(|e| {
+                loom_error::Error::builder()
+                    .code(loom_error::ErrorCode::Unknown)
+                    .message(format!("Encoding failed: {}", e))
+                    .build()
+            })
+        }
+
+        #[cfg(not(feature = "json"))]
+        {
+            let _ = (path, data);
+            Err(loom_error::Error::builder()
+                .code(loom_error::ErrorCode::BadArguments)
+                .message(format!(
+                    "Saving '{}' records requires the json feature",
+                    codec.format()
+                ))
+                .build())
+        }
+    }
 
-        Ok(())
+    /// Run a DataSource call under the configured [`IoRetry`] policy: each
+    /// attempt is bounded by its timeout, and transient failures are retried
+    /// with backoff so a network blip doesn't fail a long run at its final
+    /// save. Each retry emits an `io.retry` signal and giving up `io.failed`.
+    async fn retry_io<T, E, F, Fut>(
+        &self,
+        action: &str,
+        path: &Path,
+        is_transient: fn(&E) -> bool,
+        mut attempt: F,
+    ) -> Result<T>
+    where
+        E: std::fmt::Display,
+        F: FnMut() -> Fut,
+        Fut: std::future::Future<Output = std::result::Result<T, E>>,
+    {
+        let io = self.config().io;
+        let policy = RetryPolicy::from(&io);
+        let timeout = io.timeout();
+        let mut retries = 0;
+
+        loop {
+            let outcome = match timeout {
+                Some(timeout) => tokio::time::timeout(timeout, attempt()).await.ok(),
+                None => Some(attempt().await),
+            };
+
+            let (error, transient, code) = match outcome {
+                Some(Ok(value)) => return Ok(value),
+                Some(Err(e)) => (
+                    e.to_string(),
+                    is_transient(&e),
+                    loom_error::ErrorCode::Unknown,
+                ),
+                None => (
+                    format!("timed out after {:?}", timeout.unwrap_or_default()),
+                    true,
+                    loom_error::ErrorCode::Timeout,
+                ),
+            };
+
+            if !transient || retries >= policy.max_retries {
+                self.emit(
+                    Signal::new()
+                        .otype(SignalType::Event)
+                        .level(Level::Error)
+                        .name("io.failed")
+                        .attr("action", action)
+                        .attr("path", path.to_string())
+                        .attr("attempts", (retries + 1) as i64)
+                        .attr("error", error.clone())
+                        .build(),
+                );
+
+                return Err(loom_error::Error::builder()
+                    .code(code)
+                    .message(format!("Failed to {} path '{}': {}", action, path, error))
+                    .build());
+            }
+
+            let delay = policy.jittered_delay(retries);
+
+            self.emit(
+                Signal::new()
+                    .otype(SignalType::Event)
+                    .level(Level::Warn)
+                    .name("io.retry")
+                    .attr("action", action)
+                    .attr("path", path.to_string())
+                    .attr("attempt", (retries + 1) as i64)
+                    .attr("delay_ms", delay.as_millis() as i64)
+                    .attr("error", error)
+                    .build(),
+            );
+
+            tokio::time::sleep(delay).await;
+            retries += 1;
+        }
     }
 }
 
@@ -703,9 +1712,12 @@ pub struct Builder {
     codecs: CodecRegistryBuilder,
     sources: DataSourceRegistryBuilder,
     layers: LayerRegistry,
+    factory: LayerFactory,
     rconfig: Config,
-    scorer: Option<eval::score::ScoreLayer>,
+    scorer: Option<Box<dyn BatchScorer>>,
+    async_scorer: Option<Arc<dyn AsyncBatchScorer>>,
     signals: SignalBroadcaster,
+    shutdown: ShutdownHooks,
 }
 
 impl Default for Builder {
@@ -714,9 +1726,12 @@ impl Default for Builder {
             codecs: CodecRegistryBuilder::default(),
             sources: DataSourceRegistryBuilder::default(),
             layers: LayerRegistry::default(),
+            factory: LayerFactory::default(),
             rconfig: Config::new().build().unwrap(),
             scorer: None,
+            async_scorer: None,
             signals: SignalBroadcaster::default(),
+            shutdown: ShutdownHooks::default(),
         }
     }
 }
@@ -731,11 +1746,43 @@ impl Builder {
         self
     }
 
+    /// Set the encode options for `format`, e.g. pretty-printed JSON for
+    /// human-readable exports; see [`loom_codec::CodecRegistryBuilder::encode_options`].
+    pub fn encode_options(mut self, format: Format, options: loom_codec::EncodeOptions) -> Self {
+        self.codecs = self.codecs.encode_options(format, options);
+        self
+    }
+
+    /// Register a codec for a compound extension such as `json.gz`; see
+    /// [`loom_codec::CodecRegistryBuilder::compound`].
+    pub fn compound_codec<T: loom_codec::Codec + 'static>(
+        mut self,
+        extension: &str,
+        codec: T,
+    ) -> Self {
+        self.codecs = self.codecs.compound(extension, codec);
+        self
+    }
+
     pub fn source<T: loom_io::DataSource + 'static>(mut self, source: T) -> Self {
         self.sources = self.sources.source(source);
         self
     }
 
+    /// Route paths with `scheme` to the source named `name` for
+    /// [`Runtime::fetch`] and [`Runtime::store`], overriding the source that
+    /// claims the scheme itself.
+    pub fn route(mut self, scheme: Scheme, name: impl Into<String>) -> Self {
+        self.sources = self.sources.route(scheme, name);
+        self
+    }
+
+    /// Source for paths no route claims (default: the first registered)
+    pub fn default_source(mut self, name: impl Into<String>) -> Self {
+        self.sources = self.sources.default_source(name);
+        self
+    }
+
     /// Register a layer with the runtime.
     /// The layer's name() method is used as the lookup key.
     pub fn layer<L>(mut self, layer: L) -> Self
@@ -748,16 +1795,74 @@ impl Builder {
         self
     }
 
+    /// Build `layers.*` config sections tagged `tag` (by their `type` key or,
+    /// failing that, their name) with `build`; see [`LayerFactory`].
+    pub fn layer_factory<C, L, F>(mut self, tag: impl Into<String>, build: F) -> Self
+    where
+        C: DeserializeOwned + Default + 'static,
+        L: Layer + Sync + 'static,
+        L::Input: 'static,
+        L::Output: 'static,
+        F: Fn(C) -> Result<L> + Send + Sync + 'static,
+    {
+        self.factory.register(tag, build);
+        self
+    }
+
+    /// Build the layer of every enabled stage of `pipeline` that isn't
+    /// registered yet through the layer factory, from its `layers.<name>`
+    /// section of the config set with [`config`](Builder::config) or, without
+    /// one, from the layer's defaults. Stages the factory doesn't know (e.g.
+    /// `score`, or an application's own stages) are left for
+    /// [`PipelineConfig::resolve`](pipeline::PipelineConfig::resolve) to check.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let pipeline: PipelineConfig = rconfig.bind_section(&ident_path!("pipeline"))?;
+    /// let runtime = Runtime::new().config(rconfig).pipeline(&pipeline)?.try_build()?;
+    /// let stages = pipeline.resolve(runtime.layers(), &["persist"])?;
+    /// ```
+    pub fn pipeline(mut self, pipeline: &pipeline::PipelineConfig) -> Result<Self> {
+        let section = self.rconfig.get_section(&ident_path!("layers"));
+
+        for name in pipeline.enabled() {
+            if !self.layers.contains(name) {
+                self.factory
+                    .build(name, &section.get_section(name), &mut self.layers)?;
+            }
+        }
+
+        Ok(self)
+    }
+
+    /// Set the scorer used by `score()`, `score_batch()`, and `eval_scoring()`.
+    /// An explicitly set scorer takes precedence over one built from config.
+    pub fn scorer<S: BatchScorer + 'static>(mut self, scorer: S) -> Self {
+        self.scorer = Some(Box::new(scorer));
+        self
+    }
+
+    /// Set an async scorer used by `eval_scoring()`, awaited natively instead
+    /// of running on a blocking thread (e.g. a scorer backed by a remote service).
+    /// `score()` and `score_batch()` keep using the sync scorer.
+    pub fn async_scorer<S: AsyncBatchScorer + 'static>(mut self, scorer: S) -> Self {
+        self.async_scorer = Some(Arc::new(scorer));
+        self
+    }
+
     /// Set the configuration for the runtime.
-    /// Auto-builds the scorer from `layers.score` section if present.
+    /// Auto-builds the scorer from `layers.score` section if present
+    /// and no scorer has been set. The other `layers.*` sections are built
+    /// into layers on [`build`](Builder::build) through the layer factory.
     pub fn config(mut self, config: Config) -> Self {
         let score_path = ident_path!("layers.score");
         let score_section = config.get_section(&score_path);
 
-        if let Ok(score_config) = score_section.bind::<eval::score::ScoreConfig>() {
-            if let Ok(scorer) = score_config.build() {
-                self.scorer = Some(scorer);
-            }
+        if self.scorer.is_none()
+            && let Ok(score_config) = score_section.bind::<eval::score::ScoreConfig>()
+            && let Ok(scorer) = score_config.build()
+        {
+            self.scorer = Some(Box::new(scorer));
         }
 
         self.rconfig = config;
@@ -771,34 +1876,505 @@ impl Builder {
         self
     }
 
-    pub fn build(self) -> Runtime {
-        let signals: Arc<dyn Emitter + Send + Sync> = if self.signals.is_empty() {
+    /// Add a hook run by [`Runtime::shutdown`] after those already added,
+    /// e.g. to persist a cache or close a connection. `name` identifies the
+    /// hook in `runtime.shutdown_failed` signals.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let runtime = Runtime::new()
+    ///     .on_shutdown("cache", move |_| async move { cache.persist().await }.boxed())
+    ///     .build();
+    ///
+    /// runtime.shutdown().await?;
+    /// ```
+    pub fn on_shutdown<F>(mut self, name: impl Into<String>, hook: F) -> Self
+    where
+        F: for<'a> FnOnce(&'a Runtime) -> BoxFuture<'a, Result<()>> + Send + 'static,
+    {
+        self.shutdown.add(name, hook);
+        self
+    }
+
+    /// Build the runtime, failing if a layer declared in config can't be
+    /// built or the `signals` section is invalid.
+    pub fn try_build(mut self) -> Result<Runtime> {
+        if let Some((_, err)) = self.config_layers().into_iter().next() {
+            return Err(err);
+        }
+
+        self.signal_filters()?;
+
+        Ok(self.build())
+    }
+
+    /// Build the runtime. Layers declared in config that can't be built are
+    /// skipped, each reported as a `layer.failed` signal, and an invalid
+    /// `signals` section leaves signals unfiltered, reported as a
+    /// `config.failed` signal; use [`try_build`](Builder::try_build) to fail
+    /// instead.
+    pub fn build(mut self) -> Runtime {
+        let failed = self.config_layers();
+        let instrument = !self.signals.is_empty();
+        let secrets = self.rconfig.secret_values();
+        let (filters, invalid) = match self.signal_filters() {
+            Ok(filters) => (filters, None),
+            Err(err) => (SignalsConfig::default(), Some(err)),
+        };
+        let signals: Arc<dyn Emitter + Send + Sync> = if instrument && !secrets.is_empty() {
+            // Keep values loaded by secrets providers out of every signal
+            Arc::new(filters.emitter(RedactingEmitter::new(self.signals, secrets)))
+        } else if instrument {
+            Arc::new(filters.emitter(self.signals))
+        } else {
             Arc::new(NoopEmitter)
+        };
+
+        // Report source IO alongside the rest of the run's signals
+        let sources = if instrument {
+            self.sources.emitter(signals.clone())
         } else {
-            Arc::new(self.signals)
+            self.sources
         };
 
+        if let Some(err) = invalid {
+            signals.emit(
+                Signal::new()
+                    .otype(SignalType::Event)
+                    .level(Level::Error)
+                    .name("config.failed")
+                    .attr("section", "signals")
+                    .attr("error", err.to_string())
+                    .build(),
+            );
+        }
+
+        for (name, err) in failed {
+            signals.emit(
+                Signal::new()
+                    .otype(SignalType::Event)
+                    .level(Level::Error)
+                    .name("layer.failed")
+                    .attr("layer", name)
+                    .attr("error", err.to_string())
+                    .build(),
+            );
+        }
+
         // Build scorer from config or use default
-        let scorer = self.scorer.unwrap_or_else(|| {
-            eval::score::ScoreConfig::default()
-                .build()
-                .expect("default ScoreConfig should build")
+        let mut scorer = self.scorer.unwrap_or_else(|| {
+            Box::new(
+                eval::score::ScoreConfig::default()
+                    .build()
+                    .expect("default ScoreConfig should build"),
+            )
         });
 
-        // Wrap scorer in Arc<Mutex<>> for shared access
+        // Wrap scorer in Arc<Mutex<>> for shared access, with its interactive
+        // replica (if scheduled) behind a separate lock
+        let interactive = scorer
+            .take_interactive()
+            .map(|interactive| Arc::new(Mutex::new(interactive)));
         let scorer = Arc::new(Mutex::new(scorer));
 
         // Register the scorer layer wrapper for runtime.eval() access
         let mut layers = self.layers;
-        layers.register(ScorerLayerWrapper(scorer.clone()));
+        layers.register(ScorerLayerWrapper {
+            scorer: scorer.clone(),
+            interactive,
+        });
+        layers.register(ScorerBatchLayerWrapper {
+            scorer: scorer.clone(),
+            signals: signals.clone(),
+        });
 
         Runtime {
             codecs: self.codecs.build(),
-            sources: self.sources.build(),
+            sources: sources.build(),
             layers,
             rconfig: self.rconfig,
             scorer,
+            async_scorer: self.async_scorer,
             signals,
+            shutdown: self.shutdown,
+            memory_polled: Mutex::new(None),
         }
     }
+
+    /// The filters of the `signals` config section, if it binds and is valid
+    fn signal_filters(&self) -> Result<SignalsConfig> {
+        let path = ident_path!("signals");
+        let filters: SignalsConfig = match self.rconfig.get(&path) {
+            None => SignalsConfig::default(),
+            Some(_) => self.rconfig.bind_section(&path)?,
+        };
+
+        filters.validate()?;
+        Ok(filters)
+    }
+
+    /// Build and register the layers declared under `layers.*`, except names
+    /// already registered, returning those that failed.
+    fn config_layers(&mut self) -> Vec<(String, loom_error::Error)> {
+        let section = self.rconfig.get_section(&ident_path!("layers"));
+        let mut failed = Vec::new();
+
+        for name in section.keys().into_iter().flatten() {
+            if self.layers.contains(name) {
+                continue;
+            }
+
+            if let Err(err) = self
+                .factory
+                .build(name, &section.get_section(name), &mut self.layers)
+            {
+                failed.push((name.to_string(), err));
+            }
+        }
+
+        failed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use async_trait::async_trait;
+    use loom_config::MemoryProvider;
+    use loom_core::value::Value;
+    use loom_io::path::FilePath;
+    use loom_io::sources::MemorySource;
+    use serde::Deserialize;
+
+    use super::*;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Row {
+        id: i64,
+        text: String,
+    }
+
+    fn source(record: Record) -> MemorySource {
+        MemorySource::builder().with_record(record).build()
+    }
+
+    /// A MemorySource whose first `failures` reads fail with `kind`
+    struct FlakySource {
+        inner: MemorySource,
+        kind: std::io::ErrorKind,
+        failures: usize,
+        calls: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl DataSource for FlakySource {
+        fn name(&self) -> &str {
+            "flaky"
+        }
+
+        async fn exists(&self, path: &Path) -> std::result::Result<bool, ReadError> {
+            self.inner.exists(path).await
+        }
+
+        async fn count(&self, path: &Path) -> std::result::Result<usize, ReadError> {
+            self.inner.count(path).await
+        }
+
+        async fn find_one(&self, path: &Path) -> std::result::Result<Record, ReadError> {
+            match self.calls.fetch_add(1, Ordering::SeqCst) < self.failures {
+                true => Err(std::io::Error::from(self.kind).into()),
+                false => self.inner.find_one(path).await,
+            }
+        }
+
+        async fn find(&self, path: &Path) -> std::result::Result<Vec<Record>, ReadError> {
+            self.inner.find(path).await
+        }
+
+        async fn create(&self, record: Record) -> std::result::Result<(), WriteError> {
+            self.inner.create(record).await
+        }
+
+        async fn update(&self, record: Record) -> std::result::Result<(), WriteError> {
+            self.inner.update(record).await
+        }
+
+        async fn upsert(&self, record: Record) -> std::result::Result<(), WriteError> {
+            self.inner.upsert(record).await
+        }
+
+        async fn delete(&self, path: &Path) -> std::result::Result<(), WriteError> {
+            self.inner.delete(path).await
+        }
+    }
+
+    /// A runtime reading `/rows.json` from a [`FlakySource`], retrying up
+    /// to twice without delay, and the source's call count
+    fn flaky_runtime(
+        kind: std::io::ErrorKind,
+        failures: usize,
+        signals: MemoryEmitter,
+    ) -> (Runtime, Arc<AtomicUsize>) {
+        let record = Record::from_str(
+            Path::File(FilePath::parse("/rows.json")),
+            MediaType::TextJson,
+            r#"[{"id": 1, "text": "a"}]"#,
+        );
+        let calls = Arc::new(AtomicUsize::new(0));
+        let config = Config::new()
+            .with_provider(MemoryProvider::from_pairs([
+                ("io.max_retries", Value::from(2)),
+                ("io.backoff_ms", Value::from(0)),
+            ]))
+            .build()
+            .unwrap();
+        let runtime = Runtime::new()
+            .source(FlakySource {
+                inner: source(record),
+                kind,
+                failures,
+                calls: calls.clone(),
+            })
+            .emitter(signals)
+            .config(config)
+            .build();
+
+        (runtime, calls)
+    }
+
+    #[tokio::test]
+    async fn test_load_retries_transient_errors() {
+        let signals = MemoryEmitter::new();
+        let (runtime, calls) = flaky_runtime(std::io::ErrorKind::TimedOut, 2, signals.clone());
+        let path = Path::File(FilePath::parse("/rows.json"));
+
+        let rows: Vec<Row> = runtime.load("flaky", &path).await.unwrap();
+
+        assert_eq!(rows.len(), 1);
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+        assert_eq!(signals.find_by_name("io.retry").len(), 2);
+        assert!(signals.find_by_name("io.failed").is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_load_gives_up_after_max_retries() {
+        let signals = MemoryEmitter::new();
+        let (runtime, calls) = flaky_runtime(std::io::ErrorKind::TimedOut, 5, signals.clone());
+        let path = Path::File(FilePath::parse("/rows.json"));
+
+        assert!(runtime.load::<Vec<Row>>("flaky", &path).await.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+        assert_eq!(signals.find_by_name("io.retry").len(), 2);
+
+        let failed = signals.find_by_name("io.failed");
+        assert_eq!(failed.len(), 1);
+        assert_eq!(
+            failed[0].attributes().get("attempts"),
+            Some(&Value::from(3i64))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_load_does_not_retry_not_found() {
+        let signals = MemoryEmitter::new();
+        let (runtime, calls) = flaky_runtime(std::io::ErrorKind::NotFound, 1, signals.clone());
+        let path = Path::File(FilePath::parse("/rows.json"));
+
+        assert!(runtime.load::<Vec<Row>>("flaky", &path).await.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert!(signals.find_by_name("io.retry").is_empty());
+        assert_eq!(signals.find_by_name("io.failed").len(), 1);
+    }
+
+    #[test]
+    fn test_invalid_signal_filters_are_reported() {
+        for (key, value) in [
+            ("signals.level", Value::from("loud")),
+            ("signals.sample.eval", Value::from(f64::NAN)),
+        ] {
+            let config = || {
+                Config::new()
+                    .with_provider(MemoryProvider::from_pairs([(key, value.clone())]))
+                    .build()
+                    .unwrap()
+            };
+            let signals = MemoryEmitter::new();
+
+            assert!(Runtime::new().config(config()).try_build().is_err());
+
+            let runtime = Runtime::new()
+                .emitter(signals.clone())
+                .config(config())
+                .build();
+            runtime.emit(Signal::new().name("io.read").build());
+
+            let failed = signals.find_by_name("config.failed");
+            assert_eq!(failed.len(), 1, "{}", key);
+            assert_eq!(
+                failed[0].attributes().get("section"),
+                Some(&Value::from("signals"))
+            );
+            assert_eq!(signals.find_by_name("io.read").len(), 1, "{}", key);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_load_json() {
+        let path = Path::File(FilePath::parse("/rows.json"));
+        let record = Record::from_str(
+            path.clone(),
+            MediaType::TextJson,
+            r#"[{"id": 1, "text": "a"}]"#,
+        );
+        let runtime = Runtime::new().source(source(record)).build();
+
+        let rows: Vec<Row> = runtime.load("memory", &path).await.unwrap();
+        assert_eq!(
+            rows,
+            [Row {
+                id: 1,
+                text: "a".to_string()
+            }]
+        );
+    }
+
+    #[test]
+    fn test_pipeline_builds_enabled_layers() {
+        let mut pipeline = pipeline::PipelineConfig::new(["prefilter", "facet", "persist"]);
+        pipeline.stages[1].enabled = false;
+
+        let runtime = Runtime::new().pipeline(&pipeline).unwrap().build();
+
+        assert!(runtime.layers().contains("prefilter"));
+        assert!(!runtime.layers().contains("facet"));
+        assert_eq!(
+            pipeline.resolve(runtime.layers(), &["persist"]).unwrap(),
+            ["prefilter", "persist"]
+        );
+    }
+
+    #[cfg(feature = "msgpack")]
+    #[tokio::test]
+    async fn test_save_msgpack() {
+        let path = Path::File(FilePath::parse("/rows.msgpack"));
+        let runtime = Runtime::new()
+            .source(MemorySource::builder().build())
+            .codec(MsgPackCodec::new())
+            .build();
+        let rows = [Row {
+            id: 1,
+            text: "a".to_string(),
+        }];
+
+        runtime
+            .save("memory", &path, &rows, Format::MsgPack)
+            .await
+            .unwrap();
+
+        let record = runtime
+            .sources()
+            .get("memory")
+            .unwrap()
+            .find_one(&path)
+            .await
+            .unwrap();
+        assert_eq!(record.media_type, MediaType::MsgPack);
+
+        let loaded: Vec<Row> = runtime.load("memory", &path).await.unwrap();
+        assert_eq!(loaded, rows);
+    }
+
+    #[cfg(all(feature = "gzip", feature = "json"))]
+    #[tokio::test]
+    async fn test_save_compressed() {
+        let path = Path::File(FilePath::parse("/rows.json.gz"));
+        let runtime = Runtime::new()
+            .source(MemorySource::builder().build())
+            .compound_codec("json.gz", CompressedCodec::gzip(JsonCodec::new()))
+            .build();
+        let rows = [Row {
+            id: 1,
+            text: "a".to_string(),
+        }];
+
+        runtime
+            .save("memory", &path, &rows, Format::Json)
+            .await
+            .unwrap();
+
+        let record = runtime
+            .sources()
+            .get("memory")
+            .unwrap()
+            .find_one(&path)
+            .await
+            .unwrap();
+        assert_eq!(record.media_type, MediaType::ArchiveGzip);
+
+        let loaded: Vec<Row> = runtime.load("memory", &path).await.unwrap();
+        assert_eq!(loaded, rows);
+
+        let err = runtime
+            .save("memory", &path, &rows, Format::Yaml)
+            .await
+            .unwrap_err();
+        assert!(err.code().is_bad_arguments());
+    }
+
+    #[tokio::test]
+    async fn test_save_compressed_without_codec() {
+        let path = Path::File(FilePath::parse("/rows.json.gz"));
+        let runtime = Runtime::new()
+            .source(MemorySource::builder().build())
+            .build();
+
+        let err = runtime
+            .save("memory", &path, &["a"], Format::Json)
+            .await
+            .unwrap_err();
+
+        assert!(err.code().is_bad_arguments());
+        assert!(
+            !runtime
+                .sources()
+                .get("memory")
+                .unwrap()
+                .exists(&path)
+                .await
+                .unwrap()
+        );
+    }
+
+    #[cfg(feature = "parquet")]
+    #[tokio::test]
+    async fn test_load_parquet() {
+        use loom_core::value::{Object, Value};
+
+        let rows: Vec<Object> = [(1_i64, "a"), (2, "b")]
+            .into_iter()
+            .map(|(id, text)| {
+                let mut row = Object::new();
+                row.insert("id".to_string(), Value::from(id));
+                row.insert("text".to_string(), Value::from(text));
+                row
+            })
+            .collect();
+        let path = Path::File(FilePath::parse("/rows.parquet"));
+        let bytes = ParquetCodec::new().encode_rows(&rows).unwrap();
+        let runtime = Runtime::new()
+            .source(source(Record::new(path.clone(), MediaType::Parquet, bytes)))
+            .codec(ParquetCodec::new())
+            .build();
+
+        let rows: Vec<Row> = runtime.load("memory", &path).await.unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(
+            rows[1],
+            Row {
+                id: 2,
+                text: "b".to_string()
+            }
+        );
+    }
 }
@@ -0,0 +1,89 @@
+use loom_cortex::config::{CortexModelConfig, CortexNerConfig};
+use loom_error::{Error, ErrorCode, Result};
+use serde::{Deserialize, Serialize};
+use serde_valid::Validate;
+
+use super::NerLayer;
+
+/// Configuration for the named entity recognition layer
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct NerConfig {
+    /// Token classification model used to recognize entities
+    #[serde(default = "NerConfig::model")]
+    pub model: CortexModelConfig,
+
+    /// Entities scored below this are dropped
+    #[serde(default = "NerConfig::min_score")]
+    #[validate(minimum = 0.0)]
+    #[validate(maximum = 1.0)]
+    pub min_score: f64,
+}
+
+impl NerConfig {
+    fn model() -> CortexModelConfig {
+        CortexModelConfig::Ner(CortexNerConfig::default())
+    }
+
+    fn min_score() -> f64 {
+        0.0
+    }
+
+    /// Build the layer, loading the configured model
+    pub fn build(self) -> Result<NerLayer> {
+        self.validate()
+            .map_err(|e| Error::builder().message(e.to_string()).build())?;
+
+        if !self.model.is_ner() {
+            return Err(Error::builder()
+                .code(ErrorCode::BadArguments)
+                .message("NerLayer requires a Ner model")
+                .build());
+        }
+
+        let model = self.model.clone().build()?;
+        Ok(NerLayer::new(model, self))
+    }
+}
+
+impl Default for NerConfig {
+    fn default() -> Self {
+        Self {
+            model: Self::model(),
+            min_score: Self::min_score(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_config_uses_defaults() {
+        let config: NerConfig = serde_json::from_str("{}").unwrap();
+
+        assert!(config.model.is_ner());
+        assert_eq!(config.min_score, 0.0);
+    }
+
+    #[test]
+    fn build_rejects_non_ner_models() {
+        let config = NerConfig {
+            model: CortexModelConfig::default(),
+            ..Default::default()
+        };
+
+        let err = config.build().err().unwrap();
+        assert_eq!(*err.code(), ErrorCode::BadArguments);
+    }
+
+    #[test]
+    fn build_rejects_out_of_range_min_score() {
+        let config = NerConfig {
+            min_score: 1.5,
+            ..Default::default()
+        };
+
+        assert!(config.build().is_err());
+    }
+}
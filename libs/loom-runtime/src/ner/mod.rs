@@ -0,0 +1,78 @@
+//! Named entity recognition: finds people, places, dates etc. in a text
+//! so they can be turned into facets.
+
+mod config;
+
+pub use config::*;
+
+use loom_cortex::CortexModel;
+use loom_error::{Error, ErrorCode, Result};
+use loom_pipe::{LayerMeta, LayerResult};
+
+use crate::Context;
+use crate::facet::NamedEntity;
+
+/// Recognizes named entities with a token classification model.
+pub struct NerLayer {
+    model: CortexModel,
+    config: NerConfig,
+}
+
+impl NerLayer {
+    pub(crate) fn new(model: CortexModel, config: NerConfig) -> Self {
+        Self { model, config }
+    }
+
+    /// Get the configuration for this layer
+    pub fn config(&self) -> &NerConfig {
+        &self.config
+    }
+
+    /// Recognize the entities of a single text, dropping those below `min_score`
+    pub fn recognize(&self, text: &str) -> Result<Vec<NamedEntity>> {
+        let model = match &self.model {
+            CortexModel::Ner { model, .. } => model,
+            _ => {
+                return Err(Error::builder()
+                    .code(ErrorCode::BadArguments)
+                    .message("NerLayer requires a Ner model")
+                    .build());
+            }
+        };
+
+        Ok(model
+            .predict(&[text])
+            .into_iter()
+            .flatten()
+            .filter(|e| e.score >= self.config.min_score)
+            .map(|e| NamedEntity::new(e.word, e.label, e.score))
+            .collect())
+    }
+
+    /// Invoke the ner layer directly with a context
+    pub fn invoke<Input>(&self, ctx: &Context<Input>) -> Result<LayerResult<Vec<NamedEntity>>> {
+        let entities = self.recognize(&ctx.text)?;
+        let mut result = LayerResult::new(entities);
+
+        result.meta.set_step(ctx.step);
+        result.meta.set("text", ctx.text.clone().into());
+        Ok(result)
+    }
+}
+
+impl loom_pipe::Layer for NerLayer {
+    type Input = Context<()>;
+    type Output = Vec<NamedEntity>;
+
+    fn process(&self, input: Self::Input) -> Result<LayerResult<Self::Output>> {
+        self.invoke(&input)
+    }
+
+    fn process_ref(&self, input: &Self::Input) -> Option<Result<LayerResult<Self::Output>>> {
+        Some(self.invoke(input))
+    }
+
+    fn name(&self) -> &'static str {
+        "ner"
+    }
+}
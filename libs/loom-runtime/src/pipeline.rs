@@ -0,0 +1,297 @@
+//! Pipelines declared in config: an ordered list of named stages, so
+//! deployments can reorder or disable stages without code changes.
+
+use std::collections::HashSet;
+
+use loom_error::{Error, ErrorCode, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::LayerRegistry;
+
+/// Ordered stages of a pipeline
+///
+/// # Example
+/// ```yaml
+/// pipeline:
+///   stages:
+///     - prefilter
+///     - name: score
+///       version: "1"
+///     - name: ner
+///       enabled: false
+///     - persist
+///   on_version_mismatch: warn
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PipelineConfig {
+    #[serde(default)]
+    pub stages: Vec<StageConfig>,
+
+    /// What to do when a layer does not satisfy its stage's pinned version
+    #[serde(default)]
+    pub on_version_mismatch: VersionMismatchPolicy,
+}
+
+/// Handling of a stage whose pinned version the registered layer does not satisfy
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VersionMismatchPolicy {
+    /// Resolve anyway; see [`PipelineConfig::version_mismatches`] to report it
+    Warn,
+    /// Fail resolution
+    #[default]
+    Error,
+}
+
+impl PipelineConfig {
+    pub fn new<S: Into<String>>(stages: impl IntoIterator<Item = S>) -> Self {
+        Self {
+            stages: stages.into_iter().map(StageConfig::new).collect(),
+            on_version_mismatch: VersionMismatchPolicy::default(),
+        }
+    }
+
+    /// Names of the enabled stages, in order
+    pub fn enabled(&self) -> impl Iterator<Item = &str> {
+        self.stages
+            .iter()
+            .filter(|s| s.enabled)
+            .map(|s| s.name.as_str())
+    }
+
+    /// Enabled stages whose pinned version is not satisfied, with the error
+    /// describing each mismatch: the registered layer's version differs, or
+    /// the stage is not a layer and has no version to pin
+    pub fn version_mismatches(&self, layers: &LayerRegistry) -> Vec<(&StageConfig, Error)> {
+        self.stages
+            .iter()
+            .filter(|s| s.enabled)
+            .filter_map(|s| Some((s, s.version_mismatch(layers)?)))
+            .collect()
+    }
+
+    /// Resolve the enabled stages, in order.
+    ///
+    /// Every stage must be a layer registered in `layers` or one of the
+    /// caller's `native` stages (ones that are not layers, e.g. persistence),
+    /// and may only appear once. Stages that pin a version must be layers whose
+    /// registered version matches it, unless `on_version_mismatch` is `warn`.
+    pub fn resolve(&self, layers: &LayerRegistry, native: &[&str]) -> Result<Vec<String>> {
+        let mut seen = HashSet::new();
+        let mut stages = Vec::new();
+
+        for stage in self.stages.iter().filter(|s| s.enabled) {
+            let name = stage.name.as_str();
+
+            if !layers.contains(name) && !native.contains(&name) {
+                return Err(Error::builder()
+                    .code(ErrorCode::NotFound)
+                    .message(format!("Pipeline stage '{}' not found", name))
+                    .build());
+            }
+
+            if self.on_version_mismatch == VersionMismatchPolicy::Error
+                && let Some(err) = stage.version_mismatch(layers)
+            {
+                return Err(err);
+            }
+
+            if !seen.insert(name) {
+                return Err(Error::builder()
+                    .code(ErrorCode::BadArguments)
+                    .message(format!(
+                        "Pipeline stage '{}' is declared more than once",
+                        name
+                    ))
+                    .build());
+            }
+
+            stages.push(name.to_string());
+        }
+
+        Ok(stages)
+    }
+}
+
+/// A single pipeline stage, written either as its name or as a map
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(from = "StageEntry")]
+pub struct StageConfig {
+    /// Layer or native stage name
+    pub name: String,
+    /// Disabled stages are skipped
+    pub enabled: bool,
+    /// Layer version this stage was written against (e.g. `"1"` or `"1.2"`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+}
+
+impl StageConfig {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            enabled: true,
+            version: None,
+        }
+    }
+
+    /// Pin the layer version this stage expects
+    pub fn with_version(mut self, version: impl Into<String>) -> Self {
+        self.version = Some(version.into());
+        self
+    }
+
+    fn enabled() -> bool {
+        true
+    }
+
+    /// Why the pinned version, if any, is not satisfied
+    fn version_mismatch(&self, layers: &LayerRegistry) -> Option<Error> {
+        let pinned = self.version.as_deref()?;
+
+        if !layers.contains(&self.name) {
+            return Some(
+                Error::builder()
+                    .code(ErrorCode::BadArguments)
+                    .message(format!(
+                        "Pipeline stage '{}' is not a layer, so it can't pin version '{}'",
+                        self.name, pinned
+                    ))
+                    .build(),
+            );
+        }
+
+        layers.check_version(&self.name, pinned).err()
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StageEntry {
+    Name(String),
+    Full {
+        name: String,
+        #[serde(default = "StageConfig::enabled")]
+        enabled: bool,
+        #[serde(default)]
+        version: Option<String>,
+    },
+}
+
+impl From<StageEntry> for StageConfig {
+    fn from(entry: StageEntry) -> Self {
+        match entry {
+            StageEntry::Name(name) => Self::new(name),
+            StageEntry::Full {
+                name,
+                enabled,
+                version,
+            } => Self {
+                name,
+                enabled,
+                version,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prefilter::PrefilterLayer;
+
+    fn layers() -> LayerRegistry {
+        let mut layers = LayerRegistry::new();
+        layers.register(PrefilterLayer::default());
+        layers
+    }
+
+    #[test]
+    fn deserializes_names_and_maps() {
+        let json = r#"{ "stages": ["prefilter", { "name": "ner", "enabled": false }, { "name": "persist" }] }"#;
+        let config: PipelineConfig = serde_json::from_str(json).unwrap();
+
+        assert_eq!(config.stages.len(), 3);
+        assert!(!config.stages[1].enabled);
+        assert!(config.stages[2].enabled);
+        assert_eq!(
+            config.enabled().collect::<Vec<_>>(),
+            ["prefilter", "persist"]
+        );
+    }
+
+    #[test]
+    fn resolve_keeps_order_of_enabled_stages() {
+        let config = PipelineConfig::new(["persist", "prefilter"]);
+        let stages = config.resolve(&layers(), &["persist"]).unwrap();
+
+        assert_eq!(stages, ["persist", "prefilter"]);
+    }
+
+    #[test]
+    fn resolve_rejects_unknown_stages() {
+        let config = PipelineConfig::new(["prefilter", "missing"]);
+        let err = config.resolve(&layers(), &[]).unwrap_err();
+
+        assert_eq!(*err.code(), ErrorCode::NotFound);
+    }
+
+    #[test]
+    fn resolve_rejects_duplicate_stages() {
+        let config = PipelineConfig::new(["prefilter", "prefilter"]);
+        let err = config.resolve(&layers(), &[]).unwrap_err();
+
+        assert_eq!(*err.code(), ErrorCode::BadArguments);
+    }
+
+    #[test]
+    fn deserializes_version_pins() {
+        let json = r#"{ "stages": [{ "name": "prefilter", "version": "1" }], "on_version_mismatch": "warn" }"#;
+        let config: PipelineConfig = serde_json::from_str(json).unwrap();
+
+        assert_eq!(config.stages[0].version.as_deref(), Some("1"));
+        assert_eq!(config.on_version_mismatch, VersionMismatchPolicy::Warn);
+    }
+
+    #[test]
+    fn resolve_rejects_version_mismatch() {
+        let config = PipelineConfig {
+            stages: vec![StageConfig::new("prefilter").with_version("2")],
+            ..Default::default()
+        };
+        let err = config.resolve(&layers(), &[]).unwrap_err();
+
+        assert_eq!(*err.code(), ErrorCode::BadArguments);
+        assert_eq!(config.version_mismatches(&layers()).len(), 1);
+    }
+
+    #[test]
+    fn resolve_warns_on_version_mismatch() {
+        let config = PipelineConfig {
+            stages: vec![
+                StageConfig::new("prefilter").with_version("2"),
+                StageConfig::new("persist").with_version("9"),
+            ],
+            on_version_mismatch: VersionMismatchPolicy::Warn,
+        };
+        let stages = config.resolve(&layers(), &["persist"]).unwrap();
+        let mismatches = config.version_mismatches(&layers());
+
+        assert_eq!(stages, ["prefilter", "persist"]);
+        assert_eq!(mismatches.len(), 2);
+        assert_eq!(mismatches[0].0.name, "prefilter");
+        assert_eq!(mismatches[1].0.name, "persist");
+    }
+
+    #[test]
+    fn resolve_rejects_version_pin_on_native_stage() {
+        let config = PipelineConfig {
+            stages: vec![StageConfig::new("persist").with_version("9")],
+            ..Default::default()
+        };
+        let err = config.resolve(&layers(), &["persist"]).unwrap_err();
+
+        assert_eq!(*err.code(), ErrorCode::BadArguments);
+        assert!(err.to_string().contains("not a layer"));
+    }
+}
@@ -0,0 +1,56 @@
+use loom_error::{Error, Result};
+use serde::{Deserialize, Serialize};
+use serde_valid::Validate;
+
+use super::PrefilterLayer;
+
+/// Configuration for the pre-filter layer
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Validate)]
+pub struct PrefilterConfig {
+    /// Texts shorter than this many characters (after trimming) are rejected
+    #[serde(default)]
+    pub min_chars: usize,
+
+    /// Texts longer than this many characters (after trimming) are rejected
+    #[serde(default)]
+    #[validate(minimum = 1)]
+    pub max_chars: Option<usize>,
+
+    /// Texts containing any of these phrases (case-insensitive) are rejected
+    #[serde(default)]
+    pub deny: Vec<String>,
+}
+
+impl PrefilterConfig {
+    /// Build the layer
+    pub fn build(self) -> Result<PrefilterLayer> {
+        self.validate()
+            .map_err(|e| Error::builder().message(e.to_string()).build())?;
+
+        Ok(PrefilterLayer::new(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_config_accepts_everything() {
+        let config: PrefilterConfig = serde_json::from_str("{}").unwrap();
+
+        assert_eq!(config.min_chars, 0);
+        assert!(config.max_chars.is_none());
+        assert!(config.deny.is_empty());
+    }
+
+    #[test]
+    fn build_rejects_zero_max_chars() {
+        let config = PrefilterConfig {
+            max_chars: Some(0),
+            ..Default::default()
+        };
+
+        assert!(config.build().is_err());
+    }
+}
@@ -0,0 +1,162 @@
+//! Pre-filtering: cheap text rules checked before any model runs, so
+//! obviously irrelevant input is dropped without being scored.
+
+mod config;
+
+pub use config::*;
+
+use loom_error::{Error, ErrorCode, Result};
+use loom_pipe::{LayerMeta, LayerResult};
+
+use crate::Context;
+
+/// Rejects texts that break a configured rule.
+///
+/// Rules are checked in order: minimum length, maximum length, denied phrases.
+/// A rejected text yields a `Cancel` error, like a score below threshold.
+pub struct PrefilterLayer {
+    config: PrefilterConfig,
+    deny: Vec<String>,
+}
+
+impl PrefilterLayer {
+    pub(crate) fn new(config: PrefilterConfig) -> Self {
+        let deny = config.deny.iter().map(|p| p.to_lowercase()).collect();
+        Self { config, deny }
+    }
+
+    /// Get the configuration for this layer
+    pub fn config(&self) -> &PrefilterConfig {
+        &self.config
+    }
+
+    /// Check a text against every rule
+    pub fn check(&self, text: &str) -> Result<()> {
+        let text = text.trim();
+        let len = text.chars().count();
+
+        if len < self.config.min_chars {
+            return Err(cancel(format!(
+                "text length {} is less than minimum {}",
+                len, self.config.min_chars
+            )));
+        }
+
+        if let Some(max) = self.config.max_chars
+            && len > max
+        {
+            return Err(cancel(format!(
+                "text length {} is greater than maximum {}",
+                len, max
+            )));
+        }
+
+        let lower = text.to_lowercase();
+
+        if let Some(phrase) = self.deny.iter().find(|p| lower.contains(p.as_str())) {
+            return Err(cancel(format!("text contains denied phrase '{}'", phrase)));
+        }
+
+        Ok(())
+    }
+
+    /// Invoke the pre-filter layer directly with a context
+    pub fn invoke<Input>(&self, ctx: &Context<Input>) -> Result<LayerResult<()>> {
+        self.check(&ctx.text)?;
+        let mut result = LayerResult::new(());
+
+        result.meta.set_step(ctx.step);
+        result.meta.set("text", ctx.text.clone().into());
+        Ok(result)
+    }
+}
+
+impl Default for PrefilterLayer {
+    fn default() -> Self {
+        Self::new(PrefilterConfig::default())
+    }
+}
+
+impl loom_pipe::Layer for PrefilterLayer {
+    type Input = Context<()>;
+    type Output = ();
+
+    fn process(&self, input: Self::Input) -> Result<LayerResult<Self::Output>> {
+        self.invoke(&input)
+    }
+
+    fn process_ref(&self, input: &Self::Input) -> Option<Result<LayerResult<Self::Output>>> {
+        Some(self.invoke(input))
+    }
+
+    fn name(&self) -> &'static str {
+        "prefilter"
+    }
+}
+
+fn cancel(message: String) -> Error {
+    Error::builder()
+        .code(ErrorCode::Cancel)
+        .message(message)
+        .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layer(config: PrefilterConfig) -> PrefilterLayer {
+        config.build().unwrap()
+    }
+
+    #[test]
+    fn default_accepts_any_text() {
+        assert!(PrefilterLayer::default().check("").is_ok());
+        assert!(PrefilterLayer::default().check("hello").is_ok());
+    }
+
+    #[test]
+    fn rejects_short_text() {
+        let layer = layer(PrefilterConfig {
+            min_chars: 5,
+            ..Default::default()
+        });
+
+        let err = layer.check("  hi  ").unwrap_err();
+        assert_eq!(*err.code(), ErrorCode::Cancel);
+        assert!(layer.check("hello").is_ok());
+    }
+
+    #[test]
+    fn rejects_long_text() {
+        let layer = layer(PrefilterConfig {
+            max_chars: Some(3),
+            ..Default::default()
+        });
+
+        assert!(layer.check("abc").is_ok());
+        assert_eq!(*layer.check("abcd").unwrap_err().code(), ErrorCode::Cancel);
+    }
+
+    #[test]
+    fn rejects_denied_phrases_case_insensitively() {
+        let layer = layer(PrefilterConfig {
+            deny: vec!["Unsubscribe".to_string()],
+            ..Default::default()
+        });
+
+        assert!(layer.check("click to UNSUBSCRIBE now").is_err());
+        assert!(layer.check("see you tomorrow").is_ok());
+    }
+
+    #[test]
+    fn invoke_passes_through_accepted_text() {
+        let layer = PrefilterLayer::default();
+        let result = layer.invoke(&Context::new("hello", ())).unwrap();
+
+        assert_eq!(
+            result.meta.get("text").and_then(|v| v.as_str()),
+            Some("hello")
+        );
+    }
+}
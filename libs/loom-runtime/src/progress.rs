@@ -0,0 +1,196 @@
+//! Progress reporting for long-running work (evaluation, scoring exports).
+//!
+//! A [`ProgressReporter`] emits a standard set of signals, so every consumer
+//! (CLI progress bars, log files) reads the same attributes:
+//!
+//! | Signal            | Attributes                                      |
+//! |-------------------|-------------------------------------------------|
+//! | `{name}.start`    | `total`                                         |
+//! | `{name}.progress` | `current`, `total`, `label`, `ok`               |
+//! | `{name}.complete` | `total`, `elapsed_ms`, `throughput`, plus extras |
+
+use std::time::{Duration, Instant};
+
+use loom_signal::{Attributes, Emitter, Signal, Type as SignalType};
+
+/// Emits start / step / finish signals for a run over `total` items.
+///
+/// # Example
+/// ```ignore
+/// let mut progress = ProgressReporter::start(&emitter, "eval", samples.len());
+///
+/// for sample in &samples {
+///     progress.step(&sample.id, score(sample).is_ok());
+/// }
+///
+/// let summary = progress.finish(Attributes::new().attr("correct", correct).build());
+/// ```
+pub struct ProgressReporter<'a> {
+    emitter: &'a (dyn Emitter + Send + Sync),
+    name: String,
+    total: usize,
+    current: usize,
+    started: Instant,
+}
+
+impl<'a> ProgressReporter<'a> {
+    /// Emit `{name}.start` and begin tracking `total` items.
+    pub fn start(
+        emitter: &'a (dyn Emitter + Send + Sync),
+        name: impl Into<String>,
+        total: usize,
+    ) -> Self {
+        let reporter = Self {
+            emitter,
+            name: name.into(),
+            total,
+            current: 0,
+            started: Instant::now(),
+        };
+
+        reporter.emit("start", Attributes::new().attr("total", total as i64));
+        reporter
+    }
+
+    /// Record one finished item, emitting `{name}.progress`.
+    /// `label` identifies the item (e.g. a sample id); `ok` is whether it succeeded.
+    pub fn step(&mut self, label: impl Into<String>, ok: bool) {
+        self.current += 1;
+        self.emit(
+            "progress",
+            Attributes::new()
+                .attr("current", self.current as i64)
+                .attr("total", self.total as i64)
+                .attr("label", label.into())
+                .attr("ok", ok),
+        );
+    }
+
+    /// Items finished so far
+    pub fn current(&self) -> usize {
+        self.current
+    }
+
+    /// Items expected in total
+    pub fn total(&self) -> usize {
+        self.total
+    }
+
+    /// Emit `{name}.complete` with timing and any extra `attributes`.
+    pub fn finish(self, attributes: Attributes) -> ProgressSummary {
+        let elapsed = self.started.elapsed();
+        let throughput = if elapsed.as_secs_f32() > 0.0 {
+            self.total as f32 / elapsed.as_secs_f32()
+        } else {
+            0.0
+        };
+
+        self.emit(
+            "complete",
+            Attributes::new()
+                .attr("total", self.total as i64)
+                .attr("elapsed_ms", elapsed.as_millis() as i64)
+                .attr("throughput", throughput as f64)
+                .merge(attributes),
+        );
+
+        ProgressSummary {
+            elapsed,
+            throughput,
+        }
+    }
+
+    fn emit(&self, event: &str, attributes: loom_signal::AttributesBuilder) {
+        self.emitter.emit(
+            Signal::new()
+                .otype(SignalType::Event)
+                .name(format!("{}.{}", self.name, event))
+                .attributes(attributes.build())
+                .build(),
+        );
+    }
+}
+
+/// Timing of a finished run
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProgressSummary {
+    pub elapsed: Duration,
+    /// Items per second
+    pub throughput: f32,
+}
+
+/// A `{name}.progress` signal read back by a consumer.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProgressUpdate {
+    pub current: usize,
+    pub total: usize,
+    pub label: String,
+    pub ok: bool,
+}
+
+impl ProgressUpdate {
+    /// Parse a progress signal, or `None` for any other signal.
+    pub fn from_signal(signal: &Signal) -> Option<Self> {
+        if !signal.name().ends_with(".progress") {
+            return None;
+        }
+
+        let attrs = signal.attributes();
+        let int = |key: &str| attrs.get(key).and_then(|v| v.as_int()).unwrap_or(0) as usize;
+
+        Some(Self {
+            current: int("current"),
+            total: int("total"),
+            label: attrs
+                .get("label")
+                .and_then(|v| v.as_str())
+                .unwrap_or_default()
+                .to_string(),
+            ok: attrs.get("ok").and_then(|v| v.as_bool()).unwrap_or(false),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use loom_signal::consumers::MemoryEmitter;
+
+    #[test]
+    fn emits_start_progress_and_complete() {
+        let signals = MemoryEmitter::new();
+        let mut progress = ProgressReporter::start(&signals, "eval", 2);
+
+        progress.step("s1", true);
+        progress.step("s2", false);
+        progress.finish(Attributes::new().attr("correct", 1_i64).build());
+
+        let updates: Vec<_> = signals
+            .find_by_name("eval.progress")
+            .iter()
+            .filter_map(ProgressUpdate::from_signal)
+            .collect();
+        let complete = &signals.find_by_name("eval.complete")[0];
+
+        assert_eq!(signals.find_by_name("eval.start").len(), 1);
+        assert_eq!(updates.len(), 2);
+        assert_eq!(updates[1].current, 2);
+        assert_eq!(updates[1].label, "s2");
+        assert!(!updates[1].ok);
+        assert_eq!(
+            complete
+                .attributes()
+                .get("correct")
+                .and_then(|v| v.as_int()),
+            Some(1)
+        );
+        assert!(complete.attributes().exists("elapsed_ms"));
+    }
+
+    #[test]
+    fn ignores_other_signals() {
+        let signal = Signal::new().name("eval.start").build();
+
+        assert!(ProgressUpdate::from_signal(&signal).is_none());
+    }
+}
@@ -10,15 +10,24 @@
 //! | `start_time` | `String` | ISO-8601 timestamp of execution start |
 //! | `step` | `i64` | Processing step number in pipeline |
 //! | `text` | `String` | Input text that was processed |
+//! | `text_hash` | `String` | blake3 hash (hex) of the input text |
 //! | `inference_ms` | `i64` | Model inference time only (excludes overhead) |
 //! | `batch_count` | `i64` | Number of batches processed |
+//! | `guard` | `String` | Guard rule that accepted the result, if any |
+//!
+//! Prefer the typed accessors of [`LayerMeta`](loom_pipe::LayerMeta) for the
+//! well-known keys; see [`LayerMeta::compose`](loom_pipe::LayerMeta::compose)
+//! for how metadata merges across pipeline stages.
 //!
 //! # Example
 //!
 //! ```ignore
+//! use loom_runtime::LayerMeta;
+//!
 //! let result = layer.invoke(ctx)?;
-//! let elapsed = result.meta.get("elapsed_ms"); // i64 milliseconds
-//! let start = result.meta.get("start_time");   // ISO-8601 string
+//! let elapsed = result.meta.elapsed();              // Option<Duration>
+//! let step = result.meta.step();                    // Option<usize>
+//! let start = result.meta.typed::<String>("start_time"); // ISO-8601 string
 //! ```
 
 use std::collections::BTreeMap;
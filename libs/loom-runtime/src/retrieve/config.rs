@@ -0,0 +1,146 @@
+use loom_cortex::config::{CortexModelConfig, CortexSentenceEmbeddingsConfig};
+use loom_error::{Error, ErrorCode, Result};
+use serde::{Deserialize, Serialize};
+use serde_valid::Validate;
+
+use super::RetrieveLayer;
+
+/// Configuration for hybrid (vector + keyword) memory retrieval
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct RetrieveConfig {
+    /// Sentence embeddings model used to embed queries
+    #[serde(default = "RetrieveConfig::model")]
+    pub model: CortexModelConfig,
+
+    /// Number of memories returned when the caller does not ask for a count
+    #[serde(default = "RetrieveConfig::k")]
+    #[validate(minimum = 1)]
+    pub k: usize,
+
+    /// Upper bound for a caller supplied count
+    #[serde(default = "RetrieveConfig::max_k")]
+    #[validate(minimum = 1)]
+    pub max_k: usize,
+
+    /// Weight of the embedding cosine similarity in the final score
+    #[serde(default = "RetrieveConfig::vector_weight")]
+    #[validate(minimum = 0.0)]
+    #[validate(maximum = 1.0)]
+    pub vector_weight: f32,
+
+    /// Weight of the full text keyword rank in the final score
+    #[serde(default = "RetrieveConfig::keyword_weight")]
+    #[validate(minimum = 0.0)]
+    #[validate(maximum = 1.0)]
+    pub keyword_weight: f32,
+}
+
+impl RetrieveConfig {
+    fn model() -> CortexModelConfig {
+        CortexModelConfig::SentenceEmbeddings(CortexSentenceEmbeddingsConfig::default())
+    }
+
+    fn k() -> usize {
+        10
+    }
+
+    fn max_k() -> usize {
+        100
+    }
+
+    fn vector_weight() -> f32 {
+        0.7
+    }
+
+    fn keyword_weight() -> f32 {
+        0.3
+    }
+
+    /// Resolve a requested count, falling back to `k` and capping at `max_k`
+    pub fn k_or_default(&self, k: Option<usize>) -> usize {
+        k.unwrap_or(self.k).clamp(1, self.max_k)
+    }
+
+    /// Combine a vector similarity and a keyword rank into a single score
+    pub fn score(&self, similarity: f32, keyword: f32) -> f32 {
+        self.vector_weight * similarity + self.keyword_weight * keyword
+    }
+
+    /// Build a RetrieveLayer from this configuration
+    pub fn build(self) -> Result<RetrieveLayer> {
+        self.validate()
+            .map_err(|e| Error::builder().message(e.to_string()).build())?;
+
+        if self.k > self.max_k {
+            return Err(Error::builder()
+                .code(ErrorCode::BadArguments)
+                .message("k must not exceed max_k")
+                .build());
+        }
+
+        if !self.model.is_sentence_embeddings() {
+            return Err(Error::builder()
+                .code(ErrorCode::BadArguments)
+                .message("RetrieveLayer requires a SentenceEmbeddings model")
+                .build());
+        }
+
+        let model = self.model.clone().build()?;
+        Ok(RetrieveLayer::new(model, self))
+    }
+}
+
+impl Default for RetrieveConfig {
+    fn default() -> Self {
+        Self {
+            model: Self::model(),
+            k: Self::k(),
+            max_k: Self::max_k(),
+            vector_weight: Self::vector_weight(),
+            keyword_weight: Self::keyword_weight(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_config_uses_defaults() {
+        let config: RetrieveConfig = serde_json::from_str("{}").unwrap();
+
+        assert!(config.model.is_sentence_embeddings());
+        assert_eq!(config.k, 10);
+        assert_eq!(config.vector_weight, 0.7);
+        assert_eq!(config.keyword_weight, 0.3);
+    }
+
+    #[test]
+    fn k_is_defaulted_and_capped() {
+        let config = RetrieveConfig::default();
+
+        assert_eq!(config.k_or_default(None), 10);
+        assert_eq!(config.k_or_default(Some(0)), 1);
+        assert_eq!(config.k_or_default(Some(5000)), 100);
+    }
+
+    #[test]
+    fn score_weights_both_signals() {
+        let config = RetrieveConfig::default();
+
+        assert!((config.score(1.0, 0.0) - 0.7).abs() < 1e-6);
+        assert!((config.score(0.5, 1.0) - 0.65).abs() < 1e-6);
+    }
+
+    #[test]
+    fn build_rejects_k_above_max() {
+        let config = RetrieveConfig {
+            k: 200,
+            ..Default::default()
+        };
+
+        let err = config.build().err().unwrap();
+        assert_eq!(*err.code(), ErrorCode::BadArguments);
+    }
+}
@@ -0,0 +1,55 @@
+//! Memory retrieval: embeds a query for hybrid vector + keyword search and
+//! holds the weights used to rank the results.
+
+mod config;
+
+pub use config::*;
+
+use loom_cortex::CortexModel;
+use loom_error::Result;
+use loom_pipe::LayerResult;
+
+use crate::{Context, embed};
+
+/// Embeds retrieval queries with a sentence embeddings model.
+///
+/// The search itself runs in storage; the layer provides the query embedding
+/// and the ranking weights from its [`RetrieveConfig`].
+pub struct RetrieveLayer {
+    model: CortexModel,
+    config: RetrieveConfig,
+}
+
+impl RetrieveLayer {
+    pub(crate) fn new(model: CortexModel, config: RetrieveConfig) -> Self {
+        Self { model, config }
+    }
+
+    /// Get the configuration for this layer
+    pub fn config(&self) -> &RetrieveConfig {
+        &self.config
+    }
+
+    /// Embed a query
+    pub fn embed(&self, query: &str) -> Result<Vec<f32>> {
+        embed::embed(&self.model, "RetrieveLayer", query)
+    }
+
+    /// Invoke the retrieve layer directly with a context
+    pub fn invoke<Input>(&self, ctx: Context<Input>) -> Result<LayerResult<Vec<f32>>> {
+        Ok(embed::embedding_result(&ctx, self.embed(&ctx.text)?))
+    }
+}
+
+impl loom_pipe::Layer for RetrieveLayer {
+    type Input = Context<()>;
+    type Output = Vec<f32>;
+
+    fn process(&self, input: Self::Input) -> Result<LayerResult<Self::Output>> {
+        self.invoke(input)
+    }
+
+    fn name(&self) -> &'static str {
+        "retrieve"
+    }
+}
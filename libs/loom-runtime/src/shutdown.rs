@@ -0,0 +1,60 @@
+use std::sync::Mutex;
+
+use futures::future::BoxFuture;
+use loom_error::Result;
+
+use crate::Runtime;
+
+type Hook = Box<dyn for<'a> FnOnce(&'a Runtime) -> BoxFuture<'a, Result<()>> + Send>;
+
+/// Named teardown steps run by [`Runtime::shutdown`], in registration order.
+///
+/// Each hook runs at most once: shutting down takes them all, so a second
+/// call finds none left.
+#[derive(Default)]
+pub struct ShutdownHooks {
+    hooks: Mutex<Vec<(String, Hook)>>,
+}
+
+impl ShutdownHooks {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a hook to run after those already added
+    pub fn add<F>(&mut self, name: impl Into<String>, hook: F)
+    where
+        F: for<'a> FnOnce(&'a Runtime) -> BoxFuture<'a, Result<()>> + Send + 'static,
+    {
+        self.hooks
+            .get_mut()
+            .expect("shutdown hooks lock poisoned")
+            .push((name.into(), Box::new(hook)));
+    }
+
+    /// Names of the hooks not yet run, in order
+    pub fn names(&self) -> Vec<String> {
+        self.hooks
+            .lock()
+            .expect("shutdown hooks lock poisoned")
+            .iter()
+            .map(|(name, _)| name.clone())
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.hooks
+            .lock()
+            .expect("shutdown hooks lock poisoned")
+            .len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Remove every hook, leaving none for the next shutdown
+    pub(crate) fn take(&self) -> Vec<(String, Hook)> {
+        std::mem::take(&mut *self.hooks.lock().expect("shutdown hooks lock poisoned"))
+    }
+}
@@ -4,7 +4,7 @@ use loom_core::value::Value;
 
 #[derive(Default, Clone, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
 #[serde(transparent)]
-pub struct Attributes(BTreeMap<String, Value>);
+pub struct Attributes(pub(crate) BTreeMap<String, Value>);
 
 impl Attributes {
     pub fn new() -> AttributesBuilder {
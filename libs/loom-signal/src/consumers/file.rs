@@ -71,6 +71,12 @@ impl Emitter for FileEmitter {
             }
         }
     }
+
+    fn flush(&self) {
+        if let Ok(mut writer) = self.writer.lock() {
+            let _ = writer.flush();
+        }
+    }
 }
 
 #[cfg(test)]
@@ -1,11 +1,17 @@
-use std::sync::{Arc, Mutex};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, SystemTime};
 
-use crate::{Emitter, Signal};
+use loom_core::value::Value;
+
+use crate::filter::matches;
+use crate::{Emitter, Level, Signal, Type};
 
 /// An emitter that collects signals in memory.
 ///
 /// Useful for testing and inspection. Signals can be retrieved
-/// after emission for verification.
+/// after emission for verification, and queried by name, level, time and
+/// attributes.
 ///
 /// # Example
 /// ```ignore
@@ -16,19 +22,28 @@ use crate::{Emitter, Signal};
 ///
 /// assert_eq!(emitter.len(), 2);
 /// let signals = emitter.signals();
+///
+/// let failed = emitter
+///     .query()
+///     .name("eval.*")
+///     .level(Level::Error)
+///     .attr("layer", "score")
+///     .count();
 /// ```
 #[derive(Clone)]
 pub struct MemoryEmitter {
-    signals: Arc<Mutex<Vec<Signal>>>,
+    signals: Arc<Mutex<VecDeque<Signal>>>,
     capacity: Option<usize>,
+    retention: Option<Duration>,
 }
 
 impl MemoryEmitter {
     /// Create a new memory emitter with unlimited capacity.
     pub fn new() -> Self {
         Self {
-            signals: Arc::new(Mutex::new(Vec::new())),
+            signals: Arc::new(Mutex::new(VecDeque::new())),
             capacity: None,
+            retention: None,
         }
     }
 
@@ -39,14 +54,23 @@ impl MemoryEmitter {
         self
     }
 
+    /// Set how long signals are kept. Signals created longer ago than this
+    /// are removed as new ones are emitted or the signals are read.
+    pub fn with_retention(mut self, retention: Duration) -> Self {
+        self.retention = Some(retention);
+        self
+    }
+
     /// Get a copy of all collected signals.
     pub fn signals(&self) -> Vec<Signal> {
-        self.signals.lock().map(|s| s.clone()).unwrap_or_default()
+        self.retained()
+            .map(|s| s.iter().cloned().collect())
+            .unwrap_or_default()
     }
 
     /// Get the number of collected signals.
     pub fn len(&self) -> usize {
-        self.signals.lock().map(|s| s.len()).unwrap_or(0)
+        self.retained().map(|s| s.len()).unwrap_or(0)
     }
 
     /// Check if there are no signals.
@@ -63,7 +87,7 @@ impl MemoryEmitter {
 
     /// Get the last emitted signal, if any.
     pub fn last(&self) -> Option<Signal> {
-        self.signals.lock().ok().and_then(|s| s.last().cloned())
+        self.retained().and_then(|s| s.back().cloned())
     }
 
     /// Find signals by name.
@@ -73,6 +97,49 @@ impl MemoryEmitter {
             .filter(|s| s.name() == name)
             .collect()
     }
+
+    /// Query the collected signals, e.g. to assert on them in a test.
+    pub fn query(&self) -> SignalQuery {
+        SignalQuery {
+            signals: self.signals(),
+        }
+    }
+
+    /// Find signals whose name matches `glob`, e.g. `eval.*`.
+    pub fn by_name(&self, glob: &str) -> Vec<Signal> {
+        self.query().name(glob).collect()
+    }
+
+    /// Find signals at `level` or above.
+    pub fn by_level(&self, level: Level) -> Vec<Signal> {
+        self.query().level(level).collect()
+    }
+
+    /// Find signals created at or after `time`.
+    pub fn since(&self, time: SystemTime) -> Vec<Signal> {
+        self.query().since(time).collect()
+    }
+
+    /// Find signals with a `key` attribute for which `predicate` holds.
+    pub fn by_attr(&self, key: &str, predicate: impl Fn(&Value) -> bool) -> Vec<Signal> {
+        self.query().attr_matches(key, predicate).collect()
+    }
+
+    /// Lock the signals, first removing those older than the retention
+    fn retained(&self) -> Option<MutexGuard<'_, VecDeque<Signal>>> {
+        let mut signals = self.signals.lock().ok()?;
+
+        if let Some(cutoff) = self
+            .retention
+            .and_then(|retention| SystemTime::now().checked_sub(retention))
+        {
+            while signals.front().is_some_and(|s| s.created_at() < cutoff) {
+                signals.pop_front();
+            }
+        }
+
+        Some(signals)
+    }
 }
 
 impl Default for MemoryEmitter {
@@ -83,17 +150,92 @@ impl Default for MemoryEmitter {
 
 impl Emitter for MemoryEmitter {
     fn emit(&self, signal: Signal) {
-        if let Ok(mut signals) = self.signals.lock() {
+        if let Some(mut signals) = self.retained() {
             if let Some(cap) = self.capacity {
                 if signals.len() >= cap {
-                    signals.remove(0); // Ring buffer behavior
+                    signals.pop_front(); // Ring buffer behavior
                 }
             }
-            signals.push(signal);
+            signals.push_back(signal);
         }
     }
 }
 
+/// Signals of a [`MemoryEmitter`] narrowed down by each filter applied, in
+/// the order they were emitted.
+///
+/// # Example
+/// ```ignore
+/// let batches = emitter
+///     .query()
+///     .name("eval.batch")
+///     .attr_matches("duration_ms", |v| v.as_int().is_some_and(|ms| ms > 1_000))
+///     .collect();
+/// ```
+pub struct SignalQuery {
+    signals: Vec<Signal>,
+}
+
+impl SignalQuery {
+    /// Keep signals whose name matches `glob`; `*` matches any run of
+    /// characters and `?` any one character.
+    pub fn name(self, glob: &str) -> Self {
+        self.filter(|s| matches(glob, s.name()))
+    }
+
+    /// Keep signals at `level` or above.
+    pub fn level(self, level: Level) -> Self {
+        self.filter(|s| s.level() as u8 >= level as u8)
+    }
+
+    /// Keep signals of type `otype`.
+    pub fn otype(self, otype: Type) -> Self {
+        self.filter(|s| s.otype() == otype)
+    }
+
+    /// Keep signals created at or after `time`.
+    pub fn since(self, time: SystemTime) -> Self {
+        self.filter(|s| s.created_at() >= time)
+    }
+
+    /// Keep signals whose `key` attribute equals `value`.
+    pub fn attr(self, key: &str, value: impl Into<Value>) -> Self {
+        let value = value.into();
+        self.attr_matches(key, |v| *v == value)
+    }
+
+    /// Keep signals with a `key` attribute for which `predicate` holds.
+    pub fn attr_matches(self, key: &str, predicate: impl Fn(&Value) -> bool) -> Self {
+        self.filter(|s| s.attributes().get(key).is_some_and(&predicate))
+    }
+
+    /// Keep signals for which `predicate` holds.
+    pub fn filter(mut self, predicate: impl Fn(&Signal) -> bool) -> Self {
+        self.signals.retain(predicate);
+        self
+    }
+
+    pub fn count(&self) -> usize {
+        self.signals.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.signals.is_empty()
+    }
+
+    pub fn first(&self) -> Option<&Signal> {
+        self.signals.first()
+    }
+
+    pub fn last(&self) -> Option<&Signal> {
+        self.signals.last()
+    }
+
+    pub fn collect(self) -> Vec<Signal> {
+        self.signals
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -191,4 +333,91 @@ mod tests {
         assert_eq!(emitter1.len(), 1);
         assert_eq!(emitter2.len(), 1);
     }
+
+    #[test]
+    fn test_memory_emitter_retention() {
+        let emitter = MemoryEmitter::new().with_retention(Duration::from_secs(60));
+
+        emitter.emit(Signal::new().name("stale").build());
+
+        // Backdate the first signal past the retention
+        if let Ok(mut signals) = emitter.signals.lock() {
+            signals[0].created_at = SystemTime::now() - Duration::from_secs(120);
+        }
+
+        emitter.emit(Signal::new().name("fresh").build());
+
+        let signals = emitter.signals();
+        assert_eq!(signals.len(), 1);
+        assert_eq!(signals[0].name(), "fresh");
+    }
+
+    #[test]
+    fn test_memory_emitter_retention_on_read() {
+        let emitter = MemoryEmitter::new().with_retention(Duration::from_secs(60));
+
+        emitter.emit(Signal::new().name("stale").build());
+
+        if let Ok(mut signals) = emitter.signals.lock() {
+            signals[0].created_at = SystemTime::now() - Duration::from_secs(120);
+        }
+
+        assert!(emitter.is_empty());
+        assert!(emitter.last().is_none());
+        assert!(emitter.query().is_empty());
+    }
+
+    #[test]
+    fn test_memory_emitter_query() {
+        let emitter = MemoryEmitter::new();
+        let start = SystemTime::now();
+
+        emitter.emit(
+            Signal::new()
+                .name("eval.batch")
+                .level(Level::Info)
+                .attr("size", 16i64)
+                .build(),
+        );
+        emitter.emit(
+            Signal::new()
+                .name("eval.batch_error")
+                .level(Level::Error)
+                .attr("size", 16i64)
+                .build(),
+        );
+        emitter.emit(
+            Signal::new()
+                .otype(Type::Span)
+                .name("score.invoke")
+                .level(Level::Warn)
+                .build(),
+        );
+
+        assert_eq!(emitter.by_name("eval.*").len(), 2);
+        assert_eq!(emitter.by_name("eval.batch").len(), 1);
+        assert_eq!(emitter.by_level(Level::Warn).len(), 2);
+        assert_eq!(emitter.since(start).len(), 3);
+        assert!(
+            emitter
+                .since(SystemTime::now() + Duration::from_secs(1))
+                .is_empty()
+        );
+        assert_eq!(
+            emitter
+                .by_attr("size", |v| v.as_int().is_some_and(|size| size > 8))
+                .len(),
+            2
+        );
+
+        let query = emitter
+            .query()
+            .name("eval.*")
+            .level(Level::Error)
+            .attr("size", 16i64);
+        assert_eq!(query.count(), 1);
+        assert_eq!(query.first().unwrap().name(), "eval.batch_error");
+        assert_eq!(emitter.query().otype(Type::Span).count(), 1);
+        assert!(emitter.query().attr("size", 32i64).is_empty());
+    }
 }
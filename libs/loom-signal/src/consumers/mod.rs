@@ -1,7 +1,16 @@
 mod file;
 mod memory;
+#[cfg(feature = "otlp")]
+mod otlp;
 mod stdout;
+#[cfg(feature = "tracing")]
+mod tracing;
 
 pub use file::*;
 pub use memory::*;
+#[cfg(feature = "otlp")]
+pub use otlp::*;
 pub use stdout::*;
+// `self::` as the `tracing` crate shares the module's name
+#[cfg(feature = "tracing")]
+pub use self::tracing::*;
@@ -0,0 +1,541 @@
+use std::hash::{DefaultHasher, Hasher};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError, Sender, SyncSender, TrySendError};
+use std::sync::{Mutex, OnceLock};
+use std::thread::JoinHandle;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use loom_core::value::{Number, Value};
+use serde_json::json;
+
+use crate::span::random_id;
+use crate::{Emitter, Level, Signal, Type};
+
+/// An emitter that exports signals to an OpenTelemetry collector over
+/// OTLP/HTTP with JSON encoding (`otlp` feature).
+///
+/// `Span` signals become OTLP spans, ending when the signal was emitted and
+/// starting `duration_ms` before; `Error` spans get an error status. Every
+/// other signal becomes a log record with the signal name as its body.
+/// Attributes carry over as-is.
+///
+/// Signals are batched and posted to `<endpoint>/v1/traces` and
+/// `<endpoint>/v1/logs` from a background thread, every `batch_size` signals
+/// or `interval`, on [`flush`](Emitter::flush), and when the emitter is
+/// dropped. Export failures are dropped like any other emitter's. Signals
+/// emitted while `queue_size` others wait for a slow or unreachable
+/// collector are dropped too, and counted by [`dropped`](OtlpEmitter::dropped).
+///
+/// # Example
+/// ```ignore
+/// let emitter = OtlpEmitter::new("http://tempo:4318")
+///     .with_service_name("merc-worker")
+///     .with_level(Level::Debug);
+///
+/// emitter.emit(span.finish());
+/// ```
+pub struct OtlpEmitter {
+    exporter: OtlpExporter,
+    min_level: Level,
+    batch_size: usize,
+    queue_size: usize,
+    interval: Duration,
+    worker: OnceLock<Worker>,
+    dropped: AtomicU64,
+}
+
+struct Worker {
+    sender: Mutex<SyncSender<Message>>,
+    handle: Mutex<Option<JoinHandle<()>>>,
+}
+
+enum Message {
+    Signal(Signal),
+    Flush(Sender<()>),
+}
+
+impl OtlpEmitter {
+    /// Create an emitter exporting to the collector at `endpoint`, e.g.
+    /// `http://localhost:4318`
+    pub fn new(endpoint: impl Into<String>) -> Self {
+        Self {
+            exporter: OtlpExporter {
+                endpoint: endpoint.into().trim_end_matches('/').to_string(),
+                service_name: "loom".to_string(),
+                headers: Vec::new(),
+                timeout: Duration::from_secs(10),
+            },
+            min_level: Level::Trace,
+            batch_size: 512,
+            queue_size: 4_096,
+            interval: Duration::from_secs(5),
+            worker: OnceLock::new(),
+            dropped: AtomicU64::new(0),
+        }
+    }
+
+    /// Set the `service.name` resource attribute (default: `loom`).
+    pub fn with_service_name(mut self, name: impl Into<String>) -> Self {
+        self.exporter.service_name = name.into();
+        self
+    }
+
+    /// Send a header with every export, e.g. an auth token.
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.exporter.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Set the minimum log level to export.
+    pub fn with_level(mut self, level: Level) -> Self {
+        self.min_level = level;
+        self
+    }
+
+    /// Export once this many signals are buffered (default: 512).
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Queue at most this many signals for the background exporter, dropping
+    /// the rest while it is busy (default: 4096).
+    pub fn with_queue_size(mut self, queue_size: usize) -> Self {
+        self.queue_size = queue_size.max(1);
+        self
+    }
+
+    /// Export buffered signals at least this often (default: 5s).
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Give up on an export request after `timeout` (default: 10s).
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.exporter.timeout = timeout;
+        self
+    }
+
+    /// Number of signals dropped because the exporter's queue was full
+    pub fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+
+    fn should_emit(&self, signal: &Signal) -> bool {
+        signal.level() as u8 >= self.min_level as u8
+    }
+
+    /// The background exporter, started on the first signal
+    fn worker(&self) -> &Worker {
+        self.worker.get_or_init(|| {
+            let (sender, receiver) = mpsc::sync_channel(self.queue_size);
+            let exporter = self.exporter.clone();
+            let batch_size = self.batch_size;
+            let interval = self.interval;
+
+            let handle = std::thread::spawn(move || {
+                let mut batch = Vec::new();
+
+                loop {
+                    match receiver.recv_timeout(interval) {
+                        Ok(Message::Signal(signal)) => {
+                            batch.push(signal);
+
+                            if batch.len() >= batch_size {
+                                exporter.export(std::mem::take(&mut batch));
+                            }
+                        }
+                        Ok(Message::Flush(done)) => {
+                            exporter.export(std::mem::take(&mut batch));
+                            let _ = done.send(());
+                        }
+                        Err(RecvTimeoutError::Timeout) => {
+                            exporter.export(std::mem::take(&mut batch));
+                        }
+                        Err(RecvTimeoutError::Disconnected) => {
+                            exporter.export(std::mem::take(&mut batch));
+                            return;
+                        }
+                    }
+                }
+            });
+
+            Worker {
+                sender: Mutex::new(sender),
+                handle: Mutex::new(Some(handle)),
+            }
+        })
+    }
+}
+
+impl Emitter for OtlpEmitter {
+    fn emit(&self, signal: Signal) {
+        if !self.should_emit(&signal) {
+            return;
+        }
+
+        if let Ok(sender) = self.worker().sender.lock()
+            && let Err(TrySendError::Full(_)) = sender.try_send(Message::Signal(signal))
+        {
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    fn flush(&self) {
+        let Some(worker) = self.worker.get() else {
+            return;
+        };
+
+        let (done, wait) = mpsc::channel();
+
+        if let Ok(sender) = worker.sender.lock()
+            && sender.send(Message::Flush(done)).is_ok()
+        {
+            let _ = wait.recv();
+        }
+    }
+}
+
+impl Drop for OtlpEmitter {
+    fn drop(&mut self) {
+        let Some(worker) = self.worker.take() else {
+            return;
+        };
+
+        // Hanging up makes the worker export what's left and exit
+        drop(worker.sender);
+
+        if let Ok(mut handle) = worker.handle.lock()
+            && let Some(handle) = handle.take()
+        {
+            let _ = handle.join();
+        }
+    }
+}
+
+/// Posts batches of signals to a collector
+#[derive(Clone)]
+struct OtlpExporter {
+    endpoint: String,
+    service_name: String,
+    headers: Vec<(String, String)>,
+    timeout: Duration,
+}
+
+impl OtlpExporter {
+    fn export(&self, signals: Vec<Signal>) {
+        let (spans, logs): (Vec<_>, Vec<_>) = signals
+            .into_iter()
+            .partition(|signal| signal.otype() == Type::Span);
+
+        if !spans.is_empty() {
+            self.post("/v1/traces", self.traces(&spans));
+        }
+
+        if !logs.is_empty() {
+            self.post("/v1/logs", self.logs(&logs));
+        }
+    }
+
+    fn post(&self, path: &str, body: serde_json::Value) {
+        let mut request = ureq::post(&format!("{}{}", self.endpoint, path)).timeout(self.timeout);
+
+        for (name, value) in &self.headers {
+            request = request.set(name, value);
+        }
+
+        let _ = request.send_json(body);
+    }
+
+    fn resource(&self) -> serde_json::Value {
+        json!({
+            "attributes": [{
+                "key": "service.name",
+                "value": { "stringValue": self.service_name },
+            }],
+        })
+    }
+
+    /// `ExportTraceServiceRequest` of `signals`
+    fn traces(&self, signals: &[Signal]) -> serde_json::Value {
+        json!({
+            "resourceSpans": [{
+                "resource": self.resource(),
+                "scopeSpans": [{
+                    "scope": { "name": env!("CARGO_PKG_NAME") },
+                    "spans": signals.iter().map(span).collect::<Vec<_>>(),
+                }],
+            }],
+        })
+    }
+
+    /// `ExportLogsServiceRequest` of `signals`
+    fn logs(&self, signals: &[Signal]) -> serde_json::Value {
+        json!({
+            "resourceLogs": [{
+                "resource": self.resource(),
+                "scopeLogs": [{
+                    "scope": { "name": env!("CARGO_PKG_NAME") },
+                    "logRecords": signals.iter().map(log_record).collect::<Vec<_>>(),
+                }],
+            }],
+        })
+    }
+}
+
+/// OTLP span of a `Span` signal
+fn span(signal: &Signal) -> serde_json::Value {
+    let end = unix_nanos(signal.created_at());
+    let duration_ms = signal
+        .attributes()
+        .get("duration_ms")
+        .and_then(|value| value.as_int())
+        .unwrap_or(0)
+        .max(0) as u128;
+
+    let status = match signal.level() {
+        Level::Error => json!({
+            "code": 2,
+            "message": signal.attributes().get("error").map(|e| e.to_string()).unwrap_or_default(),
+        }),
+        _ => json!({}),
+    };
+
+    let mut span = json!({
+        "traceId": id(signal, "trace_id", 2),
+        "spanId": id(signal, "span_id", 1),
+        "name": signal.name(),
+        "kind": 1,
+        "startTimeUnixNano": end.saturating_sub(duration_ms * 1_000_000).to_string(),
+        "endTimeUnixNano": end.to_string(),
+        "attributes": attributes(signal),
+        "status": status,
+    });
+
+    if signal.attributes().exists("parent_span_id") {
+        span["parentSpanId"] = json!(id(signal, "parent_span_id", 1));
+    }
+
+    span
+}
+
+/// OTLP id of `words` 64-bit words from the `key` attribute of a signal,
+/// e.g. as set by a `SpanGuard`. Ids that aren't already hex of that length,
+/// like a request's correlation id, are hashed so every span of the trace
+/// still shares one; without the attribute the id is random.
+fn id(signal: &Signal, key: &str, words: usize) -> String {
+    let Some(value) = signal.attributes().get(key) else {
+        return random_id(words);
+    };
+
+    let value = value.to_string();
+
+    if value.len() == words * 16 && value.chars().all(|c| c.is_ascii_hexdigit()) {
+        return value.to_lowercase();
+    }
+
+    (0..words)
+        .map(|word| {
+            let mut hasher = DefaultHasher::new();
+            hasher.write_usize(word);
+            hasher.write(value.as_bytes());
+            format!("{:016x}", hasher.finish())
+        })
+        .collect()
+}
+
+/// OTLP log record of an `Event`, `Log` or `Metric` signal
+fn log_record(signal: &Signal) -> serde_json::Value {
+    let time = unix_nanos(signal.created_at()).to_string();
+    let mut attributes = attributes(signal);
+    attributes.push(key_value(
+        "signal.type",
+        &Value::from(signal.otype().as_str()),
+    ));
+
+    json!({
+        "timeUnixNano": time,
+        "observedTimeUnixNano": time,
+        "severityNumber": severity_number(signal.level()),
+        "severityText": signal.level().as_str().to_uppercase(),
+        "body": { "stringValue": signal.name() },
+        "attributes": attributes,
+    })
+}
+
+fn attributes(signal: &Signal) -> Vec<serde_json::Value> {
+    signal
+        .attributes()
+        .iter()
+        .map(|(key, value)| key_value(key, value))
+        .collect()
+}
+
+fn key_value(key: &str, value: &Value) -> serde_json::Value {
+    json!({ "key": key, "value": any_value(value) })
+}
+
+/// OTLP `AnyValue` of `value`; 64-bit ints are strings in OTLP JSON
+fn any_value(value: &Value) -> serde_json::Value {
+    match value {
+        Value::Null => json!({}),
+        Value::Bool(v) => json!({ "boolValue": v }),
+        Value::Number(Number::Int(v)) => json!({ "intValue": v.to_string() }),
+        Value::Number(Number::Float(v)) => json!({ "doubleValue": v }),
+        Value::String(v) => json!({ "stringValue": v }),
+        Value::Array(values) => json!({
+            "arrayValue": { "values": values.iter().map(any_value).collect::<Vec<_>>() },
+        }),
+        Value::Object(object) => json!({
+            "kvlistValue": {
+                "values": object
+                    .iter()
+                    .map(|(key, value)| key_value(key, value))
+                    .collect::<Vec<_>>(),
+            },
+        }),
+    }
+}
+
+/// OTLP `SeverityNumber` of the first severity in `level`'s range
+fn severity_number(level: Level) -> u8 {
+    match level {
+        Level::Trace => 1,
+        Level::Debug => 5,
+        Level::Info => 9,
+        Level::Warn => 13,
+        Level::Error => 17,
+    }
+}
+
+fn unix_nanos(time: SystemTime) -> u128 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|since| since.as_nanos())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_span() {
+        let signal = Signal::new()
+            .otype(Type::Span)
+            .level(Level::Error)
+            .name("score.batch")
+            .attr("duration_ms", 250i64)
+            .attr("error", "timeout")
+            .build();
+        let span = span(&signal);
+
+        let end: u128 = span["endTimeUnixNano"].as_str().unwrap().parse().unwrap();
+        let start: u128 = span["startTimeUnixNano"].as_str().unwrap().parse().unwrap();
+        assert_eq!(end - start, 250_000_000);
+        assert_eq!(span["name"], "score.batch");
+        assert_eq!(span["traceId"].as_str().unwrap().len(), 32);
+        assert_eq!(span["spanId"].as_str().unwrap().len(), 16);
+        assert_eq!(span["status"]["code"], 2);
+        assert_eq!(span["status"]["message"], "timeout");
+    }
+
+    #[test]
+    fn test_span_ids() {
+        let signal = |parent: Option<&str>| {
+            let mut builder = Signal::new()
+                .otype(Type::Span)
+                .name("score.predict")
+                .attr("trace_id", "request-1")
+                .attr("span_id", "00f067aa0ba902b7");
+
+            if let Some(parent) = parent {
+                builder = builder.attr("parent_span_id", parent);
+            }
+
+            builder.build()
+        };
+
+        let root = span(&signal(None));
+        let child = span(&signal(Some("53995c3f42cd8ad8")));
+
+        assert_eq!(root["traceId"].as_str().unwrap().len(), 32);
+        assert_eq!(root["traceId"], child["traceId"]);
+        assert_eq!(root["spanId"], "00f067aa0ba902b7");
+        assert!(root.get("parentSpanId").is_none());
+        assert_eq!(child["parentSpanId"], "53995c3f42cd8ad8");
+    }
+
+    #[test]
+    fn test_log_record() {
+        let signal = Signal::new()
+            .level(Level::Warn)
+            .name("eval.progress")
+            .attr("done", 10i64)
+            .attr("ratio", 0.5)
+            .build();
+        let record = log_record(&signal);
+
+        assert_eq!(record["severityNumber"], 13);
+        assert_eq!(record["severityText"], "WARN");
+        assert_eq!(record["body"]["stringValue"], "eval.progress");
+
+        let attributes = record["attributes"].as_array().unwrap();
+        assert!(attributes.contains(&json!({ "key": "done", "value": { "intValue": "10" } })));
+        assert!(attributes.contains(&json!({ "key": "ratio", "value": { "doubleValue": 0.5 } })));
+        assert!(
+            attributes
+                .contains(&json!({ "key": "signal.type", "value": { "stringValue": "event" } }))
+        );
+    }
+
+    #[test]
+    fn test_any_value_nested() {
+        let value = Value::from(vec![Value::from("a"), Value::from(true)]);
+
+        assert_eq!(
+            any_value(&value),
+            json!({ "arrayValue": { "values": [{ "stringValue": "a" }, { "boolValue": true }] } })
+        );
+    }
+
+    #[test]
+    fn test_full_queue_drops_signals() {
+        // Accepts connections but never answers, so every export times out
+        let collector = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let emitter = OtlpEmitter::new(format!("http://{}", collector.local_addr().unwrap()))
+            .with_batch_size(1)
+            .with_queue_size(1)
+            .with_timeout(Duration::from_millis(200));
+
+        for _ in 0..10 {
+            emitter.emit(Signal::new().name("eval.progress").build());
+        }
+
+        assert!(emitter.dropped() >= 5);
+    }
+
+    #[test]
+    fn test_export_batches_by_type() {
+        let emitter = OtlpEmitter::new("http://localhost:4318/").with_service_name("merc");
+        let exporter = &emitter.exporter;
+        let signals = [
+            Signal::new().otype(Type::Span).name("a").build(),
+            Signal::new().otype(Type::Span).name("b").build(),
+        ];
+        let traces = exporter.traces(&signals);
+
+        assert_eq!(exporter.endpoint, "http://localhost:4318");
+        assert_eq!(
+            traces["resourceSpans"][0]["resource"]["attributes"][0]["value"]["stringValue"],
+            "merc"
+        );
+        assert_eq!(
+            traces["resourceSpans"][0]["scopeSpans"][0]["spans"]
+                .as_array()
+                .unwrap()
+                .len(),
+            2
+        );
+    }
+}
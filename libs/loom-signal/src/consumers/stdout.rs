@@ -82,6 +82,10 @@ impl Emitter for StdoutEmitter {
 
         let _ = writeln!(io::stdout(), "{}", output);
     }
+
+    fn flush(&self) {
+        let _ = io::stdout().flush();
+    }
 }
 
 #[cfg(test)]
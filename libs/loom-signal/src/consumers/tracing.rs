@@ -0,0 +1,243 @@
+use std::collections::BTreeMap;
+
+use loom_core::value::Value;
+use tracing::field::{Field, Visit};
+use tracing_subscriber::layer::Context;
+
+use crate::{Attributes, Emitter, Level, Signal, Type};
+
+/// Target of the `tracing` events a [`TracingEmitter`] records, which a
+/// [`SignalLayer`] skips so signals don't loop between the two
+pub const TRACING_TARGET: &str = "loom_signal";
+
+/// An emitter that forwards signals to the `tracing` ecosystem as events
+/// (`tracing` feature), so an application with a `tracing` subscriber gets
+/// Loom's telemetry in the same output as its own.
+///
+/// Events have the signal's level, the target `loom_signal` and the signal
+/// name as their message, with `otype` and `attributes` fields.
+///
+/// # Example
+/// ```ignore
+/// tracing_subscriber::fmt().init();
+///
+/// let runtime = Runtime::new()
+///     .emitter(TracingEmitter::new().with_level(Level::Info))
+///     .build();
+/// ```
+pub struct TracingEmitter {
+    min_level: Level,
+}
+
+impl TracingEmitter {
+    /// Create a new tracing emitter, forwarding signals of every level.
+    pub fn new() -> Self {
+        Self {
+            min_level: Level::Trace,
+        }
+    }
+
+    /// Set the minimum log level to forward.
+    pub fn with_level(mut self, level: Level) -> Self {
+        self.min_level = level;
+        self
+    }
+}
+
+impl Default for TracingEmitter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Emitter for TracingEmitter {
+    fn emit(&self, signal: Signal) {
+        if (signal.level() as u8) < self.min_level as u8 {
+            return;
+        }
+
+        // `tracing` levels must be known at compile time
+        macro_rules! forward {
+            ($level:expr) => {
+                tracing::event!(
+                    target: TRACING_TARGET,
+                    $level,
+                    otype = signal.otype().as_str(),
+                    attributes = ?signal.attributes(),
+                    "{}",
+                    signal.name()
+                )
+            };
+        }
+
+        match signal.level() {
+            Level::Trace => forward!(tracing::Level::TRACE),
+            Level::Debug => forward!(tracing::Level::DEBUG),
+            Level::Info => forward!(tracing::Level::INFO),
+            Level::Warn => forward!(tracing::Level::WARN),
+            Level::Error => forward!(tracing::Level::ERROR),
+        }
+    }
+}
+
+/// A `tracing-subscriber` layer that turns `tracing` events into `Log`
+/// signals emitted through `E` (`tracing` feature), so code instrumented
+/// with `tracing` shows up next to Loom's own signals.
+///
+/// A signal is named after the event's message, or the event's callsite when
+/// it has none, with the other fields and the event target as attributes.
+/// Events recorded by a [`TracingEmitter`] are skipped.
+///
+/// # Example
+/// ```ignore
+/// tracing_subscriber::registry()
+///     .with(SignalLayer::new(FileEmitter::new("signals.jsonl")?))
+///     .init();
+/// ```
+pub struct SignalLayer<E> {
+    emitter: E,
+}
+
+impl<E: Emitter> SignalLayer<E> {
+    pub fn new(emitter: E) -> Self {
+        Self { emitter }
+    }
+}
+
+impl<E, S> tracing_subscriber::Layer<S> for SignalLayer<E>
+where
+    E: Emitter + 'static,
+    S: tracing::Subscriber,
+{
+    fn on_event(&self, event: &tracing::Event<'_>, _: Context<'_, S>) {
+        let metadata = event.metadata();
+
+        if metadata.target() == TRACING_TARGET {
+            return;
+        }
+
+        let mut fields = Fields::default();
+        event.record(&mut fields);
+        fields
+            .attributes
+            .insert("target".to_string(), Value::from(metadata.target()));
+
+        self.emitter.emit(
+            Signal::new()
+                .otype(Type::Log)
+                .level(level(metadata.level()))
+                .name(
+                    fields
+                        .message
+                        .unwrap_or_else(|| metadata.name().to_string()),
+                )
+                .attributes(Attributes(fields.attributes))
+                .build(),
+        );
+    }
+}
+
+fn level(level: &tracing::Level) -> Level {
+    match *level {
+        tracing::Level::TRACE => Level::Trace,
+        tracing::Level::DEBUG => Level::Debug,
+        tracing::Level::INFO => Level::Info,
+        tracing::Level::WARN => Level::Warn,
+        tracing::Level::ERROR => Level::Error,
+    }
+}
+
+/// Fields of a `tracing` event, split into its message and the rest
+#[derive(Default)]
+struct Fields {
+    message: Option<String>,
+    attributes: BTreeMap<String, Value>,
+}
+
+impl Fields {
+    fn record(&mut self, field: &Field, value: Value) {
+        self.attributes.insert(field.name().to_string(), value);
+    }
+}
+
+impl Visit for Fields {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        let value = format!("{:?}", value);
+
+        match field.name() {
+            "message" => self.message = Some(value),
+            _ => self.record(field, Value::from(value)),
+        }
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        match field.name() {
+            "message" => self.message = Some(value.to_string()),
+            _ => self.record(field, Value::from(value)),
+        }
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.record(field, Value::from(value));
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.record(field, Value::from(value));
+    }
+
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.record(field, Value::from(value));
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.record(field, Value::from(value));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tracing_subscriber::layer::SubscriberExt;
+
+    use super::*;
+    use crate::consumers::MemoryEmitter;
+
+    #[test]
+    fn test_signal_layer() {
+        let memory = MemoryEmitter::new();
+        let subscriber = tracing_subscriber::registry().with(SignalLayer::new(memory.clone()));
+
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::warn!(target: "app", user = "alice", retries = 3, "login.failed");
+        });
+
+        let signals = memory.signals();
+        assert_eq!(signals.len(), 1);
+        assert_eq!(signals[0].name(), "login.failed");
+        assert_eq!(signals[0].otype(), Type::Log);
+        assert_eq!(signals[0].level(), Level::Warn);
+        assert_eq!(
+            signals[0].attributes().get("user"),
+            Some(&Value::from("alice"))
+        );
+        assert_eq!(
+            signals[0].attributes().get("retries"),
+            Some(&Value::from(3i64))
+        );
+        assert_eq!(
+            signals[0].attributes().get("target"),
+            Some(&Value::from("app"))
+        );
+    }
+
+    #[test]
+    fn test_tracing_emitter_is_not_looped_back() {
+        let memory = MemoryEmitter::new();
+        let subscriber = tracing_subscriber::registry().with(SignalLayer::new(memory.clone()));
+
+        tracing::subscriber::with_default(subscriber, || {
+            TracingEmitter::new().emit(Signal::new().name("eval.batch").build());
+        });
+
+        assert!(memory.is_empty());
+    }
+}
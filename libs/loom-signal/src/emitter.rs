@@ -53,6 +53,12 @@ impl Emitter for SignalBroadcaster {
             emitter.emit(signal.clone());
         }
     }
+
+    fn flush(&self) {
+        for emitter in &self.emitters {
+            emitter.flush();
+        }
+    }
 }
 
 /// A no-op emitter that discards all signals.
@@ -128,6 +134,29 @@ mod tests {
         assert_eq!(*count2.lock().unwrap(), 1);
     }
 
+    struct FlushingEmitter(Arc<Mutex<usize>>);
+
+    impl Emitter for FlushingEmitter {
+        fn emit(&self, _signal: Signal) {}
+
+        fn flush(&self) {
+            *self.0.lock().unwrap() += 1;
+        }
+    }
+
+    #[test]
+    fn test_broadcaster_flushes_all() {
+        let flushes = Arc::new(Mutex::new(0));
+        let broadcaster = SignalBroadcaster::new()
+            .add(FlushingEmitter(flushes.clone()))
+            .add(NoopEmitter)
+            .add(FlushingEmitter(flushes.clone()));
+
+        broadcaster.flush();
+
+        assert_eq!(*flushes.lock().unwrap(), 2);
+    }
+
     #[test]
     fn test_noop_emitter() {
         let emitter = NoopEmitter;
@@ -0,0 +1,220 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::{Emitter, Level, Signal};
+
+/// An emitter that drops signals before passing the rest on: those below a
+/// minimum level, those whose name doesn't match the include globs or does
+/// match an exclude glob, and all but a sampled fraction of those matching a
+/// sample glob.
+///
+/// Globs match the whole signal name; `*` matches any run of characters and
+/// `?` any one character. Sampling is even rather than random: a ratio of
+/// `0.01` keeps every hundredth matching signal.
+///
+/// # Example
+/// ```ignore
+/// let emitter = FilterEmitter::new(broadcaster)
+///     .with_level(Level::Info)
+///     .with_exclude("io.*")
+///     .with_sample("eval.progress", 0.01);
+/// ```
+pub struct FilterEmitter<E> {
+    inner: E,
+    min_level: Level,
+    include: Vec<String>,
+    exclude: Vec<String>,
+    samples: Vec<Sample>,
+}
+
+/// Signals matching `glob`, of which `ratio` are kept
+struct Sample {
+    glob: String,
+    ratio: f64,
+    seen: AtomicU64,
+}
+
+impl<E: Emitter> FilterEmitter<E> {
+    /// Wrap `inner`, passing on every signal until rules are added
+    pub fn new(inner: E) -> Self {
+        Self {
+            inner,
+            min_level: Level::Trace,
+            include: Vec::new(),
+            exclude: Vec::new(),
+            samples: Vec::new(),
+        }
+    }
+
+    /// Set the minimum level to pass on.
+    pub fn with_level(mut self, level: Level) -> Self {
+        self.min_level = level;
+        self
+    }
+
+    /// Pass on only signals matching `glob` or another include glob.
+    pub fn with_include(mut self, glob: impl Into<String>) -> Self {
+        self.include.push(glob.into());
+        self
+    }
+
+    /// Drop signals matching `glob`.
+    pub fn with_exclude(mut self, glob: impl Into<String>) -> Self {
+        self.exclude.push(glob.into());
+        self
+    }
+
+    /// Pass on `ratio` (clamped to 0..=1) of the signals matching `glob`.
+    /// A signal is sampled by the first sample glob it matches.
+    pub fn with_sample(mut self, glob: impl Into<String>, ratio: f64) -> Self {
+        self.samples.push(Sample {
+            glob: glob.into(),
+            ratio: ratio.clamp(0.0, 1.0),
+            seen: AtomicU64::new(0),
+        });
+        self
+    }
+
+    fn should_emit(&self, signal: &Signal) -> bool {
+        let name = signal.name();
+
+        if (signal.level() as u8) < self.min_level as u8 {
+            return false;
+        }
+
+        if !self.include.is_empty() && !self.include.iter().any(|glob| matches(glob, name)) {
+            return false;
+        }
+
+        if self.exclude.iter().any(|glob| matches(glob, name)) {
+            return false;
+        }
+
+        match self
+            .samples
+            .iter()
+            .find(|sample| matches(&sample.glob, name))
+        {
+            Some(sample) => sample.keep(),
+            None => true,
+        }
+    }
+}
+
+impl Sample {
+    /// Whether to keep the next matching signal: the `n`th is kept when
+    /// `n * ratio` reaches a new whole number
+    fn keep(&self) -> bool {
+        let seen = self.seen.fetch_add(1, Ordering::Relaxed) as f64;
+        ((seen + 1.0) * self.ratio).floor() > (seen * self.ratio).floor()
+    }
+}
+
+impl<E: Emitter> Emitter for FilterEmitter<E> {
+    fn emit(&self, signal: Signal) {
+        if self.should_emit(&signal) {
+            self.inner.emit(signal);
+        }
+    }
+
+    fn flush(&self) {
+        self.inner.flush();
+    }
+}
+
+/// Whether `glob` matches all of `name`
+pub(crate) fn matches(glob: &str, name: &str) -> bool {
+    let glob: Vec<char> = glob.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    let (mut g, mut n) = (0, 0);
+    // Position after the last `*` and the name position it matched up to
+    let mut backtrack = None;
+
+    while n < name.len() {
+        match glob.get(g) {
+            Some('*') => {
+                g += 1;
+                backtrack = Some((g, n));
+            }
+            Some(&c) if c == '?' || c == name[n] => {
+                g += 1;
+                n += 1;
+            }
+            _ => match backtrack {
+                // Let the last `*` swallow one more character
+                Some((star, matched)) => {
+                    g = star;
+                    n = matched + 1;
+                    backtrack = Some((star, n));
+                }
+                None => return false,
+            },
+        }
+    }
+
+    glob[g..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consumers::MemoryEmitter;
+
+    fn signal(name: &str, level: Level) -> Signal {
+        Signal::new().name(name).level(level).build()
+    }
+
+    #[test]
+    fn test_matches() {
+        assert!(matches("eval.progress", "eval.progress"));
+        assert!(matches("eval.*", "eval.progress"));
+        assert!(matches("*.batch", "eval.batch"));
+        assert!(matches("io.*.retry", "io.read.retry"));
+        assert!(matches("score.?nvoke", "score.invoke"));
+        assert!(matches("*", ""));
+        assert!(!matches("eval.*", "score.invoke"));
+        assert!(!matches("eval", "eval.progress"));
+        assert!(!matches("io.*.retry", "io.read"));
+    }
+
+    #[test]
+    fn test_filter_by_level_and_name() {
+        let memory = MemoryEmitter::new();
+        let emitter = FilterEmitter::new(memory.clone())
+            .with_level(Level::Info)
+            .with_include("eval.*")
+            .with_include("io.*")
+            .with_exclude("io.read");
+
+        emitter.emit(signal("eval.batch", Level::Debug));
+        emitter.emit(signal("eval.batch", Level::Info));
+        emitter.emit(signal("score.invoke", Level::Error));
+        emitter.emit(signal("io.read", Level::Info));
+        emitter.emit(signal("io.write", Level::Warn));
+
+        let names: Vec<String> = memory
+            .signals()
+            .iter()
+            .map(|s| s.name().to_string())
+            .collect();
+        assert_eq!(names, ["eval.batch", "io.write"]);
+    }
+
+    #[test]
+    fn test_sample() {
+        let memory = MemoryEmitter::new();
+        let emitter = FilterEmitter::new(memory.clone())
+            .with_sample("eval.progress", 0.01)
+            .with_sample("eval.*", 0.0);
+
+        for _ in 0..1_000 {
+            emitter.emit(signal("eval.progress", Level::Info));
+        }
+
+        emitter.emit(signal("eval.batch", Level::Info));
+        emitter.emit(signal("score.invoke", Level::Info));
+
+        let signals = memory.signals();
+        assert_eq!(signals.len(), 11);
+        assert_eq!(signals[10].name(), "score.invoke");
+    }
+}
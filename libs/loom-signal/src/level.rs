@@ -1,9 +1,15 @@
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, serde::Deserialize, serde::Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum Level {
+    #[serde(alias = "trace")]
     Trace,
+    #[serde(alias = "debug")]
     Debug,
+    #[serde(alias = "info")]
     Info,
+    #[serde(alias = "warn")]
     Warn,
+    #[serde(alias = "error")]
     Error,
 }
 
@@ -1,20 +1,36 @@
 mod attr;
 pub mod consumers;
 mod emitter;
+mod filter;
 mod level;
 mod otype;
+mod redact;
 mod span;
 
 pub use attr::*;
 pub use emitter::*;
+pub use filter::*;
 pub use level::*;
 pub use otype::*;
+pub use redact::*;
 pub use span::*;
 
 use loom_core::value::Value;
 
 pub trait Emitter {
     fn emit(&self, signal: Signal);
+
+    /// Write out anything buffered, e.g. before the process exits
+    fn flush(&self) {}
+
+    /// Start a span emitted through this emitter when the returned guard is
+    /// dropped
+    fn span(&self, name: impl Into<String>) -> SpanGuard<'_, Self>
+    where
+        Self: Sized,
+    {
+        SpanGuard::new(self, name)
+    }
 }
 
 pub trait Consumer {
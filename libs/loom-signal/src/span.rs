@@ -1,8 +1,10 @@
-use std::time::{Duration, Instant};
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 use loom_core::value::Value;
 
-use crate::{Attributes, Level, Signal, Type};
+use crate::{Attributes, Emitter, Level, Signal, Type};
 
 /// A span represents a timed operation.
 ///
@@ -89,6 +91,160 @@ impl Span {
     }
 }
 
+/// A span emitted when it goes out of scope, e.g. at the end of a layer
+/// call, whichever way the call returns.
+///
+/// Each guard has a trace id shared with its [`child`](SpanGuard::child)
+/// spans and a span id of its own, emitted as the `trace_id`, `span_id` and
+/// `parent_span_id` attributes along with `duration_ms`. Start one with
+/// [`Emitter::span`].
+///
+/// # Example
+/// ```ignore
+/// let span = emitter.span("eval.scoring").with_trace_id(request_id);
+///
+/// for batch in batches {
+///     let mut child = span.child("eval.batch").with_attr("size", batch.len());
+///
+///     if let Err(e) = score(batch) {
+///         child.fail(e.to_string());
+///     }
+/// }
+/// ```
+pub struct SpanGuard<'a, E: Emitter + ?Sized> {
+    emitter: &'a E,
+    span: Option<Span>,
+    trace_id: String,
+    span_id: String,
+    parent_id: Option<String>,
+    error: Option<String>,
+}
+
+impl<'a, E: Emitter + ?Sized> SpanGuard<'a, E> {
+    /// Start a span in a new trace, emitted through `emitter` on drop.
+    pub fn new(emitter: &'a E, name: impl Into<String>) -> Self {
+        Self {
+            emitter,
+            span: Some(Span::new(name)),
+            trace_id: random_id(2),
+            span_id: random_id(1),
+            parent_id: None,
+            error: None,
+        }
+    }
+
+    /// Join the trace `trace_id` instead, e.g. a request's correlation id.
+    pub fn with_trace_id(mut self, trace_id: impl Into<String>) -> Self {
+        self.trace_id = trace_id.into();
+        self
+    }
+
+    /// Set the log level for this span.
+    pub fn with_level(mut self, level: Level) -> Self {
+        self.span = self.span.take().map(|span| span.with_level(level));
+        self
+    }
+
+    /// Add an attribute to the span.
+    pub fn with_attr(mut self, key: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.set_attr(key, value);
+        self
+    }
+
+    /// Add an attribute once the span has started, e.g. a result count.
+    pub fn set_attr(&mut self, key: impl Into<String>, value: impl Into<Value>) {
+        self.span = self.span.take().map(|span| span.with_attr(key, value));
+    }
+
+    /// Mark the span failed; it is emitted at `Error` level with an `error`
+    /// attribute.
+    pub fn fail(&mut self, error: impl Into<String>) {
+        self.error = Some(error.into());
+    }
+
+    /// Start a span nested in this one, in the same trace.
+    pub fn child(&self, name: impl Into<String>) -> SpanGuard<'a, E> {
+        SpanGuard {
+            emitter: self.emitter,
+            span: Some(Span::new(name)),
+            trace_id: self.trace_id.clone(),
+            span_id: random_id(1),
+            parent_id: Some(self.span_id.clone()),
+            error: None,
+        }
+    }
+
+    pub fn trace_id(&self) -> &str {
+        &self.trace_id
+    }
+
+    pub fn span_id(&self) -> &str {
+        &self.span_id
+    }
+
+    /// Get the elapsed duration since the span was started.
+    pub fn elapsed(&self) -> Duration {
+        self.span.as_ref().map(Span::elapsed).unwrap_or_default()
+    }
+
+    /// End the span now rather than when it goes out of scope.
+    pub fn end(self) {}
+}
+
+impl<E: Emitter + ?Sized> Drop for SpanGuard<'_, E> {
+    fn drop(&mut self) {
+        let Some(mut span) = self.span.take() else {
+            return;
+        };
+
+        span = span
+            .with_attr("trace_id", self.trace_id.as_str())
+            .with_attr("span_id", self.span_id.as_str());
+
+        if let Some(parent_id) = &self.parent_id {
+            span = span.with_attr("parent_span_id", parent_id.as_str());
+        }
+
+        self.emitter.emit(match self.error.take() {
+            Some(error) => span.finish_with_error(error),
+            None => span.finish(),
+        });
+    }
+}
+
+impl dyn Emitter + '_ {
+    /// Start a span emitted through this emitter when the returned guard is
+    /// dropped.
+    pub fn span(&self, name: impl Into<String>) -> SpanGuard<'_, Self> {
+        SpanGuard::new(self, name)
+    }
+}
+
+impl dyn Emitter + Send + Sync + '_ {
+    /// Start a span emitted through this emitter when the returned guard is
+    /// dropped.
+    pub fn span(&self, name: impl Into<String>) -> SpanGuard<'_, Self> {
+        SpanGuard::new(self, name)
+    }
+}
+
+/// Random hex id of `words` 64-bit words; a trace id is 2, a span id 1
+pub(crate) fn random_id(words: usize) -> String {
+    (0..words)
+        .map(|_| {
+            // Each `RandomState` is seeded differently
+            let mut hasher = RandomState::new().build_hasher();
+            hasher.write_u128(
+                SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map(|since| since.as_nanos())
+                    .unwrap_or(0),
+            );
+            format!("{:016x}", hasher.finish())
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -131,4 +287,51 @@ mod tests {
         assert_eq!(signal.level(), Level::Error);
         assert!(signal.attributes().exists("error"));
     }
+
+    #[test]
+    fn test_span_guard_emits_on_drop() {
+        let emitter = crate::consumers::MemoryEmitter::new();
+
+        {
+            let span = emitter.span("eval.scoring").with_attr("total", 2i64);
+            let mut child = span.child("eval.batch");
+            child.fail("out of memory");
+        }
+
+        let signals = emitter.signals();
+        assert_eq!(signals.len(), 2);
+
+        let (child, parent) = (&signals[0], &signals[1]);
+        assert_eq!(child.name(), "eval.batch");
+        assert_eq!(child.level(), Level::Error);
+        assert_eq!(parent.name(), "eval.scoring");
+        assert_eq!(parent.otype(), Type::Span);
+        assert!(parent.attributes().exists("duration_ms"));
+        assert!(parent.attributes().exists("total"));
+        assert!(!parent.attributes().exists("parent_span_id"));
+        assert_eq!(
+            child.attributes().get("trace_id"),
+            parent.attributes().get("trace_id")
+        );
+        assert_eq!(
+            child.attributes().get("parent_span_id"),
+            parent.attributes().get("span_id")
+        );
+    }
+
+    #[test]
+    fn test_span_guard_on_dyn_emitter() {
+        let memory = crate::consumers::MemoryEmitter::new();
+        let emitter: &dyn Emitter = &memory;
+
+        let span = emitter.span("score.invoke").with_trace_id("request-1");
+        assert_eq!(span.trace_id(), "request-1");
+        assert_eq!(span.span_id().len(), 16);
+        span.end();
+
+        assert_eq!(
+            memory.signals()[0].attributes().get("trace_id"),
+            Some(&Value::from("request-1"))
+        );
+    }
 }
@@ -0,0 +1,148 @@
+use loom_runtime::eval::{Decision, Difficulty, Sample, SampleDataset};
+
+/// Builder for eval [`Sample`]s with test-friendly defaults
+/// (accepted, `medium` difficulty, no labels).
+pub struct SampleBuilder {
+    sample: Sample,
+}
+
+impl SampleBuilder {
+    pub fn new(id: impl Into<String>, text: impl Into<String>) -> Self {
+        Self {
+            sample: Sample {
+                id: id.into(),
+                text: text.into(),
+                context: None,
+                expected_decision: Decision::Accept,
+                expected_labels: Vec::new(),
+                primary_category: String::new(),
+                difficulty: Difficulty::Medium,
+                notes: None,
+                metadata: None,
+                weight: None,
+                source: None,
+            },
+        }
+    }
+
+    pub fn context(mut self, context: impl Into<String>) -> Self {
+        self.sample.context = Some(context.into());
+        self
+    }
+
+    pub fn accept(self) -> Self {
+        self.decision(Decision::Accept)
+    }
+
+    pub fn reject(self) -> Self {
+        self.decision(Decision::Reject)
+    }
+
+    pub fn decision(mut self, decision: Decision) -> Self {
+        self.sample.expected_decision = decision;
+        self
+    }
+
+    /// Add an expected label
+    pub fn label(mut self, label: impl Into<String>) -> Self {
+        self.sample.expected_labels.push(label.into());
+        self
+    }
+
+    pub fn category(mut self, category: impl Into<String>) -> Self {
+        self.sample.primary_category = category.into();
+        self
+    }
+
+    pub fn difficulty(mut self, difficulty: Difficulty) -> Self {
+        self.sample.difficulty = difficulty;
+        self
+    }
+
+    pub fn notes(mut self, notes: impl Into<String>) -> Self {
+        self.sample.notes = Some(notes.into());
+        self
+    }
+
+    /// Source dataset the sample was derived from
+    pub fn source(mut self, source: impl Into<String>) -> Self {
+        self.sample.source = Some(source.into());
+        self
+    }
+
+    /// Weight of the sample in weighted metrics
+    pub fn weight(mut self, weight: f32) -> Self {
+        self.sample.weight = Some(weight);
+        self
+    }
+
+    pub fn build(self) -> Sample {
+        self.sample
+    }
+}
+
+impl From<SampleBuilder> for Sample {
+    fn from(builder: SampleBuilder) -> Self {
+        builder.build()
+    }
+}
+
+/// Builder for eval [`SampleDataset`]s
+pub struct DatasetBuilder {
+    dataset: SampleDataset,
+}
+
+impl DatasetBuilder {
+    pub fn new() -> Self {
+        Self {
+            dataset: SampleDataset::new(),
+        }
+    }
+
+    pub fn version(mut self, version: impl Into<String>) -> Self {
+        self.dataset.version = version.into();
+        self
+    }
+
+    pub fn sample(mut self, sample: impl Into<Sample>) -> Self {
+        self.dataset.samples.push(sample.into());
+        self
+    }
+
+    pub fn build(self) -> SampleDataset {
+        self.dataset
+    }
+}
+
+impl Default for DatasetBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builds_dataset_from_sample_builders() {
+        let dataset = DatasetBuilder::new()
+            .sample(
+                SampleBuilder::new("s1", "ship it friday")
+                    .category("task")
+                    .label("task"),
+            )
+            .sample(
+                SampleBuilder::new("s2", "hi there")
+                    .reject()
+                    .category("context")
+                    .label("phatic"),
+            )
+            .build();
+
+        assert_eq!(dataset.samples.len(), 2);
+        assert_eq!(dataset.samples[0].expected_labels, vec!["task".to_string()]);
+        assert_eq!(dataset.samples[1].expected_decision, Decision::Reject);
+        assert!(dataset.validate().is_empty());
+    }
+}
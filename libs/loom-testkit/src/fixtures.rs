@@ -0,0 +1,72 @@
+use std::collections::BTreeMap;
+
+use loom_runtime::eval::score::{
+    ScoreCategoryConfig, ScoreComparator, ScoreConfig, ScoreGuardAction, ScoreGuardConfig,
+    ScoreLabelConfig,
+};
+
+/// Categories and labels of the [`score_config`] fixture
+pub const FIXTURE_LABELS: &[(&str, &[&str])] = &[
+    ("context", &["phatic"]),
+    ("sentiment", &["negative", "positive"]),
+    ("task", &["task"]),
+];
+
+/// Small score configuration for tests.
+///
+/// Every label has weight `1.0`, threshold `0.5`, and identity calibration, so a
+/// label's score equals its raw score once it clears `0.5`. Category `top_k` is 1,
+/// and texts with a `phatic` raw score of `0.8` or more are rejected by a guard.
+pub fn score_config() -> ScoreConfig {
+    let categories = FIXTURE_LABELS
+        .iter()
+        .map(|(category, labels)| {
+            let labels = labels
+                .iter()
+                .map(|label| {
+                    let config = ScoreLabelConfig {
+                        hypothesis: format!("This example is {}.", label),
+                        weight: 1.0,
+                        threshold: 0.5,
+                        ..Default::default()
+                    };
+
+                    (label.to_string(), config)
+                })
+                .collect::<BTreeMap<_, _>>();
+
+            (
+                category.to_string(),
+                ScoreCategoryConfig { top_k: 1, labels },
+            )
+        })
+        .collect();
+
+    ScoreConfig {
+        categories,
+        guards: vec![ScoreGuardConfig {
+            label: "phatic".to_string(),
+            comparator: ScoreComparator::Gte,
+            threshold: 0.8,
+            action: ScoreGuardAction::Reject,
+        }],
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn score_config_contains_fixture_labels() {
+        let config = score_config();
+
+        for (category, labels) in FIXTURE_LABELS {
+            let category = config.category(category).unwrap();
+            assert_eq!(category.labels.len(), labels.len());
+        }
+
+        assert!(config.label("phatic").is_some());
+    }
+}
@@ -0,0 +1,27 @@
+//! Test fixtures for the Loom ecosystem.
+//!
+//! Provides a deterministic [`MockScorer`], dataset builders, and an in-memory
+//! runtime factory so pipelines can be unit tested without loading a model.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use loom_testkit::{MockScorer, score_config};
+//!
+//! let scorer = MockScorer::new(score_config())
+//!     .text("let's deploy on friday", &[("task", 0.95)])
+//!     .text("hi there", &[("phatic", 0.90)]);
+//!
+//! let runtime = loom_testkit::runtime(scorer).build();
+//! let result = runtime.score("let's deploy on friday")?;
+//! ```
+
+mod dataset;
+mod fixtures;
+mod runtime;
+mod scorer;
+
+pub use dataset::*;
+pub use fixtures::*;
+pub use runtime::*;
+pub use scorer::*;
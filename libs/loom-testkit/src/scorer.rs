@@ -0,0 +1,206 @@
+use std::collections::HashMap;
+
+use loom_error::Result;
+use loom_runtime::eval::score::{
+    AsyncBatchScorer, BatchScorer, ScoreConfig, ScoreLayerOutput, ScoreProvenance, ScoreResult,
+    Scorer,
+};
+use loom_runtime::{Context, LayerMeta, LayerResult};
+
+/// Model id recorded in the provenance of mock results
+pub const MOCK_MODEL: &str = "mock";
+
+/// Deterministic scorer backed by a lookup table of raw label scores.
+///
+/// Texts are matched exactly, as the premise the model would see: with
+/// `context_turns` set, the previous turns of a context come first, one
+/// `speaker: text` line each. Unknown texts fall back to the default scores
+/// (all zero unless set with [`MockScorer::default_scores`]). Raw scores are
+/// calibrated, weighted, and guarded exactly like the model-backed `ScoreLayer`.
+pub struct MockScorer {
+    config: ScoreConfig,
+    provenance: ScoreProvenance,
+    table: HashMap<String, HashMap<String, f32>>,
+    defaults: HashMap<String, f32>,
+}
+
+impl MockScorer {
+    pub fn new(config: ScoreConfig) -> Self {
+        let provenance = ScoreProvenance::new(config.provenance().config_hash, MOCK_MODEL);
+        Self {
+            config,
+            provenance,
+            table: HashMap::new(),
+            defaults: HashMap::new(),
+        }
+    }
+
+    /// Set the raw label scores returned for `text`
+    pub fn text(mut self, text: impl Into<String>, scores: &[(&str, f32)]) -> Self {
+        self.table.insert(text.into(), Self::to_map(scores));
+        self
+    }
+
+    /// Set the raw label scores returned for texts missing from the table
+    pub fn default_scores(mut self, scores: &[(&str, f32)]) -> Self {
+        self.defaults = Self::to_map(scores);
+        self
+    }
+
+    /// Get the provenance attached to every result produced by this scorer
+    pub fn provenance(&self) -> &ScoreProvenance {
+        &self.provenance
+    }
+
+    fn to_map(scores: &[(&str, f32)]) -> HashMap<String, f32> {
+        scores
+            .iter()
+            .map(|(label, score)| (label.to_string(), *score))
+            .collect()
+    }
+
+    fn result_of(&self, text: &str) -> ScoreResult {
+        let scores = self.table.get(text).unwrap_or(&self.defaults);
+        let raw_scores: HashMap<&str, f32> = scores
+            .iter()
+            .map(|(label, score)| (label.as_str(), *score))
+            .collect();
+
+        self.config
+            .result_of(&raw_scores)
+            .with_provenance(self.provenance.clone())
+    }
+}
+
+impl Scorer for MockScorer {
+    fn config(&self) -> &ScoreConfig {
+        &self.config
+    }
+
+    fn invoke(&self, ctx: Context<()>) -> Result<LayerResult<ScoreResult>> {
+        let premise = ctx.premise(self.config.context_turns.unwrap_or(0));
+        let mut result = LayerResult::new(self.result_of(&premise));
+
+        if let Some(guard) = self.config.check(ctx.text.len(), &result.output)? {
+            result.meta.set("guard", guard.to_string().into());
+        }
+
+        result.meta.set_step(ctx.step);
+        result.meta.set("text", ctx.text.clone().into());
+        Ok(result)
+    }
+}
+
+impl BatchScorer for MockScorer {
+    fn score_batch(&self, texts: &[&str]) -> Result<Vec<ScoreLayerOutput>> {
+        Ok(texts
+            .iter()
+            .map(|text| ScoreLayerOutput::guarded(self.result_of(text), &self.config))
+            .collect())
+    }
+}
+
+#[async_trait::async_trait]
+impl AsyncBatchScorer for MockScorer {
+    fn config(&self) -> &ScoreConfig {
+        &self.config
+    }
+
+    async fn score_batch(&self, texts: &[String]) -> Result<Vec<ScoreLayerOutput>> {
+        Ok(texts
+            .iter()
+            .map(|text| ScoreLayerOutput::guarded(self.result_of(text), &self.config))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::score_config;
+    use loom_runtime::Turn;
+    use loom_runtime::eval::Decision;
+
+    fn scorer() -> MockScorer {
+        MockScorer::new(score_config())
+            .text("ship it friday", &[("task", 0.95)])
+            .text("hi there", &[("phatic", 0.90), ("task", 0.95)])
+    }
+
+    #[test]
+    fn invoke_scores_from_table() {
+        let result = scorer().invoke(Context::new("ship it friday", ())).unwrap();
+
+        assert_eq!(result.output.label("task").unwrap().raw_score, 0.95);
+        assert_eq!(result.output.provenance.model, MOCK_MODEL);
+    }
+
+    #[test]
+    fn invoke_rejects_unknown_text() {
+        let err = scorer().invoke(Context::new("unknown", ())).unwrap_err();
+        assert!(err.code().is_cancel());
+    }
+
+    #[test]
+    fn invoke_applies_guards() {
+        let err = scorer().invoke(Context::new("hi there", ())).unwrap_err();
+        assert!(err.code().is_cancel());
+    }
+
+    #[test]
+    fn score_batch_is_not_filtered() {
+        let outputs = BatchScorer::score_batch(&scorer(), &["ship it friday", "unknown"]).unwrap();
+
+        assert_eq!(outputs.len(), 2);
+        assert_eq!(outputs[0].detected_labels(), vec!["task".to_string()]);
+        assert!(outputs[1].detected_labels().is_empty());
+    }
+
+    #[test]
+    fn score_batch_applies_guards() {
+        let outputs = BatchScorer::score_batch(&scorer(), &["hi there", "ship it friday"]).unwrap();
+
+        assert_eq!(outputs[0].decision(), Decision::Reject);
+        assert_eq!(outputs[1].decision(), Decision::Accept);
+    }
+
+    #[test]
+    fn batch_contexts_match_single_invoke_with_history() {
+        let mut config = score_config();
+        config.context_turns = Some(1);
+        let scorer = MockScorer::new(config)
+            .text("ship it friday", &[("task", 0.95)])
+            .text(
+                "alice: is the fix ready?\nship it friday",
+                &[("task", 0.80)],
+            );
+        let with_history = || {
+            Context::new("ship it friday", ()).with_history(vec![
+                Turn::new("bob", 0, "morning"),
+                Turn::new("alice", 1, "is the fix ready?"),
+            ])
+        };
+
+        let single = scorer.invoke(with_history()).unwrap().output;
+        let bare = scorer
+            .invoke(Context::new("ship it friday", ()))
+            .unwrap()
+            .output;
+        let batch = scorer
+            .score_contexts(&[with_history(), Context::new("ship it friday", ())])
+            .unwrap();
+
+        assert_eq!(single.label("task").unwrap().raw_score, 0.80);
+        assert_eq!(batch[0].score(), single.score);
+        assert_eq!(batch[0].labels(), ScoreLayerOutput::new(single).labels());
+        assert_eq!(batch[1].score(), bare.score);
+    }
+
+    #[test]
+    fn default_scores_apply_to_unknown_text() {
+        let scorer = scorer().default_scores(&[("positive", 0.99)]);
+        let result = scorer.invoke(Context::new("unknown", ())).unwrap();
+
+        assert_eq!(result.output.label("positive").unwrap().raw_score, 0.99);
+    }
+}
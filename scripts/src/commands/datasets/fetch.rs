@@ -1,75 +1,78 @@
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
-use anyhow::{Context, Result};
-use crossterm::style::{Color, ResetColor, SetForegroundColor};
-use crossterm::ExecutableCommand;
+use anyhow::{Context, Result, bail};
+use clap::Args;
+use crossterm::style::Color;
 
+use super::{format_bytes, print_status, save_dataset};
 use crate::datasets::build_client;
-use crate::sample::Dataset;
+use crate::datasets::hub;
+use crate::datasets::manifest::{Manifest, ManifestEntry};
+use crate::datasets::registry::SourceRegistry;
 use crate::widgets::{ProgressBar, Widget};
-use crate::datasets;
-
-const DATASETS: [(&str, &str); 5] = [
-    ("DailyDialog", "daily_dialog.samples.json"),
-    ("Multi-Session Chat", "multi_session_chat.samples.json"),
-    ("MSC-Self-Instruct", "msc_self_instruct.samples.json"),
-    ("LongMemEval", "longmemeval.samples.json"),
-    ("LoCoMo", "locomo.samples.json"),
-];
-
-fn format_bytes(bytes: u64) -> String {
-    const KB: u64 = 1024;
-    const MB: u64 = 1024 * KB;
-    const GB: u64 = 1024 * MB;
-
-    if bytes >= GB {
-        format!("{:.1} GB", bytes as f64 / GB as f64)
-    } else if bytes >= MB {
-        format!("{:.1} MB", bytes as f64 / MB as f64)
-    } else if bytes >= KB {
-        format!("{:.1} KB", bytes as f64 / KB as f64)
-    } else {
-        format!("{} B", bytes)
-    }
-}
 
-fn save_dataset(dataset: &Dataset, path: &Path) -> Result<u64> {
-    // Create parent directory if needed
-    if let Some(parent) = path.parent() {
-        fs::create_dir_all(parent)?;
-    }
+const DEFAULT_CONFIG: &str = "datasets/sources.json";
+
+#[derive(Args)]
+pub struct FetchArgs {
+    /// Dataset sources file [default: datasets/sources.json if present,
+    /// otherwise the builtin datasets]
+    #[arg(short, long)]
+    config: Option<PathBuf>,
 
-    let json = serde_json::to_string_pretty(dataset)?;
-    fs::write(path, &json)?;
+    /// Only fetch these datasets, by name or file stem
+    #[arg(long, value_name = "NAME", num_args = 1..)]
+    only: Vec<String>,
 
-    Ok(json.len() as u64)
+    /// Download even if the dataset is current with its hub revision
+    #[arg(short, long)]
+    force: bool,
 }
 
-fn print_status(icon: char, color: Color, message: &str) {
-    let mut stdout = std::io::stdout();
-    let _ = stdout.execute(SetForegroundColor(color));
-    print!("{} ", icon);
-    let _ = stdout.execute(ResetColor);
-    println!("{}", message);
+#[derive(PartialEq)]
+enum Outcome {
+    Fetched,
+    Current,
+    Failed,
+}
+
+fn load_registry(config: Option<PathBuf>) -> Result<SourceRegistry> {
+    match config {
+        Some(path) => SourceRegistry::load(&path),
+        None if Path::new(DEFAULT_CONFIG).exists() => {
+            SourceRegistry::load(Path::new(DEFAULT_CONFIG))
+        }
+        None => Ok(SourceRegistry::builtin()),
+    }
 }
 
-pub async fn run() -> Result<()> {
+pub async fn run(args: FetchArgs) -> Result<()> {
+    let registry = load_registry(args.config)?.only(&args.only);
+
+    if registry.is_empty() {
+        bail!("No datasets match {}", args.only.join(", "));
+    }
+
     println!("\nFetching datasets...\n");
 
     // Get output directory (relative to project root)
     let output_dir = Path::new("datasets");
+    let mut manifest = Manifest::load(output_dir).context("Failed to read dataset manifest")?;
 
     // Build HTTP client
     let client = build_client().context("Failed to build HTTP client")?;
 
-    let mut results: Vec<(String, usize, u64, bool)> = Vec::new();
+    let mut results: Vec<(String, usize, u64, Outcome)> = Vec::new();
+
+    for (idx, source) in registry.sources().iter().enumerate() {
+        let name = source.name();
+        let filename = source.filename();
 
-    for (idx, (name, filename)) in DATASETS.iter().enumerate() {
         // Show overall progress
         ProgressBar::new()
             .current(idx)
-            .total(DATASETS.len())
+            .total(registry.len())
             .message(format!("Downloading {}...", name))
             .render()
             .write();
@@ -79,18 +82,37 @@ pub async fn run() -> Result<()> {
 
         let output_path = output_dir.join(filename);
 
-        let result = match *name {
-            "DailyDialog" => datasets::daily_dialog::download(&client).await,
-            "Multi-Session Chat" => datasets::multi_session_chat::download(&client).await,
-            "MSC-Self-Instruct" => datasets::msc_self_instruct::download(&client).await,
-            "LongMemEval" => datasets::longmemeval::download(&client).await,
-            "LoCoMo" => datasets::locomo::download(&client).await,
-            _ => unreachable!(),
+        let info = match hub::dataset_info(&client, source.repo(), source.revision()).await {
+            Ok(info) => info,
+            Err(e) => {
+                print_status(
+                    '!',
+                    Color::Yellow,
+                    &format!("No hub metadata for {}: {}", source.repo(), e),
+                );
+                hub::HubInfo::default()
+            }
         };
 
-        match result {
-            Ok(dataset) => {
+        if !args.force
+            && let Some(entry) = manifest.current(name, info.sha.as_deref(), output_dir)
+        {
+            let file_size = fs::metadata(&output_path).map(|m| m.len()).unwrap_or(0);
+            print_status(
+                '✓',
+                Color::Green,
+                &format!("{} is up to date ({} samples)", filename, entry.samples),
+            );
+            results.push((name.to_string(), entry.samples, file_size, Outcome::Current));
+            println!();
+            continue;
+        }
+
+        match source.download(&client).await {
+            Ok(mut dataset) => {
                 let sample_count = dataset.samples.len();
+                dataset.license = info.license.clone();
+                dataset.revision = info.sha.clone();
 
                 if sample_count > 0 {
                     match save_dataset(&dataset, &output_path) {
@@ -105,7 +127,23 @@ pub async fn run() -> Result<()> {
                                     format_bytes(file_size)
                                 ),
                             );
-                            results.push((name.to_string(), sample_count, file_size, true));
+                            manifest.record(
+                                name,
+                                ManifestEntry {
+                                    repo: source.repo().to_string(),
+                                    revision: info.sha,
+                                    license: info.license,
+                                    filename: filename.to_string(),
+                                    samples: sample_count,
+                                    fetched: dataset.created.clone(),
+                                },
+                            );
+                            results.push((
+                                name.to_string(),
+                                sample_count,
+                                file_size,
+                                Outcome::Fetched,
+                            ));
                         }
                         Err(e) => {
                             print_status(
@@ -113,17 +151,25 @@ pub async fn run() -> Result<()> {
                                 Color::Red,
                                 &format!("Failed to save {}: {}", filename, e),
                             );
-                            results.push((name.to_string(), sample_count, 0, false));
+                            results.push((name.to_string(), sample_count, 0, Outcome::Failed));
                         }
                     }
                 } else {
-                    print_status('✗', Color::Yellow, &format!("No samples found for {}", name));
-                    results.push((name.to_string(), 0, 0, false));
+                    print_status(
+                        '✗',
+                        Color::Yellow,
+                        &format!("No samples found for {}", name),
+                    );
+                    results.push((name.to_string(), 0, 0, Outcome::Failed));
                 }
             }
             Err(e) => {
-                print_status('✗', Color::Red, &format!("Failed to download {}: {}", name, e));
-                results.push((name.to_string(), 0, 0, false));
+                print_status(
+                    '✗',
+                    Color::Red,
+                    &format!("Failed to download {}: {}", name, e),
+                );
+                results.push((name.to_string(), 0, 0, Outcome::Failed));
             }
         }
 
@@ -133,6 +179,10 @@ pub async fn run() -> Result<()> {
     // Clear progress bar
     ProgressBar::clear();
 
+    manifest
+        .save(output_dir)
+        .context("Failed to write dataset manifest")?;
+
     // Print summary
     println!("=== Summary ===\n");
 
@@ -140,19 +190,31 @@ pub async fn run() -> Result<()> {
     let mut total_size = 0u64;
     let mut success_count = 0usize;
 
-    for (name, samples, size, success) in &results {
-        if *success {
-            print_status(
-                '✓',
-                Color::Green,
-                &format!("{:<20} {:>10} samples  {:>10}", name, samples, format_bytes(*size)),
-            );
-            total_samples += samples;
-            total_size += size;
-            success_count += 1;
-        } else {
+    for (name, samples, size, outcome) in &results {
+        if *outcome == Outcome::Failed {
             print_status('✗', Color::Red, &format!("{:<20} failed", name));
+            continue;
         }
+
+        let cached = if *outcome == Outcome::Current {
+            "  (up to date)"
+        } else {
+            ""
+        };
+        print_status(
+            '✓',
+            Color::Green,
+            &format!(
+                "{:<20} {:>10} samples  {:>10}{}",
+                name,
+                samples,
+                format_bytes(*size),
+                cached
+            ),
+        );
+        total_samples += samples;
+        total_size += size;
+        success_count += 1;
     }
 
     println!();
@@ -164,7 +226,7 @@ pub async fn run() -> Result<()> {
     );
     println!();
 
-    if success_count < DATASETS.len() {
+    if success_count < registry.len() {
         std::process::exit(1);
     }
 
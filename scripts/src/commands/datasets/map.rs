@@ -0,0 +1,121 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use clap::Args;
+use crossterm::style::Color;
+
+use super::{format_bytes, print_status, save_dataset};
+use crate::datasets::mapping::Mapping;
+use crate::sample::Dataset;
+
+#[derive(Args)]
+pub struct MapArgs {
+    /// Samples file to convert
+    input: PathBuf,
+
+    /// Mapping file (JSON)
+    #[arg(short, long)]
+    mapping: PathBuf,
+
+    /// Output samples file [default: <input>.mapped.samples.json]
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+
+    /// Report unmapped labels without writing the output
+    #[arg(long)]
+    dry_run: bool,
+
+    /// Fail if any label or category is unmapped
+    #[arg(long)]
+    strict: bool,
+}
+
+fn default_output(input: &Path) -> PathBuf {
+    let name = input
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or_default();
+    let stem = name
+        .strip_suffix(".samples.json")
+        .or_else(|| name.strip_suffix(".json"))
+        .unwrap_or(name);
+
+    input.with_file_name(format!("{}.mapped.samples.json", stem))
+}
+
+fn print_unmapped(kind: &str, unmapped: &BTreeMap<String, usize>) {
+    if unmapped.is_empty() {
+        return;
+    }
+
+    let mut counts: Vec<_> = unmapped.iter().collect();
+    counts.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
+
+    println!("Unmapped {}:", kind);
+
+    for (name, count) in counts {
+        println!("  {:<30} {:>10} samples", name, count);
+    }
+
+    println!();
+}
+
+pub fn run(args: MapArgs) -> Result<()> {
+    let mapping = Mapping::load(&args.mapping)?;
+    let text = fs::read_to_string(&args.input)
+        .with_context(|| format!("Failed to read {}", args.input.display()))?;
+    let mut dataset: Dataset = serde_json::from_str(&text)
+        .with_context(|| format!("Failed to parse {}", args.input.display()))?;
+
+    println!("\nMapping {}...\n", args.input.display());
+
+    let report = mapping.apply(&mut dataset);
+
+    print_unmapped("labels", &report.labels);
+    print_unmapped("categories", &report.categories);
+
+    if report.is_complete() {
+        print_status(
+            '✓',
+            Color::Green,
+            &format!("Mapped all {} samples", report.samples),
+        );
+    } else {
+        print_status(
+            '!',
+            Color::Yellow,
+            &format!(
+                "{} unmapped labels, {} unmapped categories across {} samples",
+                report.labels.len(),
+                report.categories.len(),
+                report.samples
+            ),
+        );
+    }
+
+    if !args.dry_run {
+        let output = args.output.unwrap_or_else(|| default_output(&args.input));
+        let file_size = save_dataset(&dataset, &output)?;
+
+        print_status(
+            '✓',
+            Color::Green,
+            &format!(
+                "Saved {} samples to {} ({})",
+                dataset.samples.len(),
+                output.display(),
+                format_bytes(file_size)
+            ),
+        );
+    }
+
+    println!();
+
+    if args.strict && !report.is_complete() {
+        std::process::exit(1);
+    }
+
+    Ok(())
+}
@@ -1,7 +1,16 @@
 mod fetch;
+mod map;
+mod synth;
+
+use std::fs;
+use std::path::Path;
 
 use anyhow::Result;
 use clap::{Args, Subcommand};
+use crossterm::ExecutableCommand;
+use crossterm::style::{Color, ResetColor, SetForegroundColor};
+
+use crate::sample::Dataset;
 
 #[derive(Args)]
 pub struct DatasetsArgs {
@@ -12,11 +21,53 @@ pub struct DatasetsArgs {
 #[derive(Subcommand)]
 enum DatasetsCommands {
     /// Fetch datasets from HuggingFace
-    Fetch,
+    Fetch(fetch::FetchArgs),
+    /// Map a samples file's labels and categories onto ours
+    Map(map::MapArgs),
+    /// Slice fetched conversations into annotated samples
+    Synth(synth::SynthArgs),
 }
 
 pub async fn run(args: DatasetsArgs) -> Result<()> {
     match args.command {
-        DatasetsCommands::Fetch => fetch::run().await,
+        DatasetsCommands::Fetch(args) => fetch::run(args).await,
+        DatasetsCommands::Map(args) => map::run(args),
+        DatasetsCommands::Synth(args) => synth::run(args),
     }
 }
+
+fn format_bytes(bytes: u64) -> String {
+    const KB: u64 = 1024;
+    const MB: u64 = 1024 * KB;
+    const GB: u64 = 1024 * MB;
+
+    if bytes >= GB {
+        format!("{:.1} GB", bytes as f64 / GB as f64)
+    } else if bytes >= MB {
+        format!("{:.1} MB", bytes as f64 / MB as f64)
+    } else if bytes >= KB {
+        format!("{:.1} KB", bytes as f64 / KB as f64)
+    } else {
+        format!("{} B", bytes)
+    }
+}
+
+fn save_dataset(dataset: &Dataset, path: &Path) -> Result<u64> {
+    // Create parent directory if needed
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let json = serde_json::to_string_pretty(dataset)?;
+    fs::write(path, &json)?;
+
+    Ok(json.len() as u64)
+}
+
+fn print_status(icon: char, color: Color, message: &str) {
+    let mut stdout = std::io::stdout();
+    let _ = stdout.execute(SetForegroundColor(color));
+    print!("{} ", icon);
+    let _ = stdout.execute(ResetColor);
+    println!("{}", message);
+}
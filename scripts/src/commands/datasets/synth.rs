@@ -0,0 +1,110 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+use clap::Args;
+use crossterm::style::Color;
+
+use super::{format_bytes, print_status, save_dataset};
+use crate::datasets::synth::{SynthConfig, Synthesizer};
+use crate::sample::Dataset;
+
+const DEFAULT_CONFIG: &str = "datasets/synth.json";
+
+#[derive(Args)]
+pub struct SynthArgs {
+    /// Fetched samples files to slice
+    #[arg(required = true)]
+    inputs: Vec<PathBuf>,
+
+    /// Heuristics file [default: datasets/synth.json if present]
+    #[arg(short, long)]
+    config: Option<PathBuf>,
+
+    /// Output samples file
+    #[arg(short, long, default_value = "datasets/synth.samples.json")]
+    output: PathBuf,
+
+    /// Previous turns included as context (overrides the config)
+    #[arg(long)]
+    window: Option<usize>,
+}
+
+fn load_config(path: Option<PathBuf>) -> Result<(SynthConfig, Option<PathBuf>)> {
+    match path {
+        Some(path) => Ok((SynthConfig::load(&path)?, Some(path))),
+        None if Path::new(DEFAULT_CONFIG).exists() => {
+            let path = PathBuf::from(DEFAULT_CONFIG);
+            Ok((SynthConfig::load(&path)?, Some(path)))
+        }
+        None => Ok((SynthConfig::default(), None)),
+    }
+}
+
+pub fn run(args: SynthArgs) -> Result<()> {
+    let (mut config, config_path) = load_config(args.config)?;
+
+    if let Some(window) = args.window {
+        config.window = window;
+    }
+
+    let mut synthesizer = Synthesizer::new(config);
+
+    if let Some(path) = &config_path {
+        synthesizer = synthesizer.config_path(path.display().to_string());
+    }
+
+    println!("\nSynthesizing samples...\n");
+
+    let mut sources = Vec::new();
+    let mut samples = Vec::new();
+
+    for input in &args.inputs {
+        let text = fs::read_to_string(input)
+            .with_context(|| format!("Failed to read {}", input.display()))?;
+        let dataset: Dataset = serde_json::from_str(&text)
+            .with_context(|| format!("Failed to parse {}", input.display()))?;
+        let synthesized = synthesizer.synthesize(&dataset);
+
+        print_status(
+            '✓',
+            Color::Green,
+            &format!(
+                "{:<40} {:>10} turns  {:>10} samples",
+                input.display(),
+                dataset.samples.len(),
+                synthesized.len()
+            ),
+        );
+
+        sources.push(dataset.source);
+        samples.extend(synthesized);
+    }
+
+    if samples.is_empty() {
+        bail!("No samples synthesized");
+    }
+
+    let mut dataset = Dataset::new(
+        sources.join(", "),
+        "Samples sliced from multi-turn conversations with heuristic annotations",
+    );
+    dataset.samples = samples;
+
+    let file_size = save_dataset(&dataset, &args.output)?;
+
+    println!();
+    print_status(
+        '✓',
+        Color::Green,
+        &format!(
+            "Saved {} samples to {} ({})",
+            dataset.samples.len(),
+            args.output.display(),
+            format_bytes(file_size)
+        ),
+    );
+    println!();
+
+    Ok(())
+}
@@ -0,0 +1,107 @@
+use anyhow::Result;
+use futures::future::BoxFuture;
+use reqwest::Client;
+use serde::Deserialize;
+
+use super::{
+    DatasetSource, daily_dialog, locomo, longmemeval, msc_self_instruct, multi_session_chat,
+};
+use crate::sample::Dataset;
+
+/// Datasets with a hand-written converter
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Builtin {
+    DailyDialog,
+    MultiSessionChat,
+    MscSelfInstruct,
+    #[serde(rename = "longmemeval")]
+    LongMemEval,
+    Locomo,
+}
+
+impl Builtin {
+    pub const ALL: [Builtin; 5] = [
+        Self::DailyDialog,
+        Self::MultiSessionChat,
+        Self::MscSelfInstruct,
+        Self::LongMemEval,
+        Self::Locomo,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::DailyDialog => "DailyDialog",
+            Self::MultiSessionChat => "Multi-Session Chat",
+            Self::MscSelfInstruct => "MSC-Self-Instruct",
+            Self::LongMemEval => "LongMemEval",
+            Self::Locomo => "LoCoMo",
+        }
+    }
+
+    pub fn filename(self) -> &'static str {
+        match self {
+            Self::DailyDialog => "daily_dialog.samples.json",
+            Self::MultiSessionChat => "multi_session_chat.samples.json",
+            Self::MscSelfInstruct => "msc_self_instruct.samples.json",
+            Self::LongMemEval => "longmemeval.samples.json",
+            Self::Locomo => "locomo.samples.json",
+        }
+    }
+
+    pub fn repo(self) -> &'static str {
+        match self {
+            Self::DailyDialog => "roskoN/dailydialog",
+            Self::MultiSessionChat => "nayohan/multi_session_chat",
+            Self::MscSelfInstruct => "MemGPT/MSC-Self-Instruct",
+            Self::LongMemEval => "xiaowu0162/longmemeval-cleaned",
+            Self::Locomo => "Percena/locomo-mc10",
+        }
+    }
+}
+
+/// A [`Builtin`] dataset, optionally saved under a different file name
+pub struct BuiltinSource {
+    builtin: Builtin,
+    filename: String,
+}
+
+impl BuiltinSource {
+    pub fn new(builtin: Builtin) -> Self {
+        Self {
+            builtin,
+            filename: builtin.filename().to_string(),
+        }
+    }
+
+    pub fn filename(mut self, filename: impl Into<String>) -> Self {
+        self.filename = filename.into();
+        self
+    }
+}
+
+impl DatasetSource for BuiltinSource {
+    fn name(&self) -> &str {
+        self.builtin.name()
+    }
+
+    fn filename(&self) -> &str {
+        &self.filename
+    }
+
+    fn repo(&self) -> &str {
+        self.builtin.repo()
+    }
+
+    fn download<'a>(&'a self, client: &'a Client) -> BoxFuture<'a, Result<Dataset>> {
+        Box::pin(async move {
+            match self.builtin {
+                Builtin::DailyDialog => daily_dialog::download(client).await,
+                Builtin::MultiSessionChat => multi_session_chat::download(client).await,
+                Builtin::MscSelfInstruct => msc_self_instruct::download(client).await,
+                Builtin::LongMemEval => longmemeval::download(client).await,
+                Builtin::Locomo => locomo::download(client).await,
+            }
+        })
+    }
+}
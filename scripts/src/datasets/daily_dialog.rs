@@ -229,6 +229,7 @@ pub async fn download(client: &Client) -> Result<Dataset> {
                     expected_labels: labels,
                     primary_category: primary_category.to_string(),
                     difficulty: difficulty.to_string(),
+                    source: "daily_dialog".to_string(),
                     metadata: Metadata {
                         source: "daily_dialog".to_string(),
                         split: Some(split.to_string()),
@@ -236,6 +237,7 @@ pub async fn download(client: &Client) -> Result<Dataset> {
                         turn_id: turn_idx,
                         speaker: None,
                         session_id: None,
+                        provenance: None,
                     },
                 };
 
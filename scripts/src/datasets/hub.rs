@@ -0,0 +1,73 @@
+use anyhow::Result;
+use reqwest::Client;
+use serde::Deserialize;
+use serde_json::Value;
+
+pub const HUB_URL: &str = "https://huggingface.co";
+
+/// Hub metadata for a dataset revision
+#[derive(Debug, Clone, Default)]
+pub struct HubInfo {
+    /// Commit the revision resolves to
+    pub sha: Option<String>,
+    /// License from the dataset card (e.g. "cc-by-nc-sa-4.0")
+    pub license: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RevisionResponse {
+    sha: Option<String>,
+    #[serde(rename = "cardData")]
+    card_data: Option<CardData>,
+    #[serde(default)]
+    tags: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CardData {
+    license: Option<Value>,
+}
+
+/// Fetch the commit and license of `repo` at `revision`
+pub async fn dataset_info(client: &Client, repo: &str, revision: &str) -> Result<HubInfo> {
+    let url = format!("{}/api/datasets/{}/revision/{}", HUB_URL, repo, revision);
+    let response: RevisionResponse = client
+        .get(&url)
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+
+    // The card may list several licenses; the `license:` tags mirror it
+    let license = response
+        .card_data
+        .and_then(|card| card.license)
+        .and_then(|license| match license {
+            Value::String(s) => Some(s),
+            Value::Array(items) => {
+                let names: Vec<&str> = items.iter().filter_map(|v| v.as_str()).collect();
+                (!names.is_empty()).then(|| names.join(", "))
+            }
+            _ => None,
+        })
+        .or_else(|| {
+            response
+                .tags
+                .iter()
+                .find_map(|tag| tag.strip_prefix("license:").map(str::to_string))
+        });
+
+    Ok(HubInfo {
+        sha: response.sha,
+        license,
+    })
+}
+
+/// URL of `file` in `repo` at `revision`
+pub fn resolve_url(repo: &str, revision: &str, file: &str) -> String {
+    format!(
+        "{}/datasets/{}/resolve/{}/{}",
+        HUB_URL, repo, revision, file
+    )
+}
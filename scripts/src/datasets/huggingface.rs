@@ -0,0 +1,244 @@
+use anyhow::{Result, bail};
+use futures::future::BoxFuture;
+use reqwest::Client;
+use serde::Deserialize;
+use serde_json::Value;
+
+use super::{DatasetSource, DownloadProgress, download_text, hub, render_download_progress};
+use crate::sample::{Dataset, Metadata, Sample, calculate_difficulty};
+
+fn default_revision() -> String {
+    "main".to_string()
+}
+
+fn default_text() -> String {
+    "text".to_string()
+}
+
+fn default_category() -> String {
+    "conversational".to_string()
+}
+
+fn default_decision() -> String {
+    "accept".to_string()
+}
+
+/// A hub dataset converted by field mapping, with one sample per record
+#[derive(Debug, Clone, Deserialize)]
+pub struct HuggingFaceConfig {
+    /// Display name
+    pub name: String,
+    /// Hub repository (e.g. "google-research-datasets/go_emotions")
+    pub repo: String,
+    #[serde(default = "default_revision")]
+    pub revision: String,
+    /// Data files in the repository, as JSON arrays or JSON Lines. Each file
+    /// is a split named after its file stem.
+    pub files: Vec<String>,
+    /// Output file name, defaults to `{name}.samples.json`
+    pub filename: Option<String>,
+    pub description: Option<String>,
+    /// Record field holding the sample text
+    #[serde(default = "default_text")]
+    pub text: String,
+    /// Record field holding the sample context
+    pub context: Option<String>,
+    /// Record field holding the labels: a string, an integer class id, or an
+    /// array of either
+    pub labels: Option<String>,
+    /// Label names for integer class ids, by index
+    #[serde(default)]
+    pub label_names: Vec<String>,
+    #[serde(default = "default_category")]
+    pub category: String,
+    #[serde(default = "default_decision")]
+    pub decision: String,
+    /// Maximum samples per file
+    pub limit: Option<usize>,
+}
+
+pub struct HuggingFaceSource {
+    config: HuggingFaceConfig,
+    filename: String,
+}
+
+impl HuggingFaceSource {
+    pub fn new(config: HuggingFaceConfig) -> Self {
+        let filename = config
+            .filename
+            .clone()
+            .unwrap_or_else(|| format!("{}.samples.json", slug(&config.name)));
+
+        Self { config, filename }
+    }
+
+    async fn download_dataset(&self, client: &Client) -> Result<Dataset> {
+        let config = &self.config;
+        let mut dataset = Dataset::new(
+            format!("{}/datasets/{}", hub::HUB_URL, config.repo),
+            config
+                .description
+                .clone()
+                .unwrap_or_else(|| config.name.clone()),
+        );
+
+        for file in &config.files {
+            let url = hub::resolve_url(&config.repo, &config.revision, file);
+            let split = file_stem(file);
+
+            println!("  Downloading {} {}...", config.name, file);
+
+            let text = download_text(client, &url, |downloaded, total| {
+                render_download_progress(downloaded, total, &format!("Downloading {}...", file))
+            })
+            .await?;
+
+            DownloadProgress::clear();
+
+            let records = parse_records(file, &text)?;
+            let limit = config.limit.unwrap_or(records.len());
+
+            println!(
+                "  Processing {} {} records...",
+                records.len().min(limit),
+                split
+            );
+
+            for (idx, record) in records.iter().take(limit).enumerate() {
+                if let Some(sample) = self.sample(record, split, idx) {
+                    dataset.samples.push(sample);
+                }
+            }
+        }
+
+        Ok(dataset)
+    }
+
+    fn sample(&self, record: &Value, split: &str, idx: usize) -> Option<Sample> {
+        let config = &self.config;
+        let text = record.get(&config.text)?.as_str()?.trim();
+
+        if text.is_empty() {
+            return None;
+        }
+
+        let context = config
+            .context
+            .as_ref()
+            .and_then(|field| record.get(field))
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string());
+
+        let mut labels = config
+            .labels
+            .as_ref()
+            .and_then(|field| record.get(field))
+            .map(|v| self.labels(v))
+            .unwrap_or_default();
+
+        if labels.is_empty() {
+            labels.push("neutral".to_string());
+        }
+
+        Some(Sample {
+            id: format!("{}-{}-{}", slug(&config.name), split, idx),
+            text: text.to_string(),
+            context,
+            expected_decision: config.decision.clone(),
+            expected_labels: labels,
+            primary_category: config.category.clone(),
+            difficulty: calculate_difficulty(text).to_string(),
+            source: config.repo.clone(),
+            metadata: Metadata {
+                source: config.repo.clone(),
+                split: Some(split.to_string()),
+                conversation_id: idx,
+                turn_id: 0,
+                speaker: None,
+                session_id: None,
+                provenance: None,
+            },
+        })
+    }
+
+    fn labels(&self, value: &Value) -> Vec<String> {
+        match value {
+            Value::Array(items) => items.iter().flat_map(|v| self.labels(v)).collect(),
+            Value::String(s) if !s.is_empty() => vec![s.clone()],
+            Value::Number(n) => n
+                .as_u64()
+                .map(|id| {
+                    self.config
+                        .label_names
+                        .get(id as usize)
+                        .cloned()
+                        .unwrap_or_else(|| id.to_string())
+                })
+                .into_iter()
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+impl DatasetSource for HuggingFaceSource {
+    fn name(&self) -> &str {
+        &self.config.name
+    }
+
+    fn filename(&self) -> &str {
+        &self.filename
+    }
+
+    fn repo(&self) -> &str {
+        &self.config.repo
+    }
+
+    fn revision(&self) -> &str {
+        &self.config.revision
+    }
+
+    fn download<'a>(&'a self, client: &'a Client) -> BoxFuture<'a, Result<Dataset>> {
+        Box::pin(self.download_dataset(client))
+    }
+}
+
+/// Records of a JSON Lines file, a JSON array, or a JSON object wrapping an
+/// array under `data` / `rows`
+fn parse_records(file: &str, text: &str) -> Result<Vec<Value>> {
+    if file.ends_with(".jsonl") || file.ends_with(".ndjson") {
+        return Ok(text
+            .lines()
+            .filter(|l| !l.trim().is_empty())
+            .filter_map(|l| serde_json::from_str(l).ok())
+            .collect());
+    }
+
+    match serde_json::from_str::<Value>(text)? {
+        Value::Array(records) => Ok(records),
+        Value::Object(mut object) => {
+            match object.remove("data").or_else(|| object.remove("rows")) {
+                Some(Value::Array(records)) => Ok(records),
+                _ => bail!("{} has no record array", file),
+            }
+        }
+        _ => bail!("{} has no record array", file),
+    }
+}
+
+fn file_stem(file: &str) -> &str {
+    let name = file.rsplit('/').next().unwrap_or(file);
+    name.split('.').next().unwrap_or(name)
+}
+
+fn slug(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
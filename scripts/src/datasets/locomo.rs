@@ -107,6 +107,7 @@ pub async fn download(client: &Client) -> Result<Dataset> {
             expected_labels: labels,
             primary_category: "memory".to_string(),
             difficulty: difficulty.to_string(),
+            source: "Percena/locomo-mc10".to_string(),
             metadata: Metadata {
                 source: "Percena/locomo-mc10".to_string(),
                 split: Some("train".to_string()),
@@ -114,6 +115,7 @@ pub async fn download(client: &Client) -> Result<Dataset> {
                 turn_id: 0,
                 speaker: None,
                 session_id: None,
+                provenance: None,
             },
         };
 
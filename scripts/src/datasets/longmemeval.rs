@@ -84,6 +84,7 @@ pub async fn download(client: &Client) -> Result<Dataset> {
                 expected_labels: labels,
                 primary_category: "memory".to_string(),
                 difficulty: difficulty.to_string(),
+                source: "xiaowu0162/longmemeval-cleaned".to_string(),
                 metadata: Metadata {
                     source: "xiaowu0162/longmemeval-cleaned".to_string(),
                     split: Some(split.to_string()),
@@ -91,6 +92,7 @@ pub async fn download(client: &Client) -> Result<Dataset> {
                     turn_id: 0,
                     speaker: None,
                     session_id: None,
+                    provenance: None,
                 },
             };
 
@@ -0,0 +1,74 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+const MANIFEST_FILE: &str = ".manifest.json";
+
+/// What was fetched for a dataset, used to skip downloads that are already
+/// current
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    pub repo: String,
+    /// Commit the dataset was fetched at
+    pub revision: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub license: Option<String>,
+    pub filename: String,
+    pub samples: usize,
+    pub fetched: String,
+}
+
+/// Fetched datasets by name, stored as `.manifest.json` in the datasets
+/// directory
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Manifest {
+    pub datasets: BTreeMap<String, ManifestEntry>,
+}
+
+impl Manifest {
+    fn path(dir: &Path) -> PathBuf {
+        dir.join(MANIFEST_FILE)
+    }
+
+    /// Load the manifest in `dir`, or an empty one if there is none
+    pub fn load(dir: &Path) -> Result<Self> {
+        let path = Self::path(dir);
+
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    pub fn save(&self, dir: &Path) -> Result<()> {
+        fs::create_dir_all(dir)?;
+        fs::write(Self::path(dir), serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// The entry for `name` if it was fetched at `revision` and its file is
+    /// still in `dir`
+    pub fn current(
+        &self,
+        name: &str,
+        revision: Option<&str>,
+        dir: &Path,
+    ) -> Option<&ManifestEntry> {
+        let entry = self.datasets.get(name)?;
+        let revision = revision?;
+
+        if entry.revision.as_deref() != Some(revision) || !dir.join(&entry.filename).exists() {
+            return None;
+        }
+
+        Some(entry)
+    }
+
+    pub fn record(&mut self, name: impl Into<String>, entry: ManifestEntry) {
+        self.datasets.insert(name.into(), entry);
+    }
+}
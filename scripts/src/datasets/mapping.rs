@@ -0,0 +1,152 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use crate::sample::{Dataset, Sample};
+
+/// One or more target labels for a source label
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum Target {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl Target {
+    fn labels(&self) -> &[String] {
+        match self {
+            Self::One(label) => std::slice::from_ref(label),
+            Self::Many(labels) => labels,
+        }
+    }
+}
+
+/// What to do with source labels the mapping doesn't cover
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Unmapped {
+    /// Keep the label as is
+    #[default]
+    Keep,
+    /// Drop the label
+    Drop,
+}
+
+/// Sets the category and/or decision of samples with any of `labels`
+#[derive(Debug, Clone, Deserialize)]
+pub struct Rule {
+    /// Source labels that trigger the rule
+    pub labels: Vec<String>,
+    pub category: Option<String>,
+    pub decision: Option<String>,
+}
+
+impl Rule {
+    fn matches(&self, labels: &[String]) -> bool {
+        self.labels.iter().any(|label| labels.contains(label))
+    }
+}
+
+/// Declarative conversion from a dataset's label schema to ours
+///
+/// Labels are mapped through `labels`, then the first rule matching the
+/// sample's source labels sets its category and decision; samples no rule
+/// matches map their category through `categories`.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct Mapping {
+    /// Source label to target label(s); an empty array drops the label
+    pub labels: BTreeMap<String, Target>,
+    /// Source category to target category
+    pub categories: BTreeMap<String, String>,
+    /// Rules, first match wins
+    pub rules: Vec<Rule>,
+    pub unmapped: Unmapped,
+    /// Label for samples left without any, e.g. "neutral"
+    pub fallback: Option<String>,
+}
+
+impl Mapping {
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+
+        serde_json::from_str(&text).with_context(|| format!("Failed to parse {}", path.display()))
+    }
+
+    /// Map every sample in `dataset`, reporting what the mapping didn't cover
+    pub fn apply(&self, dataset: &mut Dataset) -> MappingReport {
+        let mut report = MappingReport::default();
+
+        for sample in &mut dataset.samples {
+            self.apply_sample(sample, &mut report);
+        }
+
+        report.samples = dataset.samples.len();
+        report
+    }
+
+    fn apply_sample(&self, sample: &mut Sample, report: &mut MappingReport) {
+        let source = std::mem::take(&mut sample.expected_labels);
+        let mut labels = BTreeSet::new();
+
+        for label in &source {
+            match self.labels.get(label) {
+                Some(target) => labels.extend(target.labels().iter().cloned()),
+                None => {
+                    *report.labels.entry(label.clone()).or_default() += 1;
+
+                    if self.unmapped == Unmapped::Keep {
+                        labels.insert(label.clone());
+                    }
+                }
+            }
+        }
+
+        if labels.is_empty()
+            && let Some(fallback) = &self.fallback
+        {
+            labels.insert(fallback.clone());
+        }
+
+        sample.expected_labels = labels.into_iter().collect();
+
+        let rule = self.rules.iter().find(|rule| rule.matches(&source));
+
+        if let Some(decision) = rule.and_then(|rule| rule.decision.as_ref()) {
+            sample.expected_decision = decision.clone();
+        }
+
+        match rule.and_then(|rule| rule.category.as_ref()) {
+            Some(category) => sample.primary_category = category.clone(),
+            None => match self.categories.get(&sample.primary_category) {
+                Some(category) => sample.primary_category = category.clone(),
+                None if !self.categories.is_empty() => {
+                    *report
+                        .categories
+                        .entry(sample.primary_category.clone())
+                        .or_default() += 1;
+                }
+                None => {}
+            },
+        }
+    }
+}
+
+/// Source labels and categories a [`Mapping`] didn't cover, with the number
+/// of samples each occurred in
+#[derive(Debug, Default)]
+pub struct MappingReport {
+    pub samples: usize,
+    pub labels: BTreeMap<String, usize>,
+    pub categories: BTreeMap<String, usize>,
+}
+
+impl MappingReport {
+    pub fn is_complete(&self) -> bool {
+        self.labels.is_empty() && self.categories.is_empty()
+    }
+}
@@ -1,8 +1,18 @@
+pub mod builtin;
 pub mod daily_dialog;
+pub mod hub;
+pub mod huggingface;
 pub mod locomo;
 pub mod longmemeval;
+pub mod manifest;
+pub mod mapping;
 pub mod msc_self_instruct;
 pub mod multi_session_chat;
+pub mod registry;
+pub mod synth;
+mod source;
+
+pub use source::*;
 
 use std::path::Path;
 
@@ -22,12 +32,7 @@ pub fn build_client() -> Result<Client> {
 
 /// Download a file with progress reporting
 #[allow(dead_code)]
-pub async fn download_file<F>(
-    client: &Client,
-    url: &str,
-    dest: &Path,
-    on_progress: F,
-) -> Result<()>
+pub async fn download_file<F>(client: &Client, url: &str, dest: &Path, on_progress: F) -> Result<()>
 where
     F: Fn(u64, Option<u64>),
 {
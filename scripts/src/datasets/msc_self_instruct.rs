@@ -110,6 +110,7 @@ pub async fn download(client: &Client) -> Result<Dataset> {
                 expected_labels: vec!["multi_session".to_string(), "persona".to_string()],
                 primary_category: "conversational".to_string(),
                 difficulty: difficulty.to_string(),
+                source: "MemGPT/MSC-Self-Instruct".to_string(),
                 metadata: Metadata {
                     source: "MemGPT/MSC-Self-Instruct".to_string(),
                     split: None,
@@ -117,6 +118,7 @@ pub async fn download(client: &Client) -> Result<Dataset> {
                     turn_id: turn_idx,
                     speaker: Some(speaker.clone()),
                     session_id: None,
+                    provenance: None,
                 },
             };
 
@@ -102,6 +102,7 @@ pub async fn download(client: &Client) -> Result<Dataset> {
                     expected_labels: vec!["multi_session".to_string()],
                     primary_category: "conversational".to_string(),
                     difficulty: difficulty.to_string(),
+                    source: "nayohan/multi_session_chat".to_string(),
                     metadata: Metadata {
                         source: "nayohan/multi_session_chat".to_string(),
                         split: Some(split.to_string()),
@@ -109,6 +110,7 @@ pub async fn download(client: &Client) -> Result<Dataset> {
                         turn_id: turn_idx,
                         speaker: Some(speaker),
                         session_id: Some(session_id.to_string()),
+                        provenance: None,
                     },
                 };
 
@@ -198,6 +200,7 @@ pub async fn download(client: &Client) -> Result<Dataset> {
                             expected_labels: vec!["multi_session".to_string()],
                             primary_category: "conversational".to_string(),
                             difficulty: difficulty.to_string(),
+                            source: "nayohan/multi_session_chat".to_string(),
                             metadata: Metadata {
                                 source: "nayohan/multi_session_chat".to_string(),
                                 split: Some(split.to_string()),
@@ -205,6 +208,7 @@ pub async fn download(client: &Client) -> Result<Dataset> {
                                 turn_id: turn_idx,
                                 speaker: Some(speaker),
                                 session_id: Some(session_id.to_string()),
+                                provenance: None,
                             },
                         };
 
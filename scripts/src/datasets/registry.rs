@@ -0,0 +1,125 @@
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use super::DatasetSource;
+use super::builtin::{Builtin, BuiltinSource};
+use super::huggingface::{HuggingFaceConfig, HuggingFaceSource};
+
+/// Dataset sources file (e.g. `datasets/sources.json`)
+#[derive(Debug, Clone, Deserialize)]
+pub struct SourcesConfig {
+    pub sources: Vec<SourceConfig>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SourceConfig {
+    /// A dataset with a hand-written converter
+    Builtin {
+        id: Builtin,
+        filename: Option<String>,
+    },
+    /// A hub dataset converted by field mapping
+    #[serde(rename = "huggingface")]
+    HuggingFace(HuggingFaceConfig),
+}
+
+impl SourceConfig {
+    pub fn build(self) -> Box<dyn DatasetSource> {
+        match self {
+            Self::Builtin { id, filename } => {
+                let source = BuiltinSource::new(id);
+
+                match filename {
+                    Some(filename) => Box::new(source.filename(filename)),
+                    None => Box::new(source),
+                }
+            }
+            Self::HuggingFace(config) => Box::new(HuggingFaceSource::new(config)),
+        }
+    }
+}
+
+/// The dataset sources to fetch, in order
+pub struct SourceRegistry {
+    sources: Vec<Box<dyn DatasetSource>>,
+}
+
+impl SourceRegistry {
+    pub fn new() -> Self {
+        Self {
+            sources: Vec::new(),
+        }
+    }
+
+    /// All builtin datasets
+    pub fn builtin() -> Self {
+        Builtin::ALL
+            .into_iter()
+            .fold(Self::new(), |registry, builtin| {
+                registry.source(BuiltinSource::new(builtin))
+            })
+    }
+
+    pub fn from_config(config: SourcesConfig) -> Self {
+        Self {
+            sources: config
+                .sources
+                .into_iter()
+                .map(SourceConfig::build)
+                .collect(),
+        }
+    }
+
+    /// Load a sources file
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let config: SourcesConfig = serde_json::from_str(&text)
+            .with_context(|| format!("Failed to parse {}", path.display()))?;
+
+        Ok(Self::from_config(config))
+    }
+
+    pub fn source(mut self, source: impl DatasetSource + 'static) -> Self {
+        self.sources.push(Box::new(source));
+        self
+    }
+
+    /// Keep only the sources whose name or file stem matches one of `names`
+    pub fn only(mut self, names: &[String]) -> Self {
+        if names.is_empty() {
+            return self;
+        }
+
+        self.sources.retain(|source| {
+            let stem = source.filename().split('.').next().unwrap_or_default();
+            names.iter().any(|name| {
+                name.eq_ignore_ascii_case(source.name()) || name.eq_ignore_ascii_case(stem)
+            })
+        });
+
+        self
+    }
+
+    pub fn sources(&self) -> &[Box<dyn DatasetSource>] {
+        &self.sources
+    }
+
+    pub fn len(&self) -> usize {
+        self.sources.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sources.is_empty()
+    }
+}
+
+impl Default for SourceRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,26 @@
+use anyhow::Result;
+use futures::future::BoxFuture;
+use reqwest::Client;
+
+use crate::sample::Dataset;
+
+/// A dataset that can be downloaded from the HuggingFace hub and converted to
+/// the samples format
+pub trait DatasetSource: Send + Sync {
+    /// Display name (e.g. "DailyDialog")
+    fn name(&self) -> &str;
+
+    /// Output file name in the datasets directory
+    fn filename(&self) -> &str;
+
+    /// Hub repository the dataset comes from (e.g. "roskoN/dailydialog")
+    fn repo(&self) -> &str;
+
+    /// Hub revision (branch, tag or commit) to fetch
+    fn revision(&self) -> &str {
+        "main"
+    }
+
+    /// Download and convert the dataset
+    fn download<'a>(&'a self, client: &'a Client) -> BoxFuture<'a, Result<Dataset>>;
+}
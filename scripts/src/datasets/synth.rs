@@ -0,0 +1,292 @@
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result, bail};
+use serde::Deserialize;
+
+use crate::sample::{Dataset, Provenance, Sample, calculate_difficulty};
+
+fn default_window() -> usize {
+    3
+}
+
+fn default_min_words() -> usize {
+    3
+}
+
+fn default_stride() -> usize {
+    1
+}
+
+fn default_decision() -> String {
+    "reject".to_string()
+}
+
+fn default_category() -> String {
+    "conversational".to_string()
+}
+
+/// Annotation heuristic: turns that meet every condition get the rule's
+/// labels, and the first matching rule sets the category and decision. A rule
+/// without conditions matches every turn.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Rule {
+    pub name: String,
+    /// Phrases, any of which must appear in the turn (case-insensitive, whole
+    /// words)
+    #[serde(default)]
+    pub phrases: Vec<String>,
+    /// Source labels, any of which the turn must carry
+    #[serde(default)]
+    pub source_labels: Vec<String>,
+    pub min_words: Option<usize>,
+    pub max_words: Option<usize>,
+    #[serde(default)]
+    pub labels: Vec<String>,
+    pub category: Option<String>,
+    pub decision: Option<String>,
+}
+
+impl Rule {
+    fn matches(&self, words: &[String], source_labels: &[String]) -> bool {
+        (self.phrases.is_empty()
+            || self
+                .phrases
+                .iter()
+                .any(|phrase| contains_phrase(words, phrase)))
+            && (self.source_labels.is_empty()
+                || self
+                    .source_labels
+                    .iter()
+                    .any(|label| source_labels.contains(label)))
+            && self.min_words.is_none_or(|min| words.len() >= min)
+            && self.max_words.is_none_or(|max| words.len() <= max)
+    }
+}
+
+/// How conversations are sliced into samples and annotated
+#[derive(Debug, Clone, Deserialize)]
+pub struct SynthConfig {
+    /// Previous turns included as context
+    #[serde(default = "default_window")]
+    pub window: usize,
+    /// Turns with fewer words are skipped
+    #[serde(default = "default_min_words")]
+    pub min_words: usize,
+    /// Slice every `stride`-th turn of a conversation
+    #[serde(default = "default_stride")]
+    pub stride: usize,
+    /// Decision for turns no rule decides
+    #[serde(default = "default_decision")]
+    pub decision: String,
+    /// Category for turns no rule categorizes
+    #[serde(default = "default_category")]
+    pub category: String,
+    /// Keep the labels the source dataset assigned
+    #[serde(default)]
+    pub keep_source_labels: bool,
+    /// Label for turns left without any
+    pub fallback_label: Option<String>,
+    #[serde(default)]
+    pub rules: Vec<Rule>,
+}
+
+impl Default for SynthConfig {
+    fn default() -> Self {
+        Self {
+            window: default_window(),
+            min_words: default_min_words(),
+            stride: default_stride(),
+            decision: default_decision(),
+            category: default_category(),
+            keep_source_labels: false,
+            fallback_label: None,
+            rules: Vec::new(),
+        }
+    }
+}
+
+impl SynthConfig {
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let config: Self = serde_json::from_str(&text)
+            .with_context(|| format!("Failed to parse {}", path.display()))?;
+
+        config.check()?;
+        Ok(config)
+    }
+
+    /// Decisions must be ones loom can load
+    fn check(&self) -> Result<()> {
+        let decisions = std::iter::once(&self.decision)
+            .chain(self.rules.iter().filter_map(|rule| rule.decision.as_ref()));
+
+        for decision in decisions {
+            if decision != "accept" && decision != "reject" {
+                bail!("Invalid decision '{}', expected accept or reject", decision);
+            }
+        }
+
+        if self.stride == 0 {
+            bail!("stride must be at least 1");
+        }
+
+        Ok(())
+    }
+}
+
+/// Slices conversations into annotated samples
+pub struct Synthesizer {
+    config: SynthConfig,
+    config_path: Option<String>,
+}
+
+impl Synthesizer {
+    pub fn new(config: SynthConfig) -> Self {
+        Self {
+            config,
+            config_path: None,
+        }
+    }
+
+    /// Record the config file in each sample's provenance
+    pub fn config_path(mut self, path: impl Into<String>) -> Self {
+        self.config_path = Some(path.into());
+        self
+    }
+
+    /// One sample per kept turn of each conversation in `dataset`, with the
+    /// preceding turns as context
+    pub fn synthesize(&self, dataset: &Dataset) -> Vec<Sample> {
+        conversations(&dataset.samples)
+            .into_iter()
+            .flat_map(|turns| self.slice(&turns))
+            .collect()
+    }
+
+    fn slice(&self, turns: &[&Sample]) -> Vec<Sample> {
+        let config = &self.config;
+        let mut samples = Vec::new();
+
+        for (i, turn) in turns.iter().enumerate().step_by(config.stride) {
+            let words = words(&turn.text);
+
+            if words.len() < config.min_words {
+                continue;
+            }
+
+            let window = &turns[i.saturating_sub(config.window)..i];
+            let rules: Vec<&Rule> = config
+                .rules
+                .iter()
+                .filter(|rule| rule.matches(&words, &turn.expected_labels))
+                .collect();
+
+            let mut labels: BTreeSet<String> = rules
+                .iter()
+                .flat_map(|rule| rule.labels.iter().cloned())
+                .collect();
+
+            if config.keep_source_labels {
+                labels.extend(turn.expected_labels.iter().cloned());
+            }
+
+            if labels.is_empty()
+                && let Some(fallback) = &config.fallback_label
+            {
+                labels.insert(fallback.clone());
+            }
+
+            let decision = rules
+                .iter()
+                .find_map(|rule| rule.decision.clone())
+                .unwrap_or_else(|| config.decision.clone());
+            let category = rules
+                .iter()
+                .find_map(|rule| rule.category.clone())
+                .unwrap_or_else(|| config.category.clone());
+
+            let mut metadata = turn.metadata.clone();
+            metadata.provenance = Some(Provenance {
+                sample_id: turn.id.clone(),
+                context_turns: window.iter().map(|t| t.metadata.turn_id).collect(),
+                rules: rules.iter().map(|rule| rule.name.clone()).collect(),
+                config: self.config_path.clone(),
+            });
+
+            samples.push(Sample {
+                id: format!("synth-{}", turn.id),
+                text: turn.text.clone(),
+                context: context(window),
+                expected_decision: decision,
+                expected_labels: labels.into_iter().collect(),
+                primary_category: category,
+                difficulty: calculate_difficulty(&turn.text).to_string(),
+                source: turn.source.clone(),
+                metadata,
+            });
+        }
+
+        samples
+    }
+}
+
+/// Turns grouped by conversation (and session), in first-seen order, each
+/// sorted by turn id
+fn conversations(samples: &[Sample]) -> Vec<Vec<&Sample>> {
+    let mut index: HashMap<(&str, Option<&str>, usize, Option<&str>), usize> = HashMap::new();
+    let mut conversations: Vec<Vec<&Sample>> = Vec::new();
+
+    for sample in samples {
+        let meta = &sample.metadata;
+        let key = (
+            sample.source.as_str(),
+            meta.split.as_deref(),
+            meta.conversation_id,
+            meta.session_id.as_deref(),
+        );
+        let idx = *index.entry(key).or_insert_with(|| {
+            conversations.push(Vec::new());
+            conversations.len() - 1
+        });
+
+        conversations[idx].push(sample);
+    }
+
+    for turns in &mut conversations {
+        turns.sort_by_key(|turn| turn.metadata.turn_id);
+    }
+
+    conversations
+}
+
+fn context(window: &[&Sample]) -> Option<String> {
+    if window.is_empty() {
+        return None;
+    }
+
+    let lines: Vec<String> = window
+        .iter()
+        .map(|turn| match &turn.metadata.speaker {
+            Some(speaker) => format!("{}: {}", speaker, turn.text),
+            None => turn.text.clone(),
+        })
+        .collect();
+
+    Some(lines.join("\n"))
+}
+
+fn words(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric() && c != '\'')
+        .filter(|w| !w.is_empty())
+        .map(|w| w.to_lowercase())
+        .collect()
+}
+
+fn contains_phrase(words: &[String], phrase: &str) -> bool {
+    let phrase = self::words(phrase);
+
+    !phrase.is_empty() && words.windows(phrase.len()).any(|window| window == phrase)
+}
@@ -1,6 +1,6 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Sample {
     pub id: String,
     pub text: String,
@@ -10,10 +10,12 @@ pub struct Sample {
     pub expected_labels: Vec<String>,
     pub primary_category: String,
     pub difficulty: String,
+    /// Dataset the sample was derived from, used for per-source eval breakdowns
+    pub source: String,
     pub metadata: Metadata,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Metadata {
     pub source: String,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -24,14 +26,36 @@ pub struct Metadata {
     pub speaker: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub session_id: Option<String>,
+    /// How a synthesized sample was derived
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub provenance: Option<Provenance>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Provenance {
+    /// Sample the synthesized one was sliced at
+    pub sample_id: String,
+    /// Turn ids of the context window, oldest first
+    pub context_turns: Vec<usize>,
+    /// Heuristic rules that matched
+    pub rules: Vec<String>,
+    /// Synthesis config file, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub config: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Dataset {
     pub version: String,
     pub created: String,
     pub source: String,
     pub description: String,
+    /// License of the source dataset, from its hub card
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub license: Option<String>,
+    /// Hub commit the source dataset was fetched at
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub revision: Option<String>,
     pub samples: Vec<Sample>,
 }
 
@@ -42,6 +66,8 @@ impl Dataset {
             created: chrono::Utc::now().format("%Y-%m-%d").to_string(),
             source: source.into(),
             description: description.into(),
+            license: None,
+            revision: None,
             samples: Vec::new(),
         }
     }